@@ -5,16 +5,16 @@
 
 use std::path::{Path, PathBuf};
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use log::{debug, warn};
-use openssl::pkey::{PKey, Private};
 use tokio::sync::oneshot;
 use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
-use super::KeyStoreConfig;
+use super::{KeyPassphraseSource, KeyStoreConfig};
+use crate::store::stats::KeyStoreStats;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct LocalKeyStoreConfig {
@@ -22,6 +22,7 @@ pub struct LocalKeyStoreConfig {
     position: Option<YamlDocPosition>,
     dir_path: PathBuf,
     watch: bool,
+    passphrase: Option<KeyPassphraseSource>,
 }
 
 impl LocalKeyStoreConfig {
@@ -31,6 +32,7 @@ impl LocalKeyStoreConfig {
             position,
             dir_path: PathBuf::new(),
             watch: false,
+            passphrase: None,
         }
     }
 
@@ -72,6 +74,13 @@ impl LocalKeyStoreConfig {
                 self.watch = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "passphrase" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let source = KeyPassphraseSource::parse_yaml(v, lookup_dir)
+                    .context(format!("invalid key passphrase source value for key {k}"))?;
+                self.passphrase = Some(source);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -87,6 +96,17 @@ impl KeyStoreConfig for LocalKeyStoreConfig {
         const BATCH_SIZE: usize = 128;
 
         debug!("loading keys from dir {}", self.dir_path.display());
+        let passphrase = match &self.passphrase {
+            Some(source) => Some(
+                source
+                    .load()
+                    .await
+                    .context("failed to load key passphrase")?,
+            ),
+            None => None,
+        };
+        let stats = crate::store::stats::get_or_insert(&self.name);
+
         let mut dir = tokio::fs::read_dir(&self.dir_path)
             .await
             .map_err(|e| anyhow!("failed to open {}: {e}", self.dir_path.display()))?;
@@ -117,13 +137,13 @@ impl KeyStoreConfig for LocalKeyStoreConfig {
             };
 
             if filetype.is_file() {
-                load_add_key(&path).await;
+                load_add_key(&path, passphrase.as_deref(), &stats).await;
             } else if filetype.is_symlink() {
                 // traverse the symlink to get the real file type
                 match tokio::fs::metadata(&path).await {
                     Ok(meta) => {
                         if meta.is_file() {
-                            load_add_key(&path).await;
+                            load_add_key(&path, passphrase.as_deref(), &stats).await;
                         } else {
                             debug!(" - skip non-regular file {}", path.display());
                         }
@@ -165,6 +185,8 @@ impl KeyStoreConfig for LocalKeyStoreConfig {
         let mut event_stream = inotify.into_event_stream(buffer)?;
 
         let dir_path = self.dir_path.to_path_buf();
+        let passphrase_source = self.passphrase.clone();
+        let stats = crate::store::stats::get_or_insert(&self.name);
         let async_watch = async move {
             loop {
                 match poll_fn(|cx| event_stream.poll_next_unpin(cx)).await {
@@ -172,7 +194,18 @@ impl KeyStoreConfig for LocalKeyStoreConfig {
                         if let Some(p) = v.name {
                             let path = dir_path.join(p);
                             debug!("got close_write event on {}", path.display());
-                            load_add_key(&path).await;
+                            // always reload the passphrase as it may have been rotated
+                            let passphrase = match &passphrase_source {
+                                Some(source) => match source.load().await {
+                                    Ok(p) => Some(p),
+                                    Err(e) => {
+                                        warn!("failed to load key passphrase: {e:?}");
+                                        continue;
+                                    }
+                                },
+                                None => None,
+                            };
+                            load_add_key(&path, passphrase.as_deref(), &stats).await;
                         }
                     }
                     Some(Err(e)) => {
@@ -202,36 +235,39 @@ impl KeyStoreConfig for LocalKeyStoreConfig {
     }
 }
 
-async fn load_add_key(path: &Path) {
-    match load_key(path).await {
-        Ok(Some(key)) => match crate::store::add_global(key) {
-            Ok(_) => {
-                debug!(" - loaded key from file {}", path.display());
-            }
-            Err(e) => {
-                warn!(" - failed to add key from file {}: {e}", path.display());
-            }
-        },
-        Ok(None) => {
-            debug!(" - no key found in file {}", path.display());
+async fn load_add_key(path: &Path, passphrase: Option<&[u8]>, stats: &KeyStoreStats) {
+    let is_key_file = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("key"))
+        .unwrap_or(false);
+    if !is_key_file {
+        debug!(" - no key found in file {}", path.display());
+        return;
+    }
+
+    let content = match tokio::fs::read(path).await {
+        Ok(content) => content,
+        Err(e) => {
+            warn!(" - failed to read content of file {}: {e}", path.display());
+            return;
         }
+    };
+    let key = match crate::store::parse_private_key(&content, passphrase) {
+        Ok(key) => key,
         Err(e) => {
+            stats.add_load_error(&e);
             warn!(" - failed to load key from file {}: {e}", path.display());
+            return;
         }
-    }
-}
+    };
 
-async fn load_key<T: AsRef<Path>>(path: T) -> anyhow::Result<Option<PKey<Private>>> {
-    let path = path.as_ref();
-    if let Some(ext) = path.extension() {
-        if ext.eq_ignore_ascii_case("key") {
-            let content = tokio::fs::read_to_string(path)
-                .await
-                .map_err(|e| anyhow!("failed to read content of file {}: {e}", path.display()))?;
-            let key = PKey::private_key_from_pem(content.as_bytes())
-                .map_err(|e| anyhow!("invalid private key pem file {}: {e}", path.display()))?;
-            return Ok(Some(key));
+    match crate::store::add_global(key) {
+        Ok(_) => {
+            stats.add_loaded();
+            debug!(" - loaded key from file {}", path.display());
+        }
+        Err(e) => {
+            warn!(" - failed to add key from file {}: {e}", path.display());
         }
     }
-    Ok(None)
 }
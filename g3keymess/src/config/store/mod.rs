@@ -14,6 +14,10 @@ use g3_types::metrics::NodeName;
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 mod local;
+mod passphrase;
+use passphrase::KeyPassphraseSource;
+#[cfg(feature = "pkcs11")]
+pub(crate) mod pkcs11;
 mod redis;
 
 mod registry;
@@ -36,6 +40,8 @@ pub trait KeyStoreConfig {
 pub enum AnyKeyStoreConfig {
     Local(local::LocalKeyStoreConfig),
     Redis(redis::RedisKeyStoreConfig),
+    #[cfg(feature = "pkcs11")]
+    Pkcs11(pkcs11::Pkcs11KeyStoreConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -68,6 +74,11 @@ fn load_store(
             let config = redis::RedisKeyStoreConfig::parse(map, position)?;
             Ok(AnyKeyStoreConfig::Redis(config))
         }
+        #[cfg(feature = "pkcs11")]
+        "pkcs11" => {
+            let config = pkcs11::Pkcs11KeyStoreConfig::parse(map, position)?;
+            Ok(AnyKeyStoreConfig::Pkcs11(config))
+        }
         _ => Err(anyhow!("unsupported key store type {store_type}")),
     }
 }
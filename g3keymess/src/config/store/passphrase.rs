@@ -0,0 +1,191 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+const SYSTEMD_CREDENTIALS_DIRECTORY_ENV: &str = "CREDENTIALS_DIRECTORY";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum KeyPassphraseSource {
+    File(PathBuf),
+    Env(String),
+    SystemdCredential(String),
+}
+
+impl KeyPassphraseSource {
+    pub(super) fn parse_yaml(v: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for 'key passphrase source' should be 'map'"
+            ));
+        };
+        if map.len() != 1 {
+            return Err(anyhow!("only one passphrase source should be set"));
+        }
+
+        let mut source = None;
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "file" | "path" => {
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                source = Some(KeyPassphraseSource::File(path));
+                Ok(())
+            }
+            "env" | "env_var" => {
+                let name = g3_yaml::value::as_string(v)?;
+                source = Some(KeyPassphraseSource::Env(name));
+                Ok(())
+            }
+            "credential" | "systemd_credential" => {
+                let name = g3_yaml::value::as_string(v)?;
+                if name.is_empty() || name.contains('/') {
+                    return Err(anyhow!("invalid systemd credential name {name}"));
+                }
+                source = Some(KeyPassphraseSource::SystemdCredential(name));
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        source.ok_or_else(|| anyhow!("no passphrase source set"))
+    }
+
+    pub(crate) async fn load(&self) -> anyhow::Result<Vec<u8>> {
+        let mut passphrase = match self {
+            KeyPassphraseSource::File(path) => tokio::fs::read(path)
+                .await
+                .map_err(|e| anyhow!("failed to read passphrase file {}: {e}", path.display()))?,
+            KeyPassphraseSource::Env(name) => std::env::var_os(name)
+                .ok_or_else(|| anyhow!("environment variable {name} is not set"))?
+                .into_encoded_bytes(),
+            KeyPassphraseSource::SystemdCredential(name) => {
+                let dir = std::env::var_os(SYSTEMD_CREDENTIALS_DIRECTORY_ENV).ok_or_else(|| {
+                    anyhow!("no systemd credentials directory found, is LoadCredential set?")
+                })?;
+                let path = Path::new(&dir).join(name);
+                tokio::fs::read(&path).await.map_err(|e| {
+                    anyhow!("failed to read systemd credential {}: {e}", path.display())
+                })?
+            }
+        };
+
+        // strip the trailing newline added by text editors or echo
+        while matches!(passphrase.last(), Some(b'\n' | b'\r')) {
+            passphrase.pop();
+        }
+        if passphrase.is_empty() {
+            return Err(anyhow!("empty passphrase"));
+        }
+        Ok(passphrase)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_file() {
+        let dir = std::env::temp_dir();
+        let file_name = format!("g3keymess-passphrase-test-{}", std::process::id());
+        let path = dir.join(&file_name);
+        std::fs::write(&path, b"secret\n").unwrap();
+
+        let v = Yaml::Hash(
+            [(
+                Yaml::String("file".to_string()),
+                Yaml::String(file_name.clone()),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let source = KeyPassphraseSource::parse_yaml(&v, &dir).unwrap();
+        assert_eq!(
+            source,
+            KeyPassphraseSource::File(path.canonicalize().unwrap())
+        );
+
+        let v = Yaml::Hash(
+            [(
+                Yaml::String("path".to_string()),
+                Yaml::String(path.display().to_string()),
+            )]
+            .into_iter()
+            .collect(),
+        );
+        let source = KeyPassphraseSource::parse_yaml(&v, Path::new("/")).unwrap();
+        assert_eq!(
+            source,
+            KeyPassphraseSource::File(path.canonicalize().unwrap())
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn parse_env() {
+        let v = yaml_doc!("env: KEY_PASSPHRASE");
+        let source = KeyPassphraseSource::parse_yaml(&v, Path::new("/")).unwrap();
+        assert_eq!(
+            source,
+            KeyPassphraseSource::Env("KEY_PASSPHRASE".to_string())
+        );
+
+        let v = yaml_doc!("env_var: KEY_PASSPHRASE");
+        let source = KeyPassphraseSource::parse_yaml(&v, Path::new("/")).unwrap();
+        assert_eq!(
+            source,
+            KeyPassphraseSource::Env("KEY_PASSPHRASE".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_systemd_credential() {
+        let v = yaml_doc!("credential: key-passphrase");
+        let source = KeyPassphraseSource::parse_yaml(&v, Path::new("/")).unwrap();
+        assert_eq!(
+            source,
+            KeyPassphraseSource::SystemdCredential("key-passphrase".to_string())
+        );
+
+        let v = yaml_doc!("systemd_credential: key-passphrase");
+        let source = KeyPassphraseSource::parse_yaml(&v, Path::new("/")).unwrap();
+        assert_eq!(
+            source,
+            KeyPassphraseSource::SystemdCredential("key-passphrase".to_string())
+        );
+
+        let v = yaml_doc!("credential: ../key-passphrase");
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+        let v = yaml_doc!(r#"credential: """#);
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let v = yaml_doc!("secret");
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+
+        let v = yaml_doc!("{}");
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+
+        let v = yaml_doc!(
+            r#"
+                env: KEY_PASSPHRASE
+                credential: key-passphrase
+            "#
+        );
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+
+        let v = yaml_doc!("value: secret");
+        assert!(KeyPassphraseSource::parse_yaml(&v, Path::new("/")).is_err());
+    }
+}
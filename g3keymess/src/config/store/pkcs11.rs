@@ -0,0 +1,146 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::PathBuf;
+
+use anyhow::{Context, anyhow};
+use log::debug;
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use super::{KeyPassphraseSource, KeyStoreConfig};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pkcs11KeyStoreConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) module_path: PathBuf,
+    pub(crate) slot: Option<u64>,
+    pub(crate) token_label: Option<String>,
+    user_pin: Option<KeyPassphraseSource>,
+    session_pool_size: usize,
+}
+
+impl Pkcs11KeyStoreConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        Pkcs11KeyStoreConfig {
+            name: NodeName::default(),
+            position,
+            module_path: PathBuf::new(),
+            slot: None,
+            token_label: None,
+            user_pin: None,
+            session_pool_size: 0,
+        }
+    }
+
+    pub(super) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut server = Pkcs11KeyStoreConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| server.set(k, v))?;
+
+        server.check()?;
+        Ok(server)
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.module_path.as_os_str().is_empty() {
+            return Err(anyhow!("module path is not set"));
+        }
+        if self.slot.is_none() && self.token_label.is_none() {
+            return Err(anyhow!("either slot or token label should be set"));
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_STORE_TYPE => Ok(()),
+            "name" => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "module" | "module_path" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                self.module_path = g3_yaml::value::as_file_path(v, lookup_dir, false)?;
+                Ok(())
+            }
+            "slot" | "slot_id" => {
+                let slot = g3_yaml::value::as_u64(v)?;
+                self.slot = Some(slot);
+                Ok(())
+            }
+            "token" | "token_label" => {
+                let label = g3_yaml::value::as_string(v)?;
+                self.token_label = Some(label);
+                Ok(())
+            }
+            "pin" | "user_pin" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let source = KeyPassphraseSource::parse_yaml(v, lookup_dir)
+                    .context(format!("invalid key passphrase source value for key {k}"))?;
+                self.user_pin = Some(source);
+                Ok(())
+            }
+            "session_pool_size" => {
+                self.session_pool_size = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    pub(crate) fn session_pool_size(&self) -> usize {
+        if self.session_pool_size > 0 {
+            self.session_pool_size
+        } else {
+            g3_daemon::runtime::worker::worker_count().max(1)
+        }
+    }
+}
+
+impl KeyStoreConfig for Pkcs11KeyStoreConfig {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    async fn load_keys(&self) -> anyhow::Result<()> {
+        debug!(
+            "loading keys from pkcs#11 module {}",
+            self.module_path.display()
+        );
+        let user_pin = match &self.user_pin {
+            Some(source) => {
+                let pin = source
+                    .load()
+                    .await
+                    .context("failed to load pkcs#11 user pin")?;
+                let pin = String::from_utf8(pin)
+                    .map_err(|_| anyhow!("the pkcs#11 user pin is not valid utf-8 string"))?;
+                Some(pin)
+            }
+            None => None,
+        };
+        let config = self.clone();
+        let keys =
+            tokio::task::spawn_blocking(move || crate::store::pkcs11::load_keys(&config, user_pin))
+                .await
+                .map_err(|e| anyhow!("failed to spawn pkcs#11 key load task: {e}"))??;
+
+        for key in keys {
+            crate::store::add_global_pkcs11(key).context("failed to add pkcs#11 key")?;
+        }
+        Ok(())
+    }
+}
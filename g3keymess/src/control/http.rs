@@ -0,0 +1,117 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use async_trait::async_trait;
+use log::debug;
+use serde_json::{Map, Value};
+
+use g3_daemon::control::{HttpControlError, HttpControlHandler, HttpControlResult};
+use g3_types::metrics::NodeName;
+
+const KIND_SERVER: &str = "servers";
+const KIND_KEY: &str = "keys";
+
+/// all keys published through the http api will be added to the global key store
+const GLOBAL_KEY_STORE: &str = "global";
+
+pub struct HttpController {}
+
+impl HttpController {
+    pub fn start() -> anyhow::Result<()> {
+        g3_daemon::control::HttpController::start(HttpControlImpl)
+    }
+
+    pub(super) async fn abort() {
+        debug!("aborting http controller");
+        g3_daemon::control::HttpController::abort().await;
+    }
+}
+
+struct HttpControlImpl;
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    fn version(&self) -> &'static str {
+        crate::build::VERSION
+    }
+
+    fn list(&self, kind: &str) -> HttpControlResult<Vec<String>> {
+        let mut names: Vec<String> = match kind {
+            KIND_SERVER => crate::serve::get_names()
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect(),
+            KIND_KEY => crate::store::get_all_ski()
+                .into_iter()
+                .map(hex::encode)
+                .collect(),
+            _ => return Err(HttpControlError::unknown_kind(kind)),
+        };
+        names.sort();
+        Ok(names)
+    }
+
+    fn status(&self, kind: &str, name: &str) -> HttpControlResult<Map<String, Value>> {
+        let mut map = Map::new();
+        map.insert("name".to_string(), Value::from(name));
+        match kind {
+            KIND_SERVER => {
+                let node_name = unsafe { NodeName::new_unchecked(name) };
+                let server = crate::serve::get_server(&node_name)
+                    .map_err(|_| HttpControlError::not_found(kind, name))?;
+                let stats = server.get_server_stats();
+                map.insert("online".to_string(), Value::from(stats.is_online()));
+                map.insert(
+                    "alive_task_count".to_string(),
+                    Value::from(stats.get_alive_count()),
+                );
+                map.insert(
+                    "total_task_count".to_string(),
+                    Value::from(stats.get_task_total()),
+                );
+                map.insert(
+                    "listen_addr".to_string(),
+                    Value::from(server.listen_addr().to_string()),
+                );
+            }
+            KIND_KEY => {
+                let ski = hex::decode(name).map_err(|e| {
+                    HttpControlError::BadRequest(format!("invalid hex encoded ski {name}: {e}"))
+                })?;
+                if crate::store::get_by_ski(&ski).is_none() {
+                    return Err(HttpControlError::not_found(kind, name));
+                }
+            }
+            _ => return Err(HttpControlError::unknown_kind(kind)),
+        }
+        Ok(map)
+    }
+
+    async fn reload(&self, kind: &str, _name: &str) -> HttpControlResult<()> {
+        match kind {
+            KIND_SERVER | KIND_KEY => Err(HttpControlError::BadRequest(format!(
+                "reload is not supported for {kind}"
+            ))),
+            _ => Err(HttpControlError::unknown_kind(kind)),
+        }
+    }
+
+    async fn publish(&self, kind: &str, name: &str, data: String) -> HttpControlResult<()> {
+        match kind {
+            KIND_KEY => {
+                if name != GLOBAL_KEY_STORE {
+                    return Err(HttpControlError::not_found("key store", name));
+                }
+                super::bridge::add_key(&data)
+                    .await
+                    .map_err(HttpControlError::Failed)
+            }
+            KIND_SERVER => Err(HttpControlError::BadRequest(format!(
+                "publish is not supported for {kind}"
+            ))),
+            _ => Err(HttpControlError::unknown_kind(kind)),
+        }
+    }
+}
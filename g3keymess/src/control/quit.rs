@@ -5,6 +5,7 @@
 
 use g3_daemon::control::quit::QuitAction;
 
+use super::HttpController;
 use super::local::{DaemonController, UniqueController};
 
 #[derive(Default)]
@@ -13,6 +14,7 @@ pub struct QuitActor {}
 impl QuitAction for QuitActor {
     async fn do_release_controller(&self) {
         DaemonController::abort().await;
+        HttpController::abort().await;
     }
 
     fn do_resume_controller(&self) -> anyhow::Result<()> {
@@ -20,6 +22,7 @@ impl QuitAction for QuitActor {
         tokio::spawn(async move {
             daemon_ctl.await;
         });
+        HttpController::start()?;
         Ok(())
     }
 
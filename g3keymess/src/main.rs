@@ -16,6 +16,9 @@ fn main() -> anyhow::Result<()> {
         openssl_probe::init_openssl_env_vars();
     }
     openssl::init();
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .unwrap();
 
     let Some(proc_args) =
         g3keymess::opts::parse_clap().context("failed to parse command line options")?
@@ -117,6 +120,7 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
                 daemon_ctl.await;
             });
         }
+        g3keymess::control::HttpController::start().context("failed to start http controller")?;
         g3keymess::control::QuitActor::tokio_spawn_run();
 
         g3keymess::signal::register().context("failed to setup signal handler")?;
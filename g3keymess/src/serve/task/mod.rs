@@ -140,6 +140,23 @@ impl WrappedKeylessRequest {
         }
     }
 
+    #[cfg(feature = "pkcs11")]
+    pub(crate) async fn process_by_pkcs11(
+        &self,
+        key: &crate::store::pkcs11::Pkcs11Key,
+    ) -> KeylessResponse {
+        match key.process(&self.inner).await {
+            Ok(d) => {
+                self.stats.add_passed();
+                KeylessResponse::Data(d)
+            }
+            Err(e) => {
+                self.stats.add_by_error_code(e.error_code());
+                KeylessResponse::Error(e)
+            }
+        }
+    }
+
     pub(crate) fn build_response(&self, rsp: KeylessResponse) -> WrappedKeylessResponse {
         WrappedKeylessResponse::new(rsp, self.ctx.clone())
     }
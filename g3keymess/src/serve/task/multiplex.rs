@@ -3,6 +3,9 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+#[cfg(feature = "pkcs11")]
+use std::sync::Arc;
+
 use openssl::pkey::{PKey, Private};
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::sync::{broadcast, mpsc};
@@ -14,6 +17,9 @@ use crate::backend::DispatchedKeylessRequest;
 use crate::log::request::RequestErrorLogContext;
 use crate::protocol::KeylessResponse;
 use crate::serve::{ServerReloadCommand, ServerTaskError};
+use crate::store::KeylessKey;
+#[cfg(feature = "pkcs11")]
+use crate::store::pkcs11::Pkcs11Key;
 
 impl KeylessTask {
     pub(crate) async fn into_multiplex_running<R, W>(mut self, reader: R, mut writer: W)
@@ -165,6 +171,15 @@ impl KeylessTask {
             }
         };
 
+        let key = match key {
+            KeylessKey::Openssl(key) => key,
+            #[cfg(feature = "pkcs11")]
+            KeylessKey::Pkcs11(key) => {
+                self.async_process_by_pkcs11(req, key, msg_sender).await;
+                return Ok(());
+            }
+        };
+
         if self.allow_dispatch {
             self.async_process_by_dispatch(req, key, msg_sender).await;
             return Ok(());
@@ -207,6 +222,26 @@ impl KeylessTask {
         }
     }
 
+    #[cfg(feature = "pkcs11")]
+    async fn async_process_by_pkcs11(
+        &self,
+        mut req: WrappedKeylessRequest,
+        key: Arc<Pkcs11Key>,
+        msg_sender: &mpsc::Sender<WrappedKeylessResponse>,
+    ) {
+        if let Some(sem) = self.ctx.concurrency_limit.clone() {
+            if let Ok(permit) = sem.acquire_owned().await {
+                req.server_sem_permit = Some(permit);
+            }
+        }
+
+        let msg_sender = msg_sender.clone();
+        tokio::spawn(async move {
+            let rsp = req.process_by_pkcs11(&key).await;
+            let _ = msg_sender.send(req.build_response(rsp)).await;
+        });
+    }
+
     #[cfg(feature = "openssl-async-job")]
     async fn async_process_by_openssl(
         &self,
@@ -12,6 +12,7 @@ use super::KeylessTask;
 use crate::log::request::RequestErrorLogContext;
 use crate::protocol::KeylessResponse;
 use crate::serve::{RequestProcessContext, ServerReloadCommand, ServerTaskError};
+use crate::store::KeylessKey;
 
 impl KeylessTask {
     pub(crate) async fn into_simplex_running<R, W>(mut self, reader: R, mut writer: W)
@@ -109,7 +110,11 @@ impl KeylessTask {
             None
         };
 
-        let rsp = req.process_by_openssl(&key);
+        let rsp = match key {
+            KeylessKey::Openssl(key) => req.process_by_openssl(&key),
+            #[cfg(feature = "pkcs11")]
+            KeylessKey::Pkcs11(key) => req.process_by_pkcs11(&key).await,
+        };
 
         drop(server_sem);
 
@@ -4,3 +4,4 @@
  */
 
 pub(super) mod server;
+pub(super) mod store;
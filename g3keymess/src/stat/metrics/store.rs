@@ -0,0 +1,76 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use g3_daemon::metrics::TAG_KEY_STAT_ID;
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::stats::GlobalStatsMap;
+
+use crate::store::stats::{KeyStoreSnapshot, KeyStoreStats};
+
+const TAG_KEY_STORE: &str = "store";
+const TAG_KEY_REASON: &str = "reason";
+
+const METRIC_NAME_STORE_KEY_LOADED: &str = "store.key.loaded";
+const METRIC_NAME_STORE_KEY_LOAD_FAILED: &str = "store.key.load_failed";
+
+const FAIL_REASON_NO_PASSPHRASE: &str = "no_passphrase";
+const FAIL_REASON_BAD_PASSPHRASE: &str = "bad_passphrase";
+const FAIL_REASON_INVALID_KEY: &str = "invalid_key";
+
+type StoreStatsValue = (Arc<KeyStoreStats>, KeyStoreSnapshot);
+
+static STORE_STATS_MAP: Mutex<GlobalStatsMap<StoreStatsValue>> = Mutex::new(GlobalStatsMap::new());
+
+pub(in crate::stat) fn sync_stats() {
+    let mut store_stats_map = STORE_STATS_MAP.lock().unwrap();
+    crate::store::stats::foreach(|stats| {
+        store_stats_map.get_or_insert_with(stats.stat_id(), || {
+            (stats.clone(), KeyStoreSnapshot::default())
+        });
+    });
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut store_stats_map = STORE_STATS_MAP.lock().unwrap();
+    store_stats_map.retain(|(stats, snap)| {
+        emit_store_stats(client, stats, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(stats) > 1
+    });
+}
+
+fn emit_store_stats(client: &mut StatsdClient, stats: &KeyStoreStats, snap: &mut KeyStoreSnapshot) {
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_tag(TAG_KEY_STORE, stats.name());
+    common_tags.add_tag(TAG_KEY_STAT_ID, stats.stat_id().as_u64().to_string());
+
+    let new_snap = stats.snapshot();
+
+    let new_value = new_snap.loaded;
+    let diff_value = new_value.wrapping_sub(snap.loaded);
+    client
+        .count_with_tags(METRIC_NAME_STORE_KEY_LOADED, diff_value, &common_tags)
+        .send();
+    snap.loaded = new_value;
+
+    macro_rules! emit_failed_stats_u64 {
+        ($id:ident, $reason:expr) => {
+            let new_value = new_snap.$id;
+            if new_value != 0 || snap.$id != 0 {
+                let diff_value = new_value.wrapping_sub(snap.$id);
+                client
+                    .count_with_tags(METRIC_NAME_STORE_KEY_LOAD_FAILED, diff_value, &common_tags)
+                    .with_tag(TAG_KEY_REASON, $reason)
+                    .send();
+                snap.$id = new_value;
+            }
+        };
+    }
+    emit_failed_stats_u64!(no_passphrase, FAIL_REASON_NO_PASSPHRASE);
+    emit_failed_stats_u64!(bad_passphrase, FAIL_REASON_BAD_PASSPHRASE);
+    emit_failed_stats_u64!(invalid_key, FAIL_REASON_INVALID_KEY);
+}
@@ -37,9 +37,11 @@ fn spawn_main_thread(config: &StatsdClientConfig) -> anyhow::Result<JoinHandle<(
                 let instant_start = Instant::now();
 
                 metrics::server::sync_stats();
+                metrics::store::sync_stats();
                 g3_daemon::log::metrics::sync_stats();
 
                 metrics::server::emit_stats(&mut client);
+                metrics::store::emit_stats(&mut client);
                 g3_daemon::runtime::metrics::emit_stats(&mut client);
                 g3_daemon::log::metrics::emit_stats(&mut client);
 
@@ -4,6 +4,8 @@
  */
 
 use std::collections::HashMap;
+#[cfg(feature = "pkcs11")]
+use std::sync::Arc;
 use std::sync::RwLock;
 
 use anyhow::anyhow;
@@ -17,7 +19,32 @@ pub use ops::{load_all, reload_all};
 
 mod registry;
 
-static GLOBAL_SKI_MAP: RwLock<HashMap<Vec<u8>, PKey<Private>, FixedState>> =
+mod pem;
+pub(crate) use pem::{KeyLoadError, parse_private_key};
+
+pub(crate) mod stats;
+
+#[cfg(feature = "pkcs11")]
+pub(crate) mod pkcs11;
+
+#[derive(Clone)]
+pub(crate) enum KeylessKey {
+    Openssl(PKey<Private>),
+    #[cfg(feature = "pkcs11")]
+    Pkcs11(Arc<pkcs11::Pkcs11Key>),
+}
+
+impl KeylessKey {
+    pub(crate) fn size(&self) -> usize {
+        match self {
+            KeylessKey::Openssl(k) => k.size(),
+            #[cfg(feature = "pkcs11")]
+            KeylessKey::Pkcs11(k) => k.size(),
+        }
+    }
+}
+
+static GLOBAL_SKI_MAP: RwLock<HashMap<Vec<u8>, KeylessKey, FixedState>> =
     RwLock::new(HashMap::with_hasher(FixedState::with_seed(0)));
 
 pub(crate) fn add_global(key: PKey<Private>) -> anyhow::Result<()> {
@@ -25,7 +52,20 @@ pub(crate) fn add_global(key: PKey<Private>) -> anyhow::Result<()> {
     let mut map = GLOBAL_SKI_MAP
         .write()
         .map_err(|e| anyhow!("failed to get global ski_map: {e}"))?;
-    map.insert(ski.to_vec(), key);
+    map.insert(ski.to_vec(), KeylessKey::Openssl(key));
+    Ok(())
+}
+
+#[cfg(feature = "pkcs11")]
+pub(crate) fn add_global_pkcs11(key: pkcs11::Pkcs11Key) -> anyhow::Result<()> {
+    let ski = key
+        .public_key()
+        .ski()
+        .map_err(|e| anyhow!("failed to get SKI: {e}"))?;
+    let mut map = GLOBAL_SKI_MAP
+        .write()
+        .map_err(|e| anyhow!("failed to get global ski_map: {e}"))?;
+    map.insert(ski.to_vec(), KeylessKey::Pkcs11(Arc::new(key)));
     Ok(())
 }
 
@@ -34,7 +74,7 @@ pub(crate) fn get_all_ski() -> Vec<Vec<u8>> {
     map.keys().map(|v| v.to_vec()).collect()
 }
 
-pub(crate) fn get_by_ski(ski: &[u8]) -> Option<PKey<Private>> {
+pub(crate) fn get_by_ski(ski: &[u8]) -> Option<KeylessKey> {
     let map = GLOBAL_SKI_MAP.read().unwrap();
     map.get(ski).cloned()
 }
@@ -63,6 +63,7 @@ pub async fn reload_all() -> anyhow::Result<()> {
             registry::del_subscriber(name);
         }
     }
+    super::stats::retain(&new_names);
 
     Ok(())
 }
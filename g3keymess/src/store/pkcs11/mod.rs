@@ -0,0 +1,342 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::Session;
+use cryptoki::slot::Slot;
+use cryptoki::types::AuthPin;
+use foldhash::fast::FixedState;
+use log::{debug, warn};
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey, EcPoint};
+use openssl::nid::Nid;
+use openssl::pkey::{Id, PKey, Public};
+use openssl::rsa::Rsa;
+
+use crate::config::store::pkcs11::Pkcs11KeyStoreConfig;
+
+mod process;
+
+mod session;
+use session::SessionPool;
+
+static PKCS11_MODULE_REGISTRY: Mutex<HashMap<PathBuf, Pkcs11, FixedState>> =
+    Mutex::new(HashMap::with_hasher(FixedState::with_seed(0)));
+
+pub(crate) struct Pkcs11Key {
+    pool: Arc<SessionPool>,
+    handle: ObjectHandle,
+    public_key: PKey<Public>,
+}
+
+impl Pkcs11Key {
+    pub(crate) fn public_key(&self) -> &PKey<Public> {
+        &self.public_key
+    }
+
+    pub(crate) fn size(&self) -> usize {
+        self.public_key.size()
+    }
+}
+
+fn get_module(path: &Path) -> anyhow::Result<Pkcs11> {
+    let mut ht = PKCS11_MODULE_REGISTRY.lock().unwrap();
+    if let Some(ctx) = ht.get(path) {
+        return Ok(ctx.clone());
+    }
+
+    // a module can only be initialized once in the whole process
+    let ctx = Pkcs11::new(path)
+        .map_err(|e| anyhow!("failed to load pkcs#11 module {}: {e}", path.display()))?;
+    ctx.initialize(CInitializeArgs::OsThreads).map_err(|e| {
+        anyhow!(
+            "failed to initialize pkcs#11 module {}: {e}",
+            path.display()
+        )
+    })?;
+    ht.insert(path.to_path_buf(), ctx.clone());
+    Ok(ctx)
+}
+
+fn find_slot(ctx: &Pkcs11, config: &Pkcs11KeyStoreConfig) -> anyhow::Result<Slot> {
+    let slots = ctx
+        .get_slots_with_token()
+        .map_err(|e| anyhow!("failed to list slots: {e}"))?;
+    for slot in slots {
+        if config.slot.is_some_and(|id| slot.id() != id) {
+            continue;
+        }
+        if let Some(label) = &config.token_label {
+            let token_info = ctx
+                .get_token_info(slot)
+                .map_err(|e| anyhow!("failed to get token info for slot {slot}: {e}"))?;
+            if token_info.label() != label {
+                continue;
+            }
+        }
+        return Ok(slot);
+    }
+    Err(anyhow!("no matched token found"))
+}
+
+/// Find all private keys on the token, this is a blocking call
+pub(crate) fn load_keys(
+    config: &Pkcs11KeyStoreConfig,
+    user_pin: Option<String>,
+) -> anyhow::Result<Vec<Pkcs11Key>> {
+    let ctx = get_module(&config.module_path)?;
+    let slot = find_slot(&ctx, config)?;
+    debug!("using pkcs#11 slot {slot}");
+
+    let pool = Arc::new(SessionPool::new(
+        ctx,
+        slot,
+        user_pin.map(AuthPin::new),
+        config.session_pool_size(),
+    ));
+    let session = pool.open_session()?;
+
+    let handles = session
+        .find_objects(&[Attribute::Class(ObjectClass::PRIVATE_KEY)])
+        .map_err(|e| anyhow!("failed to find private keys: {e}"))?;
+
+    let mut keys = Vec::with_capacity(handles.len());
+    for handle in handles {
+        match load_public_key(&session, handle) {
+            Ok(public_key) => {
+                debug!(" - loaded private key object {handle}");
+                keys.push(Pkcs11Key {
+                    pool: pool.clone(),
+                    handle,
+                    public_key,
+                });
+            }
+            Err(e) => {
+                warn!(" - failed to load private key object {handle}: {e}");
+            }
+        }
+    }
+    Ok(keys)
+}
+
+fn load_public_key(session: &Session, handle: ObjectHandle) -> anyhow::Result<PKey<Public>> {
+    let attrs = session
+        .get_attributes(handle, &[AttributeType::KeyType, AttributeType::Id])
+        .map_err(|e| anyhow!("failed to get key attributes: {e}"))?;
+    let mut key_type = None;
+    let mut key_id = None;
+    for attr in attrs {
+        match attr {
+            Attribute::KeyType(t) => key_type = Some(t),
+            Attribute::Id(id) => key_id = Some(id),
+            _ => {}
+        }
+    }
+    let key_type = key_type.ok_or_else(|| anyhow!("no key type found"))?;
+
+    // the public part may not be readable on the private key object, so use the public key
+    // object with the same ID if present
+    let mut public_handle = handle;
+    if let Some(id) = key_id {
+        let found = session
+            .find_objects(&[Attribute::Class(ObjectClass::PUBLIC_KEY), Attribute::Id(id)])
+            .map_err(|e| anyhow!("failed to find the matched public key: {e}"))?;
+        if let Some(h) = found.first() {
+            public_handle = *h;
+        }
+    }
+
+    if key_type == KeyType::RSA {
+        let attrs = session
+            .get_attributes(
+                public_handle,
+                &[AttributeType::Modulus, AttributeType::PublicExponent],
+            )
+            .map_err(|e| anyhow!("failed to get rsa public key attributes: {e}"))?;
+        let mut n = None;
+        let mut e = None;
+        for attr in attrs {
+            match attr {
+                Attribute::Modulus(v) => n = Some(v),
+                Attribute::PublicExponent(v) => e = Some(v),
+                _ => {}
+            }
+        }
+        let (Some(n), Some(e)) = (n, e) else {
+            return Err(anyhow!("no rsa modulus or public exponent found"));
+        };
+        let rsa = Rsa::from_public_components(BigNum::from_slice(&n)?, BigNum::from_slice(&e)?)?;
+        Ok(PKey::from_rsa(rsa)?)
+    } else if key_type == KeyType::EC {
+        let (params, point) = get_ec_attributes(session, public_handle)?;
+        let nid = ec_curve_nid(&params).ok_or_else(|| anyhow!("unsupported ec curve"))?;
+        let group = EcGroup::from_curve_name(nid)?;
+        let mut bn_ctx = BigNumContext::new()?;
+        let point = EcPoint::from_bytes(&group, decode_ec_point(&point), &mut bn_ctx)?;
+        let ec_key = EcKey::from_public_key(&group, &point)?;
+        Ok(PKey::from_ec_key(ec_key)?)
+    } else if key_type == KeyType::EC_EDWARDS {
+        let (_, point) = get_ec_attributes(session, public_handle)?;
+        Ok(PKey::public_key_from_raw_bytes(
+            decode_ec_point(&point),
+            Id::ED25519,
+        )?)
+    } else {
+        Err(anyhow!("unsupported key type {key_type}"))
+    }
+}
+
+fn get_ec_attributes(
+    session: &Session,
+    handle: ObjectHandle,
+) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+    let attrs = session
+        .get_attributes(handle, &[AttributeType::EcParams, AttributeType::EcPoint])
+        .map_err(|e| anyhow!("failed to get ec public key attributes: {e}"))?;
+    let mut params = None;
+    let mut point = None;
+    for attr in attrs {
+        match attr {
+            Attribute::EcParams(v) => params = Some(v),
+            Attribute::EcPoint(v) => point = Some(v),
+            _ => {}
+        }
+    }
+    let (Some(params), Some(point)) = (params, point) else {
+        return Err(anyhow!("no ec params or ec point found"));
+    };
+    Ok((params, point))
+}
+
+fn ec_curve_nid(params: &[u8]) -> Option<Nid> {
+    // DER encoded named curve OID
+    match params {
+        [0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07] => Some(Nid::X9_62_PRIME256V1),
+        [0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x22] => Some(Nid::SECP384R1),
+        [0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x23] => Some(Nid::SECP521R1),
+        _ => None,
+    }
+}
+
+/// The CKA_EC_POINT value should be a DER encoded OCTET STRING,
+/// but some tokens return the raw point
+fn decode_ec_point(v: &[u8]) -> &[u8] {
+    if v.len() < 2 || v[0] != 0x04 {
+        return v;
+    }
+    let (len, offset) = match v[1] {
+        n if n < 0x80 => (n as usize, 2),
+        0x81 if v.len() > 2 => (v[2] as usize, 3),
+        0x82 if v.len() > 3 => (((v[2] as usize) << 8) + v[3] as usize, 4),
+        _ => return v,
+    };
+    if offset + len == v.len() {
+        &v[offset..]
+    } else {
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::ec::PointConversionForm;
+
+    fn der_octet_string(v: &[u8]) -> Vec<u8> {
+        let mut s = vec![0x04];
+        match v.len() {
+            n if n < 0x80 => s.push(n as u8),
+            n if n <= 0xFF => s.extend_from_slice(&[0x81, n as u8]),
+            n => {
+                s.push(0x82);
+                s.extend_from_slice(&(n as u16).to_be_bytes());
+            }
+        }
+        s.extend_from_slice(v);
+        s
+    }
+
+    #[test]
+    fn decode_ec_point_der() {
+        let mut bn_ctx = BigNumContext::new().unwrap();
+        for nid in [Nid::X9_62_PRIME256V1, Nid::SECP384R1, Nid::SECP521R1] {
+            let group = EcGroup::from_curve_name(nid).unwrap();
+            let key = EcKey::generate(&group).unwrap();
+            let raw = key
+                .public_key()
+                .to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut bn_ctx)
+                .unwrap();
+            let der = der_octet_string(&raw);
+            assert_eq!(decode_ec_point(&der), raw.as_slice());
+        }
+
+        let key = PKey::generate_ed25519().unwrap();
+        let raw = key.raw_public_key().unwrap();
+        let der = der_octet_string(&raw);
+        assert_eq!(decode_ec_point(&der), raw.as_slice());
+
+        let raw = [0xAB; 300];
+        let der = der_octet_string(&raw);
+        assert_eq!(decode_ec_point(&der), raw.as_slice());
+    }
+
+    #[test]
+    fn decode_ec_point_raw() {
+        let key = PKey::generate_ed25519().unwrap();
+        let raw = key.raw_public_key().unwrap();
+        if raw[0] != 0x04 {
+            assert_eq!(decode_ec_point(&raw), raw.as_slice());
+        }
+
+        // uncompressed ec point, the second byte doesn't match the remaining length
+        let mut raw = [0x22u8; 65];
+        raw[0] = 0x04;
+        assert_eq!(decode_ec_point(&raw), raw.as_slice());
+
+        // truncated long form length
+        assert_eq!(decode_ec_point(&[0x04, 0x81]), &[0x04, 0x81]);
+        assert_eq!(decode_ec_point(&[0x04, 0x82, 0x01]), &[0x04, 0x82, 0x01]);
+        // length larger than the data
+        assert_eq!(decode_ec_point(&[0x04, 0x05, 0x01]), &[0x04, 0x05, 0x01]);
+        assert_eq!(decode_ec_point(&[0x04]), &[0x04]);
+        assert!(decode_ec_point(&[]).is_empty());
+    }
+
+    #[test]
+    fn ec_curve_nid_known() {
+        let cases: [(&[u8], Nid); 3] = [
+            (
+                &[0x06, 0x08, 0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07],
+                Nid::X9_62_PRIME256V1,
+            ),
+            (&[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x22], Nid::SECP384R1),
+            (&[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x23], Nid::SECP521R1),
+        ];
+        for (params, nid) in cases {
+            assert_eq!(ec_curve_nid(params), Some(nid));
+
+            // the named curve OID should be the same as the one in the SubjectPublicKeyInfo
+            let group = EcGroup::from_curve_name(nid).unwrap();
+            let key = EcKey::generate(&group).unwrap();
+            let spki = key.public_key_to_der().unwrap();
+            assert!(spki.windows(params.len()).any(|w| w == params));
+        }
+    }
+
+    #[test]
+    fn ec_curve_nid_unknown() {
+        // secp256k1
+        assert!(ec_curve_nid(&[0x06, 0x05, 0x2B, 0x81, 0x04, 0x00, 0x0A]).is_none());
+        // explicit curve parameters are not supported
+        assert!(ec_curve_nid(&[0x30, 0x03, 0x02, 0x01, 0x01]).is_none());
+        assert!(ec_curve_nid(&[]).is_none());
+    }
+}
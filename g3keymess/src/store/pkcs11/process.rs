@@ -0,0 +1,199 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+use cryptoki::mechanism::eddsa::{EddsaParams, EddsaSignatureScheme};
+use cryptoki::mechanism::rsa::{PkcsMgfType, PkcsPssParams};
+use cryptoki::mechanism::{Mechanism, MechanismType};
+use cryptoki::object::ObjectHandle;
+use cryptoki::session::Session;
+use cryptoki::types::Ulong;
+use log::debug;
+use openssl::bn::BigNum;
+use openssl::ecdsa::EcdsaSig;
+use openssl::nid::Nid;
+use openssl::rsa::Padding;
+
+use super::Pkcs11Key;
+use crate::protocol::{KeylessAction, KeylessDataResponse, KeylessErrorResponse, KeylessRequest};
+
+impl Pkcs11Key {
+    pub(crate) async fn process(
+        &self,
+        req: &KeylessRequest,
+    ) -> Result<KeylessDataResponse, KeylessErrorResponse> {
+        let err_rsp = KeylessErrorResponse::new(req.id);
+
+        let mut session = self.pool.acquire().await.map_err(|e| {
+            debug!("failed to get pkcs#11 session: {e}");
+            err_rsp.crypto_fail()
+        })?;
+
+        let handle = self.handle;
+        let action = req.action;
+        let payload = req.payload.clone();
+        let (session, r) = tokio::task::spawn_blocking(move || {
+            let r = match session.get() {
+                Ok(s) => run_action(s, handle, action, &payload),
+                Err(e) => Err(e),
+            };
+            if r.is_err() {
+                // the session may be broken, so don't reuse it
+                session.invalidate();
+            }
+            (session, r)
+        })
+        .await
+        .map_err(|e| {
+            debug!("failed to spawn pkcs#11 task: {e}");
+            err_rsp.crypto_fail()
+        })?;
+        drop(session);
+
+        let output = r.map_err(|e| {
+            debug!("pkcs#11 operation failed: {e}");
+            err_rsp.crypto_fail()
+        })?;
+
+        let mut data_rsp = KeylessDataResponse::new(req.id, self.size());
+        let buf = data_rsp.payload_data_mut();
+        if output.len() > buf.len() {
+            return Err(err_rsp.crypto_fail());
+        }
+        buf[..output.len()].copy_from_slice(&output);
+        data_rsp.finalize_payload(output.len());
+        Ok(data_rsp)
+    }
+}
+
+fn run_action(
+    session: &Session,
+    key: ObjectHandle,
+    action: KeylessAction,
+    payload: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    match action {
+        KeylessAction::RsaDecrypt(p) => {
+            let mechanism = match p {
+                Padding::PKCS1 => Mechanism::RsaPkcs,
+                Padding::NONE => Mechanism::RsaX509,
+                _ => return Err(anyhow!("unsupported rsa padding")),
+            };
+            Ok(session.decrypt(&mechanism, key, payload)?)
+        }
+        KeylessAction::RsaSign(h) => {
+            let prefix = digest_info_prefix(h)?;
+            let mut data = Vec::with_capacity(prefix.len() + payload.len());
+            data.extend_from_slice(prefix);
+            data.extend_from_slice(payload);
+            Ok(session.sign(&Mechanism::RsaPkcs, key, &data)?)
+        }
+        KeylessAction::RsaPssSign(h) => {
+            let (hash_alg, mgf) = match h {
+                Nid::SHA256 => (MechanismType::SHA256, PkcsMgfType::MGF1_SHA256),
+                Nid::SHA384 => (MechanismType::SHA384, PkcsMgfType::MGF1_SHA384),
+                Nid::SHA512 => (MechanismType::SHA512, PkcsMgfType::MGF1_SHA512),
+                _ => return Err(anyhow!("unsupported rsa pss digest {h:?}")),
+            };
+            let params = PkcsPssParams {
+                hash_alg,
+                mgf,
+                s_len: Ulong::try_from(payload.len())?,
+            };
+            Ok(session.sign(&Mechanism::RsaPkcsPss(params), key, payload)?)
+        }
+        KeylessAction::EcdsaSign(_) => {
+            // the token returns r and s in raw format
+            let sig = session.sign(&Mechanism::Ecdsa, key, payload)?;
+            if sig.is_empty() || sig.len() % 2 != 0 {
+                return Err(anyhow!("invalid ecdsa signature length {}", sig.len()));
+            }
+            let (r, s) = sig.split_at(sig.len() / 2);
+            let sig =
+                EcdsaSig::from_private_components(BigNum::from_slice(r)?, BigNum::from_slice(s)?)?;
+            Ok(sig.to_der()?)
+        }
+        KeylessAction::Ed25519Sign => {
+            let mechanism = Mechanism::Eddsa(EddsaParams::new(EddsaSignatureScheme::Pure));
+            Ok(session.sign(&mechanism, key, payload)?)
+        }
+        KeylessAction::NotSet | KeylessAction::Ping => Err(anyhow!("unexpected action")),
+    }
+}
+
+/// Get the DER encoded DigestInfo prefix for PKCS#1 v1.5 signature
+fn digest_info_prefix(h: Nid) -> anyhow::Result<&'static [u8]> {
+    let prefix: &[u8] = match h {
+        Nid::MD5_SHA1 => &[],
+        Nid::SHA1 => &[
+            0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04,
+            0x14,
+        ],
+        Nid::SHA224 => &[
+            0x30, 0x2d, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x04, 0x05, 0x00, 0x04, 0x1c,
+        ],
+        Nid::SHA256 => &[
+            0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x01, 0x05, 0x00, 0x04, 0x20,
+        ],
+        Nid::SHA384 => &[
+            0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x02, 0x05, 0x00, 0x04, 0x30,
+        ],
+        Nid::SHA512 => &[
+            0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+            0x03, 0x05, 0x00, 0x04, 0x40,
+        ],
+        _ => return Err(anyhow!("unsupported rsa digest {h:?}")),
+    };
+    Ok(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::hash::MessageDigest;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::sign::Verifier;
+
+    #[test]
+    fn digest_info_prefix_verify() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let pkey = PKey::from_rsa(rsa.clone()).unwrap();
+        let data = b"g3keymess pkcs#11 test data";
+
+        for (nid, md) in [
+            (Nid::SHA1, MessageDigest::sha1()),
+            (Nid::SHA224, MessageDigest::sha224()),
+            (Nid::SHA256, MessageDigest::sha256()),
+            (Nid::SHA384, MessageDigest::sha384()),
+            (Nid::SHA512, MessageDigest::sha512()),
+        ] {
+            let digest = openssl::hash::hash(md, data).unwrap();
+            let prefix = digest_info_prefix(nid).unwrap();
+            assert_eq!(prefix.last(), Some(&(digest.len() as u8)));
+            assert_eq!(prefix[1] as usize + 2, prefix.len() + digest.len());
+
+            // sign the DigestInfo the same way as CKM_RSA_PKCS, and verify it as a normal signature
+            let mut msg = prefix.to_vec();
+            msg.extend_from_slice(&digest);
+            let mut sig = vec![0u8; rsa.size() as usize];
+            let len = rsa.private_encrypt(&msg, &mut sig, Padding::PKCS1).unwrap();
+
+            let mut verifier = Verifier::new(md, &pkey).unwrap();
+            verifier.update(data).unwrap();
+            assert!(verifier.verify(&sig[..len]).unwrap());
+        }
+    }
+
+    #[test]
+    fn digest_info_prefix_special() {
+        assert!(digest_info_prefix(Nid::MD5_SHA1).unwrap().is_empty());
+        assert!(digest_info_prefix(Nid::MD5).is_err());
+        assert!(digest_info_prefix(Nid::SHA3_256).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use cryptoki::context::Pkcs11;
+use cryptoki::error::{Error, RvError};
+use cryptoki::session::{Session, UserType};
+use cryptoki::slot::Slot;
+use cryptoki::types::AuthPin;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+pub(super) struct SessionPool {
+    ctx: Pkcs11,
+    slot: Slot,
+    user_pin: Option<AuthPin>,
+    idle_sessions: Mutex<Vec<Session>>,
+    semaphore: Arc<Semaphore>,
+}
+
+impl SessionPool {
+    pub(super) fn new(ctx: Pkcs11, slot: Slot, user_pin: Option<AuthPin>, size: usize) -> Self {
+        SessionPool {
+            ctx,
+            slot,
+            user_pin,
+            idle_sessions: Mutex::new(Vec::with_capacity(size)),
+            semaphore: Arc::new(Semaphore::new(size)),
+        }
+    }
+
+    /// Open a new logged in session, this is a blocking call
+    pub(super) fn open_session(&self) -> anyhow::Result<Session> {
+        let session = self
+            .ctx
+            .open_ro_session(self.slot)
+            .map_err(|e| anyhow!("failed to open session on slot {}: {e}", self.slot))?;
+        if let Some(pin) = &self.user_pin {
+            // the login state is shared by all sessions of the same token
+            match session.login(UserType::User, Some(pin)) {
+                Ok(_) => {}
+                Err(Error::Pkcs11(RvError::UserAlreadyLoggedIn, _)) => {}
+                Err(e) => return Err(anyhow!("failed to login to slot {}: {e}", self.slot)),
+            }
+        }
+        Ok(session)
+    }
+
+    pub(super) async fn acquire(self: &Arc<Self>) -> anyhow::Result<PooledSession> {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("session pool closed: {e}"))?;
+        let session = self.idle_sessions.lock().unwrap().pop();
+        Ok(PooledSession {
+            pool: self.clone(),
+            session,
+            _permit: permit,
+        })
+    }
+}
+
+/// A session slot taken from the pool, the session will be opened lazily
+pub(super) struct PooledSession {
+    pool: Arc<SessionPool>,
+    session: Option<Session>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PooledSession {
+    /// Get the session, and open it if needed, this is a blocking call
+    pub(super) fn get(&mut self) -> anyhow::Result<&Session> {
+        if self.session.is_none() {
+            let session = self.pool.open_session()?;
+            self.session = Some(session);
+        }
+        Ok(self.session.as_ref().unwrap())
+    }
+
+    /// Close the session instead of returning it back to the pool
+    pub(super) fn invalidate(&mut self) {
+        self.session = None;
+    }
+}
+
+impl Drop for PooledSession {
+    fn drop(&mut self) {
+        if let Some(session) = self.session.take() {
+            self.pool.idle_sessions.lock().unwrap().push(session);
+        }
+    }
+}
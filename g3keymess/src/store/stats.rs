@@ -0,0 +1,102 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use foldhash::fast::FixedState;
+
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+use super::KeyLoadError;
+
+static KEY_STORE_STATS_REGISTRY: Mutex<HashMap<NodeName, Arc<KeyStoreStats>, FixedState>> =
+    Mutex::new(HashMap::with_hasher(FixedState::with_seed(0)));
+
+pub(crate) struct KeyStoreStats {
+    name: NodeName,
+    id: StatId,
+
+    loaded: AtomicU64,
+    no_passphrase: AtomicU64,
+    bad_passphrase: AtomicU64,
+    invalid_key: AtomicU64,
+}
+
+#[derive(Default)]
+pub(crate) struct KeyStoreSnapshot {
+    pub(crate) loaded: u64,
+    pub(crate) no_passphrase: u64,
+    pub(crate) bad_passphrase: u64,
+    pub(crate) invalid_key: u64,
+}
+
+impl KeyStoreStats {
+    fn new(name: &NodeName) -> Self {
+        KeyStoreStats {
+            name: name.clone(),
+            id: StatId::new_unique(),
+            loaded: AtomicU64::new(0),
+            no_passphrase: AtomicU64::new(0),
+            bad_passphrase: AtomicU64::new(0),
+            invalid_key: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    pub(crate) fn add_loaded(&self) {
+        self.loaded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_load_error(&self, e: &KeyLoadError) {
+        match e {
+            KeyLoadError::NoPassphrase => self.no_passphrase.fetch_add(1, Ordering::Relaxed),
+            KeyLoadError::BadPassphrase(_) => self.bad_passphrase.fetch_add(1, Ordering::Relaxed),
+            KeyLoadError::InvalidKey(_) => self.invalid_key.fetch_add(1, Ordering::Relaxed),
+        };
+    }
+
+    pub(crate) fn snapshot(&self) -> KeyStoreSnapshot {
+        KeyStoreSnapshot {
+            loaded: self.loaded.load(Ordering::Relaxed),
+            no_passphrase: self.no_passphrase.load(Ordering::Relaxed),
+            bad_passphrase: self.bad_passphrase.load(Ordering::Relaxed),
+            invalid_key: self.invalid_key.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub(crate) fn get_or_insert(name: &NodeName) -> Arc<KeyStoreStats> {
+    let mut ht = KEY_STORE_STATS_REGISTRY.lock().unwrap();
+    ht.entry(name.clone())
+        .or_insert_with(|| Arc::new(KeyStoreStats::new(name)))
+        .clone()
+}
+
+pub(super) fn retain(names: &HashSet<NodeName>) {
+    let mut ht = KEY_STORE_STATS_REGISTRY.lock().unwrap();
+    ht.retain(|name, _| names.contains(name));
+}
+
+pub(crate) fn foreach<F>(mut f: F)
+where
+    F: FnMut(&Arc<KeyStoreStats>),
+{
+    let ht = KEY_STORE_STATS_REGISTRY.lock().unwrap();
+    for stats in ht.values() {
+        f(stats)
+    }
+}
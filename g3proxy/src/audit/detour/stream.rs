@@ -165,8 +165,8 @@ where
                         idle_count += n;
 
                         if let Some(user) = self.task_notes.user() {
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -183,8 +183,8 @@ where
                     }
 
                     if let Some(user) = self.task_notes.user() {
-                        if user.is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user.blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
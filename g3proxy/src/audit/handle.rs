@@ -9,7 +9,7 @@ use slog::Logger;
 
 use g3_dpi::{
     H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, ProtocolInspectPolicy,
-    ProtocolInspectionConfig, ProtocolPortMap, SmtpInterceptionConfig,
+    ProtocolInspectionConfig, ProtocolPortMap, SmtpInterceptionConfig, WebSocketInterceptionConfig,
 };
 use g3_icap_client::reqmod::IcapReqmodClient;
 use g3_icap_client::respmod::IcapRespmodClient;
@@ -124,6 +124,11 @@ impl AuditHandle {
         &self.auditor_config.imap_interception
     }
 
+    #[inline]
+    pub(crate) fn websocket_interception(&self) -> &WebSocketInterceptionConfig {
+        &self.auditor_config.websocket_interception
+    }
+
     #[inline]
     pub(crate) fn icap_reqmod_client(&self) -> Option<&IcapReqmodClient> {
         self.icap_reqmod_client.as_ref()
@@ -146,4 +151,11 @@ impl AuditHandle {
         let mut rng = rand::rng();
         self.auditor_config.task_audit_ratio.sample(&mut rng)
     }
+
+    pub(crate) fn do_websocket_icap(&self) -> bool {
+        use rand::distr::Distribution;
+
+        let mut rng = rand::rng();
+        self.auditor_config.websocket_icap_ratio.sample(&mut rng)
+    }
 }
@@ -20,7 +20,7 @@ pub use ops::load_all;
 pub(crate) use ops::reload;
 
 mod registry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{get_config, get_names, get_or_insert_default};
 
 mod handle;
 pub(crate) use handle::AuditHandle;
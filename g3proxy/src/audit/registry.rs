@@ -40,7 +40,7 @@ pub(crate) fn get_names() -> HashSet<NodeName> {
     names
 }
 
-pub(super) fn get_config(name: &NodeName) -> Option<AuditorConfig> {
+pub(crate) fn get_config(name: &NodeName) -> Option<AuditorConfig> {
     let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
     ht.get(name).map(|a| a.config.as_ref().clone())
 }
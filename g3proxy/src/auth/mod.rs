@@ -23,13 +23,14 @@ pub use ops::load_all;
 pub(crate) use ops::reload;
 
 mod registry;
-pub(crate) use registry::{get_all_groups, get_names, get_or_insert_default};
+pub(crate) use registry::{get_all_groups, get_config, get_names, get_or_insert_default};
 
 mod site;
 pub(crate) use site::UserSite;
 use site::UserSites;
 
 mod user;
+use user::UserRevokeReason;
 pub(crate) use user::{User, UserContext};
 
 mod stats;
@@ -143,6 +144,7 @@ impl UserGroup {
         ));
         group.check_quit_sender = Some(source::new_check_job(
             group.config.refresh_interval,
+            group.config.live_task_revoke,
             group.static_users.clone(),
             group.dynamic_users.clone(),
         ));
@@ -152,10 +154,11 @@ impl UserGroup {
 
     fn reload(&self, config: UserGroupConfig) -> anyhow::Result<Arc<Self>> {
         let datetime_now = Utc::now();
+        let revoke_policy = &config.live_task_revoke;
         let mut static_users = AHashMap::new();
         for (username, user_config) in &config.static_users {
             let user = if let Some(user) = self.static_users.get(username) {
-                user.new_for_reload(user_config, &datetime_now)?
+                user.new_for_reload(user_config, &datetime_now, revoke_policy)?
             } else {
                 User::new(config.name(), user_config, &datetime_now)?
             };
@@ -165,7 +168,7 @@ impl UserGroup {
         let anonymous_user = match &config.anonymous_user {
             Some(user_config) => {
                 let user = if let Some(old) = &self.anonymous_user {
-                    old.new_for_reload(user_config, &datetime_now)?
+                    old.new_for_reload(user_config, &datetime_now, revoke_policy)?
                 } else {
                     User::new(config.name(), user_config, &datetime_now)?
                 };
@@ -183,6 +186,23 @@ impl UserGroup {
             }
         }
 
+        for (username, user) in self.static_users.iter() {
+            if !static_users.contains_key(username) && !dynamic_users.contains_key(username) {
+                user.revoke_live_tasks(UserRevokeReason::Removed, revoke_policy);
+            }
+        }
+        if dynamic_users.is_empty() {
+            let users = self.dynamic_users.load();
+            for (username, user) in users.iter() {
+                if !static_users.contains_key(username) {
+                    user.revoke_live_tasks(UserRevokeReason::Removed, revoke_policy);
+                }
+            }
+        }
+        if let (None, Some(user)) = (&anonymous_user, &self.anonymous_user) {
+            user.revoke_live_tasks(UserRevokeReason::Removed, revoke_policy);
+        }
+
         let mut group = Self::new_without_users(config);
         group.static_users = Arc::new(static_users);
         if !dynamic_users.is_empty() {
@@ -197,6 +217,7 @@ impl UserGroup {
         ));
         group.check_quit_sender = Some(source::new_check_job(
             group.config.refresh_interval,
+            group.config.live_task_revoke,
             group.static_users.clone(),
             group.dynamic_users.clone(),
         ));
@@ -231,6 +252,17 @@ impl UserGroup {
         self.get_anonymous_user()
     }
 
+    /// revoke the live tasks of all users in this group, should be called when the group is deleted
+    fn revoke_all_users(&self) {
+        let revoke_policy = &self.config.live_task_revoke;
+        self.foreach_user(|_, user| {
+            user.revoke_live_tasks(UserRevokeReason::Removed, revoke_policy);
+        });
+        if let Some(user) = &self.anonymous_user {
+            user.revoke_live_tasks(UserRevokeReason::Removed, revoke_policy);
+        }
+    }
+
     fn stop_fetch_job(&self) {
         if let Some(sender) = &self.fetch_quit_sender {
             let _ = sender.try_send(());
@@ -50,6 +50,7 @@ pub(super) fn del(name: &NodeName) {
     let mut ht = RUNTIME_USER_GROUP_REGISTRY.lock().unwrap();
     if let Some(old_group) = ht.remove(name) {
         old_group.stop_fetch_job();
+        old_group.revoke_all_users();
     }
 }
 
@@ -62,7 +63,7 @@ pub(crate) fn get_names() -> HashSet<NodeName> {
     names
 }
 
-pub(super) fn get_config(name: &NodeName) -> Option<UserGroupConfig> {
+pub(crate) fn get_config(name: &NodeName) -> Option<UserGroupConfig> {
     let ht = RUNTIME_USER_GROUP_REGISTRY.lock().unwrap();
     ht.get(name).map(|g| g.config.as_ref().clone())
 }
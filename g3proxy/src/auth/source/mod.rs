@@ -12,8 +12,8 @@ use chrono::{DateTime, Utc};
 use log::warn;
 use tokio::sync::{mpsc, oneshot};
 
-use super::{User, UserGroupConfig};
-use crate::config::auth::{UserConfig, UserDynamicSource};
+use super::{User, UserGroupConfig, UserRevokeReason};
+use crate::config::auth::{UserConfig, UserDynamicSource, UserRevokePolicy};
 
 #[cfg(feature = "lua")]
 mod lua;
@@ -113,6 +113,7 @@ pub(super) fn new_fetch_job(
 
 pub(super) fn new_check_job(
     check_interval: Duration,
+    revoke_policy: UserRevokePolicy,
     static_users: Arc<AHashMap<Arc<str>, Arc<User>>>,
     dynamic_users_container: Arc<ArcSwap<AHashMap<Arc<str>, Arc<User>>>>,
 ) -> oneshot::Sender<()> {
@@ -131,8 +132,8 @@ pub(super) fn new_check_job(
             }
 
             let datetime_now = Utc::now();
-            check_dynamic_users(&datetime_now, &dynamic_users_container);
-            check_static_users(&datetime_now, &static_users);
+            check_dynamic_users(&datetime_now, &revoke_policy, &dynamic_users_container);
+            check_static_users(&datetime_now, &revoke_policy, &static_users);
 
             interval.tick().await;
         }
@@ -153,32 +154,40 @@ pub(super) fn publish_dynamic_users(
         let user_config = Arc::new(user_config);
         let username = user_config.name();
         let user = if let Some(old_user) = old_dynamic_users.get(username.as_ref()) {
-            old_user.new_for_reload(&user_config, &datetime_now)?
+            old_user.new_for_reload(&user_config, &datetime_now, &group_config.live_task_revoke)?
         } else {
             User::new(group_config.name(), &user_config, &datetime_now)?
         };
         new_dynamic_users.insert(username.clone(), Arc::new(user));
     }
 
+    for (username, user) in old_dynamic_users.iter() {
+        if !new_dynamic_users.contains_key(username) {
+            user.revoke_live_tasks(UserRevokeReason::Removed, &group_config.live_task_revoke);
+        }
+    }
+
     dynamic_users_container.store(Arc::new(new_dynamic_users));
     Ok(())
 }
 
 fn check_dynamic_users(
     datetime_now: &DateTime<Utc>,
+    revoke_policy: &UserRevokePolicy,
     dynamic_users_container: &Arc<ArcSwap<AHashMap<Arc<str>, Arc<User>>>>,
 ) {
     let old_dynamic_users = dynamic_users_container.load();
     for (_, user) in old_dynamic_users.iter() {
-        user.check_expired(datetime_now);
+        user.check_expired(datetime_now, revoke_policy);
     }
 }
 
 fn check_static_users(
     datetime_now: &DateTime<Utc>,
+    revoke_policy: &UserRevokePolicy,
     static_users: &Arc<AHashMap<Arc<str>, Arc<User>>>,
 ) {
     for (_, user) in static_users.iter() {
-        user.check_expired(datetime_now);
+        user.check_expired(datetime_now, revoke_policy);
     }
 }
@@ -4,7 +4,7 @@
  */
 
 use std::net::SocketAddr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
@@ -13,9 +13,15 @@ use arc_swap::ArcSwapOption;
 use chrono::{DateTime, Utc};
 use foldhash::HashMap;
 use governor::{RateLimiter, clock::DefaultClock, state::InMemoryState, state::NotKeyed};
+use log::info;
+use tokio::runtime::Handle;
+use tokio::task::AbortHandle;
 use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 
-use g3_io_ext::{GlobalDatagramLimiter, GlobalLimitGroup, GlobalStreamLimiter};
+use g3_io_ext::{
+    GlobalDatagramLimiter, GlobalLimitGroup, GlobalStreamLimiter, IdleForceQuitReason,
+};
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::auth::UserAuthError;
@@ -28,14 +34,180 @@ use super::{
     UserForbiddenStats, UserRequestStats, UserSite, UserSiteDurationRecorder, UserSiteStats,
     UserSites, UserTrafficStats, UserType, UserUpstreamTrafficStats,
 };
-use crate::config::auth::{UserAuditConfig, UserConfig};
+use crate::config::auth::{UserAuditConfig, UserConfig, UserRevokePolicy};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum UserRevokeReason {
+    Removed,
+    Blocked,
+    Expired,
+}
+
+impl UserRevokeReason {
+    const fn as_str(&self) -> &'static str {
+        match self {
+            UserRevokeReason::Removed => "removed",
+            UserRevokeReason::Blocked => "blocked",
+            UserRevokeReason::Expired => "expired",
+        }
+    }
+
+    const fn as_u8(&self) -> u8 {
+        match self {
+            UserRevokeReason::Removed => 1,
+            UserRevokeReason::Blocked => 2,
+            UserRevokeReason::Expired => 3,
+        }
+    }
+
+    const fn from_u8(v: u8) -> Self {
+        match v {
+            1 => UserRevokeReason::Removed,
+            3 => UserRevokeReason::Expired,
+            _ => UserRevokeReason::Blocked,
+        }
+    }
+
+    const fn force_quit_reason(&self) -> IdleForceQuitReason {
+        match self {
+            UserRevokeReason::Removed => IdleForceQuitReason::UserRemoved,
+            UserRevokeReason::Blocked => IdleForceQuitReason::UserBlocked,
+            UserRevokeReason::Expired => IdleForceQuitReason::UserExpired,
+        }
+    }
+}
+
+struct UserRevokeNotify {
+    token: CancellationToken,
+    /// bumped on each reset or new drain timer, so a stale timer won't block the user
+    generation: u64,
+    drain_timer: Option<AbortHandle>,
+}
+
+impl UserRevokeNotify {
+    fn stop_drain_timer(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+        if let Some(timer) = self.drain_timer.take() {
+            timer.abort();
+        }
+    }
+}
+
+/// The block state shared by all versions of the same user, which will be checked by live tasks
+struct UserBlockState {
+    blocked: AtomicBool,
+    /// the unix timestamp in milliseconds after which the live tasks should be blocked, 0 if unset
+    block_at: AtomicI64,
+    reason: AtomicU8,
+    /// cancelled when the live tasks should quit, so they don't need to wait for the idle check
+    notify: Mutex<UserRevokeNotify>,
+}
+
+impl UserBlockState {
+    fn new(blocked: bool) -> Self {
+        UserBlockState {
+            blocked: AtomicBool::new(blocked),
+            block_at: AtomicI64::new(0),
+            reason: AtomicU8::new(UserRevokeReason::Blocked.as_u8()),
+            notify: Mutex::new(UserRevokeNotify {
+                token: CancellationToken::new(),
+                generation: 0,
+                drain_timer: None,
+            }),
+        }
+    }
+
+    fn revoke_token(&self) -> CancellationToken {
+        self.notify.lock().unwrap().token.clone()
+    }
+
+    fn blocked_reason(&self) -> Option<UserRevokeReason> {
+        let blocked = self.blocked.load(Ordering::Relaxed) || {
+            let block_at = self.block_at.load(Ordering::Relaxed);
+            block_at > 0 && Utc::now().timestamp_millis() >= block_at
+        };
+        if blocked {
+            Some(UserRevokeReason::from_u8(
+                self.reason.load(Ordering::Relaxed),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn block_now(&self, reason: UserRevokeReason) {
+        self.reason.store(reason.as_u8(), Ordering::Relaxed);
+        self.blocked.store(true, Ordering::Relaxed);
+        let mut notify = self.notify.lock().unwrap();
+        notify.stop_drain_timer();
+        notify.token.cancel();
+    }
+
+    fn block_after(self: &Arc<Self>, reason: UserRevokeReason, delay: Duration) {
+        let block_at = Utc::now()
+            .timestamp_millis()
+            .saturating_add(delay.as_millis().try_into().unwrap_or(i64::MAX));
+        // keep the earlier one if there is already a pending block
+        if self
+            .block_at
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |old| {
+                if old > 0 && old <= block_at {
+                    None
+                } else {
+                    Some(block_at)
+                }
+            })
+            .is_err()
+        {
+            return;
+        }
+        self.reason.store(reason.as_u8(), Ordering::Relaxed);
+
+        let mut notify = self.notify.lock().unwrap();
+        notify.stop_drain_timer();
+        if delay.is_zero() {
+            notify.token.cancel();
+            return;
+        }
+        let Ok(rt_handle) = Handle::try_current() else {
+            // the idle check will still find the expired deadline
+            return;
+        };
+        let generation = notify.generation;
+        let state = Arc::downgrade(self);
+        let timer = rt_handle.spawn(async move {
+            tokio::time::sleep(delay).await;
+            let Some(state) = state.upgrade() else {
+                return;
+            };
+            let notify = state.notify.lock().unwrap();
+            if notify.generation == generation {
+                state.blocked.store(true, Ordering::Relaxed);
+                notify.token.cancel();
+            }
+        });
+        notify.drain_timer = Some(timer.abort_handle());
+    }
+
+    fn reset(&self) {
+        self.blocked.store(false, Ordering::Relaxed);
+        self.block_at.store(0, Ordering::Relaxed);
+        self.reason
+            .store(UserRevokeReason::Blocked.as_u8(), Ordering::Relaxed);
+        let mut notify = self.notify.lock().unwrap();
+        notify.stop_drain_timer();
+        if notify.token.is_cancelled() {
+            notify.token = CancellationToken::new();
+        }
+    }
+}
 
 pub(crate) struct User {
     config: Arc<UserConfig>,
     group: NodeName,
     started: Instant,
     is_expired: AtomicBool,
-    is_blocked: Arc<AtomicBool>,
+    block_state: Arc<UserBlockState>,
     request_rate_limit: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     tcp_conn_rate_limit: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     tcp_all_upload_speed_limit: Option<Arc<GlobalStreamLimiter>>,
@@ -60,6 +232,16 @@ impl User {
         self.config.task_idle_max_count
     }
 
+    #[inline]
+    pub(crate) fn websocket_message_max_size(&self) -> Option<usize> {
+        self.config.websocket_message_max_size
+    }
+
+    #[inline]
+    pub(crate) fn websocket_message_max_count(&self) -> Option<usize> {
+        self.config.websocket_message_max_count
+    }
+
     fn update_ingress_net_filter(&mut self) {
         self.ingress_net_filter = self
             .config
@@ -134,7 +316,7 @@ impl User {
         };
 
         let is_expired = AtomicBool::new(config.is_expired(datetime_now));
-        let is_blocked = Arc::new(AtomicBool::new(config.block_and_delay.is_some()));
+        let block_state = Arc::new(UserBlockState::new(config.block_and_delay.is_some()));
 
         let explicit_sites = UserSites::new(config.explicit_sites.values(), config.name(), group)
             .context("failed to build sites config")?;
@@ -144,7 +326,7 @@ impl User {
             group: group.clone(),
             started: Instant::now(),
             is_expired,
-            is_blocked,
+            block_state,
             request_rate_limit,
             tcp_conn_rate_limit,
             tcp_all_upload_speed_limit,
@@ -172,6 +354,7 @@ impl User {
         &self,
         config: &Arc<UserConfig>,
         datetime_now: &DateTime<Utc>,
+        revoke_policy: &UserRevokePolicy,
     ) -> anyhow::Result<Self> {
         let request_rate_limit = if let Some(quota) = &config.request_rate_limit {
             if let Some(old_limiter) = &self.request_rate_limit {
@@ -282,15 +465,22 @@ impl User {
         };
 
         // use the expired state from new config for new tasks
-        let is_expired = AtomicBool::new(config.is_expired(datetime_now));
+        let is_expired = config.is_expired(datetime_now);
 
-        // use the latest block state in new config
+        // use the latest block and expire state in new config for live tasks
         if config.block_and_delay.is_some() {
-            self.is_blocked.fetch_or(true, Ordering::Relaxed);
+            if self.config.block_and_delay.is_none() {
+                self.revoke_live_tasks(UserRevokeReason::Blocked, revoke_policy);
+            }
+        } else if is_expired {
+            if !self.is_expired() {
+                self.revoke_live_tasks(UserRevokeReason::Expired, revoke_policy);
+            }
         } else {
-            self.is_blocked.fetch_and(false, Ordering::Relaxed);
+            self.block_state.reset();
         }
-        let is_blocked = Arc::clone(&self.is_blocked);
+        let is_expired = AtomicBool::new(is_expired);
+        let block_state = Arc::clone(&self.block_state);
 
         let explicit_sites = self
             .explicit_sites
@@ -302,7 +492,7 @@ impl User {
             group: self.group.clone(),
             started: self.started,
             is_expired,
-            is_blocked,
+            block_state,
             request_rate_limit,
             tcp_conn_rate_limit,
             tcp_all_upload_speed_limit,
@@ -338,9 +528,44 @@ impl User {
         Ok(user)
     }
 
-    /// for user blocked check in idle checking
-    pub(crate) fn is_blocked(&self) -> bool {
-        self.is_blocked.load(Ordering::Relaxed)
+    /// for user blocked check in idle checking, with the reason why the live tasks should quit
+    pub(crate) fn blocked_reason(&self) -> Option<IdleForceQuitReason> {
+        self.block_state
+            .blocked_reason()
+            .map(|r| r.force_quit_reason())
+    }
+
+    /// the token which will be cancelled when the live tasks of this user should quit
+    pub(crate) fn revoke_token(&self) -> CancellationToken {
+        self.block_state.revoke_token()
+    }
+
+    /// notify the live tasks of this user to quit according to the revoke policy
+    pub(super) fn revoke_live_tasks(&self, reason: UserRevokeReason, policy: &UserRevokePolicy) {
+        let name = self.config.name();
+        let reason_s = reason.as_str();
+        match policy {
+            UserRevokePolicy::Immediate => {
+                info!(
+                    "user {name} in group {} is {reason_s}, revoke all live tasks now",
+                    self.group
+                );
+                self.block_state.block_now(reason);
+            }
+            UserRevokePolicy::Drain(delay) => {
+                info!(
+                    "user {name} in group {} is {reason_s}, revoke all live tasks after {delay:?}",
+                    self.group
+                );
+                self.block_state.block_after(reason, *delay);
+            }
+            UserRevokePolicy::Finish => {
+                info!(
+                    "user {name} in group {} is {reason_s}, allow all live tasks to finish",
+                    self.group
+                );
+            }
+        }
     }
 
     #[inline]
@@ -348,10 +573,15 @@ impl User {
         self.is_expired.load(Ordering::Relaxed)
     }
 
-    pub(super) fn check_expired(&self, datetime_now: &DateTime<Utc>) -> bool {
+    pub(super) fn check_expired(
+        &self,
+        datetime_now: &DateTime<Utc>,
+        revoke_policy: &UserRevokePolicy,
+    ) -> bool {
         if self.config.is_expired(datetime_now) {
-            // TODO log user expire ?
-            self.is_expired.swap(true, Ordering::Relaxed);
+            if !self.is_expired.swap(true, Ordering::Relaxed) {
+                self.revoke_live_tasks(UserRevokeReason::Expired, revoke_policy);
+            }
             true
         } else {
             // it's not possible for expired users to be valid with out new config reload
@@ -910,3 +1140,102 @@ impl UserContext {
             .or(self.user.config.http_rsp_hdr_recv_timeout)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_state_reason() {
+        let state = UserBlockState::new(false);
+        assert!(state.blocked_reason().is_none());
+
+        state.block_now(UserRevokeReason::Removed);
+        assert_eq!(state.blocked_reason(), Some(UserRevokeReason::Removed));
+
+        state.reset();
+        assert!(state.blocked_reason().is_none());
+
+        let state = UserBlockState::new(true);
+        assert_eq!(state.blocked_reason(), Some(UserRevokeReason::Blocked));
+    }
+
+    #[test]
+    fn block_state_drain() {
+        let state = Arc::new(UserBlockState::new(false));
+        state.block_after(UserRevokeReason::Expired, Duration::from_secs(3600));
+        assert!(state.blocked_reason().is_none());
+
+        // a later drain time won't delay the pending one
+        state.block_after(UserRevokeReason::Removed, Duration::from_secs(7200));
+        assert!(state.blocked_reason().is_none());
+
+        state.block_after(UserRevokeReason::Removed, Duration::ZERO);
+        assert_eq!(state.blocked_reason(), Some(UserRevokeReason::Removed));
+
+        state.reset();
+        assert!(state.blocked_reason().is_none());
+    }
+
+    #[tokio::test]
+    async fn block_state_notify() {
+        let state = Arc::new(UserBlockState::new(false));
+        let task_token = state.revoke_token().child_token();
+        assert!(!task_token.is_cancelled());
+
+        state.block_now(UserRevokeReason::Removed);
+        tokio::time::timeout(Duration::from_secs(1), task_token.cancelled())
+            .await
+            .unwrap();
+
+        // new tasks should not be affected after the user is restored
+        state.reset();
+        assert!(!state.revoke_token().is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn block_state_drain_notify() {
+        let state = Arc::new(UserBlockState::new(false));
+        let task_token = state.revoke_token().child_token();
+
+        state.block_after(UserRevokeReason::Expired, Duration::from_millis(200));
+        assert!(!task_token.is_cancelled());
+
+        tokio::time::timeout(Duration::from_secs(2), task_token.cancelled())
+            .await
+            .unwrap();
+        assert_eq!(state.blocked_reason(), Some(UserRevokeReason::Expired));
+
+        // a reset will stop the pending drain timer
+        state.reset();
+        let task_token = state.revoke_token().child_token();
+        state.block_after(UserRevokeReason::Expired, Duration::from_millis(100));
+        state.reset();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!task_token.is_cancelled());
+        assert!(state.blocked_reason().is_none());
+    }
+
+    #[test]
+    fn revoke_reason_force_quit() {
+        for reason in [
+            UserRevokeReason::Removed,
+            UserRevokeReason::Blocked,
+            UserRevokeReason::Expired,
+        ] {
+            assert_eq!(UserRevokeReason::from_u8(reason.as_u8()), reason);
+        }
+        assert!(matches!(
+            UserRevokeReason::Removed.force_quit_reason(),
+            IdleForceQuitReason::UserRemoved
+        ));
+        assert!(matches!(
+            UserRevokeReason::Blocked.force_quit_reason(),
+            IdleForceQuitReason::UserBlocked
+        ));
+        assert!(matches!(
+            UserRevokeReason::Expired.force_quit_reason(),
+            IdleForceQuitReason::UserExpired
+        ));
+    }
+}
@@ -13,7 +13,7 @@ use g3_cert_agent::CertAgentConfig;
 use g3_dpi::{
     H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig,
     ProtocolInspectPolicyBuilder, ProtocolInspectionConfig, ProtocolPortMap,
-    SmtpInterceptionConfig,
+    SmtpInterceptionConfig, WebSocketInterceptionConfig,
 };
 use g3_icap_client::IcapServiceConfig;
 use g3_tls_ticket::TlsTicketConfig;
@@ -23,6 +23,7 @@ use g3_types::net::{
 };
 use g3_udpdump::StreamDumpConfig;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 #[cfg(feature = "quic")]
 use super::AuditStreamDetourConfig;
@@ -44,6 +45,7 @@ pub(crate) struct AuditorConfig {
     pub(crate) h2_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) h2_interception: H2InterceptionConfig,
     pub(crate) websocket_inspect_policy: ProtocolInspectPolicyBuilder,
+    pub(crate) websocket_interception: WebSocketInterceptionConfig,
     pub(crate) smtp_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) smtp_interception: SmtpInterceptionConfig,
     pub(crate) imap_inspect_policy: ProtocolInspectPolicyBuilder,
@@ -53,6 +55,7 @@ pub(crate) struct AuditorConfig {
     #[cfg(feature = "quic")]
     pub(crate) stream_detour_service: Option<Arc<AuditStreamDetourConfig>>,
     pub(crate) task_audit_ratio: Bernoulli,
+    pub(crate) websocket_icap_ratio: Bernoulli,
 }
 
 impl AuditorConfig {
@@ -81,6 +84,7 @@ impl AuditorConfig {
             h2_inspect_policy: Default::default(),
             h2_interception: Default::default(),
             websocket_inspect_policy: Default::default(),
+            websocket_interception: Default::default(),
             smtp_inspect_policy: Default::default(),
             smtp_interception: Default::default(),
             imap_inspect_policy: Default::default(),
@@ -90,6 +94,7 @@ impl AuditorConfig {
             #[cfg(feature = "quic")]
             stream_detour_service: None,
             task_audit_ratio: Bernoulli::new(1.0).unwrap(),
+            websocket_icap_ratio: Bernoulli::new(0.0).unwrap(),
         }
     }
 
@@ -201,6 +206,12 @@ impl AuditorConfig {
                         .context(format!("invalid protocol inspect policy value for key {k}"))?;
                 Ok(())
             }
+            "websocket_interception" => {
+                self.websocket_interception =
+                    g3_yaml::value::as_websocket_interception_config(v)
+                        .context(format!("invalid websocket interception value for key {k}"))?;
+                Ok(())
+            }
             "smtp_inspect_policy" => {
                 self.smtp_inspect_policy = g3_yaml::value::as_protocol_inspect_policy_builder(v)
                     .context(format!("invalid protocol inspect policy value for key {k}"))?;
@@ -252,7 +263,44 @@ impl AuditorConfig {
                     .context(format!("invalid random ratio value for key {k}"))?;
                 Ok(())
             }
+            "websocket_icap_ratio" => {
+                self.websocket_icap_ratio = g3_yaml::value::as_random_ratio(v)
+                    .context(format!("invalid random ratio value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
 }
+
+impl YamlDump for AuditorConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("protocol_inspection", &self.protocol_inspection)
+            .set("server_tcp_portmap", &self.server_tcp_portmap)
+            .set("client_tcp_portmap", &self.client_tcp_portmap)
+            .set("tls_cert_agent", &self.tls_cert_agent)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("tls_interception_client", &self.tls_interception_client)
+            .set("tls_interception_server", &self.tls_interception_server)
+            .set("tls_stream_dump", &self.tls_stream_dump)
+            .set("log_uri_max_chars", &self.log_uri_max_chars)
+            .set("h1_interception", &self.h1_interception)
+            .set("h2_inspect_policy", &self.h2_inspect_policy)
+            .set("h2_interception", &self.h2_interception)
+            .set("websocket_inspect_policy", &self.websocket_inspect_policy)
+            .set("websocket_interception", &self.websocket_interception)
+            .set("smtp_inspect_policy", &self.smtp_inspect_policy)
+            .set("smtp_interception", &self.smtp_interception)
+            .set("imap_inspect_policy", &self.imap_inspect_policy)
+            .set("imap_interception", &self.imap_interception)
+            .set("icap_reqmod_service", &self.icap_reqmod_service)
+            .set("icap_respmod_service", &self.icap_respmod_service);
+        #[cfg(feature = "quic")]
+        map.set("stream_detour_service", &self.stream_detour_service);
+        map.set("task_audit_ratio", &self.task_audit_ratio)
+            .set("websocket_icap_ratio", &self.websocket_icap_ratio);
+        map.build()
+    }
+}
@@ -16,6 +16,7 @@ use g3_types::net::{
     SocketBufferConfig, UpstreamAddr,
 };
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 const DEFAULT_DETOUR_PORT: u16 = 2888;
 
@@ -129,3 +130,19 @@ impl AuditStreamDetourConfig {
         Ok(config)
     }
 }
+
+impl YamlDump for AuditStreamDetourConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("peer_addr", &self.peer_addr)
+            .set("tls_client", &self.tls_client)
+            .set("tls_name", &self.tls_name)
+            .set("connection_pool", &self.connection_pool)
+            .set("connection_reuse_limit", &self.connection_reuse_limit)
+            .set("quic_transport", &self.quic_transport)
+            .set("stream_open_timeout", &self.stream_open_timeout)
+            .set("request_timeout", &self.request_timeout)
+            .set("socket_buffer", &self.socket_buffer);
+        map.build()
+    }
+}
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::path::Path;
 
 use anyhow::anyhow;
@@ -30,6 +31,18 @@ pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     })
 }
 
+/// Parse all auditor configs without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AuditorConfig>> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let config = load_auditor(map, position)?;
+        all.borrow_mut().push(config);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AuditorConfig> {
     let doc = g3_yaml::load_doc(position)?;
     if let Yaml::Hash(map) = doc {
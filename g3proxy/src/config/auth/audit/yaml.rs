@@ -6,6 +6,8 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use super::UserAuditConfig;
 
 impl UserAuditConfig {
@@ -39,3 +41,17 @@ impl UserAuditConfig {
         }
     }
 }
+
+impl YamlDump for UserAuditConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(
+            "enable_protocol_inspection",
+            &self.enable_protocol_inspection,
+        )
+        .set("prohibit_unknown_protocol", &self.prohibit_unknown_protocol)
+        .set("prohibit_timeout_protocol", &self.prohibit_timeout_protocol)
+        .set("task_audit_ratio", &self.task_audit_ratio);
+        map.build()
+    }
+}
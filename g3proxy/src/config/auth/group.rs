@@ -13,11 +13,53 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{UserConfig, UserDynamicSource};
 
 const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
 
+/// What to do with the live tasks of a user if it is removed, blocked or expired
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum UserRevokePolicy {
+    /// cancel the live tasks at the next idle check
+    #[default]
+    Immediate,
+    /// cancel the live tasks after the drain time
+    Drain(Duration),
+    /// allow the live tasks to finish
+    Finish,
+}
+
+impl UserRevokePolicy {
+    fn parse(v: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::String(s) = v {
+            match g3_yaml::key::normalize(s).as_str() {
+                "immediate" => return Ok(UserRevokePolicy::Immediate),
+                "finish" => return Ok(UserRevokePolicy::Finish),
+                _ => {}
+            }
+        }
+        let drain = g3_yaml::humanize::as_duration(v)
+            .context("neither a valid policy string nor a drain time")?;
+        if drain.is_zero() {
+            Ok(UserRevokePolicy::Immediate)
+        } else {
+            Ok(UserRevokePolicy::Drain(drain))
+        }
+    }
+}
+
+impl YamlDump for UserRevokePolicy {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        match self {
+            UserRevokePolicy::Immediate => Yaml::String("immediate".to_string()),
+            UserRevokePolicy::Drain(drain) => drain.dump_yaml(redact),
+            UserRevokePolicy::Finish => Yaml::String("finish".to_string()),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct UserGroupConfig {
     name: NodeName,
@@ -27,6 +69,7 @@ pub(crate) struct UserGroupConfig {
     pub(crate) dynamic_cache: PathBuf,
     pub(crate) refresh_interval: Duration,
     pub(crate) anonymous_user: Option<Arc<UserConfig>>,
+    pub(crate) live_task_revoke: UserRevokePolicy,
 }
 
 impl UserGroupConfig {
@@ -47,6 +90,7 @@ impl UserGroupConfig {
             dynamic_cache: PathBuf::default(),
             refresh_interval: DEFAULT_REFRESH_INTERVAL,
             anonymous_user: None,
+            live_task_revoke: UserRevokePolicy::default(),
         }
     }
 
@@ -59,6 +103,7 @@ impl UserGroupConfig {
             dynamic_cache: PathBuf::default(),
             refresh_interval: DEFAULT_REFRESH_INTERVAL,
             anonymous_user: None,
+            live_task_revoke: UserRevokePolicy::default(),
         }
     }
 
@@ -124,6 +169,11 @@ impl UserGroupConfig {
                     .context(format!("invalid duration value for key {k}"))?;
                 Ok(())
             }
+            "live_task_revoke" => {
+                self.live_task_revoke = UserRevokePolicy::parse(v)
+                    .context(format!("invalid user revoke policy value for key {k}"))?;
+                Ok(())
+            }
             "anonymous_user" => {
                 if let Yaml::Hash(map) = v {
                     let mut user = UserConfig::parse_yaml(map, self.position.as_ref())?;
@@ -138,3 +188,58 @@ impl UserGroupConfig {
         }
     }
 }
+
+impl YamlDump for UserGroupConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name);
+        if !self.static_users.is_empty() {
+            let mut users: Vec<_> = self.static_users.values().collect();
+            users.sort_by(|a, b| a.name().cmp(b.name()));
+            map.set("static_users", &users);
+        }
+        map.set("source", &self.dynamic_source);
+        if !self.dynamic_cache.as_os_str().is_empty() {
+            map.set("cache", &self.dynamic_cache);
+        }
+        map.set("refresh_interval", &self.refresh_interval)
+            .set("live_task_revoke", &self.live_task_revoke)
+            .set("anonymous_user", &self.anonymous_user);
+        map.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_revoke_policy() {
+        let v = Yaml::String("immediate".to_string());
+        assert_eq!(
+            UserRevokePolicy::parse(&v).unwrap(),
+            UserRevokePolicy::Immediate
+        );
+
+        let v = Yaml::String("finish".to_string());
+        assert_eq!(
+            UserRevokePolicy::parse(&v).unwrap(),
+            UserRevokePolicy::Finish
+        );
+
+        let v = Yaml::String("30s".to_string());
+        assert_eq!(
+            UserRevokePolicy::parse(&v).unwrap(),
+            UserRevokePolicy::Drain(Duration::from_secs(30))
+        );
+
+        let v = Yaml::Integer(0);
+        assert_eq!(
+            UserRevokePolicy::parse(&v).unwrap(),
+            UserRevokePolicy::Immediate
+        );
+
+        let v = Yaml::String("later".to_string());
+        assert!(UserRevokePolicy::parse(&v).is_err());
+    }
+}
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::path::Path;
 
 use anyhow::anyhow;
@@ -23,7 +24,7 @@ mod user;
 pub(crate) use user::UserConfig;
 
 mod group;
-pub(crate) use group::UserGroupConfig;
+pub(crate) use group::{UserGroupConfig, UserRevokePolicy};
 
 pub(crate) mod source;
 pub(crate) use source::UserDynamicSource;
@@ -40,6 +41,18 @@ pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     })
 }
 
+/// Parse all user group configs without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<UserGroupConfig>> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let config = load_user_group(map, position)?;
+        all.borrow_mut().push(config);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<UserGroupConfig> {
     let doc = g3_yaml::load_doc(position)?;
     if let Yaml::Hash(map) = doc {
@@ -7,6 +7,7 @@ use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::UserSiteConfig;
 
@@ -95,3 +96,39 @@ impl UserSiteConfig {
         }
     }
 }
+
+impl YamlDump for UserSiteConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut exact_match: Vec<Yaml> = self
+            .exact_match_domain
+            .iter()
+            .map(|d| d.dump_yaml(redact))
+            .collect();
+        exact_match.extend(
+            self.exact_match_ipaddr
+                .iter()
+                .map(|ip| ip.dump_yaml(redact)),
+        );
+
+        let mut map = YamlMapDumper::new(redact);
+        map.set("id", &self.id);
+        if !exact_match.is_empty() {
+            map.set_yaml("exact_match", Yaml::Array(exact_match));
+        }
+        if !self.subnet_match_ipaddr.is_empty() {
+            map.set("subnet_match", &self.subnet_match_ipaddr);
+        }
+        if !self.child_match_domain.is_empty() {
+            map.set("child_match", &self.child_match_domain);
+        }
+        map.set("emit_stats", &self.emit_stats)
+            .set("duration_stats", &self.duration_stats)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("tls_client", &self.tls_client)
+            .set(
+                "http_rsp_header_recv_timeout",
+                &self.http_rsp_hdr_recv_timeout,
+            );
+        map.build()
+    }
+}
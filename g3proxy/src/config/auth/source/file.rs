@@ -11,6 +11,7 @@ use url::Url;
 use yaml_rust::{Yaml, yaml};
 
 use g3_types::fs::ConfigFileFormat;
+use g3_yaml::dump::YamlMapDumper;
 
 use crate::config::auth::UserConfig;
 
@@ -76,6 +77,11 @@ impl UserDynamicFileSource {
         }
     }
 
+    pub(super) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set(CONFIG_KEY_SOURCE_PATH, &self.path)
+            .set("format", &self.format);
+    }
+
     pub(crate) async fn fetch_records(&self) -> anyhow::Result<Vec<UserConfig>> {
         // TODO limit the read size
         let contents = tokio::fs::read_to_string(&self.path)
@@ -11,6 +11,7 @@ use log::warn;
 use yaml_rust::{Yaml, yaml};
 
 use g3_types::fs::ConfigFileFormat;
+use g3_yaml::dump::YamlMapDumper;
 
 use super::file::UserDynamicFileSource;
 use crate::config::auth::UserConfig;
@@ -84,6 +85,16 @@ impl UserDynamicLuaSource {
         }
     }
 
+    pub(super) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("fetch_script", &self.fetch_script)
+            .set("fetch_timeout", &self.fetch_timeout)
+            .set("report_script", &self.report_script)
+            .set("report_timeout", &self.report_timeout);
+        if !self.cache_file.as_os_str().is_empty() {
+            map.set("cache_file", &self.cache_file);
+        }
+    }
+
     fn check(&self) -> anyhow::Result<()> {
         if self.fetch_script.as_os_str().is_empty() {
             return Err(anyhow!("no fetch script is set"));
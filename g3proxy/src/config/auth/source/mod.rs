@@ -10,6 +10,8 @@ use anyhow::anyhow;
 use url::Url;
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 pub(crate) mod cache;
 pub(crate) mod file;
 
@@ -70,3 +72,26 @@ impl UserDynamicSource {
         }
     }
 }
+
+impl YamlDump for UserDynamicSource {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        match self {
+            UserDynamicSource::File(source) => {
+                map.set(CONFIG_KEY_SOURCE_TYPE, "file");
+                source.dump_yaml_kv(&mut map);
+            }
+            #[cfg(feature = "lua")]
+            UserDynamicSource::Lua(source) => {
+                map.set(CONFIG_KEY_SOURCE_TYPE, "lua");
+                source.dump_yaml_kv(&mut map);
+            }
+            #[cfg(feature = "python")]
+            UserDynamicSource::Python(source) => {
+                map.set(CONFIG_KEY_SOURCE_TYPE, "python");
+                source.dump_yaml_kv(&mut map);
+            }
+        }
+        map.build()
+    }
+}
@@ -11,6 +11,7 @@ use log::warn;
 use yaml_rust::{Yaml, yaml};
 
 use g3_types::fs::ConfigFileFormat;
+use g3_yaml::dump::YamlMapDumper;
 
 use super::file::UserDynamicFileSource;
 use crate::config::auth::UserConfig;
@@ -74,6 +75,15 @@ impl UserDynamicPythonSource {
         }
     }
 
+    pub(super) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("script", &self.script_file)
+            .set("fetch_timeout", &self.fetch_timeout)
+            .set("report_timeout", &self.report_timeout);
+        if !self.cache_file.as_os_str().is_empty() {
+            map.set("cache_file", &self.cache_file);
+        }
+    }
+
     fn check(&self) -> anyhow::Result<()> {
         if self.script_file.as_os_str().is_empty() {
             return Err(anyhow!("no script is set"));
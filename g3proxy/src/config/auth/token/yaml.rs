@@ -8,6 +8,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::auth::FastHashedPassPhrase;
 use g3_xcrypt::XCryptHash;
+use g3_yaml::dump::YamlMapDumper;
 
 use super::{CONFIG_KEY_TYPE, PasswordToken};
 
@@ -87,4 +88,26 @@ impl PasswordToken {
             _ => Err(anyhow!("invalid value type")),
         }
     }
+
+    pub(crate) fn dump_yaml_kv(&self, key: &str, map: &mut YamlMapDumper) {
+        match self {
+            PasswordToken::Forbidden => {}
+            PasswordToken::SkipVerify => {
+                map.set_null(key);
+            }
+            PasswordToken::FastHash(pass) => {
+                let mut hash_map = YamlMapDumper::new(false);
+                hash_map
+                    .set(CONFIG_KEY_TYPE, "fast_hash")
+                    .set(CONFIG_KEY_SALT, &pass.salt_hex());
+                for (name, value) in pass.hash_hex_iter() {
+                    hash_map.set(name, &value);
+                }
+                map.set_secret(key, &hash_map.build());
+            }
+            PasswordToken::XCrypt(hash) => {
+                map.set_secret(key, &hash.to_string());
+            }
+        }
+    }
 }
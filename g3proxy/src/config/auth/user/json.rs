@@ -4,6 +4,7 @@
  */
 
 use std::str::FromStr;
+use std::sync::Arc;
 
 use ahash::AHashMap;
 use anyhow::{Context, anyhow};
@@ -13,6 +14,7 @@ use serde_json::{Map, Value};
 use g3_types::metrics::NodeName;
 
 use super::{PasswordToken, UserConfig, UserSiteConfig};
+use crate::config::http_header::HttpHeaderRules;
 use crate::escape::EgressPathSelection;
 
 impl UserConfig {
@@ -190,6 +192,14 @@ impl UserConfig {
                 self.http_user_agent_filter = Some(filter);
                 Ok(())
             }
+            "http_header_rules" => {
+                let rules = HttpHeaderRules::parse_json(v)
+                    .context(format!("invalid http header rules value for key {k}"))?;
+                if !rules.is_empty() {
+                    self.http_header_rules = Some(Arc::new(rules));
+                }
+                Ok(())
+            }
             "resolve_strategy" => {
                 let strategy = g3_json::value::as_resolve_strategy(v)
                     .context(format!("invalid resolve strategy value for key {k}"))?;
@@ -220,6 +230,18 @@ impl UserConfig {
                 self.task_idle_max_count = Some(count);
                 Ok(())
             }
+            "websocket_message_max_size" => {
+                let size = g3_json::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.websocket_message_max_size = Some(size);
+                Ok(())
+            }
+            "websocket_message_max_count" => {
+                let count = g3_json::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                self.websocket_message_max_count = Some(count);
+                Ok(())
+            }
             "socks_use_udp_associate" => {
                 self.socks_use_udp_associate = g3_json::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
@@ -26,6 +26,7 @@ use g3_types::net::{
 use g3_types::resolve::{ResolveRedirectionBuilder, ResolveStrategy};
 
 use super::{PasswordToken, UserAuditConfig, UserSiteConfig};
+use crate::config::http_header::HttpHeaderRules;
 use crate::escape::EgressPathSelection;
 
 mod json;
@@ -62,9 +63,12 @@ pub(crate) struct UserConfig {
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) http_user_agent_filter: Option<AclUserAgentRule>,
+    pub(crate) http_header_rules: Option<Arc<HttpHeaderRules>>,
     pub(crate) resolve_strategy: Option<ResolveStrategy>,
     pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
     pub(crate) task_idle_max_count: Option<usize>,
+    pub(crate) websocket_message_max_size: Option<usize>,
+    pub(crate) websocket_message_max_count: Option<usize>,
     pub(crate) socks_use_udp_associate: bool,
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
     pub(crate) explicit_sites: BTreeMap<NodeName, Arc<UserSiteConfig>>,
@@ -102,9 +106,12 @@ impl Default for UserConfig {
             dst_host_filter: None,
             dst_port_filter: None,
             http_user_agent_filter: None,
+            http_header_rules: None,
             resolve_strategy: None,
             resolve_redirection: None,
             task_idle_max_count: None,
+            websocket_message_max_size: None,
+            websocket_message_max_count: None,
             socks_use_udp_associate: false,
             egress_path_selection: None,
             explicit_sites: BTreeMap::new(),
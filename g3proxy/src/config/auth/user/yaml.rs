@@ -3,7 +3,9 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use ahash::AHashMap;
 use anyhow::{Context, anyhow};
@@ -11,8 +13,10 @@ use log::warn;
 use yaml_rust::{Yaml, yaml};
 
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{PasswordToken, UserConfig, UserSiteConfig};
+use crate::config::http_header::HttpHeaderRules;
 use crate::escape::EgressPathSelection;
 
 impl UserConfig {
@@ -196,6 +200,14 @@ impl UserConfig {
                 self.http_user_agent_filter = Some(filter);
                 Ok(())
             }
+            "http_header_rules" => {
+                let rules = HttpHeaderRules::parse_yaml(v)
+                    .context(format!("invalid http header rules value for key {k}"))?;
+                if !rules.is_empty() {
+                    self.http_header_rules = Some(Arc::new(rules));
+                }
+                Ok(())
+            }
             "resolve_strategy" => {
                 let strategy = g3_yaml::value::as_resolve_strategy(v)
                     .context(format!("invalid resolve strategy value for key {k}"))?;
@@ -226,6 +238,18 @@ impl UserConfig {
                 self.task_idle_max_count = Some(count);
                 Ok(())
             }
+            "websocket_message_max_size" => {
+                let size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.websocket_message_max_size = Some(size);
+                Ok(())
+            }
+            "websocket_message_max_count" => {
+                let count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                self.websocket_message_max_count = Some(count);
+                Ok(())
+            }
             "socks_use_udp_associate" => {
                 self.socks_use_udp_associate = g3_yaml::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
@@ -278,3 +302,83 @@ impl UserConfig {
         }
     }
 }
+
+impl YamlDump for UserConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name);
+        self.password_token.dump_yaml_kv("token", &mut map);
+        map.set("expire", &self.expire_datetime)
+            .set("block_and_delay", &self.block_and_delay)
+            .set("tcp_connect", &self.tcp_connect)
+            .set("tcp_sock_speed_limit", &self.tcp_sock_speed_limit)
+            .set("udp_sock_speed_limit", &self.udp_sock_speed_limit)
+            .set(
+                "tcp_all_upload_speed_limit",
+                &self.tcp_all_upload_speed_limit,
+            )
+            .set(
+                "tcp_all_download_speed_limit",
+                &self.tcp_all_download_speed_limit,
+            )
+            .set(
+                "udp_all_upload_speed_limit",
+                &self.udp_all_upload_speed_limit,
+            )
+            .set(
+                "udp_all_download_speed_limit",
+                &self.udp_all_download_speed_limit,
+            )
+            .set("tcp_remote_keepalive", &self.tcp_remote_keepalive)
+            .set("tcp_remote_misc_opts", &self.tcp_remote_misc_opts)
+            .set("udp_remote_misc_opts", &self.udp_remote_misc_opts)
+            .set("tcp_client_misc_opts", &self.tcp_client_misc_opts)
+            .set("udp_client_misc_opts", &self.udp_client_misc_opts)
+            .set("http_upstream_keepalive", &self.http_upstream_keepalive)
+            .set(
+                "http_rsp_header_recv_timeout",
+                &self.http_rsp_hdr_recv_timeout,
+            )
+            .set("tcp_conn_rate_limit", &self.tcp_conn_rate_limit)
+            .set("request_rate_limit", &self.request_rate_limit)
+            .set("request_alive_max", &self.request_alive_max)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("proxy_request_filter", &self.proxy_request_filter)
+            .set("dst_host_filter_set", &self.dst_host_filter)
+            .set("dst_port_filter", &self.dst_port_filter)
+            .set("http_user_agent_filter", &self.http_user_agent_filter)
+            .set("http_header_rules", &self.http_header_rules)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("resolve_redirection", &self.resolve_redirection)
+            .set("log_rate_limit", &self.log_rate_limit)
+            .set("log_uri_max_chars", &self.log_uri_max_chars)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set(
+                "websocket_message_max_size",
+                &self.websocket_message_max_size,
+            )
+            .set(
+                "websocket_message_max_count",
+                &self.websocket_message_max_count,
+            )
+            .set("socks_use_udp_associate", &self.socks_use_udp_associate)
+            .set("audit", &self.audit);
+        if !self.explicit_sites.is_empty() {
+            let sites: Vec<_> = self.explicit_sites.values().collect();
+            map.set("explicit_sites", &sites);
+        }
+        match &self.egress_path_selection {
+            Some(EgressPathSelection::MatchId(id_map)) => {
+                let id_map: BTreeMap<_, _> = id_map.iter().collect();
+                map.set("egress_path_id_map", &id_map);
+            }
+            Some(EgressPathSelection::MatchValue(value_map)) => {
+                let value_map: BTreeMap<_, _> =
+                    value_map.iter().map(|(k, v)| (k, v.to_string())).collect();
+                map.set("egress_path_value_map", &value_map);
+            }
+            Some(EgressPathSelection::Index(_)) | None => {}
+        }
+        map.build()
+    }
+}
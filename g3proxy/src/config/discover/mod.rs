@@ -0,0 +1,100 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_discover::{
+    DiscoverConfig, DiscoverConfigDiffAction, FileDiscoverConfig, HostResolverDiscoverConfig,
+    SrvDiscoverConfig, StaticAddrDiscoverConfig,
+};
+use g3_macros::AnyConfig;
+use g3_types::metrics::NodeName;
+use g3_yaml::{HybridParser, YamlDocPosition};
+
+mod registry;
+pub(crate) use registry::{clear, get_all};
+
+const CONFIG_KEY_DISCOVER_TYPE: &str = "type";
+
+#[derive(Clone, Debug, AnyConfig)]
+#[def_fn(name, &NodeName)]
+#[def_fn(r#type, &'static str)]
+#[def_fn(position, Option<YamlDocPosition>)]
+pub(crate) enum AnyDiscoverConfig {
+    StaticAddr(StaticAddrDiscoverConfig),
+    HostResolver(HostResolverDiscoverConfig),
+    File(FileDiscoverConfig),
+    Srv(SrvDiscoverConfig),
+}
+
+impl AnyDiscoverConfig {
+    pub(crate) fn diff_action(&self, new: &Self) -> DiscoverConfigDiffAction {
+        match (self, new) {
+            (AnyDiscoverConfig::StaticAddr(old), AnyDiscoverConfig::StaticAddr(new)) => {
+                old.diff_action(new)
+            }
+            (AnyDiscoverConfig::HostResolver(old), AnyDiscoverConfig::HostResolver(new)) => {
+                old.diff_action(new)
+            }
+            (AnyDiscoverConfig::File(old), AnyDiscoverConfig::File(new)) => old.diff_action(new),
+            (AnyDiscoverConfig::Srv(old), AnyDiscoverConfig::Srv(new)) => old.diff_action(new),
+            _ => DiscoverConfigDiffAction::SpawnNew,
+        }
+    }
+}
+
+pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    parser.foreach_map(v, |map, position| {
+        let discover = load_discover(map, position)?;
+        registry::add(discover, false)?;
+        Ok(())
+    })?;
+    Ok(())
+}
+
+pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyDiscoverConfig> {
+    let doc = g3_yaml::load_doc(position)?;
+    if let Yaml::Hash(map) = doc {
+        let discover = load_discover(&map, Some(position.clone()))?;
+        registry::add(discover.clone(), true)?;
+        Ok(discover)
+    } else {
+        Err(anyhow!("yaml doc {position} is not a map"))
+    }
+}
+
+fn load_discover(
+    map: &yaml::Hash,
+    position: Option<YamlDocPosition>,
+) -> anyhow::Result<AnyDiscoverConfig> {
+    let discover_type = g3_yaml::hash_get_required_str(map, CONFIG_KEY_DISCOVER_TYPE)?;
+    match g3_yaml::key::normalize(discover_type).as_str() {
+        "static_addr" | "staticaddr" => {
+            let discover = StaticAddrDiscoverConfig::parse_yaml_conf(map, position)
+                .context("failed to load this StaticAddr discover")?;
+            Ok(AnyDiscoverConfig::StaticAddr(discover))
+        }
+        "host_resolver" | "hostresolver" => {
+            let discover = HostResolverDiscoverConfig::parse_yaml_conf(map, position)
+                .context("failed to load this HostResolver discover")?;
+            Ok(AnyDiscoverConfig::HostResolver(discover))
+        }
+        "file" => {
+            let discover = FileDiscoverConfig::parse_yaml_conf(map, position)
+                .context("failed to load this File discover")?;
+            Ok(AnyDiscoverConfig::File(discover))
+        }
+        "srv" | "dns_srv" | "dnssrv" => {
+            let discover = SrvDiscoverConfig::parse_yaml_conf(map, position)
+                .context("failed to load this Srv discover")?;
+            Ok(AnyDiscoverConfig::Srv(discover))
+        }
+        _ => Err(anyhow!("unsupported discover type {}", discover_type)),
+    }
+}
@@ -0,0 +1,446 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::anyhow;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::NodeName;
+use g3_yaml::dump::YamlDump;
+
+use super::audit::AuditorConfig;
+use super::auth::UserGroupConfig;
+use super::escaper::AnyEscaperConfig;
+use super::resolver::AnyResolverConfig;
+use super::server::AnyServerConfig;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ConfigKind {
+    UserGroup,
+    Resolver,
+    Auditor,
+    Escaper,
+    Server,
+}
+
+impl ConfigKind {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ConfigKind::UserGroup => "user-group",
+            ConfigKind::Resolver => "resolver",
+            ConfigKind::Auditor => "auditor",
+            ConfigKind::Escaper => "escaper",
+            ConfigKind::Server => "server",
+        }
+    }
+}
+
+pub(crate) enum ConfigChangeAction {
+    Added,
+    Removed,
+    /// with the top level keys that have been changed
+    Modified(Vec<String>),
+    /// will be reloaded as it depends on the other changed object
+    Dependent(ConfigKind, NodeName),
+}
+
+pub(crate) struct ConfigChange {
+    pub(crate) kind: ConfigKind,
+    pub(crate) name: NodeName,
+    pub(crate) action: ConfigChangeAction,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = self.kind.as_str();
+        let name = &self.name;
+        match &self.action {
+            ConfigChangeAction::Added => write!(f, "+ {kind} {name}"),
+            ConfigChangeAction::Removed => write!(f, "- {kind} {name}"),
+            ConfigChangeAction::Modified(keys) => {
+                write!(f, "~ {kind} {name}: {}", keys.join(", "))
+            }
+            ConfigChangeAction::Dependent(dep_kind, dep_name) => {
+                write!(
+                    f,
+                    "* {kind} {name}: depends on {} {dep_name}",
+                    dep_kind.as_str()
+                )
+            }
+        }
+    }
+}
+
+/// The unredacted dump of all configs, so changes of secret values can be detected
+type ConfigSnapshot = BTreeMap<(ConfigKind, NodeName), Yaml>;
+
+pub(crate) fn dump_user_group(name: &NodeName) -> anyhow::Result<String> {
+    let config =
+        crate::auth::get_config(name).ok_or_else(|| anyhow!("no user group named {name} found"))?;
+    g3_yaml::dump::emit_to_string(&config.dump_yaml(true))
+}
+
+pub(crate) fn dump_resolver(name: &NodeName) -> anyhow::Result<String> {
+    let config = crate::resolve::get_config(name)
+        .ok_or_else(|| anyhow!("no resolver named {name} found"))?;
+    g3_yaml::dump::emit_to_string(&config.dump_yaml(true))
+}
+
+pub(crate) fn dump_auditor(name: &NodeName) -> anyhow::Result<String> {
+    let config =
+        crate::audit::get_config(name).ok_or_else(|| anyhow!("no auditor named {name} found"))?;
+    g3_yaml::dump::emit_to_string(&config.dump_yaml(true))
+}
+
+pub(crate) fn dump_escaper(name: &NodeName) -> anyhow::Result<String> {
+    let config =
+        crate::escape::get_config(name).ok_or_else(|| anyhow!("no escaper named {name} found"))?;
+    g3_yaml::dump::emit_to_string(&config.dump_yaml(true))
+}
+
+pub(crate) fn dump_server(name: &NodeName) -> anyhow::Result<String> {
+    let config =
+        crate::serve::get_config(name).ok_or_else(|| anyhow!("no server named {name} found"))?;
+    g3_yaml::dump::emit_to_string(&config.dump_yaml(true))
+}
+
+fn running_snapshot() -> ConfigSnapshot {
+    let mut snapshot = ConfigSnapshot::new();
+    for name in crate::auth::get_names() {
+        if let Some(c) = crate::auth::get_config(&name) {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::UserGroup, name), doc);
+        }
+    }
+    for name in crate::resolve::get_names() {
+        if let Some(c) = crate::resolve::get_config(&name) {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Resolver, name), doc);
+        }
+    }
+    for name in crate::audit::get_names() {
+        if let Some(c) = crate::audit::get_config(&name) {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Auditor, name), doc);
+        }
+    }
+    for name in crate::escape::get_names() {
+        if let Some(c) = crate::escape::get_config(&name) {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Escaper, name), doc);
+        }
+    }
+    for name in crate::serve::get_names() {
+        if let Some(c) = crate::serve::get_config(&name) {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Server, name), doc);
+        }
+    }
+    snapshot
+}
+
+/// All configs parsed from the config files on disk, which have not been applied
+#[derive(Default)]
+pub(super) struct DiskConfig {
+    pub(super) user_group: BTreeMap<NodeName, UserGroupConfig>,
+    pub(super) resolver: BTreeMap<NodeName, AnyResolverConfig>,
+    pub(super) auditor: BTreeMap<NodeName, AuditorConfig>,
+    pub(super) escaper: BTreeMap<NodeName, AnyEscaperConfig>,
+    pub(super) server: BTreeMap<NodeName, AnyServerConfig>,
+}
+
+fn insert_unique<T>(
+    map: &mut BTreeMap<NodeName, T>,
+    kind: ConfigKind,
+    name: &NodeName,
+    config: T,
+) -> anyhow::Result<()> {
+    if map.insert(name.clone(), config).is_some() {
+        Err(anyhow!("{} with name {name} already exists", kind.as_str()))
+    } else {
+        Ok(())
+    }
+}
+
+impl DiskConfig {
+    /// Parse the config files on disk.
+    ///
+    /// This will do file IO, so it should be called in a blocking thread.
+    pub(super) fn load() -> anyhow::Result<Self> {
+        let conf_file =
+            g3_daemon::opts::config_file().ok_or_else(|| anyhow!("no config file set"))?;
+        let conf_dir = g3_daemon::opts::config_dir()
+            .ok_or_else(|| anyhow!("no valid config dir has been set"))?;
+
+        let disk = RefCell::new(DiskConfig::default());
+        g3_yaml::foreach_doc(conf_file, |_, doc| match doc {
+            Yaml::Hash(map) => g3_yaml::foreach_kv(map, |k, v| {
+                let mut disk = disk.borrow_mut();
+                match g3_yaml::key::normalize(k).as_str() {
+                    "user" | "user_group" => {
+                        for c in super::auth::parse_all(v, conf_dir)? {
+                            let name = c.name().clone();
+                            insert_unique(&mut disk.user_group, ConfigKind::UserGroup, &name, c)?;
+                        }
+                    }
+                    "resolver" => {
+                        for c in super::resolver::parse_all(v, conf_dir)? {
+                            let name = c.name().clone();
+                            insert_unique(&mut disk.resolver, ConfigKind::Resolver, &name, c)?;
+                        }
+                    }
+                    "auditor" => {
+                        for c in super::audit::parse_all(v, conf_dir)? {
+                            let name = c.name().clone();
+                            insert_unique(&mut disk.auditor, ConfigKind::Auditor, &name, c)?;
+                        }
+                    }
+                    "escaper" => {
+                        for c in super::escaper::parse_all(v, conf_dir)? {
+                            let name = c.name().clone();
+                            insert_unique(&mut disk.escaper, ConfigKind::Escaper, &name, c)?;
+                        }
+                    }
+                    "server" => {
+                        for c in super::server::parse_all(v, conf_dir)? {
+                            let name = c.name().clone();
+                            insert_unique(&mut disk.server, ConfigKind::Server, &name, c)?;
+                        }
+                    }
+                    _ => {}
+                }
+                Ok(())
+            }),
+            _ => Err(anyhow!("yaml doc root should be hash")),
+        })?;
+
+        Ok(disk.into_inner())
+    }
+
+    fn snapshot(&self) -> ConfigSnapshot {
+        let mut snapshot = ConfigSnapshot::new();
+        for (name, c) in &self.user_group {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::UserGroup, name.clone()), doc);
+        }
+        for (name, c) in &self.resolver {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Resolver, name.clone()), doc);
+        }
+        for (name, c) in &self.auditor {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Auditor, name.clone()), doc);
+        }
+        for (name, c) in &self.escaper {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Escaper, name.clone()), doc);
+        }
+        for (name, c) in &self.server {
+            let doc = c.dump_yaml(false);
+            snapshot.insert((ConfigKind::Server, name.clone()), doc);
+        }
+        snapshot
+    }
+}
+
+fn changed_keys(old: &Yaml, new: &Yaml) -> Vec<String> {
+    let (Yaml::Hash(old), Yaml::Hash(new)) = (old, new) else {
+        return Vec::new();
+    };
+
+    let mut keys = Vec::new();
+    for (k, v) in old {
+        if new.get(k) != Some(v) {
+            keys.push(k.as_str().unwrap_or_default().to_string());
+        }
+    }
+    for k in new.keys() {
+        if !old.contains_key(k) {
+            keys.push(k.as_str().unwrap_or_default().to_string());
+        }
+    }
+    keys.sort();
+    keys
+}
+
+/// Compare the running config with the config files on disk.
+///
+/// This will do file IO, so it should be called in a blocking thread.
+pub(crate) fn diff_with_disk() -> anyhow::Result<Vec<ConfigChange>> {
+    let disk = DiskConfig::load()?;
+    Ok(diff_with_running(&disk))
+}
+
+pub(super) fn diff_with_running(disk: &DiskConfig) -> Vec<ConfigChange> {
+    diff_snapshot(running_snapshot(), disk.snapshot())
+}
+
+fn diff_snapshot(
+    mut old_snapshot: ConfigSnapshot,
+    new_snapshot: ConfigSnapshot,
+) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    for ((kind, name), new) in new_snapshot {
+        let action = match old_snapshot.remove(&(kind, name.clone())) {
+            Some(old) => {
+                if old == new {
+                    continue;
+                }
+                ConfigChangeAction::Modified(changed_keys(&old, &new))
+            }
+            None => ConfigChangeAction::Added,
+        };
+        changes.push(ConfigChange { kind, name, action });
+    }
+    for (kind, name) in old_snapshot.into_keys() {
+        changes.push(ConfigChange {
+            kind,
+            name,
+            action: ConfigChangeAction::Removed,
+        });
+    }
+    changes.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::str::FromStr;
+    use yaml_rust::YamlLoader;
+
+    fn load_yaml(s: &str) -> Yaml {
+        YamlLoader::load_from_str(s).unwrap().pop().unwrap()
+    }
+
+    fn reload_escaper(doc: &Yaml) -> Yaml {
+        let s = g3_yaml::dump::emit_to_string(doc).unwrap();
+        let v = load_yaml(&format!("- {}", s.replace('\n', "\n  ")));
+        let escapers = super::super::escaper::parse_all(&v, Path::new("/")).unwrap();
+        escapers[0].dump_yaml(false)
+    }
+
+    #[test]
+    fn escaper_round_trip() {
+        let v = load_yaml(
+            r#"
+            - name: parent
+              type: proxy_http
+              proxy_addr: 127.0.0.1:8080
+              proxy_username: user
+              proxy_password: pass
+              peer_negotiation_timeout: 20s
+            "#,
+        );
+        let escapers = super::super::escaper::parse_all(&v, Path::new("/")).unwrap();
+        let doc = escapers[0].dump_yaml(false);
+        let Yaml::Hash(map) = &doc else {
+            panic!("not a map");
+        };
+        assert_eq!(
+            map.get(&Yaml::String("proxy_addr".to_string())),
+            Some(&load_yaml("[{addr: 127.0.0.1:8080, weight: 1.0}]"))
+        );
+        assert_eq!(reload_escaper(&doc), doc);
+    }
+
+    #[test]
+    fn redact_secrets() {
+        let v = load_yaml(
+            r#"
+            - name: parent
+              type: proxy_socks5
+              proxy_addr: 127.0.0.1:1080
+              proxy_username: user
+              proxy_password: secret-pass
+            "#,
+        );
+        let escapers = super::super::escaper::parse_all(&v, Path::new("/")).unwrap();
+        let redacted = g3_yaml::dump::emit_to_string(&escapers[0].dump_yaml(true)).unwrap();
+        assert!(redacted.contains(g3_yaml::dump::REDACTED_VALUE));
+        assert!(!redacted.contains("secret-pass"));
+        assert!(redacted.contains("user"));
+        let plain = g3_yaml::dump::emit_to_string(&escapers[0].dump_yaml(false)).unwrap();
+        assert!(plain.contains("secret-pass"));
+
+        let v = load_yaml(
+            r#"
+            - name: default
+              static_users:
+                - name: root
+                  token: "$1$DDiGYGte$K/SAC4VvllDonGcP1EfaY1"
+            "#,
+        );
+        let groups = super::super::auth::parse_all(&v, Path::new("/")).unwrap();
+        let redacted = g3_yaml::dump::emit_to_string(&groups[0].dump_yaml(true)).unwrap();
+        assert!(redacted.contains("root"));
+        assert!(!redacted.contains("K/SAC4VvllDonGcP1EfaY1"));
+        let plain = g3_yaml::dump::emit_to_string(&groups[0].dump_yaml(false)).unwrap();
+        assert!(plain.contains("K/SAC4VvllDonGcP1EfaY1"));
+    }
+
+    #[test]
+    fn server_keys() {
+        let v = load_yaml(
+            r#"
+            - name: http
+              type: http_proxy
+              escaper: default
+              listen: 127.0.0.1:8080
+              req_header_max_size: 64K
+            "#,
+        );
+        let servers = super::super::server::parse_all(&v, Path::new("/")).unwrap();
+        let Yaml::Hash(map) = servers[0].dump_yaml(true) else {
+            panic!("not a map");
+        };
+        let get = |k: &str| map.get(&Yaml::String(k.to_string())).cloned();
+        assert_eq!(get("name"), Some(Yaml::String("http".to_string())));
+        assert_eq!(get("escaper"), Some(Yaml::String("default".to_string())));
+        assert_eq!(get("req_header_max_size"), Some(Yaml::Integer(64000)));
+        assert!(get("position").is_none());
+    }
+
+    #[test]
+    fn changed_keys_of_map() {
+        let old = load_yaml("{a: 1, b: 2, c: 3}");
+        let new = load_yaml("{a: 1, b: 4, d: 5}");
+        assert_eq!(changed_keys(&old, &new), vec!["b", "c", "d"]);
+        assert!(changed_keys(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn diff_snapshots() {
+        let node = |s: &str| NodeName::from_str(s).unwrap();
+
+        let mut old = ConfigSnapshot::new();
+        old.insert(
+            (ConfigKind::Escaper, node("a")),
+            load_yaml("{name: a, x: 1}"),
+        );
+        old.insert((ConfigKind::Escaper, node("b")), load_yaml("{name: b}"));
+        old.insert((ConfigKind::Server, node("c")), load_yaml("{name: c}"));
+        let mut new = ConfigSnapshot::new();
+        new.insert(
+            (ConfigKind::Escaper, node("a")),
+            load_yaml("{name: a, x: 2}"),
+        );
+        new.insert((ConfigKind::Server, node("c")), load_yaml("{name: c}"));
+        new.insert((ConfigKind::Resolver, node("d")), load_yaml("{name: d}"));
+
+        let changes = diff_snapshot(old, new)
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec!["+ resolver d", "~ escaper a: x", "- escaper b"]
+        );
+    }
+}
@@ -8,6 +8,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{EscaperConfig, EscaperConfigDiffAction};
 use crate::config::escaper::AnyEscaperConfig;
@@ -92,6 +93,10 @@ impl EscaperConfig for ComplyAuditEscaperConfig {
         Default::default()
     }
 
+    fn auditor(&self) -> &NodeName {
+        &self.auditor
+    }
+
     fn diff_action(&self, new: &AnyEscaperConfig) -> EscaperConfigDiffAction {
         let AnyEscaperConfig::ComplyAudit(new) = new else {
             return EscaperConfigDiffAction::SpawnNew;
@@ -104,3 +109,14 @@ impl EscaperConfig for ComplyAuditEscaperConfig {
         }
     }
 }
+
+impl YamlDump for ComplyAuditEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("next", &self.next)
+            .set("auditor", &self.auditor);
+        map.build()
+    }
+}
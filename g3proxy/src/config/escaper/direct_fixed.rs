@@ -5,6 +5,7 @@
 
 use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, anyhow};
 use ascii::AsciiString;
@@ -26,6 +27,7 @@ use g3_types::net::{
 };
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
 
@@ -58,6 +60,8 @@ pub(crate) struct DirectFixedEscaperConfig {
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) udp_misc_opts: UdpMiscSockOpts,
     pub(crate) enable_path_selection: bool,
+    pub(crate) enable_https_rr: bool,
+    pub(crate) https_rr_query_timeout: Duration,
     pub(crate) use_proxy_protocol: Option<ProxyProtocolVersion>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
@@ -90,6 +94,8 @@ impl DirectFixedEscaperConfig {
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
             enable_path_selection: false,
+            enable_https_rr: false,
+            https_rr_query_timeout: Duration::from_millis(100),
             use_proxy_protocol: None,
             extra_metrics_tags: None,
         }
@@ -165,6 +171,15 @@ impl DirectFixedEscaperConfig {
                 self.enable_path_selection = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "enable_https_rr" => {
+                self.enable_https_rr = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "https_rr_query_timeout" => {
+                self.https_rr_query_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
             "egress_network_filter" | "egress_net_filter" => {
                 self.egress_net_filter = g3_yaml::value::acl::as_egress_network_rule_builder(v)
                     .context(format!("invalid network acl rule value for key {k}"))?;
@@ -298,3 +313,43 @@ impl EscaperConfig for DirectFixedEscaperConfig {
         self.shared_logger.as_ref().map(|s| s.as_str())
     }
 }
+
+impl YamlDump for DirectFixedEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags);
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("bind_interface", &self.bind_interface);
+        if !self.bind4.is_empty() || !self.bind6.is_empty() {
+            let bind_ip: Vec<IpAddr> = self.bind4.iter().chain(&self.bind6).copied().collect();
+            map.set("bind_ip", &bind_ip);
+        }
+        map.set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("resolve_redirection", &self.resolve_redirection)
+            .set("enable_path_selection", &self.enable_path_selection)
+            .set("enable_https_rr", &self.enable_https_rr)
+            .set("https_rr_query_timeout", &self.https_rr_query_timeout)
+            .set("egress_network_filter", &self.egress_net_filter)
+            .set("tcp_sock_speed_limit", &self.general.tcp_sock_speed_limit)
+            .set("udp_sock_speed_limit", &self.general.udp_sock_speed_limit)
+            .set("tcp_keepalive", &self.tcp_keepalive)
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set("no_ipv4", &self.no_ipv4)
+            .set("no_ipv6", &self.no_ipv6)
+            .set("tcp_connect", &self.general.tcp_connect)
+            .set("happy_eyeballs", &self.happy_eyeballs)
+            .set("use_proxy_protocol", &self.use_proxy_protocol);
+        map.build()
+    }
+}
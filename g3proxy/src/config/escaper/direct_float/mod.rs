@@ -5,6 +5,7 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, anyhow};
 use ascii::AsciiString;
@@ -16,6 +17,7 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{HappyEyeballsConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts};
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
 
@@ -42,6 +44,8 @@ pub(crate) struct DirectFloatEscaperConfig {
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) udp_misc_opts: UdpMiscSockOpts,
+    pub(crate) enable_https_rr: bool,
+    pub(crate) https_rr_query_timeout: Duration,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
 
@@ -64,6 +68,8 @@ impl DirectFloatEscaperConfig {
             tcp_keepalive: TcpKeepAliveConfig::default_enabled(),
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
+            enable_https_rr: false,
+            https_rr_query_timeout: Duration::from_millis(100),
             extra_metrics_tags: None,
         }
     }
@@ -112,6 +118,15 @@ impl DirectFloatEscaperConfig {
                 self.resolve_redirection = Some(redirect);
                 Ok(())
             }
+            "enable_https_rr" => {
+                self.enable_https_rr = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "https_rr_query_timeout" => {
+                self.https_rr_query_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
             "egress_network_filter" | "egress_net_filter" => {
                 self.egress_net_filter = g3_yaml::value::acl::as_egress_network_rule_builder(v)
                     .context(format!("invalid network acl rule value for key {k}"))?;
@@ -260,3 +275,31 @@ impl EscaperConfig for DirectFloatEscaperConfig {
         self.shared_logger.as_ref().map(|s| s.as_str())
     }
 }
+
+impl YamlDump for DirectFloatEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("resolve_redirection", &self.resolve_redirection)
+            .set("enable_https_rr", &self.enable_https_rr)
+            .set("https_rr_query_timeout", &self.https_rr_query_timeout)
+            .set("egress_network_filter", &self.egress_net_filter)
+            .set("tcp_sock_speed_limit", &self.general.tcp_sock_speed_limit)
+            .set("udp_sock_speed_limit", &self.general.udp_sock_speed_limit)
+            .set("no_ipv4", &self.no_ipv4)
+            .set("no_ipv6", &self.no_ipv6)
+            .set("cache_ipv4", &self.cache_ipv4)
+            .set("cache_ipv6", &self.cache_ipv6)
+            .set("tcp_connect", &self.general.tcp_connect)
+            .set("happy_eyeballs", &self.happy_eyeballs)
+            .set("tcp_keepalive", &self.tcp_keepalive)
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("udp_misc_opts", &self.udp_misc_opts);
+        map.build()
+    }
+}
@@ -10,6 +10,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{EscaperConfig, EscaperConfigDiffAction};
 use crate::config::escaper::AnyEscaperConfig;
@@ -98,3 +99,13 @@ impl EscaperConfig for DummyDenyEscaperConfig {
         EscaperConfigDiffAction::NoAction
     }
 }
+
+impl YamlDump for DummyDenyEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, &self.custom_type)
+            .set("extra_metrics_tags", &self.extra_metrics_tags);
+        map.build()
+    }
+}
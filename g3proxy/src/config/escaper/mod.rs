@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::collections::BTreeSet;
 use std::path::Path;
 use std::sync::Arc;
@@ -15,6 +16,7 @@ use g3_daemon::config::TopoMap;
 use g3_macros::AnyConfig;
 use g3_types::metrics::NodeName;
 use g3_types::net::{TcpConnectConfig, TcpSockSpeedLimitConfig, UdpSockSpeedLimitConfig};
+use g3_yaml::dump::YamlDump;
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 pub(crate) mod comply_audit;
@@ -25,6 +27,8 @@ pub(crate) mod dummy_deny;
 pub(crate) mod proxy_float;
 pub(crate) mod proxy_http;
 pub(crate) mod proxy_https;
+#[cfg(feature = "quic")]
+pub(crate) mod proxy_masque;
 pub(crate) mod proxy_socks5;
 pub(crate) mod proxy_socks5s;
 pub(crate) mod route_client;
@@ -57,6 +61,9 @@ pub(crate) trait EscaperConfig {
     fn position(&self) -> Option<YamlDocPosition>;
     fn r#type(&self) -> &str;
     fn resolver(&self) -> &NodeName;
+    fn auditor(&self) -> &NodeName {
+        Default::default()
+    }
 
     fn diff_action(&self, new: &AnyEscaperConfig) -> EscaperConfigDiffAction;
 
@@ -75,7 +82,7 @@ pub(crate) trait EscaperConfig {
     }
 }
 
-#[derive(Clone, Default, Eq, PartialEq)]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub(crate) struct GeneralEscaperConfig {
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
     pub(crate) udp_sock_speed_limit: UdpSockSpeedLimitConfig,
@@ -85,9 +92,12 @@ pub(crate) struct GeneralEscaperConfig {
 #[derive(Clone, AnyConfig)]
 #[def_fn(name, &NodeName)]
 #[def_fn(position, Option<YamlDocPosition>)]
+#[def_fn(r#type, &str)]
 #[def_fn(dependent_escaper, Option<BTreeSet<NodeName>>)]
 #[def_fn(resolver, &NodeName)]
+#[def_fn(auditor, &NodeName)]
 #[def_fn(diff_action, &Self, EscaperConfigDiffAction)]
+#[def_fn(dump_yaml, bool, Yaml)]
 pub(crate) enum AnyEscaperConfig {
     ComplyAudit(comply_audit::ComplyAuditEscaperConfig),
     DirectFixed(direct_fixed::DirectFixedEscaperConfig),
@@ -97,6 +107,8 @@ pub(crate) enum AnyEscaperConfig {
     ProxyFloat(proxy_float::ProxyFloatEscaperConfig),
     ProxyHttp(proxy_http::ProxyHttpEscaperConfig),
     ProxyHttps(proxy_https::ProxyHttpsEscaperConfig),
+    #[cfg(feature = "quic")]
+    ProxyMasque(proxy_masque::ProxyMasqueEscaperConfig),
     ProxySocks5(proxy_socks5::ProxySocks5EscaperConfig),
     ProxySocks5s(proxy_socks5s::ProxySocks5sEscaperConfig),
     RouteFailover(route_failover::RouteFailoverEscaperConfig),
@@ -127,6 +139,18 @@ pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse all escaper configs without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AnyEscaperConfig>> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let config = load_escaper(map, position)?;
+        all.borrow_mut().push(config);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyEscaperConfig> {
     let doc = g3_yaml::load_doc(position)?;
     if let Yaml::Hash(map) = doc {
@@ -183,6 +207,11 @@ fn load_escaper(
             let config = proxy_https::ProxyHttpsEscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::ProxyHttps(config))
         }
+        #[cfg(feature = "quic")]
+        "proxy_masque" | "proxymasque" => {
+            let config = proxy_masque::ProxyMasqueEscaperConfig::parse(map, position)?;
+            Ok(AnyEscaperConfig::ProxyMasque(config))
+        }
         "proxy_socks5" | "proxysocks5" => {
             let config = proxy_socks5::ProxySocks5EscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::ProxySocks5(config))
@@ -27,6 +27,7 @@ use g3_types::net::{
     OpensslClientConfigBuilder, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts,
 };
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction};
 
@@ -264,3 +265,37 @@ impl EscaperConfig for ProxyFloatEscaperConfig {
         None
     }
 }
+
+impl YamlDump for ProxyFloatEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags);
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("bind_interface", &self.bind_interface);
+        map.set("bind_ipv4", &self.bind_v4)
+            .set("bind_ipv6", &self.bind_v6)
+            .set("tls_client", &self.tls_config)
+            .set("source", &self.source)
+            .set("cache", &self.cache_file)
+            .set("refresh_interval", &self.refresh_interval)
+            .set("tcp_connect_timeout", &self.tcp_connect_timeout)
+            .set("tcp_keepalive", &self.tcp_keepalive)
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set(
+                "expire_guard_duration",
+                &self.expire_guard_duration.to_std().ok(),
+            )
+            .set("peer_negotiation_timeout", &self.peer_negotiation_timeout);
+        map.build()
+    }
+}
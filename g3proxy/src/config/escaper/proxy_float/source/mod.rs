@@ -8,6 +8,7 @@ use url::Url;
 use yaml_rust::Yaml;
 
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 pub(crate) mod redis;
 
@@ -58,3 +59,19 @@ impl ProxyFloatSource {
         }
     }
 }
+
+impl YamlDump for ProxyFloatSource {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        match self {
+            ProxyFloatSource::Passive => {
+                map.set(CONFIG_KEY_SOURCE_TYPE, "passive");
+            }
+            ProxyFloatSource::Redis(source) => {
+                map.set(CONFIG_KEY_SOURCE_TYPE, "redis");
+                source.dump_yaml_kv(&mut map);
+            }
+        }
+        map.build()
+    }
+}
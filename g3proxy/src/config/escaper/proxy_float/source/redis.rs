@@ -12,6 +12,7 @@ use yaml_rust::{Yaml, yaml};
 use g3_redis_client::RedisClientConfigBuilder;
 use g3_types::net::UpstreamAddr;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::YamlMapDumper;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub(crate) struct ProxyFloatRedisSource {
@@ -78,6 +79,11 @@ impl ProxyFloatRedisSource {
         }
     }
 
+    pub(super) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        self.client_builder.dump_yaml_kv(map);
+        map.set("sets_key", &self.sets_key);
+    }
+
     fn check(&self) -> anyhow::Result<()> {
         if self.sets_key.is_empty() {
             return Err(anyhow!("no sets name set"));
@@ -24,11 +24,13 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 ))]
 use g3_types::net::Interface;
 use g3_types::net::{
-    HappyEyeballsConfig, Host, HttpForwardCapability, OpensslClientConfigBuilder,
-    ProxyProtocolVersion, TcpKeepAliveConfig, TcpMiscSockOpts, WeightedUpstreamAddr,
+    HappyEyeballsConfig, Host, Http2ConnectPoolConfig, HttpForwardCapability,
+    OpensslClientConfigBuilder, ProxyProtocolVersion, TcpKeepAliveConfig, TcpMiscSockOpts,
+    WeightedUpstreamAddr,
 };
 use g3_types::resolve::{QueryStrategy, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
 
@@ -69,6 +71,7 @@ pub(crate) struct ProxyHttpsEscaperConfig {
     pub(crate) pass_proxy_userid: bool,
     pub(crate) use_proxy_protocol: Option<ProxyProtocolVersion>,
     pub(crate) peer_negotiation_timeout: Duration,
+    pub(crate) http2_connect: Option<Http2ConnectPoolConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
 
@@ -108,6 +111,7 @@ impl ProxyHttpsEscaperConfig {
             pass_proxy_userid: false,
             use_proxy_protocol: None,
             peer_negotiation_timeout: Duration::from_secs(10),
+            http2_connect: None,
             extra_metrics_tags: None,
         }
     }
@@ -276,6 +280,17 @@ impl ProxyHttpsEscaperConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "http2_connect" => {
+                if let Yaml::Boolean(enable) = v {
+                    self.http2_connect = enable.then(Http2ConnectPoolConfig::default);
+                } else {
+                    let config = g3_yaml::value::as_http2_connect_pool_config(v).context(
+                        format!("invalid http2 connect pool config value for key {k}"),
+                    )?;
+                    self.http2_connect = Some(config);
+                }
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -338,6 +353,12 @@ impl ProxyHttpsEscaperConfig {
             }
         }
 
+        if self.http2_connect.is_some() && self.use_proxy_protocol.is_some() {
+            return Err(anyhow!(
+                "proxy protocol can not be used as http2 connections are shared by tasks"
+            ));
+        }
+
         if !self.proxy_username.is_empty() {
             if self.pass_proxy_userid {
                 return Err(anyhow!(
@@ -389,3 +410,48 @@ impl EscaperConfig for ProxyHttpsEscaperConfig {
         self.shared_logger.as_ref().map(|s| s.as_str())
     }
 }
+
+impl YamlDump for ProxyHttpsEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("proxy_addr", &self.proxy_nodes)
+            .set("proxy_addr_pick_policy", &self.proxy_pick_policy)
+            .set("proxy_username", &self.proxy_username)
+            .set("proxy_password", &self.proxy_password);
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("bind_interface", &self.bind_interface);
+        map.set("bind_ipv4", &self.bind_v4)
+            .set("bind_ipv6", &self.bind_v6)
+            .set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("tcp_sock_speed_limit", &self.general.tcp_sock_speed_limit)
+            .set("http_forward_capability", &self.http_forward_capability)
+            .set("tcp_keepalive", &self.tcp_keepalive)
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("no_ipv4", &self.no_ipv4)
+            .set("no_ipv6", &self.no_ipv6)
+            .set("tls_client", &self.tls_config)
+            .set("tls_name", &self.tls_name)
+            .set("tcp_connect", &self.general.tcp_connect)
+            .set("happy_eyeballs", &self.happy_eyeballs)
+            .set(
+                "http_connect_rsp_header_max_size",
+                &self.http_connect_rsp_hdr_max_size,
+            )
+            .set("pass_proxy_userid", &self.pass_proxy_userid)
+            .set("use_proxy_protocol", &self.use_proxy_protocol)
+            .set("peer_negotiation_timeout", &self.peer_negotiation_timeout)
+            .set("http2_connect", &self.http2_connect);
+        map.build()
+    }
+}
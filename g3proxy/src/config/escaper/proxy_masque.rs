@@ -0,0 +1,431 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use ascii::AsciiString;
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::auth::{Password, Username};
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::metrics::{MetricTagMap, NodeName};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "illumos",
+    target_os = "solaris"
+))]
+use g3_types::net::Interface;
+use g3_types::net::{
+    Host, QuinnTransportConfigBuilder, RustlsClientConfigBuilder, SocketBufferConfig,
+    UdpMiscSockOpts, WeightedUpstreamAddr,
+};
+use g3_types::resolve::{QueryStrategy, ResolveStrategy};
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
+
+const ESCAPER_CONFIG_TYPE: &str = "ProxyMasque";
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ProxyMasqueEscaperConfig {
+    pub(crate) name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) shared_logger: Option<AsciiString>,
+    pub(crate) proxy_nodes: Vec<WeightedUpstreamAddr>,
+    pub(crate) proxy_pick_policy: SelectivePickPolicy,
+    proxy_username: Username,
+    proxy_password: Password,
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "illumos",
+        target_os = "solaris"
+    ))]
+    pub(crate) bind_interface: Option<Interface>,
+    pub(crate) bind_v4: Option<Ipv4Addr>,
+    pub(crate) bind_v6: Option<Ipv6Addr>,
+    pub(crate) no_ipv4: bool,
+    pub(crate) no_ipv6: bool,
+    pub(crate) tls_client: RustlsClientConfigBuilder,
+    pub(crate) tls_name: Option<Host>,
+    pub(crate) quic_transport: QuinnTransportConfigBuilder,
+    pub(crate) socket_buffer: SocketBufferConfig,
+    pub(crate) udp_misc_opts: UdpMiscSockOpts,
+    pub(crate) resolver: NodeName,
+    pub(crate) resolve_strategy: ResolveStrategy,
+    pub(crate) general: GeneralEscaperConfig,
+    pub(crate) append_http_headers: Vec<String>,
+    pub(crate) pass_proxy_userid: bool,
+    pub(crate) peer_negotiation_timeout: Duration,
+    pub(crate) connection_idle_timeout: Duration,
+    pub(crate) udp_packet_queue_size: usize,
+    pub(crate) udp_relay_max_sessions: usize,
+    pub(crate) udp_relay_session_idle_timeout: Duration,
+    pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
+}
+
+impl ProxyMasqueEscaperConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        ProxyMasqueEscaperConfig {
+            name: NodeName::default(),
+            position,
+            shared_logger: None,
+            proxy_nodes: Vec::with_capacity(1),
+            proxy_pick_policy: SelectivePickPolicy::Random,
+            proxy_username: Username::empty(),
+            proxy_password: Password::empty(),
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "illumos",
+                target_os = "solaris"
+            ))]
+            bind_interface: None,
+            bind_v4: None,
+            bind_v6: None,
+            no_ipv4: false,
+            no_ipv6: false,
+            tls_client: RustlsClientConfigBuilder::default(),
+            tls_name: None,
+            quic_transport: QuinnTransportConfigBuilder::default(),
+            socket_buffer: SocketBufferConfig::default(),
+            udp_misc_opts: Default::default(),
+            resolver: NodeName::default(),
+            resolve_strategy: Default::default(),
+            general: Default::default(),
+            append_http_headers: Vec::new(),
+            pass_proxy_userid: false,
+            peer_negotiation_timeout: Duration::from_secs(10),
+            connection_idle_timeout: Duration::from_secs(60),
+            udp_packet_queue_size: 256,
+            udp_relay_max_sessions: 16,
+            udp_relay_session_idle_timeout: Duration::from_secs(30),
+            extra_metrics_tags: None,
+        }
+    }
+
+    pub(super) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut config = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| config.set(k, v))?;
+
+        config.check()?;
+        Ok(config)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_ESCAPER_TYPE => Ok(()),
+            super::CONFIG_KEY_ESCAPER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "shared_logger" => {
+                let name = g3_yaml::value::as_ascii(v)?;
+                self.shared_logger = Some(name);
+                Ok(())
+            }
+            "extra_metrics_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                self.extra_metrics_tags = Some(Arc::new(tags));
+                Ok(())
+            }
+            "proxy_addr" => {
+                self.proxy_nodes = g3_yaml::value::as_list(v, |v| {
+                    g3_yaml::value::as_weighted_upstream_addr(v, 443)
+                })
+                .context(format!(
+                    "invalid weighted upstream address list value for key {k}"
+                ))?;
+                Ok(())
+            }
+            "proxy_addr_pick_policy" => {
+                self.proxy_pick_policy = g3_yaml::value::as_selective_pick_policy(v)?;
+                Ok(())
+            }
+            "proxy_username" | "proxy_user" => {
+                self.proxy_username = g3_yaml::value::as_username(v)
+                    .context(format!("invalid username value for key {k}"))?;
+                Ok(())
+            }
+            "proxy_password" | "proxy_passwd" => {
+                self.proxy_password = g3_yaml::value::as_password(v)
+                    .context(format!("invalid password value for key {k}"))?;
+                Ok(())
+            }
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "illumos",
+                target_os = "solaris"
+            ))]
+            "bind_interface" => {
+                let interface = g3_yaml::value::as_interface(v)
+                    .context(format!("invalid interface name value for key {k}"))?;
+                self.bind_interface = Some(interface);
+                Ok(())
+            }
+            "bind_ipv4" => {
+                let ip4 = g3_yaml::value::as_ipv4addr(v)?;
+                self.bind_v4 = Some(ip4);
+                Ok(())
+            }
+            "bind_ipv6" => {
+                let ip6 = g3_yaml::value::as_ipv6addr(v)?;
+                self.bind_v6 = Some(ip6);
+                Ok(())
+            }
+            "resolver" => {
+                self.resolver = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "resolve_strategy" => {
+                self.resolve_strategy = g3_yaml::value::as_resolve_strategy(v)?;
+                Ok(())
+            }
+            "tcp_sock_speed_limit" => {
+                self.general.tcp_sock_speed_limit = g3_yaml::value::as_tcp_sock_speed_limit(v)
+                    .context(format!("invalid tcp socket speed limit value for key {k}"))?;
+                Ok(())
+            }
+            "udp_misc_opts" => {
+                self.udp_misc_opts = g3_yaml::value::as_udp_misc_sock_opts(v)
+                    .context(format!("invalid udp misc sock opts value for key {k}"))?;
+                Ok(())
+            }
+            "socket_buffer" => {
+                self.socket_buffer = g3_yaml::value::as_socket_buffer_config(v)
+                    .context(format!("invalid socket buffer config value for key {k}"))?;
+                Ok(())
+            }
+            "no_ipv4" => {
+                self.no_ipv4 = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "no_ipv6" => {
+                self.no_ipv6 = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "tls" | "tls_client" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                self.tls_client =
+                    g3_yaml::value::as_rustls_client_config_builder(v, Some(lookup_dir)).context(
+                        format!("invalid rustls tls client config value for key {k}"),
+                    )?;
+                Ok(())
+            }
+            "tls_name" => {
+                let name = g3_yaml::value::as_host(v)
+                    .context(format!("invalid tls server name value for key {k}"))?;
+                self.tls_name = Some(name);
+                Ok(())
+            }
+            "quic_transport" => {
+                self.quic_transport = g3_yaml::value::as_quinn_transport_config(v)
+                    .context(format!("invalid quinn transport config value for key {k}"))?;
+                Ok(())
+            }
+            "pass_proxy_userid" => {
+                self.pass_proxy_userid = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "peer_negotiation_timeout" => {
+                self.peer_negotiation_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "connection_idle_timeout" => {
+                self.connection_idle_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "udp_packet_queue_size" => {
+                self.udp_packet_queue_size = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?
+                    .get();
+                Ok(())
+            }
+            "udp_relay_max_sessions" => {
+                self.udp_relay_max_sessions = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?
+                    .get();
+                Ok(())
+            }
+            "udp_relay_session_idle_timeout" => {
+                self.udp_relay_session_idle_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.proxy_nodes.is_empty() {
+            return Err(anyhow!("proxy addr is not set"));
+        }
+        self.proxy_nodes.reverse(); // reverse as we push to the back
+        if self.no_ipv4 && self.no_ipv6 {
+            return Err(anyhow!("both ipv4 and ipv6 are disabled"));
+        }
+
+        let mut disable_ipv4 = true;
+        let mut disable_ipv6 = true;
+        let mut check_resolver = false;
+        for node in &self.proxy_nodes {
+            match node.inner().host() {
+                Host::Domain(_) => {
+                    disable_ipv4 = false;
+                    disable_ipv6 = false;
+                    check_resolver = true;
+                }
+                Host::Ip(IpAddr::V4(_)) => {
+                    if self.no_ipv4 {
+                        return Err(anyhow!("ipv4 is disable but the proxy addr is also ipv4"));
+                    }
+                    disable_ipv4 = false;
+                }
+                Host::Ip(IpAddr::V6(_)) => {
+                    if self.no_ipv6 {
+                        return Err(anyhow!("ipv6 is disable but the proxy addr is also ipv6"));
+                    }
+                    disable_ipv6 = false;
+                }
+            }
+        }
+        if disable_ipv4 {
+            self.no_ipv4 = true;
+        }
+        if disable_ipv6 {
+            self.no_ipv6 = true;
+        }
+        if check_resolver {
+            if self.resolver.is_empty() {
+                return Err(anyhow!("resolver is not set"));
+            }
+            self.resolve_strategy
+                .update_query_strategy(self.no_ipv4, self.no_ipv6)
+                .context("found incompatible resolver strategy")?;
+            if !self.no_ipv4 && !self.no_ipv6 {
+                match self.resolve_strategy.query {
+                    QueryStrategy::Ipv4Only => self.no_ipv6 = true,
+                    QueryStrategy::Ipv6Only => self.no_ipv4 = true,
+                    _ => {}
+                }
+            }
+        }
+
+        if !self.proxy_username.is_empty() {
+            if self.pass_proxy_userid {
+                return Err(anyhow!(
+                    "auth is needed for next proxy, we can not pass userid to it"
+                ));
+            }
+
+            self.append_http_headers
+                .push(g3_http::header::proxy_authorization_basic(
+                    &self.proxy_username,
+                    &self.proxy_password,
+                ));
+        }
+
+        Ok(())
+    }
+}
+
+impl EscaperConfig for ProxyMasqueEscaperConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &str {
+        ESCAPER_CONFIG_TYPE
+    }
+
+    fn resolver(&self) -> &NodeName {
+        &self.resolver
+    }
+
+    fn diff_action(&self, new: &AnyEscaperConfig) -> EscaperConfigDiffAction {
+        let AnyEscaperConfig::ProxyMasque(new) = new else {
+            return EscaperConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return EscaperConfigDiffAction::NoAction;
+        }
+
+        EscaperConfigDiffAction::Reload
+    }
+
+    fn shared_logger(&self) -> Option<&str> {
+        self.shared_logger.as_ref().map(|s| s.as_str())
+    }
+}
+
+impl YamlDump for ProxyMasqueEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("proxy_addr", &self.proxy_nodes)
+            .set("proxy_addr_pick_policy", &self.proxy_pick_policy)
+            .set("proxy_username", &self.proxy_username)
+            .set("proxy_password", &self.proxy_password);
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("bind_interface", &self.bind_interface);
+        map.set("bind_ipv4", &self.bind_v4)
+            .set("bind_ipv6", &self.bind_v6)
+            .set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("tcp_sock_speed_limit", &self.general.tcp_sock_speed_limit)
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set("socket_buffer", &self.socket_buffer)
+            .set("no_ipv4", &self.no_ipv4)
+            .set("no_ipv6", &self.no_ipv6)
+            .set("tls_client", &self.tls_client)
+            .set("tls_name", &self.tls_name)
+            .set("quic_transport", &self.quic_transport)
+            .set("pass_proxy_userid", &self.pass_proxy_userid)
+            .set("peer_negotiation_timeout", &self.peer_negotiation_timeout)
+            .set("connection_idle_timeout", &self.connection_idle_timeout)
+            .set("udp_packet_queue_size", &self.udp_packet_queue_size)
+            .set("udp_relay_max_sessions", &self.udp_relay_max_sessions)
+            .set(
+                "udp_relay_session_idle_timeout",
+                &self.udp_relay_session_idle_timeout,
+            );
+        map.build()
+    }
+}
@@ -30,6 +30,7 @@ use g3_types::net::{
 };
 use g3_types::resolve::{QueryStrategy, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
 
@@ -408,3 +409,52 @@ impl EscaperConfig for ProxySocks5sEscaperConfig {
         self.shared_logger.as_ref().map(|s| s.as_str())
     }
 }
+
+impl YamlDump for ProxySocks5sEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("proxy_addr", &self.proxy_nodes)
+            .set("proxy_addr_pick_policy", &self.proxy_pick_policy)
+            .set("proxy_username", &self.proxy_username)
+            .set("proxy_password", &self.proxy_password);
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("bind_interface", &self.bind_interface);
+        map.set("bind_ipv4", &self.bind_v4)
+            .set("bind_ipv6", &self.bind_v6)
+            .set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("tcp_sock_speed_limit", &self.general.tcp_sock_speed_limit)
+            .set("udp_sock_speed_limit", &self.general.udp_sock_speed_limit)
+            .set("tcp_keepalive", &self.tcp_keepalive)
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set("no_ipv4", &self.no_ipv4)
+            .set("no_ipv6", &self.no_ipv6)
+            .set("tls_client", &self.tls_config)
+            .set("tls_name", &self.tls_name)
+            .set("tcp_connect", &self.general.tcp_connect)
+            .set("happy_eyeballs", &self.happy_eyeballs)
+            .set("peer_negotiation_timeout", &self.peer_negotiation_timeout)
+            .set("end_on_control_closed", &self.end_on_control_closed);
+        match &self.transmute_udp_peer_ip {
+            Some(ip_map) if ip_map.is_empty() => {
+                map.set("transmute_udp_peer_ip", &true);
+            }
+            Some(ip_map) => {
+                map.set("transmute_udp_peer_ip", ip_map);
+            }
+            None => {}
+        }
+        map.build()
+    }
+}
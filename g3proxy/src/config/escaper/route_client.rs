@@ -12,6 +12,7 @@ use ip_network::IpNetwork;
 use yaml_rust::{Yaml, yaml};
 
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
 
@@ -217,3 +218,37 @@ impl EscaperConfig for RouteClientEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteClientEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE);
+        if !self.exact_match_ipaddr.is_empty() {
+            let rules: Vec<Yaml> = self
+                .exact_match_ipaddr
+                .iter()
+                .map(|(next, ips)| {
+                    let mut rule = YamlMapDumper::new(redact);
+                    rule.set("next", next).set("ips", ips);
+                    rule.build()
+                })
+                .collect();
+            map.set_yaml("exact_match", Yaml::Array(rules));
+        }
+        if !self.subnet_match_ipaddr.is_empty() {
+            let rules: Vec<Yaml> = self
+                .subnet_match_ipaddr
+                .iter()
+                .map(|(next, subnets)| {
+                    let mut rule = YamlMapDumper::new(redact);
+                    rule.set("next", next).set("subnets", subnets);
+                    rule.build()
+                })
+                .collect();
+            map.set_yaml("subnet_match", Yaml::Array(rules));
+        }
+        map.set("default_next", &self.default_next);
+        map.build()
+    }
+}
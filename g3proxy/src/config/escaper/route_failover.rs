@@ -11,6 +11,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction};
 
@@ -122,3 +123,15 @@ impl EscaperConfig for RouteFailoverEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteFailoverEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("primary", &self.primary_node)
+            .set("standby", &self.standby_node)
+            .set("fallback_delay", &self.fallback_delay);
+        map.build()
+    }
+}
@@ -15,6 +15,7 @@ use g3_ip_locate::IpLocateServiceConfig;
 use g3_types::metrics::NodeName;
 use g3_types::resolve::ResolveStrategy;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
 
@@ -267,3 +268,47 @@ impl EscaperConfig for RouteGeoIpEscaperConfig {
         Some(set)
     }
 }
+
+fn geo_match_rule<'a, 'b>(
+    rules: &'b mut BTreeMap<&'a NodeName, YamlMapDumper>,
+    next: &'a NodeName,
+    redact: bool,
+) -> &'b mut YamlMapDumper {
+    rules.entry(next).or_insert_with(|| {
+        let mut rule = YamlMapDumper::new(redact);
+        rule.set("next", next);
+        rule
+    })
+}
+
+impl YamlDump for RouteGeoIpEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("resolution_delay", &self.resolution_delay)
+            .set("ip_locate_service", &self.ip_locate_service);
+
+        let mut rules = BTreeMap::<&NodeName, YamlMapDumper>::new();
+        for (next, networks) in &self.lpm_rules {
+            geo_match_rule(&mut rules, next, redact).set("networks", networks);
+        }
+        for (next, asn_set) in &self.asn_rules {
+            geo_match_rule(&mut rules, next, redact).set("asn", asn_set);
+        }
+        for (next, countries) in &self.country_rules {
+            geo_match_rule(&mut rules, next, redact).set("countries", countries);
+        }
+        for (next, continents) in &self.continent_rules {
+            geo_match_rule(&mut rules, next, redact).set("continents", continents);
+        }
+        if !rules.is_empty() {
+            let rules = rules.into_values().map(|rule| rule.build()).collect();
+            map.set_yaml("geo_match", Yaml::Array(rules));
+        }
+        map.set("default_next", &self.default_next);
+        map.build()
+    }
+}
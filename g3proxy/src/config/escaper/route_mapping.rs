@@ -11,6 +11,7 @@ use indexmap::IndexSet;
 use yaml_rust::{Yaml, yaml};
 
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction};
 
@@ -120,3 +121,21 @@ impl EscaperConfig for RouteMappingEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteMappingEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set_yaml(
+                "next",
+                Yaml::Array(
+                    self.next_nodes
+                        .iter()
+                        .map(|n| n.dump_yaml(redact))
+                        .collect(),
+                ),
+            );
+        map.build()
+    }
+}
@@ -14,6 +14,7 @@ use g3_types::collection::SelectivePickPolicy;
 use g3_types::metrics::NodeName;
 use g3_types::net::SocketBufferConfig;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction};
 
@@ -202,3 +203,24 @@ impl EscaperConfig for RouteQueryEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteQueryEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("query_pass_client_ip", &self.query_pass_client_ip)
+            .set("query_allowed_next", &self.query_allowed_nodes)
+            .set("fallback_node", &self.fallback_node)
+            .set("cache_request_batch_count", &self.cache_request_batch_count)
+            .set("cache_request_timeout", &self.cache_request_timeout)
+            .set("cache_pick_policy", &self.cache_pick_policy)
+            .set("query_peer_addr", &self.query_peer_addr)
+            .set("query_socket_buffer", &self.query_socket_buffer)
+            .set("query_wait_timeout", &self.query_wait_timeout)
+            .set("protective_cache_ttl", &self.protective_cache_ttl)
+            .set("maximum_cache_ttl", &self.maximum_cache_ttl)
+            .set("cache_vanish_wait", &self.cache_vanish_wait);
+        map.build()
+    }
+}
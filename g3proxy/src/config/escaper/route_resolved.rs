@@ -14,6 +14,7 @@ use yaml_rust::{Yaml, yaml};
 use g3_types::metrics::NodeName;
 use g3_types::resolve::ResolveStrategy;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
 
@@ -189,3 +190,28 @@ impl EscaperConfig for RouteResolvedEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteResolvedEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("resolver", &self.resolver)
+            .set("resolve_strategy", &self.resolve_strategy)
+            .set("resolution_delay", &self.resolution_delay);
+        if !self.lpm_rules.is_empty() {
+            let rules: Vec<Yaml> = self
+                .lpm_rules
+                .iter()
+                .map(|(next, networks)| {
+                    let mut rule = YamlMapDumper::new(redact);
+                    rule.set("next", next).set("networks", networks);
+                    rule.build()
+                })
+                .collect();
+            map.set_yaml("lpm_match", Yaml::Array(rules));
+        }
+        map.set("default_next", &self.default_next);
+        map.build()
+    }
+}
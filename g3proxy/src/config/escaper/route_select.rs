@@ -11,6 +11,7 @@ use yaml_rust::{Yaml, yaml};
 use g3_types::collection::{SelectivePickPolicy, WeightedValue};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction};
 
@@ -120,3 +121,14 @@ impl EscaperConfig for RouteSelectEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteSelectEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("next_nodes", &self.next_nodes)
+            .set("next_pick_policy", &self.next_pick_policy);
+        map.build()
+    }
+}
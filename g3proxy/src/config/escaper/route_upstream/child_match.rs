@@ -11,6 +11,7 @@ use radix_trie::{Trie, TrieCommon};
 use yaml_rust::Yaml;
 
 use g3_types::metrics::NodeName;
+use g3_yaml::dump::YamlDump;
 
 use crate::config::escaper::verify::EscaperConfigVerifier;
 
@@ -109,6 +110,16 @@ impl<T> ChildMatch<T> {
     }
 }
 
+impl YamlDump for ChildMatchBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        if self.inner.is_empty() {
+            Yaml::Null
+        } else {
+            self.inner.dump_yaml(redact)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
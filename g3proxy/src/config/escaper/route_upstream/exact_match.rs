@@ -15,6 +15,7 @@ use yaml_rust::Yaml;
 
 use g3_types::metrics::NodeName;
 use g3_types::net::Host;
+use g3_yaml::dump::YamlDump;
 
 use crate::config::escaper::verify::EscaperConfigVerifier;
 
@@ -118,6 +119,25 @@ impl ExactMatchBuilder {
     }
 }
 
+impl YamlDump for ExactMatchBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut rules = BTreeMap::<&NodeName, Vec<Yaml>>::new();
+        for (escaper, domains) in &self.domain {
+            let values = rules.entry(escaper).or_default();
+            values.extend(domains.iter().map(|d| d.dump_yaml(redact)));
+        }
+        for (escaper, ips) in &self.ipaddr {
+            let values = rules.entry(escaper).or_default();
+            values.extend(ips.iter().map(|ip| ip.dump_yaml(redact)));
+        }
+        if rules.is_empty() {
+            Yaml::Null
+        } else {
+            rules.dump_yaml(redact)
+        }
+    }
+}
+
 #[derive(Default)]
 struct ExactMatchValues {
     domain: BTreeSet<Arc<str>>,
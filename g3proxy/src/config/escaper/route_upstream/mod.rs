@@ -10,6 +10,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction};
 
@@ -156,3 +157,18 @@ impl EscaperConfig for RouteUpstreamEscaperConfig {
         Some(set)
     }
 }
+
+impl YamlDump for RouteUpstreamEscaperConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_ESCAPER_NAME, &self.name)
+            .set(super::CONFIG_KEY_ESCAPER_TYPE, ESCAPER_CONFIG_TYPE)
+            .set("exact_match", &self.exact_match)
+            .set("subnet_match", &self.subnet_match)
+            .set("suffix_match", &self.suffix_match)
+            .set("child_match", &self.child_match)
+            .set("regex_match", &self.regex_match)
+            .set("default_next", &self.default_next);
+        map.build()
+    }
+}
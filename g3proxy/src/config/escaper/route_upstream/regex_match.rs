@@ -15,6 +15,7 @@ use yaml_rust::Yaml;
 use crate::config::escaper::verify::EscaperConfigVerifier;
 
 use g3_types::metrics::NodeName;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 #[derive(Clone, Default, PartialEq, Eq)]
 pub(crate) struct RegexMatchBuilder {
@@ -182,6 +183,28 @@ impl RegexMatchValue {
     }
 }
 
+impl YamlDump for RegexMatchValue {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        if self.parent_domain.is_empty() {
+            return self.sub_domain_regex.dump_yaml(redact);
+        }
+        let mut map = YamlMapDumper::new(redact);
+        map.set("parent", &self.parent_domain)
+            .set("regex", &self.sub_domain_regex);
+        map.build()
+    }
+}
+
+impl YamlDump for RegexMatchBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        if self.inner.is_empty() {
+            Yaml::Null
+        } else {
+            self.inner.dump_yaml(redact)
+        }
+    }
+}
+
 pub(crate) struct RegexMatch<T> {
     parent_match: Trie<String, Vec<(RegexSet, T)>>,
     full_match: Vec<(RegexSet, T)>,
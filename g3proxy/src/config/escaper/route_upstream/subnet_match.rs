@@ -13,6 +13,7 @@ use ip_network_table::IpNetworkTable;
 use yaml_rust::Yaml;
 
 use g3_types::metrics::NodeName;
+use g3_yaml::dump::YamlDump;
 
 use crate::config::escaper::verify::EscaperConfigVerifier;
 
@@ -105,6 +106,16 @@ impl<T> SubnetMatch<T> {
     }
 }
 
+impl YamlDump for SubnetMatchBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        if self.inner.is_empty() {
+            Yaml::Null
+        } else {
+            self.inner.dump_yaml(redact)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
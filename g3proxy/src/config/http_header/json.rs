@@ -0,0 +1,90 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use serde_json::Value;
+
+use super::{HttpHeaderRule, HttpHeaderRuleBuilder, HttpHeaderRules};
+
+impl HttpHeaderRule {
+    fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        let Value::Object(map) = v else {
+            return Err(anyhow!(
+                "json value type for 'http header rule' should be 'map'"
+            ));
+        };
+
+        let mut builder = HttpHeaderRuleBuilder::default();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "name" | "header" => {
+                    let name = g3_json::value::as_string(v)?;
+                    builder
+                        .set_name(&name)
+                        .context(format!("invalid header name value for key {k}"))?;
+                }
+                "action" => {
+                    let action = g3_json::value::as_string(v)?;
+                    builder.action = Some(action.to_lowercase());
+                }
+                "value" => {
+                    let value = g3_json::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    builder.value = Some(value);
+                }
+                "regex" => {
+                    let regex = g3_json::value::as_regex(v)
+                        .context(format!("invalid regex value for key {k}"))?;
+                    builder.regex = Some(regex);
+                }
+                "host" | "hosts" => {
+                    let hosts = g3_json::value::as_list(v, g3_json::value::as_string)
+                        .context(format!("invalid host string list value for key {k}"))?;
+                    for host in hosts {
+                        builder.condition.add_host(&host);
+                    }
+                }
+                "path" | "path_prefix" => {
+                    let prefix = g3_json::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    builder.condition.path_prefix = Some(prefix);
+                }
+                "path_regex" => {
+                    let regex = g3_json::value::as_regex(v)
+                        .context(format!("invalid regex value for key {k}"))?;
+                    builder.condition.path_regex = Some(regex);
+                }
+                _ => return Err(anyhow!("invalid key {k}")),
+            }
+        }
+        builder.build()
+    }
+}
+
+impl HttpHeaderRules {
+    pub(crate) fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        let Value::Object(map) = v else {
+            return Err(anyhow!(
+                "json value type for 'http header rules' should be 'map'"
+            ));
+        };
+
+        let mut rules = HttpHeaderRules::default();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "request" => {
+                    rules.request = g3_json::value::as_list(v, HttpHeaderRule::parse_json)
+                        .context(format!("invalid http header rule list value for key {k}"))?;
+                }
+                "response" => {
+                    rules.response = g3_json::value::as_list(v, HttpHeaderRule::parse_json)
+                        .context(format!("invalid http header rule list value for key {k}"))?;
+                }
+                _ => return Err(anyhow!("invalid key {k}")),
+            }
+        }
+        Ok(rules)
+    }
+}
@@ -0,0 +1,279 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use http::{HeaderName, header};
+use regex::Regex;
+
+use g3_types::net::Host;
+
+mod json;
+mod yaml;
+
+mod template;
+pub(crate) use template::{HttpHeaderTemplateVar, HttpHeaderValueTemplate};
+
+#[derive(Clone, Debug)]
+pub(crate) enum HttpHeaderRuleAction {
+    /// append a new value
+    Add(HttpHeaderValueTemplate),
+    /// replace all existing values
+    Set(HttpHeaderValueTemplate),
+    Remove,
+    /// rewrite each existing value by regex replacement
+    Replace(Regex, String),
+}
+
+impl PartialEq for HttpHeaderRuleAction {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HttpHeaderRuleAction::Add(a), HttpHeaderRuleAction::Add(b)) => a.eq(b),
+            (HttpHeaderRuleAction::Set(a), HttpHeaderRuleAction::Set(b)) => a.eq(b),
+            (HttpHeaderRuleAction::Remove, HttpHeaderRuleAction::Remove) => true,
+            (HttpHeaderRuleAction::Replace(r1, v1), HttpHeaderRuleAction::Replace(r2, v2)) => {
+                r1.as_str().eq(r2.as_str()) && v1.eq(v2)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for HttpHeaderRuleAction {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum HttpHeaderRuleHost {
+    Exact(String),
+    /// match all sub domains of the domain
+    Suffix(String),
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HttpHeaderRuleCondition {
+    hosts: Vec<HttpHeaderRuleHost>,
+    path_prefix: Option<String>,
+    path_regex: Option<Regex>,
+}
+
+impl PartialEq for HttpHeaderRuleCondition {
+    fn eq(&self, other: &Self) -> bool {
+        self.hosts.eq(&other.hosts)
+            && self.path_prefix.eq(&other.path_prefix)
+            && self.path_regex.as_ref().map(|r| r.as_str())
+                == other.path_regex.as_ref().map(|r| r.as_str())
+    }
+}
+
+impl Eq for HttpHeaderRuleCondition {}
+
+impl HttpHeaderRuleCondition {
+    fn add_host(&mut self, host: &str) {
+        let host = host.to_lowercase();
+        if let Some(domain) = host.strip_prefix("*.") {
+            self.hosts
+                .push(HttpHeaderRuleHost::Suffix(format!(".{domain}")));
+        } else {
+            self.hosts.push(HttpHeaderRuleHost::Exact(host));
+        }
+    }
+
+    fn match_host(&self, host: &Host) -> bool {
+        if self.hosts.is_empty() {
+            return true;
+        }
+        let host = match host {
+            Host::Domain(domain) => domain.to_lowercase(),
+            Host::Ip(ip) => ip.to_string(),
+        };
+        self.hosts.iter().any(|h| match h {
+            HttpHeaderRuleHost::Exact(v) => host.eq(v),
+            HttpHeaderRuleHost::Suffix(v) => host.ends_with(v.as_str()),
+        })
+    }
+
+    fn match_path(&self, path: &str) -> bool {
+        let prefix_matched = self
+            .path_prefix
+            .as_ref()
+            .map(|prefix| path.starts_with(prefix.as_str()))
+            .unwrap_or(true);
+        prefix_matched
+            && self
+                .path_regex
+                .as_ref()
+                .map(|regex| regex.is_match(path))
+                .unwrap_or(true)
+    }
+
+    pub(crate) fn matches(&self, host: &Host, path: &str) -> bool {
+        self.match_host(host) && self.match_path(path)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpHeaderRule {
+    pub(crate) name: HeaderName,
+    pub(crate) action: HttpHeaderRuleAction,
+    pub(crate) condition: HttpHeaderRuleCondition,
+}
+
+#[derive(Default)]
+struct HttpHeaderRuleBuilder {
+    name: Option<HeaderName>,
+    action: Option<String>,
+    value: Option<String>,
+    regex: Option<Regex>,
+    condition: HttpHeaderRuleCondition,
+}
+
+impl HttpHeaderRuleBuilder {
+    fn set_name(&mut self, name: &str) -> anyhow::Result<()> {
+        let name = HeaderName::from_str(name).map_err(|e| anyhow!("invalid header name: {e}"))?;
+        match name {
+            header::CONNECTION
+            | header::CONTENT_LENGTH
+            | header::HOST
+            | header::PROXY_AUTHENTICATE
+            | header::PROXY_AUTHORIZATION
+            | header::TE
+            | header::TRAILER
+            | header::TRANSFER_ENCODING
+            | header::UPGRADE => Err(anyhow!("header {name} is not allowed to be changed")),
+            _ => {
+                if name.as_str() == "keep-alive" || name.as_str() == "proxy-connection" {
+                    return Err(anyhow!("header {name} is not allowed to be changed"));
+                }
+                self.name = Some(name);
+                Ok(())
+            }
+        }
+    }
+
+    fn build(self) -> anyhow::Result<HttpHeaderRule> {
+        let Some(name) = self.name else {
+            return Err(anyhow!("no header name set"));
+        };
+        let Some(action) = self.action else {
+            return Err(anyhow!("no action set"));
+        };
+        let action = match action.as_str() {
+            "add" | "append" => {
+                let Some(value) = self.value else {
+                    return Err(anyhow!("no value set for action {action}"));
+                };
+                HttpHeaderRuleAction::Add(HttpHeaderValueTemplate::from_str(&value)?)
+            }
+            "set" => {
+                let Some(value) = self.value else {
+                    return Err(anyhow!("no value set for action {action}"));
+                };
+                HttpHeaderRuleAction::Set(HttpHeaderValueTemplate::from_str(&value)?)
+            }
+            "remove" | "delete" => HttpHeaderRuleAction::Remove,
+            "replace" => {
+                let Some(regex) = self.regex else {
+                    return Err(anyhow!("no regex set for action {action}"));
+                };
+                HttpHeaderRuleAction::Replace(regex, self.value.unwrap_or_default())
+            }
+            _ => return Err(anyhow!("unsupported action {action}")),
+        };
+        Ok(HttpHeaderRule {
+            name,
+            action,
+            condition: self.condition,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct HttpHeaderRules {
+    pub(crate) request: Vec<HttpHeaderRule>,
+    pub(crate) response: Vec<HttpHeaderRule>,
+}
+
+impl HttpHeaderRules {
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.request.is_empty() && self.response.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+
+    #[test]
+    fn condition() {
+        let mut condition = HttpHeaderRuleCondition::default();
+        condition.add_host("www.example.com");
+        condition.add_host("*.example.net");
+        condition.path_prefix = Some("/api/".to_string());
+
+        let host = Host::Domain("WWW.example.com".into());
+        assert!(condition.matches(&host, "/api/v1"));
+        assert!(!condition.matches(&host, "/index.html"));
+        let host = Host::Domain("a.example.net".into());
+        assert!(condition.matches(&host, "/api/v1"));
+        let host = Host::Domain("example.net".into());
+        assert!(!condition.matches(&host, "/api/v1"));
+        let host = Host::Ip(IpAddr::from([127, 0, 0, 1]));
+        assert!(!condition.matches(&host, "/api/v1"));
+
+        let condition = HttpHeaderRuleCondition::default();
+        assert!(condition.matches(&host, "/"));
+    }
+
+    #[test]
+    fn builder() {
+        let mut builder = HttpHeaderRuleBuilder::default();
+        assert!(builder.set_name("Transfer-Encoding").is_err());
+        builder.set_name("X-Tenant-Id").unwrap();
+        builder.action = Some("set".to_string());
+        assert!(builder.build().is_err());
+
+        let mut builder = HttpHeaderRuleBuilder::default();
+        builder.set_name("Server").unwrap();
+        builder.action = Some("replace".to_string());
+        builder.regex = Some(Regex::new("^nginx/.*$").unwrap());
+        builder.value = Some("nginx".to_string());
+        let rule = builder.build().unwrap();
+        assert_eq!(rule.name, header::SERVER);
+    }
+
+    #[test]
+    fn dump_yaml() {
+        use g3_yaml::dump::YamlDump;
+        use yaml_rust::YamlLoader;
+
+        let doc = YamlLoader::load_from_str(
+            r#"
+            request:
+              - name: X-Tenant-Id
+                action: set
+                value: "tenant-${user}-$$"
+                hosts: [www.example.com, "*.example.net"]
+                path_prefix: /api/
+            response:
+              - name: Server
+                action: replace
+                regex: "^nginx/.*$"
+                value: nginx
+                path_regex: "^/static/"
+              - name: X-Powered-By
+                action: remove
+            "#,
+        )
+        .unwrap();
+        let rules = HttpHeaderRules::parse_yaml(&doc[0]).unwrap();
+        let v = rules.dump_yaml(true);
+        assert_eq!(v["request"][0]["hosts"][1].as_str(), Some("*.example.net"));
+        assert_eq!(v["request"][0]["value"].as_str(), Some("tenant-${user}-$$"));
+        let rules2 = HttpHeaderRules::parse_yaml(&v).unwrap();
+        assert_eq!(rules2, rules);
+    }
+}
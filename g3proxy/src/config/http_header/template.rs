@@ -0,0 +1,188 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use http::HeaderValue;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HttpHeaderTemplateVar {
+    UserName,
+    ClientIp,
+    ClientAddr,
+    ServerIp,
+    ServerAddr,
+    EscaperName,
+    TaskId,
+    Host,
+}
+
+impl HttpHeaderTemplateVar {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HttpHeaderTemplateVar::UserName => "user",
+            HttpHeaderTemplateVar::ClientIp => "client_ip",
+            HttpHeaderTemplateVar::ClientAddr => "client_addr",
+            HttpHeaderTemplateVar::ServerIp => "server_ip",
+            HttpHeaderTemplateVar::ServerAddr => "server_addr",
+            HttpHeaderTemplateVar::EscaperName => "escaper",
+            HttpHeaderTemplateVar::TaskId => "task_id",
+            HttpHeaderTemplateVar::Host => "host",
+        }
+    }
+}
+
+impl FromStr for HttpHeaderTemplateVar {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "user" | "user_name" => Ok(HttpHeaderTemplateVar::UserName),
+            "client_ip" => Ok(HttpHeaderTemplateVar::ClientIp),
+            "client_addr" => Ok(HttpHeaderTemplateVar::ClientAddr),
+            "server_ip" => Ok(HttpHeaderTemplateVar::ServerIp),
+            "server_addr" => Ok(HttpHeaderTemplateVar::ServerAddr),
+            "escaper" | "escaper_name" => Ok(HttpHeaderTemplateVar::EscaperName),
+            "task_id" => Ok(HttpHeaderTemplateVar::TaskId),
+            "host" => Ok(HttpHeaderTemplateVar::Host),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Var(HttpHeaderTemplateVar),
+}
+
+/// Header value template, variables are written as `${name}`, and `$$` can be used for a literal `$`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpHeaderValueTemplate {
+    parts: Vec<TemplatePart>,
+}
+
+impl HttpHeaderValueTemplate {
+    pub(crate) fn render<F>(&self, mut expand: F) -> String
+    where
+        F: FnMut(HttpHeaderTemplateVar, &mut String),
+    {
+        let mut s = String::new();
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(v) => s.push_str(v),
+                TemplatePart::Var(var) => expand(*var, &mut s),
+            }
+        }
+        s
+    }
+}
+
+impl FromStr for HttpHeaderValueTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+
+        let mut left = s;
+        while let Some(p) = left.find('$') {
+            literal.push_str(&left[..p]);
+            left = &left[p + 1..];
+            if let Some(r) = left.strip_prefix('$') {
+                literal.push('$');
+                left = r;
+            } else if let Some(r) = left.strip_prefix('{') {
+                let Some(end) = r.find('}') else {
+                    return Err(anyhow!("no matching '}}' found for variable"));
+                };
+                let name = &r[..end];
+                let var = HttpHeaderTemplateVar::from_str(name)
+                    .map_err(|_| anyhow!("unsupported variable {name}"))?;
+                if !literal.is_empty() {
+                    parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(TemplatePart::Var(var));
+                left = &r[end + 1..];
+            } else {
+                return Err(anyhow!("'$' should be followed by '{{' or '$'"));
+            }
+        }
+        literal.push_str(left);
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+
+        for part in &parts {
+            if let TemplatePart::Literal(v) = part {
+                HeaderValue::from_str(v)
+                    .map_err(|e| anyhow!("invalid http header value string {v}: {e}"))?;
+            }
+        }
+        Ok(HttpHeaderValueTemplate { parts })
+    }
+}
+
+impl fmt::Display for HttpHeaderValueTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(v) => f.write_str(&v.replace('$', "$$"))?,
+                TemplatePart::Var(var) => write!(f, "${{{}}}", var.as_str())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(s: &str) -> String {
+        let template = HttpHeaderValueTemplate::from_str(s).unwrap();
+        template.render(|var, s| match var {
+            HttpHeaderTemplateVar::UserName => s.push_str("alice"),
+            HttpHeaderTemplateVar::TaskId => s.push_str("1234"),
+            _ => {}
+        })
+    }
+
+    #[test]
+    fn parse_render() {
+        assert_eq!(render("static"), "static");
+        assert_eq!(render("${user}"), "alice");
+        assert_eq!(
+            render("tenant-${user}; id=${task_id}"),
+            "tenant-alice; id=1234"
+        );
+        assert_eq!(render("$$${user}$$"), "$alice$");
+        assert_eq!(render("${client_ip}"), "");
+        assert_eq!(render(""), "");
+    }
+
+    #[test]
+    fn to_string() {
+        for s in [
+            "static",
+            "tenant-${user}; id=${task_id}",
+            "$$${escaper}$$",
+            "",
+        ] {
+            let template = HttpHeaderValueTemplate::from_str(s).unwrap();
+            assert_eq!(template.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(HttpHeaderValueTemplate::from_str("${unknown}").is_err());
+        assert!(HttpHeaderValueTemplate::from_str("${user").is_err());
+        assert!(HttpHeaderValueTemplate::from_str("$user").is_err());
+        assert!(HttpHeaderValueTemplate::from_str("a\r\nb").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{
+    HttpHeaderRule, HttpHeaderRuleAction, HttpHeaderRuleBuilder, HttpHeaderRuleHost,
+    HttpHeaderRules,
+};
+
+impl HttpHeaderRule {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for 'http header rule' should be 'map'"
+            ));
+        };
+
+        let mut builder = HttpHeaderRuleBuilder::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "name" | "header" => {
+                let name = g3_yaml::value::as_string(v)?;
+                builder
+                    .set_name(&name)
+                    .context(format!("invalid header name value for key {k}"))
+            }
+            "action" => {
+                let action = g3_yaml::value::as_string(v)?;
+                builder.action = Some(action.to_lowercase());
+                Ok(())
+            }
+            "value" => {
+                let value = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                builder.value = Some(value);
+                Ok(())
+            }
+            "regex" => {
+                let regex = g3_yaml::value::as_regex(v)
+                    .context(format!("invalid regex value for key {k}"))?;
+                builder.regex = Some(regex);
+                Ok(())
+            }
+            "host" | "hosts" => {
+                let hosts = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                    .context(format!("invalid host string list value for key {k}"))?;
+                for host in hosts {
+                    builder.condition.add_host(&host);
+                }
+                Ok(())
+            }
+            "path" | "path_prefix" => {
+                let prefix = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                builder.condition.path_prefix = Some(prefix);
+                Ok(())
+            }
+            "path_regex" => {
+                let regex = g3_yaml::value::as_regex(v)
+                    .context(format!("invalid regex value for key {k}"))?;
+                builder.condition.path_regex = Some(regex);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        builder.build()
+    }
+}
+
+impl HttpHeaderRules {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for 'http header rules' should be 'map'"
+            ));
+        };
+
+        let mut rules = HttpHeaderRules::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "request" => {
+                rules.request = g3_yaml::value::as_list(v, HttpHeaderRule::parse_yaml)
+                    .context(format!("invalid http header rule list value for key {k}"))?;
+                Ok(())
+            }
+            "response" => {
+                rules.response = g3_yaml::value::as_list(v, HttpHeaderRule::parse_yaml)
+                    .context(format!("invalid http header rule list value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        Ok(rules)
+    }
+}
+
+impl YamlDump for HttpHeaderRule {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", self.name.as_str());
+        match &self.action {
+            HttpHeaderRuleAction::Add(v) => {
+                map.set("action", "add").set("value", &v.to_string());
+            }
+            HttpHeaderRuleAction::Set(v) => {
+                map.set("action", "set").set("value", &v.to_string());
+            }
+            HttpHeaderRuleAction::Remove => {
+                map.set("action", "remove");
+            }
+            HttpHeaderRuleAction::Replace(regex, v) => {
+                map.set("action", "replace")
+                    .set("regex", regex.as_str())
+                    .set("value", v);
+            }
+        }
+
+        let condition = &self.condition;
+        if !condition.hosts.is_empty() {
+            let hosts: Vec<String> = condition
+                .hosts
+                .iter()
+                .map(|h| match h {
+                    HttpHeaderRuleHost::Exact(v) => v.clone(),
+                    HttpHeaderRuleHost::Suffix(v) => format!("*{v}"),
+                })
+                .collect();
+            map.set("hosts", &hosts);
+        }
+        map.set("path_prefix", &condition.path_prefix).set(
+            "path_regex",
+            &condition.path_regex.as_ref().map(|r| r.as_str()),
+        );
+        map.build()
+    }
+}
+
+impl YamlDump for HttpHeaderRules {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        if !self.request.is_empty() {
+            map.set("request", &self.request);
+        }
+        if !self.response.is_empty() {
+            map.set("response", &self.response);
+        }
+        map.build()
+    }
+}
@@ -17,9 +17,15 @@ pub use mermaid::mermaid_graph;
 mod plantuml;
 pub use plantuml::plantuml_graph;
 
+pub(crate) mod validate;
+pub use validate::validate_report;
+
 pub(crate) mod audit;
 pub(crate) mod auth;
+pub(crate) mod discover;
+pub(crate) mod dump;
 pub(crate) mod escaper;
+pub(crate) mod http_header;
 pub(crate) mod log;
 pub(crate) mod resolver;
 pub(crate) mod server;
@@ -43,6 +49,7 @@ fn clear_all() {
     auth::clear();
     server::clear();
     resolver::clear();
+    discover::clear();
 }
 
 pub(crate) async fn reload() -> anyhow::Result<()> {
@@ -73,6 +80,7 @@ fn reload_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "resolver" => resolver::load_all(v, conf_dir),
         "user" | "user_group" => auth::load_all(v, conf_dir),
         "auditor" => audit::load_all(v, conf_dir),
+        "discover" => discover::load_all(v, conf_dir),
         _ => Ok(()),
     })?;
     Ok(())
@@ -92,6 +100,7 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "resolver" => resolver::load_all(v, conf_dir),
         "user" | "user_group" => auth::load_all(v, conf_dir),
         "auditor" => audit::load_all(v, conf_dir),
+        "discover" => discover::load_all(v, conf_dir),
         _ => Err(anyhow!("invalid key {k} in main conf")),
     })?;
     Ok(())
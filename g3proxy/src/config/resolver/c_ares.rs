@@ -13,6 +13,7 @@ use g3_resolver::driver::c_ares::CAresDriverConfig;
 use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyResolverConfig, ResolverConfigDiffAction};
 
@@ -77,15 +78,13 @@ impl CAresResolverConfig {
                 self.name = g3_yaml::value::as_metric_node_name(v)?;
                 Ok(())
             }
-            "graceful_stop_wait" => {
-                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
-                Ok(())
-            }
-            "protective_query_timeout" => {
-                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
-                Ok(())
+            _ => {
+                if super::set_runtime_config(&mut self.runtime, self.position.as_ref(), k, v)? {
+                    Ok(())
+                } else {
+                    self.driver.set_by_yaml_kv(k, v)
+                }
             }
-            _ => self.driver.set_by_yaml_kv(k, v),
         }
     }
 
@@ -126,3 +125,14 @@ impl super::ResolverConfig for CAresResolverConfig {
         None
     }
 }
+
+impl YamlDump for CAresResolverConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("type", RESOLVER_CONFIG_TYPE);
+        self.driver.dump_yaml_kv(&mut map);
+        super::dump_runtime_yaml(&mut map, &self.runtime);
+        map.build()
+    }
+}
@@ -10,6 +10,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
 
@@ -82,3 +83,12 @@ impl ResolverConfig for DenyAllResolverConfig {
         None
     }
 }
+
+impl YamlDump for DenyAllResolverConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("type", RESOLVER_CONFIG_TYPE);
+        map.build()
+    }
+}
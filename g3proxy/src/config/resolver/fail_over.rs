@@ -12,6 +12,7 @@ use g3_resolver::ResolverRuntimeConfig;
 use g3_resolver::driver::fail_over::FailOverDriverStaticConfig;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
 
@@ -81,15 +82,13 @@ impl FailOverResolverConfig {
                 self.static_conf.set_retry_empty_record(retry_empty_record);
                 Ok(())
             }
-            "graceful_stop_wait" => {
-                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
-                Ok(())
-            }
-            "protective_query_timeout" => {
-                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
-                Ok(())
+            _ => {
+                if super::set_runtime_config(&mut self.runtime, self.position.as_ref(), k, v)? {
+                    Ok(())
+                } else {
+                    Err(anyhow!("invalid key {k}"))
+                }
             }
-            _ => Err(anyhow!("invalid key {k}")),
         }
     }
 
@@ -145,3 +144,21 @@ impl ResolverConfig for FailOverResolverConfig {
         Some(set)
     }
 }
+
+impl YamlDump for FailOverResolverConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("type", RESOLVER_CONFIG_TYPE)
+            .set("primary", &self.primary)
+            .set("standby", &self.standby)
+            .set("fallback_delay", &self.static_conf.get_fallback_delay())
+            .set("negative_ttl", &self.static_conf.get_negative_ttl())
+            .set(
+                "retry_empty_record",
+                &self.static_conf.get_retry_empty_record(),
+            );
+        super::dump_runtime_yaml(&mut map, &self.runtime);
+        map.build()
+    }
+}
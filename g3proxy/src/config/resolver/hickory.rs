@@ -14,6 +14,7 @@ use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{AnyResolverConfig, ResolverConfigDiffAction};
 
@@ -85,15 +86,10 @@ impl HickoryResolverConfig {
                 self.name = g3_yaml::value::as_metric_node_name(v)?;
                 Ok(())
             }
-            "graceful_stop_wait" => {
-                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
-                Ok(())
-            }
-            "protective_query_timeout" => {
-                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
-                Ok(())
-            }
             _ => {
+                if super::set_runtime_config(&mut self.runtime, self.position.as_ref(), k, v)? {
+                    return Ok(());
+                }
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 self.driver.set_by_yaml_kv(k, v, Some(lookup_dir))
             }
@@ -137,3 +133,14 @@ impl super::ResolverConfig for HickoryResolverConfig {
         None
     }
 }
+
+impl YamlDump for HickoryResolverConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("type", RESOLVER_CONFIG_TYPE);
+        self.driver.dump_yaml_kv(&mut map);
+        super::dump_runtime_yaml(&mut map, &self.runtime);
+        map.build()
+    }
+}
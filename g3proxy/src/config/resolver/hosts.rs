@@ -0,0 +1,169 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_resolver::ResolverRuntimeConfig;
+use g3_resolver::driver::hosts::HostsDriverConfig;
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
+
+const RESOLVER_CONFIG_TYPE: &str = "hosts";
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct HostsResolverConfig {
+    position: Option<YamlDocPosition>,
+    name: NodeName,
+    pub(crate) runtime: ResolverRuntimeConfig,
+    pub(crate) driver: HostsDriverConfig,
+    pub(crate) fallback: Option<NodeName>,
+}
+
+impl HostsResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        HostsResolverConfig {
+            position,
+            name: NodeName::default(),
+            runtime: Default::default(),
+            driver: HostsDriverConfig::default(),
+            fallback: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "path" | "file" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                self.driver.set_path(path);
+                Ok(())
+            }
+            "records" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                g3_yaml::foreach_kv(map, |name, v| {
+                    let ips = g3_yaml::value::as_list(v, g3_yaml::value::as_ipaddr)
+                        .context(format!("invalid ip address list value for host {name}"))?;
+                    self.driver.add_record(name.to_string(), ips);
+                    Ok(())
+                })
+            }
+            "check_interval" => {
+                let interval = g3_yaml::humanize::as_duration(v)?;
+                self.driver.set_check_interval(interval);
+                Ok(())
+            }
+            "ttl" => {
+                let ttl = g3_yaml::value::as_u32(v)?;
+                self.driver.set_ttl(ttl);
+                Ok(())
+            }
+            "negative_ttl" | "protective_cache_ttl" => {
+                let ttl = g3_yaml::value::as_u32(v)?;
+                self.driver.set_negative_ttl(ttl);
+                Ok(())
+            }
+            "fallback" | "fallback_next" => {
+                self.fallback = Some(g3_yaml::value::as_metric_node_name(v)?);
+                Ok(())
+            }
+            _ => {
+                if super::set_runtime_config(&mut self.runtime, self.position.as_ref(), k, v)? {
+                    Ok(())
+                } else {
+                    Err(anyhow!("invalid key {k}"))
+                }
+            }
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if let Some(fallback) = &self.fallback {
+            if fallback.eq(&self.name) {
+                return Err(anyhow!("the fallback resolver should not be itself"));
+            }
+        }
+        self.driver.check()
+    }
+}
+
+impl ResolverConfig for HostsResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::Hosts(new) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new.as_ref()) {
+            return ResolverConfigDiffAction::NoAction;
+        }
+
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        let fallback = self.fallback.as_ref()?;
+        let mut set = BTreeSet::new();
+        set.insert(fallback.clone());
+        Some(set)
+    }
+}
+
+impl YamlDump for HostsResolverConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("type", RESOLVER_CONFIG_TYPE)
+            .set("path", &self.driver.path());
+        if !self.driver.records().is_empty() {
+            map.set("records", self.driver.records());
+        }
+        map.set("check_interval", &self.driver.check_interval())
+            .set("ttl", &self.driver.ttl())
+            .set("negative_ttl", &self.driver.negative_ttl())
+            .set("fallback", &self.fallback);
+        super::dump_runtime_yaml(&mut map, &self.runtime);
+        map.build()
+    }
+}
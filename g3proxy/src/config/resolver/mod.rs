@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::collections::BTreeSet;
 use std::path::Path;
 use std::sync::Arc;
@@ -12,7 +13,9 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_daemon::config::TopoMap;
 use g3_macros::AnyConfig;
+use g3_resolver::ResolverRuntimeConfig;
 use g3_types::metrics::NodeName;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 #[cfg(feature = "c-ares")]
@@ -21,6 +24,8 @@ pub(crate) mod hickory;
 
 pub(crate) mod deny_all;
 pub(crate) mod fail_over;
+pub(crate) mod hosts;
+pub(crate) mod route_domain;
 
 mod registry;
 pub(crate) use registry::clear;
@@ -46,14 +51,18 @@ pub(crate) trait ResolverConfig {
 #[derive(Clone, AnyConfig)]
 #[def_fn(name, &NodeName)]
 #[def_fn(position, Option<YamlDocPosition>)]
+#[def_fn(r#type, &'static str)]
 #[def_fn(dependent_resolver, Option<BTreeSet<NodeName>>)]
 #[def_fn(diff_action, &Self, ResolverConfigDiffAction)]
+#[def_fn(dump_yaml, bool, Yaml)]
 pub(crate) enum AnyResolverConfig {
     #[cfg(feature = "c-ares")]
     CAres(c_ares::CAresResolverConfig),
     Hickory(Box<hickory::HickoryResolverConfig>),
     DenyAll(deny_all::DenyAllResolverConfig),
     FailOver(fail_over::FailOverResolverConfig),
+    RouteDomain(route_domain::RouteDomainResolverConfig),
+    Hosts(Box<hosts::HostsResolverConfig>),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -73,6 +82,18 @@ pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse all resolver configs without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AnyResolverConfig>> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let config = load_resolver(map, position)?;
+        all.borrow_mut().push(config);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyResolverConfig> {
     let doc = g3_yaml::load_doc(position)?;
     if let Yaml::Hash(map) = doc {
@@ -121,10 +142,77 @@ fn load_resolver(
                 .context("failed to load this FailOver resolver")?;
             Ok(AnyResolverConfig::FailOver(resolver))
         }
+        "route_domain" | "routedomain" => {
+            let resolver = route_domain::RouteDomainResolverConfig::parse(map, position)
+                .context("failed to load this RouteDomain resolver")?;
+            Ok(AnyResolverConfig::RouteDomain(resolver))
+        }
+        "hosts" | "hosts_file" => {
+            let resolver = hosts::HostsResolverConfig::parse(map, position)
+                .context("failed to load this Hosts resolver")?;
+            Ok(AnyResolverConfig::Hosts(Box::new(resolver)))
+        }
         _ => Err(anyhow!("unsupported resolver type {resolver_type}")),
     }
 }
 
+/// Set the runtime config keys shared by all resolvers, return false if the key is not one of them
+fn set_runtime_config(
+    runtime: &mut ResolverRuntimeConfig,
+    position: Option<&YamlDocPosition>,
+    k: &str,
+    v: &Yaml,
+) -> anyhow::Result<bool> {
+    match g3_yaml::key::normalize(k).as_str() {
+        "graceful_stop_wait" => {
+            runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
+        }
+        "protective_query_timeout" => {
+            runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
+        }
+        "serve_stale_max_age" => {
+            runtime.serve_stale_max_age = g3_yaml::humanize::as_duration(v)?;
+        }
+        "serve_stale_client_timeout" => {
+            runtime.serve_stale_client_timeout = g3_yaml::humanize::as_duration(v)?;
+        }
+        "prefetch_window" => {
+            runtime.prefetch_window = g3_yaml::humanize::as_duration(v)?;
+        }
+        "prefetch_min_hits" => {
+            runtime.prefetch_min_hits = g3_yaml::value::as_usize(v)?;
+        }
+        "cache_snapshot_path" | "cache_snapshot_file" => {
+            let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+            let path = g3_yaml::value::as_file_path(v, lookup_dir, true)
+                .context(format!("invalid file path value for key {k}"))?;
+            runtime.cache_snapshot_path = Some(path);
+        }
+        "cache_snapshot_interval" => {
+            runtime.cache_snapshot_interval = g3_yaml::humanize::as_duration(v)?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn dump_runtime_yaml(map: &mut YamlMapDumper, runtime: &ResolverRuntimeConfig) {
+    map.set("graceful_stop_wait", &runtime.graceful_stop_wait)
+        .set(
+            "protective_query_timeout",
+            &runtime.protective_query_timeout,
+        )
+        .set("serve_stale_max_age", &runtime.serve_stale_max_age)
+        .set(
+            "serve_stale_client_timeout",
+            &runtime.serve_stale_client_timeout,
+        )
+        .set("prefetch_window", &runtime.prefetch_window)
+        .set("prefetch_min_hits", &runtime.prefetch_min_hits)
+        .set("cache_snapshot_path", &runtime.cache_snapshot_path)
+        .set("cache_snapshot_interval", &runtime.cache_snapshot_interval);
+}
+
 fn build_topology_map() -> anyhow::Result<TopoMap> {
     let mut topo_map = TopoMap::default();
 
@@ -0,0 +1,312 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_resolver::ResolverRuntimeConfig;
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
+
+const RESOLVER_CONFIG_TYPE: &str = "route-domain";
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct RouteDomainRuleConfig {
+    pub(crate) next: NodeName,
+    pub(crate) exact_match: BTreeSet<Arc<str>>,
+    pub(crate) child_match: BTreeSet<String>,
+    pub(crate) regex_match: BTreeSet<String>,
+}
+
+impl RouteDomainRuleConfig {
+    fn parse(map: &yaml::Hash) -> anyhow::Result<Self> {
+        let mut rule = RouteDomainRuleConfig::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "next" | "resolver" => {
+                rule.next = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "exact_match" | "exact" => {
+                let domains = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                rule.exact_match.extend(domains.into_iter().map(Arc::from));
+                Ok(())
+            }
+            "child_match" | "child" => {
+                let domains = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                rule.child_match.extend(domains);
+                Ok(())
+            }
+            "regex_match" | "regex" => {
+                let regexes = g3_yaml::value::as_list(v, g3_yaml::value::as_regex)
+                    .context(format!("invalid regex list value for key {k}"))?;
+                rule.regex_match
+                    .extend(regexes.into_iter().map(|r| r.as_str().to_string()));
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if rule.next.is_empty() {
+            return Err(anyhow!("no next resolver set"));
+        }
+        if rule.exact_match.is_empty() && rule.child_match.is_empty() && rule.regex_match.is_empty()
+        {
+            return Err(anyhow!("no match rules set"));
+        }
+        Ok(rule)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RouteDomainResolverConfig {
+    position: Option<YamlDocPosition>,
+    name: NodeName,
+    pub(crate) runtime: ResolverRuntimeConfig,
+    pub(crate) default_next: NodeName,
+    pub(crate) rules: Vec<RouteDomainRuleConfig>,
+    pub(crate) negative_ttl: Option<u32>,
+}
+
+impl RouteDomainResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        RouteDomainResolverConfig {
+            position,
+            name: NodeName::default(),
+            runtime: Default::default(),
+            default_next: NodeName::default(),
+            rules: Vec::new(),
+            negative_ttl: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "default_next" | "default" => {
+                self.default_next = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "rules" => {
+                let Yaml::Array(seq) = v else {
+                    return Err(anyhow!("invalid array value for key {k}"));
+                };
+                for (i, v) in seq.iter().enumerate() {
+                    let Yaml::Hash(map) = v else {
+                        return Err(anyhow!("yaml value type for #{i} should be map"));
+                    };
+                    let rule = RouteDomainRuleConfig::parse(map)
+                        .context(format!("invalid route rule for #{i}"))?;
+                    self.rules.push(rule);
+                }
+                Ok(())
+            }
+            "negative_ttl" | "protective_cache_ttl" => {
+                let ttl = g3_yaml::value::as_u32(v)?;
+                self.negative_ttl = Some(ttl);
+                Ok(())
+            }
+            _ => {
+                if super::set_runtime_config(&mut self.runtime, self.position.as_ref(), k, v)? {
+                    Ok(())
+                } else {
+                    Err(anyhow!("invalid key {k}"))
+                }
+            }
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.default_next.is_empty() {
+            return Err(anyhow!("no default next resolver set"));
+        }
+        if self.default_next.eq(&self.name) {
+            return Err(anyhow!("the default next resolver should not be itself"));
+        }
+        for (i, rule) in self.rules.iter().enumerate() {
+            if rule.next.eq(&self.name) {
+                return Err(anyhow!(
+                    "the next resolver for rule #{i} should not be itself"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ResolverConfig for RouteDomainResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::RouteDomain(new) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ResolverConfigDiffAction::NoAction;
+        }
+
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        let mut set = BTreeSet::new();
+        set.insert(self.default_next.clone());
+        for rule in &self.rules {
+            set.insert(rule.next.clone());
+        }
+        Some(set)
+    }
+}
+
+impl YamlDump for RouteDomainRuleConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("next", &self.next);
+        if !self.exact_match.is_empty() {
+            map.set("exact_match", &self.exact_match);
+        }
+        if !self.child_match.is_empty() {
+            map.set("child_match", &self.child_match);
+        }
+        if !self.regex_match.is_empty() {
+            map.set("regex_match", &self.regex_match);
+        }
+        map.build()
+    }
+}
+
+impl YamlDump for RouteDomainResolverConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", &self.name)
+            .set("type", RESOLVER_CONFIG_TYPE)
+            .set("default_next", &self.default_next)
+            .set("rules", &self.rules)
+            .set("negative_ttl", &self.negative_ttl);
+        super::dump_runtime_yaml(&mut map, &self.runtime);
+        map.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse() {
+        let conf = r#"
+        name: route
+        type: route_domain
+        default_next: public
+        rules:
+          - next: internal
+            exact_match: a.corp.example
+            child_match:
+              - corp.internal
+            regex_match:
+              - '^.*\.corp[0-9]+\.internal$'
+        "#;
+
+        let v = YamlLoader::load_from_str(conf).unwrap();
+        let Yaml::Hash(map) = &v[0] else {
+            panic!("not a map");
+        };
+        let config = RouteDomainResolverConfig::parse(map, None).unwrap();
+        assert_eq!(config.default_next.as_str(), "public");
+        assert_eq!(config.rules.len(), 1);
+        let rule = &config.rules[0];
+        assert_eq!(rule.next.as_str(), "internal");
+        assert!(rule.exact_match.contains("a.corp.example"));
+        assert!(rule.child_match.contains("corp.internal"));
+        assert_eq!(rule.regex_match.len(), 1);
+
+        let deps = config.dependent_resolver().unwrap();
+        assert_eq!(deps.len(), 2);
+    }
+
+    #[test]
+    fn parse_runtime() {
+        let conf = r#"
+        name: route
+        type: route_domain
+        default_next: public
+        serve_stale_max_age: 1h
+        prefetch_min_hits: 3
+        cache_snapshot_interval: 10m
+        "#;
+
+        let v = YamlLoader::load_from_str(conf).unwrap();
+        let Yaml::Hash(map) = &v[0] else {
+            panic!("not a map");
+        };
+        let config = RouteDomainResolverConfig::parse(map, None).unwrap();
+        assert_eq!(
+            config.runtime.serve_stale_max_age,
+            Duration::from_secs(3600)
+        );
+        assert_eq!(config.runtime.prefetch_min_hits, 3);
+        assert_eq!(
+            config.runtime.cache_snapshot_interval,
+            Duration::from_secs(600)
+        );
+    }
+
+    #[test]
+    fn parse_err() {
+        let conf = r#"
+        name: route
+        type: route_domain
+        rules:
+          - next: internal
+            child_match: corp.internal
+        "#;
+
+        let v = YamlLoader::load_from_str(conf).unwrap();
+        let Yaml::Hash(map) = &v[0] else {
+            panic!("not a map");
+        };
+        assert!(RouteDomainResolverConfig::parse(map, None).is_err());
+    }
+}
@@ -0,0 +1,450 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use ascii::AsciiString;
+use ip_network::IpNetwork;
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::acl::AclNetworkRuleBuilder;
+use g3_types::acl_set::AclDstHostRuleSetBuilder;
+use g3_types::limit::RateLimitQuotaConfig;
+use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::net::{TcpListenConfig, TcpMiscSockOpts, UdpListenConfig};
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{AnyServerConfig, ServerConfig, ServerConfigDiffAction};
+
+const SERVER_CONFIG_TYPE: &str = "DnsServer";
+
+const DEFAULT_DOH_PATH: &str = "/dns-query";
+const DEFAULT_MAX_CONCURRENT_QUERIES: usize = 1024;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct DnsClientHostFilterConfig {
+    pub(crate) networks: Vec<IpNetwork>,
+    pub(crate) dst_host_filter: AclDstHostRuleSetBuilder,
+}
+
+impl DnsClientHostFilterConfig {
+    fn parse(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for client host filter should be 'map'"
+            ));
+        };
+
+        let mut networks = Vec::new();
+        let mut dst_host_filter = None;
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "network" | "networks" => {
+                networks = g3_yaml::value::as_list(v, g3_yaml::value::as_ip_network)
+                    .context(format!("invalid list of ip network value for key {k}"))?;
+                Ok(())
+            }
+            "dst_host_filter_set" => {
+                let filter = g3_yaml::value::acl_set::as_dst_host_rule_set_builder(v)
+                    .context(format!("invalid dst host acl rule set value for key {k}"))?;
+                dst_host_filter = Some(filter);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if networks.is_empty() {
+            return Err(anyhow!("no client network set"));
+        }
+        let Some(dst_host_filter) = dst_host_filter else {
+            return Err(anyhow!("no dst host filter set"));
+        };
+        Ok(DnsClientHostFilterConfig {
+            networks,
+            dst_host_filter,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct DnsServerConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) resolver: NodeName,
+    pub(crate) shared_logger: Option<AsciiString>,
+    pub(crate) listen: Option<UdpListenConfig>,
+    pub(crate) tcp_listen: Option<TcpListenConfig>,
+    pub(crate) listen_in_worker: bool,
+    pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
+    pub(crate) client_dst_host_filters: Vec<DnsClientHostFilterConfig>,
+    pub(crate) query_rate_limit: Option<RateLimitQuotaConfig>,
+    pub(crate) client_query_rate_limit: Option<RateLimitQuotaConfig>,
+    pub(crate) max_concurrent_queries: usize,
+    pub(crate) forward_server: Option<SocketAddr>,
+    pub(crate) query_timeout: Duration,
+    pub(crate) answer_ttl: u32,
+    pub(crate) tcp_idle_timeout: Duration,
+    pub(crate) tcp_misc_opts: TcpMiscSockOpts,
+    pub(crate) doh_path: String,
+    pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
+}
+
+impl DnsServerConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        DnsServerConfig {
+            name: NodeName::default(),
+            position,
+            resolver: NodeName::default(),
+            shared_logger: None,
+            listen: None,
+            tcp_listen: None,
+            listen_in_worker: false,
+            ingress_net_filter: None,
+            dst_host_filter: None,
+            client_dst_host_filters: Vec::new(),
+            query_rate_limit: None,
+            client_query_rate_limit: None,
+            max_concurrent_queries: DEFAULT_MAX_CONCURRENT_QUERIES,
+            forward_server: None,
+            query_timeout: Duration::from_secs(8),
+            answer_ttl: 60,
+            tcp_idle_timeout: Duration::from_secs(10),
+            tcp_misc_opts: Default::default(),
+            doh_path: DEFAULT_DOH_PATH.to_string(),
+            extra_metrics_tags: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut server = DnsServerConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| server.set(k, v))?;
+
+        server.check()?;
+        Ok(server)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_SERVER_TYPE => Ok(()),
+            super::CONFIG_KEY_SERVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "resolver" => {
+                self.resolver = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "shared_logger" => {
+                let name = g3_yaml::value::as_ascii(v)?;
+                self.shared_logger = Some(name);
+                Ok(())
+            }
+            "extra_metrics_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                self.extra_metrics_tags = Some(Arc::new(tags));
+                Ok(())
+            }
+            "listen" | "udp_listen" => {
+                let config = g3_yaml::value::as_udp_listen_config(v)
+                    .context(format!("invalid udp listen config value for key {k}"))?;
+                self.listen = Some(config);
+                Ok(())
+            }
+            "tcp_listen" => {
+                let config = g3_yaml::value::as_tcp_listen_config(v)
+                    .context(format!("invalid tcp listen config value for key {k}"))?;
+                self.tcp_listen = Some(config);
+                Ok(())
+            }
+            "listen_in_worker" => {
+                self.listen_in_worker = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "ingress_network_filter" | "ingress_net_filter" => {
+                let filter = g3_yaml::value::acl::as_ingress_network_rule_builder(v).context(
+                    format!("invalid ingress network acl rule value for key {k}"),
+                )?;
+                self.ingress_net_filter = Some(filter);
+                Ok(())
+            }
+            "dst_host_filter_set" => {
+                let filter = g3_yaml::value::acl_set::as_dst_host_rule_set_builder(v)
+                    .context(format!("invalid dst host acl rule set value for key {k}"))?;
+                self.dst_host_filter = Some(filter);
+                Ok(())
+            }
+            "client_dst_host_filter_set" | "client_dst_host_filter_sets" => {
+                self.client_dst_host_filters =
+                    g3_yaml::value::as_list(v, DnsClientHostFilterConfig::parse)
+                        .context(format!("invalid client host filter list value for key {k}"))?;
+                Ok(())
+            }
+            "query_rate_limit" => {
+                let quota = g3_yaml::value::as_rate_limit_quota(v)
+                    .context(format!("invalid request quota value for key {k}"))?;
+                self.query_rate_limit = Some(quota);
+                Ok(())
+            }
+            "client_query_rate_limit" => {
+                let quota = g3_yaml::value::as_rate_limit_quota(v)
+                    .context(format!("invalid request quota value for key {k}"))?;
+                self.client_query_rate_limit = Some(quota);
+                Ok(())
+            }
+            "max_concurrent_queries" => {
+                self.max_concurrent_queries = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
+            "forward_server" => {
+                let addr = g3_yaml::value::as_sockaddr(v)
+                    .context(format!("invalid socket address value for key {k}"))?;
+                self.forward_server = Some(addr);
+                Ok(())
+            }
+            "query_timeout" => {
+                self.query_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "answer_ttl" => {
+                self.answer_ttl = g3_yaml::value::as_u32(v)?;
+                Ok(())
+            }
+            "tcp_idle_timeout" => {
+                self.tcp_idle_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "tcp_misc_opts" => {
+                self.tcp_misc_opts = g3_yaml::value::as_tcp_misc_sock_opts(v)
+                    .context(format!("invalid tcp misc sock opts value for key {k}"))?;
+                Ok(())
+            }
+            "doh_path" => {
+                let path = g3_yaml::value::as_string(v)?;
+                if !path.starts_with('/') {
+                    return Err(anyhow!("the doh path should start with '/'"));
+                }
+                self.doh_path = path;
+                Ok(())
+            }
+            "user_group" => Err(anyhow!(
+                "user auth is not supported for dns queries, \
+                 use client_dst_host_filter_sets and client_query_rate_limit instead"
+            )),
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.resolver.is_empty() {
+            return Err(anyhow!("resolver is not set"));
+        }
+        if let Some(listen) = &mut self.listen {
+            listen.check()?;
+        }
+        if self.max_concurrent_queries == 0 {
+            return Err(anyhow!("max concurrent queries should not be 0"));
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerConfig for DnsServerConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        SERVER_CONFIG_TYPE
+    }
+
+    fn escaper(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn resolver(&self) -> &NodeName {
+        &self.resolver
+    }
+
+    fn diff_action(&self, new: &AnyServerConfig) -> ServerConfigDiffAction {
+        let AnyServerConfig::DnsServer(new) = new else {
+            return ServerConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ServerConfigDiffAction::NoAction;
+        }
+
+        if self.listen != new.listen || self.tcp_listen != new.tcp_listen {
+            return ServerConfigDiffAction::ReloadAndRespawn;
+        }
+
+        ServerConfigDiffAction::ReloadNoRespawn
+    }
+
+    fn shared_logger(&self) -> Option<&str> {
+        self.shared_logger.as_ref().map(|s| s.as_str())
+    }
+}
+
+impl YamlDump for DnsClientHostFilterConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("networks", &self.networks)
+            .set("dst_host_filter_set", &self.dst_host_filter);
+        map.build()
+    }
+}
+
+impl YamlDump for DnsServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("resolver", &self.resolver)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("tcp_listen", &self.tcp_listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("dst_host_filter_set", &self.dst_host_filter);
+        if !self.client_dst_host_filters.is_empty() {
+            map.set("client_dst_host_filter_sets", &self.client_dst_host_filters);
+        }
+        map.set("query_rate_limit", &self.query_rate_limit)
+            .set("client_query_rate_limit", &self.client_query_rate_limit)
+            .set("max_concurrent_queries", &self.max_concurrent_queries)
+            .set("forward_server", &self.forward_server)
+            .set("query_timeout", &self.query_timeout)
+            .set("answer_ttl", &self.answer_ttl)
+            .set("tcp_idle_timeout", &self.tcp_idle_timeout)
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("doh_path", &self.doh_path);
+        map.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn parse_str(conf: &str) -> anyhow::Result<DnsServerConfig> {
+        let v = YamlLoader::load_from_str(conf).unwrap();
+        let Yaml::Hash(map) = &v[0] else {
+            panic!("not a map");
+        };
+        DnsServerConfig::parse(map, None)
+    }
+
+    #[test]
+    fn parse() {
+        let config = parse_str(
+            r#"
+            name: dns
+            type: dns_server
+            resolver: default
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.max_concurrent_queries,
+            DEFAULT_MAX_CONCURRENT_QUERIES
+        );
+        assert!(config.forward_server.is_none());
+        assert_eq!(config.answer_ttl, 60);
+
+        let config = parse_str(
+            r#"
+            name: dns
+            type: dns_server
+            resolver: default
+            max_concurrent_queries: 16
+            forward_server: 127.0.0.1:5353
+            answer_ttl: 300
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.max_concurrent_queries, 16);
+        assert_eq!(
+            config.forward_server,
+            Some(SocketAddr::from(([127, 0, 0, 1], 5353)))
+        );
+        assert_eq!(config.answer_ttl, 300);
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(
+            parse_str(
+                r#"
+                name: dns
+                type: dns_server
+                resolver: default
+                max_concurrent_queries: 0
+                "#,
+            )
+            .is_err()
+        );
+        assert!(
+            parse_str(
+                r#"
+                name: dns
+                type: dns_server
+                resolver: default
+                forward_server: 127.0.0.1
+                "#,
+            )
+            .is_err()
+        );
+        assert!(
+            parse_str(
+                r#"
+                name: dns
+                type: dns_server
+                "#,
+            )
+            .is_err()
+        );
+        assert!(
+            parse_str(
+                r#"
+                name: dns
+                type: dns_server
+                resolver: default
+                user_group: default
+                "#,
+            )
+            .is_err()
+        );
+    }
+}
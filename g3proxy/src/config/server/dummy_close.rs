@@ -8,6 +8,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::ServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
@@ -93,3 +94,12 @@ impl ServerConfig for DummyCloseServerConfig {
         ServerConfigDiffAction::NoAction
     }
 }
+
+impl YamlDump for DummyCloseServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE);
+        map.build()
+    }
+}
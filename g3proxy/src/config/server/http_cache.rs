@@ -0,0 +1,150 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpCacheDiskConfig {
+    pub(crate) path: PathBuf,
+    pub(crate) size: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HttpCacheConfig {
+    pub(crate) memory_size: usize,
+    pub(crate) memory_max_object_size: usize,
+    pub(crate) max_object_size: usize,
+    pub(crate) max_variants: usize,
+    pub(crate) heuristic_max_lifetime: Duration,
+    pub(crate) disk: Option<HttpCacheDiskConfig>,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        HttpCacheConfig {
+            memory_size: 64 << 20,           // 64MiB
+            memory_max_object_size: 1 << 20, // 1MiB
+            max_object_size: 16 << 20,       // 16MiB
+            max_variants: 8,
+            heuristic_max_lifetime: Duration::from_secs(86400),
+            disk: None,
+        }
+    }
+}
+
+impl HttpCacheConfig {
+    pub(crate) fn parse(
+        value: &Yaml,
+        position: Option<&YamlDocPosition>,
+    ) -> anyhow::Result<Option<Self>> {
+        let mut config = HttpCacheConfig::default();
+        let mut disk_path: Option<PathBuf> = None;
+        let mut disk_size: usize = 1 << 30; // 1GiB
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "memory_size" => {
+                        config.memory_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "memory_max_object_size" => {
+                        config.memory_max_object_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_object_size" => {
+                        config.max_object_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_variants" => {
+                        config.max_variants = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "heuristic_max_lifetime" => {
+                        config.heuristic_max_lifetime = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "disk_path" | "disk_directory" => {
+                        let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                        let path = g3_yaml::value::as_dir_path(v, lookup_dir, true)
+                            .context(format!("invalid directory path value for key {k}"))?;
+                        disk_path = Some(path);
+                        Ok(())
+                    }
+                    "disk_size" => {
+                        disk_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(enable) => {
+                if !enable {
+                    return Ok(None);
+                }
+            }
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'http cache config' should be 'map' or 'bool'"
+                ));
+            }
+        }
+
+        if let Some(path) = disk_path {
+            config.disk = Some(HttpCacheDiskConfig {
+                path,
+                size: disk_size,
+            });
+        }
+        config.check()?;
+        Ok(Some(config))
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.memory_size == 0 && self.disk.is_none() {
+            return Err(anyhow!("no memory or disk storage set"));
+        }
+        if self.max_variants == 0 {
+            return Err(anyhow!("max variants should not be 0"));
+        }
+        if self.memory_max_object_size > self.memory_size {
+            self.memory_max_object_size = self.memory_size;
+        }
+        if self.disk.is_none() && self.max_object_size > self.memory_max_object_size {
+            // all objects will be stored in memory
+            self.max_object_size = self.memory_max_object_size;
+        }
+        Ok(())
+    }
+}
+
+impl YamlDump for HttpCacheConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("memory_size", &self.memory_size)
+            .set("memory_max_object_size", &self.memory_max_object_size)
+            .set("max_object_size", &self.max_object_size)
+            .set("max_variants", &self.max_variants)
+            .set("heuristic_max_lifetime", &self.heuristic_max_lifetime);
+        if let Some(disk) = &self.disk {
+            map.set("disk_path", &disk.path)
+                .set("disk_size", &disk.size);
+        }
+        map.build()
+    }
+}
@@ -26,11 +26,13 @@ use g3_types::net::{
     TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
 };
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{
-    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
+    AnyServerConfig, HttpCacheConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
 };
+use crate::config::http_header::HttpHeaderRules;
 
 const SERVER_CONFIG_TYPE: &str = "HttpProxy";
 
@@ -92,6 +94,8 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) body_line_max_len: usize,
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
     pub(crate) http_forward_mark_upstream: bool,
+    pub(crate) http_cache: Option<Arc<HttpCacheConfig>>,
+    pub(crate) http_header_rules: Option<Arc<HttpHeaderRules>>,
     pub(crate) echo_chained_info: bool,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) egress_path_selection_header: Option<HeaderName>,
@@ -140,6 +144,8 @@ impl HttpProxyServerConfig {
             body_line_max_len: 8192,
             http_forward_upstream_keepalive: Default::default(),
             http_forward_mark_upstream: false,
+            http_cache: None,
+            http_header_rules: None,
             echo_chained_info: false,
             untrusted_read_limit: None,
             egress_path_selection_header: None,
@@ -382,6 +388,20 @@ impl HttpProxyServerConfig {
                 self.http_forward_mark_upstream = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "http_cache" => {
+                let config = HttpCacheConfig::parse(v, self.position.as_ref())
+                    .context(format!("invalid http cache config value for key {k}"))?;
+                self.http_cache = config.map(Arc::new);
+                Ok(())
+            }
+            "http_header_rules" => {
+                let rules = HttpHeaderRules::parse_yaml(v)
+                    .context(format!("invalid http header rules value for key {k}"))?;
+                if !rules.is_empty() {
+                    self.http_header_rules = Some(Arc::new(rules));
+                }
+                Ok(())
+            }
             "echo_chained_info" => {
                 self.echo_chained_info = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -498,3 +518,74 @@ impl ServerConfig for HttpProxyServerConfig {
         self.task_idle_max_count
     }
 }
+
+impl YamlDump for HttpProxyServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("escaper", &self.escaper)
+            .set("auditor", &self.auditor)
+            .set("user_group", &self.user_group)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("tls_server", &self.server_tls_config)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("tls_client", &self.client_tls_config)
+            .set("ftp_client", &self.ftp_client_config)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("dst_host_filter_set", &self.dst_host_filter)
+            .set("dst_port_filter", &self.dst_port_filter);
+        if !self.local_server_names.is_empty() {
+            map.set("local_server_name", &self.local_server_names);
+        }
+        map.set("server_id", &self.server_id)
+            .set("auth_realm", &self.auth_realm)
+            .set("tcp_sock_speed_limit", &self.tcp_sock_speed_limit)
+            .set("tcp_copy_buffer_size", &self.tcp_copy.buffer_size())
+            .set("tcp_copy_yield_size", &self.tcp_copy.yield_size())
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("task_idle_check_duration", &self.task_idle_check_duration)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set("flush_task_log_on_created", &self.flush_task_log_on_created)
+            .set(
+                "flush_task_log_on_connected",
+                &self.flush_task_log_on_connected,
+            )
+            .set("task_log_flush_interval", &self.task_log_flush_interval)
+            .set("req_header_recv_timeout", &self.timeout.recv_req_header)
+            .set("rsp_header_recv_timeout", &self.timeout.recv_rsp_header)
+            .set("req_header_max_size", &self.req_hdr_max_size)
+            .set("rsp_header_max_size", &self.rsp_hdr_max_size)
+            .set("log_uri_max_chars", &self.log_uri_max_chars)
+            .set("pipeline_size", &self.pipeline_size)
+            .set(
+                "pipeline_read_idle_timeout",
+                &self.pipeline_read_idle_timeout,
+            )
+            .set("no_early_error_reply", &self.no_early_error_reply)
+            .set("allow_custom_host", &self.allow_custom_host)
+            .set("drop_default_port_in_host", &self.drop_default_port_in_host)
+            .set("body_line_max_length", &self.body_line_max_len)
+            .set(
+                "http_forward_upstream_keepalive",
+                &self.http_forward_upstream_keepalive,
+            )
+            .set(
+                "http_forward_mark_upstream",
+                &self.http_forward_mark_upstream,
+            )
+            .set("http_cache", &self.http_cache)
+            .set("http_header_rules", &self.http_header_rules)
+            .set("echo_chained_info", &self.echo_chained_info)
+            .set("untrusted_read_speed_limit", &self.untrusted_read_limit)
+            .set(
+                "egress_path_selection_header",
+                &self.egress_path_selection_header,
+            )
+            .set("steal_forwarded_for", &self.steal_forwarded_for);
+        map.build()
+    }
+}
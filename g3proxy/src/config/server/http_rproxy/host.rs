@@ -3,16 +3,31 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::sync::Arc;
+
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
-use g3_types::net::{Host, OpensslClientConfigBuilder, RustlsServerConfigBuilder, UpstreamAddr};
+use g3_acme::AcmeConfig;
+use g3_discover::DiscoverRegisterData;
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::metrics::NodeName;
+use g3_types::net::{
+    Host, OpensslClientConfigBuilder, RustlsServerConfigBuilder, WeightedUpstreamAddr,
+};
+use g3_yaml::dump::YamlMapDumper;
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
+use crate::serve::UpstreamPickConfig;
+
 #[derive(Debug, PartialEq)]
 pub(crate) struct HttpHostConfig {
-    upstream: UpstreamAddr,
+    upstream: Vec<WeightedUpstreamAddr>,
+    upstream_pick_policy: SelectivePickPolicy,
+    upstream_discover: Option<NodeName>,
+    upstream_discover_data: DiscoverRegisterData,
     pub(crate) tls_server_builder: Option<RustlsServerConfigBuilder>,
+    pub(crate) acme: Option<Arc<AcmeConfig>>,
     pub(crate) tls_client_builder: Option<OpensslClientConfigBuilder>,
     pub(crate) tls_name: Host,
 }
@@ -20,8 +35,12 @@ pub(crate) struct HttpHostConfig {
 impl Default for HttpHostConfig {
     fn default() -> Self {
         HttpHostConfig {
-            upstream: UpstreamAddr::empty(),
+            upstream: Vec::new(),
+            upstream_pick_policy: SelectivePickPolicy::Random,
+            upstream_discover: None,
+            upstream_discover_data: DiscoverRegisterData::Null,
             tls_server_builder: None,
+            acme: None,
             tls_client_builder: None,
             tls_name: Host::empty(),
         }
@@ -29,9 +48,32 @@ impl Default for HttpHostConfig {
 }
 
 impl HttpHostConfig {
-    pub(crate) fn upstream(&self) -> &UpstreamAddr {
+    pub(super) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("upstream", &self.upstream)
+            .set("upstream_pick_policy", &self.upstream_pick_policy)
+            .set("upstream_discover", &self.upstream_discover)
+            .set("upstream_discover_data", &self.upstream_discover_data)
+            .set("tls_server", &self.tls_server_builder)
+            .set("acme", &self.acme)
+            .set("tls_client", &self.tls_client_builder)
+            .set("tls_name", &self.tls_name);
+    }
+}
+
+impl UpstreamPickConfig for HttpHostConfig {
+    fn upstream(&self) -> &[WeightedUpstreamAddr] {
         &self.upstream
     }
+
+    fn upstream_pick_policy(&self) -> SelectivePickPolicy {
+        self.upstream_pick_policy
+    }
+
+    fn upstream_discover(&self) -> Option<(&NodeName, &DiscoverRegisterData)> {
+        self.upstream_discover
+            .as_ref()
+            .map(|name| (name, &self.upstream_discover_data))
+    }
 }
 
 impl YamlMapCallback for HttpHostConfig {
@@ -47,8 +89,25 @@ impl YamlMapCallback for HttpHostConfig {
     ) -> anyhow::Result<()> {
         match key {
             "upstream" => {
-                self.upstream = g3_yaml::value::as_upstream_addr(value, 80)
-                    .context(format!("invalid upstream addr value for key {key}"))?;
+                self.upstream = g3_yaml::value::as_list(value, |v| {
+                    g3_yaml::value::as_weighted_upstream_addr(v, 80)
+                })
+                .context(format!(
+                    "invalid weighted upstream address list value for key {key}"
+                ))?;
+                Ok(())
+            }
+            "upstream_pick_policy" => {
+                self.upstream_pick_policy = g3_yaml::value::as_selective_pick_policy(value)?;
+                Ok(())
+            }
+            "upstream_discover" => {
+                let name = g3_yaml::value::as_metric_node_name(value)?;
+                self.upstream_discover = Some(name);
+                Ok(())
+            }
+            "upstream_discover_data" => {
+                self.upstream_discover_data = DiscoverRegisterData::Yaml(value.clone());
                 Ok(())
             }
             "tls_server" => {
@@ -61,6 +120,13 @@ impl YamlMapCallback for HttpHostConfig {
                 self.tls_server_builder = Some(builder);
                 Ok(())
             }
+            "acme" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                let config = AcmeConfig::parse_yaml(value, Some(lookup_dir))
+                    .context(format!("invalid acme config value for key {key}"))?;
+                self.acme = Some(Arc::new(config));
+                Ok(())
+            }
             "tls_client" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
                 let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
@@ -83,11 +149,17 @@ impl YamlMapCallback for HttpHostConfig {
     }
 
     fn check(&mut self) -> anyhow::Result<()> {
-        if self.upstream.is_empty() {
+        if self.upstream_discover.is_some() {
+            if self.upstream_discover_data == DiscoverRegisterData::Null {
+                return Err(anyhow!("upstream discover data is not set"));
+            }
+        } else if self.upstream.is_empty() {
             return Err(anyhow!("upstream is empty"));
         }
         if self.tls_name.is_empty() {
-            self.upstream.host().clone_into(&mut self.tls_name);
+            if let Some(upstream) = self.upstream.first() {
+                upstream.inner().host().clone_into(&mut self.tls_name);
+            }
         }
         Ok(())
     }
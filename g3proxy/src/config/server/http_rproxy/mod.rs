@@ -22,11 +22,13 @@ use g3_types::net::{
 };
 use g3_types::route::HostMatch;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper, host_matched_obj_to_yaml};
 
 use super::{
-    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
+    AnyServerConfig, HttpCacheConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
     IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
 };
+use crate::config::http_header::HttpHeaderRules;
 
 mod host;
 pub(crate) use host::HttpHostConfig;
@@ -80,6 +82,8 @@ pub(crate) struct HttpRProxyServerConfig {
     pub(crate) no_early_error_reply: bool,
     pub(crate) body_line_max_len: usize,
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
+    pub(crate) http_cache: Option<Arc<HttpCacheConfig>>,
+    pub(crate) http_header_rules: Option<Arc<HttpHeaderRules>>,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) append_forwarded_for: HttpForwardedHeaderType,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
@@ -120,6 +124,8 @@ impl HttpRProxyServerConfig {
             no_early_error_reply: false,
             body_line_max_len: 8192,
             http_forward_upstream_keepalive: Default::default(),
+            http_cache: None,
+            http_header_rules: None,
             untrusted_read_limit: None,
             append_forwarded_for: HttpForwardedHeaderType::default(),
             extra_metrics_tags: None,
@@ -297,6 +303,20 @@ impl HttpRProxyServerConfig {
                     .context(format!("invalid http keepalive config value for key {k}"))?;
                 Ok(())
             }
+            "http_cache" => {
+                let config = HttpCacheConfig::parse(v, self.position.as_ref())
+                    .context(format!("invalid http cache config value for key {k}"))?;
+                self.http_cache = config.map(Arc::new);
+                Ok(())
+            }
+            "http_header_rules" => {
+                let rules = HttpHeaderRules::parse_yaml(v)
+                    .context(format!("invalid http header rules value for key {k}"))?;
+                if !rules.is_empty() {
+                    self.http_header_rules = Some(Arc::new(rules));
+                }
+                Ok(())
+            }
             "untrusted_read_speed_limit" => {
                 let limit = g3_yaml::value::as_tcp_sock_speed_limit(v)
                     .context(format!("invalid tcp socket speed limit value for key {k}"))?;
@@ -431,3 +451,61 @@ impl ServerConfig for HttpRProxyServerConfig {
         self.task_idle_max_count
     }
 }
+
+impl YamlDump for HttpRProxyServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("escaper", &self.escaper)
+            .set("user_group", &self.user_group)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("server_id", &self.server_id)
+            .set("auth_realm", &self.auth_realm)
+            .set("tcp_sock_speed_limit", &self.tcp_sock_speed_limit)
+            .set("tcp_copy_buffer_size", &self.tcp_copy.buffer_size())
+            .set("tcp_copy_yield_size", &self.tcp_copy.yield_size())
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("task_idle_check_duration", &self.task_idle_check_duration)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set("flush_task_log_on_created", &self.flush_task_log_on_created)
+            .set(
+                "flush_task_log_on_connected",
+                &self.flush_task_log_on_connected,
+            )
+            .set("task_log_flush_interval", &self.task_log_flush_interval)
+            .set("req_header_recv_timeout", &self.timeout.recv_req_header)
+            .set("rsp_header_recv_timeout", &self.timeout.recv_rsp_header)
+            .set("req_header_max_size", &self.req_hdr_max_size)
+            .set("rsp_header_max_size", &self.rsp_hdr_max_size)
+            .set("log_uri_max_chars", &self.log_uri_max_chars)
+            .set("pipeline_size", &self.pipeline_size)
+            .set(
+                "pipeline_read_idle_timeout",
+                &self.pipeline_read_idle_timeout,
+            )
+            .set("no_early_error_reply", &self.no_early_error_reply)
+            .set("body_line_max_length", &self.body_line_max_len)
+            .set(
+                "http_forward_upstream_keepalive",
+                &self.http_forward_upstream_keepalive,
+            )
+            .set("http_cache", &self.http_cache)
+            .set("http_header_rules", &self.http_header_rules)
+            .set("untrusted_read_speed_limit", &self.untrusted_read_limit)
+            .set("append_forwarded_for", &self.append_forwarded_for)
+            .set_yaml(
+                "hosts",
+                host_matched_obj_to_yaml(&self.hosts, redact, HttpHostConfig::dump_yaml_kv),
+            )
+            .set("enable_tls_server", &self.enable_tls_server)
+            .set("global_tls_server", &self.global_tls_server)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("client_hello_recv_timeout", &self.client_hello_recv_timeout);
+        map.build()
+    }
+}
@@ -13,6 +13,7 @@ use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::NodeName;
 use g3_types::net::{ProxyProtocolVersion, TcpListenConfig};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::ServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
@@ -180,3 +181,26 @@ impl ServerConfig for IntelliProxyConfig {
         Some(set)
     }
 }
+
+impl YamlDump for IntelliProxyConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("http_server", &self.http_server)
+            .set("socks_server", &self.socks_server)
+            .set(
+                "protocol_detection_timeout",
+                &self.protocol_detection_timeout,
+            )
+            .set("proxy_protocol", &self.proxy_protocol)
+            .set(
+                "proxy_protocol_read_timeout",
+                &self.proxy_protocol_read_timeout,
+            );
+        map.build()
+    }
+}
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::collections::BTreeSet;
 use std::path::Path;
 use std::sync::Arc;
@@ -16,6 +17,7 @@ use g3_daemon::config::TopoMap;
 use g3_io_ext::StreamCopyConfig;
 use g3_macros::AnyConfig;
 use g3_types::metrics::NodeName;
+use g3_yaml::dump::YamlDump;
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 use crate::audit::AuditHandle;
@@ -29,6 +31,13 @@ pub(crate) mod plain_quic_port;
 pub(crate) mod plain_tcp_port;
 pub(crate) mod plain_tls_port;
 
+mod http_cache;
+pub(crate) use http_cache::{HttpCacheConfig, HttpCacheDiskConfig};
+
+mod upstream_affinity;
+pub(crate) use upstream_affinity::UpstreamAffinityConfig;
+
+pub(crate) mod dns_server;
 pub(crate) mod http_proxy;
 pub(crate) mod http_rproxy;
 pub(crate) mod sni_proxy;
@@ -42,6 +51,9 @@ pub(crate) mod tcp_stream;
 ))]
 pub(crate) mod tcp_tproxy;
 pub(crate) mod tls_stream;
+pub(crate) mod udp_stream;
+#[cfg(target_os = "linux")]
+pub(crate) mod udp_tproxy;
 
 mod registry;
 pub(crate) use registry::clear;
@@ -70,6 +82,9 @@ pub(crate) trait ServerConfig {
     fn escaper(&self) -> &NodeName;
     fn user_group(&self) -> &NodeName;
     fn auditor(&self) -> &NodeName;
+    fn resolver(&self) -> &NodeName {
+        Default::default()
+    }
 
     fn diff_action(&self, new: &AnyServerConfig) -> ServerConfigDiffAction;
 
@@ -127,7 +142,9 @@ pub(crate) trait ServerConfig {
 #[def_fn(escaper, &NodeName)]
 #[def_fn(user_group, &NodeName)]
 #[def_fn(auditor, &NodeName)]
+#[def_fn(resolver, &NodeName)]
 #[def_fn(diff_action, &Self, ServerConfigDiffAction)]
+#[def_fn(dump_yaml, bool, Yaml)]
 pub(crate) enum AnyServerConfig {
     DummyClose(dummy_close::DummyCloseServerConfig),
     PlainTcpPort(plain_tcp_port::PlainTcpPortConfig),
@@ -145,10 +162,14 @@ pub(crate) enum AnyServerConfig {
     ))]
     TcpTProxy(tcp_tproxy::TcpTProxyServerConfig),
     TlsStream(tls_stream::TlsStreamServerConfig),
+    UdpStream(udp_stream::UdpStreamServerConfig),
+    #[cfg(target_os = "linux")]
+    UdpTProxy(udp_tproxy::UdpTProxyServerConfig),
     SniProxy(sni_proxy::SniProxyServerConfig),
     SocksProxy(socks_proxy::SocksProxyServerConfig),
     HttpProxy(http_proxy::HttpProxyServerConfig),
     HttpRProxy(http_rproxy::HttpRProxyServerConfig),
+    DnsServer(dns_server::DnsServerConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -168,6 +189,18 @@ pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Parse all server configs without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AnyServerConfig>> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let config = load_server(map, position)?;
+        all.borrow_mut().push(config);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyServerConfig> {
     let doc = g3_yaml::load_doc(position)?;
     if let Yaml::Hash(map) = doc {
@@ -249,6 +282,17 @@ fn load_server(
                 .context("failed to load this TLsStream server")?;
             Ok(AnyServerConfig::TlsStream(server))
         }
+        "udp_stream" | "udpstream" => {
+            let server = udp_stream::UdpStreamServerConfig::parse(map, position)
+                .context("failed to load this UdpStream server")?;
+            Ok(AnyServerConfig::UdpStream(server))
+        }
+        #[cfg(target_os = "linux")]
+        "udp_tproxy" | "udptproxy" => {
+            let server = udp_tproxy::UdpTProxyServerConfig::parse(map, position)
+                .context("failed to load this UdpTProxy server")?;
+            Ok(AnyServerConfig::UdpTProxy(server))
+        }
         "sni_proxy" | "sniproxy" => {
             let server = sni_proxy::SniProxyServerConfig::parse(map, position)
                 .context("failed to load this SniProxy server")?;
@@ -270,6 +314,11 @@ fn load_server(
                 .context("failed to load this HttpRProxy server")?;
             Ok(AnyServerConfig::HttpRProxy(server))
         }
+        "dns_server" | "dnsserver" => {
+            let server = dns_server::DnsServerConfig::parse(map, position)
+                .context("failed to load this DnsServer server")?;
+            Ok(AnyServerConfig::DnsServer(server))
+        }
         _ => Err(anyhow!("unsupported server type {}", server_type)),
     }
 }
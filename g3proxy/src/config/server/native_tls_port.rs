@@ -4,16 +4,19 @@
  */
 
 use std::collections::BTreeSet;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
+use g3_acme::{AcmeChallengeType, AcmeConfig};
 use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::NodeName;
 use g3_types::net::{OpensslServerConfigBuilder, ProxyProtocolVersion, TcpListenConfig};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::ServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
@@ -28,6 +31,7 @@ pub(crate) struct NativeTlsPortConfig {
     pub(crate) listen_in_worker: bool,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) server_tls_config: Option<OpensslServerConfigBuilder>,
+    pub(crate) acme: Option<Arc<AcmeConfig>>,
     pub(crate) tls_ticketer: Option<TlsTicketConfig>,
     pub(crate) server: NodeName,
     pub(crate) proxy_protocol: Option<ProxyProtocolVersion>,
@@ -43,6 +47,7 @@ impl NativeTlsPortConfig {
             listen_in_worker: false,
             ingress_net_filter: None,
             server_tls_config: None,
+            acme: None,
             tls_ticketer: None,
             server: NodeName::default(),
             proxy_protocol: None,
@@ -93,6 +98,13 @@ impl NativeTlsPortConfig {
                 self.server_tls_config = Some(builder);
                 Ok(())
             }
+            "acme" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = AcmeConfig::parse_yaml(v, Some(lookup_dir))
+                    .context(format!("invalid acme config value for key {k}"))?;
+                self.acme = Some(Arc::new(config));
+                Ok(())
+            }
             "tls_ticketer" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 let ticketer = TlsTicketConfig::parse_yaml(v, Some(lookup_dir))
@@ -129,7 +141,12 @@ impl NativeTlsPortConfig {
         }
         // make sure listen is always set
         self.listen.check().context("invalid listen config")?;
-        if self.server_tls_config.is_none() {
+        if let Some(acme) = &self.acme {
+            // the challenge should be responded by a http reverse proxy server
+            if acme.challenge_type() != AcmeChallengeType::Http01 {
+                return Err(anyhow!("only acme http-01 challenge is supported"));
+            }
+        } else if self.server_tls_config.is_none() {
             return Err(anyhow!("tls server config is not set"));
         }
 
@@ -184,3 +201,24 @@ impl ServerConfig for NativeTlsPortConfig {
         Some(set)
     }
 }
+
+impl YamlDump for NativeTlsPortConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("tls_server", &self.server_tls_config)
+            .set("acme", &self.acme)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("server", &self.server)
+            .set("proxy_protocol", &self.proxy_protocol)
+            .set(
+                "proxy_protocol_read_timeout",
+                &self.proxy_protocol_read_timeout,
+            );
+        map.build()
+    }
+}
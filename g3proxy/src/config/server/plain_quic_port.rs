@@ -14,6 +14,7 @@ use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::NodeName;
 use g3_types::net::{RustlsServerConfigBuilder, UdpListenConfig};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::ServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
@@ -190,3 +191,19 @@ impl ServerConfig for PlainQuicPortConfig {
         Some(set)
     }
 }
+
+impl YamlDump for PlainQuicPortConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("offline_rebind_port", &self.offline_rebind_port)
+            .set("quic_server", &self.tls_server)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("server", &self.server);
+        map.build()
+    }
+}
@@ -13,6 +13,7 @@ use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::NodeName;
 use g3_types::net::{ProxyProtocolVersion, TcpListenConfig};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::ServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
@@ -161,3 +162,21 @@ impl ServerConfig for PlainTcpPortConfig {
         Some(set)
     }
 }
+
+impl YamlDump for PlainTcpPortConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("server", &self.server)
+            .set("proxy_protocol", &self.proxy_protocol)
+            .set(
+                "proxy_protocol_read_timeout",
+                &self.proxy_protocol_read_timeout,
+            );
+        map.build()
+    }
+}
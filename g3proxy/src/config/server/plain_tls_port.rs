@@ -14,6 +14,7 @@ use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::NodeName;
 use g3_types::net::{ProxyProtocolVersion, RustlsServerConfigBuilder, TcpListenConfig};
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::ServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
@@ -183,3 +184,23 @@ impl ServerConfig for PlainTlsPortConfig {
         Some(set)
     }
 }
+
+impl YamlDump for PlainTlsPortConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("tls_server", &self.server_tls_config)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("server", &self.server)
+            .set("proxy_protocol", &self.proxy_protocol)
+            .set(
+                "proxy_protocol_read_timeout",
+                &self.proxy_protocol_read_timeout,
+            );
+        map.build()
+    }
+}
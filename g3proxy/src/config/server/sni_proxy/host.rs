@@ -7,6 +7,7 @@ use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
 use g3_types::net::{Host, UpstreamAddr};
+use g3_yaml::dump::YamlMapDumper;
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
 #[derive(Default, Debug, Eq, PartialEq)]
@@ -20,6 +21,11 @@ impl SniHostConfig {
         Ok(())
     }
 
+    pub(super) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("redirect_host", &self.redirect_host)
+            .set("redirect_port", &self.redirect_port);
+    }
+
     pub(crate) fn redirect(&self, orig_ups: &UpstreamAddr) -> UpstreamAddr {
         if let Some(host) = &self.redirect_host {
             let port = self.redirect_port.unwrap_or_else(|| orig_ups.port());
@@ -18,6 +18,7 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig};
 use g3_types::route::HostMatch;
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper, host_matched_obj_to_yaml};
 
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
@@ -308,3 +309,41 @@ impl ServerConfig for SniProxyServerConfig {
         self.task_idle_max_count
     }
 }
+
+impl YamlDump for SniProxyServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("escaper", &self.escaper)
+            .set("auditor", &self.auditor)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("tcp_sock_speed_limit", &self.tcp_sock_speed_limit)
+            .set("tcp_copy_buffer_size", &self.tcp_copy.buffer_size())
+            .set("tcp_copy_yield_size", &self.tcp_copy.yield_size())
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("tls_max_client_hello_size", &self.tls_max_client_hello_size)
+            .set("task_idle_check_duration", &self.task_idle_check_duration)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set("flush_task_log_on_created", &self.flush_task_log_on_created)
+            .set(
+                "flush_task_log_on_connected",
+                &self.flush_task_log_on_connected,
+            )
+            .set("task_log_flush_interval", &self.task_log_flush_interval)
+            .set("request_wait_timeout", &self.request_wait_timeout)
+            .set("request_recv_timeout", &self.request_recv_timeout)
+            .set("protocol_inspection", &self.protocol_inspection)
+            .set("server_tcp_portmap", &self.server_tcp_portmap)
+            .set("client_tcp_portmap", &self.client_tcp_portmap);
+        if let Some(sites) = &self.allowed_sites {
+            let v = host_matched_obj_to_yaml(sites, redact, SniHostConfig::dump_yaml_kv);
+            map.set_yaml("allowed_sites", v);
+        }
+        map.build()
+    }
+}
@@ -22,6 +22,7 @@ use g3_types::net::{
     UdpMiscSockOpts, UdpSockSpeedLimitConfig,
 };
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
@@ -423,3 +424,58 @@ impl ServerConfig for SocksProxyServerConfig {
         self.task_idle_max_count
     }
 }
+
+impl YamlDump for SocksProxyServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("escaper", &self.escaper)
+            .set("auditor", &self.auditor)
+            .set("user_group", &self.user_group)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("use_udp_associate", &self.use_udp_associate)
+            .set("udp_bind_ipv4", &self.udp_bind4)
+            .set("udp_bind_ipv6", &self.udp_bind6)
+            .set("udp_bind_port_range", &self.udp_bind_port_range)
+            .set("udp_socket_buffer", &self.udp_socket_buffer)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("dst_host_filter_set", &self.dst_host_filter)
+            .set("dst_port_filter", &self.dst_port_filter)
+            .set("tcp_sock_speed_limit", &self.tcp_sock_speed_limit)
+            .set("udp_sock_speed_limit", &self.udp_sock_speed_limit)
+            .set("tcp_copy_buffer_size", &self.tcp_copy.buffer_size())
+            .set("tcp_copy_yield_size", &self.tcp_copy.yield_size())
+            .set("udp_relay_packet_size", &self.udp_relay.packet_size())
+            .set("udp_relay_yield_size", &self.udp_relay.yield_size())
+            .set("udp_relay_batch_size", &self.udp_relay.batch_size())
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set("negotiation_timeout", &self.timeout.negotiation)
+            .set(
+                "udp_client_initial_timeout",
+                &self.timeout.udp_client_initial,
+            )
+            .set("task_idle_check_duration", &self.task_idle_check_duration)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set("flush_task_log_on_created", &self.flush_task_log_on_created)
+            .set(
+                "flush_task_log_on_connected",
+                &self.flush_task_log_on_connected,
+            )
+            .set("task_log_flush_interval", &self.task_log_flush_interval);
+        match &self.transmute_udp_echo_ip {
+            Some(ip_map) if ip_map.is_empty() => {
+                map.set("transmute_udp_echo_ip", &true);
+            }
+            Some(ip_map) => {
+                map.set("transmute_udp_echo_ip", ip_map);
+            }
+            None => {}
+        }
+        map.build()
+    }
+}
@@ -11,6 +11,7 @@ use ascii::AsciiString;
 use log::warn;
 use yaml_rust::{Yaml, yaml};
 
+use g3_discover::DiscoverRegisterData;
 use g3_io_ext::StreamCopyConfig;
 use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::AclNetworkRuleBuilder;
@@ -21,11 +22,13 @@ use g3_types::net::{
     TcpSockSpeedLimitConfig, WeightedUpstreamAddr,
 };
 use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
 
 use super::{
     AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
-    IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
+    IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction, UpstreamAffinityConfig,
 };
+use crate::serve::UpstreamPickConfig;
 
 const SERVER_CONFIG_TYPE: &str = "TlsStream";
 
@@ -44,6 +47,10 @@ pub(crate) struct TlsStreamServerConfig {
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) upstream: Vec<WeightedUpstreamAddr>,
     pub(crate) upstream_pick_policy: SelectivePickPolicy,
+    pub(crate) upstream_slow_start: Duration,
+    pub(crate) upstream_affinity: Option<UpstreamAffinityConfig>,
+    pub(crate) upstream_discover: Option<NodeName>,
+    pub(crate) upstream_discover_data: DiscoverRegisterData,
     pub(crate) upstream_tls_name: Option<Host>,
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
     pub(crate) task_idle_check_duration: Duration,
@@ -72,6 +79,10 @@ impl TlsStreamServerConfig {
             ingress_net_filter: None,
             upstream: Vec::new(),
             upstream_pick_policy: SelectivePickPolicy::Random,
+            upstream_slow_start: Duration::ZERO,
+            upstream_affinity: None,
+            upstream_discover: None,
+            upstream_discover_data: DiscoverRegisterData::Null,
             upstream_tls_name: None,
             tcp_sock_speed_limit: TcpSockSpeedLimitConfig::default(),
             task_idle_check_duration: IDLE_CHECK_DEFAULT_DURATION,
@@ -185,6 +196,26 @@ impl TlsStreamServerConfig {
                 self.upstream_pick_policy = g3_yaml::value::as_selective_pick_policy(v)?;
                 Ok(())
             }
+            "upstream_slow_start" => {
+                self.upstream_slow_start = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "upstream_affinity" => {
+                self.upstream_affinity = UpstreamAffinityConfig::parse(v).context(format!(
+                    "invalid upstream affinity config value for key {k}"
+                ))?;
+                Ok(())
+            }
+            "upstream_discover" => {
+                let name = g3_yaml::value::as_metric_node_name(v)?;
+                self.upstream_discover = Some(name);
+                Ok(())
+            }
+            "upstream_discover_data" => {
+                self.upstream_discover_data = DiscoverRegisterData::Yaml(v.clone());
+                Ok(())
+            }
             "upstream_tls_name" => {
                 let tls_name = g3_yaml::value::as_host(v)
                     .context(format!("invalid tls server name value for key {k}"))?;
@@ -252,7 +283,11 @@ impl TlsStreamServerConfig {
         if self.escaper.is_empty() {
             return Err(anyhow!("escaper is not set"));
         }
-        if self.upstream.is_empty() {
+        if self.upstream_discover.is_some() {
+            if self.upstream_discover_data == DiscoverRegisterData::Null {
+                return Err(anyhow!("upstream discover data is not set"));
+            }
+        } else if self.upstream.is_empty() {
             return Err(anyhow!("upstream is not set"));
         }
 
@@ -332,3 +367,65 @@ impl ServerConfig for TlsStreamServerConfig {
         self.task_idle_max_count
     }
 }
+
+impl UpstreamPickConfig for TlsStreamServerConfig {
+    fn upstream(&self) -> &[WeightedUpstreamAddr] {
+        &self.upstream
+    }
+
+    fn upstream_pick_policy(&self) -> SelectivePickPolicy {
+        self.upstream_pick_policy
+    }
+
+    fn upstream_slow_start(&self) -> Duration {
+        self.upstream_slow_start
+    }
+
+    fn upstream_affinity(&self) -> Option<&UpstreamAffinityConfig> {
+        self.upstream_affinity.as_ref()
+    }
+
+    fn upstream_discover(&self) -> Option<(&NodeName, &DiscoverRegisterData)> {
+        self.upstream_discover
+            .as_ref()
+            .map(|name| (name, &self.upstream_discover_data))
+    }
+}
+
+impl YamlDump for TlsStreamServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("escaper", &self.escaper)
+            .set("auditor", &self.auditor)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("tls_server", &self.server_tls_config)
+            .set("tls_ticketer", &self.tls_ticketer)
+            .set("tls_client", &self.client_tls_config)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("upstream", &self.upstream)
+            .set("upstream_pick_policy", &self.upstream_pick_policy)
+            .set("upstream_slow_start", &self.upstream_slow_start)
+            .set("upstream_affinity", &self.upstream_affinity)
+            .set("upstream_discover", &self.upstream_discover)
+            .set("upstream_discover_data", &self.upstream_discover_data)
+            .set("upstream_tls_name", &self.upstream_tls_name)
+            .set("tcp_sock_speed_limit", &self.tcp_sock_speed_limit)
+            .set("tcp_copy_buffer_size", &self.tcp_copy.buffer_size())
+            .set("tcp_copy_yield_size", &self.tcp_copy.yield_size())
+            .set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("task_idle_check_duration", &self.task_idle_check_duration)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set("flush_task_log_on_created", &self.flush_task_log_on_created)
+            .set(
+                "flush_task_log_on_connected",
+                &self.flush_task_log_on_connected,
+            )
+            .set("task_log_flush_interval", &self.task_log_flush_interval);
+        map.build()
+    }
+}
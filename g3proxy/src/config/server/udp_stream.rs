@@ -0,0 +1,347 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use ascii::AsciiString;
+use yaml_rust::{Yaml, yaml};
+
+use g3_io_ext::LimitedUdpRelayConfig;
+use g3_types::acl::AclNetworkRuleBuilder;
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::net::{
+    SocketBufferConfig, UdpListenConfig, UdpMiscSockOpts, UdpSockSpeedLimitConfig,
+    WeightedUpstreamAddr,
+};
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{
+    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
+    IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction,
+};
+use crate::serve::UdpFlowLimit;
+
+const SERVER_CONFIG_TYPE: &str = "UdpStream";
+
+const DEFAULT_FLOW_QUEUE_SIZE: usize = 64;
+const DEFAULT_MAX_FLOWS: usize = 65536;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UdpStreamServerConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) escaper: NodeName,
+    pub(crate) auditor: NodeName,
+    pub(crate) shared_logger: Option<AsciiString>,
+    pub(crate) listen: UdpListenConfig,
+    pub(crate) listen_in_worker: bool,
+    pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) upstream: Vec<WeightedUpstreamAddr>,
+    pub(crate) upstream_pick_policy: SelectivePickPolicy,
+    pub(crate) udp_sock_speed_limit: UdpSockSpeedLimitConfig,
+    pub(crate) udp_socket_buffer: SocketBufferConfig,
+    pub(crate) udp_relay: LimitedUdpRelayConfig,
+    pub(crate) udp_misc_opts: UdpMiscSockOpts,
+    pub(crate) flow_queue_size: usize,
+    pub(crate) flow_limit: UdpFlowLimit,
+    pub(crate) task_idle_check_duration: Duration,
+    pub(crate) task_idle_max_count: usize,
+    pub(crate) flush_task_log_on_created: bool,
+    pub(crate) flush_task_log_on_connected: bool,
+    pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
+}
+
+impl UdpStreamServerConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        UdpStreamServerConfig {
+            name: NodeName::default(),
+            position,
+            escaper: NodeName::default(),
+            auditor: NodeName::default(),
+            shared_logger: None,
+            listen: UdpListenConfig::default(),
+            listen_in_worker: false,
+            ingress_net_filter: None,
+            upstream: Vec::new(),
+            upstream_pick_policy: SelectivePickPolicy::Random,
+            udp_sock_speed_limit: UdpSockSpeedLimitConfig::default(),
+            udp_socket_buffer: SocketBufferConfig::default(),
+            udp_relay: Default::default(),
+            udp_misc_opts: Default::default(),
+            flow_queue_size: DEFAULT_FLOW_QUEUE_SIZE,
+            flow_limit: UdpFlowLimit {
+                max_flows: DEFAULT_MAX_FLOWS,
+                max_flows_per_client: 0,
+            },
+            task_idle_check_duration: IDLE_CHECK_DEFAULT_DURATION,
+            task_idle_max_count: IDLE_CHECK_DEFAULT_MAX_COUNT,
+            flush_task_log_on_created: false,
+            flush_task_log_on_connected: false,
+            task_log_flush_interval: None,
+            extra_metrics_tags: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut server = UdpStreamServerConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| server.set(k, v))?;
+
+        server.check()?;
+        Ok(server)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_SERVER_TYPE => Ok(()),
+            super::CONFIG_KEY_SERVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "escaper" => {
+                self.escaper = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "auditor" => {
+                self.auditor = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "shared_logger" => {
+                let name = g3_yaml::value::as_ascii(v)?;
+                self.shared_logger = Some(name);
+                Ok(())
+            }
+            "extra_metrics_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                self.extra_metrics_tags = Some(Arc::new(tags));
+                Ok(())
+            }
+            "listen" => {
+                self.listen = g3_yaml::value::as_udp_listen_config(v)
+                    .context(format!("invalid udp listen config value for key {k}"))?;
+                Ok(())
+            }
+            "listen_in_worker" => {
+                self.listen_in_worker = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "ingress_network_filter" | "ingress_net_filter" => {
+                let filter = g3_yaml::value::acl::as_ingress_network_rule_builder(v).context(
+                    format!("invalid ingress network acl rule value for key {k}"),
+                )?;
+                self.ingress_net_filter = Some(filter);
+                Ok(())
+            }
+            "upstream" | "proxy_pass" => {
+                self.upstream =
+                    g3_yaml::value::as_list(v, |v| g3_yaml::value::as_weighted_upstream_addr(v, 0))
+                        .context(format!(
+                            "invalid weighted upstream address value for key {k}"
+                        ))?;
+                Ok(())
+            }
+            "upstream_pick_policy" => {
+                self.upstream_pick_policy = g3_yaml::value::as_selective_pick_policy(v)?;
+                Ok(())
+            }
+            "udp_sock_speed_limit" => {
+                self.udp_sock_speed_limit = g3_yaml::value::as_udp_sock_speed_limit(v)
+                    .context(format!("invalid udp socket speed limit value for key {k}"))?;
+                Ok(())
+            }
+            "udp_socket_buffer" => {
+                self.udp_socket_buffer = g3_yaml::value::as_socket_buffer_config(v)
+                    .context(format!("invalid socket buffer config value for key {k}"))?;
+                Ok(())
+            }
+            "udp_relay_packet_size" => {
+                let packet_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.udp_relay.set_packet_size(packet_size);
+                Ok(())
+            }
+            "udp_relay_yield_size" => {
+                let yield_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.udp_relay.set_yield_size(yield_size);
+                Ok(())
+            }
+            "udp_relay_batch_size" => {
+                let batch_size = g3_yaml::value::as_usize(v)?;
+                self.udp_relay.set_batch_size(batch_size);
+                Ok(())
+            }
+            "udp_misc_opts" => {
+                self.udp_misc_opts = g3_yaml::value::as_udp_misc_sock_opts(v)
+                    .context(format!("invalid udp misc sock opts value for key {k}"))?;
+                Ok(())
+            }
+            "flow_queue_size" => {
+                self.flow_queue_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "max_flows" => {
+                self.flow_limit.max_flows = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "max_flows_per_client" => {
+                self.flow_limit.max_flows_per_client = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "task_idle_check_duration" => {
+                self.task_idle_check_duration = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "task_idle_max_count" => {
+                self.task_idle_max_count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "flush_task_log_on_created" => {
+                self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "flush_task_log_on_connected" => {
+                self.flush_task_log_on_connected = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "task_log_flush_interval" => {
+                let interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.task_log_flush_interval = Some(interval);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.escaper.is_empty() {
+            return Err(anyhow!("escaper is not set"));
+        }
+        if self.upstream.is_empty() {
+            return Err(anyhow!("upstream is not set"));
+        }
+        if self.flow_queue_size == 0 {
+            self.flow_queue_size = DEFAULT_FLOW_QUEUE_SIZE;
+        }
+        if self.task_idle_check_duration > IDLE_CHECK_MAXIMUM_DURATION {
+            self.task_idle_check_duration = IDLE_CHECK_MAXIMUM_DURATION;
+        }
+
+        self.listen.check()?;
+
+        Ok(())
+    }
+}
+
+impl ServerConfig for UdpStreamServerConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        SERVER_CONFIG_TYPE
+    }
+
+    fn escaper(&self) -> &NodeName {
+        &self.escaper
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        &self.auditor
+    }
+
+    fn diff_action(&self, new: &AnyServerConfig) -> ServerConfigDiffAction {
+        let AnyServerConfig::UdpStream(new) = new else {
+            return ServerConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ServerConfigDiffAction::NoAction;
+        }
+
+        if self.listen != new.listen {
+            return ServerConfigDiffAction::ReloadAndRespawn;
+        }
+
+        ServerConfigDiffAction::ReloadNoRespawn
+    }
+
+    fn shared_logger(&self) -> Option<&str> {
+        self.shared_logger.as_ref().map(|s| s.as_str())
+    }
+
+    fn task_log_flush_interval(&self) -> Option<Duration> {
+        self.task_log_flush_interval
+    }
+
+    #[inline]
+    fn task_max_idle_count(&self) -> usize {
+        self.task_idle_max_count
+    }
+}
+
+impl YamlDump for UdpStreamServerConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set(super::CONFIG_KEY_SERVER_NAME, &self.name)
+            .set(super::CONFIG_KEY_SERVER_TYPE, SERVER_CONFIG_TYPE)
+            .set("escaper", &self.escaper)
+            .set("auditor", &self.auditor)
+            .set("shared_logger", &self.shared_logger)
+            .set("extra_metrics_tags", &self.extra_metrics_tags)
+            .set("listen", &self.listen)
+            .set("listen_in_worker", &self.listen_in_worker)
+            .set("ingress_network_filter", &self.ingress_net_filter)
+            .set("upstream", &self.upstream)
+            .set("upstream_pick_policy", &self.upstream_pick_policy)
+            .set("udp_sock_speed_limit", &self.udp_sock_speed_limit)
+            .set("udp_socket_buffer", &self.udp_socket_buffer)
+            .set("udp_relay_packet_size", &self.udp_relay.packet_size())
+            .set("udp_relay_yield_size", &self.udp_relay.yield_size())
+            .set("udp_relay_batch_size", &self.udp_relay.batch_size())
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set("flow_queue_size", &self.flow_queue_size)
+            .set("max_flows", &self.flow_limit.max_flows)
+            .set(
+                "max_flows_per_client",
+                &self.flow_limit.max_flows_per_client,
+            )
+            .set("task_idle_check_duration", &self.task_idle_check_duration)
+            .set("task_idle_max_count", &self.task_idle_max_count)
+            .set("flush_task_log_on_created", &self.flush_task_log_on_created)
+            .set(
+                "flush_task_log_on_connected",
+                &self.flush_task_log_on_connected,
+            )
+            .set("task_log_flush_interval", &self.task_log_flush_interval);
+        map.build()
+    }
+}
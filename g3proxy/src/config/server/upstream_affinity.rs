@@ -0,0 +1,86 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct UpstreamAffinityConfig {
+    pub(crate) ttl: Duration,
+    pub(crate) max_entries: usize,
+}
+
+impl Default for UpstreamAffinityConfig {
+    fn default() -> Self {
+        UpstreamAffinityConfig {
+            ttl: Duration::from_secs(600),
+            max_entries: 65536,
+        }
+    }
+}
+
+impl UpstreamAffinityConfig {
+    pub(crate) fn parse(value: &Yaml) -> anyhow::Result<Option<Self>> {
+        let mut config = UpstreamAffinityConfig::default();
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "ttl" => {
+                        config.ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_entries" => {
+                        config.max_entries = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(enable) => {
+                if !enable {
+                    return Ok(None);
+                }
+            }
+            Yaml::String(_) | Yaml::Integer(_) => {
+                config.ttl = g3_yaml::humanize::as_duration(value)
+                    .context("invalid humanize duration value for ttl")?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'upstream affinity config' should be 'map', 'bool' or 'duration'"
+                ));
+            }
+        }
+
+        config.check()?;
+        Ok(Some(config))
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.ttl.is_zero() {
+            return Err(anyhow!("ttl should not be zero"));
+        }
+        if self.max_entries == 0 {
+            return Err(anyhow!("max entries should not be 0"));
+        }
+        Ok(())
+    }
+}
+
+impl YamlDump for UpstreamAffinityConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("ttl", &self.ttl)
+            .set("max_entries", &self.max_entries);
+        map.build()
+    }
+}
@@ -0,0 +1,361 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::Write;
+
+use anyhow::anyhow;
+
+use g3_daemon::config::TopoMap;
+use g3_types::metrics::NodeName;
+
+use super::dump::{ConfigChange, ConfigChangeAction, ConfigKind, DiskConfig};
+
+pub(crate) struct ValidateReport {
+    pub(crate) errors: Vec<String>,
+    /// the changed objects, followed by the ones that depend on them
+    pub(crate) changes: Vec<ConfigChange>,
+}
+
+impl ValidateReport {
+    fn new(disk: &DiskConfig, changes: Vec<ConfigChange>) -> Self {
+        let mut errors = Vec::new();
+        check_reference(disk, &mut errors);
+        check_loop(ConfigKind::Resolver, &disk.resolver, &mut errors, |c| {
+            c.dependent_resolver()
+        });
+        check_loop(ConfigKind::Escaper, &disk.escaper, &mut errors, |c| {
+            c.dependent_escaper()
+        });
+        check_loop(ConfigKind::Server, &disk.server, &mut errors, |c| {
+            c.dependent_server()
+        });
+
+        let changes = add_dependent(disk, changes);
+        ValidateReport { errors, changes }
+    }
+}
+
+/// Validate the config files on disk, and return the objects in them.
+///
+/// All objects will be treated as new ones, as there is nothing running yet.
+pub fn validate_report() -> anyhow::Result<String> {
+    let disk = DiskConfig::load()?;
+
+    let mut changes = Vec::new();
+    let mut add = |kind: ConfigKind, names: Vec<&NodeName>| {
+        for name in names {
+            changes.push(ConfigChange {
+                kind,
+                name: name.clone(),
+                action: ConfigChangeAction::Added,
+            });
+        }
+    };
+    add(ConfigKind::UserGroup, disk.user_group.keys().collect());
+    add(ConfigKind::Resolver, disk.resolver.keys().collect());
+    add(ConfigKind::Auditor, disk.auditor.keys().collect());
+    add(ConfigKind::Escaper, disk.escaper.keys().collect());
+    add(ConfigKind::Server, disk.server.keys().collect());
+
+    let report = ValidateReport::new(&disk, changes);
+    if !report.errors.is_empty() {
+        let mut msg = format!("{} error(s) found in config:", report.errors.len());
+        for e in &report.errors {
+            let _ = write!(msg, "\n  {e}");
+        }
+        return Err(anyhow!(msg));
+    }
+
+    let mut content = String::with_capacity(1024);
+    for change in &report.changes {
+        let _ = writeln!(content, "{change}");
+    }
+    Ok(content)
+}
+
+/// Validate the config files on disk, and compare them with the running config.
+///
+/// This will do file IO, so it should be called in a blocking thread.
+pub(crate) fn validate_with_running() -> anyhow::Result<ValidateReport> {
+    let disk = DiskConfig::load()?;
+    let changes = super::dump::diff_with_running(&disk);
+    Ok(ValidateReport::new(&disk, changes))
+}
+
+fn check_reference(disk: &DiskConfig, errors: &mut Vec<String>) {
+    for r in disk.references() {
+        if !disk.contains(r.ref_kind, &r.ref_name) {
+            errors.push(format!(
+                "{} {}: {} {} not found",
+                r.kind.as_str(),
+                r.name,
+                r.ref_kind.as_str(),
+                r.ref_name
+            ));
+        }
+    }
+}
+
+fn check_loop<T, F>(
+    kind: ConfigKind,
+    all: &BTreeMap<NodeName, T>,
+    errors: &mut Vec<String>,
+    dependent: F,
+) where
+    F: Fn(&T) -> Option<BTreeSet<NodeName>>,
+{
+    let mut topo_map = TopoMap::default();
+    for name in all.keys() {
+        if let Err(e) = topo_map.add_node(name, &|name| all.get(name).and_then(&dependent)) {
+            errors.push(format!("{} {name}: {e:#}", kind.as_str()));
+            // the topology map is incomplete now, and the same loop may be reported again
+            break;
+        }
+    }
+}
+
+/// A reference from one object to another one
+struct Reference {
+    kind: ConfigKind,
+    name: NodeName,
+    ref_kind: ConfigKind,
+    ref_name: NodeName,
+}
+
+impl DiskConfig {
+    fn contains(&self, kind: ConfigKind, name: &NodeName) -> bool {
+        match kind {
+            ConfigKind::UserGroup => self.user_group.contains_key(name),
+            ConfigKind::Resolver => self.resolver.contains_key(name),
+            ConfigKind::Auditor => self.auditor.contains_key(name),
+            ConfigKind::Escaper => self.escaper.contains_key(name),
+            ConfigKind::Server => self.server.contains_key(name),
+        }
+    }
+
+    /// Get all the references, the same as the edges in the config graph
+    fn references(&self) -> Vec<Reference> {
+        let mut all = Vec::new();
+        let mut add =
+            |kind: ConfigKind, name: &NodeName, ref_kind: ConfigKind, ref_name: &NodeName| {
+                if !ref_name.is_empty() {
+                    all.push(Reference {
+                        kind,
+                        name: name.clone(),
+                        ref_kind,
+                        ref_name: ref_name.clone(),
+                    });
+                }
+            };
+
+        for (name, c) in &self.resolver {
+            for v in c.dependent_resolver().unwrap_or_default() {
+                add(ConfigKind::Resolver, name, ConfigKind::Resolver, &v);
+            }
+        }
+        for (name, c) in &self.escaper {
+            add(
+                ConfigKind::Escaper,
+                name,
+                ConfigKind::Resolver,
+                c.resolver(),
+            );
+            add(ConfigKind::Escaper, name, ConfigKind::Auditor, c.auditor());
+            for v in c.dependent_escaper().unwrap_or_default() {
+                add(ConfigKind::Escaper, name, ConfigKind::Escaper, &v);
+            }
+        }
+        for (name, c) in &self.server {
+            add(ConfigKind::Server, name, ConfigKind::Escaper, c.escaper());
+            add(
+                ConfigKind::Server,
+                name,
+                ConfigKind::UserGroup,
+                c.user_group(),
+            );
+            add(ConfigKind::Server, name, ConfigKind::Auditor, c.auditor());
+            add(ConfigKind::Server, name, ConfigKind::Resolver, c.resolver());
+            for v in c.dependent_server().unwrap_or_default() {
+                add(ConfigKind::Server, name, ConfigKind::Server, &v);
+            }
+        }
+        all
+    }
+}
+
+fn add_dependent(disk: &DiskConfig, mut changes: Vec<ConfigChange>) -> Vec<ConfigChange> {
+    // the objects that need to be reloaded if the target object is changed
+    let mut dependents: BTreeMap<(ConfigKind, NodeName), Vec<(ConfigKind, NodeName)>> =
+        BTreeMap::new();
+    for r in disk.references() {
+        dependents
+            .entry((r.ref_kind, r.ref_name))
+            .or_default()
+            .push((r.kind, r.name));
+    }
+
+    let mut visited = changes
+        .iter()
+        .map(|c| (c.kind, c.name.clone()))
+        .collect::<BTreeSet<_>>();
+    let mut queue = visited.iter().cloned().collect::<VecDeque<_>>();
+
+    // finish those in the same level first, then go in depth
+    while let Some((kind, target)) = queue.pop_front() {
+        let Some(all) = dependents.get(&(kind, target.clone())) else {
+            continue;
+        };
+        for (dep_kind, dep_name) in all {
+            if visited.insert((*dep_kind, dep_name.clone())) {
+                queue.push_back((*dep_kind, dep_name.clone()));
+                changes.push(ConfigChange {
+                    kind: *dep_kind,
+                    name: dep_name.clone(),
+                    action: ConfigChangeAction::Dependent(kind, target.clone()),
+                });
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::str::FromStr;
+    use yaml_rust::YamlLoader;
+
+    fn load_disk(resolver: &str, escaper: &str, server: &str) -> DiskConfig {
+        let load = |s: &str| YamlLoader::load_from_str(s).unwrap().pop().unwrap();
+        let conf_dir = Path::new("/");
+
+        let mut disk = DiskConfig::default();
+        for c in crate::config::resolver::parse_all(&load(resolver), conf_dir).unwrap() {
+            disk.resolver.insert(c.name().clone(), c);
+        }
+        for c in crate::config::escaper::parse_all(&load(escaper), conf_dir).unwrap() {
+            disk.escaper.insert(c.name().clone(), c);
+        }
+        for c in crate::config::server::parse_all(&load(server), conf_dir).unwrap() {
+            disk.server.insert(c.name().clone(), c);
+        }
+        disk
+    }
+
+    fn modified(kind: ConfigKind, name: &str) -> ConfigChange {
+        ConfigChange {
+            kind,
+            name: NodeName::from_str(name).unwrap(),
+            action: ConfigChangeAction::Modified(vec!["a".to_string()]),
+        }
+    }
+
+    #[test]
+    fn missing_reference() {
+        let disk = load_disk(
+            "[{name: r1, type: deny_all}]",
+            r#"
+            - {name: e1, type: direct_fixed, resolver: r2}
+            - {name: e2, type: route_failover, primary: e1, standby: e3}
+            "#,
+            r#"
+            - {name: s1, type: http_proxy, escaper: e2, user_group: g1}
+            "#,
+        );
+        let mut errors = Vec::new();
+        check_reference(&disk, &mut errors);
+        assert_eq!(
+            errors,
+            vec![
+                "escaper e1: resolver r2 not found",
+                "escaper e2: escaper e3 not found",
+                "server s1: user-group g1 not found",
+            ]
+        );
+    }
+
+    #[test]
+    fn loop_dependency() {
+        let disk = load_disk(
+            "[]",
+            r#"
+            - {name: e1, type: route_failover, primary: e2, standby: e3}
+            - {name: e2, type: route_failover, primary: e3, standby: e1}
+            - {name: e3, type: dummy_deny}
+            "#,
+            "[]",
+        );
+        let mut errors = Vec::new();
+        check_loop(ConfigKind::Escaper, &disk.escaper, &mut errors, |c| {
+            c.dependent_escaper()
+        });
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("escaper e1: "));
+
+        let disk = load_disk(
+            "[]",
+            r#"
+            - {name: e1, type: route_failover, primary: e2, standby: e3}
+            - {name: e2, type: route_failover, primary: e3, standby: e3}
+            - {name: e3, type: dummy_deny}
+            "#,
+            "[]",
+        );
+        let mut errors = Vec::new();
+        check_loop(ConfigKind::Escaper, &disk.escaper, &mut errors, |c| {
+            c.dependent_escaper()
+        });
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn cascade_dependent() {
+        let disk = load_disk(
+            "[{name: r1, type: deny_all}]",
+            r#"
+            - {name: e1, type: direct_fixed, resolver: r1}
+            - {name: e2, type: route_failover, primary: e1, standby: e3}
+            - {name: e3, type: dummy_deny}
+            "#,
+            r#"
+            - {name: s1, type: http_proxy, escaper: e2}
+            - {name: s2, type: http_proxy, escaper: e3}
+            "#,
+        );
+
+        let changes = add_dependent(&disk, vec![modified(ConfigKind::Resolver, "r1")]);
+        let changes = changes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                "~ resolver r1: a",
+                "* escaper e1: depends on resolver r1",
+                "* escaper e2: depends on escaper e1",
+                "* server s1: depends on escaper e2",
+            ]
+        );
+
+        // the changed ones should not be reported again as dependent
+        let changes = add_dependent(
+            &disk,
+            vec![
+                modified(ConfigKind::Escaper, "e3"),
+                modified(ConfigKind::Server, "s1"),
+            ],
+        );
+        let changes = changes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![
+                "~ escaper e3: a",
+                "~ server s1: a",
+                "* escaper e2: depends on escaper e3",
+                "* server s2: depends on escaper e3",
+            ]
+        );
+    }
+}
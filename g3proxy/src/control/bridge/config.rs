@@ -0,0 +1,21 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+
+use crate::config::dump::ConfigChange;
+use crate::config::validate::ValidateReport;
+
+pub(in crate::control) async fn diff_config() -> anyhow::Result<Vec<ConfigChange>> {
+    tokio::task::spawn_blocking(crate::config::dump::diff_with_disk)
+        .await
+        .map_err(|e| anyhow!("failed to join config diff task: {e}"))?
+}
+
+pub(in crate::control) async fn validate_config() -> anyhow::Result<ValidateReport> {
+    tokio::task::spawn_blocking(crate::config::validate::validate_with_running)
+        .await
+        .map_err(|e| anyhow!("failed to join config validate task: {e}"))?
+}
@@ -3,7 +3,11 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+mod config;
+pub(super) use config::{diff_config, validate_config};
+
 mod reload;
 pub(super) use reload::{
-    reload_auditor, reload_escaper, reload_resolver, reload_server, reload_user_group,
+    reload_auditor, reload_discover, reload_escaper, reload_resolver, reload_server,
+    reload_user_group,
 };
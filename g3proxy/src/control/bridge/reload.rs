@@ -29,3 +29,4 @@ impl_reload!(reload_auditor, audit);
 impl_reload!(reload_resolver, resolve);
 impl_reload!(reload_escaper, escape);
 impl_reload!(reload_server, serve);
+impl_reload!(reload_discover, discover);
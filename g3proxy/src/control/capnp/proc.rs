@@ -9,12 +9,14 @@ use capnp_rpc::pry;
 use g3_types::metrics::NodeName;
 
 use g3proxy_proto::escaper_capnp::escaper_control;
-use g3proxy_proto::proc_capnp::proc_control;
+use g3proxy_proto::proc_capnp::{config_change, proc_control};
 use g3proxy_proto::resolver_capnp::resolver_control;
 use g3proxy_proto::server_capnp::server_control;
 use g3proxy_proto::types_capnp::fetch_result;
 use g3proxy_proto::user_group_capnp::user_group_control;
 
+use crate::config::dump::{ConfigChange, ConfigChangeAction};
+
 use super::set_operation_result;
 
 pub(super) struct ProcControlImpl;
@@ -65,6 +67,18 @@ impl proc_control::Server for ProcControlImpl {
         })
     }
 
+    fn save_resolver_cache(
+        &mut self,
+        _params: proc_control::SaveResolverCacheParams,
+        mut results: proc_control::SaveResolverCacheResults,
+    ) -> Promise<(), capnp::Error> {
+        Promise::from_future(async move {
+            let r = crate::resolve::save_cache_snapshot().await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
+
     fn list_user_group(
         &mut self,
         _params: proc_control::ListUserGroupParams,
@@ -104,6 +118,19 @@ impl proc_control::Server for ProcControlImpl {
         Promise::ok(())
     }
 
+    fn list_discover(
+        &mut self,
+        _params: proc_control::ListDiscoverParams,
+        mut results: proc_control::ListDiscoverResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = crate::discover::get_names();
+        let mut builder = results.get().init_result(set.len() as u32);
+        for (i, name) in set.iter().enumerate() {
+            builder.set(i as u32, name.as_str());
+        }
+        Promise::ok(())
+    }
+
     fn list_escaper(
         &mut self,
         _params: proc_control::ListEscaperParams,
@@ -169,6 +196,19 @@ impl proc_control::Server for ProcControlImpl {
         })
     }
 
+    fn reload_discover(
+        &mut self,
+        params: proc_control::ReloadDiscoverParams,
+        mut results: proc_control::ReloadDiscoverResults,
+    ) -> Promise<(), capnp::Error> {
+        let discover = pry!(pry!(pry!(params.get()).get_name()).to_string());
+        Promise::from_future(async move {
+            let r = crate::control::bridge::reload_discover(discover, None).await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
+
     fn reload_escaper(
         &mut self,
         params: proc_control::ReloadEscaperParams,
@@ -269,6 +309,109 @@ impl proc_control::Server for ProcControlImpl {
         results.get().init_result().set_ok("success");
         Promise::ok(())
     }
+    fn dump_user_group_config(
+        &mut self,
+        params: proc_control::DumpUserGroupConfigParams,
+        mut results: proc_control::DumpUserGroupConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        let user_group = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let user_group = unsafe { NodeName::new_unchecked(user_group) };
+        let r = crate::config::dump::dump_user_group(&user_group);
+        pry!(set_dump_result(results.get().init_result(), r));
+        Promise::ok(())
+    }
+
+    fn dump_resolver_config(
+        &mut self,
+        params: proc_control::DumpResolverConfigParams,
+        mut results: proc_control::DumpResolverConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        let resolver = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let resolver = unsafe { NodeName::new_unchecked(resolver) };
+        let r = crate::config::dump::dump_resolver(&resolver);
+        pry!(set_dump_result(results.get().init_result(), r));
+        Promise::ok(())
+    }
+
+    fn dump_auditor_config(
+        &mut self,
+        params: proc_control::DumpAuditorConfigParams,
+        mut results: proc_control::DumpAuditorConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        let auditor = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let auditor = unsafe { NodeName::new_unchecked(auditor) };
+        let r = crate::config::dump::dump_auditor(&auditor);
+        pry!(set_dump_result(results.get().init_result(), r));
+        Promise::ok(())
+    }
+
+    fn dump_escaper_config(
+        &mut self,
+        params: proc_control::DumpEscaperConfigParams,
+        mut results: proc_control::DumpEscaperConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        let escaper = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let escaper = unsafe { NodeName::new_unchecked(escaper) };
+        let r = crate::config::dump::dump_escaper(&escaper);
+        pry!(set_dump_result(results.get().init_result(), r));
+        Promise::ok(())
+    }
+
+    fn dump_server_config(
+        &mut self,
+        params: proc_control::DumpServerConfigParams,
+        mut results: proc_control::DumpServerConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        let server = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let server = unsafe { NodeName::new_unchecked(server) };
+        let r = crate::config::dump::dump_server(&server);
+        pry!(set_dump_result(results.get().init_result(), r));
+        Promise::ok(())
+    }
+
+    fn diff_config(
+        &mut self,
+        _params: proc_control::DiffConfigParams,
+        mut results: proc_control::DiffConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        Promise::from_future(async move {
+            match crate::control::bridge::diff_config().await {
+                Ok(changes) => {
+                    let mut builder = results.get().init_changes(changes.len() as u32);
+                    for (i, change) in changes.iter().enumerate() {
+                        set_config_change(builder.reborrow().get(i as u32), change);
+                    }
+                    set_operation_result(results.get().init_result(), Ok(()));
+                }
+                Err(e) => set_operation_result(results.get().init_result(), Err(e)),
+            }
+            Ok(())
+        })
+    }
+
+    fn validate_config(
+        &mut self,
+        _params: proc_control::ValidateConfigParams,
+        mut results: proc_control::ValidateConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        Promise::from_future(async move {
+            match crate::control::bridge::validate_config().await {
+                Ok(report) => {
+                    let mut builder = results.get().init_errors(report.errors.len() as u32);
+                    for (i, e) in report.errors.iter().enumerate() {
+                        builder.set(i as u32, e.as_str());
+                    }
+                    let mut builder = results.get().init_changes(report.changes.len() as u32);
+                    for (i, change) in report.changes.iter().enumerate() {
+                        set_config_change(builder.reborrow().get(i as u32), change);
+                    }
+                    set_operation_result(results.get().init_result(), Ok(()));
+                }
+                Err(e) => set_operation_result(results.get().init_result(), Err(e)),
+            }
+            Ok(())
+        })
+    }
 }
 
 fn set_fetch_result<'a, T>(
@@ -288,3 +431,39 @@ where
         }
     }
 }
+
+fn set_dump_result(
+    mut builder: fetch_result::Builder<'_, capnp::text::Owned>,
+    r: anyhow::Result<String>,
+) -> capnp::Result<()> {
+    match r {
+        Ok(s) => builder.set_data(s.as_str()),
+        Err(e) => {
+            let mut ev = builder.init_err();
+            ev.set_code(-1);
+            ev.set_reason(format!("{e:?}").as_str());
+            Ok(())
+        }
+    }
+}
+
+fn set_config_change(mut builder: config_change::Builder<'_>, change: &ConfigChange) {
+    builder.set_kind(change.kind.as_str());
+    builder.set_name(change.name.as_str());
+    match &change.action {
+        ConfigChangeAction::Added => builder.set_action(config_change::Action::Added),
+        ConfigChangeAction::Removed => builder.set_action(config_change::Action::Removed),
+        ConfigChangeAction::Modified(keys) => {
+            builder.set_action(config_change::Action::Modified);
+            let mut keys_builder = builder.init_changed_keys(keys.len() as u32);
+            for (i, key) in keys.iter().enumerate() {
+                keys_builder.set(i as u32, key.as_str());
+            }
+        }
+        ConfigChangeAction::Dependent(kind, name) => {
+            builder.set_action(config_change::Action::Dependent);
+            builder.set_depend_kind(kind.as_str());
+            builder.set_depend_name(name.as_str());
+        }
+    }
+}
@@ -3,13 +3,19 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::sync::Arc;
+
+use anyhow::anyhow;
 use capnp::capability::Promise;
+use capnp_rpc::pry;
+use uuid::Uuid;
 
 use g3_types::metrics::NodeName;
 
 use g3proxy_proto::server_capnp::server_control;
 
-use crate::serve::ArcServer;
+use super::set_operation_result;
+use crate::serve::{ArcServer, ServerTaskRegistry};
 
 pub(super) struct ServerControlImpl {
     server: ArcServer,
@@ -21,6 +27,12 @@ impl ServerControlImpl {
         let server = crate::serve::get_server(&name)?;
         Ok(capnp_rpc::new_client(ServerControlImpl { server }))
     }
+
+    fn task_registry(&self) -> Result<&Arc<ServerTaskRegistry>, capnp::Error> {
+        self.server.get_task_registry().ok_or_else(|| {
+            capnp::Error::failed("task registry is not supported on this server".to_string())
+        })
+    }
 }
 
 impl server_control::Server for ServerControlImpl {
@@ -42,4 +54,75 @@ impl server_control::Server for ServerControlImpl {
             ))
         }
     }
+
+    fn list_tasks(
+        &mut self,
+        params: server_control::ListTasksParams,
+        mut results: server_control::ListTasksResults,
+    ) -> Promise<(), capnp::Error> {
+        let registry = pry!(self.task_registry());
+        let params = pry!(params.get());
+        let user = pry!(pry!(params.get_user()).to_str());
+        let upstream = pry!(pry!(params.get_upstream()).to_str());
+
+        let tasks = registry.list(
+            Some(user).filter(|s| !s.is_empty()),
+            Some(upstream).filter(|s| !s.is_empty()),
+        );
+        let mut builder = results.get().init_result(tasks.len() as u32);
+        for (i, task) in tasks.iter().enumerate() {
+            let mut b = builder.reborrow().get(i as u32);
+            b.set_id(task.id().to_string().as_str());
+            if let Some(user) = task.user() {
+                b.set_user(user);
+            }
+            b.set_client_addr(task.client_addr().to_string().as_str());
+            b.set_upstream(task.upstream().to_string().as_str());
+            b.set_escaper(task.escaper().as_str());
+            b.set_start_at(task.start_at().to_rfc3339().as_str());
+            b.set_age_millis(task.age().as_millis() as u64);
+            let stats = task.stats();
+            b.set_client_read_bytes(stats.client_read_bytes());
+            b.set_client_write_bytes(stats.client_write_bytes());
+            b.set_upstream_read_bytes(stats.upstream_read_bytes());
+            b.set_upstream_write_bytes(stats.upstream_write_bytes());
+        }
+        Promise::ok(())
+    }
+
+    fn cancel_task(
+        &mut self,
+        params: server_control::CancelTaskParams,
+        mut results: server_control::CancelTaskResults,
+    ) -> Promise<(), capnp::Error> {
+        let registry = pry!(self.task_registry());
+        let id = pry!(pry!(pry!(params.get()).get_id()).to_str());
+        let r = match Uuid::parse_str(id) {
+            Ok(id) => {
+                if registry.cancel(&id) {
+                    Ok(())
+                } else {
+                    Err(anyhow!("no live task with id {id} found"))
+                }
+            }
+            Err(e) => Err(anyhow!("invalid task id {id}: {e}")),
+        };
+        set_operation_result(results.get().init_result(), r);
+        Promise::ok(())
+    }
+
+    fn cancel_user_tasks(
+        &mut self,
+        params: server_control::CancelUserTasksParams,
+        mut results: server_control::CancelUserTasksResults,
+    ) -> Promise<(), capnp::Error> {
+        let registry = pry!(self.task_registry());
+        let user = pry!(pry!(pry!(params.get()).get_user()).to_str());
+        let count = registry.cancel_user(user);
+        results
+            .get()
+            .init_result()
+            .set_ok(format!("{count} tasks canceled").as_str());
+        Promise::ok(())
+    }
 }
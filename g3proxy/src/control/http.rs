@@ -0,0 +1,193 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use log::debug;
+use serde_json::{Map, Value};
+
+use g3_daemon::control::{HttpControlError, HttpControlHandler, HttpControlResult};
+use g3_types::metrics::NodeName;
+
+const KIND_SERVER: &str = "servers";
+const KIND_ESCAPER: &str = "escapers";
+const KIND_RESOLVER: &str = "resolvers";
+const KIND_USER_GROUP: &str = "user-groups";
+const KIND_AUDITOR: &str = "auditors";
+const KIND_DISCOVER: &str = "discovers";
+
+pub struct HttpController {}
+
+impl HttpController {
+    pub fn start() -> anyhow::Result<()> {
+        g3_daemon::control::HttpController::start(HttpControlImpl)
+    }
+
+    pub(super) async fn abort() {
+        debug!("aborting http controller");
+        g3_daemon::control::HttpController::abort().await;
+    }
+}
+
+struct HttpControlImpl;
+
+fn get_names(kind: &str) -> HttpControlResult<HashSet<NodeName>> {
+    match kind {
+        KIND_SERVER => Ok(crate::serve::get_names()),
+        KIND_ESCAPER => Ok(crate::escape::get_names()),
+        KIND_RESOLVER => Ok(crate::resolve::get_names()),
+        KIND_USER_GROUP => Ok(crate::auth::get_names()),
+        KIND_AUDITOR => Ok(crate::audit::get_names()),
+        KIND_DISCOVER => Ok(crate::discover::get_names()),
+        _ => Err(HttpControlError::unknown_kind(kind)),
+    }
+}
+
+fn check_name(kind: &str, name: &str) -> HttpControlResult<NodeName> {
+    let name = unsafe { NodeName::new_unchecked(name) };
+    if get_names(kind)?.contains(&name) {
+        Ok(name)
+    } else {
+        Err(HttpControlError::not_found(kind, name.as_str()))
+    }
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    fn version(&self) -> &'static str {
+        crate::build::VERSION
+    }
+
+    fn list(&self, kind: &str) -> HttpControlResult<Vec<String>> {
+        let mut names: Vec<String> = get_names(kind)?
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn status(&self, kind: &str, name: &str) -> HttpControlResult<Map<String, Value>> {
+        let name = check_name(kind, name)?;
+
+        let mut map = Map::new();
+        map.insert("name".to_string(), Value::from(name.as_str()));
+        match kind {
+            KIND_SERVER => {
+                let server = crate::serve::get_server(&name)?;
+                map.insert("type".to_string(), Value::from(server.r#type()));
+                if let Some(stats) = server.get_server_stats() {
+                    map.insert("online".to_string(), Value::from(stats.is_online()));
+                    map.insert(
+                        "alive_task_count".to_string(),
+                        Value::from(stats.get_alive_count()),
+                    );
+                    map.insert(
+                        "total_conn_count".to_string(),
+                        Value::from(stats.get_conn_total()),
+                    );
+                    map.insert(
+                        "total_task_count".to_string(),
+                        Value::from(stats.get_task_total()),
+                    );
+                }
+            }
+            KIND_ESCAPER => {
+                let escaper = crate::escape::get_escaper(&name)?;
+                if let Some(stats) = escaper.get_escape_stats() {
+                    map.insert(
+                        "total_task_count".to_string(),
+                        Value::from(stats.get_task_total()),
+                    );
+                    map.insert(
+                        "connection_attempted".to_string(),
+                        Value::from(stats.connection_attempted()),
+                    );
+                    map.insert(
+                        "connection_established".to_string(),
+                        Value::from(stats.connection_established()),
+                    );
+                }
+            }
+            KIND_RESOLVER => {
+                crate::resolve::foreach_resolver(|n, resolver| {
+                    if n != &name {
+                        return;
+                    }
+                    let snap = resolver.get_stats().inner().snapshot();
+                    map.insert("query_a_total".to_string(), Value::from(snap.query_a.total));
+                    map.insert(
+                        "query_a_cached".to_string(),
+                        Value::from(snap.query_a.cached),
+                    );
+                    map.insert(
+                        "query_aaaa_total".to_string(),
+                        Value::from(snap.query_aaaa.total),
+                    );
+                    map.insert(
+                        "query_aaaa_cached".to_string(),
+                        Value::from(snap.query_aaaa.cached),
+                    );
+                    map.insert(
+                        "query_https_total".to_string(),
+                        Value::from(snap.query_https.total),
+                    );
+                    map.insert(
+                        "query_https_cached".to_string(),
+                        Value::from(snap.query_https.cached),
+                    );
+                });
+            }
+            KIND_USER_GROUP => {
+                let user_group = crate::auth::get_or_insert_default(&name);
+                map.insert(
+                    "static_user_count".to_string(),
+                    Value::from(user_group.all_static_users().len()),
+                );
+                map.insert(
+                    "dynamic_user_count".to_string(),
+                    Value::from(user_group.all_dynamic_users().len()),
+                );
+            }
+            _ => {}
+        }
+        Ok(map)
+    }
+
+    async fn reload(&self, kind: &str, name: &str) -> HttpControlResult<()> {
+        let name = check_name(kind, name)?.to_string();
+        let r = match kind {
+            KIND_SERVER => super::bridge::reload_server(name, None).await,
+            KIND_ESCAPER => super::bridge::reload_escaper(name, None).await,
+            KIND_RESOLVER => super::bridge::reload_resolver(name, None).await,
+            KIND_USER_GROUP => super::bridge::reload_user_group(name, None).await,
+            KIND_AUDITOR => super::bridge::reload_auditor(name, None).await,
+            KIND_DISCOVER => super::bridge::reload_discover(name, None).await,
+            _ => return Err(HttpControlError::unknown_kind(kind)),
+        };
+        r.map_err(HttpControlError::Failed)
+    }
+
+    async fn publish(&self, kind: &str, name: &str, data: String) -> HttpControlResult<()> {
+        let name = check_name(kind, name)?;
+        let r = match kind {
+            KIND_ESCAPER => {
+                let escaper = crate::escape::get_escaper(&name)?;
+                escaper.publish(data).await
+            }
+            KIND_USER_GROUP => {
+                let user_group = crate::auth::get_or_insert_default(&name);
+                user_group.publish_dynamic_users(&data).await
+            }
+            _ => {
+                return Err(HttpControlError::BadRequest(format!(
+                    "publish is not supported for {kind}"
+                )));
+            }
+        };
+        r.map_err(HttpControlError::Failed)
+    }
+}
@@ -16,6 +16,9 @@ pub use upgrade::UpgradeActor;
 mod local;
 pub use local::{DaemonController, UniqueController};
 
+mod http;
+pub use http::HttpController;
+
 pub mod capnp;
 
 static IO_MUTEX: Mutex<Option<Mutex<()>>> = Mutex::const_new(Some(Mutex::const_new(())));
@@ -40,6 +40,12 @@ impl UpgradeAction for UpgradeActor {
         check_operation_result(rsp.get()?.get_result()?)
     }
 
+    async fn save_resolver_cache(&self) -> anyhow::Result<()> {
+        let req = self.proc_control.save_resolver_cache_request();
+        let rsp = req.send().promise.await?;
+        check_operation_result(rsp.get()?.get_result()?)
+    }
+
     async fn confirm_shutdown(&self) -> anyhow::Result<()> {
         let req = self.proc_control.offline_request();
         let rsp = req.send().promise.await?;
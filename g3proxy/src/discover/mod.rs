@@ -0,0 +1,58 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use yaml_rust::Yaml;
+
+use g3_discover::DiscoverRegisterData;
+use g3_types::net::WeightedUpstreamAddr;
+
+use crate::config::discover::AnyDiscoverConfig;
+
+mod ops;
+pub use ops::load_all;
+pub(crate) use ops::{get_discover, reload};
+
+mod registry;
+pub(crate) use registry::get_names;
+
+pub(crate) type DiscoverResult = g3_discover::DiscoverResult<WeightedUpstreamAddr>;
+
+pub(crate) trait Discover {
+    fn register_yaml(&self, data: &Yaml) -> anyhow::Result<watch::Receiver<DiscoverResult>>;
+
+    fn register_data(
+        &self,
+        data: &DiscoverRegisterData,
+    ) -> anyhow::Result<watch::Receiver<DiscoverResult>> {
+        self.register_yaml(data.as_yaml()?)
+    }
+}
+
+trait DiscoverInternal: Discover {
+    fn _clone_config(&self) -> AnyDiscoverConfig;
+}
+
+pub(crate) type ArcDiscover = Arc<dyn Discover + Send + Sync>;
+type ArcDiscoverInternal = Arc<dyn DiscoverInternal + Send + Sync>;
+
+impl Discover for AnyDiscoverConfig {
+    fn register_yaml(&self, data: &Yaml) -> anyhow::Result<watch::Receiver<DiscoverResult>> {
+        match self {
+            AnyDiscoverConfig::StaticAddr(c) => c.register_yaml(data),
+            AnyDiscoverConfig::HostResolver(c) => c.register_yaml(data),
+            AnyDiscoverConfig::File(c) => c.register_yaml(data),
+            AnyDiscoverConfig::Srv(c) => c.register_yaml(data),
+        }
+    }
+}
+
+impl DiscoverInternal for AnyDiscoverConfig {
+    fn _clone_config(&self) -> AnyDiscoverConfig {
+        self.clone()
+    }
+}
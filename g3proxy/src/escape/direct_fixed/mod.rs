@@ -12,7 +12,7 @@ use slog::Logger;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
-use g3_resolver::ResolveError;
+use g3_resolver::{ResolveError, ServiceBinding};
 use g3_socket::BindAddr;
 use g3_socket::util::AddressFamily;
 use g3_types::acl::AclNetworkRule;
@@ -196,6 +196,42 @@ impl DirectFixedEscaper {
         HappyEyeballsResolveJob::new_dyn(strategy, &self.resolver_handle, domain)
     }
 
+    /// Get the best service binding in the HTTPS RR of the upstream, which should be served on the same port.
+    /// The query will be skipped if any resolve redirection is set for the domain.
+    async fn resolve_https_service(
+        &self,
+        upstream: &UpstreamAddr,
+        task_notes: &ServerTaskNotes,
+    ) -> Option<ServiceBinding> {
+        if !self.config.enable_https_rr {
+            return None;
+        }
+        let Host::Domain(domain) = upstream.host() else {
+            return None;
+        };
+
+        if let Some(user_ctx) = task_notes.user_ctx() {
+            if let Some(redirect) = user_ctx.user().resolve_redirection() {
+                if redirect.query_value(domain).is_some() {
+                    return None;
+                }
+            }
+        }
+        if let Some(redirect) = &self.resolve_redirection {
+            if redirect.query_value(domain).is_some() {
+                return None;
+            }
+        }
+
+        let query_name = g3_resolver::https_query_name(domain, upstream.port());
+        let mut job = self.resolver_handle.query_https(query_name).ok()?;
+        let (record, _) = tokio::time::timeout(self.config.https_rr_query_timeout, job.recv())
+            .await
+            .ok()?
+            .ok()?;
+        record.best_service_on_port(upstream.port()).cloned()
+    }
+
     async fn resolve_best(
         &self,
         domain: Arc<str>,
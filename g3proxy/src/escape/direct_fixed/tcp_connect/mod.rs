@@ -14,12 +14,14 @@ use tokio::time::Instant;
 
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
 use g3_io_ext::{LimitedReader, LimitedWriter};
+use g3_resolver::ServiceBinding;
 use g3_socket::BindAddr;
 use g3_socket::util::AddressFamily;
 use g3_types::acl::AclAction;
 use g3_types::net::{
     ConnectError, Host, TcpConnectConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UpstreamAddr,
 };
+use g3_types::resolve::ResolveRedirectionValue;
 
 use super::DirectFixedEscaper;
 use crate::log::escape::tcp_connect::EscapeLogForTcpConnect;
@@ -331,12 +333,7 @@ impl DirectFixedEscaper {
         }
     }
 
-    pub(super) async fn tcp_connect_to(
-        &self,
-        task_conf: &TcpConnectTaskConf<'_>,
-        tcp_notes: &mut TcpConnectTaskNotes,
-        task_notes: &ServerTaskNotes,
-    ) -> Result<TcpStream, TcpConnectError> {
+    fn tcp_connect_config(&self, task_notes: &ServerTaskNotes) -> DirectTcpConnectConfig<'_> {
         let mut config = DirectTcpConnectConfig {
             connect: self.config.general.tcp_connect,
             keepalive: self.config.tcp_keepalive,
@@ -354,6 +351,17 @@ impl DirectFixedEscaper {
             config.misc_opts = user_config.tcp_remote_misc_opts(&self.config.tcp_misc_opts);
         }
 
+        config
+    }
+
+    pub(super) async fn tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<TcpStream, TcpConnectError> {
+        let config = self.tcp_connect_config(task_notes);
+
         match task_conf.upstream.host() {
             Host::Ip(ip) => {
                 self.fixed_try_connect(*ip, config, task_conf, tcp_notes, task_notes)
@@ -372,6 +380,38 @@ impl DirectFixedEscaper {
         }
     }
 
+    /// Connect to the service endpoint got from the HTTPS RR of the upstream,
+    /// the address hints will be used directly if present.
+    pub(super) async fn tcp_connect_to_service(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        service: &ServiceBinding,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<TcpStream, TcpConnectError> {
+        let host = match &service.target {
+            Some(target) => Host::Domain(target.clone()),
+            None => task_conf.upstream.host().clone(),
+        };
+        let upstream = UpstreamAddr::new(host, task_conf.upstream.port());
+        let task_conf = TcpConnectTaskConf {
+            upstream: &upstream,
+        };
+
+        if !service.has_address_hint() {
+            return self.tcp_connect_to(&task_conf, tcp_notes, task_notes).await;
+        }
+
+        let config = self.tcp_connect_config(task_notes);
+        let resolver_job = HappyEyeballsResolveJob::new_redirected(
+            self.get_resolve_strategy(task_notes),
+            &self.resolver_handle,
+            ResolveRedirectionValue::Ip(service.address_hints()),
+        )?;
+        self.happy_try_connect(resolver_job, config, &task_conf, tcp_notes, task_notes)
+            .await
+    }
+
     pub(super) async fn tcp_connect_to_again(
         &self,
         task_conf: &TcpConnectTaskConf<'_>,
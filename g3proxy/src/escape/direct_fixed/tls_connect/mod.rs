@@ -29,9 +29,19 @@ impl DirectFixedEscaper {
         task_notes: &ServerTaskNotes,
         tls_application: TlsApplication,
     ) -> Result<SslStream<impl AsyncRead + AsyncWrite + use<>>, TcpConnectError> {
-        let mut stream = self
-            .tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
-            .await?;
+        let service = self
+            .resolve_https_service(task_conf.tcp.upstream, task_notes)
+            .await;
+        let mut stream = match &service {
+            Some(service) => {
+                self.tcp_connect_to_service(&task_conf.tcp, service, tcp_notes, task_notes)
+                    .await?
+            }
+            None => {
+                self.tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
+                    .await?
+            }
+        };
         if let Some(version) = self.config.use_proxy_protocol {
             self.send_tcp_proxy_protocol_header(version, &mut stream, task_notes, false)
                 .await?;
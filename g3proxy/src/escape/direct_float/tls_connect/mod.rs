@@ -35,9 +35,19 @@ impl DirectFloatEscaper {
         ),
         TcpConnectError,
     > {
-        let (stream, bind) = self
-            .tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
-            .await?;
+        let service = self
+            .resolve_https_service(task_conf.tcp.upstream, task_notes)
+            .await;
+        let (stream, bind) = match &service {
+            Some(service) => {
+                self.tcp_connect_to_service(&task_conf.tcp, service, tcp_notes, task_notes)
+                    .await?
+            }
+            None => {
+                self.tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
+                    .await?
+            }
+        };
 
         // set limit config and add escaper stats, do not count in task stats
         let limit_config = &self.config.general.tcp_sock_speed_limit;
@@ -35,7 +35,9 @@ use crate::serve::ServerTaskNotes;
 
 mod registry;
 use registry::EscaperRegistry;
-pub(crate) use registry::{foreach as foreach_escaper, get_names, get_or_insert_default};
+pub(crate) use registry::{
+    foreach as foreach_escaper, get_config, get_names, get_or_insert_default,
+};
 
 mod stats;
 pub(crate) use stats::{
@@ -56,6 +58,8 @@ mod dummy_deny;
 mod proxy_float;
 mod proxy_http;
 mod proxy_https;
+#[cfg(feature = "quic")]
+mod proxy_masque;
 mod proxy_socks5;
 mod proxy_socks5s;
 mod route_client;
@@ -227,6 +231,7 @@ pub(crate) trait EscaperExt: Escaper {
             SelectivePickPolicy::Random => nodes.pick_random(),
             SelectivePickPolicy::Serial => nodes.pick_serial(),
             SelectivePickPolicy::RoundRobin => nodes.pick_round_robin(),
+            SelectivePickPolicy::WeightedRoundRobin => nodes.pick_weighted_round_robin(),
             SelectivePickPolicy::Ketama => {
                 let key = ConsistentKey {
                     client_ip: task_notes.client_ip(),
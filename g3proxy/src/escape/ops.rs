@@ -25,6 +25,8 @@ use super::dummy_deny::DummyDenyEscaper;
 use super::proxy_float::ProxyFloatEscaper;
 use super::proxy_http::ProxyHttpEscaper;
 use super::proxy_https::ProxyHttpsEscaper;
+#[cfg(feature = "quic")]
+use super::proxy_masque::ProxyMasqueEscaper;
 use super::proxy_socks5::ProxySocks5Escaper;
 use super::proxy_socks5s::ProxySocks5sEscaper;
 use super::route_client::RouteClientEscaper;
@@ -249,6 +251,8 @@ async fn spawn_new_unlocked(config: AnyEscaperConfig) -> anyhow::Result<()> {
         AnyEscaperConfig::ProxyFloat(c) => ProxyFloatEscaper::prepare_initial(c).await?,
         AnyEscaperConfig::ProxyHttp(c) => ProxyHttpEscaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxyHttps(c) => ProxyHttpsEscaper::prepare_initial(c)?,
+        #[cfg(feature = "quic")]
+        AnyEscaperConfig::ProxyMasque(c) => ProxyMasqueEscaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxySocks5(c) => ProxySocks5Escaper::prepare_initial(c)?,
         AnyEscaperConfig::ProxySocks5s(c) => ProxySocks5sEscaper::prepare_initial(c)?,
         AnyEscaperConfig::RouteFailover(c) => RouteFailoverEscaper::prepare_initial(c)?,
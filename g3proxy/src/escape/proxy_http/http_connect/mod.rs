@@ -6,6 +6,8 @@
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use http::HeaderMap;
+use log::debug;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
 
@@ -17,9 +19,13 @@ use g3_io_ext::{
     AsyncStream, FlexBufReader, LimitedReader, LimitedStream, LimitedWriter, OnceBufReader,
 };
 use g3_openssl::{SslConnector, SslStream};
+use g3_types::net::UpstreamAddr;
 
 use super::ProxyHttpEscaper;
 use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
+use crate::module::h2_connect::{
+    self, H2ConnectError, H2ConnectPool, H2ConnectPoolAcquire, H2ConnectStream, HttpConnectStream,
+};
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectRemoteWrapperStats, TcpConnectResult, TcpConnectTaskConf,
     TcpConnectTaskNotes, TlsConnectTaskConf,
@@ -27,14 +33,15 @@ use crate::module::tcp_connect::{
 use crate::serve::ServerTaskNotes;
 
 impl ProxyHttpEscaper {
-    async fn http_connect_tcp_connect_to(
+    async fn http1_connect_to(
         &self,
+        peer_proxy: &UpstreamAddr,
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
     ) -> Result<FlexBufReader<LimitedStream<TcpStream>>, TcpConnectError> {
         let mut stream = self
-            .tcp_new_connection(task_conf, tcp_notes, task_notes)
+            .tcp_new_connection_to(peer_proxy, task_conf, tcp_notes, task_notes)
             .await?;
 
         let mut req = HttpConnectRequest::new(task_conf.upstream, &self.config.append_http_headers);
@@ -60,12 +67,89 @@ impl ProxyHttpEscaper {
         Ok(buf_stream)
     }
 
+    fn h2_connect_headers(&self, task_notes: &ServerTaskNotes) -> HeaderMap {
+        let mut headers = h2_connect::build_connect_headers(&self.config.append_http_headers);
+        if self.config.pass_proxy_userid {
+            if let Some(name) = task_notes.raw_user_name() {
+                let line = crate::module::http_header::proxy_authorization_basic_pass(name);
+                headers.extend(h2_connect::build_connect_headers([&line]));
+            }
+        }
+        headers
+    }
+
+    /// Open a CONNECT stream on a pooled HTTP/2 connection to the peer,
+    /// return `None` if we should fall back to HTTP/1.1
+    async fn h2_connect_to(
+        &self,
+        pool: &Arc<H2ConnectPool>,
+        peer_proxy: &UpstreamAddr,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<Option<H2ConnectStream>, TcpConnectError> {
+        let sender = match pool.acquire(peer_proxy) {
+            H2ConnectPoolAcquire::Reuse(sender) => {
+                sender.fill_tcp_notes(tcp_notes);
+                sender
+            }
+            H2ConnectPoolAcquire::New(permit) => {
+                let stream = self
+                    .tcp_new_connection_to(peer_proxy, task_conf, tcp_notes, task_notes)
+                    .await?;
+                match permit.handshake(stream, tcp_notes).await {
+                    Ok(sender) => sender,
+                    Err(e) => {
+                        debug!("h2 handshake with next proxy {peer_proxy} failed: {e}");
+                        return Ok(None);
+                    }
+                }
+            }
+            H2ConnectPoolAcquire::Fallback => return Ok(None),
+        };
+
+        match sender
+            .send_connect(task_conf.upstream, self.h2_connect_headers(task_notes))
+            .await
+        {
+            Ok(stream) => Ok(Some(stream)),
+            Err(H2ConnectError::Negotiation(e)) => Err(e),
+            Err(e) => {
+                debug!("h2 CONNECT to next proxy {peer_proxy} failed: {e}");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn http_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<HttpConnectStream<FlexBufReader<LimitedStream<TcpStream>>>, TcpConnectError> {
+        let peer_proxy = self.get_next_proxy(task_notes, task_conf.upstream.host());
+
+        if let Some(pool) = &self.h2_pool {
+            if let Some(stream) = self
+                .h2_connect_to(pool, peer_proxy, task_conf, tcp_notes, task_notes)
+                .await?
+            {
+                return Ok(HttpConnectStream::Http2(stream));
+            }
+        }
+
+        let buf_stream = self
+            .http1_connect_to(peer_proxy, task_conf, tcp_notes, task_notes)
+            .await?;
+        Ok(HttpConnectStream::Http1(buf_stream))
+    }
+
     async fn timed_http_connect_tcp_connect_to(
         &self,
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
-    ) -> Result<FlexBufReader<LimitedStream<TcpStream>>, TcpConnectError> {
+    ) -> Result<HttpConnectStream<FlexBufReader<LimitedStream<TcpStream>>>, TcpConnectError> {
         tokio::time::timeout(
             self.config.peer_negotiation_timeout,
             self.http_connect_tcp_connect_to(task_conf, tcp_notes, task_notes),
@@ -81,9 +165,24 @@ impl ProxyHttpEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcTcpConnectionTaskRemoteStats,
     ) -> TcpConnectResult {
-        let mut buf_stream = self
+        let mut buf_stream = match self
             .timed_http_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
-            .await?;
+            .await?
+        {
+            HttpConnectStream::Http1(buf_stream) => buf_stream,
+            HttpConnectStream::Http2(stream) => {
+                let (ups_r, ups_w) = stream.into_split();
+
+                // add task and user stats
+                let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+                wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+                let wrapper_stats = Arc::new(wrapper_stats);
+
+                let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+                let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+                return Ok((Box::new(ups_r), Box::new(ups_w)));
+            }
+        };
 
         // add in read buffered data
         let r_buffer_size = buf_stream.buffer().len() as u64;
@@ -111,12 +210,18 @@ impl ProxyHttpEscaper {
         task_notes: &ServerTaskNotes,
         tls_application: TlsApplication,
     ) -> Result<SslStream<impl AsyncRead + AsyncWrite + use<>>, TcpConnectError> {
-        let buf_stream = self
+        let stream = match self
             .timed_http_connect_tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
-            .await?;
+            .await?
+        {
+            HttpConnectStream::Http1(buf_stream) => {
+                HttpConnectStream::Http1(buf_stream.into_inner())
+            }
+            HttpConnectStream::Http2(stream) => HttpConnectStream::Http2(stream),
+        };
 
         let ssl = task_conf.build_ssl()?;
-        let connector = SslConnector::new(ssl, buf_stream.into_inner())
+        let connector = SslConnector::new(ssl, stream)
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
         match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
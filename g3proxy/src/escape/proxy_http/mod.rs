@@ -27,6 +27,7 @@ use crate::module::ftp_over_http::{
     ArcFtpTaskRemoteControlStats, ArcFtpTaskRemoteTransferStats, BoxFtpConnectContext,
     BoxFtpRemoteConnection, DirectFtpConnectContext,
 };
+use crate::module::h2_connect::H2ConnectPool;
 use crate::module::http_forward::{
     ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, BoxHttpForwardContext,
     ProxyHttpForwardContext,
@@ -58,6 +59,7 @@ pub(super) struct ProxyHttpEscaper {
     proxy_nodes: SelectiveVec<WeightedUpstreamAddr>,
     resolver_handle: Option<ArcIntegratedResolverHandle>,
     escape_logger: Option<Logger>,
+    h2_pool: Option<Arc<H2ConnectPool>>,
 }
 
 impl ProxyHttpEscaper {
@@ -82,6 +84,8 @@ impl ProxyHttpEscaper {
             Some(crate::resolve::get_handle(resolver)?)
         };
 
+        let h2_pool = config.http2_connect.map(H2ConnectPool::new);
+
         stats.set_extra_tags(config.extra_metrics_tags.clone());
 
         let escaper = ProxyHttpEscaper {
@@ -90,6 +94,7 @@ impl ProxyHttpEscaper {
             proxy_nodes,
             resolver_handle,
             escape_logger,
+            h2_pool,
         };
 
         Ok(Arc::new(escaper))
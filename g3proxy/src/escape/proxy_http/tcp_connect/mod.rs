@@ -12,7 +12,7 @@ use tokio::time::Instant;
 
 use g3_io_ext::LimitedStream;
 use g3_socket::BindAddr;
-use g3_types::net::{ConnectError, Host, ProxyProtocolEncoder};
+use g3_types::net::{ConnectError, Host, ProxyProtocolEncoder, UpstreamAddr};
 
 use super::ProxyHttpEscaper;
 use crate::log::escape::tcp_connect::EscapeLogForTcpConnect;
@@ -300,12 +300,11 @@ impl ProxyHttpEscaper {
 
     async fn tcp_connect_to(
         &self,
+        peer_proxy: &UpstreamAddr,
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
     ) -> Result<TcpStream, TcpConnectError> {
-        let peer_proxy = self.get_next_proxy(task_notes, task_conf.upstream.host());
-
         match peer_proxy.host() {
             Host::Ip(ip) => {
                 self.fixed_try_connect(
@@ -336,9 +335,21 @@ impl ProxyHttpEscaper {
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
+    ) -> Result<LimitedStream<TcpStream>, TcpConnectError> {
+        let peer_proxy = self.get_next_proxy(task_notes, task_conf.upstream.host());
+        self.tcp_new_connection_to(peer_proxy, task_conf, tcp_notes, task_notes)
+            .await
+    }
+
+    pub(super) async fn tcp_new_connection_to(
+        &self,
+        peer_proxy: &UpstreamAddr,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
     ) -> Result<LimitedStream<TcpStream>, TcpConnectError> {
         let stream = self
-            .tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .tcp_connect_to(peer_proxy, task_conf, tcp_notes, task_notes)
             .await?;
 
         let limit_config = &self.config.general.tcp_sock_speed_limit;
@@ -6,6 +6,8 @@
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use http::HeaderMap;
+use log::debug;
 use tokio::io::{AsyncRead, AsyncWrite};
 
 use g3_daemon::stat::remote::{
@@ -14,26 +16,28 @@ use g3_daemon::stat::remote::{
 use g3_http::connect::{HttpConnectRequest, HttpConnectResponse};
 use g3_io_ext::{AsyncStream, FlexBufReader, LimitedReader, LimitedWriter, OnceBufReader};
 use g3_openssl::{SslConnector, SslStream};
+use g3_types::net::UpstreamAddr;
 
 use super::ProxyHttpsEscaper;
 use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
+use crate::module::h2_connect::{
+    self, H2ConnectError, H2ConnectPoolAcquire, H2ConnectSender, H2ConnectStream, HttpConnectStream,
+};
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
 use crate::serve::ServerTaskNotes;
 
 impl ProxyHttpsEscaper {
-    async fn http_connect_tcp_connect_to(
+    async fn http1_connect_on<S>(
         &self,
+        mut stream: S,
         task_conf: &TcpConnectTaskConf<'_>,
-        tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
-    ) -> Result<FlexBufReader<SslStream<impl AsyncRead + AsyncWrite + use<>>>, TcpConnectError>
+    ) -> Result<FlexBufReader<S>, TcpConnectError>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
     {
-        let mut stream = self
-            .tls_handshake_to_remote(task_conf, tcp_notes, task_notes)
-            .await?;
-
         let mut req = HttpConnectRequest::new(task_conf.upstream, &self.config.append_http_headers);
 
         if self.config.pass_proxy_userid {
@@ -57,13 +61,110 @@ impl ProxyHttpsEscaper {
         Ok(buf_stream)
     }
 
+    fn h2_connect_headers(&self, task_notes: &ServerTaskNotes) -> HeaderMap {
+        let mut headers = h2_connect::build_connect_headers(&self.config.append_http_headers);
+        if self.config.pass_proxy_userid {
+            if let Some(name) = task_notes.raw_user_name() {
+                let line = crate::module::http_header::proxy_authorization_basic_pass(name);
+                headers.extend(h2_connect::build_connect_headers([&line]));
+            }
+        }
+        headers
+    }
+
+    /// Send CONNECT request on a new stream, return `None` if we should fall back to HTTP/1.1
+    async fn h2_send_connect(
+        &self,
+        sender: H2ConnectSender,
+        peer_proxy: &UpstreamAddr,
+        task_conf: &TcpConnectTaskConf<'_>,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<Option<H2ConnectStream>, TcpConnectError> {
+        match sender
+            .send_connect(task_conf.upstream, self.h2_connect_headers(task_notes))
+            .await
+        {
+            Ok(stream) => Ok(Some(stream)),
+            Err(H2ConnectError::Negotiation(e)) => Err(e),
+            Err(e) => {
+                debug!("h2 CONNECT to next proxy {peer_proxy} failed: {e}");
+                Ok(None)
+            }
+        }
+    }
+
+    async fn http_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<
+        HttpConnectStream<FlexBufReader<SslStream<impl AsyncRead + AsyncWrite + use<>>>>,
+        TcpConnectError,
+    > {
+        let peer_proxy = self.get_next_proxy(task_notes, task_conf.upstream.host());
+
+        let acquire = match &self.h2_pool {
+            Some(pool) => pool.acquire(peer_proxy),
+            None => H2ConnectPoolAcquire::Fallback,
+        };
+        let stream = match acquire {
+            H2ConnectPoolAcquire::Reuse(sender) => {
+                sender.fill_tcp_notes(tcp_notes);
+                if let Some(stream) = self
+                    .h2_send_connect(sender, peer_proxy, task_conf, task_notes)
+                    .await?
+                {
+                    return Ok(HttpConnectStream::Http2(stream));
+                }
+                self.tls_handshake_to_peer(peer_proxy, task_conf, tcp_notes, task_notes, false)
+                    .await?
+            }
+            H2ConnectPoolAcquire::New(permit) => {
+                let stream = self
+                    .tls_handshake_to_peer(peer_proxy, task_conf, tcp_notes, task_notes, true)
+                    .await?;
+                if stream.ssl().selected_alpn_protocol() == Some(b"h2") {
+                    match permit.handshake(stream, tcp_notes).await {
+                        Ok(sender) => {
+                            if let Some(stream) = self
+                                .h2_send_connect(sender, peer_proxy, task_conf, task_notes)
+                                .await?
+                            {
+                                return Ok(HttpConnectStream::Http2(stream));
+                            }
+                        }
+                        Err(e) => {
+                            debug!("h2 handshake with next proxy {peer_proxy} failed: {e}");
+                        }
+                    }
+                    self.tls_handshake_to_peer(peer_proxy, task_conf, tcp_notes, task_notes, false)
+                        .await?
+                } else {
+                    // the peer doesn't support h2, just use this connection
+                    permit.set_h1_only();
+                    stream
+                }
+            }
+            H2ConnectPoolAcquire::Fallback => {
+                self.tls_handshake_to_peer(peer_proxy, task_conf, tcp_notes, task_notes, false)
+                    .await?
+            }
+        };
+
+        let buf_stream = self.http1_connect_on(stream, task_conf, task_notes).await?;
+        Ok(HttpConnectStream::Http1(buf_stream))
+    }
+
     async fn timed_http_connect_tcp_connect_to(
         &self,
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
-    ) -> Result<FlexBufReader<SslStream<impl AsyncRead + AsyncWrite + use<>>>, TcpConnectError>
-    {
+    ) -> Result<
+        HttpConnectStream<FlexBufReader<SslStream<impl AsyncRead + AsyncWrite + use<>>>>,
+        TcpConnectError,
+    > {
         tokio::time::timeout(
             self.config.peer_negotiation_timeout,
             self.http_connect_tcp_connect_to(task_conf, tcp_notes, task_notes),
@@ -79,9 +180,24 @@ impl ProxyHttpsEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcTcpConnectionTaskRemoteStats,
     ) -> TcpConnectResult {
-        let buf_stream = self
+        let buf_stream = match self
             .timed_http_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
-            .await?;
+            .await?
+        {
+            HttpConnectStream::Http1(buf_stream) => buf_stream,
+            HttpConnectStream::Http2(stream) => {
+                let (ups_r, ups_w) = stream.into_split();
+
+                // add task and user stats
+                let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+                wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+                let wrapper_stats = Arc::new(wrapper_stats);
+
+                let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+                let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+                return Ok((Box::new(ups_r), Box::new(ups_w)));
+            }
+        };
 
         // add task and user stats
         // add in read buffered data
@@ -110,12 +226,18 @@ impl ProxyHttpsEscaper {
         task_notes: &ServerTaskNotes,
         tls_application: TlsApplication,
     ) -> Result<SslStream<impl AsyncRead + AsyncWrite + use<>>, TcpConnectError> {
-        let buf_stream = self
+        let stream = match self
             .timed_http_connect_tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
-            .await?;
+            .await?
+        {
+            HttpConnectStream::Http1(buf_stream) => {
+                HttpConnectStream::Http1(buf_stream.into_inner())
+            }
+            HttpConnectStream::Http2(stream) => HttpConnectStream::Http2(stream),
+        };
 
         let ssl = task_conf.build_ssl()?;
-        let connector = SslConnector::new(ssl, buf_stream.into_inner())
+        let connector = SslConnector::new(ssl, stream)
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
         match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
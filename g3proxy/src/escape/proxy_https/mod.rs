@@ -29,6 +29,7 @@ use crate::module::ftp_over_http::{
     ArcFtpTaskRemoteControlStats, ArcFtpTaskRemoteTransferStats, BoxFtpConnectContext,
     BoxFtpRemoteConnection, DirectFtpConnectContext,
 };
+use crate::module::h2_connect::H2ConnectPool;
 use crate::module::http_forward::{
     ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, BoxHttpForwardContext,
     ProxyHttpForwardContext,
@@ -62,6 +63,7 @@ pub(super) struct ProxyHttpsEscaper {
     tls_config: OpensslClientConfig,
     resolver_handle: Option<ArcIntegratedResolverHandle>,
     escape_logger: Option<Logger>,
+    h2_pool: Option<Arc<H2ConnectPool>>,
 }
 
 impl ProxyHttpsEscaper {
@@ -91,6 +93,8 @@ impl ProxyHttpsEscaper {
             Some(crate::resolve::get_handle(resolver)?)
         };
 
+        let h2_pool = config.http2_connect.map(H2ConnectPool::new);
+
         stats.set_extra_tags(config.extra_metrics_tags.clone());
 
         let escaper = ProxyHttpsEscaper {
@@ -100,6 +104,7 @@ impl ProxyHttpsEscaper {
             tls_config,
             resolver_handle,
             escape_logger,
+            h2_pool,
         };
         Ok(Arc::new(escaper))
     }
@@ -300,15 +300,12 @@ impl ProxyHttpsEscaper {
 
     async fn tcp_connect_to(
         &self,
+        peer_proxy: &UpstreamAddr,
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
-    ) -> Result<(UpstreamAddr, TcpStream), TcpConnectError> {
-        let peer_proxy = self
-            .get_next_proxy(task_notes, task_conf.upstream.host())
-            .clone();
-
-        let stream = match peer_proxy.host() {
+    ) -> Result<TcpStream, TcpConnectError> {
+        match peer_proxy.host() {
             Host::Ip(ip) => {
                 self.fixed_try_connect(
                     SocketAddr::new(*ip, peer_proxy.port()),
@@ -316,7 +313,7 @@ impl ProxyHttpsEscaper {
                     tcp_notes,
                     task_notes,
                 )
-                .await?
+                .await
             }
             Host::Domain(domain) => {
                 let resolver_job = self.resolve_happy(domain.clone())?;
@@ -328,21 +325,20 @@ impl ProxyHttpsEscaper {
                     tcp_notes,
                     task_notes,
                 )
-                .await?
+                .await
             }
-        };
-
-        Ok((peer_proxy, stream))
+        }
     }
 
     pub(super) async fn tcp_new_connection(
         &self,
+        peer_proxy: &UpstreamAddr,
         task_conf: &TcpConnectTaskConf<'_>,
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
-    ) -> Result<(UpstreamAddr, LimitedStream<TcpStream>), TcpConnectError> {
-        let (peer, stream) = self
-            .tcp_connect_to(task_conf, tcp_notes, task_notes)
+    ) -> Result<LimitedStream<TcpStream>, TcpConnectError> {
+        let stream = self
+            .tcp_connect_to(peer_proxy, task_conf, tcp_notes, task_notes)
             .await?;
 
         let limit_config = &self.config.general.tcp_sock_speed_limit;
@@ -365,6 +361,6 @@ impl ProxyHttpsEscaper {
                 .map_err(TcpConnectError::ProxyProtocolWriteFailed)?;
         }
 
-        Ok((peer, stream))
+        Ok(stream)
     }
 }
@@ -7,13 +7,15 @@ use anyhow::anyhow;
 use tokio::io::{AsyncRead, AsyncWrite};
 
 use g3_openssl::{SslConnector, SslInfoCallbackWhere, SslStream};
-use g3_types::net::{TlsAlert, TlsAlertType};
+use g3_types::net::{TlsAlert, TlsAlertType, UpstreamAddr};
 
 use super::ProxyHttpsEscaper;
 use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
 use crate::module::tcp_connect::{TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes};
 use crate::serve::ServerTaskNotes;
 
+const H2_OR_HTTP11_ALPN: &[u8] = b"\x02h2\x08http/1.1";
+
 impl ProxyHttpsEscaper {
     pub(super) async fn tls_handshake_to_remote(
         &self,
@@ -21,8 +23,23 @@ impl ProxyHttpsEscaper {
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
     ) -> Result<SslStream<impl AsyncRead + AsyncWrite + use<>>, TcpConnectError> {
-        let (peer, ups_s) = self
-            .tcp_new_connection(task_conf, tcp_notes, task_notes)
+        let peer = self.get_next_proxy(task_notes, task_conf.upstream.host());
+        self.tls_handshake_to_peer(peer, task_conf, tcp_notes, task_notes, false)
+            .await
+    }
+
+    /// Connect and do tls handshake with the peer proxy,
+    /// h2 will be offered in ALPN if `offer_h2` is set
+    pub(super) async fn tls_handshake_to_peer(
+        &self,
+        peer: &UpstreamAddr,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        offer_h2: bool,
+    ) -> Result<SslStream<impl AsyncRead + AsyncWrite + use<>>, TcpConnectError> {
+        let ups_s = self
+            .tcp_new_connection(peer, task_conf, tcp_notes, task_notes)
             .await?;
 
         let tls_name = self.config.tls_name.as_ref().unwrap_or_else(|| peer.host());
@@ -30,6 +47,10 @@ impl ProxyHttpsEscaper {
             .tls_config
             .build_ssl(tls_name, peer.port())
             .map_err(TcpConnectError::InternalTlsClientError)?;
+        if offer_h2 {
+            ssl.set_alpn_protos(H2_OR_HTTP11_ALPN)
+                .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow!(e)))?;
+        }
         let escaper_stats = self.stats.clone();
         ssl.set_info_callback(move |_ssl, r#where, ret| {
             let mask = SslInfoCallbackWhere::from_bits_retain(r#where);
@@ -59,7 +80,7 @@ impl ProxyHttpsEscaper {
                         tcp_notes,
                         task_id: &task_notes.id,
                         tls_name,
-                        tls_peer: &peer,
+                        tls_peer: peer,
                         tls_application: TlsApplication::HttpProxy,
                     }
                     .log(logger, &e);
@@ -75,7 +96,7 @@ impl ProxyHttpsEscaper {
                         tcp_notes,
                         task_id: &task_notes.id,
                         tls_name,
-                        tls_peer: &peer,
+                        tls_peer: peer,
                         tls_application: TlsApplication::HttpProxy,
                     }
                     .log(logger, &e);
@@ -0,0 +1,89 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use g3_dpi::parser::quic::VarInt;
+
+/// The context ID for UDP payloads, see RFC 9298 section 4
+const UDP_PAYLOAD_CONTEXT_ID: u64 = 0;
+
+fn put_var_int(buf: &mut BytesMut, value: u64) {
+    // all values here are less than 2^62, as the QUIC stream IDs are
+    let v = VarInt::new(value).unwrap();
+    let mut b = [0u8; 8];
+    let len = v.encode(&mut b).unwrap();
+    buf.put_slice(&b[..len]);
+}
+
+/// Build the HTTP Datagram header for UDP payloads sent on the request stream,
+/// which contains the quarter stream ID and the context ID
+pub(super) fn udp_payload_header(quarter_stream_id: u64) -> Bytes {
+    let mut buf = BytesMut::with_capacity(16);
+    put_var_int(&mut buf, quarter_stream_id);
+    put_var_int(&mut buf, UDP_PAYLOAD_CONTEXT_ID);
+    buf.freeze()
+}
+
+pub(super) fn new_datagram(header: &[u8], payload: &[u8]) -> Bytes {
+    let mut buf = BytesMut::with_capacity(header.len() + payload.len());
+    buf.put_slice(header);
+    buf.put_slice(payload);
+    buf.freeze()
+}
+
+/// Split the quarter stream ID from the received HTTP Datagram
+pub(super) fn split_quarter_stream_id(data: Bytes) -> Option<(u64, Bytes)> {
+    let id = VarInt::try_parse(&data)?;
+    Some((id.value(), data.slice(id.encoded_len()..)))
+}
+
+/// Get the UDP payload from the datagram of a connect-udp session,
+/// `None` will be returned for invalid datagrams and those with unknown context IDs
+pub(super) fn udp_payload(data: Bytes) -> Option<Bytes> {
+    let context_id = VarInt::try_parse(&data)?;
+    if context_id.value() != UDP_PAYLOAD_CONTEXT_ID {
+        return None;
+    }
+    Some(data.slice(context_id.encoded_len()..))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn udp_payload_roundtrip() {
+        for quarter_stream_id in [0, 1, 63, 64, 16383, 16384, 1 << 40] {
+            let header = udp_payload_header(quarter_stream_id);
+            let datagram = new_datagram(&header, b"hello");
+
+            let (id, data) = split_quarter_stream_id(datagram).unwrap();
+            assert_eq!(id, quarter_stream_id);
+            assert_eq!(udp_payload(data).unwrap().as_ref(), b"hello");
+        }
+
+        let header = udp_payload_header(4);
+        assert_eq!(header.as_ref(), &[0x04, 0x00]);
+        let header = udp_payload_header(100);
+        assert_eq!(header.as_ref(), &[0x40, 0x64, 0x00]);
+
+        let datagram = new_datagram(&header, b"");
+        let (_, data) = split_quarter_stream_id(datagram).unwrap();
+        assert!(udp_payload(data).unwrap().is_empty());
+    }
+
+    #[test]
+    fn skip_invalid() {
+        assert!(split_quarter_stream_id(Bytes::new()).is_none());
+        assert!(split_quarter_stream_id(Bytes::from_static(&[0x40])).is_none());
+        assert!(udp_payload(Bytes::new()).is_none());
+        assert!(udp_payload(Bytes::from_static(&[0x80, 0x00])).is_none());
+
+        // unknown context IDs
+        assert!(udp_payload(Bytes::from_static(&[0x02, 0x01, 0x02])).is_none());
+        assert!(udp_payload(Bytes::from_static(&[0x40, 0x02, 0x01])).is_none());
+    }
+}
@@ -0,0 +1,230 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use http::{HeaderMap, Method, Request, Uri, Version};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use g3_daemon::stat::remote::{
+    ArcTcpConnectionTaskRemoteStats, TcpConnectionTaskRemoteStatsWrapper,
+};
+use g3_io_ext::{AsyncStream, LimitedReader, LimitedStream, LimitedWriter};
+use g3_openssl::{SslConnector, SslStream};
+
+use super::ProxyMasqueEscaper;
+use super::pool::{MasqueConnection, MasqueStreamGuard};
+use super::stream::{H3BidiStream, H3ConnectStream};
+use crate::log::escape::tls_handshake::{EscapeLogForTlsHandshake, TlsApplication};
+use crate::module::h2_connect;
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectRemoteWrapperStats, TcpConnectResult, TcpConnectTaskConf,
+    TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::serve::ServerTaskNotes;
+
+/// Open a new request stream on the connection, the returned guard should be held
+/// as long as the stream is in use
+pub(super) async fn send_request(
+    conn: &Arc<MasqueConnection>,
+    req: Request<()>,
+) -> Result<(H3BidiStream, MasqueStreamGuard), TcpConnectError> {
+    let guard = conn.new_stream_guard();
+    let stream = conn
+        .send_request()
+        .send_request(req)
+        .await
+        .map_err(|e| TcpConnectError::NegotiationWriteFailed(io::Error::other(e)))?;
+    Ok((stream, guard))
+}
+
+pub(super) async fn recv_response(stream: &mut H3BidiStream) -> Result<(), TcpConnectError> {
+    let rsp = stream
+        .recv_response()
+        .await
+        .map_err(|e| TcpConnectError::NegotiationReadFailed(io::Error::other(e)))?;
+
+    let status = rsp.status();
+    if !status.is_success() {
+        let e = match status.as_u16() {
+            504 | 522 | 524 => TcpConnectError::NegotiationPeerTimeout,
+            code => TcpConnectError::NegotiationRejected(format!(
+                "rejected by remote proxy with response {code} {}",
+                status.canonical_reason().unwrap_or_default()
+            )),
+        };
+        return Err(e);
+    }
+    Ok(())
+}
+
+impl ProxyMasqueEscaper {
+    pub(super) fn masque_request_headers(&self, task_notes: &ServerTaskNotes) -> HeaderMap {
+        let mut headers = h2_connect::build_connect_headers(&self.config.append_http_headers);
+        if self.config.pass_proxy_userid {
+            if let Some(name) = task_notes.raw_user_name() {
+                let line = crate::module::http_header::proxy_authorization_basic_pass(name);
+                headers.extend(h2_connect::build_connect_headers([&line]));
+            }
+        }
+        headers
+    }
+
+    async fn masque_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<LimitedStream<H3ConnectStream>, TcpConnectError> {
+        let (conn, _peer_proxy) = self
+            .get_masque_connection(task_conf.upstream, tcp_notes, task_notes)
+            .await?;
+
+        let uri = Uri::builder()
+            .authority(task_conf.upstream.to_string())
+            .build()
+            .map_err(|_| {
+                TcpConnectError::InternalServerError("invalid upstream address for h3 CONNECT")
+            })?;
+        let mut req = Request::builder()
+            .method(Method::CONNECT)
+            .version(Version::HTTP_3)
+            .uri(uri)
+            .body(())
+            .map_err(|_| {
+                TcpConnectError::InternalServerError("failed to build h3 CONNECT request")
+            })?;
+        *req.headers_mut() = self.masque_request_headers(task_notes);
+
+        let (mut stream, guard) = send_request(&conn, req).await?;
+        recv_response(&mut stream).await?;
+
+        let limit_config = &self.config.general.tcp_sock_speed_limit;
+        let stream = LimitedStream::local_limited(
+            H3ConnectStream::new(stream, guard),
+            limit_config.shift_millis,
+            limit_config.max_south,
+            limit_config.max_north,
+            self.stats.clone(),
+        );
+        Ok(stream)
+    }
+
+    pub(super) async fn timed_masque_connect_tcp_connect_to(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<LimitedStream<H3ConnectStream>, TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.masque_connect_tcp_connect_to(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    pub(super) async fn masque_new_tcp_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let mut ups_s = self
+            .timed_masque_connect_tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .await?;
+
+        let mut wrapper_stats = TcpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        ups_s.reset_stats(wrapper_stats);
+        let (r, w) = ups_s.into_split();
+
+        Ok((Box::new(r), Box::new(w)))
+    }
+
+    pub(super) async fn masque_connect_tls_connect_to(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        tls_application: TlsApplication,
+    ) -> Result<SslStream<impl AsyncRead + AsyncWrite + use<>>, TcpConnectError> {
+        let ups_s = self
+            .timed_masque_connect_tcp_connect_to(&task_conf.tcp, tcp_notes, task_notes)
+            .await?;
+
+        let ssl = task_conf.build_ssl()?;
+        let connector = SslConnector::new(ssl, ups_s)
+            .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
+
+        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
+            Ok(Ok(stream)) => Ok(stream),
+            Ok(Err(e)) => {
+                let e = anyhow::Error::new(e);
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeFailed(e))
+            }
+            Err(_) => {
+                let e = anyhow!("upstream tls handshake timed out");
+                if let Some(logger) = &self.escape_logger {
+                    EscapeLogForTlsHandshake {
+                        upstream: task_conf.tcp.upstream,
+                        tcp_notes,
+                        task_id: &task_notes.id,
+                        tls_name: task_conf.tls_name,
+                        tls_peer: task_conf.tcp.upstream,
+                        tls_application,
+                    }
+                    .log(logger, &e);
+                }
+                Err(TcpConnectError::UpstreamTlsHandshakeTimeout)
+            }
+        }
+    }
+
+    pub(super) async fn masque_new_tls_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpConnectResult {
+        let tls_stream = self
+            .masque_connect_tls_connect_to(
+                task_conf,
+                tcp_notes,
+                task_notes,
+                TlsApplication::TcpStream,
+            )
+            .await?;
+
+        let (ups_r, ups_w) = tls_stream.into_split();
+
+        // add task and user stats
+        let mut wrapper_stats = TcpConnectionTaskRemoteStatsWrapper::new(task_stats);
+        wrapper_stats.push_other_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let ups_r = LimitedReader::new(ups_r, wrapper_stats.clone());
+        let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+
+        Ok((Box::new(ups_r), Box::new(ups_w)))
+    }
+}
@@ -0,0 +1,339 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use quinn::{TransportConfig, VarInt};
+use slog::Logger;
+
+use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
+use g3_resolver::{ResolveError, ResolveLocalError};
+use g3_types::collection::{SelectiveVec, SelectiveVecBuilder};
+use g3_types::metrics::NodeName;
+use g3_types::net::{
+    AlpnProtocol, Host, RustlsQuicClientConfig, UpstreamAddr, WeightedUpstreamAddr,
+};
+
+use super::{
+    ArcEscaper, ArcEscaperInternalStats, ArcEscaperStats, Escaper, EscaperExt, EscaperInternal,
+    EscaperRegistry, EscaperStats,
+};
+use crate::audit::AuditContext;
+use crate::auth::UserUpstreamTrafficStats;
+use crate::config::escaper::proxy_masque::ProxyMasqueEscaperConfig;
+use crate::config::escaper::{AnyEscaperConfig, EscaperConfig};
+use crate::module::ftp_over_http::{
+    ArcFtpTaskRemoteControlStats, ArcFtpTaskRemoteTransferStats, BoxFtpConnectContext,
+    BoxFtpRemoteConnection, DirectFtpConnectContext,
+};
+use crate::module::http_forward::{
+    ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, BoxHttpForwardContext,
+    DirectHttpForwardContext,
+};
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::module::udp_connect::{
+    ArcUdpConnectTaskRemoteStats, UdpConnectResult, UdpConnectTaskConf, UdpConnectTaskNotes,
+};
+use crate::module::udp_relay::{
+    ArcUdpRelayTaskRemoteStats, UdpRelaySetupResult, UdpRelayTaskConf, UdpRelayTaskNotes,
+};
+use crate::resolve::{ArcIntegratedResolverHandle, ArriveFirstResolveJob};
+use crate::serve::ServerTaskNotes;
+
+mod stats;
+pub(crate) use stats::ProxyMasqueEscaperStats;
+
+mod pool;
+use pool::MasqueConnectionPool;
+
+mod stream;
+
+mod datagram;
+mod udp_session;
+
+mod http_forward;
+mod masque_connect;
+mod quic_connect;
+mod udp_connect;
+mod udp_relay;
+
+pub(super) struct ProxyMasqueEscaper {
+    config: Arc<ProxyMasqueEscaperConfig>,
+    stats: Arc<ProxyMasqueEscaperStats>,
+    proxy_nodes: SelectiveVec<WeightedUpstreamAddr>,
+    tls_client: RustlsQuicClientConfig,
+    quic_transport: Arc<TransportConfig>,
+    pool: MasqueConnectionPool,
+    resolver_handle: Option<ArcIntegratedResolverHandle>,
+    escape_logger: Option<Logger>,
+}
+
+impl ProxyMasqueEscaper {
+    fn new_obj(
+        config: ProxyMasqueEscaperConfig,
+        stats: Arc<ProxyMasqueEscaperStats>,
+    ) -> anyhow::Result<ArcEscaper> {
+        let mut nodes_builder = SelectiveVecBuilder::new();
+        for node in &config.proxy_nodes {
+            nodes_builder.insert(node.clone());
+        }
+        let proxy_nodes = nodes_builder
+            .build()
+            .ok_or_else(|| anyhow!("no next proxy node set"))?;
+
+        let tls_client = config
+            .tls_client
+            .build_quic_with_alpn_protocols(Some(vec![AlpnProtocol::Http3]))?;
+        let mut quic_transport = config.quic_transport.build_for_client();
+        // the control stream and the QPACK streams are needed by the h3 client
+        quic_transport.max_concurrent_uni_streams(VarInt::from_u32(8));
+
+        let escape_logger = config.get_escape_logger();
+
+        let resolver = config.resolver();
+        let resolver_handle = if resolver.is_empty() {
+            None
+        } else {
+            Some(crate::resolve::get_handle(resolver)?)
+        };
+
+        stats.set_extra_tags(config.extra_metrics_tags.clone());
+
+        let escaper = ProxyMasqueEscaper {
+            config: Arc::new(config),
+            stats,
+            proxy_nodes,
+            tls_client,
+            quic_transport: Arc::new(quic_transport),
+            pool: MasqueConnectionPool::default(),
+            resolver_handle,
+            escape_logger,
+        };
+
+        Ok(Arc::new(escaper))
+    }
+
+    pub(super) fn prepare_initial(config: ProxyMasqueEscaperConfig) -> anyhow::Result<ArcEscaper> {
+        let stats = Arc::new(ProxyMasqueEscaperStats::new(config.name()));
+        ProxyMasqueEscaper::new_obj(config, stats)
+    }
+
+    fn prepare_reload(
+        config: AnyEscaperConfig,
+        stats: Arc<ProxyMasqueEscaperStats>,
+    ) -> anyhow::Result<ArcEscaper> {
+        if let AnyEscaperConfig::ProxyMasque(config) = config {
+            ProxyMasqueEscaper::new_obj(config, stats)
+        } else {
+            Err(anyhow!("invalid escaper config type"))
+        }
+    }
+
+    fn get_next_proxy(&self, task_notes: &ServerTaskNotes, target_host: &Host) -> &UpstreamAddr {
+        self.select_consistent(
+            &self.proxy_nodes,
+            self.config.proxy_pick_policy,
+            task_notes,
+            target_host,
+        )
+        .inner()
+    }
+
+    fn resolve_first(&self, domain: Arc<str>) -> Result<ArriveFirstResolveJob, ResolveError> {
+        if let Some(resolver_handle) = &self.resolver_handle {
+            ArriveFirstResolveJob::new(resolver_handle, self.config.resolve_strategy, domain)
+        } else {
+            Err(ResolveLocalError::NoResolverSet.into())
+        }
+    }
+
+    fn fetch_user_upstream_io_stats(
+        &self,
+        task_notes: &ServerTaskNotes,
+    ) -> Vec<Arc<UserUpstreamTrafficStats>> {
+        task_notes
+            .user_ctx()
+            .map(|ctx| ctx.fetch_upstream_traffic_stats(self.name(), self.stats.share_extra_tags()))
+            .unwrap_or_default()
+    }
+}
+
+impl EscaperExt for ProxyMasqueEscaper {}
+
+#[async_trait]
+impl Escaper for ProxyMasqueEscaper {
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    fn get_escape_stats(&self) -> Option<ArcEscaperStats> {
+        Some(Arc::clone(&self.stats) as ArcEscaperStats)
+    }
+
+    async fn publish(&self, _data: String) -> anyhow::Result<()> {
+        Err(anyhow!("not implemented"))
+    }
+
+    async fn tcp_setup_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+        _audit_ctx: &mut AuditContext,
+    ) -> TcpConnectResult {
+        self.stats.interface.add_tcp_connect_attempted();
+        tcp_notes.escaper.clone_from(&self.config.name);
+        self.masque_new_tcp_connection(task_conf, tcp_notes, task_notes, task_stats)
+            .await
+    }
+
+    async fn tls_setup_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+        _audit_ctx: &mut AuditContext,
+    ) -> TcpConnectResult {
+        self.stats.interface.add_tls_connect_attempted();
+        tcp_notes.escaper.clone_from(&self.config.name);
+        self.masque_new_tls_connection(task_conf, tcp_notes, task_notes, task_stats)
+            .await
+    }
+
+    async fn udp_setup_connection(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        udp_notes: &mut UdpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpConnectTaskRemoteStats,
+    ) -> UdpConnectResult {
+        self.stats.interface.add_udp_connect_attempted();
+        udp_notes.escaper.clone_from(&self.config.name);
+        self.udp_connect_to(task_conf, udp_notes, task_notes, task_stats)
+            .await
+    }
+
+    async fn udp_setup_relay(
+        &self,
+        task_conf: &UdpRelayTaskConf<'_>,
+        udp_notes: &mut UdpRelayTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpRelayTaskRemoteStats,
+    ) -> UdpRelaySetupResult {
+        self.stats.interface.add_udp_relay_session_attempted();
+        udp_notes.escaper.clone_from(&self.config.name);
+        self.udp_setup_relay(task_conf, task_notes, task_stats)
+            .await
+    }
+
+    fn new_http_forward_context(&self, escaper: ArcEscaper) -> BoxHttpForwardContext {
+        let ctx = DirectHttpForwardContext::new(
+            Arc::clone(&self.stats) as ArcEscaperInternalStats,
+            escaper,
+        );
+        Box::new(ctx)
+    }
+
+    async fn new_ftp_connect_context(
+        &self,
+        escaper: ArcEscaper,
+        task_conf: &TcpConnectTaskConf<'_>,
+        _task_notes: &ServerTaskNotes,
+    ) -> BoxFtpConnectContext {
+        Box::new(DirectFtpConnectContext::new(
+            escaper,
+            task_conf.upstream.clone(),
+        ))
+    }
+}
+
+#[async_trait]
+impl EscaperInternal for ProxyMasqueEscaper {
+    fn _resolver(&self) -> &NodeName {
+        self.config.resolver()
+    }
+
+    fn _depend_on_escaper(&self, _name: &NodeName) -> bool {
+        false
+    }
+
+    fn _clone_config(&self) -> AnyEscaperConfig {
+        let config = &*self.config;
+        AnyEscaperConfig::ProxyMasque(config.clone())
+    }
+
+    fn _reload(
+        &self,
+        config: AnyEscaperConfig,
+        _registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
+        let stats = Arc::clone(&self.stats);
+        ProxyMasqueEscaper::prepare_reload(config, stats)
+    }
+
+    async fn _new_http_forward_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        self.stats.interface.add_http_forward_connection_attempted();
+        tcp_notes.escaper.clone_from(&self.config.name);
+        self.http_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
+            .await
+    }
+
+    async fn _new_https_forward_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        self.stats
+            .interface
+            .add_https_forward_connection_attempted();
+        tcp_notes.escaper.clone_from(&self.config.name);
+        self.https_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
+            .await
+    }
+
+    async fn _new_ftp_control_connection(
+        &self,
+        _task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcFtpTaskRemoteControlStats,
+    ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
+        self.stats.interface.add_ftp_over_http_request_attempted();
+        self.stats.interface.add_ftp_control_connection_attempted();
+        tcp_notes.escaper.clone_from(&self.config.name);
+        Err(TcpConnectError::MethodUnavailable)
+    }
+
+    async fn _new_ftp_transfer_connection(
+        &self,
+        _task_conf: &TcpConnectTaskConf<'_>,
+        transfer_tcp_notes: &mut TcpConnectTaskNotes,
+        _control_tcp_notes: &TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcFtpTaskRemoteTransferStats,
+        _ftp_server: &UpstreamAddr,
+    ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
+        self.stats.interface.add_ftp_transfer_connection_attempted();
+        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        Err(TcpConnectError::MethodUnavailable)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,300 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::future::poll_fn;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use h3::client::SendRequest;
+use h3_quinn::OpenStreams;
+use log::debug;
+use quinn::{Connection, SendDatagramError, VarInt};
+use rustc_hash::FxHashMap;
+use tokio::sync::mpsc;
+
+use g3_socket::BindAddr;
+use g3_types::net::UpstreamAddr;
+
+use super::datagram;
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+
+const H3_NO_ERROR: u32 = 0x100;
+
+type ConnectionSlot = Arc<tokio::sync::Mutex<Option<Arc<MasqueConnection>>>>;
+
+/// Shared QUIC connections to the next hop MASQUE proxies, keyed by the peer address
+#[derive(Default)]
+pub(super) struct MasqueConnectionPool {
+    slots: Mutex<HashMap<UpstreamAddr, ConnectionSlot>>,
+}
+
+impl MasqueConnectionPool {
+    /// Get the slot for the peer, the caller should hold the lock of the slot
+    /// when establishing a new connection, so only one will be created at a time
+    pub(super) fn slot(&self, peer: &UpstreamAddr) -> ConnectionSlot {
+        let mut slots = self.slots.lock().unwrap();
+        slots.entry(peer.clone()).or_default().clone()
+    }
+}
+
+pub(super) struct MasqueConnection {
+    quic: Connection,
+    send_request: SendRequest<OpenStreams, Bytes>,
+    bind: BindAddr,
+    local: SocketAddr,
+    peer: SocketAddr,
+    active_streams: AtomicUsize,
+    idle_since: Mutex<Instant>,
+    udp_sessions: Mutex<FxHashMap<u64, mpsc::Sender<Bytes>>>,
+}
+
+impl MasqueConnection {
+    pub(super) fn new(
+        quic: Connection,
+        send_request: SendRequest<OpenStreams, Bytes>,
+        bind: BindAddr,
+        local: SocketAddr,
+    ) -> Self {
+        let peer = quic.remote_address();
+        MasqueConnection {
+            quic,
+            send_request,
+            bind,
+            local,
+            peer,
+            active_streams: AtomicUsize::new(0),
+            idle_since: Mutex::new(Instant::now()),
+            udp_sessions: Mutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Drive the h3 connection and dispatch the received datagrams,
+    /// the connection will be closed if it has been idle for `idle_timeout`
+    pub(super) fn spawn_driver(
+        self: &Arc<Self>,
+        mut driver: h3::client::Connection<h3_quinn::Connection, Bytes>,
+        idle_timeout: Duration,
+    ) {
+        let conn = self.clone();
+        tokio::spawn(async move {
+            let mut idle_interval = tokio::time::interval(idle_timeout);
+            idle_interval.tick().await;
+            loop {
+                tokio::select! {
+                    e = poll_fn(|cx| driver.poll_close(cx)) => {
+                        debug!("h3 connection to next proxy {} closed: {e}", conn.peer);
+                        break;
+                    }
+                    r = conn.quic.read_datagram() => {
+                        match r {
+                            Ok(data) => conn.dispatch_datagram(data),
+                            Err(e) => {
+                                debug!("quic connection to next proxy {} closed: {e}", conn.peer);
+                                break;
+                            }
+                        }
+                    }
+                    _ = idle_interval.tick() => {
+                        if conn.is_idle(idle_timeout) {
+                            conn.quic.close(VarInt::from_u32(H3_NO_ERROR), b"idle");
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    fn is_idle(&self, idle_timeout: Duration) -> bool {
+        self.active_streams.load(Ordering::Acquire) == 0
+            && self.idle_since.lock().unwrap().elapsed() >= idle_timeout
+    }
+
+    fn dispatch_datagram(&self, data: Bytes) {
+        let Some((quarter_stream_id, data)) = datagram::split_quarter_stream_id(data) else {
+            return;
+        };
+        let sessions = self.udp_sessions.lock().unwrap();
+        if let Some(sender) = sessions.get(&quarter_stream_id) {
+            // drop the packet if the session queue is full
+            let _ = sender.try_send(data);
+        }
+    }
+
+    #[inline]
+    pub(super) fn is_closed(&self) -> bool {
+        self.quic.close_reason().is_some()
+    }
+
+    pub(super) fn fill_tcp_notes(&self, tcp_notes: &mut TcpConnectTaskNotes) {
+        tcp_notes.bind = self.bind;
+        tcp_notes.next = Some(self.peer);
+        tcp_notes.local = Some(self.local);
+    }
+
+    #[inline]
+    pub(super) fn local_addr(&self) -> SocketAddr {
+        self.local
+    }
+
+    #[inline]
+    pub(super) fn peer_addr(&self) -> SocketAddr {
+        self.peer
+    }
+
+    pub(super) fn send_request(&self) -> SendRequest<OpenStreams, Bytes> {
+        self.send_request.clone()
+    }
+
+    pub(super) fn new_stream_guard(self: &Arc<Self>) -> MasqueStreamGuard {
+        self.active_streams.fetch_add(1, Ordering::AcqRel);
+        MasqueStreamGuard { conn: self.clone() }
+    }
+
+    pub(super) fn send_datagram(&self, data: Bytes) -> Result<(), SendDatagramError> {
+        self.quic.send_datagram(data)
+    }
+}
+
+/// Keep the connection active while there are streams on it
+pub(super) struct MasqueStreamGuard {
+    conn: Arc<MasqueConnection>,
+}
+
+impl MasqueStreamGuard {
+    /// Register a connect-udp session on the request stream,
+    /// the datagrams for this session will be sent to the returned receiver
+    pub(super) fn register_udp_session(
+        &self,
+        quarter_stream_id: u64,
+        queue_size: usize,
+    ) -> MasqueUdpSessionGuard {
+        let (sender, receiver) = mpsc::channel(queue_size);
+        let mut sessions = self.conn.udp_sessions.lock().unwrap();
+        sessions.insert(quarter_stream_id, sender);
+        MasqueUdpSessionGuard {
+            conn: self.conn.clone(),
+            quarter_stream_id,
+            receiver,
+        }
+    }
+
+    #[inline]
+    pub(super) fn connection(&self) -> &Arc<MasqueConnection> {
+        &self.conn
+    }
+}
+
+impl Drop for MasqueStreamGuard {
+    fn drop(&mut self) {
+        if self.conn.active_streams.fetch_sub(1, Ordering::AcqRel) == 1 {
+            *self.conn.idle_since.lock().unwrap() = Instant::now();
+        }
+    }
+}
+
+pub(super) struct MasqueUdpSessionGuard {
+    conn: Arc<MasqueConnection>,
+    quarter_stream_id: u64,
+    pub(super) receiver: mpsc::Receiver<Bytes>,
+}
+
+impl Drop for MasqueUdpSessionGuard {
+    fn drop(&mut self) {
+        let mut sessions = self.conn.udp_sessions.lock().unwrap();
+        sessions.remove(&self.quarter_stream_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use super::super::tests::MasqueTestServer;
+
+    fn udp_datagram(quarter_stream_id: u64, payload: &[u8]) -> Bytes {
+        let header = datagram::udp_payload_header(quarter_stream_id);
+        datagram::new_datagram(&header, payload)
+    }
+
+    #[tokio::test]
+    async fn dispatch_udp_session() {
+        let server = MasqueTestServer::start();
+        let conn = server.connect(Duration::from_secs(60)).await;
+
+        let guard = conn.new_stream_guard();
+        let mut s1 = guard.register_udp_session(1, 2);
+        let mut s2 = guard.register_udp_session(100, 2);
+
+        conn.dispatch_datagram(udp_datagram(1, b"a"));
+        conn.dispatch_datagram(udp_datagram(100, b"b"));
+        conn.dispatch_datagram(udp_datagram(2, b"c"));
+        conn.dispatch_datagram(Bytes::new());
+        // the context ID is left to the session
+        assert_eq!(s1.receiver.try_recv().unwrap().as_ref(), b"\x00a");
+        assert_eq!(s2.receiver.try_recv().unwrap().as_ref(), b"\x00b");
+        assert!(s1.receiver.try_recv().is_err());
+        assert!(s2.receiver.try_recv().is_err());
+
+        // drop the packets if the queue is full
+        for p in [b"1", b"2", b"3"] {
+            conn.dispatch_datagram(udp_datagram(1, p));
+        }
+        assert_eq!(s1.receiver.try_recv().unwrap().as_ref(), b"\x001");
+        assert_eq!(s1.receiver.try_recv().unwrap().as_ref(), b"\x002");
+        assert!(s1.receiver.try_recv().is_err());
+
+        drop(s1);
+        assert!(!conn.udp_sessions.lock().unwrap().contains_key(&1));
+        assert!(conn.udp_sessions.lock().unwrap().contains_key(&100));
+        conn.dispatch_datagram(udp_datagram(1, b"a"));
+        drop(s2);
+        assert!(conn.udp_sessions.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn dispatch_from_peer() {
+        let server = MasqueTestServer::start();
+        let conn = server.connect(Duration::from_secs(60)).await;
+
+        let guard = conn.new_stream_guard();
+        let mut s = guard.register_udp_session(4, 4);
+        conn.send_datagram(udp_datagram(4, b"ping")).unwrap();
+        conn.send_datagram(udp_datagram(8, b"pong")).unwrap();
+
+        let data = tokio::time::timeout(Duration::from_secs(5), s.receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(data.as_ref(), b"\x00ping");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(s.receiver.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn idle_close() {
+        let server = MasqueTestServer::start();
+        let conn = server.connect(Duration::from_millis(100)).await;
+
+        let guard = conn.new_stream_guard();
+        assert!(!conn.is_idle(Duration::ZERO));
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert!(!conn.is_closed());
+
+        drop(guard);
+        assert!(conn.is_idle(Duration::ZERO));
+        assert!(!conn.is_idle(Duration::from_secs(60)));
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert!(conn.is_closed());
+        match conn.quic.close_reason() {
+            Some(quinn::ConnectionError::LocallyClosed) => {}
+            r => panic!("unexpected close reason: {r:?}"),
+        }
+    }
+}
@@ -0,0 +1,193 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::poll_fn;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+use quinn::{ClientConfig, ConnectionError, Endpoint, TokioRuntime};
+use tokio::time::Instant;
+
+use g3_socket::BindAddr;
+use g3_types::net::{ConnectError, Host, UpstreamAddr};
+
+use super::ProxyMasqueEscaper;
+use super::pool::MasqueConnection;
+use crate::log::escape::tcp_connect::EscapeLogForTcpConnect;
+use crate::module::tcp_connect::{TcpConnectError, TcpConnectTaskNotes};
+use crate::serve::ServerTaskNotes;
+
+impl ProxyMasqueEscaper {
+    fn prepare_quic_socket(
+        &self,
+        peer: SocketAddr,
+    ) -> Result<(std::net::UdpSocket, BindAddr), TcpConnectError> {
+        let bind_ip = match peer.ip() {
+            IpAddr::V4(_) => {
+                if self.config.no_ipv4 {
+                    return Err(TcpConnectError::ForbiddenAddressFamily);
+                }
+                self.config.bind_v4.map(IpAddr::V4)
+            }
+            IpAddr::V6(_) => {
+                if self.config.no_ipv6 {
+                    return Err(TcpConnectError::ForbiddenAddressFamily);
+                }
+                self.config.bind_v6.map(IpAddr::V6)
+            }
+        };
+
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        let bind = bind_ip.map(BindAddr::Ip).unwrap_or_else(|| {
+            self.config
+                .bind_interface
+                .map(BindAddr::Interface)
+                .unwrap_or_default()
+        });
+        #[cfg(not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        )))]
+        let bind = bind_ip.map(BindAddr::Ip).unwrap_or_default();
+        let socket = g3_socket::udp::new_std_socket_to(
+            peer,
+            &bind,
+            self.config.socket_buffer,
+            self.config.udp_misc_opts,
+        )
+        .map_err(TcpConnectError::SetupSocketFailed)?;
+        socket
+            .connect(peer)
+            .map_err(TcpConnectError::SetupSocketFailed)?;
+        Ok((socket, bind))
+    }
+
+    async fn resolve_peer_proxy(
+        &self,
+        peer_proxy: &UpstreamAddr,
+    ) -> Result<SocketAddr, TcpConnectError> {
+        match peer_proxy.host() {
+            Host::Ip(ip) => Ok(SocketAddr::new(*ip, peer_proxy.port())),
+            Host::Domain(domain) => {
+                let mut resolver_job = self.resolve_first(domain.clone())?;
+                let ip = poll_fn(|cx| resolver_job.poll_best_addr(cx)).await?;
+                Ok(SocketAddr::new(ip, peer_proxy.port()))
+            }
+        }
+    }
+
+    async fn new_masque_connection(
+        &self,
+        peer_proxy: &UpstreamAddr,
+        upstream: &UpstreamAddr,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<Arc<MasqueConnection>, TcpConnectError> {
+        let peer = self.resolve_peer_proxy(peer_proxy).await?;
+        let (socket, bind) = self.prepare_quic_socket(peer)?;
+        tcp_notes.next = Some(peer);
+        tcp_notes.bind = bind;
+        let local = socket
+            .local_addr()
+            .map_err(TcpConnectError::SetupSocketFailed)?;
+        tcp_notes.local = Some(local);
+
+        let endpoint = Endpoint::new(Default::default(), None, socket, Arc::new(TokioRuntime))
+            .map_err(TcpConnectError::SetupSocketFailed)?;
+        let mut client_config = ClientConfig::new(self.tls_client.driver.clone());
+        client_config.transport_config(self.quic_transport.clone());
+        let tls_name = self
+            .config
+            .tls_name
+            .as_ref()
+            .unwrap_or_else(|| peer_proxy.host())
+            .to_string();
+        let client_connect = endpoint
+            .connect_with(client_config, peer, &tls_name)
+            .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
+
+        let instant_now = Instant::now();
+        self.stats.tcp.connect.add_attempted();
+        tcp_notes.tries = 1;
+        let r = tokio::time::timeout(self.tls_client.handshake_timeout, client_connect).await;
+        tcp_notes.duration = instant_now.elapsed();
+        let e = match r {
+            Ok(Ok(quic)) => {
+                self.stats.tcp.connect.add_success();
+                match h3::client::builder()
+                    .enable_extended_connect(true)
+                    .enable_datagram(true)
+                    .build(h3_quinn::Connection::new(quic.clone()))
+                    .await
+                {
+                    Ok((driver, send_request)) => {
+                        self.stats.tcp.connect.add_established();
+                        let conn = Arc::new(MasqueConnection::new(quic, send_request, bind, local));
+                        conn.spawn_driver(driver, self.config.connection_idle_timeout);
+                        return Ok(conn);
+                    }
+                    Err(_) => TcpConnectError::NegotiationProtocolErr,
+                }
+            }
+            Ok(Err(ConnectionError::TimedOut)) => {
+                self.stats.tcp.connect.add_timeout();
+                TcpConnectError::ConnectFailed(ConnectError::TimedOut)
+            }
+            Ok(Err(e)) => {
+                self.stats.tcp.connect.add_error();
+                TcpConnectError::PeerTlsHandshakeFailed(anyhow::Error::new(e))
+            }
+            Err(_) => {
+                self.stats.tcp.connect.add_timeout();
+                TcpConnectError::PeerTlsHandshakeTimeout
+            }
+        };
+
+        if let Some(logger) = &self.escape_logger {
+            EscapeLogForTcpConnect {
+                upstream,
+                tcp_notes,
+                task_id: &task_notes.id,
+            }
+            .log(logger, &e);
+        }
+        Err(e)
+    }
+
+    /// Get a usable connection to the next proxy, a new one will be created if none available
+    pub(super) async fn get_masque_connection(
+        &self,
+        upstream: &UpstreamAddr,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(Arc<MasqueConnection>, UpstreamAddr), TcpConnectError> {
+        let peer_proxy = self.get_next_proxy(task_notes, upstream.host()).clone();
+
+        let slot = self.pool.slot(&peer_proxy);
+        let mut slot = slot.lock().await;
+        if let Some(conn) = slot.as_ref().filter(|c| !c.is_closed()) {
+            conn.fill_tcp_notes(tcp_notes);
+            return Ok((conn.clone(), peer_proxy));
+        }
+
+        let conn = self
+            .new_masque_connection(&peer_proxy, upstream, tcp_notes, task_notes)
+            .await
+            .inspect_err(|_| {
+                slot.take();
+            })?;
+        *slot = Some(conn.clone());
+        Ok((conn, peer_proxy))
+    }
+}
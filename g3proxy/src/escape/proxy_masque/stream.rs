@@ -0,0 +1,198 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Buf, Bytes};
+use h3::client::RequestStream;
+use h3::error::StreamError;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_util::sync::ReusableBoxFuture;
+
+use g3_io_ext::AsyncStream;
+
+use super::pool::MasqueStreamGuard;
+
+pub(super) type H3BidiStream = RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>;
+pub(super) type H3SendStream = RequestStream<h3_quinn::SendStream<Bytes>, Bytes>;
+pub(super) type H3RecvStream = RequestStream<h3_quinn::RecvStream, Bytes>;
+
+pub(crate) struct H3ConnectReader {
+    inner: H3RecvStream,
+    buf: Bytes,
+    _guard: Arc<MasqueStreamGuard>,
+}
+
+impl AsyncRead for H3ConnectReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while self.buf.is_empty() {
+            match ready!(self.inner.poll_recv_data(cx)) {
+                Ok(Some(mut data)) => {
+                    self.buf = data.copy_to_bytes(data.remaining());
+                }
+                Ok(None) => return Poll::Ready(Ok(())),
+                Err(e) => return Poll::Ready(Err(io::Error::other(e))),
+            }
+        }
+
+        let to_copy = self.buf.len().min(buf.remaining());
+        buf.put_slice(&self.buf[..to_copy]);
+        self.buf.advance(to_copy);
+        Poll::Ready(Ok(()))
+    }
+}
+
+type SendResult = (H3SendStream, Result<(), StreamError>);
+
+/// The h3 stream only has async send methods, so we move the stream into the sending future,
+/// and get it back when the future is done
+pub(crate) struct H3ConnectWriter {
+    inner: Option<H3SendStream>,
+    send_fut: ReusableBoxFuture<'static, SendResult>,
+    sending: bool,
+    finished: bool,
+    _guard: Arc<MasqueStreamGuard>,
+}
+
+impl H3ConnectWriter {
+    fn poll_send_done(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if self.sending {
+            let (stream, r) = ready!(self.send_fut.poll(cx));
+            self.sending = false;
+            self.inner = Some(stream);
+            r.map_err(io::Error::other)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn take_stream(&mut self) -> io::Result<H3SendStream> {
+        if self.finished {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "h3 stream already finished",
+            ));
+        }
+        self.inner
+            .take()
+            .ok_or_else(|| io::Error::other("h3 stream is not available"))
+    }
+}
+
+impl AsyncWrite for H3ConnectWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        ready!(self.poll_send_done(cx))?;
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let mut stream = self.take_stream()?;
+        let data = Bytes::copy_from_slice(buf);
+        self.send_fut.set(async move {
+            let r = stream.send_data(data).await;
+            (stream, r)
+        });
+        self.sending = true;
+        // the data has been moved into the future, return error only if failed immediately
+        match self.poll_send_done(cx) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            _ => Poll::Ready(Ok(buf.len())),
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_send_done(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_send_done(cx))?;
+        if self.finished {
+            return Poll::Ready(Ok(()));
+        }
+
+        let mut stream = self.take_stream()?;
+        self.send_fut.set(async move {
+            let r = stream.finish().await;
+            (stream, r)
+        });
+        self.sending = true;
+        self.finished = true;
+        self.poll_send_done(cx)
+    }
+}
+
+/// A CONNECT tunnel carried by a HTTP/3 request stream
+pub(crate) struct H3ConnectStream {
+    reader: H3ConnectReader,
+    writer: H3ConnectWriter,
+}
+
+impl H3ConnectStream {
+    pub(super) fn new(stream: H3BidiStream, guard: MasqueStreamGuard) -> Self {
+        let guard = Arc::new(guard);
+        let (send_stream, recv_stream) = stream.split();
+        H3ConnectStream {
+            reader: H3ConnectReader {
+                inner: recv_stream,
+                buf: Bytes::new(),
+                _guard: guard.clone(),
+            },
+            writer: H3ConnectWriter {
+                inner: Some(send_stream),
+                send_fut: ReusableBoxFuture::new(std::future::pending()),
+                sending: false,
+                finished: false,
+                _guard: guard,
+            },
+        }
+    }
+}
+
+impl AsyncRead for H3ConnectStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.reader).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for H3ConnectStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.writer).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_shutdown(cx)
+    }
+}
+
+impl AsyncStream for H3ConnectStream {
+    type R = H3ConnectReader;
+    type W = H3ConnectWriter;
+
+    fn into_split(self) -> (Self::R, Self::W) {
+        (self.reader, self.writer)
+    }
+}
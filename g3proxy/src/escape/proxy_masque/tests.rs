@@ -0,0 +1,205 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::{Response, StatusCode};
+use openssl::asn1::Asn1Time;
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::PKey;
+use openssl::x509::X509Builder;
+use openssl::x509::extension::SubjectAlternativeName;
+use quinn::crypto::rustls::{QuicClientConfig, QuicServerConfig};
+use quinn::{ClientConfig, Endpoint, ServerConfig};
+use rustls::RootCertStore;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+
+use g3_socket::BindAddr;
+use g3_types::net::UpstreamAddr;
+
+use super::pool::MasqueConnection;
+
+/// The target port for which the server will reject the connect-udp requests
+pub(super) const REJECTED_PORT: u16 = 1;
+/// The target port for which the server will close the session after the response
+pub(super) const CLOSED_PORT: u16 = 7;
+
+fn install_crypto_provider() {
+    #[cfg(feature = "rustls-ring")]
+    let _ = rustls::crypto::ring::default_provider().install_default();
+    #[cfg(not(feature = "rustls-ring"))]
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+}
+
+fn new_cert_pair() -> (CertificateDer<'static>, PrivateKeyDer<'static>) {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+    let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+    let mut builder = X509Builder::new().unwrap();
+    builder.set_version(2).unwrap();
+    builder.set_pubkey(&key).unwrap();
+    builder
+        .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+        .unwrap();
+    builder
+        .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+        .unwrap();
+    let san = SubjectAlternativeName::new()
+        .dns("localhost")
+        .build(&builder.x509v3_context(None, None))
+        .unwrap();
+    builder.append_extension(san).unwrap();
+    builder.sign(&key, MessageDigest::sha256()).unwrap();
+
+    let cert = CertificateDer::from(builder.build().to_der().unwrap());
+    let key = PrivatePkcs8KeyDer::from(key.private_key_to_pkcs8().unwrap());
+    (cert, PrivateKeyDer::Pkcs8(key))
+}
+
+/// A MASQUE server which echoes all received datagrams
+pub(super) struct MasqueTestServer {
+    addr: SocketAddr,
+    cert: CertificateDer<'static>,
+    requests: Arc<Mutex<Vec<UpstreamAddr>>>,
+}
+
+impl MasqueTestServer {
+    pub(super) fn start() -> Self {
+        install_crypto_provider();
+        let (cert, key) = new_cert_pair();
+        let mut tls_config = rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert.clone()], key)
+            .unwrap();
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        let quic_config = QuicServerConfig::try_from(tls_config).unwrap();
+        let endpoint = Endpoint::server(
+            ServerConfig::with_crypto(Arc::new(quic_config)),
+            SocketAddr::from(([127, 0, 0, 1], 0)),
+        )
+        .unwrap();
+        let addr = endpoint.local_addr().unwrap();
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server_requests = requests.clone();
+        tokio::spawn(async move {
+            while let Some(incoming) = endpoint.accept().await {
+                let Ok(conn) = incoming.await else {
+                    continue;
+                };
+                tokio::spawn(serve_connection(conn, server_requests.clone()));
+            }
+        });
+
+        MasqueTestServer {
+            addr,
+            cert,
+            requests,
+        }
+    }
+
+    /// Get the targets of all received connect-udp requests
+    pub(super) fn requests(&self) -> Vec<UpstreamAddr> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub(super) fn peer_proxy(&self) -> UpstreamAddr {
+        UpstreamAddr::from_host_str_and_port("localhost", self.addr.port()).unwrap()
+    }
+
+    /// Connect to the server and drive the h3 connection in the background
+    pub(super) async fn connect(&self, idle_timeout: Duration) -> Arc<MasqueConnection> {
+        let mut roots = RootCertStore::empty();
+        roots.add(self.cert.clone()).unwrap();
+        let mut tls_config = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        tls_config.alpn_protocols = vec![b"h3".to_vec()];
+        let quic_config = QuicClientConfig::try_from(tls_config).unwrap();
+
+        let endpoint = Endpoint::client(SocketAddr::from(([127, 0, 0, 1], 0))).unwrap();
+        let local = endpoint.local_addr().unwrap();
+        let quic = endpoint
+            .connect_with(
+                ClientConfig::new(Arc::new(quic_config)),
+                self.addr,
+                "localhost",
+            )
+            .unwrap()
+            .await
+            .unwrap();
+        let (driver, send_request) = h3::client::builder()
+            .enable_extended_connect(true)
+            .enable_datagram(true)
+            .build(h3_quinn::Connection::new(quic.clone()))
+            .await
+            .unwrap();
+        let conn = Arc::new(MasqueConnection::new(
+            quic,
+            send_request,
+            BindAddr::None,
+            local,
+        ));
+        conn.spawn_driver(driver, idle_timeout);
+        conn
+    }
+}
+
+fn parse_udp_target(path: &str) -> Option<UpstreamAddr> {
+    let path = path.strip_prefix("/.well-known/masque/udp/")?;
+    let mut parts = path.split('/');
+    let host = parts.next()?.replace("%3A", ":");
+    let port = parts.next()?.parse::<u16>().ok()?;
+    UpstreamAddr::from_host_str_and_port(&host, port).ok()
+}
+
+async fn serve_connection(conn: quinn::Connection, requests: Arc<Mutex<Vec<UpstreamAddr>>>) {
+    let echo_conn = conn.clone();
+    tokio::spawn(async move {
+        while let Ok(data) = echo_conn.read_datagram().await {
+            let _ = echo_conn.send_datagram(data);
+        }
+    });
+
+    let mut h3_conn = h3::server::builder()
+        .enable_extended_connect(true)
+        .enable_datagram(true)
+        .build::<_, Bytes>(h3_quinn::Connection::new(conn))
+        .await
+        .unwrap();
+    // keep the session streams open
+    let mut streams = Vec::new();
+    while let Ok(Some(resolver)) = h3_conn.accept().await {
+        let Ok((req, mut stream)) = resolver.resolve_request().await else {
+            continue;
+        };
+        let Some(target) = parse_udp_target(req.uri().path()) else {
+            continue;
+        };
+        requests.lock().unwrap().push(target.clone());
+
+        let status = if target.port() == REJECTED_PORT {
+            StatusCode::FORBIDDEN
+        } else {
+            StatusCode::OK
+        };
+        let rsp = Response::builder()
+            .status(status)
+            .header("capsule-protocol", "?1")
+            .body(())
+            .unwrap();
+        if stream.send_response(rsp).await.is_err() {
+            continue;
+        }
+        if status != StatusCode::OK || target.port() == CLOSED_PORT {
+            let _ = stream.finish().await;
+        }
+        streams.push(stream);
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use super::ProxyMasqueEscaper;
+use super::udp_session::{MasqueUdpSession, connect_udp_request};
+use crate::module::tcp_connect::{TcpConnectError, TcpConnectTaskNotes};
+use crate::module::udp_connect::{
+    ArcUdpConnectTaskRemoteStats, UdpConnectError, UdpConnectRemoteWrapperStats, UdpConnectResult,
+    UdpConnectTaskConf, UdpConnectTaskNotes,
+};
+use crate::serve::ServerTaskNotes;
+
+mod recv;
+mod send;
+
+use recv::ProxyMasqueUdpConnectRemoteRecv;
+use send::ProxyMasqueUdpConnectRemoteSend;
+
+impl ProxyMasqueEscaper {
+    async fn masque_connect_udp(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<MasqueUdpSession, TcpConnectError> {
+        let (conn, peer_proxy) = self
+            .get_masque_connection(task_conf.upstream, tcp_notes, task_notes)
+            .await?;
+
+        let req = connect_udp_request(
+            &peer_proxy,
+            task_conf.upstream,
+            self.masque_request_headers(task_notes),
+        )?;
+        MasqueUdpSession::open(&conn, req, self.config.udp_packet_queue_size).await
+    }
+
+    async fn timed_masque_connect_udp(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<MasqueUdpSession, TcpConnectError> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.masque_connect_udp(task_conf, tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+
+    pub(super) async fn udp_connect_to(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        udp_notes: &mut UdpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpConnectTaskRemoteStats,
+    ) -> UdpConnectResult {
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        let session = self
+            .timed_masque_connect_udp(task_conf, &mut tcp_notes, task_notes)
+            .await
+            .map_err(|e| UdpConnectError::SetupSocketFailed(io::Error::other(e)))?;
+
+        let conn = session.connection().clone();
+        udp_notes.bind = tcp_notes.bind;
+        udp_notes.local = Some(conn.local_addr());
+        udp_notes.next = Some(conn.peer_addr());
+
+        let mut wrapper_stats = UdpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let send = ProxyMasqueUdpConnectRemoteSend::new(
+            conn,
+            session.quarter_stream_id(),
+            wrapper_stats.clone(),
+        );
+        let recv = ProxyMasqueUdpConnectRemoteRecv::new(session, wrapper_stats);
+
+        Ok((Box::new(recv), Box::new(send), self.escape_logger.clone()))
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+
+use g3_io_ext::{LimitedRecvStats, UdpCopyRemoteError, UdpCopyRemoteRecv};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::{UdpCopyPacket, UdpCopyPacketMeta};
+
+use super::super::udp_session::MasqueUdpSession;
+use crate::module::udp_connect::UdpConnectRemoteWrapperStats;
+
+pub(super) struct ProxyMasqueUdpConnectRemoteRecv {
+    session: MasqueUdpSession,
+    stats: Arc<UdpConnectRemoteWrapperStats>,
+}
+
+impl ProxyMasqueUdpConnectRemoteRecv {
+    pub(super) fn new(session: MasqueUdpSession, stats: Arc<UdpConnectRemoteWrapperStats>) -> Self {
+        ProxyMasqueUdpConnectRemoteRecv { session, stats }
+    }
+
+    fn poll_next_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Bytes, UdpCopyRemoteError>> {
+        match ready!(self.session.poll_recv_payload(cx)) {
+            Ok(Some(payload)) => Poll::Ready(Ok(payload)),
+            Ok(None) => Poll::Ready(Err(UdpCopyRemoteError::RemoteSessionClosed)),
+            Err(e) => Poll::Ready(Err(UdpCopyRemoteError::RemoteSessionError(e))),
+        }
+    }
+}
+
+impl UdpCopyRemoteRecv for ProxyMasqueUdpConnectRemoteRecv {
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyRemoteError>> {
+        let payload = ready!(self.poll_next_payload(cx))?;
+        let len = payload.len().min(buf.len());
+        buf[..len].copy_from_slice(&payload[..len]);
+
+        self.stats.add_recv_bytes(len);
+        self.stats.add_recv_packet();
+        Poll::Ready(Ok((0, len)))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        let mut count = 0;
+        let mut total_len = 0;
+        for p in packets.iter_mut() {
+            let payload = match self.poll_next_payload(cx) {
+                Poll::Pending => break,
+                Poll::Ready(Ok(payload)) => payload,
+                Poll::Ready(Err(e)) => {
+                    if count > 0 {
+                        break;
+                    }
+                    return Poll::Ready(Err(e));
+                }
+            };
+
+            let buf = p.buf_mut();
+            let len = payload.len().min(buf.len());
+            buf[..len].copy_from_slice(&payload[..len]);
+            let meta = UdpCopyPacketMeta::new(&std::io::IoSliceMut::new(buf), 0, len);
+            meta.set_packet(p);
+
+            count += 1;
+            total_len += len;
+        }
+
+        if count == 0 {
+            return Poll::Pending;
+        }
+        self.stats.add_recv_bytes(total_len);
+        self.stats.add_recv_packets(count);
+        Poll::Ready(Ok(count))
+    }
+}
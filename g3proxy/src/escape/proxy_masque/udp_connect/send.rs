@@ -0,0 +1,96 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use quinn::SendDatagramError;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::UdpCopyPacket;
+use g3_io_ext::{LimitedSendStats, UdpCopyRemoteError, UdpCopyRemoteSend};
+
+use super::super::datagram;
+use super::super::pool::MasqueConnection;
+use crate::module::udp_connect::UdpConnectRemoteWrapperStats;
+
+pub(super) struct ProxyMasqueUdpConnectRemoteSend {
+    conn: Arc<MasqueConnection>,
+    /// the quarter stream ID and the context ID 0
+    header: Bytes,
+    stats: Arc<UdpConnectRemoteWrapperStats>,
+}
+
+impl ProxyMasqueUdpConnectRemoteSend {
+    pub(super) fn new(
+        conn: Arc<MasqueConnection>,
+        quarter_stream_id: u64,
+        stats: Arc<UdpConnectRemoteWrapperStats>,
+    ) -> Self {
+        ProxyMasqueUdpConnectRemoteSend {
+            conn,
+            header: datagram::udp_payload_header(quarter_stream_id),
+            stats,
+        }
+    }
+
+    fn send_datagram(&self, payload: &[u8]) -> Result<(), UdpCopyRemoteError> {
+        let datagram = datagram::new_datagram(&self.header, payload);
+        match self.conn.send_datagram(datagram) {
+            Ok(_) => Ok(()),
+            // drop the packet like what will happen on a real udp socket
+            Err(SendDatagramError::TooLarge) => Ok(()),
+            Err(e) => Err(UdpCopyRemoteError::SendFailed(io::Error::other(e))),
+        }
+    }
+}
+
+impl UdpCopyRemoteSend for ProxyMasqueUdpConnectRemoteSend {
+    fn poll_send_packet(
+        &mut self,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        self.send_datagram(buf)?;
+        self.stats.add_send_bytes(buf.len());
+        self.stats.add_send_packet();
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_send_packets(
+        &mut self,
+        _cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyRemoteError>> {
+        let mut total_len = 0;
+        for p in packets {
+            let payload = p.payload();
+            self.send_datagram(payload)?;
+            total_len += payload.len();
+        }
+        self.stats.add_send_bytes(total_len);
+        self.stats.add_send_packets(packets.len());
+        Poll::Ready(Ok(packets.len()))
+    }
+}
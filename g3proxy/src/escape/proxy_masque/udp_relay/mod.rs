@@ -0,0 +1,248 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use super::ProxyMasqueEscaper;
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+use crate::module::udp_relay::{
+    ArcUdpRelayTaskRemoteStats, UdpRelayRemoteWrapperStats, UdpRelaySetupError,
+    UdpRelaySetupResult, UdpRelayTaskConf,
+};
+use crate::serve::ServerTaskNotes;
+
+mod recv;
+mod send;
+
+use recv::{ProxyMasqueUdpRelayRemoteRecv, RelaySession, RelaySessionState, RelaySessionUpdate};
+use send::{MasqueUdpSessionOpener, ProxyMasqueUdpRelayRemoteSend};
+
+impl ProxyMasqueEscaper {
+    /// Relay the UDP packets through connect-udp sessions on the same connection,
+    /// a new session will be opened for each target upstream.
+    /// Idle sessions will be closed when a new one is needed, and the relay will fail
+    /// if the count of sessions still reaches the limit
+    pub(super) async fn udp_setup_relay(
+        &self,
+        task_conf: &UdpRelayTaskConf<'_>,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpRelayTaskRemoteStats,
+    ) -> UdpRelaySetupResult {
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        let (conn, peer_proxy) = self
+            .get_masque_connection(task_conf.initial_peer, &mut tcp_notes, task_notes)
+            .await
+            .map_err(|e| UdpRelaySetupError::SetupSocketFailed(io::Error::other(e)))?;
+
+        let mut wrapper_stats = UdpRelayRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let (session_sender, session_receiver) = mpsc::unbounded_channel();
+        let recv = ProxyMasqueUdpRelayRemoteRecv::new(
+            conn.local_addr(),
+            conn.peer_addr(),
+            session_receiver,
+            wrapper_stats.clone(),
+        );
+        let opener = MasqueUdpSessionOpener {
+            conn,
+            peer_proxy,
+            headers: self.masque_request_headers(task_notes),
+            queue_size: self.config.udp_packet_queue_size,
+            timeout: self.config.peer_negotiation_timeout,
+            max_sessions: self.config.udp_relay_max_sessions,
+            idle_timeout: self.config.udp_relay_session_idle_timeout,
+        };
+        let send = ProxyMasqueUdpRelayRemoteSend::new(opener, session_sender, wrapper_stats);
+
+        Ok((Box::new(recv), Box::new(send), self.escape_logger.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+    use std::time::Duration;
+
+    use http::HeaderMap;
+
+    use g3_io_ext::{UdpRelayRemoteError, UdpRelayRemoteRecv, UdpRelayRemoteSend};
+    use g3_types::metrics::NodeName;
+    use g3_types::net::UpstreamAddr;
+
+    use super::super::ProxyMasqueEscaperStats;
+    use super::super::tests::{CLOSED_PORT, MasqueTestServer, REJECTED_PORT};
+
+    async fn new_relay(
+        server: &MasqueTestServer,
+    ) -> (ProxyMasqueUdpRelayRemoteRecv, ProxyMasqueUdpRelayRemoteSend) {
+        new_limited_relay(server, 16, Duration::from_secs(60)).await
+    }
+
+    async fn new_limited_relay(
+        server: &MasqueTestServer,
+        max_sessions: usize,
+        idle_timeout: Duration,
+    ) -> (ProxyMasqueUdpRelayRemoteRecv, ProxyMasqueUdpRelayRemoteSend) {
+        let conn = server.connect(Duration::from_secs(60)).await;
+        let stats = Arc::new(ProxyMasqueEscaperStats::new(&NodeName::default()));
+        let wrapper_stats = Arc::new(UdpRelayRemoteWrapperStats::new(stats.clone(), stats));
+
+        let (session_sender, session_receiver) = mpsc::unbounded_channel();
+        let recv = ProxyMasqueUdpRelayRemoteRecv::new(
+            conn.local_addr(),
+            conn.peer_addr(),
+            session_receiver,
+            wrapper_stats.clone(),
+        );
+        let opener = MasqueUdpSessionOpener {
+            conn,
+            peer_proxy: server.peer_proxy(),
+            headers: HeaderMap::new(),
+            queue_size: 16,
+            timeout: Duration::from_secs(5),
+            max_sessions,
+            idle_timeout,
+        };
+        let send = ProxyMasqueUdpRelayRemoteSend::new(opener, session_sender, wrapper_stats);
+        (recv, send)
+    }
+
+    fn target(port: u16) -> UpstreamAddr {
+        UpstreamAddr::from_host_str_and_port("127.0.0.1", port).unwrap()
+    }
+
+    async fn send_to(
+        send: &mut ProxyMasqueUdpRelayRemoteSend,
+        data: &[u8],
+        to: &UpstreamAddr,
+    ) -> Result<usize, UdpRelayRemoteError> {
+        poll_fn(|cx| send.poll_send_packet(cx, data, to)).await
+    }
+
+    async fn recv_from(
+        recv: &mut ProxyMasqueUdpRelayRemoteRecv,
+        timeout: Duration,
+    ) -> Option<(Vec<u8>, UpstreamAddr)> {
+        let mut buf = [0u8; 64];
+        let (off, len, from) =
+            tokio::time::timeout(timeout, poll_fn(|cx| recv.poll_recv_packet(cx, &mut buf)))
+                .await
+                .ok()?
+                .unwrap();
+        Some((buf[off..len].to_vec(), from))
+    }
+
+    #[tokio::test]
+    async fn multiple_targets() {
+        let server = MasqueTestServer::start();
+        let (mut recv, mut send) = new_relay(&server).await;
+
+        let t1 = target(53);
+        let t2 = target(123);
+        assert_eq!(send_to(&mut send, b"a1", &t1).await.unwrap(), 2);
+        assert_eq!(send_to(&mut send, b"b1", &t2).await.unwrap(), 2);
+        assert_eq!(send_to(&mut send, b"a2", &t1).await.unwrap(), 2);
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            received.push(recv_from(&mut recv, Duration::from_secs(5)).await.unwrap());
+        }
+        received.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            received,
+            vec![
+                (b"a1".to_vec(), t1.clone()),
+                (b"a2".to_vec(), t1.clone()),
+                (b"b1".to_vec(), t2.clone()),
+            ]
+        );
+        // one session for each target
+        assert_eq!(server.requests(), vec![t1, t2]);
+    }
+
+    #[tokio::test]
+    async fn session_limit() {
+        let server = MasqueTestServer::start();
+        let (mut recv, mut send) = new_limited_relay(&server, 2, Duration::from_millis(200)).await;
+
+        let t1 = target(53);
+        let t2 = target(123);
+        let t3 = target(161);
+        send_to(&mut send, b"a", &t1).await.unwrap();
+        send_to(&mut send, b"b", &t2).await.unwrap();
+        match send_to(&mut send, b"c", &t3).await {
+            Err(UdpRelayRemoteError::RemoteSessionError(..)) => {}
+            r => panic!("unexpected result: {r:?}"),
+        }
+        for _ in 0..2 {
+            recv_from(&mut recv, Duration::from_secs(5)).await.unwrap();
+        }
+        assert_eq!(recv.session_count(), 2);
+
+        // the idle sessions will be closed to open the new one
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        send_to(&mut send, b"c", &t3).await.unwrap();
+        assert_eq!(
+            recv_from(&mut recv, Duration::from_secs(5)).await,
+            Some((b"c".to_vec(), t3.clone()))
+        );
+        assert_eq!(recv.session_count(), 1);
+
+        send_to(&mut send, b"a", &t1).await.unwrap();
+        assert_eq!(server.requests(), vec![t1.clone(), t2, t3, t1]);
+    }
+
+    #[tokio::test]
+    async fn rejected_target() {
+        let server = MasqueTestServer::start();
+        let (_recv, mut send) = new_relay(&server).await;
+
+        match send_to(&mut send, b"a", &target(REJECTED_PORT)).await {
+            Err(UdpRelayRemoteError::RemoteSessionError(..)) => {}
+            r => panic!("unexpected result: {r:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn reopen_closed_session() {
+        let server = MasqueTestServer::start();
+        let (mut recv, mut send) = new_relay(&server).await;
+
+        let t = target(CLOSED_PORT);
+        send_to(&mut send, b"a", &t).await.unwrap();
+        // the closed session will be removed by the recv half
+        while recv_from(&mut recv, Duration::from_millis(500))
+            .await
+            .is_some()
+        {}
+        send_to(&mut send, b"a", &t).await.unwrap();
+        assert_eq!(server.requests(), vec![t.clone(), t]);
+    }
+
+    #[tokio::test]
+    async fn send_half_closed() {
+        let server = MasqueTestServer::start();
+        let (mut recv, send) = new_relay(&server).await;
+
+        drop(send);
+        let mut buf = [0u8; 64];
+        let r = tokio::time::timeout(
+            Duration::from_secs(1),
+            poll_fn(|cx| recv.poll_recv_packet(cx, &mut buf)),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            r,
+            Err(UdpRelayRemoteError::RemoteSessionClosed(..))
+        ));
+    }
+}
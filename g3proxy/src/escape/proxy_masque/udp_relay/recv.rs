@@ -0,0 +1,245 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use log::debug;
+use tokio::sync::mpsc;
+
+use g3_io_ext::{LimitedRecvStats, UdpRelayRemoteError, UdpRelayRemoteRecv};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::{UdpRelayPacket, UdpRelayPacketMeta};
+use g3_types::net::UpstreamAddr;
+
+use super::super::udp_session::MasqueUdpSession;
+use crate::module::udp_relay::UdpRelayRemoteWrapperStats;
+
+/// The state of a connect-udp session shared between the send and the recv half
+pub(super) struct RelaySessionState {
+    closed: AtomicBool,
+    created: Instant,
+    /// the milliseconds since created
+    last_received: AtomicU64,
+}
+
+impl RelaySessionState {
+    pub(super) fn new() -> Self {
+        RelaySessionState {
+            closed: AtomicBool::new(false),
+            created: Instant::now(),
+            last_received: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Acquire)
+    }
+
+    fn set_received(&self) {
+        let elapsed = self.created.elapsed().as_millis() as u64;
+        self.last_received.store(elapsed, Ordering::Relaxed);
+    }
+
+    /// Get the time of the last received packet, or the created time if no one received
+    pub(super) fn last_received(&self) -> Instant {
+        self.created + Duration::from_millis(self.last_received.load(Ordering::Relaxed))
+    }
+}
+
+/// The connect-udp session to a single target, the send half will be notified when it's closed
+pub(super) struct RelaySession {
+    upstream: UpstreamAddr,
+    session: MasqueUdpSession,
+    state: Arc<RelaySessionState>,
+}
+
+impl RelaySession {
+    pub(super) fn new(
+        upstream: UpstreamAddr,
+        session: MasqueUdpSession,
+        state: Arc<RelaySessionState>,
+    ) -> Self {
+        RelaySession {
+            upstream,
+            session,
+            state,
+        }
+    }
+}
+
+impl Drop for RelaySession {
+    fn drop(&mut self) {
+        self.state.closed.store(true, Ordering::Release);
+    }
+}
+
+/// The session changes sent from the send half
+pub(super) enum RelaySessionUpdate {
+    Opened(RelaySession),
+    /// the idle session to the upstream should be closed
+    Evicted(UpstreamAddr),
+}
+
+pub(super) struct ProxyMasqueUdpRelayRemoteRecv {
+    local_addr: SocketAddr,
+    peer_addr: SocketAddr,
+    session_updates: mpsc::UnboundedReceiver<RelaySessionUpdate>,
+    no_new_session: bool,
+    sessions: Vec<RelaySession>,
+    next_index: usize,
+    stats: Arc<UdpRelayRemoteWrapperStats>,
+}
+
+impl ProxyMasqueUdpRelayRemoteRecv {
+    pub(super) fn new(
+        local_addr: SocketAddr,
+        peer_addr: SocketAddr,
+        session_updates: mpsc::UnboundedReceiver<RelaySessionUpdate>,
+        stats: Arc<UdpRelayRemoteWrapperStats>,
+    ) -> Self {
+        ProxyMasqueUdpRelayRemoteRecv {
+            local_addr,
+            peer_addr,
+            session_updates,
+            no_new_session: false,
+            sessions: Vec::new(),
+            next_index: 0,
+            stats,
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn session_count(&self) -> usize {
+        self.sessions.len()
+    }
+
+    /// Poll all sessions in turn for the next UDP payload,
+    /// closed sessions will be removed, so they can be reopened by the send half
+    fn poll_next_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(Bytes, UpstreamAddr), UdpRelayRemoteError>> {
+        while !self.no_new_session {
+            match self.session_updates.poll_recv(cx) {
+                Poll::Ready(Some(RelaySessionUpdate::Opened(s))) => self.sessions.push(s),
+                Poll::Ready(Some(RelaySessionUpdate::Evicted(upstream))) => {
+                    // drop the session to close the request stream
+                    self.sessions.retain(|s| s.upstream != upstream);
+                }
+                Poll::Ready(None) => self.no_new_session = true,
+                Poll::Pending => break,
+            }
+        }
+
+        let mut i = 0;
+        while i < self.sessions.len() {
+            let index = (self.next_index + i) % self.sessions.len();
+            let s = &mut self.sessions[index];
+            match s.session.poll_recv_payload(cx) {
+                Poll::Pending => i += 1,
+                Poll::Ready(Ok(Some(payload))) => {
+                    s.state.set_received();
+                    self.next_index = index + 1;
+                    return Poll::Ready(Ok((payload, s.upstream.clone())));
+                }
+                Poll::Ready(r) => {
+                    if let Err(e) = r {
+                        debug!("connect-udp session to {} closed: {e}", s.upstream);
+                    }
+                    self.sessions.swap_remove(index);
+                    // poll all the left sessions again to make sure all wakers are registered
+                    i = 0;
+                }
+            }
+        }
+
+        if self.no_new_session && self.sessions.is_empty() {
+            return Poll::Ready(Err(UdpRelayRemoteError::RemoteSessionClosed(
+                self.local_addr,
+                self.peer_addr,
+            )));
+        }
+        Poll::Pending
+    }
+}
+
+impl UdpRelayRemoteRecv for ProxyMasqueUdpRelayRemoteRecv {
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize, UpstreamAddr), UdpRelayRemoteError>> {
+        let (payload, upstream) = ready!(self.poll_next_payload(cx))?;
+        let len = payload.len().min(buf.len());
+        buf[..len].copy_from_slice(&payload[..len]);
+
+        self.stats.add_recv_bytes(len);
+        self.stats.add_recv_packet();
+        Poll::Ready(Ok((0, len, upstream)))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpRelayPacket],
+    ) -> Poll<Result<usize, UdpRelayRemoteError>> {
+        let mut count = 0;
+        let mut total_len = 0;
+        for p in packets.iter_mut() {
+            let (payload, upstream) = match self.poll_next_payload(cx) {
+                Poll::Pending => break,
+                Poll::Ready(Ok(r)) => r,
+                Poll::Ready(Err(e)) => {
+                    if count > 0 {
+                        break;
+                    }
+                    return Poll::Ready(Err(e));
+                }
+            };
+
+            let buf = p.buf_mut();
+            let len = payload.len().min(buf.len());
+            buf[..len].copy_from_slice(&payload[..len]);
+            let meta = UdpRelayPacketMeta::new(&std::io::IoSliceMut::new(buf), 0, len, upstream);
+            meta.set_packet(p);
+
+            count += 1;
+            total_len += len;
+        }
+
+        if count == 0 {
+            return Poll::Pending;
+        }
+        self.stats.add_recv_bytes(total_len);
+        self.stats.add_recv_packets(count);
+        Poll::Ready(Ok(count))
+    }
+}
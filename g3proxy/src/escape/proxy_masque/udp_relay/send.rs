@@ -0,0 +1,199 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use http::HeaderMap;
+use quinn::SendDatagramError;
+use rustc_hash::FxHashMap;
+use tokio::sync::mpsc;
+use tokio_util::sync::ReusableBoxFuture;
+
+use g3_io_ext::{LimitedSendStats, UdpRelayRemoteError, UdpRelayRemoteSend};
+use g3_types::net::UpstreamAddr;
+
+use super::super::datagram;
+use super::super::pool::MasqueConnection;
+use super::super::udp_session::{MasqueUdpSession, connect_udp_request};
+use super::{RelaySession, RelaySessionState, RelaySessionUpdate};
+use crate::module::tcp_connect::TcpConnectError;
+use crate::module::udp_relay::UdpRelayRemoteWrapperStats;
+
+type OpenResult = Result<MasqueUdpSession, TcpConnectError>;
+
+/// Open new connect-udp sessions for the relay on the same connection
+pub(super) struct MasqueUdpSessionOpener {
+    pub(super) conn: Arc<MasqueConnection>,
+    pub(super) peer_proxy: UpstreamAddr,
+    pub(super) headers: HeaderMap,
+    pub(super) queue_size: usize,
+    pub(super) timeout: Duration,
+    pub(super) max_sessions: usize,
+    pub(super) idle_timeout: Duration,
+}
+
+impl MasqueUdpSessionOpener {
+    async fn open(self: Arc<Self>, upstream: UpstreamAddr) -> OpenResult {
+        let req = connect_udp_request(&self.peer_proxy, &upstream, self.headers.clone())?;
+        tokio::time::timeout(
+            self.timeout,
+            MasqueUdpSession::open(&self.conn, req, self.queue_size),
+        )
+        .await
+        .map_err(|_| TcpConnectError::NegotiationPeerTimeout)?
+    }
+}
+
+struct SessionSender {
+    /// the quarter stream ID and the context ID 0
+    header: Bytes,
+    state: Arc<RelaySessionState>,
+    last_active: Instant,
+}
+
+pub(super) struct ProxyMasqueUdpRelayRemoteSend {
+    opener: Arc<MasqueUdpSessionOpener>,
+    local_addr: SocketAddr,
+    peer_addr: SocketAddr,
+    sessions: FxHashMap<UpstreamAddr, SessionSender>,
+    opening: Option<(UpstreamAddr, ReusableBoxFuture<'static, OpenResult>)>,
+    session_update_sender: mpsc::UnboundedSender<RelaySessionUpdate>,
+    stats: Arc<UdpRelayRemoteWrapperStats>,
+}
+
+impl ProxyMasqueUdpRelayRemoteSend {
+    pub(super) fn new(
+        opener: MasqueUdpSessionOpener,
+        session_update_sender: mpsc::UnboundedSender<RelaySessionUpdate>,
+        stats: Arc<UdpRelayRemoteWrapperStats>,
+    ) -> Self {
+        let local_addr = opener.conn.local_addr();
+        let peer_addr = opener.conn.peer_addr();
+        ProxyMasqueUdpRelayRemoteSend {
+            opener: Arc::new(opener),
+            local_addr,
+            peer_addr,
+            sessions: FxHashMap::default(),
+            opening: None,
+            session_update_sender,
+            stats,
+        }
+    }
+
+    /// Get the datagram header for the target upstream,
+    /// a new session will be opened if there is no usable one
+    fn poll_session_header(
+        &mut self,
+        cx: &mut Context<'_>,
+        to: &UpstreamAddr,
+    ) -> Poll<Result<Bytes, UdpRelayRemoteError>> {
+        loop {
+            if let Some(s) = self.sessions.get_mut(to) {
+                if !s.state.is_closed() {
+                    s.last_active = Instant::now();
+                    return Poll::Ready(Ok(s.header.clone()));
+                }
+                self.sessions.remove(to);
+            }
+
+            if self.opening.is_none() {
+                self.evict_idle_sessions();
+                if self.sessions.len() >= self.opener.max_sessions {
+                    return Poll::Ready(Err(UdpRelayRemoteError::RemoteSessionError(
+                        self.local_addr,
+                        self.peer_addr,
+                        io::Error::other("too many connect-udp sessions"),
+                    )));
+                }
+            }
+
+            let (upstream, open_fut) = self.opening.get_or_insert_with(|| {
+                let fut = self.opener.clone().open(to.clone());
+                (to.clone(), ReusableBoxFuture::new(fut))
+            });
+            let r = ready!(open_fut.poll(cx));
+            let upstream = upstream.clone();
+            self.opening = None;
+
+            let session = r.map_err(|e| {
+                UdpRelayRemoteError::RemoteSessionError(
+                    self.local_addr,
+                    self.peer_addr,
+                    io::Error::other(e),
+                )
+            })?;
+            let sender = SessionSender {
+                header: datagram::udp_payload_header(session.quarter_stream_id()),
+                state: Arc::new(RelaySessionState::new()),
+                last_active: Instant::now(),
+            };
+            let relay_session = RelaySession::new(upstream.clone(), session, sender.state.clone());
+            if self
+                .session_update_sender
+                .send(RelaySessionUpdate::Opened(relay_session))
+                .is_err()
+            {
+                return Poll::Ready(Err(UdpRelayRemoteError::RemoteSessionClosed(
+                    self.local_addr,
+                    self.peer_addr,
+                )));
+            }
+            self.sessions.insert(upstream, sender);
+        }
+    }
+
+    /// Remove the closed sessions, and close the sessions which have no packets sent
+    /// or received within the idle timeout
+    fn evict_idle_sessions(&mut self) {
+        let now = Instant::now();
+        let idle_timeout = self.opener.idle_timeout;
+        let update_sender = &self.session_update_sender;
+        self.sessions.retain(|upstream, s| {
+            if s.state.is_closed() {
+                return false;
+            }
+            let last_active = s.last_active.max(s.state.last_received());
+            if now.saturating_duration_since(last_active) < idle_timeout {
+                return true;
+            }
+            let _ = update_sender.send(RelaySessionUpdate::Evicted(upstream.clone()));
+            false
+        });
+    }
+}
+
+impl UdpRelayRemoteSend for ProxyMasqueUdpRelayRemoteSend {
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        to: &UpstreamAddr,
+    ) -> Poll<Result<usize, UdpRelayRemoteError>> {
+        let header = ready!(self.poll_session_header(cx, to))?;
+        match self
+            .opener
+            .conn
+            .send_datagram(datagram::new_datagram(&header, buf))
+        {
+            // drop the packet like what will happen on a real udp socket
+            Ok(_) | Err(SendDatagramError::TooLarge) => {}
+            Err(e) => {
+                return Poll::Ready(Err(UdpRelayRemoteError::SendFailed(
+                    self.local_addr,
+                    self.peer_addr,
+                    io::Error::other(e),
+                )));
+            }
+        }
+        self.stats.add_send_bytes(buf.len());
+        self.stats.add_send_packet();
+        Poll::Ready(Ok(buf.len()))
+    }
+}
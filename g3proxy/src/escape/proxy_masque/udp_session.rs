@@ -0,0 +1,225 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use h3::ext::Protocol;
+use http::{HeaderMap, HeaderValue, Method, Request, Uri, Version};
+
+use g3_http::uri::HttpMasque;
+use g3_types::net::UpstreamAddr;
+
+use super::datagram;
+use super::masque_connect::{recv_response, send_request};
+use super::pool::{MasqueConnection, MasqueStreamGuard, MasqueUdpSessionGuard};
+use super::stream::H3BidiStream;
+use crate::module::tcp_connect::TcpConnectError;
+
+/// Build the CONNECT-UDP request to the peer proxy for the target upstream
+pub(super) fn connect_udp_request(
+    peer_proxy: &UpstreamAddr,
+    upstream: &UpstreamAddr,
+    headers: HeaderMap,
+) -> Result<Request<()>, TcpConnectError> {
+    let uri = format!("https://{peer_proxy}{}", HttpMasque::udp_path(upstream));
+    let uri = Uri::try_from(uri).map_err(|_| {
+        TcpConnectError::InternalServerError("invalid upstream address for h3 CONNECT-UDP")
+    })?;
+    let mut req = Request::builder()
+        .method(Method::CONNECT)
+        .version(Version::HTTP_3)
+        .uri(uri)
+        .extension(Protocol::CONNECT_UDP)
+        .body(())
+        .map_err(|_| {
+            TcpConnectError::InternalServerError("failed to build h3 CONNECT-UDP request")
+        })?;
+    *req.headers_mut() = headers;
+    req.headers_mut()
+        .insert("capsule-protocol", HeaderValue::from_static("?1"));
+    Ok(req)
+}
+
+/// A connect-udp session on a request stream
+pub(super) struct MasqueUdpSession {
+    stream: H3BidiStream,
+    stream_closed: bool,
+    session: MasqueUdpSessionGuard,
+    guard: MasqueStreamGuard,
+}
+
+impl MasqueUdpSession {
+    pub(super) async fn open(
+        conn: &Arc<MasqueConnection>,
+        req: Request<()>,
+        queue_size: usize,
+    ) -> Result<Self, TcpConnectError> {
+        let (mut stream, guard) = send_request(conn, req).await?;
+        // register the session before the response, as datagrams may arrive earlier
+        let quarter_stream_id = stream.id().into_inner() >> 2;
+        let session = guard.register_udp_session(quarter_stream_id, queue_size);
+        recv_response(&mut stream).await?;
+
+        Ok(MasqueUdpSession {
+            stream,
+            stream_closed: false,
+            session,
+            guard,
+        })
+    }
+
+    #[inline]
+    pub(super) fn connection(&self) -> &Arc<MasqueConnection> {
+        self.guard.connection()
+    }
+
+    #[inline]
+    pub(super) fn quarter_stream_id(&self) -> u64 {
+        self.stream.id().into_inner() >> 2
+    }
+
+    /// Drain the capsules sent on the request stream, and detect the close of the session
+    fn check_stream(&mut self, cx: &mut Context<'_>) -> io::Result<bool> {
+        loop {
+            match self.stream.poll_recv_data(cx) {
+                Poll::Pending => return Ok(true),
+                Poll::Ready(Ok(Some(_))) => {} // no capsule types are supported yet
+                Poll::Ready(Ok(None)) => return Ok(false),
+                Poll::Ready(Err(e)) => return Err(io::Error::other(e)),
+            }
+        }
+    }
+
+    /// Get the next UDP payload, datagrams with unknown context IDs will be skipped.
+    /// `None` will be returned if the session has been closed
+    pub(super) fn poll_recv_payload(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<Option<Bytes>>> {
+        if !self.stream_closed {
+            match self.check_stream(cx) {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.stream_closed = true;
+                    return Poll::Ready(Ok(None));
+                }
+                Err(e) => {
+                    self.stream_closed = true;
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+
+        loop {
+            let Some(data) = ready!(self.session.receiver.poll_recv(cx)) else {
+                return Poll::Ready(Ok(None));
+            };
+            if let Some(payload) = datagram::udp_payload(data) {
+                return Poll::Ready(Ok(Some(payload)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+    use std::time::Duration;
+
+    use super::super::tests::{CLOSED_PORT, MasqueTestServer, REJECTED_PORT};
+
+    async fn open_session(
+        server: &MasqueTestServer,
+        conn: &Arc<MasqueConnection>,
+        port: u16,
+    ) -> Result<MasqueUdpSession, TcpConnectError> {
+        let upstream = UpstreamAddr::from_host_str_and_port("127.0.0.1", port).unwrap();
+        let req = connect_udp_request(&server.peer_proxy(), &upstream, HeaderMap::new()).unwrap();
+        MasqueUdpSession::open(conn, req, 16).await
+    }
+
+    #[test]
+    fn build_request() {
+        let peer_proxy = UpstreamAddr::from_host_str_and_port("proxy.example.net", 443).unwrap();
+        let upstream = UpstreamAddr::from_host_str_and_port("2001:db8::1", 53).unwrap();
+        let req = connect_udp_request(&peer_proxy, &upstream, HeaderMap::new()).unwrap();
+        assert_eq!(req.method(), Method::CONNECT);
+        assert_eq!(
+            req.uri().to_string(),
+            "https://proxy.example.net:443/.well-known/masque/udp/2001%3Adb8%3A%3A1/53/"
+        );
+        assert_eq!(
+            req.extensions().get::<Protocol>(),
+            Some(&Protocol::CONNECT_UDP)
+        );
+        assert_eq!(req.headers().get("capsule-protocol").unwrap(), "?1");
+    }
+
+    #[tokio::test]
+    async fn echo() {
+        let server = MasqueTestServer::start();
+        let conn = server.connect(Duration::from_secs(60)).await;
+
+        let mut session = open_session(&server, &conn, 53).await.unwrap();
+        let header = datagram::udp_payload_header(session.quarter_stream_id());
+        conn.send_datagram(datagram::new_datagram(&header, b"ping"))
+            .unwrap();
+        // datagrams with unknown context IDs should be skipped
+        let mut header = header.to_vec();
+        *header.last_mut().unwrap() = 2;
+        conn.send_datagram(datagram::new_datagram(&header, b"skip"))
+            .unwrap();
+        conn.send_datagram(datagram::new_datagram(
+            &datagram::udp_payload_header(session.quarter_stream_id()),
+            b"pong",
+        ))
+        .unwrap();
+
+        for expected in [b"ping", b"pong"] {
+            let payload = tokio::time::timeout(
+                Duration::from_secs(5),
+                poll_fn(|cx| session.poll_recv_payload(cx)),
+            )
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+            assert_eq!(payload.as_ref(), expected);
+        }
+        assert_eq!(server.requests()[0].port(), 53);
+    }
+
+    #[tokio::test]
+    async fn closed() {
+        let server = MasqueTestServer::start();
+        let conn = server.connect(Duration::from_secs(60)).await;
+
+        let mut session = open_session(&server, &conn, CLOSED_PORT).await.unwrap();
+        let r = tokio::time::timeout(
+            Duration::from_secs(5),
+            poll_fn(|cx| session.poll_recv_payload(cx)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(r.is_none());
+    }
+
+    #[tokio::test]
+    async fn rejected() {
+        let server = MasqueTestServer::start();
+        let conn = server.connect(Duration::from_secs(60)).await;
+
+        match open_session(&server, &conn, REJECTED_PORT).await {
+            Err(TcpConnectError::NegotiationRejected(_)) => {}
+            Err(e) => panic!("unexpected error: {e}"),
+            Ok(_) => panic!("the request should be rejected"),
+        }
+    }
+}
@@ -62,6 +62,9 @@ impl CacheHandle {
                         SelectivePickPolicy::Random => nodes.pick_random(),
                         SelectivePickPolicy::Serial => nodes.pick_serial(),
                         SelectivePickPolicy::RoundRobin => nodes.pick_round_robin(),
+                        SelectivePickPolicy::WeightedRoundRobin => {
+                            nodes.pick_weighted_round_robin()
+                        }
                         SelectivePickPolicy::Ketama => {
                             let select_key = CacheQueryConsistentKey {
                                 client_ip: task_notes.client_ip(),
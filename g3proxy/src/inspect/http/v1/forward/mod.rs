@@ -506,6 +506,7 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut idle_count = 0;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             tokio::select! {
                 biased;
@@ -538,6 +539,9 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                     self.http_notes.mark_req_send_all();
                     break;
                 }
+                _ = cancel_token.cancelled() => {
+                    return Err(self.ctx.task_cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if clt_to_ups.is_idle() {
                         idle_count += n;
@@ -553,8 +557,8 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                         clt_to_ups.reset_active();
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
-                        return Err(ServerTaskError::CanceledAsUserBlocked);
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
+                        return Err(ServerTaskError::from(reason));
                     }
 
                     if self.ctx.server_force_quit() {
@@ -818,6 +822,7 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut idle_count = 0;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             tokio::select! {
                 biased;
@@ -836,6 +841,10 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                         Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
                     };
                 }
+                _ = cancel_token.cancelled() => {
+                    let _ = ups_to_clt.write_flush().await;
+                    return Err(self.ctx.task_cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if ups_to_clt.is_idle() {
                         idle_count += n;
@@ -851,9 +860,9 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                         ups_to_clt.reset_active();
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
                         let _ = ups_to_clt.write_flush().await;
-                        return Err(ServerTaskError::CanceledAsUserBlocked);
+                        return Err(ServerTaskError::from(reason));
                     }
 
                     if self.ctx.server_force_quit() {
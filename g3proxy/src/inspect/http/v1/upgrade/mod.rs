@@ -503,6 +503,7 @@ where
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut idle_count = 0;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             tokio::select! {
                 biased;
@@ -520,6 +521,10 @@ where
                         Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
                     };
                 }
+                _ = cancel_token.cancelled() => {
+                    let _ = ups_to_clt.write_flush().await;
+                    return Err(self.ctx.task_cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if ups_to_clt.is_idle() {
                         idle_count += n;
@@ -535,9 +540,9 @@ where
                         ups_to_clt.reset_active();
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
                         let _ = ups_to_clt.write_flush().await;
-                        return Err(ServerTaskError::CanceledAsUserBlocked);
+                        return Err(ServerTaskError::from(reason));
                     }
 
                     if self.ctx.server_force_quit() {
@@ -426,8 +426,8 @@ impl<'a, SC: ServerConfig> ExchangeHead<'a, SC> {
                             rsp_body_transfer.reset_active();
                         }
 
-                        if self.ctx.belongs_to_blocked_user() {
-                            return Err(H2StreamTransferError::CanceledAsUserBlocked);
+                        if let Some(reason) = self.ctx.user_blocked_reason() {
+                            return Err(H2StreamTransferError::from(reason));
                         }
 
                         if self.ctx.server_force_quit() {
@@ -35,8 +35,14 @@ pub(crate) enum H2InterceptionError {
     ClientConnectionClosed(h2::Error),
     #[error("canceled as user blocked")]
     CanceledAsUserBlocked,
+    #[error("canceled as user removed")]
+    CanceledAsUserRemoved,
+    #[error("canceled as user expired")]
+    CanceledAsUserExpired,
     #[error("canceled as server quit")]
     CanceledAsServerQuit,
+    #[error("canceled as task killed")]
+    CanceledAsTaskKilled,
     #[error("idle after {0:?} x {1}")]
     Idle(Duration, usize),
     #[error("unexpected error: {0:}")]
@@ -91,6 +97,10 @@ pub(crate) enum H2StreamTransferError {
     ResponseBodyTransferFailed(H2StreamBodyTransferError),
     #[error("canceled as user blocked")]
     CanceledAsUserBlocked,
+    #[error("canceled as user removed")]
+    CanceledAsUserRemoved,
+    #[error("canceled as user expired")]
+    CanceledAsUserExpired,
     #[error("canceled as server quit")]
     CanceledAsServerQuit,
     #[error("read from http client idle")]
@@ -126,6 +136,28 @@ impl H2StreamTransferError {
     }
 }
 
+impl From<IdleForceQuitReason> for H2InterceptionError {
+    fn from(reason: IdleForceQuitReason) -> Self {
+        match reason {
+            IdleForceQuitReason::UserBlocked => H2InterceptionError::CanceledAsUserBlocked,
+            IdleForceQuitReason::UserRemoved => H2InterceptionError::CanceledAsUserRemoved,
+            IdleForceQuitReason::UserExpired => H2InterceptionError::CanceledAsUserExpired,
+            IdleForceQuitReason::ServerQuit => H2InterceptionError::CanceledAsServerQuit,
+        }
+    }
+}
+
+impl From<IdleForceQuitReason> for H2StreamTransferError {
+    fn from(reason: IdleForceQuitReason) -> Self {
+        match reason {
+            IdleForceQuitReason::UserBlocked => H2StreamTransferError::CanceledAsUserBlocked,
+            IdleForceQuitReason::UserRemoved => H2StreamTransferError::CanceledAsUserRemoved,
+            IdleForceQuitReason::UserExpired => H2StreamTransferError::CanceledAsUserExpired,
+            IdleForceQuitReason::ServerQuit => H2StreamTransferError::CanceledAsServerQuit,
+        }
+    }
+}
+
 impl From<H2ReqmodAdaptationError> for H2StreamTransferError {
     fn from(e: H2ReqmodAdaptationError) -> Self {
         match e {
@@ -161,10 +193,7 @@ impl From<H2ReqmodAdaptationError> for H2StreamTransferError {
             H2ReqmodAdaptationError::HttpUpstreamWriteIdle => {
                 H2StreamTransferError::HttpUpstreamWriteIdle
             }
-            H2ReqmodAdaptationError::IdleForceQuit(reason) => match reason {
-                IdleForceQuitReason::UserBlocked => H2StreamTransferError::CanceledAsUserBlocked,
-                IdleForceQuitReason::ServerQuit => H2StreamTransferError::CanceledAsServerQuit,
-            },
+            H2ReqmodAdaptationError::IdleForceQuit(reason) => H2StreamTransferError::from(reason),
             H2ReqmodAdaptationError::HttpUpstreamRecvResponseFailed(e) => {
                 H2StreamTransferError::ResponseHeadRecvFailed(e)
             }
@@ -211,10 +240,7 @@ impl From<H2RespmodAdaptationError> for H2StreamTransferError {
             H2RespmodAdaptationError::HttpClientWriteIdle => {
                 H2StreamTransferError::HttpClientWriteIdle
             }
-            H2RespmodAdaptationError::IdleForceQuit(reason) => match reason {
-                IdleForceQuitReason::UserBlocked => H2StreamTransferError::CanceledAsUserBlocked,
-                IdleForceQuitReason::ServerQuit => H2StreamTransferError::CanceledAsServerQuit,
-            },
+            H2RespmodAdaptationError::IdleForceQuit(reason) => H2StreamTransferError::from(reason),
             e => H2StreamTransferError::InternalAdapterError(anyhow!("respmod: {e}")),
         }
     }
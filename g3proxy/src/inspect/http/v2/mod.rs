@@ -13,6 +13,7 @@ use h2::{Reason, server::Connection};
 use slog::slog_info;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::{Protocol, ProtocolInspectAction};
@@ -153,6 +154,10 @@ impl<SC: ServerConfig> StreamTransitTask for H2InterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.ctx.task_cancel_token()
+    }
 }
 
 impl<SC> H2InterceptObject<SC>
@@ -403,6 +408,7 @@ where
         let mut idle_count = 0;
         let mut is_active = false;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             tokio::select! {
                 biased;
@@ -465,6 +471,16 @@ where
                         }
                     }
                 }
+                _ = cancel_token.cancelled() => {
+                    let _ = ping_quit_sender.send(());
+                    server_graceful_shutdown(h2c_connection).await;
+
+                    return Err(self
+                        .ctx
+                        .user_blocked_reason()
+                        .map(H2InterceptionError::from)
+                        .unwrap_or(H2InterceptionError::CanceledAsTaskKilled));
+                }
                 n = idle_interval.tick() => {
                     if !is_active && self.stats.get_alive_task() <= 0 {
                         idle_count += n;
@@ -480,11 +496,11 @@ where
                         is_active = false;
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
                         let _ = ping_quit_sender.send(());
                         server_abrupt_shutdown(h2c_connection, Reason::ENHANCE_YOUR_CALM).await;
 
-                        return Err(H2InterceptionError::CanceledAsUserBlocked);
+                        return Err(H2InterceptionError::from(reason));
                     }
 
                     if self.ctx.server_force_quit() {
@@ -54,6 +54,7 @@ where
 
         let mut active = false;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             relay_buf.cmd_recv_buf.consume_line();
             relay_buf.rsp_recv_buf.consume_line();
@@ -117,6 +118,10 @@ where
                             }
                         }
                     }
+                }
+                _ = cancel_token.cancelled() => {
+                    let _ = ByeResponse::reply_server_quit(clt_w).await;
+                    return Ok(CloseReason::Local(self.ctx.task_cancel_error()));
                 }
                  n = idle_interval.tick() => {
                     if !active {
@@ -129,9 +134,9 @@ where
                         idle_count = 0;
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
                         let _ = ByeResponse::reply_blocked(clt_w).await;
-                        return Ok(CloseReason::Local(ServerTaskError::CanceledAsUserBlocked));
+                        return Ok(CloseReason::Local(ServerTaskError::from(reason)));
                     }
 
                     if self.ctx.server_force_quit() {
@@ -398,6 +403,7 @@ where
 
         let mut active = true;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             relay_buf.cmd_recv_buf.consume_line();
             relay_buf.rsp_recv_buf.consume_line();
@@ -438,6 +444,11 @@ where
                         }
                     }
                 }
+                _ = cancel_token.cancelled() => {
+                    let _ = ByeResponse::reply_server_quit(clt_w).await;
+                    let _ = ups_w.write_all_flush(DONE_MSG).await;
+                    return Ok(Some(CloseReason::Local(self.ctx.task_cancel_error())));
+                }
                 n = idle_interval.tick() => {
                     if !active {
                         idle_count += n;
@@ -450,10 +461,10 @@ where
                         idle_count = 0;
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
                         let _ = ByeResponse::reply_blocked(clt_w).await;
                         let _ = ups_w.write_all_flush(DONE_MSG).await;
-                        return Ok(Some(CloseReason::Local(ServerTaskError::CanceledAsUserBlocked)));
+                        return Ok(Some(CloseReason::Local(ServerTaskError::from(reason))));
                     }
 
                     if self.ctx.server_force_quit() {
@@ -125,6 +125,7 @@ where
 
             let mut clt_to_ups = StreamCopy::new(&mut clt_r, ups_w, &Default::default());
 
+            let cancel_token = self.ctx.task_cancel_token();
             loop {
                 tokio::select! {
                     biased;
@@ -142,6 +143,10 @@ where
                             Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::UpstreamWriteFailed(e)),
                         };
                     }
+                    _ = cancel_token.cancelled() => {
+                        let _ = clt_to_ups.write_flush().await;
+                        return Err(self.ctx.task_cancel_error())
+                    }
                     n = idle_interval.tick() => {
                         if clt_to_ups.is_idle() {
                             idle_count += n;
@@ -157,9 +162,9 @@ where
                             clt_to_ups.reset_active();
                         }
 
-                        if self.ctx.belongs_to_blocked_user() {
+                        if let Some(reason) = self.ctx.user_blocked_reason() {
                             let _ = clt_to_ups.write_flush().await;
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                            return Err(ServerTaskError::from(reason));
                         }
 
                         if self.ctx.server_force_quit() {
@@ -281,6 +286,7 @@ where
 
             let mut ups_to_clt = StreamCopy::new(&mut ups_r, clt_w, &Default::default());
 
+            let cancel_token = self.ctx.task_cancel_token();
             loop {
                 tokio::select! {
                     biased;
@@ -298,6 +304,10 @@ where
                             Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
                         };
                     }
+                    _ = cancel_token.cancelled() => {
+                        let _ = ups_to_clt.write_flush().await;
+                        return Err(self.ctx.task_cancel_error())
+                    }
                     n = idle_interval.tick() => {
                         if ups_to_clt.is_idle() {
                             idle_count += n;
@@ -313,9 +323,9 @@ where
                             ups_to_clt.reset_active();
                         }
 
-                        if self.ctx.belongs_to_blocked_user() {
+                        if let Some(reason) = self.ctx.user_blocked_reason() {
                             let _ = ups_to_clt.write_flush().await;
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                            return Err(ServerTaskError::from(reason));
                         }
 
                         if self.ctx.server_force_quit() {
@@ -8,6 +8,7 @@ use std::time::Duration;
 use anyhow::anyhow;
 use slog::slog_info;
 use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::ProtocolInspectAction;
@@ -173,6 +174,10 @@ impl<SC: ServerConfig> StreamTransitTask for ImapInterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.ctx.task_cancel_token()
+    }
 }
 
 impl<SC> ImapInterceptObject<SC>
@@ -9,21 +9,24 @@ use std::time::Duration;
 
 use slog::Logger;
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::{
     H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, MaybeProtocol,
-    ProtocolInspectAction, ProtocolInspector, SmtpInterceptionConfig,
+    ProtocolInspectAction, ProtocolInspector, SmtpInterceptionConfig, WebSocketInterceptionConfig,
 };
-use g3_io_ext::IdleWheel;
+use g3_io_ext::{IdleForceQuitReason, IdleWheel};
 use g3_types::net::{Host, OpensslClientConfig};
 
 use crate::audit::AuditHandle;
 use crate::auth::{User, UserForbiddenStats, UserSite};
 use crate::config::server::ServerConfig;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
-use crate::serve::{ArcServerStats, ServerIdleChecker, ServerTaskNotes};
+use crate::serve::{
+    ArcServerStats, RegisteredTask, ServerIdleChecker, ServerTaskError, ServerTaskNotes,
+};
 
 mod error;
 pub(crate) use error::InterceptionError;
@@ -67,6 +70,7 @@ pub(crate) struct StreamInspectTaskNotes {
     pub(crate) server_addr: SocketAddr,
     worker_id: Option<usize>,
     user_ctx: Option<StreamInspectUserContext>,
+    registered_task: Option<Arc<RegisteredTask>>,
 }
 
 impl StreamInspectTaskNotes {
@@ -84,6 +88,16 @@ impl StreamInspectTaskNotes {
     pub(crate) fn task_id(&self) -> &Uuid {
         &self.task_id
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        if let Some(task) = &self.registered_task {
+            task.cancel_token().clone()
+        } else if let Some(user) = self.user() {
+            user.revoke_token()
+        } else {
+            CancellationToken::default()
+        }
+    }
 }
 
 impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
@@ -99,6 +113,10 @@ impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
                 user_site: ctx.user_site().cloned(),
                 forbidden_stats: ctx.forbidden_stats().clone(),
             }),
+            registered_task: task_notes
+                .registered_task
+                .as_ref()
+                .map(|g| g.task().clone()),
         }
     }
 }
@@ -199,6 +217,17 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.server_quit_policy.force_quit()
     }
 
+    #[inline]
+    fn task_cancel_token(&self) -> CancellationToken {
+        self.task_notes.cancel_token()
+    }
+
+    fn task_cancel_error(&self) -> ServerTaskError {
+        self.user_blocked_reason()
+            .map(ServerTaskError::from)
+            .unwrap_or(ServerTaskError::CanceledAsTaskKilled)
+    }
+
     #[inline]
     fn server_offline(&self) -> bool {
         !self.server_stats.is_online()
@@ -310,6 +339,11 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         }
     }
 
+    #[inline]
+    fn websocket_interception(&self) -> &WebSocketInterceptionConfig {
+        self.audit_handle.websocket_interception()
+    }
+
     #[inline]
     fn smtp_inspect_action(&self, host: &Host) -> ProtocolInspectAction {
         match self.audit_handle.smtp_inspect_policy.check(host) {
@@ -336,12 +370,11 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.audit_handle.imap_interception()
     }
 
-    fn belongs_to_blocked_user(&self) -> bool {
+    fn user_blocked_reason(&self) -> Option<IdleForceQuitReason> {
         self.task_notes
             .user_ctx
             .as_ref()
-            .map(|cx| cx.user.is_blocked())
-            .unwrap_or(false)
+            .and_then(|cx| cx.user.blocked_reason())
     }
 }
 
@@ -420,6 +420,7 @@ impl<'a, SC: ServerConfig> Transaction<'a, SC> {
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut idle_count = 0;
 
+        let cancel_token = self.ctx.task_cancel_token();
         loop {
             tokio::select! {
                 biased;
@@ -437,6 +438,10 @@ impl<'a, SC: ServerConfig> Transaction<'a, SC> {
                         Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::UpstreamWriteFailed(e)),
                     };
                 }
+                _ = cancel_token.cancelled() => {
+                    let _ = clt_to_ups.write_flush().await;
+                    return Err(self.ctx.task_cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if clt_to_ups.is_idle() {
                         idle_count += n;
@@ -452,9 +457,9 @@ impl<'a, SC: ServerConfig> Transaction<'a, SC> {
                         clt_to_ups.reset_active();
                     }
 
-                    if self.ctx.belongs_to_blocked_user() {
+                    if let Some(reason) = self.ctx.user_blocked_reason() {
                         let _ = clt_to_ups.write_flush().await;
-                        return Err(ServerTaskError::CanceledAsUserBlocked);
+                        return Err(ServerTaskError::from(reason));
                     }
 
                     if self.ctx.server_force_quit() {
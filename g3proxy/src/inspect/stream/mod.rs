@@ -8,6 +8,7 @@ use std::time::Duration;
 use slog::slog_info;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::{MaybeProtocol, ProtocolInspectionConfig, ProtocolInspector};
@@ -34,6 +35,14 @@ pub(crate) trait StreamTransitTask {
     fn log_flush_interval(&self) -> Option<Duration>;
     fn quit_policy(&self) -> &ServerQuitPolicy;
     fn user(&self) -> Option<&User>;
+    fn cancel_token(&self) -> CancellationToken;
+
+    fn cancel_error(&self) -> ServerTaskError {
+        self.user()
+            .and_then(|user| user.blocked_reason())
+            .map(ServerTaskError::from)
+            .unwrap_or(ServerTaskError::CanceledAsTaskKilled)
+    }
 
     async fn transit_transparent<CR, CW, UR, UW>(
         &self,
@@ -80,6 +89,7 @@ pub(crate) trait StreamTransitTask {
             .user()
             .and_then(|u| u.task_max_idle_count())
             .unwrap_or(self.max_idle_count());
+        let cancel_token = self.cancel_token();
         loop {
             tokio::select! {
                 r = &mut clt_to_ups => {
@@ -113,13 +123,16 @@ pub(crate) trait StreamTransitTask {
                 _ = log_interval.tick() => {
                     self.log_periodic();
                 }
+                _ = cancel_token.cancelled() => {
+                    return Err(self.cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if clt_to_ups.is_idle() && ups_to_clt.is_idle() {
                         idle_count += n;
 
                         if let Some(user) = self.user() {
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -134,8 +147,8 @@ pub(crate) trait StreamTransitTask {
                     }
 
                     if let Some(user) = self.user() {
-                        if user.is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user.blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -159,6 +172,7 @@ pub(crate) trait StreamTransitTask {
         CR: AsyncRead + Unpin,
         UW: AsyncWrite + Unpin,
     {
+        let cancel_token = self.cancel_token();
         loop {
             tokio::select! {
                 r = &mut clt_to_ups => {
@@ -174,13 +188,16 @@ pub(crate) trait StreamTransitTask {
                 _ = log_interval.tick() => {
                     self.log_periodic();
                 }
+                _ = cancel_token.cancelled() => {
+                    return Err(self.cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if clt_to_ups.is_idle() {
                         idle_count += n;
 
                         if let Some(user) = self.user() {
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -194,8 +211,8 @@ pub(crate) trait StreamTransitTask {
                     }
 
                     if let Some(user) = self.user() {
-                        if user.is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user.blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -219,6 +236,7 @@ pub(crate) trait StreamTransitTask {
         CW: AsyncWrite + Unpin,
         UR: AsyncRead + Unpin,
     {
+        let cancel_token = self.cancel_token();
         loop {
             tokio::select! {
                 r = &mut ups_to_clt => {
@@ -234,13 +252,16 @@ pub(crate) trait StreamTransitTask {
                 _ = log_interval.tick() => {
                     self.log_periodic();
                 }
+                _ = cancel_token.cancelled() => {
+                    return Err(self.cancel_error())
+                }
                 n = idle_interval.tick() => {
                     if ups_to_clt.is_idle() {
                         idle_count += n;
 
                         if let Some(user) = self.user() {
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -254,8 +275,8 @@ pub(crate) trait StreamTransitTask {
                     }
 
                     if let Some(user) = self.user() {
-                        if user.is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user.blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -369,6 +390,10 @@ impl<SC: ServerConfig> StreamTransitTask for UnknownStreamTransitTask<'_, SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.ctx.task_cancel_token()
+    }
 }
 
 impl<SC> StreamInspectContext<SC>
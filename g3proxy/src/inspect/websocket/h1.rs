@@ -8,6 +8,7 @@ use std::time::Duration;
 use anyhow::anyhow;
 use slog::slog_info;
 use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::ProtocolInspectAction;
@@ -15,7 +16,7 @@ use g3_io_ext::{IdleInterval, LimitedWriteExt, StreamCopyConfig};
 use g3_slog_types::{LtHttpHeaderValue, LtUpstreamAddr, LtUuid};
 use g3_types::net::{UpstreamAddr, WebSocketNotes};
 
-use super::{ClientCloseFrame, ServerCloseFrame};
+use super::{ClientCloseFrame, ServerCloseFrame, WebSocketMessageInspector};
 #[cfg(feature = "quic")]
 use crate::audit::DetourAction;
 use crate::auth::User;
@@ -138,6 +139,10 @@ impl<SC: ServerConfig> StreamTransitTask for H1WebsocketInterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.ctx.task_cancel_token()
+    }
 }
 
 impl<SC: ServerConfig> H1WebsocketInterceptObject<SC> {
@@ -245,7 +250,16 @@ impl<SC: ServerConfig> H1WebsocketInterceptObject<SC> {
             ups_w,
         } = self.io.take().unwrap();
 
-        self.transit_transparent(clt_r, clt_w, ups_r, ups_w).await
+        if let Some(inspector) = WebSocketMessageInspector::build(
+            &self.ctx,
+            "H1Websocket",
+            &self.upstream,
+            &self.ws_notes,
+        ) {
+            inspector.transit(&*self, clt_r, clt_w, ups_r, ups_w).await
+        } else {
+            self.transit_transparent(clt_r, clt_w, ups_r, ups_w).await
+        }
     }
 
     async fn do_block(&mut self) -> ServerTaskResult<()> {
@@ -9,6 +9,7 @@ use anyhow::anyhow;
 use bytes::Bytes;
 use h2::{RecvStream, SendStream};
 use slog::slog_info;
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::ProtocolInspectAction;
@@ -17,7 +18,7 @@ use g3_io_ext::{IdleInterval, StreamCopyConfig};
 use g3_slog_types::{LtHttpHeaderValue, LtUpstreamAddr, LtUuid};
 use g3_types::net::{UpstreamAddr, WebSocketNotes};
 
-use super::{ClientCloseFrame, ServerCloseFrame};
+use super::{ClientCloseFrame, ServerCloseFrame, WebSocketMessageInspector};
 #[cfg(feature = "quic")]
 use crate::audit::DetourAction;
 use crate::auth::User;
@@ -115,6 +116,10 @@ impl<SC: ServerConfig> StreamTransitTask for H2WebsocketInterceptObject<SC> {
     fn user(&self) -> Option<&User> {
         self.ctx.user()
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.ctx.task_cancel_token()
+    }
 }
 
 impl<SC: ServerConfig> H2WebsocketInterceptObject<SC> {
@@ -224,7 +229,16 @@ impl<SC: ServerConfig> H2WebsocketInterceptObject<SC> {
         let ups_r = H2StreamReader::new(ups_r);
         let ups_w = H2StreamWriter::new(ups_w);
 
-        self.transit_transparent(clt_r, clt_w, ups_r, ups_w).await
+        if let Some(inspector) = WebSocketMessageInspector::build(
+            &self.ctx,
+            "H2Websocket",
+            &self.upstream,
+            &self.ws_notes,
+        ) {
+            inspector.transit(&*self, clt_r, clt_w, ups_r, ups_w).await
+        } else {
+            self.transit_transparent(clt_r, clt_w, ups_r, ups_w).await
+        }
     }
 
     async fn do_block(
@@ -0,0 +1,610 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::anyhow;
+use log::debug;
+use slog::slog_info;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_dpi::parser::websocket::{
+    FrameHeader, Message, MessageAssembler, OpCode, PerMessageDeflateDecoder,
+    PerMessageDeflateParams,
+};
+use g3_icap_client::reqmod::websocket::{WebSocketAdaptationAction, WebSocketMessageForAdaptation};
+use g3_io_ext::{LimitedWriteExt, OptionalInterval};
+use g3_slog_types::{LtUpstreamAddr, LtUuid};
+use g3_types::net::{UpstreamAddr, WebSocketNotes};
+
+use super::{ClientCloseFrame, ServerCloseFrame};
+use crate::config::server::ServerConfig;
+use crate::inspect::{StreamInspectContext, StreamTransitTask};
+use crate::serve::{ServerTaskError, ServerTaskResult};
+
+const CLOSE_CODE_PROTOCOL_ERROR: u16 = 1002;
+const CLOSE_CODE_POLICY_VIOLATION: u16 = 1008;
+const CLOSE_CODE_MESSAGE_TOO_BIG: u16 = 1009;
+
+/// Message level inspection for websocket data frames
+pub(super) struct WebSocketMessageInspector<'a, SC: ServerConfig> {
+    ctx: &'a StreamInspectContext<SC>,
+    intercept_type: &'static str,
+    upstream: &'a UpstreamAddr,
+    ws_notes: &'a WebSocketNotes,
+    inspect_message: bool,
+    buffer_max_size: usize,
+    message_max_size: Option<usize>,
+    message_max_count: Option<usize>,
+    deflate_params: Option<PerMessageDeflateParams>,
+}
+
+impl<'a, SC: ServerConfig> WebSocketMessageInspector<'a, SC> {
+    /// Build the inspector if message inspection is enabled or there are user level limits
+    pub(super) fn build(
+        ctx: &'a StreamInspectContext<SC>,
+        intercept_type: &'static str,
+        upstream: &'a UpstreamAddr,
+        ws_notes: &'a WebSocketNotes,
+    ) -> Option<Self> {
+        let config = ctx.websocket_interception();
+        let message_max_size = ctx.user().and_then(|u| u.websocket_message_max_size());
+        let message_max_count = ctx.user().and_then(|u| u.websocket_message_max_count());
+        if !config.inspect_message && message_max_size.is_none() && message_max_count.is_none() {
+            return None;
+        }
+
+        let deflate_params = ws_notes
+            .extensions()
+            .filter_map(|v| v.to_str().ok())
+            .find_map(PerMessageDeflateParams::parse_negotiated);
+        Some(WebSocketMessageInspector {
+            ctx,
+            intercept_type,
+            upstream,
+            ws_notes,
+            inspect_message: config.inspect_message,
+            buffer_max_size: config.message_buffer_max_size,
+            message_max_size,
+            message_max_count,
+            deflate_params,
+        })
+    }
+
+    fn icap_enabled(&self, from_client: bool) -> bool {
+        if !self.inspect_message {
+            return false;
+        }
+        if from_client {
+            self.ctx.audit_handle.icap_reqmod_client().is_some()
+        } else {
+            self.ctx.audit_handle.icap_respmod_client().is_some()
+        }
+    }
+
+    fn log_message(&self, from_client: bool, message: &Message, decoded_size: Option<usize>) {
+        if let Some(logger) = self.ctx.intercept_logger() {
+            slog_info!(logger, "websocket message";
+                "intercept_type" => self.intercept_type,
+                "task_id" => LtUuid(self.ctx.server_task_id()),
+                "depth" => self.ctx.inspection_depth,
+                "upstream" => LtUpstreamAddr(self.upstream),
+                "ws_resource_name" => self.ws_notes.resource_name(),
+                "ws_message_from" => if from_client { "client" } else { "server" },
+                "ws_message_opcode" => message.opcode.as_str(),
+                "ws_message_frames" => message.frame_count,
+                "ws_message_size" => message.payload_size,
+                "ws_message_compressed" => message.compressed,
+                "ws_message_decoded_size" => decoded_size,
+            );
+        }
+    }
+
+    async fn adapt_message(
+        &self,
+        from_client: bool,
+        message: &Message,
+        payload: &[u8],
+    ) -> WebSocketAdaptationAction {
+        let host = self.upstream.to_string();
+        let adaptation_message = WebSocketMessageForAdaptation {
+            host: &host,
+            resource_name: self.ws_notes.resource_name(),
+            text: message.opcode == OpCode::Text,
+            payload,
+        };
+        let timeout = self.ctx.websocket_interception().icap_adaptation_timeout;
+
+        let (r, bypass) = if from_client {
+            let Some(client) = self.ctx.audit_handle.icap_reqmod_client() else {
+                return WebSocketAdaptationAction::Forward;
+            };
+            let r = match client.websocket_message_adaptor().await {
+                Ok(mut adapter) => {
+                    adapter.set_client_addr(self.ctx.task_notes.client_addr);
+                    if let Some(username) = self.ctx.raw_user_name() {
+                        adapter.set_client_username(username.clone());
+                    }
+                    tokio::time::timeout(timeout, adapter.adapt(&adaptation_message))
+                        .await
+                        .map_err(|_| anyhow!("icap adaptation timeout"))
+                        .and_then(|r| r.map_err(anyhow::Error::new))
+                }
+                Err(e) => Err(e),
+            };
+            (r, client.bypass())
+        } else {
+            let Some(client) = self.ctx.audit_handle.icap_respmod_client() else {
+                return WebSocketAdaptationAction::Forward;
+            };
+            let r = match client.websocket_message_adaptor().await {
+                Ok(mut adapter) => {
+                    adapter.set_client_addr(self.ctx.task_notes.client_addr);
+                    if let Some(username) = self.ctx.raw_user_name() {
+                        adapter.set_client_username(username.clone());
+                    }
+                    tokio::time::timeout(timeout, adapter.adapt(&adaptation_message))
+                        .await
+                        .map_err(|_| anyhow!("icap adaptation timeout"))
+                        .and_then(|r| r.map_err(anyhow::Error::new))
+                }
+                Err(e) => Err(e),
+            };
+            (r, client.bypass())
+        };
+
+        match r {
+            Ok(action) => action,
+            Err(e) => {
+                debug!("websocket message adaptation failed: {e:?}");
+                if bypass {
+                    WebSocketAdaptationAction::Forward
+                } else {
+                    WebSocketAdaptationAction::Block
+                }
+            }
+        }
+    }
+
+    pub(super) async fn transit<T, CR, CW, UR, UW>(
+        &self,
+        task: &T,
+        clt_r: CR,
+        clt_w: CW,
+        ups_r: UR,
+        ups_w: UW,
+    ) -> ServerTaskResult<()>
+    where
+        T: StreamTransitTask,
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+        UR: AsyncRead + Unpin,
+        UW: AsyncWrite + Unpin,
+    {
+        let mut clt_to_ups = FrameRelay::new(self, true, clt_r, ups_w);
+        let mut ups_to_clt = FrameRelay::new(self, false, ups_r, clt_w);
+
+        let r = self.run(task, &mut clt_to_ups, &mut ups_to_clt).await;
+        if let Some(code) = clt_to_ups.close_code.or(ups_to_clt.close_code) {
+            let server_close = ServerCloseFrame::encode_with_status_code(code);
+            let client_close = ClientCloseFrame::encode_with_status_code(code);
+            if ups_to_clt
+                .writer
+                .write_all_flush(&server_close)
+                .await
+                .is_ok()
+            {
+                let _ = ups_to_clt.writer.shutdown().await;
+            }
+            if clt_to_ups
+                .writer
+                .write_all_flush(&client_close)
+                .await
+                .is_ok()
+            {
+                let _ = clt_to_ups.writer.shutdown().await;
+            }
+        }
+        r
+    }
+
+    async fn run<T, CR, CW, UR, UW>(
+        &self,
+        task: &T,
+        clt_to_ups: &mut FrameRelay<'_, 'a, SC, CR, UW>,
+        ups_to_clt: &mut FrameRelay<'_, 'a, SC, UR, CW>,
+    ) -> ServerTaskResult<()>
+    where
+        T: StreamTransitTask,
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+        UR: AsyncRead + Unpin,
+        UW: AsyncWrite + Unpin,
+    {
+        let clt_active = AtomicBool::new(false);
+        let ups_active = AtomicBool::new(false);
+
+        let mut idle_interval = task.idle_check_interval();
+        let mut log_interval = task
+            .log_flush_interval()
+            .map(|log_interval| {
+                let interval =
+                    tokio::time::interval_at(Instant::now() + log_interval, log_interval);
+                OptionalInterval::with(interval)
+            })
+            .unwrap_or_default();
+        let mut idle_count = 0;
+        let max_idle_count = task
+            .user()
+            .and_then(|u| u.task_max_idle_count())
+            .unwrap_or(task.max_idle_count());
+
+        let clt_to_ups = clt_to_ups.relay(&clt_active);
+        tokio::pin!(clt_to_ups);
+        let ups_to_clt = ups_to_clt.relay(&ups_active);
+        tokio::pin!(ups_to_clt);
+        let mut clt_finished = false;
+        let mut ups_finished = false;
+
+        let cancel_token = task.cancel_token();
+        loop {
+            tokio::select! {
+                r = &mut clt_to_ups, if !clt_finished => {
+                    r?;
+                    task.log_client_shutdown();
+                    clt_finished = true;
+                    if ups_finished {
+                        return Ok(());
+                    }
+                }
+                r = &mut ups_to_clt, if !ups_finished => {
+                    r?;
+                    task.log_upstream_shutdown();
+                    ups_finished = true;
+                    if clt_finished {
+                        return Ok(());
+                    }
+                }
+                _ = log_interval.tick() => {
+                    task.log_periodic();
+                }
+                _ = cancel_token.cancelled() => {
+                    return Err(task.cancel_error())
+                }
+                n = idle_interval.tick() => {
+                    let clt_active = clt_active.swap(false, Ordering::Relaxed);
+                    let ups_active = ups_active.swap(false, Ordering::Relaxed);
+                    if clt_active || ups_active {
+                        idle_count = 0;
+                    } else {
+                        idle_count += n;
+                        if idle_count >= max_idle_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    }
+
+                    if let Some(reason) = task.user().and_then(|u| u.blocked_reason()) {
+                        return Err(ServerTaskError::from(reason));
+                    }
+
+                    if task.quit_policy().force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct FrameRelay<'i, 'a, SC: ServerConfig, R, W> {
+    inspector: &'i WebSocketMessageInspector<'a, SC>,
+    from_client: bool,
+    reader: R,
+    writer: W,
+    assembler: MessageAssembler,
+    decoder: Option<PerMessageDeflateDecoder>,
+    decoder_no_context_takeover: bool,
+    collecting: bool,
+    held: Option<Vec<u8>>,
+    message_count: usize,
+    close_code: Option<u16>,
+}
+
+impl<'i, 'a, SC, R, W> FrameRelay<'i, 'a, SC, R, W>
+where
+    SC: ServerConfig,
+    R: AsyncRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    fn new(
+        inspector: &'i WebSocketMessageInspector<'a, SC>,
+        from_client: bool,
+        reader: R,
+        writer: W,
+    ) -> Self {
+        let mut decoder_no_context_takeover = false;
+        let decoder = inspector
+            .deflate_params
+            .filter(|_| inspector.icap_enabled(from_client))
+            .map(|params| {
+                decoder_no_context_takeover = if from_client {
+                    params.client_no_context_takeover
+                } else {
+                    params.server_no_context_takeover
+                };
+                PerMessageDeflateDecoder::new(decoder_no_context_takeover)
+            });
+        FrameRelay {
+            inspector,
+            from_client,
+            reader,
+            writer,
+            assembler: MessageAssembler::new(inspector.buffer_max_size),
+            decoder,
+            decoder_no_context_takeover,
+            collecting: false,
+            held: None,
+            message_count: 0,
+            close_code: None,
+        }
+    }
+
+    fn read_error(&self, e: io::Error) -> ServerTaskError {
+        if self.from_client {
+            ServerTaskError::ClientTcpReadFailed(e)
+        } else {
+            ServerTaskError::UpstreamReadFailed(e)
+        }
+    }
+
+    fn write_error(&self, e: io::Error) -> ServerTaskError {
+        if self.from_client {
+            ServerTaskError::UpstreamWriteFailed(e)
+        } else {
+            ServerTaskError::ClientTcpWriteFailed(e)
+        }
+    }
+
+    fn protocol_error(&mut self) -> ServerTaskError {
+        self.close_code = Some(CLOSE_CODE_PROTOCOL_ERROR);
+        if self.from_client {
+            ServerTaskError::InvalidClientProtocol("invalid websocket frame")
+        } else {
+            ServerTaskError::InvalidUpstreamProtocol("invalid websocket frame")
+        }
+    }
+
+    fn limit_error(&mut self, close_code: u16, e: anyhow::Error) -> ServerTaskError {
+        self.close_code = Some(close_code);
+        if self.from_client {
+            ServerTaskError::ClientAppError(e)
+        } else {
+            ServerTaskError::UpstreamAppError(e)
+        }
+    }
+
+    async fn relay(&mut self, active: &AtomicBool) -> ServerTaskResult<()> {
+        let mut hdr_buf = [0u8; FrameHeader::MAX_SIZE];
+        let mut buf = vec![0u8; 16384];
+
+        loop {
+            let nr = self
+                .reader
+                .read(&mut hdr_buf[..FrameHeader::MIN_SIZE])
+                .await
+                .map_err(|e| self.read_error(e))?;
+            if nr == 0 {
+                let _ = self.writer.shutdown().await;
+                return Ok(());
+            }
+            active.store(true, Ordering::Relaxed);
+            if nr < FrameHeader::MIN_SIZE {
+                self.reader
+                    .read_exact(&mut hdr_buf[nr..FrameHeader::MIN_SIZE])
+                    .await
+                    .map_err(|e| self.read_error(e))?;
+            }
+            let hdr_size = FrameHeader::header_size(&[hdr_buf[0], hdr_buf[1]]);
+            self.reader
+                .read_exact(&mut hdr_buf[FrameHeader::MIN_SIZE..hdr_size])
+                .await
+                .map_err(|e| self.read_error(e))?;
+            let Ok((header, _)) = FrameHeader::parse(&hdr_buf[..hdr_size]) else {
+                return Err(self.protocol_error());
+            };
+
+            if matches!(header.opcode, OpCode::Text | OpCode::Binary) {
+                self.start_message(header.rsv1)?;
+            }
+            let Ok(is_data) = self.assembler.start_frame(&header, self.collecting) else {
+                return Err(self.protocol_error());
+            };
+            if is_data {
+                self.check_message_size(header.payload_len)?;
+            }
+
+            let hold = is_data && self.held.is_some();
+            if let Some(held) = self.held.as_mut().filter(|_| hold) {
+                held.extend_from_slice(&hdr_buf[..hdr_size]);
+            } else {
+                self.writer
+                    .write_all(&hdr_buf[..hdr_size])
+                    .await
+                    .map_err(|e| self.write_error(e))?;
+            }
+
+            let mut left = header.payload_len;
+            while left > 0 {
+                let to_read = left.min(buf.len() as u64) as usize;
+                let nr = self
+                    .reader
+                    .read(&mut buf[..to_read])
+                    .await
+                    .map_err(|e| self.read_error(e))?;
+                if nr == 0 {
+                    return Err(self.read_error(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "websocket frame not finished",
+                    )));
+                }
+                active.store(true, Ordering::Relaxed);
+                left -= nr as u64;
+
+                if !is_data {
+                    self.writer
+                        .write_all(&buf[..nr])
+                        .await
+                        .map_err(|e| self.write_error(e))?;
+                    continue;
+                }
+
+                self.assembler.push_payload(&buf[..nr]);
+                if let Some(mut held) = self.held.take() {
+                    held.extend_from_slice(&buf[..nr]);
+                    if self
+                        .assembler
+                        .message()
+                        .map(|m| m.payload.is_some())
+                        .unwrap_or(false)
+                    {
+                        self.held = Some(held);
+                    } else {
+                        // too large to be held, forward it directly
+                        self.writer
+                            .write_all(&held)
+                            .await
+                            .map_err(|e| self.write_error(e))?;
+                    }
+                } else {
+                    self.writer
+                        .write_all(&buf[..nr])
+                        .await
+                        .map_err(|e| self.write_error(e))?;
+                }
+            }
+
+            let message = if is_data {
+                self.assembler.end_frame()
+            } else {
+                None
+            };
+            if let Some(message) = message {
+                self.finish_message(message).await?;
+                continue;
+            }
+            if self.held.is_none() || !is_data {
+                self.writer.flush().await.map_err(|e| self.write_error(e))?;
+            }
+        }
+    }
+
+    fn start_message(&mut self, compressed: bool) -> ServerTaskResult<()> {
+        if let Some(max_count) = self
+            .inspector
+            .message_max_count
+            .filter(|max| self.message_count >= *max)
+        {
+            return Err(self.limit_error(
+                CLOSE_CODE_POLICY_VIOLATION,
+                anyhow!("websocket message count exceeded the limit {max_count}"),
+            ));
+        }
+        self.message_count += 1;
+
+        let icap = self.inspector.icap_enabled(self.from_client)
+            && self.inspector.ctx.audit_handle.do_websocket_icap();
+        let decode =
+            compressed && self.decoder.is_some() && (icap || !self.decoder_no_context_takeover);
+        self.collecting = icap || decode;
+        self.held = icap.then(Vec::new);
+        Ok(())
+    }
+
+    fn check_message_size(&mut self, frame_size: u64) -> ServerTaskResult<()> {
+        let Some(max_size) = self.inspector.message_max_size else {
+            return Ok(());
+        };
+        let current_size = self
+            .assembler
+            .message()
+            .map(|m| m.payload_size)
+            .unwrap_or_default();
+        if current_size + frame_size > max_size as u64 {
+            Err(self.limit_error(
+                CLOSE_CODE_MESSAGE_TOO_BIG,
+                anyhow!("websocket message size exceeded the limit {max_size}"),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn decode_message(&mut self, message: &mut Message) -> Option<Vec<u8>> {
+        let payload = message.payload.take();
+        if !message.compressed {
+            return payload;
+        }
+
+        let decoder = self.decoder.as_mut()?;
+        let Some(payload) = payload else {
+            if self.collecting && !self.decoder_no_context_takeover {
+                // the sliding window is lost as the message is too large
+                self.decoder = None;
+            }
+            return None;
+        };
+        match decoder.decode(&payload, self.inspector.buffer_max_size) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                debug!("failed to decode websocket message: {e}");
+                if !self.decoder_no_context_takeover {
+                    self.decoder = None;
+                }
+                None
+            }
+        }
+    }
+
+    async fn finish_message(&mut self, mut message: Message) -> ServerTaskResult<()> {
+        let data = self.decode_message(&mut message);
+        if self.inspector.inspect_message {
+            let decoded_size = data
+                .as_ref()
+                .filter(|_| message.compressed)
+                .map(|v| v.len());
+            self.inspector
+                .log_message(self.from_client, &message, decoded_size);
+        }
+
+        if let Some(held) = self.held.take() {
+            let action = match &data {
+                Some(data) => {
+                    self.inspector
+                        .adapt_message(self.from_client, &message, data)
+                        .await
+                }
+                None => WebSocketAdaptationAction::Forward,
+            };
+            match action {
+                WebSocketAdaptationAction::Forward => {
+                    self.writer
+                        .write_all(&held)
+                        .await
+                        .map_err(|e| self.write_error(e))?;
+                }
+                WebSocketAdaptationAction::Block => {
+                    self.close_code = Some(CLOSE_CODE_POLICY_VIOLATION);
+                    return Err(ServerTaskError::InternalAdapterError(anyhow!(
+                        "websocket message blocked by icap server"
+                    )));
+                }
+            }
+        }
+        self.collecting = false;
+        self.writer.flush().await.map_err(|e| self.write_error(e))
+    }
+}
@@ -6,6 +6,9 @@
 mod close;
 use close::{ClientCloseFrame, ServerCloseFrame};
 
+mod message;
+use message::WebSocketMessageInspector;
+
 mod h1;
 pub(crate) use h1::H1WebsocketInterceptObject;
 
@@ -7,6 +7,7 @@ pub mod audit;
 pub mod auth;
 pub mod config;
 pub mod control;
+pub mod discover;
 pub mod escape;
 pub mod opts;
 pub mod resolve;
@@ -8,6 +8,7 @@ use slog::Logger;
 use g3_types::metrics::NodeName;
 
 pub(crate) mod stream;
+pub(crate) mod udp;
 
 pub(crate) enum InspectSource {
     StreamInspection,
@@ -15,6 +16,7 @@ pub(crate) enum InspectSource {
     StartTls,
     H2ExtendedConnect,
     HttpUpgrade,
+    UdpStream,
 }
 
 impl InspectSource {
@@ -25,6 +27,7 @@ impl InspectSource {
             InspectSource::StartTls => "start tls",
             InspectSource::H2ExtendedConnect => "h2 extended connect",
             InspectSource::HttpUpgrade => "http upgrade",
+            InspectSource::UdpStream => "udp stream",
         }
     }
 }
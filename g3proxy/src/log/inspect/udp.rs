@@ -0,0 +1,31 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use slog::{Logger, slog_info};
+use uuid::Uuid;
+
+use g3_dpi::Protocol;
+use g3_slog_types::{LtUpstreamAddr, LtUuid};
+use g3_types::net::UpstreamAddr;
+
+use super::InspectSource;
+
+/// The flow log for udp flows, as there is no protocol inspection or audit service for datagrams.
+pub(crate) struct UdpFlowInspectLog<'a> {
+    pub(crate) task_id: &'a Uuid,
+    pub(crate) upstream: &'a UpstreamAddr,
+}
+
+impl UdpFlowInspectLog<'_> {
+    pub(crate) fn log(&self, logger: &Logger, source: InspectSource) {
+        slog_info!(logger, "";
+            "task_id" => LtUuid(self.task_id),
+            "depth" => 0,
+            "source" => source.as_str(),
+            "protocol" => Protocol::Unknown.as_str(),
+            "upstream" => LtUpstreamAddr(self.upstream),
+        );
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use slog::{Logger, slog_info};
+
+use g3_slog_types::{LtDateTime, LtDuration, LtUuid};
+
+use super::TaskEvent;
+use crate::serve::ServerTaskNotes;
+
+pub(crate) struct TaskLogForDnsQuery<'a> {
+    pub(crate) logger: &'a Logger,
+    pub(crate) task_notes: &'a ServerTaskNotes,
+    pub(crate) protocol: &'static str,
+    pub(crate) query_name: Option<&'a str>,
+    pub(crate) query_type: Option<String>,
+    pub(crate) rcode: String,
+    pub(crate) answer_count: usize,
+}
+
+impl TaskLogForDnsQuery<'_> {
+    pub(crate) fn log(&self, reason: Option<&str>) {
+        slog_info!(self.logger, "{}", reason.unwrap_or_default();
+            "task_type" => "DnsQuery",
+            "task_id" => LtUuid(&self.task_notes.id),
+            "task_event" => TaskEvent::Finished.as_str(),
+            "start_at" => LtDateTime(&self.task_notes.start_at),
+            "server_addr" => self.task_notes.server_addr(),
+            "client_addr" => self.task_notes.client_addr(),
+            "protocol" => self.protocol,
+            "query_name" => self.query_name,
+            "query_type" => self.query_type.as_ref(),
+            "rcode" => &self.rcode,
+            "answer_count" => self.answer_count,
+            "total_time" => LtDuration(self.task_notes.time_elapsed()),
+        )
+    }
+}
@@ -119,6 +119,7 @@ impl TaskLogForHttpForward<'_> {
             "user_agent" => self.http_user_agent,
             "rsp_status" => self.http_notes.rsp_status,
             "origin_status" => self.http_notes.origin_status,
+            "cache" => self.http_notes.cache_status.map(|s| s.as_str()),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
             "total_time" => LtDuration(self.task_notes.time_elapsed()),
@@ -165,6 +166,7 @@ impl TaskLogForHttpForward<'_> {
             "user_agent" => self.http_user_agent,
             "rsp_status" => self.http_notes.rsp_status,
             "origin_status" => self.http_notes.origin_status,
+            "cache" => self.http_notes.cache_status.map(|s| s.as_str()),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
             "dur_req_send_hdr" => LtDuration(self.http_notes.dur_req_send_hdr),
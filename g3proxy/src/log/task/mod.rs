@@ -7,6 +7,7 @@ use slog::{Logger, slog_o};
 
 use g3_types::metrics::NodeName;
 
+pub(crate) mod dns_query;
 pub(crate) mod ftp_over_http;
 pub(crate) mod http_forward;
 pub(crate) mod tcp_connect;
@@ -40,6 +40,13 @@ fn main() -> anyhow::Result<()> {
 
     // set up process logger early, only proc args is used inside
     g3_daemon::log::process::setup(&proc_args.daemon_config);
+    if proc_args.validate_config {
+        // report all reference errors before the normal load, which stops at the first one
+        let content = g3proxy::config::validate_report()?;
+        g3proxy::config::load().context("failed to load config")?;
+        print!("{content}");
+        return Ok(());
+    }
     if proc_args.daemon_config.need_daemon_controller() {
         g3proxy::control::UpgradeActor::connect_to_old_daemon();
     }
@@ -125,6 +132,7 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
                 daemon_ctl.await;
             });
         }
+        g3proxy::control::HttpController::start().context("failed to start http controller")?;
         g3proxy::control::QuitActor::tokio_spawn_run();
 
         g3proxy::signal::register().context("failed to setup signal handler")?;
@@ -161,6 +169,8 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
 }
 
 async fn load_and_spawn() -> anyhow::Result<()> {
+    // let the old daemon flush its resolver cache before we load the snapshots
+    g3_daemon::control::upgrade::save_old_resolver_cache().await;
     g3proxy::resolve::spawn_all()
         .await
         .context("failed to spawn all resolvers")?;
@@ -173,6 +183,9 @@ async fn load_and_spawn() -> anyhow::Result<()> {
     g3proxy::audit::load_all()
         .await
         .context("failed to load all auditors")?;
+    g3proxy::discover::load_all()
+        .await
+        .context("failed to load all discovers")?;
     g3proxy::serve::spawn_offline_clean();
     g3proxy::serve::spawn_all()
         .await
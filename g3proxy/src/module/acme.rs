@@ -0,0 +1,49 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+
+use g3_acme::AcmeConfig;
+use g3_types::net::{OpensslCertificatePair, RustlsCertificatePair, RustlsCertificatePairBuilder};
+
+/// Load the stored ACME certificate as rustls cert pair, return `None` if not issued yet
+pub(crate) fn load_rustls_cert_pair(
+    config: &AcmeConfig,
+) -> anyhow::Result<Option<RustlsCertificatePair>> {
+    let Some((certs, key)) = config.load_certificate()? else {
+        return Ok(None);
+    };
+    let mut cert_ders = Vec::with_capacity(certs.len());
+    for (i, cert) in certs.iter().enumerate() {
+        let der = cert
+            .to_der()
+            .map_err(|e| anyhow!("failed to encode ACME certificate #{i}: {e}"))?;
+        cert_ders.push(CertificateDer::from(der));
+    }
+    let key_der = key
+        .private_key_to_pkcs8()
+        .map_err(|e| anyhow!("failed to encode ACME private key: {e}"))?;
+    let mut builder = RustlsCertificatePairBuilder::default();
+    builder.set_certs(cert_ders);
+    builder.set_key(PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_der)));
+    let pair = builder.build().context("invalid ACME cert pair")?;
+    Ok(Some(pair))
+}
+
+/// Load the stored ACME certificate as openssl cert pair, return `None` if not issued yet
+pub(crate) fn load_openssl_cert_pair(
+    config: &AcmeConfig,
+) -> anyhow::Result<Option<OpensslCertificatePair>> {
+    let Some((certs, key)) = config.load_certificate()? else {
+        return Ok(None);
+    };
+    let mut pair = OpensslCertificatePair::default();
+    pair.set_certificates(certs)
+        .context("invalid ACME certificate")?;
+    pair.set_private_key(key)
+        .context("invalid ACME private key")?;
+    Ok(Some(pair))
+}
@@ -0,0 +1,73 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use http::{HeaderMap, HeaderName, HeaderValue};
+use thiserror::Error;
+
+use crate::module::tcp_connect::TcpConnectError;
+
+mod pool;
+pub(crate) use pool::{H2ConnectPool, H2ConnectPoolAcquire, H2ConnectSender};
+
+mod stream;
+pub(crate) use stream::{H2ConnectStream, HttpConnectStream};
+
+#[derive(Debug, Error)]
+pub(crate) enum H2ConnectError {
+    #[error("h2 connection error: {0}")]
+    Connection(h2::Error),
+    #[error("{0}")]
+    Negotiation(TcpConnectError),
+}
+
+/// Convert the extra header lines for HTTP/1.1 CONNECT request to HTTP/2 headers
+pub(crate) fn build_connect_headers<'a, I>(lines: I) -> HeaderMap
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let mut headers = HeaderMap::new();
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let Ok(name) = HeaderName::from_bytes(name.trim().as_bytes()) else {
+            continue;
+        };
+        let Ok(value) = HeaderValue::from_str(value.trim()) else {
+            continue;
+        };
+        headers.append(name, value);
+    }
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn connect_headers() {
+        let lines = [
+            "Proxy-Authorization: Basic dXNlcjpwYXNz".to_string(),
+            "X-Forwarded-For:  192.0.2.1 ".to_string(),
+            "X-Forwarded-For: 192.0.2.2".to_string(),
+            "no colon".to_string(),
+            "Bad Name: value".to_string(),
+            "X-Bad-Value: a\u{7f}b".to_string(),
+        ];
+        let headers = build_connect_headers(&lines);
+        assert_eq!(headers.len(), 3);
+        assert_eq!(
+            headers.get("proxy-authorization").unwrap(),
+            "Basic dXNlcjpwYXNz"
+        );
+        let values = headers
+            .get_all("x-forwarded-for")
+            .iter()
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["192.0.2.1", "192.0.2.2"]);
+        assert!(build_connect_headers(&[]).is_empty());
+    }
+}
@@ -0,0 +1,652 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use bytes::Bytes;
+use h2::client::SendRequest;
+use h2::{RecvStream, SendStream};
+use http::{HeaderMap, Method, Request, Uri, Version};
+use log::debug;
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use g3_socket::BindAddr;
+use g3_types::net::{Http2ConnectPoolConfig, UpstreamAddr};
+
+use super::{H2ConnectError, H2ConnectStream};
+use crate::module::tcp_connect::{TcpConnectError, TcpConnectTaskNotes};
+
+const STREAM_WINDOW_SIZE: u32 = 1 << 20;
+const CONNECTION_WINDOW_SIZE: u32 = 1 << 24;
+
+#[derive(Clone, Copy)]
+struct ConnectionNotes {
+    bind: BindAddr,
+    next: Option<SocketAddr>,
+    local: Option<SocketAddr>,
+}
+
+impl ConnectionNotes {
+    fn new(tcp_notes: &TcpConnectTaskNotes) -> Self {
+        ConnectionNotes {
+            bind: tcp_notes.bind,
+            next: tcp_notes.next,
+            local: tcp_notes.local,
+        }
+    }
+}
+
+struct PooledConnection {
+    id: u64,
+    send_request: SendRequest<Bytes>,
+    notes: ConnectionNotes,
+    active_streams: usize,
+    /// the SETTINGS_MAX_CONCURRENT_STREAMS value announced by the peer
+    peer_max_streams: usize,
+    idle_since: Instant,
+}
+
+#[derive(Default)]
+struct PeerConnections {
+    connections: Vec<PooledConnection>,
+    pending: usize,
+    h1_only_until: Option<Instant>,
+}
+
+#[derive(Default)]
+struct PoolInner {
+    peers: HashMap<UpstreamAddr, PeerConnections>,
+    next_id: u64,
+}
+
+/// Pool of HTTP/2 connections to the next hop proxies, keyed by the peer address
+pub(crate) struct H2ConnectPool {
+    config: Http2ConnectPoolConfig,
+    inner: Mutex<PoolInner>,
+}
+
+pub(crate) enum H2ConnectPoolAcquire {
+    /// Open a new stream on an existing connection
+    Reuse(H2ConnectSender),
+    /// A new connection should be established and added to the pool
+    New(H2ConnectPermit),
+    /// Use a HTTP/1.1 connection instead
+    Fallback,
+}
+
+impl H2ConnectPool {
+    pub(crate) fn new(config: Http2ConnectPoolConfig) -> Arc<Self> {
+        Arc::new(H2ConnectPool {
+            config,
+            inner: Mutex::new(PoolInner::default()),
+        })
+    }
+
+    pub(crate) fn acquire(self: &Arc<Self>, peer: &UpstreamAddr) -> H2ConnectPoolAcquire {
+        let mut inner = self.inner.lock().unwrap();
+        let peer_connections = inner.peers.entry(peer.clone()).or_default();
+
+        if let Some(until) = peer_connections.h1_only_until {
+            if until > Instant::now() {
+                return H2ConnectPoolAcquire::Fallback;
+            }
+            peer_connections.h1_only_until = None;
+        }
+
+        let max_streams = self.config.max_streams();
+        if let Some(c) = peer_connections
+            .connections
+            .iter_mut()
+            .find(|c| c.active_streams < max_streams.min(c.peer_max_streams))
+        {
+            c.active_streams += 1;
+            let sender = H2ConnectSender {
+                send_request: c.send_request.clone(),
+                notes: c.notes,
+                guard: H2StreamGuard {
+                    pool: self.clone(),
+                    peer: peer.clone(),
+                    id: c.id,
+                },
+                new_connection: false,
+            };
+            return H2ConnectPoolAcquire::Reuse(sender);
+        }
+
+        if peer_connections.connections.len() + peer_connections.pending
+            < self.config.max_connections()
+        {
+            peer_connections.pending += 1;
+            return H2ConnectPoolAcquire::New(H2ConnectPermit {
+                pool: self.clone(),
+                peer: peer.clone(),
+            });
+        }
+
+        H2ConnectPoolAcquire::Fallback
+    }
+
+    fn add_connection(
+        self: &Arc<Self>,
+        peer: &UpstreamAddr,
+        send_request: SendRequest<Bytes>,
+        notes: ConnectionNotes,
+    ) -> u64 {
+        let mut inner = self.inner.lock().unwrap();
+        inner.next_id += 1;
+        let id = inner.next_id;
+        let peer_connections = inner.peers.entry(peer.clone()).or_default();
+        peer_connections.connections.push(PooledConnection {
+            id,
+            send_request,
+            notes,
+            active_streams: 1,
+            peer_max_streams: usize::MAX,
+            idle_since: Instant::now(),
+        });
+        id
+    }
+
+    fn set_peer_max_streams(&self, peer: &UpstreamAddr, id: u64, count: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(peer_connections) = inner.peers.get_mut(peer) else {
+            return;
+        };
+        if let Some(c) = peer_connections.connections.iter_mut().find(|c| c.id == id) {
+            c.peer_max_streams = count;
+        }
+    }
+
+    fn release_pending(&self, peer: &UpstreamAddr) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(peer_connections) = inner.peers.get_mut(peer) {
+            peer_connections.pending = peer_connections.pending.saturating_sub(1);
+        }
+    }
+
+    fn set_h1_only(&self, peer: &UpstreamAddr) {
+        let mut inner = self.inner.lock().unwrap();
+        // the failed connection may have been removed together with the peer entry
+        let peer_connections = inner.peers.entry(peer.clone()).or_default();
+        peer_connections.h1_only_until = Some(Instant::now() + self.config.fallback_duration());
+    }
+
+    fn release_stream(&self, peer: &UpstreamAddr, id: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(peer_connections) = inner.peers.get_mut(peer) else {
+            return;
+        };
+        if let Some(c) = peer_connections.connections.iter_mut().find(|c| c.id == id) {
+            c.active_streams = c.active_streams.saturating_sub(1);
+            if c.active_streams == 0 {
+                c.idle_since = Instant::now();
+            }
+        }
+    }
+
+    /// Remove the connection if it has been idle for long, return true if removed
+    fn remove_idle(&self, peer: &UpstreamAddr, id: u64) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(peer_connections) = inner.peers.get_mut(peer) else {
+            return true;
+        };
+        let Some(i) = peer_connections.connections.iter().position(|c| c.id == id) else {
+            return true;
+        };
+        let c = &peer_connections.connections[i];
+        if c.active_streams == 0 && c.idle_since.elapsed() >= self.config.idle_timeout() {
+            peer_connections.connections.swap_remove(i);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn remove(&self, peer: &UpstreamAddr, id: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(peer_connections) = inner.peers.get_mut(peer) else {
+            return;
+        };
+        peer_connections.connections.retain(|c| c.id != id);
+        if peer_connections.connections.is_empty()
+            && peer_connections.pending == 0
+            && peer_connections.h1_only_until.is_none()
+        {
+            inner.peers.remove(peer);
+        }
+    }
+}
+
+/// Permit to add a new connection to the pool
+pub(crate) struct H2ConnectPermit {
+    pool: Arc<H2ConnectPool>,
+    peer: UpstreamAddr,
+}
+
+impl Drop for H2ConnectPermit {
+    fn drop(&mut self) {
+        self.pool.release_pending(&self.peer);
+    }
+}
+
+impl H2ConnectPermit {
+    /// Run HTTP/2 handshake on the new connection and add it to the pool.
+    /// The returned sender should be used to send the first CONNECT request.
+    pub(crate) async fn handshake<IO>(
+        self,
+        io: IO,
+        tcp_notes: &TcpConnectTaskNotes,
+    ) -> Result<H2ConnectSender, H2ConnectError>
+    where
+        IO: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let mut builder = h2::client::Builder::new();
+        builder
+            .enable_push(false)
+            .initial_window_size(STREAM_WINDOW_SIZE)
+            .initial_connection_window_size(CONNECTION_WINDOW_SIZE);
+        let (send_request, connection) = match builder.handshake(io).await {
+            Ok(v) => v,
+            Err(e) => {
+                self.set_h1_only();
+                return Err(H2ConnectError::Connection(e));
+            }
+        };
+
+        let notes = ConnectionNotes::new(tcp_notes);
+        let id = self
+            .pool
+            .add_connection(&self.peer, send_request.clone(), notes);
+
+        let pool = self.pool.clone();
+        let peer = self.peer.clone();
+        let idle_timeout = pool.config.idle_timeout();
+        tokio::spawn(async move {
+            tokio::pin!(connection);
+            let mut peer_max_streams = usize::MAX;
+            loop {
+                let drive_connection = std::future::poll_fn(|cx| {
+                    let r = connection.as_mut().poll(cx);
+                    // the peer may change the limit at any time by sending a new SETTINGS frame
+                    let count = connection.max_concurrent_send_streams();
+                    if count != peer_max_streams {
+                        peer_max_streams = count;
+                        pool.set_peer_max_streams(&peer, id, count);
+                    }
+                    r
+                });
+                tokio::select! {
+                    r = drive_connection => {
+                        if let Err(e) = r {
+                            debug!("h2 connection to next proxy {peer} closed: {e}");
+                        }
+                        break;
+                    }
+                    _ = tokio::time::sleep(idle_timeout) => {
+                        if pool.remove_idle(&peer, id) {
+                            break;
+                        }
+                    }
+                }
+            }
+            pool.remove(&peer, id);
+        });
+
+        Ok(H2ConnectSender {
+            send_request,
+            notes,
+            guard: H2StreamGuard {
+                pool: self.pool.clone(),
+                peer: self.peer.clone(),
+                id,
+            },
+            new_connection: true,
+        })
+    }
+
+    /// Mark the peer as HTTP/1.1 only for a while
+    pub(crate) fn set_h1_only(self) {
+        self.pool.set_h1_only(&self.peer);
+    }
+}
+
+pub(super) struct H2StreamGuard {
+    pool: Arc<H2ConnectPool>,
+    peer: UpstreamAddr,
+    id: u64,
+}
+
+impl Drop for H2StreamGuard {
+    fn drop(&mut self) {
+        self.pool.release_stream(&self.peer, self.id);
+    }
+}
+
+pub(crate) struct H2ConnectSender {
+    send_request: SendRequest<Bytes>,
+    notes: ConnectionNotes,
+    guard: H2StreamGuard,
+    new_connection: bool,
+}
+
+impl H2ConnectSender {
+    /// Fill in the notes of the underlying connection
+    pub(crate) fn fill_tcp_notes(&self, tcp_notes: &mut TcpConnectTaskNotes) {
+        tcp_notes.bind = self.notes.bind;
+        tcp_notes.next = self.notes.next;
+        tcp_notes.local = self.notes.local;
+    }
+
+    /// Send the CONNECT request on a new stream.
+    /// If this is the first stream on a new connection and the connection failed,
+    /// the peer will be marked as HTTP/1.1 only for a while.
+    pub(crate) async fn send_connect(
+        self,
+        upstream: &UpstreamAddr,
+        headers: HeaderMap,
+    ) -> Result<H2ConnectStream, H2ConnectError> {
+        match self.open_stream(upstream, headers).await {
+            Ok((recv_stream, send_stream)) => {
+                Ok(H2ConnectStream::new(recv_stream, send_stream, self.guard))
+            }
+            Err(e) => {
+                if self.new_connection && matches!(e, H2ConnectError::Connection(_)) {
+                    self.guard.pool.set_h1_only(&self.guard.peer);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn open_stream(
+        &self,
+        upstream: &UpstreamAddr,
+        headers: HeaderMap,
+    ) -> Result<(RecvStream, SendStream<Bytes>), H2ConnectError> {
+        let uri = Uri::builder()
+            .authority(upstream.to_string())
+            .build()
+            .map_err(|_| {
+                H2ConnectError::Negotiation(TcpConnectError::InternalServerError(
+                    "invalid upstream address for h2 CONNECT",
+                ))
+            })?;
+        let mut req = Request::builder()
+            .method(Method::CONNECT)
+            .version(Version::HTTP_2)
+            .uri(uri)
+            .body(())
+            .map_err(|_| {
+                H2ConnectError::Negotiation(TcpConnectError::InternalServerError(
+                    "failed to build h2 CONNECT request",
+                ))
+            })?;
+        *req.headers_mut() = headers;
+
+        let mut send_request = self
+            .send_request
+            .clone()
+            .ready()
+            .await
+            .map_err(H2ConnectError::Connection)?;
+        let (rsp_fut, send_stream) = send_request
+            .send_request(req, false)
+            .map_err(H2ConnectError::Connection)?;
+        let rsp = rsp_fut.await.map_err(|e| {
+            if e.is_reset() {
+                // the stream is refused by the peer
+                H2ConnectError::Negotiation(TcpConnectError::NegotiationRejected(format!(
+                    "h2 CONNECT stream reset by remote proxy: {e}"
+                )))
+            } else {
+                H2ConnectError::Connection(e)
+            }
+        })?;
+
+        let status = rsp.status();
+        if !status.is_success() {
+            let e = match status.as_u16() {
+                504 | 522 | 524 => TcpConnectError::NegotiationPeerTimeout,
+                code => TcpConnectError::NegotiationRejected(format!(
+                    "rejected by remote proxy with response {code} {}",
+                    status.canonical_reason().unwrap_or_default()
+                )),
+            };
+            return Err(H2ConnectError::Negotiation(e));
+        }
+
+        Ok((rsp.into_body(), send_stream))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use http::Response;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+
+    fn new_pool(
+        max_connections: usize,
+        max_streams: usize,
+        idle_timeout: Duration,
+        fallback_duration: Duration,
+    ) -> Arc<H2ConnectPool> {
+        let mut config = Http2ConnectPoolConfig::default();
+        config.set_max_connections(max_connections);
+        config.set_max_streams(max_streams);
+        config.set_idle_timeout(idle_timeout);
+        config.set_fallback_duration(fallback_duration);
+        H2ConnectPool::new(config)
+    }
+
+    fn peer() -> UpstreamAddr {
+        UpstreamAddr::from_host_str_and_port("proxy.example.net", 8080).unwrap()
+    }
+
+    fn expect_new(acquire: H2ConnectPoolAcquire) -> H2ConnectPermit {
+        match acquire {
+            H2ConnectPoolAcquire::New(permit) => permit,
+            H2ConnectPoolAcquire::Reuse(_) => panic!("unexpected reuse"),
+            H2ConnectPoolAcquire::Fallback => panic!("unexpected fallback"),
+        }
+    }
+
+    fn expect_reuse(acquire: H2ConnectPoolAcquire) -> H2ConnectSender {
+        match acquire {
+            H2ConnectPoolAcquire::Reuse(sender) => sender,
+            H2ConnectPoolAcquire::New(_) => panic!("unexpected new"),
+            H2ConnectPoolAcquire::Fallback => panic!("unexpected fallback"),
+        }
+    }
+
+    fn is_fallback(acquire: H2ConnectPoolAcquire) -> bool {
+        matches!(acquire, H2ConnectPoolAcquire::Fallback)
+    }
+
+    fn active_streams(pool: &H2ConnectPool, peer: &UpstreamAddr) -> Vec<usize> {
+        let inner = pool.inner.lock().unwrap();
+        inner
+            .peers
+            .get(peer)
+            .map(|p| p.connections.iter().map(|c| c.active_streams).collect())
+            .unwrap_or_default()
+    }
+
+    /// Run a h2 server which accepts all CONNECT requests
+    fn spawn_h2_server(io: DuplexStream, max_streams: u32) {
+        tokio::spawn(async move {
+            let mut connection = h2::server::Builder::new()
+                .max_concurrent_streams(max_streams)
+                .handshake::<_, Bytes>(io)
+                .await
+                .unwrap();
+            let mut streams = Vec::new();
+            while let Some(Ok((req, mut respond))) = connection.accept().await {
+                assert_eq!(req.method(), Method::CONNECT);
+                let send_stream = respond.send_response(Response::new(()), false).unwrap();
+                streams.push(send_stream);
+            }
+        });
+    }
+
+    async fn wait_peer_max_streams(pool: &H2ConnectPool, peer: &UpstreamAddr, count: usize) {
+        for _ in 0..100 {
+            {
+                let inner = pool.inner.lock().unwrap();
+                let c = &inner.peers.get(peer).unwrap().connections[0];
+                if c.peer_max_streams == count {
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("the peer max streams setting is not received");
+    }
+
+    #[test]
+    fn pending_and_h1_only() {
+        let peer = peer();
+        let pool = new_pool(1, 10, Duration::from_secs(60), Duration::from_secs(60));
+
+        let permit = expect_new(pool.acquire(&peer));
+        // the pending connection counts
+        assert!(is_fallback(pool.acquire(&peer)));
+        drop(permit);
+        let permit = expect_new(pool.acquire(&peer));
+        permit.set_h1_only();
+        assert!(is_fallback(pool.acquire(&peer)));
+        assert!(pool.inner.lock().unwrap().peers[&peer].pending == 0);
+
+        // a different peer is not affected
+        let other = UpstreamAddr::from_host_str_and_port("proxy.example.net", 8443).unwrap();
+        let _permit = expect_new(pool.acquire(&other));
+
+        let pool = new_pool(1, 10, Duration::from_secs(60), Duration::ZERO);
+        let permit = expect_new(pool.acquire(&peer));
+        permit.set_h1_only();
+        // the h1 only mark has been expired
+        let _permit = expect_new(pool.acquire(&peer));
+        assert!(
+            pool.inner.lock().unwrap().peers[&peer]
+                .h1_only_until
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn reuse_and_release() {
+        let peer = peer();
+        let upstream = UpstreamAddr::from_host_str_and_port("www.example.net", 443).unwrap();
+        let pool = new_pool(2, 2, Duration::from_secs(60), Duration::from_secs(60));
+        let (client_io, server_io) = tokio::io::duplex(1 << 16);
+        spawn_h2_server(server_io, 100);
+
+        let permit = expect_new(pool.acquire(&peer));
+        let sender = permit
+            .handshake(client_io, &TcpConnectTaskNotes::default())
+            .await
+            .unwrap();
+        assert_eq!(active_streams(&pool, &peer), [1]);
+        let stream1 = sender
+            .send_connect(&upstream, HeaderMap::new())
+            .await
+            .unwrap();
+        wait_peer_max_streams(&pool, &peer, 100).await;
+
+        let sender = expect_reuse(pool.acquire(&peer));
+        assert_eq!(active_streams(&pool, &peer), [2]);
+        let stream2 = sender
+            .send_connect(&upstream, HeaderMap::new())
+            .await
+            .unwrap();
+
+        // the configured max streams reached
+        let permit = expect_new(pool.acquire(&peer));
+        assert!(is_fallback(pool.acquire(&peer)));
+        drop(permit);
+
+        drop(stream1);
+        assert_eq!(active_streams(&pool, &peer), [1]);
+        let sender = expect_reuse(pool.acquire(&peer));
+        drop(sender);
+        drop(stream2);
+        assert_eq!(active_streams(&pool, &peer), [0]);
+    }
+
+    #[tokio::test]
+    async fn peer_max_streams() {
+        let peer = peer();
+        let pool = new_pool(2, 10, Duration::from_secs(60), Duration::from_secs(60));
+        let (client_io, server_io) = tokio::io::duplex(1 << 16);
+        spawn_h2_server(server_io, 1);
+
+        let permit = expect_new(pool.acquire(&peer));
+        let _sender = permit
+            .handshake(client_io, &TcpConnectTaskNotes::default())
+            .await
+            .unwrap();
+        wait_peer_max_streams(&pool, &peer, 1).await;
+
+        // the connection is full as announced by the peer, so a new one should be used
+        let _permit = expect_new(pool.acquire(&peer));
+        assert!(is_fallback(pool.acquire(&peer)));
+    }
+
+    #[tokio::test]
+    async fn idle_close() {
+        let peer = peer();
+        let pool = new_pool(1, 10, Duration::from_millis(20), Duration::from_secs(60));
+        let (client_io, server_io) = tokio::io::duplex(1 << 16);
+        spawn_h2_server(server_io, 100);
+
+        let permit = expect_new(pool.acquire(&peer));
+        let sender = permit
+            .handshake(client_io, &TcpConnectTaskNotes::default())
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        // not idle as there is an active stream
+        assert_eq!(active_streams(&pool, &peer), [1]);
+
+        drop(sender);
+        for _ in 0..100 {
+            if !pool.inner.lock().unwrap().peers.contains_key(&peer) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("the idle connection is not removed");
+    }
+
+    #[tokio::test]
+    async fn h1_peer() {
+        let peer = peer();
+        let upstream = UpstreamAddr::from_host_str_and_port("www.example.net", 443).unwrap();
+        let pool = new_pool(1, 10, Duration::from_secs(60), Duration::from_secs(60));
+        let (client_io, mut server_io) = tokio::io::duplex(1 << 16);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let _ = server_io.read(&mut buf).await;
+            let _ = server_io
+                .write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")
+                .await;
+        });
+
+        let permit = expect_new(pool.acquire(&peer));
+        let sender = permit
+            .handshake(client_io, &TcpConnectTaskNotes::default())
+            .await
+            .unwrap();
+        let r = sender.send_connect(&upstream, HeaderMap::new()).await;
+        assert!(matches!(r, Err(H2ConnectError::Connection(_))));
+        assert!(is_fallback(pool.acquire(&peer)));
+    }
+}
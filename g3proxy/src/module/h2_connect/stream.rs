@@ -0,0 +1,171 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use h2::{RecvStream, SendStream};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use g3_h2::{H2StreamReader, H2StreamWriter};
+use g3_io_ext::AsyncStream;
+
+use super::pool::H2StreamGuard;
+
+pub(crate) struct H2ConnectReader {
+    inner: H2StreamReader,
+    _guard: Arc<H2StreamGuard>,
+}
+
+impl AsyncRead for H2ConnectReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+pub(crate) struct H2ConnectWriter {
+    inner: H2StreamWriter,
+    _guard: Arc<H2StreamGuard>,
+}
+
+impl AsyncWrite for H2ConnectWriter {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// A CONNECT tunnel carried by a HTTP/2 stream
+pub(crate) struct H2ConnectStream {
+    reader: H2ConnectReader,
+    writer: H2ConnectWriter,
+}
+
+impl H2ConnectStream {
+    pub(super) fn new(
+        recv_stream: RecvStream,
+        send_stream: SendStream<Bytes>,
+        guard: H2StreamGuard,
+    ) -> Self {
+        let guard = Arc::new(guard);
+        H2ConnectStream {
+            reader: H2ConnectReader {
+                inner: H2StreamReader::new(recv_stream),
+                _guard: guard.clone(),
+            },
+            writer: H2ConnectWriter {
+                inner: H2StreamWriter::new(send_stream),
+                _guard: guard,
+            },
+        }
+    }
+}
+
+impl AsyncRead for H2ConnectStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.reader).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for H2ConnectStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.writer).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.writer).poll_shutdown(cx)
+    }
+}
+
+impl AsyncStream for H2ConnectStream {
+    type R = H2ConnectReader;
+    type W = H2ConnectWriter;
+
+    fn into_split(self) -> (Self::R, Self::W) {
+        (self.reader, self.writer)
+    }
+}
+
+/// The tunnel established by CONNECT over HTTP/1.1 or HTTP/2
+pub(crate) enum HttpConnectStream<S> {
+    Http1(S),
+    Http2(H2ConnectStream),
+}
+
+impl<S> AsyncRead for HttpConnectStream<S>
+where
+    S: AsyncRead + Unpin,
+{
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            HttpConnectStream::Http1(s) => Pin::new(s).poll_read(cx, buf),
+            HttpConnectStream::Http2(s) => Pin::new(s).poll_read(cx, buf),
+        }
+    }
+}
+
+impl<S> AsyncWrite for HttpConnectStream<S>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            HttpConnectStream::Http1(s) => Pin::new(s).poll_write(cx, buf),
+            HttpConnectStream::Http2(s) => Pin::new(s).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            HttpConnectStream::Http1(s) => Pin::new(s).poll_flush(cx),
+            HttpConnectStream::Http2(s) => Pin::new(s).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            HttpConnectStream::Http1(s) => Pin::new(s).poll_shutdown(cx),
+            HttpConnectStream::Http2(s) => Pin::new(s).poll_shutdown(cx),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// Collect the body data while it is being read, up to the max size
+pub(crate) struct HttpCacheBodyCollector<R> {
+    inner: R,
+    buf: Option<Vec<u8>>,
+    max_size: usize,
+}
+
+impl<R> HttpCacheBodyCollector<R> {
+    pub(crate) fn new(inner: R, max_size: Option<usize>) -> Self {
+        match max_size {
+            Some(max_size) => HttpCacheBodyCollector {
+                inner,
+                buf: Some(Vec::new()),
+                max_size,
+            },
+            None => HttpCacheBodyCollector {
+                inner,
+                buf: None,
+                max_size: 0,
+            },
+        }
+    }
+
+    /// Get the collected data, None will be returned if oversize
+    pub(crate) fn take_collected(&mut self) -> Option<Vec<u8>> {
+        self.buf.take()
+    }
+}
+
+impl<R> AsyncRead for HttpCacheBodyCollector<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+
+        let max_size = self.max_size;
+        if let Some(collected) = &mut self.buf {
+            let data = &buf.filled()[filled..];
+            if collected.len() + data.len() > max_size {
+                self.buf = None;
+            } else {
+                collected.extend_from_slice(data);
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,380 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use bytes::Bytes;
+use http::{HeaderName, Method, header};
+use log::debug;
+use tokio::io::AsyncReadExt;
+
+use g3_http::HttpBodyDecodeReader;
+use g3_http::HttpBodyType;
+use g3_http::cache::{
+    HttpCacheFreshState, HttpCacheFreshness, RequestCacheControl, ResponseCacheControl,
+};
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::server::HttpProxyClientRequest;
+use g3_types::net::{HttpHeaderMap, UpstreamAddr};
+
+use super::{HttpCache, HttpCacheEntry};
+
+pub(crate) enum HttpCacheLookup {
+    /// the stored response can be served without validation
+    Fresh(Arc<HttpCacheEntry>, Bytes),
+    /// the stored response should be validated by the origin server
+    Stale,
+    Miss,
+    /// the request can not be served by the cache
+    Bypass,
+}
+
+fn build_key(
+    escaper: &str,
+    upstream: &UpstreamAddr,
+    is_https: bool,
+    req: &HttpProxyClientRequest,
+    rule_headers: &[HeaderName],
+) -> String {
+    let scheme = if is_https { "https" } else { "http" };
+    let path = req.uri.path_and_query().map(|v| v.as_str()).unwrap_or("/");
+    // stored responses are not shared between escapers
+    let mut key = format!("{escaper} {scheme}://{upstream}{path}");
+    // the request headers set by header rules may be different for each user,
+    // so the rendered values should also be part of the key
+    for name in rule_headers {
+        let _ = write!(key, "\n{name}:");
+        for value in req.end_to_end_headers.get_all(name) {
+            let _ = write!(key, "\n\t{}", value.to_str());
+        }
+    }
+    key
+}
+
+pub(crate) struct HttpCacheRequestContext {
+    cache: Arc<HttpCache>,
+    key: Arc<str>,
+    cacheable: bool,
+    req_cc: RequestCacheControl,
+    request_time: SystemTime,
+    response_time: SystemTime,
+    response_headers: Option<HttpHeaderMap>,
+    validating: Option<(Arc<[HeaderName]>, Arc<HttpCacheEntry>)>,
+}
+
+impl HttpCacheRequestContext {
+    pub(crate) fn new(
+        cache: &Arc<HttpCache>,
+        escaper: &str,
+        upstream: &UpstreamAddr,
+        is_https: bool,
+        req: &HttpProxyClientRequest,
+        rule_headers: &[HeaderName],
+    ) -> Self {
+        let key = build_key(escaper, upstream, is_https, req, rule_headers);
+
+        let headers = &req.end_to_end_headers;
+        let cacheable = req.body_type().is_none()
+            && g3_http::cache::request_cacheable(&req.method, headers)
+            && !headers.contains_key(header::IF_MATCH)
+            && !headers.contains_key(header::IF_UNMODIFIED_SINCE)
+            && !headers.contains_key(header::IF_RANGE);
+        let req_cc = if cacheable {
+            RequestCacheControl::parse(headers)
+        } else {
+            RequestCacheControl::default()
+        };
+
+        let now = SystemTime::now();
+        HttpCacheRequestContext {
+            cache: cache.clone(),
+            key: Arc::from(key),
+            cacheable,
+            req_cc,
+            request_time: now,
+            response_time: now,
+            response_headers: None,
+            validating: None,
+        }
+    }
+
+    pub(crate) async fn lookup(&mut self, req: &HttpProxyClientRequest) -> HttpCacheLookup {
+        if !self.cacheable {
+            return HttpCacheLookup::Bypass;
+        }
+
+        let Some((vary_names, entry)) = self.cache.get(&self.key, &req.end_to_end_headers) else {
+            return HttpCacheLookup::Miss;
+        };
+        match entry.freshness.check(SystemTime::now(), &self.req_cc) {
+            HttpCacheFreshState::Fresh | HttpCacheFreshState::StaleAllowed => {
+                if req.method == Method::HEAD {
+                    return HttpCacheLookup::Fresh(entry, Bytes::new());
+                }
+                match entry.load_body().await {
+                    Ok(body) => HttpCacheLookup::Fresh(entry, body),
+                    Err(e) => {
+                        debug!("failed to load http cache body: {e}");
+                        self.cache.invalidate(&self.key);
+                        HttpCacheLookup::Miss
+                    }
+                }
+            }
+            HttpCacheFreshState::Stale => {
+                if req.method == Method::GET
+                    && entry.has_validator()
+                    && !g3_http::cache::request_is_conditional(&req.end_to_end_headers)
+                {
+                    self.validating = Some((vary_names, entry));
+                    HttpCacheLookup::Stale
+                } else {
+                    HttpCacheLookup::Miss
+                }
+            }
+        }
+    }
+
+    #[inline]
+    pub(crate) fn only_if_cached(&self) -> bool {
+        self.req_cc.only_if_cached
+    }
+
+    #[inline]
+    pub(crate) fn is_validating(&self) -> bool {
+        self.validating.is_some()
+    }
+
+    /// Get the conditional request that should be sent to the origin server
+    pub(crate) fn validation_request(
+        &self,
+        req: &HttpProxyClientRequest,
+    ) -> Option<HttpProxyClientRequest> {
+        self.validating
+            .as_ref()
+            .map(|(_, entry)| req.build_cache_validation(entry.etag(), entry.last_modified()))
+    }
+
+    /// Update the stored response if the origin server responds with 304
+    pub(crate) async fn revalidate(
+        &mut self,
+        rsp: &HttpForwardRemoteResponse,
+    ) -> Option<Arc<HttpCacheEntry>> {
+        if rsp.code != 304 {
+            return None;
+        }
+        let (vary_names, entry) = self.validating.take()?;
+
+        let rsp_time = SystemTime::now();
+        let mut headers = entry.headers.clone();
+        g3_http::cache::update_stored_headers(&mut headers, &rsp.end_to_end_headers);
+        let rsp_cc = ResponseCacheControl::parse(&headers);
+        let freshness = HttpCacheFreshness::new(
+            entry.code,
+            &headers,
+            &rsp_cc,
+            self.request_time,
+            rsp_time,
+            self.cache.config().heuristic_max_lifetime,
+        );
+        let new_entry = entry.revalidate(&rsp.end_to_end_headers, freshness);
+        if rsp_cc.no_store {
+            self.cache.invalidate(&self.key);
+            return Some(Arc::new(new_entry));
+        }
+
+        let new_entry = Arc::new(new_entry);
+        self.cache
+            .update(self.key.clone(), vary_names, Arc::clone(&new_entry))
+            .await;
+        Some(new_entry)
+    }
+
+    /// Invalidate the stored responses for unsafe requests, see RFC 9111 Section 4.4
+    pub(crate) fn invalidate_for_unsafe(&self, method: &Method, rsp_code: u16) {
+        if rsp_code >= 400 {
+            return;
+        }
+        match *method {
+            Method::GET | Method::HEAD | Method::OPTIONS | Method::TRACE => {}
+            _ => self.cache.invalidate(&self.key),
+        }
+    }
+
+    /// Check if the response should be stored, the max body size will be returned if so
+    pub(crate) fn check_storable(
+        &mut self,
+        req: &HttpProxyClientRequest,
+        rsp: &HttpForwardRemoteResponse,
+    ) -> Option<usize> {
+        if !self.cacheable || self.req_cc.no_store {
+            return None;
+        }
+        let rsp_cc = ResponseCacheControl::parse(&rsp.end_to_end_headers);
+        if !g3_http::cache::response_storable(
+            &req.method,
+            &req.end_to_end_headers,
+            rsp.code,
+            &rsp.end_to_end_headers,
+            &rsp_cc,
+        ) {
+            if req.method == Method::GET && rsp.code != 304 {
+                // the stored responses are outdated
+                self.cache.invalidate(&self.key);
+            }
+            return None;
+        }
+        self.response_time = SystemTime::now();
+        // the headers may be changed before sent to client
+        self.response_headers = Some(rsp.end_to_end_headers.clone());
+        Some(self.cache.config().max_object_size)
+    }
+
+    /// Store the response after all the body data have been received
+    pub(crate) async fn store(
+        self,
+        req: &HttpProxyClientRequest,
+        rsp: &HttpForwardRemoteResponse,
+        body_type: Option<HttpBodyType>,
+        data: Vec<u8>,
+        body_line_max_len: usize,
+    ) -> bool {
+        let body = match body_type {
+            Some(HttpBodyType::Chunked) => {
+                let mut reader = data.as_slice();
+                let mut decoder = HttpBodyDecodeReader::new_chunked(&mut reader, body_line_max_len);
+                let mut decoded = Vec::with_capacity(data.len());
+                if decoder.read_to_end(&mut decoded).await.is_err() {
+                    return false;
+                }
+                Bytes::from(decoded)
+            }
+            _ => Bytes::from(data),
+        };
+
+        let Some(mut headers) = self.response_headers else {
+            return false;
+        };
+        let Some(vary_names) = g3_http::cache::vary_names(&headers) else {
+            return false;
+        };
+        let vary_values = g3_http::cache::vary_values(&vary_names, &req.end_to_end_headers);
+
+        headers.remove(header::CONTENT_LENGTH);
+        let rsp_cc = ResponseCacheControl::parse(&headers);
+        let freshness = HttpCacheFreshness::new(
+            rsp.code,
+            &headers,
+            &rsp_cc,
+            self.request_time,
+            self.response_time,
+            self.cache.config().heuristic_max_lifetime,
+        );
+        let entry = HttpCacheEntry::new(
+            rsp.code,
+            rsp.reason.clone(),
+            headers,
+            freshness,
+            vary_values,
+            body,
+        );
+        self.cache
+            .insert(self.key.clone(), Arc::from(vary_names), entry)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::time::Duration;
+
+    use g3_daemon::server::ClientConnectionInfo;
+    use http::Version;
+    use tokio::io::BufReader;
+    use yaml_rust::YamlLoader;
+
+    use crate::config::http_header::HttpHeaderRules;
+    use crate::module::http_header::{self, HttpHeaderRuleContext};
+    use crate::serve::ServerTaskNotes;
+
+    async fn parse_request(content: &'static [u8]) -> HttpProxyClientRequest {
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut version = Version::HTTP_11;
+        HttpProxyClientRequest::parse_basic(&mut buf_stream, 4096, &mut version)
+            .await
+            .unwrap()
+    }
+
+    fn tenant_rules(tenant: &str) -> HttpHeaderRules {
+        let doc = format!(
+            r#"
+                request:
+                  - name: X-Tenant-Id
+                    action: set
+                    value: {tenant}
+            "#
+        );
+        let v = YamlLoader::load_from_str(&doc).unwrap().pop().unwrap();
+        HttpHeaderRules::parse_yaml(&v).unwrap()
+    }
+
+    #[tokio::test]
+    async fn key_without_rules() {
+        let upstream = UpstreamAddr::from_str("www.example.com:80").unwrap();
+        let req =
+            parse_request(b"GET http://www.example.com/index.html?a=b HTTP/1.1\r\n\r\n").await;
+        let key = build_key("default", &upstream, false, &req, &[]);
+        assert_eq!(key, "default http://www.example.com:80/index.html?a=b");
+
+        let key = build_key("direct", &upstream, true, &req, &[]);
+        assert_eq!(key, "direct https://www.example.com:80/index.html?a=b");
+    }
+
+    #[tokio::test]
+    async fn key_with_user_rules() {
+        let upstream = UpstreamAddr::from_str("www.example.com:80").unwrap();
+        let client_addr = "127.0.0.1:10000".parse().unwrap();
+        let server_addr = "127.0.0.1:8080".parse().unwrap();
+        let task_notes = ServerTaskNotes::new(
+            ClientConnectionInfo::new(client_addr, server_addr),
+            None,
+            Duration::ZERO,
+        );
+        let ctx = HttpHeaderRuleContext {
+            task_notes: &task_notes,
+            escaper: "default",
+            upstream: &upstream,
+            path: "/",
+        };
+
+        let mut keys = Vec::new();
+        for tenant in ["tenant-a", "tenant-b"] {
+            let rules = tenant_rules(tenant);
+            let mut req = parse_request(b"GET http://www.example.com/ HTTP/1.1\r\n\r\n").await;
+            http_header::apply_rules(&rules.request, &mut req.end_to_end_headers, &ctx);
+            let names = http_header::rule_header_names(&rules.request);
+            keys.push(build_key("default", &upstream, false, &req, &names));
+        }
+        assert_ne!(keys[0], keys[1]);
+        assert!(keys[0].contains("tenant-a"));
+        assert!(keys[1].contains("tenant-b"));
+
+        // request without user rules should not share entries with rewritten requests
+        let req =
+            parse_request(b"GET http://www.example.com/ HTTP/1.1\r\nX-Tenant-Id: tenant-a\r\n\r\n")
+                .await;
+        let key = build_key("default", &upstream, false, &req, &[]);
+        assert_ne!(key, keys[0]);
+
+        // the same rendered values share the same entry
+        let names = http_header::rule_header_names(&tenant_rules("tenant-a").request);
+        let key = build_key("default", &upstream, false, &req, &names);
+        assert_eq!(key, keys[0]);
+    }
+}
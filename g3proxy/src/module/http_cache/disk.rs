@@ -0,0 +1,237 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
+
+use anyhow::anyhow;
+use http::HeaderName;
+use log::{debug, warn};
+
+use g3_types::net::HttpHeaderMap;
+
+use super::{HttpCacheDiskDir, HttpCacheDiskFile, HttpCacheEntry, HttpCacheTier};
+use crate::config::server::HttpCacheDiskConfig;
+
+const FILE_EXTENSION: &str = "cache";
+
+/// All cache instances of this process use sub directories with this prefix
+static INSTANCE_DIR_PREFIX: LazyLock<String> = LazyLock::new(|| {
+    let start = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+    format!("{start:x}-{:x}-", std::process::id())
+});
+static INSTANCE_ID: AtomicU64 = AtomicU64::new(0);
+
+pub(super) struct HttpCacheDisk {
+    dir: Arc<HttpCacheDiskDir>,
+    next_id: AtomicU64,
+    index: Mutex<HttpCacheTier>,
+}
+
+impl HttpCacheDisk {
+    pub(super) fn new(config: &HttpCacheDiskConfig, max_variants: usize) -> anyhow::Result<Self> {
+        // the index is not persistent, and the old cache instance may still be in use after
+        // reload, so always use a new sub directory for each cache instance
+        let id = INSTANCE_ID.fetch_add(1, Ordering::Relaxed);
+        let path = config
+            .path
+            .join(format!("{}{id:x}", INSTANCE_DIR_PREFIX.as_str()));
+        std::fs::create_dir_all(&path)
+            .map_err(|e| anyhow!("failed to create directory {}: {e}", path.display()))?;
+        let base = config.path.clone();
+        tokio::task::spawn_blocking(move || clean_stale_instances(&base));
+
+        Ok(HttpCacheDisk {
+            dir: Arc::new(HttpCacheDiskDir::new(path)),
+            next_id: AtomicU64::new(0),
+            index: Mutex::new(HttpCacheTier::new(config.size, max_variants)),
+        })
+    }
+
+    pub(super) fn get(
+        &self,
+        key: &str,
+        req_headers: &HttpHeaderMap,
+    ) -> Option<(Arc<[HeaderName]>, Arc<HttpCacheEntry>)> {
+        let mut index = self.index.lock().unwrap();
+        index.get(key, req_headers)
+    }
+
+    pub(super) async fn store(
+        &self,
+        key: Arc<str>,
+        vary_names: Arc<[HeaderName]>,
+        entry: &HttpCacheEntry,
+    ) -> io::Result<bool> {
+        let Some(body) = entry.memory_body() else {
+            return Ok(false);
+        };
+        if !self.index.lock().unwrap().can_store(entry) {
+            return Ok(false);
+        }
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let path = self.dir.path().join(format!("{id:x}.{FILE_EXTENSION}"));
+        let tmp_path = self.dir.path().join(format!("{id:x}.tmp"));
+        tokio::fs::write(&tmp_path, body).await?;
+        if let Err(e) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(e);
+        }
+
+        let entry = entry.move_to_disk(HttpCacheDiskFile::new(self.dir.clone(), path));
+        let mut index = self.index.lock().unwrap();
+        let evicted = index.insert(key, vary_names, Arc::new(entry));
+        drop(index);
+        // the files will be deleted when the last reference dropped
+        Ok(evicted.is_some())
+    }
+
+    pub(super) fn update(
+        &self,
+        key: Arc<str>,
+        vary_names: Arc<[HeaderName]>,
+        entry: Arc<HttpCacheEntry>,
+    ) {
+        let mut index = self.index.lock().unwrap();
+        let evicted = match index.insert(key.clone(), vary_names, entry.clone()) {
+            Some(evicted) => evicted,
+            None => {
+                // the updated headers may be too large to be stored
+                let old = index.remove_variant(&key, &entry.vary_values);
+                drop(index);
+                drop(old);
+                return;
+            }
+        };
+        drop(index);
+        drop(evicted);
+    }
+
+    pub(super) fn remove_variant(&self, key: &str, vary_values: &[Option<String>]) {
+        let mut index = self.index.lock().unwrap();
+        let old = index.remove_variant(key, vary_values);
+        drop(index);
+        drop(old);
+    }
+
+    pub(super) fn remove(&self, key: &str) {
+        let mut index = self.index.lock().unwrap();
+        let old = index.remove(key);
+        drop(index);
+        drop(old);
+    }
+}
+
+/// Remove the cache files left by previous processes
+fn clean_stale_instances(base: &Path) {
+    let dir = match std::fs::read_dir(base) {
+        Ok(dir) => dir,
+        Err(e) => {
+            warn!(
+                "failed to read http cache directory {}: {e}",
+                base.display()
+            );
+            return;
+        }
+    };
+    for entry in dir.flatten() {
+        let name = entry.file_name();
+        if name
+            .to_str()
+            .is_some_and(|s| s.starts_with(INSTANCE_DIR_PREFIX.as_str()))
+        {
+            continue;
+        }
+        let path = entry.path();
+        let r = if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        if let Err(e) = r {
+            debug!("failed to remove stale http cache {}: {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::module::http_cache::entry::tests::new_entry;
+
+    #[tokio::test]
+    async fn instance_dir() {
+        let base = std::env::temp_dir().join(format!("g3proxy-http-cache-{}", std::process::id()));
+        std::fs::create_dir_all(base.join("stale-dir")).unwrap();
+        std::fs::write(base.join("stale.cache"), b"stale").unwrap();
+
+        let config = HttpCacheDiskConfig {
+            path: base.clone(),
+            size: 1 << 20,
+        };
+        let disk = HttpCacheDisk::new(&config, 4).unwrap();
+        let dir = disk.dir.path().to_path_buf();
+        assert!(dir.starts_with(&base));
+        assert!(dir.is_dir());
+
+        // the stale files are removed in background
+        for _ in 0..100 {
+            if !base.join("stale-dir").exists() && !base.join("stale.cache").exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(!base.join("stale-dir").exists());
+        assert!(!base.join("stale.cache").exists());
+        assert!(dir.is_dir());
+
+        let entry = new_entry(Vec::new(), b"hello");
+        let stored = disk
+            .store(Arc::from("a"), Arc::from([]), &entry)
+            .await
+            .unwrap();
+        assert!(stored);
+        let (_, entry) = disk.get("a", &HttpHeaderMap::default()).unwrap();
+        assert_eq!(entry.load_body().await.unwrap().as_ref(), b"hello");
+
+        // the directory is kept until all entries dropped
+        drop(disk);
+        assert!(dir.is_dir());
+        drop(entry);
+        assert!(!dir.exists());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let base =
+            std::env::temp_dir().join(format!("g3proxy-http-cache-large-{}", std::process::id()));
+        let config = HttpCacheDiskConfig {
+            path: base.clone(),
+            size: 16,
+        };
+        let disk = HttpCacheDisk::new(&config, 4).unwrap();
+        let entry = new_entry(Vec::new(), b"hello");
+        let stored = disk
+            .store(Arc::from("a"), Arc::from([]), &entry)
+            .await
+            .unwrap();
+        assert!(!stored);
+        assert!(disk.get("a", &HttpHeaderMap::default()).is_none());
+        drop(disk);
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+}
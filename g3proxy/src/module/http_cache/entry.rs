@@ -0,0 +1,314 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use bytes::{BufMut, Bytes};
+use http::{Version, header};
+
+use g3_http::cache::HttpCacheFreshness;
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+/// The cache directory of a cache instance, which will be removed after all files dropped
+pub(crate) struct HttpCacheDiskDir {
+    path: PathBuf,
+}
+
+impl HttpCacheDiskDir {
+    pub(super) fn new(path: PathBuf) -> Self {
+        HttpCacheDiskDir { path }
+    }
+
+    #[inline]
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for HttpCacheDiskDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+pub(crate) struct HttpCacheDiskFile {
+    // keep the directory alive
+    _dir: Arc<HttpCacheDiskDir>,
+    path: PathBuf,
+}
+
+impl HttpCacheDiskFile {
+    pub(super) fn new(dir: Arc<HttpCacheDiskDir>, path: PathBuf) -> Self {
+        HttpCacheDiskFile { _dir: dir, path }
+    }
+}
+
+impl Drop for HttpCacheDiskFile {
+    fn drop(&mut self) {
+        // the file will only be dropped after all readers have finished
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+enum HttpCacheBody {
+    Memory(Bytes),
+    Disk(Arc<HttpCacheDiskFile>),
+}
+
+pub(crate) struct HttpCacheEntry {
+    pub(crate) code: u16,
+    reason: String,
+    pub(crate) headers: HttpHeaderMap,
+    pub(crate) freshness: HttpCacheFreshness,
+    pub(crate) vary_values: Vec<Option<String>>,
+    header_size: usize,
+    body: HttpCacheBody,
+    body_len: usize,
+}
+
+fn header_size(reason: &str, headers: &HttpHeaderMap) -> usize {
+    let mut size = reason.len() + 16;
+    headers.for_each(|name, value| size += name.as_str().len() + value.as_bytes().len() + 4);
+    size
+}
+
+impl HttpCacheEntry {
+    pub(crate) fn new(
+        code: u16,
+        reason: String,
+        mut headers: HttpHeaderMap,
+        freshness: HttpCacheFreshness,
+        vary_values: Vec<Option<String>>,
+        body: Bytes,
+    ) -> Self {
+        // the body is always decoded before stored
+        let content_length =
+            unsafe { HttpHeaderValue::from_string_unchecked(body.len().to_string()) };
+        headers.insert(header::CONTENT_LENGTH, content_length);
+        let header_size = header_size(&reason, &headers);
+        HttpCacheEntry {
+            code,
+            reason,
+            headers,
+            freshness,
+            vary_values,
+            header_size,
+            body_len: body.len(),
+            body: HttpCacheBody::Memory(body),
+        }
+    }
+
+    pub(super) fn move_to_disk(&self, file: HttpCacheDiskFile) -> Self {
+        HttpCacheEntry {
+            code: self.code,
+            reason: self.reason.clone(),
+            headers: self.headers.clone(),
+            freshness: self.freshness.clone(),
+            vary_values: self.vary_values.clone(),
+            header_size: self.header_size,
+            body: HttpCacheBody::Disk(Arc::new(file)),
+            body_len: self.body_len,
+        }
+    }
+
+    /// Create a new entry with the headers updated by a 304 response
+    pub(crate) fn revalidate(
+        &self,
+        rsp_headers: &HttpHeaderMap,
+        freshness: HttpCacheFreshness,
+    ) -> Self {
+        let mut headers = self.headers.clone();
+        g3_http::cache::update_stored_headers(&mut headers, rsp_headers);
+        let header_size = header_size(&self.reason, &headers);
+        let body = match &self.body {
+            HttpCacheBody::Memory(b) => HttpCacheBody::Memory(b.clone()),
+            HttpCacheBody::Disk(f) => HttpCacheBody::Disk(f.clone()),
+        };
+        HttpCacheEntry {
+            code: self.code,
+            reason: self.reason.clone(),
+            headers,
+            freshness,
+            vary_values: self.vary_values.clone(),
+            header_size,
+            body,
+            body_len: self.body_len,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn size(&self) -> usize {
+        self.header_size + self.body_len
+    }
+
+    #[inline]
+    pub(crate) fn body_len(&self) -> usize {
+        self.body_len
+    }
+
+    pub(super) fn memory_body(&self) -> Option<&Bytes> {
+        match &self.body {
+            HttpCacheBody::Memory(b) => Some(b),
+            HttpCacheBody::Disk(_) => None,
+        }
+    }
+
+    pub(crate) async fn load_body(&self) -> io::Result<Bytes> {
+        match &self.body {
+            HttpCacheBody::Memory(b) => Ok(b.clone()),
+            HttpCacheBody::Disk(f) => {
+                let data = tokio::fs::read(&f.path).await?;
+                if data.len() != self.body_len {
+                    return Err(io::Error::other("cache file has been changed"));
+                }
+                Ok(Bytes::from(data))
+            }
+        }
+    }
+
+    pub(crate) fn etag(&self) -> Option<&HttpHeaderValue> {
+        self.headers.get(header::ETAG)
+    }
+
+    pub(crate) fn last_modified(&self) -> Option<&HttpHeaderValue> {
+        self.headers.get(header::LAST_MODIFIED)
+    }
+
+    #[inline]
+    pub(crate) fn has_validator(&self) -> bool {
+        self.etag().is_some() || self.last_modified().is_some()
+    }
+
+    /// Serialize the response header that should be sent to client,
+    /// the headers should be the stored ones or the ones modified from them,
+    /// the version should be the one of the client request
+    pub(crate) fn serialize_header(
+        &self,
+        version: Version,
+        headers: &HttpHeaderMap,
+        now: SystemTime,
+        keep_alive: bool,
+    ) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.header_size + 64);
+        let _ = write!(buf, "{version:?} {} {}\r\n", self.code, self.reason);
+        headers.for_each(|name, value| {
+            if name == header::AGE {
+                return;
+            }
+            value.write_to_buf(name, &mut buf);
+        });
+        let age = self.freshness.current_age(now).as_secs();
+        let _ = write!(buf, "Age: {age}\r\n");
+        if keep_alive {
+            buf.put_slice(b"Connection: keep-alive\r\n");
+        } else {
+            buf.put_slice(b"Connection: close\r\n");
+        }
+        buf.put_slice(b"\r\n");
+        buf
+    }
+
+    /// Serialize a 304 response header for conditional requests from client
+    pub(crate) fn serialize_not_modified(
+        &self,
+        version: Version,
+        headers: &HttpHeaderMap,
+        now: SystemTime,
+        keep_alive: bool,
+    ) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(256);
+        let _ = write!(buf, "{version:?} 304 Not Modified\r\n");
+        // see RFC 9110 Section 15.4.5
+        for name in [
+            header::CACHE_CONTROL,
+            header::CONTENT_LOCATION,
+            header::DATE,
+            header::ETAG,
+            header::EXPIRES,
+            header::VARY,
+        ] {
+            for value in headers.get_all(&name) {
+                value.write_to_buf(&name, &mut buf);
+            }
+        }
+        let age = self.freshness.current_age(now).as_secs();
+        let _ = write!(buf, "Age: {age}\r\n");
+        if keep_alive {
+            buf.put_slice(b"Connection: keep-alive\r\n");
+        } else {
+            buf.put_slice(b"Connection: close\r\n");
+        }
+        buf.put_slice(b"\r\n");
+        buf
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use g3_http::cache::ResponseCacheControl;
+
+    pub(crate) fn new_entry(
+        vary_values: Vec<Option<String>>,
+        body: &'static [u8],
+    ) -> HttpCacheEntry {
+        let mut headers = HttpHeaderMap::default();
+        headers.insert(
+            header::CACHE_CONTROL,
+            HttpHeaderValue::from_static("max-age=60"),
+        );
+        let rsp_cc = ResponseCacheControl::parse(&headers);
+        let now = SystemTime::now();
+        let freshness =
+            HttpCacheFreshness::new(200, &headers, &rsp_cc, now, now, Duration::from_secs(60));
+        HttpCacheEntry::new(
+            200,
+            "OK".to_string(),
+            headers,
+            freshness,
+            vary_values,
+            Bytes::from_static(body),
+        )
+    }
+
+    #[test]
+    fn size() {
+        let entry = new_entry(Vec::new(), b"hello");
+        assert_eq!(entry.body_len(), 5);
+        assert!(entry.size() > entry.body_len());
+        assert_eq!(
+            entry.headers.get(header::CONTENT_LENGTH).unwrap().to_str(),
+            "5"
+        );
+    }
+
+    #[test]
+    fn serialize_version() {
+        let entry = new_entry(Vec::new(), b"hello");
+        let now = SystemTime::now();
+
+        let buf = entry.serialize_header(Version::HTTP_11, &entry.headers, now, true);
+        let s = std::str::from_utf8(&buf).unwrap();
+        assert!(s.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(s.contains("Connection: keep-alive\r\n"));
+        assert!(s.ends_with("\r\n\r\n"));
+
+        let buf = entry.serialize_header(Version::HTTP_10, &entry.headers, now, false);
+        let s = std::str::from_utf8(&buf).unwrap();
+        assert!(s.starts_with("HTTP/1.0 200 OK\r\n"));
+        assert!(s.contains("Connection: close\r\n"));
+
+        let buf = entry.serialize_not_modified(Version::HTTP_10, &entry.headers, now, false);
+        let s = std::str::from_utf8(&buf).unwrap();
+        assert!(s.starts_with("HTTP/1.0 304 Not Modified\r\n"));
+        assert!(s.contains("Age: "));
+        assert!(!s.contains("Content-Length"));
+    }
+}
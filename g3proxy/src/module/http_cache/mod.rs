@@ -0,0 +1,42 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod entry;
+pub(crate) use entry::HttpCacheEntry;
+use entry::{HttpCacheDiskDir, HttpCacheDiskFile};
+
+mod tier;
+use tier::HttpCacheTier;
+
+mod disk;
+use disk::HttpCacheDisk;
+
+mod store;
+pub(crate) use store::HttpCache;
+
+mod collect;
+pub(crate) use collect::HttpCacheBodyCollector;
+
+mod context;
+pub(crate) use context::{HttpCacheLookup, HttpCacheRequestContext};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HttpCacheStatus {
+    Hit,
+    Miss,
+    Revalidated,
+    Expired,
+}
+
+impl HttpCacheStatus {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            HttpCacheStatus::Hit => "hit",
+            HttpCacheStatus::Miss => "miss",
+            HttpCacheStatus::Revalidated => "revalidated",
+            HttpCacheStatus::Expired => "expired",
+        }
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use http::HeaderName;
+use log::debug;
+
+use g3_types::net::HttpHeaderMap;
+
+use super::{HttpCacheDisk, HttpCacheEntry, HttpCacheTier};
+use crate::config::server::HttpCacheConfig;
+
+pub(crate) struct HttpCache {
+    config: Arc<HttpCacheConfig>,
+    memory: Mutex<HttpCacheTier>,
+    disk: Option<HttpCacheDisk>,
+}
+
+impl HttpCache {
+    pub(crate) fn new(config: &Arc<HttpCacheConfig>) -> anyhow::Result<Self> {
+        let disk = match &config.disk {
+            Some(disk_config) => Some(HttpCacheDisk::new(disk_config, config.max_variants)?),
+            None => None,
+        };
+        Ok(HttpCache {
+            config: config.clone(),
+            memory: Mutex::new(HttpCacheTier::new(config.memory_size, config.max_variants)),
+            disk,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn config(&self) -> &Arc<HttpCacheConfig> {
+        &self.config
+    }
+
+    pub(crate) fn get(
+        &self,
+        key: &str,
+        req_headers: &HttpHeaderMap,
+    ) -> Option<(Arc<[HeaderName]>, Arc<HttpCacheEntry>)> {
+        let mut memory = self.memory.lock().unwrap();
+        if let Some(v) = memory.get(key, req_headers) {
+            return Some(v);
+        }
+        drop(memory);
+
+        self.disk
+            .as_ref()
+            .and_then(|disk| disk.get(key, req_headers))
+    }
+
+    /// Store a new response, return false if it's not stored
+    pub(crate) async fn insert(
+        &self,
+        key: Arc<str>,
+        vary_names: Arc<[HeaderName]>,
+        entry: HttpCacheEntry,
+    ) -> bool {
+        if entry.body_len() > self.config.max_object_size {
+            return false;
+        }
+
+        if entry.body_len() > self.config.memory_max_object_size {
+            let Some(disk) = &self.disk else {
+                return false;
+            };
+            self.memory_remove_variant(&key, &entry.vary_values);
+            return match disk.store(key, vary_names, &entry).await {
+                Ok(stored) => stored,
+                Err(e) => {
+                    debug!("failed to store http cache entry to disk: {e}");
+                    false
+                }
+            };
+        }
+
+        if let Some(disk) = &self.disk {
+            disk.remove_variant(&key, &entry.vary_values);
+        }
+        self.memory_insert(key, vary_names, Arc::new(entry)).await
+    }
+
+    /// Insert to the memory tier, return false if it's not stored
+    async fn memory_insert(
+        &self,
+        key: Arc<str>,
+        vary_names: Arc<[HeaderName]>,
+        entry: Arc<HttpCacheEntry>,
+    ) -> bool {
+        let evicted = {
+            let mut memory = self.memory.lock().unwrap();
+            memory.insert(key, vary_names, entry)
+        };
+        let Some(evicted) = evicted else {
+            return false;
+        };
+
+        // move the evicted entries to disk
+        let Some(disk) = &self.disk else {
+            return true;
+        };
+        for (key, vary_names, entry) in evicted {
+            if let Err(e) = disk.store(key, vary_names, &entry).await {
+                debug!("failed to store http cache entry to disk: {e}");
+                break;
+            }
+        }
+        true
+    }
+
+    /// Update the entry after revalidation, the body location is kept unchanged
+    pub(crate) async fn update(
+        &self,
+        key: Arc<str>,
+        vary_names: Arc<[HeaderName]>,
+        entry: Arc<HttpCacheEntry>,
+    ) {
+        if entry.memory_body().is_some() {
+            if !self
+                .memory_insert(key.clone(), vary_names, entry.clone())
+                .await
+            {
+                // the updated headers may be too large to be stored
+                self.memory_remove_variant(&key, &entry.vary_values);
+            }
+        } else if let Some(disk) = &self.disk {
+            disk.update(key, vary_names, entry);
+        }
+    }
+
+    fn memory_remove_variant(&self, key: &str, vary_values: &[Option<String>]) {
+        let mut memory = self.memory.lock().unwrap();
+        let old = memory.remove_variant(key, vary_values);
+        drop(memory);
+        drop(old);
+    }
+
+    /// Remove all stored responses for the key, see RFC 9111 Section 4.4
+    pub(crate) fn invalidate(&self, key: &str) {
+        let mut memory = self.memory.lock().unwrap();
+        let old = memory.remove(key);
+        drop(memory);
+        drop(old);
+
+        if let Some(disk) = &self.disk {
+            disk.remove(key);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use http::HeaderName;
+use lru::LruCache;
+
+use g3_types::net::HttpHeaderMap;
+
+use super::HttpCacheEntry;
+
+pub(super) type HttpCacheEvicted = Vec<(Arc<str>, Arc<[HeaderName]>, Arc<HttpCacheEntry>)>;
+
+struct HttpCacheSlot {
+    vary_names: Arc<[HeaderName]>,
+    variants: Vec<Arc<HttpCacheEntry>>,
+    size: usize,
+}
+
+/// LRU storage with size accounting, all variants of the same URI share the same slot
+pub(super) struct HttpCacheTier {
+    lru: LruCache<Arc<str>, HttpCacheSlot>,
+    used: usize,
+    capacity: usize,
+    max_variants: usize,
+}
+
+impl HttpCacheTier {
+    pub(super) fn new(capacity: usize, max_variants: usize) -> Self {
+        HttpCacheTier {
+            lru: LruCache::unbounded(),
+            used: 0,
+            capacity,
+            max_variants,
+        }
+    }
+
+    pub(super) fn get(
+        &mut self,
+        key: &str,
+        req_headers: &HttpHeaderMap,
+    ) -> Option<(Arc<[HeaderName]>, Arc<HttpCacheEntry>)> {
+        let slot = self.lru.get(key)?;
+        let vary_values = g3_http::cache::vary_values(&slot.vary_names, req_headers);
+        slot.variants
+            .iter()
+            .find(|e| e.vary_values == vary_values)
+            .map(|e| (slot.vary_names.clone(), e.clone()))
+    }
+
+    #[inline]
+    pub(super) fn can_store(&self, entry: &HttpCacheEntry) -> bool {
+        entry.size() <= self.capacity
+    }
+
+    /// Insert the entry, the evicted entries will be returned,
+    /// or None if the entry is too large to be stored
+    pub(super) fn insert(
+        &mut self,
+        key: Arc<str>,
+        vary_names: Arc<[HeaderName]>,
+        entry: Arc<HttpCacheEntry>,
+    ) -> Option<HttpCacheEvicted> {
+        if !self.can_store(&entry) {
+            return None;
+        }
+        let mut evicted = Vec::new();
+        let entry_size = entry.size();
+
+        match self.lru.get_mut(&key) {
+            Some(slot) if slot.vary_names == vary_names => {
+                if let Some(p) = slot
+                    .variants
+                    .iter()
+                    .position(|e| e.vary_values == entry.vary_values)
+                {
+                    let old = slot.variants.remove(p);
+                    slot.size -= old.size();
+                    self.used -= old.size();
+                } else if slot.variants.len() >= self.max_variants {
+                    let old = slot.variants.remove(0);
+                    slot.size -= old.size();
+                    self.used -= old.size();
+                }
+                slot.variants.push(entry);
+                slot.size += entry_size;
+            }
+            _ => {
+                let slot = HttpCacheSlot {
+                    vary_names,
+                    variants: vec![entry],
+                    size: entry_size,
+                };
+                if let Some(old) = self.lru.put(key, slot) {
+                    self.used -= old.size;
+                }
+            }
+        }
+        self.used += entry_size;
+
+        while self.used > self.capacity {
+            let Some((key, slot)) = self.lru.pop_lru() else {
+                break;
+            };
+            self.used -= slot.size;
+            for entry in slot.variants {
+                evicted.push((key.clone(), slot.vary_names.clone(), entry));
+            }
+        }
+        Some(evicted)
+    }
+
+    pub(super) fn remove_variant(
+        &mut self,
+        key: &str,
+        vary_values: &[Option<String>],
+    ) -> Option<Arc<HttpCacheEntry>> {
+        let slot = self.lru.peek_mut(key)?;
+        let p = slot
+            .variants
+            .iter()
+            .position(|e| e.vary_values == vary_values)?;
+        let old = slot.variants.remove(p);
+        slot.size -= old.size();
+        self.used -= old.size();
+        if slot.variants.is_empty() {
+            self.lru.pop(key);
+        }
+        Some(old)
+    }
+
+    pub(super) fn remove(&mut self, key: &str) -> Option<Vec<Arc<HttpCacheEntry>>> {
+        let slot = self.lru.pop(key)?;
+        self.used -= slot.size;
+        Some(slot.variants)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+
+    use crate::module::http_cache::entry::tests::new_entry;
+
+    #[test]
+    fn too_large() {
+        let entry = Arc::new(new_entry(Vec::new(), &[0u8; 1024]));
+        let mut tier = HttpCacheTier::new(entry.size() - 1, 4);
+        assert!(!tier.can_store(&entry));
+        assert!(tier.insert(Arc::from("a"), Arc::from([]), entry).is_none());
+        assert_eq!(tier.used, 0);
+        assert!(tier.get("a", &HttpHeaderMap::default()).is_none());
+    }
+
+    #[test]
+    fn evict_lru() {
+        let entry = Arc::new(new_entry(Vec::new(), &[0u8; 1024]));
+        let size = entry.size();
+        let mut tier = HttpCacheTier::new(size * 2, 4);
+
+        let evicted = tier.insert(Arc::from("a"), Arc::from([]), entry.clone());
+        assert!(evicted.unwrap().is_empty());
+        let evicted = tier.insert(Arc::from("b"), Arc::from([]), entry.clone());
+        assert!(evicted.unwrap().is_empty());
+        assert_eq!(tier.used, size * 2);
+
+        // make "a" the most recently used one
+        assert!(tier.get("a", &HttpHeaderMap::default()).is_some());
+        let evicted = tier
+            .insert(Arc::from("c"), Arc::from([]), entry.clone())
+            .unwrap();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].0.as_ref(), "b");
+        assert_eq!(tier.used, size * 2);
+
+        // replace the existing one
+        let evicted = tier.insert(Arc::from("c"), Arc::from([]), entry).unwrap();
+        assert!(evicted.is_empty());
+        assert_eq!(tier.used, size * 2);
+
+        let old = tier.remove("a").unwrap();
+        assert_eq!(old.len(), 1);
+        assert_eq!(tier.used, size);
+        assert!(tier.remove("a").is_none());
+    }
+
+    #[test]
+    fn variants() {
+        let vary_names: Arc<[HeaderName]> = Arc::from([http::header::ACCEPT_LANGUAGE]);
+        let new_variant = |lang: &str| Arc::new(new_entry(vec![Some(lang.to_string())], b"hello"));
+        let size = new_variant("en").size();
+        let mut tier = HttpCacheTier::new(size * 8, 2);
+
+        for lang in ["en", "fr", "de"] {
+            let evicted = tier.insert(Arc::from("a"), vary_names.clone(), new_variant(lang));
+            assert!(evicted.unwrap().is_empty());
+        }
+        // the oldest variant is dropped
+        assert_eq!(tier.used, size * 2);
+
+        let mut req_headers = HttpHeaderMap::default();
+        req_headers.insert(
+            http::header::ACCEPT_LANGUAGE,
+            HttpHeaderValue::from_static("en"),
+        );
+        assert!(tier.get("a", &req_headers).is_none());
+        req_headers.insert(
+            http::header::ACCEPT_LANGUAGE,
+            HttpHeaderValue::from_static("de"),
+        );
+        let (names, entry) = tier.get("a", &req_headers).unwrap();
+        assert_eq!(names, vary_names);
+        assert_eq!(entry.vary_values, vec![Some("de".to_string())]);
+
+        assert!(tier.remove_variant("a", &entry.vary_values).is_some());
+        assert_eq!(tier.used, size);
+        assert!(
+            tier.remove_variant("a", &[Some("fr".to_string())])
+                .is_some()
+        );
+        assert_eq!(tier.used, 0);
+        assert!(tier.remove("a").is_none());
+    }
+}
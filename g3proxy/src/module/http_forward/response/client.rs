@@ -112,6 +112,11 @@ impl HttpProxyClientResponse {
         HttpProxyClientResponse::from_standard(StatusCode::SERVICE_UNAVAILABLE, version, true)
     }
 
+    #[inline]
+    pub(crate) fn gateway_timeout(version: Version, close: bool) -> Self {
+        HttpProxyClientResponse::from_standard(StatusCode::GATEWAY_TIMEOUT, version, close)
+    }
+
     #[inline]
     pub(crate) fn resource_not_found(version: Version, close: bool) -> Self {
         HttpProxyClientResponse::from_standard(StatusCode::NOT_FOUND, version, close)
@@ -391,14 +396,18 @@ impl HttpProxyClientResponse {
             ServerTaskError::ClientAppTimeout(_) => {
                 HttpProxyClientResponse::from_standard(StatusCode::REQUEST_TIMEOUT, version, true)
             }
-            ServerTaskError::CanceledAsUserBlocked => {
+            ServerTaskError::CanceledAsUserBlocked
+            | ServerTaskError::CanceledAsUserRemoved
+            | ServerTaskError::CanceledAsUserExpired => {
                 HttpProxyClientResponse::from_standard(StatusCode::FORBIDDEN, version, true)
             }
-            ServerTaskError::CanceledAsServerQuit => HttpProxyClientResponse::from_standard(
-                StatusCode::INTERNAL_SERVER_ERROR,
-                version,
-                true,
-            ),
+            ServerTaskError::CanceledAsServerQuit | ServerTaskError::CanceledAsTaskKilled => {
+                HttpProxyClientResponse::from_standard(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    version,
+                    true,
+                )
+            }
             ServerTaskError::ClientTcpReadFailed(_)
             | ServerTaskError::ClientTcpWriteFailed(_)
             | ServerTaskError::ClientUdpRecvFailed(_)
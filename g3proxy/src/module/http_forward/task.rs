@@ -6,6 +6,8 @@
 use http::{Method, Uri};
 use tokio::time::{Duration, Instant};
 
+use crate::module::http_cache::HttpCacheStatus;
+
 pub(crate) struct HttpForwardTaskNotes {
     pub(crate) method: Method,
     pub(crate) uri: Uri,
@@ -14,6 +16,7 @@ pub(crate) struct HttpForwardTaskNotes {
     pub(crate) origin_status: u16,
     pub(crate) pipeline_wait: Duration,
     pub(crate) reused_connection: bool,
+    pub(crate) cache_status: Option<HttpCacheStatus>,
     create_ins: Instant,
     pub(crate) dur_req_send_hdr: Duration,
     pub(crate) dur_req_send_all: Duration,
@@ -38,6 +41,7 @@ impl HttpForwardTaskNotes {
             origin_status: 0,
             pipeline_wait: req_received.elapsed(),
             reused_connection: false,
+            cache_status: None,
             create_ins: task_created,
             dur_req_send_hdr: Duration::default(),
             dur_req_send_all: Duration::default(),
@@ -4,10 +4,12 @@
  */
 
 mod custom;
+mod rule;
 mod standard;
 
 pub(crate) use custom::{
     dynamic_egress_info, outgoing_ip, remote_connection_info, set_dynamic_egress_info,
     set_outgoing_ip, set_remote_connection_info, set_upstream_addr, set_upstream_id, upstream_addr,
 };
+pub(crate) use rule::{HttpHeaderRuleContext, apply_rules, rule_header_names};
 pub(crate) use standard::proxy_authorization_basic_pass;
@@ -0,0 +1,163 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use http::HeaderName;
+use log::debug;
+
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue, UpstreamAddr};
+
+use crate::config::http_header::{HttpHeaderRule, HttpHeaderRuleAction, HttpHeaderTemplateVar};
+use crate::serve::ServerTaskNotes;
+
+pub(crate) struct HttpHeaderRuleContext<'a> {
+    pub(crate) task_notes: &'a ServerTaskNotes,
+    pub(crate) escaper: &'a str,
+    pub(crate) upstream: &'a UpstreamAddr,
+    pub(crate) path: &'a str,
+}
+
+impl HttpHeaderRuleContext<'_> {
+    fn expand(&self, var: HttpHeaderTemplateVar, s: &mut String) {
+        match var {
+            HttpHeaderTemplateVar::UserName => {
+                if let Some(user_ctx) = self.task_notes.user_ctx() {
+                    s.push_str(user_ctx.user_name());
+                }
+            }
+            HttpHeaderTemplateVar::ClientIp => {
+                let _ = write!(s, "{}", self.task_notes.client_ip());
+            }
+            HttpHeaderTemplateVar::ClientAddr => {
+                let _ = write!(s, "{}", self.task_notes.client_addr());
+            }
+            HttpHeaderTemplateVar::ServerIp => {
+                let _ = write!(s, "{}", self.task_notes.server_addr().ip());
+            }
+            HttpHeaderTemplateVar::ServerAddr => {
+                let _ = write!(s, "{}", self.task_notes.server_addr());
+            }
+            HttpHeaderTemplateVar::EscaperName => s.push_str(self.escaper),
+            HttpHeaderTemplateVar::TaskId => {
+                let _ = write!(s, "{}", self.task_notes.id);
+            }
+            HttpHeaderTemplateVar::Host => {
+                let _ = write!(s, "{}", self.upstream.host());
+            }
+        }
+    }
+}
+
+/// Apply the header rules in order, rules with unmatched condition will be skipped
+/// Get the sorted names of all headers that may be changed by the rules
+pub(crate) fn rule_header_names<'a, I>(rules: I) -> Vec<HeaderName>
+where
+    I: IntoIterator<Item = &'a HttpHeaderRule>,
+{
+    let mut names: Vec<HeaderName> = rules.into_iter().map(|r| r.name.clone()).collect();
+    names.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+    names.dedup();
+    names
+}
+
+pub(crate) fn apply_rules(
+    rules: &[HttpHeaderRule],
+    headers: &mut HttpHeaderMap,
+    ctx: &HttpHeaderRuleContext<'_>,
+) {
+    for rule in rules {
+        if !rule.condition.matches(ctx.upstream.host(), ctx.path) {
+            continue;
+        }
+
+        match &rule.action {
+            HttpHeaderRuleAction::Add(template) => {
+                let value = template.render(|var, s| ctx.expand(var, s));
+                match HttpHeaderValue::from_str(&value) {
+                    Ok(value) => headers.append(rule.name.clone(), value),
+                    Err(e) => debug!("invalid rendered value for header {}: {e}", rule.name),
+                }
+            }
+            HttpHeaderRuleAction::Set(template) => {
+                let value = template.render(|var, s| ctx.expand(var, s));
+                match HttpHeaderValue::from_str(&value) {
+                    Ok(mut value) => {
+                        let old = headers.remove(&rule.name);
+                        if let Some(name) = old.as_ref().and_then(|v| v.original_name()) {
+                            value.set_original_name(name);
+                        }
+                        headers.insert(rule.name.clone(), value);
+                    }
+                    Err(e) => debug!("invalid rendered value for header {}: {e}", rule.name),
+                }
+            }
+            HttpHeaderRuleAction::Remove => {
+                headers.remove(&rule.name);
+            }
+            HttpHeaderRuleAction::Replace(regex, replacement) => {
+                if !headers.contains_key(&rule.name) {
+                    continue;
+                }
+                let mut new_values = Vec::new();
+                for old in headers.get_all(&rule.name) {
+                    let new = regex.replace_all(old.to_str(), replacement.as_str());
+                    match HttpHeaderValue::from_str(&new) {
+                        Ok(mut value) => {
+                            if let Some(name) = old.original_name() {
+                                value.set_original_name(name);
+                            }
+                            new_values.push(value);
+                        }
+                        Err(e) => {
+                            debug!("invalid replaced value for header {}: {e}", rule.name);
+                            new_values.push(old.clone());
+                        }
+                    }
+                }
+                headers.remove(&rule.name);
+                for value in new_values {
+                    headers.append(rule.name.clone(), value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use http::header;
+    use yaml_rust::YamlLoader;
+
+    use crate::config::http_header::HttpHeaderRules;
+
+    #[test]
+    fn header_names() {
+        let v = yaml_doc!(
+            r#"
+                request:
+                  - name: X-Tenant-Id
+                    action: set
+                    value: a
+                  - name: User-Agent
+                    action: remove
+                  - name: x-tenant-id
+                    action: add
+                    value: b
+            "#
+        );
+        let rules = HttpHeaderRules::parse_yaml(&v).unwrap();
+        let names = rule_header_names(&rules.request);
+        assert_eq!(
+            names,
+            vec![header::USER_AGENT, HeaderName::from_static("x-tenant-id")]
+        );
+
+        assert!(rule_header_names(&rules.response).is_empty());
+    }
+}
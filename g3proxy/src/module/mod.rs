@@ -3,7 +3,10 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+pub(crate) mod acme;
 pub(crate) mod ftp_over_http;
+pub(crate) mod h2_connect;
+pub(crate) mod http_cache;
 pub(crate) mod http_forward;
 pub(crate) mod http_header;
 pub(crate) mod tcp_connect;
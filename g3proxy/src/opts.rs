@@ -18,6 +18,7 @@ const ARGS_COMPLETION: &str = "completion";
 const ARGS_VERSION: &str = "version";
 const ARGS_VERIFY_PANIC: &str = "verify-panic";
 const ARGS_DEP_GRAPH: &str = "dep-graph";
+const ARGS_VALIDATE: &str = "validate";
 const ARGS_GROUP_NAME: &str = "group-name";
 const ARGS_CONFIG_FILE: &str = "config-file";
 const ARGS_CONTROL_DIR: &str = "control-dir";
@@ -34,6 +35,7 @@ pub struct ProcArgs {
     pub output_graphviz_graph: bool,
     pub output_mermaid_graph: bool,
     pub output_plantuml_graph: bool,
+    pub validate_config: bool,
 }
 
 impl Default for ProcArgs {
@@ -43,6 +45,7 @@ impl Default for ProcArgs {
             output_graphviz_graph: false,
             output_mermaid_graph: false,
             output_plantuml_graph: false,
+            validate_config: false,
         }
     }
 }
@@ -83,6 +86,13 @@ fn build_cli_args() -> Command {
                 .value_parser([DEP_GRAPH_GRAPHVIZ, DEP_GRAPH_MERMAID, DEP_GRAPH_PLANTUML])
                 .default_missing_value(DEP_GRAPH_GRAPHVIZ),
         )
+        .arg(
+            Arg::new(ARGS_VALIDATE)
+                .help("Validate the config files and the references between them")
+                .action(ArgAction::SetTrue)
+                .long("validate")
+                .conflicts_with(ARGS_DEP_GRAPH),
+        )
         .arg(
             Arg::new(ARGS_GROUP_NAME)
                 .help("Group name")
@@ -147,6 +157,9 @@ pub fn parse_clap() -> anyhow::Result<Option<ProcArgs>> {
             }
         }
     }
+    if args.get_flag(ARGS_VALIDATE) {
+        proc_args.validate_config = true;
+    }
     if let Some(config_file) = args.get_one::<PathBuf>(ARGS_CONFIG_FILE) {
         g3_daemon::opts::validate_and_set_config_file(config_file, crate::build::PKG_NAME)
             .context(format!(
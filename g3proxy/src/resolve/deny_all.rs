@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use anyhow::anyhow;
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 
 use g3_resolver::{ResolveError, ResolveLocalError};
 use g3_types::metrics::NodeName;
@@ -68,6 +69,10 @@ impl ResolverInternal for DenyAllResolver {
         Ok(())
     }
 
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        None
+    }
+
     async fn _shutdown(&mut self) {}
 }
 
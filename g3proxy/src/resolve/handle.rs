@@ -9,13 +9,25 @@ use std::sync::Arc;
 use std::task::{Context, Poll, ready};
 use std::time::Duration;
 
-use g3_resolver::{ResolveError, ResolvedRecordSource};
+use g3_resolver::{
+    ArcResolvedServiceRecord, ResolveError, ResolveLocalError, ResolvedRecordSource,
+};
 use g3_types::metrics::NodeName;
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionValue, ResolveStrategy};
 
+/// the resolved addresses and the remaining ttl of the record
+pub(crate) type ResolvedAddrsWithTtl = (Vec<IpAddr>, Option<Duration>);
+
 pub(crate) trait LoggedResolveJob {
     fn log_error(&self, _e: &ResolveError, _source: ResolvedRecordSource) {}
     fn poll_query(&mut self, cx: &mut Context<'_>) -> Poll<Result<Vec<IpAddr>, ResolveError>>;
+    /// Poll the query result along with the remaining ttl of the resolved record, if known
+    fn poll_query_with_ttl(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<ResolvedAddrsWithTtl, ResolveError>> {
+        self.poll_query(cx).map_ok(|addrs| (addrs, None))
+    }
 }
 
 pub(crate) type BoxLoggedResolveJob = Box<dyn LoggedResolveJob + Send + Sync>;
@@ -23,9 +35,21 @@ pub(crate) type BoxLoggedResolveJob = Box<dyn LoggedResolveJob + Send + Sync>;
 macro_rules! impl_logged_poll_query {
     () => {
         fn poll_query(&mut self, cx: &mut Context<'_>) -> Poll<Result<Vec<IpAddr>, ResolveError>> {
+            self.poll_query_with_ttl(cx).map_ok(|(addrs, _)| addrs)
+        }
+
+        fn poll_query_with_ttl(
+            &mut self,
+            cx: &mut Context<'_>,
+        ) -> Poll<Result<$crate::resolve::handle::ResolvedAddrsWithTtl, ResolveError>> {
             match ready!(self.inner.poll_recv(cx)) {
                 Ok((record, source)) => match &record.result {
-                    Ok(addrs) => Poll::Ready(Ok(addrs.clone())),
+                    Ok(addrs) => {
+                        let ttl = record
+                            .expire
+                            .map(|t| t.saturating_duration_since(tokio::time::Instant::now()));
+                        Poll::Ready(Ok((addrs.clone(), ttl)))
+                    }
                     Err(e) => {
                         self.log_error(e, source);
                         Poll::Ready(Err(e.clone()))
@@ -42,10 +66,19 @@ pub(crate) trait IntegratedResolverHandle {
     fn is_closed(&self) -> bool;
     fn query_v4(&self, domain: Arc<str>) -> Result<BoxLoggedResolveJob, ResolveError>;
     fn query_v6(&self, domain: Arc<str>) -> Result<BoxLoggedResolveJob, ResolveError>;
+    fn query_https(&self, domain: Arc<str>) -> Result<ServiceResolveJob, ResolveError> {
+        let Some(inner) = self.clone_inner() else {
+            return Err(ResolveLocalError::NoResolverSet.into());
+        };
+        let job = inner.get_https(domain)?;
+        Ok(job)
+    }
 
     fn clone_inner(&self) -> Option<g3_resolver::ResolverHandle>;
 }
 
+pub(crate) type ServiceResolveJob = g3_resolver::ResolveJob<ArcResolvedServiceRecord>;
+
 pub(crate) type ArcIntegratedResolverHandle = Arc<dyn IntegratedResolverHandle + Send + Sync>;
 
 struct NeverResolveJob {}
@@ -8,6 +8,8 @@ use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use async_trait::async_trait;
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
 use slog::Logger;
 
 use g3_types::metrics::NodeName;
@@ -77,6 +79,10 @@ impl ResolverInternal for HickoryResolver {
         Ok(())
     }
 
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        Some(self.inner.save_cache_snapshot().boxed())
+    }
+
     async fn _shutdown(&mut self) {
         self.inner.shutdown().await;
     }
@@ -0,0 +1,154 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use async_trait::async_trait;
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
+use slog::Logger;
+
+use g3_resolver::driver::hosts::HostsDriverConfig;
+use g3_types::metrics::NodeName;
+
+use crate::config::resolver::hosts::HostsResolverConfig;
+use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
+use crate::resolve::{
+    ArcIntegratedResolverHandle, BoxResolverInternal, Resolver, ResolverInternal, ResolverStats,
+};
+
+pub(crate) struct HostsResolver {
+    config: Arc<HostsResolverConfig>,
+    driver_config: HostsDriverConfig,
+    inner: g3_resolver::Resolver,
+    stats: Arc<ResolverStats>,
+    logger: Option<Logger>,
+}
+
+impl HostsResolver {
+    pub(crate) fn new_obj(config: HostsResolverConfig) -> anyhow::Result<BoxResolverInternal> {
+        let mut driver_config = config.driver.clone();
+        if let Some(fallback) = &config.fallback {
+            let fallback_handle = crate::resolve::get_handle(fallback)
+                .context("failed to get fallback resolver handle")?;
+            driver_config.set_fallback_handle(fallback_handle.clone_inner());
+        }
+
+        let inner_config = g3_resolver::ResolverConfig {
+            name: config.name().to_string(),
+            runtime: config.runtime.clone(),
+            driver: g3_resolver::AnyResolveDriverConfig::Hosts(driver_config.clone()),
+        };
+        let mut builder = g3_resolver::ResolverBuilder::new(inner_config);
+        builder.thread_name(format!("res-{}", config.name()));
+        let resolver = builder.build()?;
+
+        let logger = crate::log::resolve::get_logger(config.r#type(), config.name());
+        let stats = ResolverStats::new(config.name(), resolver.get_stats());
+
+        Ok(Box::new(HostsResolver {
+            config: Arc::new(config),
+            driver_config,
+            inner: resolver,
+            stats: Arc::new(stats),
+            logger,
+        }))
+    }
+}
+
+#[async_trait]
+impl ResolverInternal for HostsResolver {
+    fn _dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        self.config.dependent_resolver()
+    }
+
+    fn _clone_config(&self) -> AnyResolverConfig {
+        AnyResolverConfig::Hosts(Box::new(self.config.as_ref().clone()))
+    }
+
+    fn _update_config(
+        &mut self,
+        config: AnyResolverConfig,
+        dep_table: BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+    ) -> anyhow::Result<()> {
+        if let AnyResolverConfig::Hosts(config) = config {
+            let config = *config;
+            let mut driver_config = config.driver.clone();
+            if let Some(fallback) = &config.fallback {
+                let fallback_handle = dep_table.get(fallback).unwrap();
+                driver_config.set_fallback_handle(fallback_handle.clone_inner());
+            }
+
+            let inner_config = g3_resolver::ResolverConfig {
+                name: config.name().to_string(),
+                runtime: config.runtime.clone(),
+                driver: g3_resolver::AnyResolveDriverConfig::Hosts(driver_config.clone()),
+            };
+
+            self.inner
+                .update_config(inner_config)
+                .context("failed to update inner hosts resolver config")?;
+            self.driver_config = driver_config;
+            self.config = Arc::new(config);
+            Ok(())
+        } else {
+            Err(anyhow!("invalid config type for HostsResolver"))
+        }
+    }
+
+    fn _update_dependent_handle(
+        &mut self,
+        target: &NodeName,
+        handle: ArcIntegratedResolverHandle,
+    ) -> anyhow::Result<()> {
+        if self.config.fallback.as_ref() != Some(target) {
+            return Err(anyhow!(
+                "resolver {} doesn't depend on resolver {}",
+                self.config.name(),
+                target
+            ));
+        }
+
+        let mut driver_config = self.driver_config.clone();
+        driver_config.set_fallback_handle(handle.clone_inner());
+
+        let inner_config = g3_resolver::ResolverConfig {
+            name: self.config.name().to_string(),
+            runtime: self.config.runtime.clone(),
+            driver: g3_resolver::AnyResolveDriverConfig::Hosts(driver_config.clone()),
+        };
+
+        self.inner
+            .update_config(inner_config)
+            .context("failed to update inner hosts resolver config")?;
+        self.driver_config = driver_config;
+        Ok(())
+    }
+
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        Some(self.inner.save_cache_snapshot().boxed())
+    }
+
+    async fn _shutdown(&mut self) {
+        self.inner.shutdown().await;
+    }
+}
+
+impl Resolver for HostsResolver {
+    fn get_handle(&self) -> ArcIntegratedResolverHandle {
+        let inner_context = self.inner.get_handle();
+        Arc::new(super::HostsResolverHandle::new(
+            &self.config,
+            inner_context,
+            self.logger.clone(),
+        ))
+    }
+
+    fn get_stats(&self) -> Arc<ResolverStats> {
+        Arc::clone(&self.stats)
+    }
+}
@@ -7,6 +7,7 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures_util::future::BoxFuture;
 
 use g3_types::metrics::NodeName;
 
@@ -24,7 +25,7 @@ mod stats;
 pub(crate) use stats::ResolverStats;
 
 mod registry;
-pub(crate) use registry::{get_handle, get_names};
+pub(crate) use registry::{get_config, get_handle, get_names};
 
 #[cfg(feature = "c-ares")]
 mod c_ares;
@@ -32,10 +33,12 @@ mod hickory;
 
 mod deny_all;
 mod fail_over;
+mod hosts;
+mod route_domain;
 
 mod ops;
 pub use ops::spawn_all;
-pub(crate) use ops::{foreach_resolver, reload};
+pub(crate) use ops::{foreach_resolver, reload, save_cache_snapshot};
 
 pub(crate) trait Resolver {
     fn get_handle(&self) -> ArcIntegratedResolverHandle;
@@ -58,6 +61,8 @@ trait ResolverInternal: Resolver {
         handle: ArcIntegratedResolverHandle,
     ) -> anyhow::Result<()>;
 
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>>;
+
     async fn _shutdown(&mut self);
 }
 
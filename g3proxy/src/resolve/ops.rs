@@ -21,6 +21,8 @@ use super::hickory::HickoryResolver;
 
 use super::deny_all::DenyAllResolver;
 use super::fail_over::FailOverResolver;
+use super::hosts::HostsResolver;
+use super::route_domain::RouteDomainResolver;
 
 use super::{Resolver, registry};
 
@@ -109,6 +111,30 @@ where
     registry::foreach(|name, resolver| f(name, resolver.as_ref()));
 }
 
+pub(crate) async fn save_cache_snapshot() -> anyhow::Result<()> {
+    let mut jobs = Vec::new();
+    registry::foreach(|name, resolver| {
+        if let Some(f) = resolver._save_cache_snapshot() {
+            jobs.push((name.clone(), f));
+        }
+    });
+
+    let mut failed = 0usize;
+    for (name, f) in jobs {
+        if let Err(e) = f.await {
+            warn!("failed to save cache snapshot for resolver {name}: {e:?}");
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        Err(anyhow!(
+            "failed to save cache snapshot for {failed} resolvers"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 #[async_recursion]
 async fn update_dependency_to_resolver_unlocked(target: &NodeName, status: &str) {
     let mut names = Vec::<NodeName>::new();
@@ -161,6 +187,7 @@ async fn delete_existed_unlocked(name: &NodeName) {
     let old_resolver = registry::del(name);
     update_dependency_to_resolver_unlocked(name, STATUS).await;
     crate::escape::update_dependency_to_resolver(name, STATUS).await;
+    crate::serve::update_dependency_to_resolver(name, STATUS).await;
     if let Some(mut resolver) = old_resolver {
         tokio::spawn(async move {
             resolver._shutdown().await;
@@ -178,10 +205,13 @@ async fn spawn_new_unlocked(config: AnyResolverConfig) -> anyhow::Result<()> {
         AnyResolverConfig::Hickory(c) => HickoryResolver::new_obj(*c)?,
         AnyResolverConfig::DenyAll(c) => DenyAllResolver::new_obj(c)?,
         AnyResolverConfig::FailOver(c) => FailOverResolver::new_obj(c)?,
+        AnyResolverConfig::RouteDomain(c) => RouteDomainResolver::new_obj(c)?,
+        AnyResolverConfig::Hosts(c) => HostsResolver::new_obj(*c)?,
     };
     let old_resolver = registry::add(name.clone(), resolver);
     update_dependency_to_resolver_unlocked(&name, STATUS).await;
     crate::escape::update_dependency_to_resolver(&name, STATUS).await;
+    crate::serve::update_dependency_to_resolver(&name, STATUS).await;
     if let Some(mut resolver) = old_resolver {
         tokio::spawn(async move {
             resolver._shutdown().await;
@@ -56,7 +56,7 @@ pub(crate) fn get_handle(name: &NodeName) -> anyhow::Result<ArcIntegratedResolve
     }
 }
 
-pub(super) fn get_config(name: &NodeName) -> Option<AnyResolverConfig> {
+pub(crate) fn get_config(name: &NodeName) -> Option<AnyResolverConfig> {
     let ht = RUNTIME_RESOLVER_REGISTRY.lock().unwrap();
     ht.get(name).map(|resolver| resolver._clone_config())
 }
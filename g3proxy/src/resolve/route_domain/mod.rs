@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod handle;
+mod resolver;
+
+use handle::RouteDomainResolverHandle;
+pub(super) use resolver::RouteDomainResolver;
@@ -0,0 +1,186 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use async_trait::async_trait;
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
+use regex::Regex;
+use slog::Logger;
+
+use g3_resolver::driver::route_domain::RouteDomainDriverConfig;
+use g3_types::metrics::NodeName;
+
+use crate::config::resolver::route_domain::RouteDomainResolverConfig;
+use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
+use crate::resolve::{
+    ArcIntegratedResolverHandle, BoxResolverInternal, Resolver, ResolverInternal, ResolverStats,
+};
+
+pub(crate) struct RouteDomainResolver {
+    config: Arc<RouteDomainResolverConfig>,
+    next_table: BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+    inner: g3_resolver::Resolver,
+    stats: Arc<ResolverStats>,
+    logger: Option<Logger>,
+}
+
+impl RouteDomainResolver {
+    pub(crate) fn new_obj(
+        config: RouteDomainResolverConfig,
+    ) -> anyhow::Result<BoxResolverInternal> {
+        let mut next_table = BTreeMap::new();
+        if let Some(names) = config.dependent_resolver() {
+            for name in names {
+                let handle = crate::resolve::get_handle(&name)
+                    .context(format!("failed to get next resolver {name} handle"))?;
+                next_table.insert(name, handle);
+            }
+        }
+
+        let inner_config = build_inner_config(&config, &next_table)?;
+        let mut builder = g3_resolver::ResolverBuilder::new(inner_config);
+        builder.thread_name(format!("res-{}", config.name()));
+        let resolver = builder.build()?;
+
+        let logger = crate::log::resolve::get_logger(config.r#type(), config.name());
+        let stats = ResolverStats::new(config.name(), resolver.get_stats());
+
+        Ok(Box::new(RouteDomainResolver {
+            config: Arc::new(config),
+            next_table,
+            inner: resolver,
+            stats: Arc::new(stats),
+            logger,
+        }))
+    }
+}
+
+fn build_inner_config(
+    config: &RouteDomainResolverConfig,
+    next_table: &BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+) -> anyhow::Result<g3_resolver::ResolverConfig> {
+    let get_inner_handle = |name: &NodeName| {
+        next_table
+            .get(name)
+            .ok_or_else(|| anyhow!("no next resolver {name} found"))
+            .map(|h| h.clone_inner())
+    };
+
+    let mut driver_config = RouteDomainDriverConfig::default();
+    driver_config.set_default_handle(get_inner_handle(&config.default_next)?);
+    if let Some(ttl) = config.negative_ttl {
+        driver_config.set_negative_ttl(ttl);
+    }
+
+    let mut index_table = BTreeMap::new();
+    for rule in &config.rules {
+        let index = match index_table.get(&rule.next) {
+            Some(index) => *index,
+            None => {
+                let index = driver_config.add_next_handle(get_inner_handle(&rule.next)?);
+                index_table.insert(rule.next.clone(), index);
+                index
+            }
+        };
+
+        for domain in &rule.exact_match {
+            driver_config.add_exact_match(domain.clone(), index);
+        }
+        for domain in &rule.child_match {
+            driver_config.add_child_match(domain, index);
+        }
+        for s in &rule.regex_match {
+            let regex = Regex::new(s).map_err(|e| anyhow!("invalid regex {s}: {e}"))?;
+            driver_config.add_regex_match(&regex, index);
+        }
+    }
+
+    Ok(g3_resolver::ResolverConfig {
+        name: config.name().to_string(),
+        runtime: config.runtime.clone(),
+        driver: g3_resolver::AnyResolveDriverConfig::RouteDomain(driver_config),
+    })
+}
+
+#[async_trait]
+impl ResolverInternal for RouteDomainResolver {
+    fn _dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        self.config.dependent_resolver()
+    }
+
+    fn _clone_config(&self) -> AnyResolverConfig {
+        AnyResolverConfig::RouteDomain(self.config.as_ref().clone())
+    }
+
+    fn _update_config(
+        &mut self,
+        config: AnyResolverConfig,
+        dep_table: BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+    ) -> anyhow::Result<()> {
+        if let AnyResolverConfig::RouteDomain(config) = config {
+            let inner_config = build_inner_config(&config, &dep_table)?;
+
+            self.inner
+                .update_config(inner_config)
+                .context("failed to update inner route_domain resolver config")?;
+            self.next_table = dep_table;
+            self.config = Arc::new(config);
+            Ok(())
+        } else {
+            Err(anyhow!("invalid config type for RouteDomainResolver"))
+        }
+    }
+
+    fn _update_dependent_handle(
+        &mut self,
+        target: &NodeName,
+        handle: ArcIntegratedResolverHandle,
+    ) -> anyhow::Result<()> {
+        if !self.next_table.contains_key(target) {
+            return Err(anyhow!(
+                "resolver {} doesn't depend on resolver {}",
+                self.config.name(),
+                target
+            ));
+        }
+
+        let mut next_table = self.next_table.clone();
+        next_table.insert(target.clone(), handle);
+        let inner_config = build_inner_config(&self.config, &next_table)?;
+
+        self.inner
+            .update_config(inner_config)
+            .context("failed to update inner route_domain resolver config")?;
+        self.next_table = next_table;
+        Ok(())
+    }
+
+    fn _save_cache_snapshot(&self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        Some(self.inner.save_cache_snapshot().boxed())
+    }
+
+    async fn _shutdown(&mut self) {
+        self.inner.shutdown().await;
+    }
+}
+
+impl Resolver for RouteDomainResolver {
+    fn get_handle(&self) -> ArcIntegratedResolverHandle {
+        let inner_context = self.inner.get_handle();
+        Arc::new(super::RouteDomainResolverHandle::new(
+            &self.config,
+            inner_context,
+            self.logger.clone(),
+        ))
+    }
+
+    fn get_stats(&self) -> Arc<ResolverStats> {
+        Arc::clone(&self.stats)
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::poll_fn;
+use std::sync::Arc;
+use std::time::Duration;
+
+use base64::prelude::*;
+use bytes::{Bytes, BytesMut};
+use h2::RecvStream;
+use h2::server::SendResponse;
+use http::{Method, Request, Response, StatusCode, header};
+use tokio::io::{AsyncRead, AsyncWrite};
+
+use g3_daemon::server::ClientConnectionInfo;
+
+use super::query::{DnsQueryContext, DnsQueryProtocol};
+use crate::serve::ServerTaskNotes;
+
+const DNS_MESSAGE_CONTENT_TYPE: &str = "application/dns-message";
+const DNS_MESSAGE_MAX_SIZE: usize = u16::MAX as usize;
+
+/// Serve DNS over HTTPS requests as defined in RFC 8484, only HTTP/2 is supported
+pub(super) async fn serve_doh<S>(
+    ctx: &Arc<DnsQueryContext>,
+    stream: S,
+    cc_info: &ClientConnectionInfo,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let idle_timeout = ctx.config.tcp_idle_timeout;
+    let mut h2c = match tokio::time::timeout(idle_timeout, h2::server::handshake(stream)).await {
+        Ok(Ok(h2c)) => h2c,
+        Ok(Err(_)) | Err(_) => return,
+    };
+
+    loop {
+        match tokio::time::timeout(idle_timeout, h2c.accept()).await {
+            Ok(Some(Ok((req, send_rsp)))) => {
+                let Some(permit) = ctx.try_acquire_query_permit() else {
+                    send_status(send_rsp, StatusCode::SERVICE_UNAVAILABLE);
+                    continue;
+                };
+                let ctx = ctx.clone();
+                let task_notes = ServerTaskNotes::new(cc_info.clone(), None, Duration::ZERO);
+                tokio::spawn(async move {
+                    handle_request(&ctx, task_notes, req, send_rsp).await;
+                    drop(permit);
+                });
+            }
+            Ok(Some(Err(_))) | Ok(None) => return,
+            Err(_) => break,
+        }
+    }
+
+    h2c.graceful_shutdown();
+    let _ = tokio::time::timeout(idle_timeout, poll_fn(|cx| h2c.poll_closed(cx))).await;
+}
+
+async fn handle_request(
+    ctx: &DnsQueryContext,
+    task_notes: ServerTaskNotes,
+    req: Request<RecvStream>,
+    mut send_rsp: SendResponse<Bytes>,
+) {
+    if req.uri().path() != ctx.config.doh_path {
+        send_status(send_rsp, StatusCode::NOT_FOUND);
+        return;
+    }
+
+    let msg = match *req.method() {
+        Method::GET => {
+            let Some(msg) = req
+                .uri()
+                .query()
+                .and_then(|q| q.split('&').find_map(|kv| kv.strip_prefix("dns=")))
+                .and_then(|v| BASE64_URL_SAFE_NO_PAD.decode(v).ok())
+            else {
+                send_status(send_rsp, StatusCode::BAD_REQUEST);
+                return;
+            };
+            msg
+        }
+        Method::POST => {
+            let content_type = req
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok());
+            if content_type != Some(DNS_MESSAGE_CONTENT_TYPE) {
+                send_status(send_rsp, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+                return;
+            }
+            match recv_body(req.into_body()).await {
+                Some(msg) => msg.to_vec(),
+                None => {
+                    send_status(send_rsp, StatusCode::BAD_REQUEST);
+                    return;
+                }
+            }
+        }
+        _ => {
+            send_status(send_rsp, StatusCode::METHOD_NOT_ALLOWED);
+            return;
+        }
+    };
+
+    let Some(dns_rsp) = ctx.answer(&task_notes, DnsQueryProtocol::Doh, &msg).await else {
+        send_status(send_rsp, StatusCode::BAD_REQUEST);
+        return;
+    };
+
+    let rsp = Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, DNS_MESSAGE_CONTENT_TYPE)
+        .header(header::CONTENT_LENGTH, dns_rsp.data.len())
+        .header(
+            header::CACHE_CONTROL,
+            format!("max-age={}", dns_rsp.max_age),
+        )
+        .body(())
+        .unwrap();
+    if let Ok(mut send_stream) = send_rsp.send_response(rsp, false) {
+        let _ = send_stream.send_data(Bytes::from(dns_rsp.data), true);
+    }
+}
+
+async fn recv_body(mut body: RecvStream) -> Option<BytesMut> {
+    let mut buf = BytesMut::new();
+    while let Some(r) = body.data().await {
+        let data = r.ok()?;
+        let _ = body.flow_control().release_capacity(data.len());
+        if buf.len() + data.len() > DNS_MESSAGE_MAX_SIZE {
+            return None;
+        }
+        buf.extend_from_slice(&data);
+    }
+    Some(buf)
+}
+
+fn send_status(mut send_rsp: SendResponse<Bytes>, status: StatusCode) {
+    let rsp = Response::builder().status(status).body(()).unwrap();
+    let _ = send_rsp.send_response(rsp, true);
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod doh;
+mod query;
+mod server;
+mod stats;
+mod stream;
+
+pub(crate) use server::DnsServer;
+pub(crate) use stats::*;
@@ -0,0 +1,726 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::poll_fn;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwapOption;
+use governor::RateLimiter;
+use governor::clock::DefaultClock;
+use governor::state::keyed::HashMapStateStore;
+use governor::state::{InMemoryState, NotKeyed};
+use hickory_proto::op::{Edns, Message, MessageType, OpCode, ResponseCode};
+use hickory_proto::rr::rdata::{A, AAAA};
+use hickory_proto::rr::{DNSClass, RData, Record, RecordType};
+use ip_network_table::IpNetworkTable;
+use slog::Logger;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use g3_resolver::{ResolveError, ResolveServerError};
+use g3_types::acl::AclAction;
+use g3_types::acl_set::AclDstHostRuleSet;
+use g3_types::net::Host;
+use g3_types::resolve::QueryStrategy;
+
+use super::DnsServerStats;
+use crate::config::server::dns_server::DnsServerConfig;
+use crate::log::task::dns_query::TaskLogForDnsQuery;
+use crate::resolve::ArcIntegratedResolverHandle;
+use crate::serve::ServerTaskNotes;
+
+/// the max udp payload size we advertise in EDNS, which should be safe to avoid ip fragmentation
+const EDNS_MAX_PAYLOAD: u16 = 1232;
+/// the max udp payload size for clients without EDNS support
+const UDP_MIN_PAYLOAD: usize = 512;
+/// shrink the per client rate limiter table if it grows larger than this
+const CLIENT_RATE_LIMITER_SHRINK_THRESHOLD: usize = 4096;
+
+#[derive(Clone, Copy)]
+pub(super) enum DnsQueryProtocol {
+    Udp,
+    Tcp,
+    Dot,
+    Doh,
+}
+
+impl DnsQueryProtocol {
+    fn as_str(&self) -> &'static str {
+        match self {
+            DnsQueryProtocol::Udp => "udp",
+            DnsQueryProtocol::Tcp => "tcp",
+            DnsQueryProtocol::Dot => "dot",
+            DnsQueryProtocol::Doh => "doh",
+        }
+    }
+}
+
+pub(super) struct DnsQueryContext {
+    pub(super) config: Arc<DnsServerConfig>,
+    pub(super) server_stats: Arc<DnsServerStats>,
+    pub(super) resolver_handle: ArcSwapOption<ArcIntegratedResolverHandle>,
+    dst_host_filter: Option<AclDstHostRuleSet>,
+    client_dst_host_filters: Option<IpNetworkTable<Arc<AclDstHostRuleSet>>>,
+    query_rate_limit: Option<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
+    client_query_rate_limit: Option<RateLimiter<IpAddr, HashMapStateStore<IpAddr>, DefaultClock>>,
+    query_semaphore: Arc<Semaphore>,
+    task_logger: Option<Logger>,
+}
+
+pub(super) struct DnsResponse {
+    pub(super) data: Vec<u8>,
+    /// the max time the response can be cached, which is the min ttl of the answers
+    pub(super) max_age: u32,
+}
+
+struct DnsQueryOutcome {
+    query_name: Option<String>,
+    query_type: Option<RecordType>,
+    answer_count: usize,
+    reason: Option<&'static str>,
+}
+
+impl DnsQueryContext {
+    pub(super) fn new(
+        config: Arc<DnsServerConfig>,
+        server_stats: Arc<DnsServerStats>,
+        task_logger: Option<Logger>,
+    ) -> Self {
+        let resolver_handle = crate::resolve::get_handle(&config.resolver)
+            .ok()
+            .map(Arc::new);
+
+        let dst_host_filter = config.dst_host_filter.as_ref().map(|b| b.build());
+        let client_dst_host_filters = if config.client_dst_host_filters.is_empty() {
+            None
+        } else {
+            let mut table = IpNetworkTable::new();
+            for c in &config.client_dst_host_filters {
+                let filter = Arc::new(c.dst_host_filter.build());
+                for net in &c.networks {
+                    table.insert(*net, filter.clone());
+                }
+            }
+            Some(table)
+        };
+
+        let query_rate_limit = config
+            .query_rate_limit
+            .as_ref()
+            .map(|quota| RateLimiter::direct(quota.get_inner()));
+        let client_query_rate_limit = config
+            .client_query_rate_limit
+            .as_ref()
+            .map(|quota| RateLimiter::hashmap(quota.get_inner()));
+
+        let query_semaphore = Arc::new(Semaphore::new(config.max_concurrent_queries));
+        DnsQueryContext {
+            config,
+            server_stats,
+            resolver_handle: ArcSwapOption::new(resolver_handle),
+            dst_host_filter,
+            client_dst_host_filters,
+            query_rate_limit,
+            client_query_rate_limit,
+            query_semaphore,
+            task_logger,
+        }
+    }
+
+    pub(super) fn update_resolver(&self) {
+        let resolver_handle = crate::resolve::get_handle(&self.config.resolver)
+            .ok()
+            .map(Arc::new);
+        self.resolver_handle.store(resolver_handle);
+    }
+
+    /// Get a permit for a new concurrent query, return None if the limit is reached
+    pub(super) fn try_acquire_query_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.query_semaphore.clone().try_acquire_owned().ok()
+    }
+
+    fn rate_limited(&self, client_ip: IpAddr) -> bool {
+        if let Some(limit) = &self.query_rate_limit {
+            if limit.check().is_err() {
+                return true;
+            }
+        }
+        if let Some(limit) = &self.client_query_rate_limit {
+            if limit.len() > CLIENT_RATE_LIMITER_SHRINK_THRESHOLD {
+                limit.retain_recent();
+            }
+            if limit.check_key(&client_ip).is_err() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Check the host against the filters, the missed action will be ignored if `only_matched` is set
+    fn check_host(&self, client_ip: IpAddr, host: &Host, only_matched: bool) -> bool {
+        let client_filter = self
+            .client_dst_host_filters
+            .as_ref()
+            .and_then(|table| table.longest_match(client_ip))
+            .map(|(_, filter)| filter.as_ref());
+        for filter in self.dst_host_filter.iter().chain(client_filter) {
+            let (found, action) = filter.check(host);
+            if only_matched && !found {
+                continue;
+            }
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => return false,
+            }
+        }
+        true
+    }
+
+    /// Answer the dns request, return None if the request should be dropped silently
+    pub(super) async fn answer(
+        &self,
+        task_notes: &ServerTaskNotes,
+        protocol: DnsQueryProtocol,
+        request: &[u8],
+    ) -> Option<DnsResponse> {
+        let _alive_guard = self.server_stats.add_task();
+
+        let mut outcome = DnsQueryOutcome {
+            query_name: None,
+            query_type: None,
+            answer_count: 0,
+            reason: None,
+        };
+        let mut udp_max_size = UDP_MIN_PAYLOAD;
+        let rsp = match Message::from_vec(request) {
+            Ok(req) => {
+                if req.message_type() != MessageType::Query {
+                    return None;
+                }
+                if let Some(edns) = req.extensions() {
+                    udp_max_size = edns.max_payload().clamp(512, EDNS_MAX_PAYLOAD) as usize;
+                }
+                self.answer_request(task_notes, protocol, req, request, &mut outcome)
+                    .await
+            }
+            Err(_) => {
+                if request.len() < 2 {
+                    return None;
+                }
+                outcome.reason = Some("invalid request");
+                let mut rsp = Message::new();
+                rsp.set_id(u16::from_be_bytes([request[0], request[1]]))
+                    .set_message_type(MessageType::Response)
+                    .set_response_code(ResponseCode::FormErr);
+                rsp
+            }
+        };
+
+        if let Some(logger) = &self.task_logger {
+            let task_log = TaskLogForDnsQuery {
+                logger,
+                task_notes,
+                protocol: protocol.as_str(),
+                query_name: outcome.query_name.as_deref(),
+                query_type: outcome.query_type.map(|t| t.to_string()),
+                rcode: format!("{:?}", rsp.response_code()),
+                answer_count: outcome.answer_count,
+            };
+            task_log.log(outcome.reason);
+        }
+
+        let max_size = match protocol {
+            DnsQueryProtocol::Udp => udp_max_size,
+            _ => u16::MAX as usize,
+        };
+        let max_age = response_max_age(&rsp, self.config.answer_ttl);
+        let data = encode_response(rsp, max_size)?;
+        Some(DnsResponse { data, max_age })
+    }
+
+    async fn answer_request(
+        &self,
+        task_notes: &ServerTaskNotes,
+        protocol: DnsQueryProtocol,
+        req: Message,
+        request: &[u8],
+        outcome: &mut DnsQueryOutcome,
+    ) -> Message {
+        let mut rsp = Message::new();
+        rsp.set_id(req.id())
+            .set_message_type(MessageType::Response)
+            .set_op_code(req.op_code())
+            .set_recursion_desired(req.recursion_desired())
+            .set_recursion_available(true)
+            .set_checking_disabled(req.checking_disabled());
+        if let Some(req_edns) = req.extensions() {
+            let mut edns = Edns::new();
+            let max_payload = match protocol {
+                DnsQueryProtocol::Udp => req_edns.max_payload().clamp(512, EDNS_MAX_PAYLOAD),
+                _ => EDNS_MAX_PAYLOAD,
+            };
+            edns.set_max_payload(max_payload);
+            rsp.set_edns(edns);
+        }
+
+        if req.op_code() != OpCode::Query {
+            outcome.reason = Some("unsupported opcode");
+            rsp.set_response_code(ResponseCode::NotImp);
+            return rsp;
+        }
+        let [query] = req.queries() else {
+            outcome.reason = Some("invalid query count");
+            rsp.set_response_code(ResponseCode::FormErr);
+            return rsp;
+        };
+        rsp.add_query(query.clone());
+
+        let mut domain = query.name().to_ascii().to_lowercase();
+        if domain.ends_with('.') {
+            domain.pop();
+        }
+        outcome.query_name = Some(domain.clone());
+        outcome.query_type = Some(query.query_type());
+
+        let client_ip = task_notes.client_ip();
+        if self.rate_limited(client_ip) {
+            outcome.reason = Some("rate limited");
+            rsp.set_response_code(ResponseCode::Refused);
+            return rsp;
+        }
+
+        if query.query_class() != DNSClass::IN {
+            outcome.reason = Some("unsupported query class");
+            rsp.set_response_code(ResponseCode::NotImp);
+            return rsp;
+        }
+
+        let domain: Arc<str> = Arc::from(domain);
+        if !self.check_host(client_ip, &Host::Domain(domain.clone()), false) {
+            self.server_stats.forbidden.add_dest_denied();
+            outcome.reason = Some("forbidden domain");
+            rsp.set_response_code(ResponseCode::Refused);
+            return rsp;
+        }
+
+        let query_strategy = match query.query_type() {
+            RecordType::A => QueryStrategy::Ipv4Only,
+            RecordType::AAAA => QueryStrategy::Ipv6Only,
+            _ => {
+                let Some(server) = self.config.forward_server else {
+                    outcome.reason = Some("unsupported query type");
+                    rsp.set_response_code(ResponseCode::NotImp);
+                    return rsp;
+                };
+                return match self.forward(server, protocol, request).await {
+                    Ok(mut forward_rsp) => {
+                        forward_rsp.set_id(req.id());
+                        outcome.answer_count = forward_rsp.answers().len();
+                        forward_rsp
+                    }
+                    Err(reason) => {
+                        outcome.reason = Some(reason);
+                        rsp.set_response_code(ResponseCode::ServFail);
+                        rsp
+                    }
+                };
+            }
+        };
+
+        match self.resolve(domain, query_strategy).await {
+            Ok((ips, record_ttl)) => {
+                let ttl = answer_ttl(record_ttl, self.config.answer_ttl);
+                let answers = ips
+                    .into_iter()
+                    .filter(|ip| self.check_host(client_ip, &Host::Ip(*ip), true))
+                    .filter_map(|ip| {
+                        let rdata = match (ip, query_strategy) {
+                            (IpAddr::V4(ip4), QueryStrategy::Ipv4Only) => RData::A(A(ip4)),
+                            (IpAddr::V6(ip6), QueryStrategy::Ipv6Only) => RData::AAAA(AAAA(ip6)),
+                            _ => return None,
+                        };
+                        Some(Record::from_rdata(query.name().clone(), ttl, rdata))
+                    })
+                    .collect::<Vec<_>>();
+                outcome.answer_count = answers.len();
+                rsp.add_answers(answers);
+                rsp.set_response_code(ResponseCode::NoError);
+            }
+            Err(e) => {
+                let (rcode, reason) = error_response(&e);
+                outcome.reason = Some(reason);
+                rsp.set_response_code(rcode);
+            }
+        }
+        rsp
+    }
+
+    async fn resolve(
+        &self,
+        domain: Arc<str>,
+        query: QueryStrategy,
+    ) -> Result<(Vec<IpAddr>, Option<Duration>), ResolveError> {
+        let Some(handle) = self.resolver_handle.load_full() else {
+            return Err(ResolveError::UnexpectedError("no resolver available"));
+        };
+        if domain.is_empty() {
+            return Err(ResolveError::EmptyDomain);
+        }
+        let mut job = match query {
+            QueryStrategy::Ipv6Only => handle.query_v6(domain)?,
+            _ => handle.query_v4(domain)?,
+        };
+        match tokio::time::timeout(
+            self.config.query_timeout,
+            poll_fn(|cx| job.poll_query_with_ttl(cx)),
+        )
+        .await
+        {
+            Ok(r) => r,
+            Err(_) => Err(ResolveError::UnexpectedError("resolve timeout")),
+        }
+    }
+
+    /// Forward the raw request to the upstream server, retry over tcp if the response is
+    /// truncated and the client is not using udp
+    async fn forward(
+        &self,
+        server: SocketAddr,
+        protocol: DnsQueryProtocol,
+        request: &[u8],
+    ) -> Result<Message, &'static str> {
+        let forward = async {
+            let rsp = forward_udp(server, request).await?;
+            if rsp.truncated() && !matches!(protocol, DnsQueryProtocol::Udp) {
+                forward_tcp(server, request).await
+            } else {
+                Ok(rsp)
+            }
+        };
+        match tokio::time::timeout(self.config.query_timeout, forward).await {
+            Ok(r) => r,
+            Err(_) => Err("forward timeout"),
+        }
+    }
+}
+
+async fn forward_udp(server: SocketAddr, request: &[u8]) -> Result<Message, &'static str> {
+    let bind_ip = match server {
+        SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    let socket = UdpSocket::bind(SocketAddr::new(bind_ip, 0))
+        .await
+        .map_err(|_| "forward socket bind failed")?;
+    socket
+        .connect(server)
+        .await
+        .map_err(|_| "forward socket connect failed")?;
+    socket
+        .send(request)
+        .await
+        .map_err(|_| "forward send failed")?;
+
+    let mut buf = vec![0u8; u16::MAX as usize];
+    loop {
+        let len = socket
+            .recv(&mut buf)
+            .await
+            .map_err(|_| "forward recv failed")?;
+        // skip the stray packets which are not the response to our request
+        if len >= 2 && buf[0..2] == request[0..2] {
+            return Message::from_vec(&buf[..len]).map_err(|_| "invalid forward response");
+        }
+    }
+}
+
+async fn forward_tcp(server: SocketAddr, request: &[u8]) -> Result<Message, &'static str> {
+    let mut stream = TcpStream::connect(server)
+        .await
+        .map_err(|_| "forward connect failed")?;
+    let data = super::stream::encode_framed(request).ok_or("forward request too large")?;
+    stream
+        .write_all(&data)
+        .await
+        .map_err(|_| "forward send failed")?;
+
+    let len = stream.read_u16().await.map_err(|_| "forward recv failed")?;
+    let mut buf = vec![0u8; len as usize];
+    stream
+        .read_exact(&mut buf)
+        .await
+        .map_err(|_| "forward recv failed")?;
+    Message::from_vec(&buf).map_err(|_| "invalid forward response")
+}
+
+/// Use the remaining ttl of the resolved record, but no more than the configured max value
+fn answer_ttl(record_ttl: Option<Duration>, max_ttl: u32) -> u32 {
+    match record_ttl {
+        Some(ttl) => u32::try_from(ttl.as_secs())
+            .unwrap_or(u32::MAX)
+            .min(max_ttl),
+        None => max_ttl,
+    }
+}
+
+fn response_max_age(rsp: &Message, max_ttl: u32) -> u32 {
+    rsp.answers()
+        .iter()
+        .map(|r| r.ttl())
+        .min()
+        .unwrap_or(max_ttl)
+        .min(max_ttl)
+}
+
+fn error_response(e: &ResolveError) -> (ResponseCode, &'static str) {
+    match e {
+        ResolveError::FromServer(ResolveServerError::NotFound) => {
+            (ResponseCode::NXDomain, "domain not found")
+        }
+        ResolveError::FromServer(ResolveServerError::Refused) => {
+            (ResponseCode::Refused, "refused by upstream")
+        }
+        ResolveError::EmptyDomain => (ResponseCode::FormErr, "empty domain"),
+        ResolveError::FromServer(_) => (ResponseCode::ServFail, "upstream server error"),
+        ResolveError::FromDriver(_) => (ResponseCode::ServFail, "resolver driver error"),
+        ResolveError::FromLocal(_) => (ResponseCode::ServFail, "resolver local error"),
+        ResolveError::UnexpectedError(s) => (ResponseCode::ServFail, s),
+    }
+}
+
+fn encode_response(mut rsp: Message, max_size: usize) -> Option<Vec<u8>> {
+    let data = rsp.to_vec().ok()?;
+    if data.len() <= max_size {
+        return Some(data);
+    }
+
+    // set the TC bit and let the client retry over tcp
+    let _ = rsp.take_answers();
+    rsp.set_truncated(true);
+    rsp.to_vec().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::server::ServerConfig;
+    use g3_daemon::server::ClientConnectionInfo;
+    use g3_resolver::ResolveLocalError;
+    use hickory_proto::op::Query;
+    use hickory_proto::rr::Name;
+    use hickory_proto::rr::rdata::TXT;
+    use tokio::net::TcpListener;
+    use yaml_rust::{Yaml, YamlLoader};
+
+    fn build_ctx(extra: &str) -> DnsQueryContext {
+        let conf = format!("name: dns\ntype: dns_server\nresolver: not-existed\n{extra}");
+        let v = YamlLoader::load_from_str(&conf).unwrap();
+        let Yaml::Hash(map) = &v[0] else {
+            panic!("not a map");
+        };
+        let config = DnsServerConfig::parse(map, None).unwrap();
+        let stats = Arc::new(DnsServerStats::new(config.name()));
+        DnsQueryContext::new(Arc::new(config), stats, None)
+    }
+
+    fn task_notes() -> ServerTaskNotes {
+        let cc_info = ClientConnectionInfo::new(
+            SocketAddr::from(([127, 0, 0, 1], 10000)),
+            SocketAddr::from(([127, 0, 0, 1], 53)),
+        );
+        ServerTaskNotes::new(cc_info, None, Duration::ZERO)
+    }
+
+    fn build_query(id: u16, name: &str, record_type: RecordType) -> Vec<u8> {
+        let mut req = Message::new();
+        req.set_id(id)
+            .set_message_type(MessageType::Query)
+            .set_recursion_desired(true)
+            .add_query(Query::query(Name::from_ascii(name).unwrap(), record_type));
+        req.to_vec().unwrap()
+    }
+
+    fn build_txt_response(req: &[u8], truncated: bool) -> Vec<u8> {
+        let req = Message::from_vec(req).unwrap();
+        let query = req.queries()[0].clone();
+        let mut rsp = Message::new();
+        rsp.set_id(req.id())
+            .set_message_type(MessageType::Response)
+            .set_truncated(truncated)
+            .add_query(query.clone());
+        if !truncated {
+            let rdata = RData::TXT(TXT::new(vec!["v=spf1 -all".to_string()]));
+            rsp.add_answer(Record::from_rdata(query.name().clone(), 30, rdata));
+        }
+        rsp.to_vec().unwrap()
+    }
+
+    #[test]
+    fn ttl() {
+        assert_eq!(answer_ttl(None, 60), 60);
+        assert_eq!(answer_ttl(Some(Duration::from_secs(10)), 60), 10);
+        assert_eq!(answer_ttl(Some(Duration::from_millis(10500)), 60), 10);
+        assert_eq!(answer_ttl(Some(Duration::from_secs(3600)), 60), 60);
+        assert_eq!(answer_ttl(Some(Duration::ZERO), 60), 0);
+        assert_eq!(answer_ttl(Some(Duration::from_secs(u64::MAX)), 60), 60);
+    }
+
+    #[test]
+    fn max_age() {
+        let name = Name::from_ascii("example.net.").unwrap();
+        let mut rsp = Message::new();
+        assert_eq!(response_max_age(&rsp, 60), 60);
+
+        let ip = Ipv4Addr::new(192, 0, 2, 1);
+        rsp.add_answer(Record::from_rdata(name.clone(), 30, RData::A(A(ip))));
+        rsp.add_answer(Record::from_rdata(name.clone(), 20, RData::A(A(ip))));
+        assert_eq!(response_max_age(&rsp, 60), 20);
+        assert_eq!(response_max_age(&rsp, 10), 10);
+    }
+
+    #[test]
+    fn error_rcode() {
+        let e = ResolveError::FromServer(ResolveServerError::NotFound);
+        assert_eq!(error_response(&e).0, ResponseCode::NXDomain);
+        let e = ResolveError::FromServer(ResolveServerError::Refused);
+        assert_eq!(error_response(&e).0, ResponseCode::Refused);
+        let e = ResolveError::FromServer(ResolveServerError::ServFail);
+        assert_eq!(error_response(&e).0, ResponseCode::ServFail);
+        assert_eq!(
+            error_response(&ResolveError::EmptyDomain).0,
+            ResponseCode::FormErr
+        );
+        let e = ResolveError::FromLocal(ResolveLocalError::DriverTimedOut);
+        assert_eq!(error_response(&e).0, ResponseCode::ServFail);
+        let e = ResolveError::UnexpectedError("resolve timeout");
+        assert_eq!(
+            error_response(&e),
+            (ResponseCode::ServFail, "resolve timeout")
+        );
+    }
+
+    #[test]
+    fn query_permit() {
+        let ctx = build_ctx("max_concurrent_queries: 2");
+        let p1 = ctx.try_acquire_query_permit().unwrap();
+        let p2 = ctx.try_acquire_query_permit().unwrap();
+        assert!(ctx.try_acquire_query_permit().is_none());
+        drop(p1);
+        let _p3 = ctx.try_acquire_query_permit().unwrap();
+        assert!(ctx.try_acquire_query_permit().is_none());
+        drop(p2);
+        assert!(ctx.try_acquire_query_permit().is_some());
+    }
+
+    #[tokio::test]
+    async fn unsupported_type() {
+        let ctx = build_ctx("");
+        let req = build_query(1, "example.net.", RecordType::TXT);
+        let rsp = ctx
+            .answer(&task_notes(), DnsQueryProtocol::Udp, &req)
+            .await
+            .unwrap();
+        let rsp = Message::from_vec(&rsp.data).unwrap();
+        assert_eq!(rsp.id(), 1);
+        assert_eq!(rsp.response_code(), ResponseCode::NotImp);
+    }
+
+    #[tokio::test]
+    async fn no_resolver() {
+        let ctx = build_ctx("");
+        let req = build_query(2, "example.net.", RecordType::A);
+        let rsp = ctx
+            .answer(&task_notes(), DnsQueryProtocol::Tcp, &req)
+            .await
+            .unwrap();
+        let rsp = Message::from_vec(&rsp.data).unwrap();
+        assert_eq!(rsp.id(), 2);
+        assert_eq!(rsp.response_code(), ResponseCode::ServFail);
+    }
+
+    #[tokio::test]
+    async fn forward() {
+        let udp_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server = udp_socket.local_addr().unwrap();
+        let tcp_listener = TcpListener::bind(server).await.unwrap();
+
+        // the udp server always set the TC bit, after sending a stray packet first
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 1500];
+            loop {
+                let (len, peer) = udp_socket.recv_from(&mut buf).await.unwrap();
+                let req = &buf[..len];
+                let mut stray = build_txt_response(req, false);
+                stray[0] = !stray[0];
+                udp_socket.send_to(&stray, peer).await.unwrap();
+                let rsp = build_txt_response(req, true);
+                udp_socket.send_to(&rsp, peer).await.unwrap();
+            }
+        });
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = tcp_listener.accept().await.unwrap();
+                let len = stream.read_u16().await.unwrap();
+                let mut req = vec![0u8; len as usize];
+                stream.read_exact(&mut req).await.unwrap();
+                let rsp = build_txt_response(&req, false);
+                let data = super::super::stream::encode_framed(&rsp).unwrap();
+                stream.write_all(&data).await.unwrap();
+            }
+        });
+
+        let ctx = build_ctx(&format!("forward_server: {server}"));
+
+        let req = build_query(3, "example.net.", RecordType::TXT);
+        let rsp = ctx
+            .answer(&task_notes(), DnsQueryProtocol::Udp, &req)
+            .await
+            .unwrap();
+        let rsp = Message::from_vec(&rsp.data).unwrap();
+        assert_eq!(rsp.id(), 3);
+        assert!(rsp.truncated());
+        assert_eq!(rsp.answer_count(), 0);
+
+        let req = build_query(4, "example.net.", RecordType::TXT);
+        let rsp = ctx
+            .answer(&task_notes(), DnsQueryProtocol::Tcp, &req)
+            .await
+            .unwrap();
+        assert_eq!(rsp.max_age, 30);
+        let rsp = Message::from_vec(&rsp.data).unwrap();
+        assert_eq!(rsp.id(), 4);
+        assert!(!rsp.truncated());
+        assert_eq!(rsp.response_code(), ResponseCode::NoError);
+        assert_eq!(rsp.answer_count(), 1);
+    }
+
+    #[test]
+    fn truncate_udp_response() {
+        let name = Name::from_ascii("example.net.").unwrap();
+        let mut rsp = Message::new();
+        rsp.set_id(1)
+            .set_message_type(MessageType::Response)
+            .add_query(Query::query(name.clone(), RecordType::A));
+        for i in 0..64u8 {
+            let ip = Ipv4Addr::new(192, 0, 2, i);
+            rsp.add_answer(Record::from_rdata(name.clone(), 60, RData::A(A(ip))));
+        }
+
+        let data = encode_response(rsp.clone(), u16::MAX as usize).unwrap();
+        let full = Message::from_vec(&data).unwrap();
+        assert!(!full.truncated());
+        assert_eq!(full.answer_count(), 64);
+
+        let data = encode_response(rsp, UDP_MIN_PAYLOAD).unwrap();
+        assert!(data.len() <= UDP_MIN_PAYLOAD);
+        let truncated = Message::from_vec(&data).unwrap();
+        assert!(truncated.truncated());
+        assert_eq!(truncated.answer_count(), 0);
+        assert_eq!(truncated.queries().len(), 1);
+    }
+}
@@ -0,0 +1,363 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use bytes::BytesMut;
+#[cfg(feature = "quic")]
+use quinn::Connection;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::broadcast;
+use tokio_rustls::server::TlsStream;
+
+use g3_daemon::listen::{
+    AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime, ReceiveUdpRuntime,
+};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
+use g3_io_ext::OnceBufReader;
+use g3_openssl::SslStream;
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::metrics::NodeName;
+
+use super::DnsServerStats;
+use super::query::{DnsQueryContext, DnsQueryProtocol};
+use crate::config::server::dns_server::DnsServerConfig;
+use crate::config::server::{AnyServerConfig, ServerConfig};
+use crate::serve::{
+    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
+    ServerRegistry, ServerStats, ServerTaskNotes, WrapArcServer,
+};
+
+/// the HTTP/2 connection preface starts with this
+const H2_PREFACE_PREFIX: &[u8] = b"PRI ";
+
+pub(crate) struct DnsServer {
+    config: Arc<DnsServerConfig>,
+    server_stats: Arc<DnsServerStats>,
+    listen_stats: Arc<ListenStats>,
+    query_ctx: Arc<DnsQueryContext>,
+    ingress_net_filter: Option<AclNetworkRule>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+    quit_policy: Arc<ServerQuitPolicy>,
+    reload_version: usize,
+}
+
+impl DnsServer {
+    fn new(
+        config: Arc<DnsServerConfig>,
+        server_stats: Arc<DnsServerStats>,
+        listen_stats: Arc<ListenStats>,
+        version: usize,
+    ) -> Self {
+        let reload_sender = crate::serve::new_reload_notify_channel();
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+
+        let task_logger = config.get_task_logger();
+
+        server_stats.set_extra_tags(config.extra_metrics_tags.clone());
+
+        let query_ctx = Arc::new(DnsQueryContext::new(
+            config.clone(),
+            server_stats.clone(),
+            task_logger,
+        ));
+
+        DnsServer {
+            config,
+            server_stats,
+            listen_stats,
+            query_ctx,
+            ingress_net_filter,
+            reload_sender,
+            quit_policy: Arc::new(ServerQuitPolicy::default()),
+            reload_version: version,
+        }
+    }
+
+    pub(crate) fn prepare_initial(config: DnsServerConfig) -> anyhow::Result<ArcServerInternal> {
+        let config = Arc::new(config);
+        let server_stats = Arc::new(DnsServerStats::new(config.name()));
+        let listen_stats = Arc::new(ListenStats::new(config.name()));
+
+        let server = DnsServer::new(config, server_stats, listen_stats, 1);
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyServerConfig) -> anyhow::Result<Self> {
+        if let AnyServerConfig::DnsServer(config) = config {
+            let config = Arc::new(config);
+            let server_stats = Arc::clone(&self.server_stats);
+            let listen_stats = Arc::clone(&self.listen_stats);
+
+            let server =
+                DnsServer::new(config, server_stats, listen_stats, self.reload_version + 1);
+            Ok(server)
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.r#type(),
+                config.r#type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    self.listen_stats.add_dropped();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    async fn run_tls_task<S>(&self, mut stream: S, cc_info: ClientConnectionInfo)
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin,
+    {
+        // DoH and DoT share the same port, detect the HTTP/2 preface to tell them apart
+        let mut buf = BytesMut::zeroed(H2_PREFACE_PREFIX.len());
+        match tokio::time::timeout(self.config.tcp_idle_timeout, stream.read_exact(&mut buf)).await
+        {
+            Ok(Ok(_)) => {}
+            Ok(Err(_)) | Err(_) => return,
+        }
+
+        let is_h2 = buf.as_ref() == H2_PREFACE_PREFIX;
+        let stream = OnceBufReader::new(stream, buf);
+        if is_h2 {
+            super::doh::serve_doh(&self.query_ctx, stream, &cc_info).await;
+        } else {
+            super::stream::serve_framed(&self.query_ctx, stream, &cc_info, DnsQueryProtocol::Dot)
+                .await;
+        }
+    }
+}
+
+impl ServerInternal for DnsServer {
+    fn _clone_config(&self) -> AnyServerConfig {
+        AnyServerConfig::DnsServer(self.config.as_ref().clone())
+    }
+
+    fn _depend_on_server(&self, _name: &NodeName) -> bool {
+        false
+    }
+
+    fn _depend_on_resolver(&self, name: &NodeName) -> bool {
+        self.config.resolver.eq(name)
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_next_servers_in_place(&self) {}
+
+    fn _update_escaper_in_place(&self) {}
+
+    fn _update_user_group_in_place(&self) {}
+
+    fn _update_audit_handle_in_place(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn _update_resolver_in_place(&self) {
+        self.query_ctx.update_resolver();
+    }
+
+    fn _reload_with_old_notifier(
+        &self,
+        config: AnyServerConfig,
+        _registry: &mut ServerRegistry,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(
+        &self,
+        config: AnyServerConfig,
+        _registry: &mut ServerRegistry,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, server: ArcServer) -> anyhow::Result<()> {
+        if let Some(listen_config) = &self.config.listen {
+            let runtime =
+                ReceiveUdpRuntime::new(WrapArcServer(server.clone()), listen_config.clone());
+            runtime.run_all_instances(self.config.listen_in_worker, &self.reload_sender)?;
+        }
+        if let Some(listen_config) = &self.config.tcp_listen {
+            let listen_stats = server.get_listen_stats();
+            let runtime = ListenTcpRuntime::new(WrapArcServer(server), listen_stats);
+            runtime.run_all_instances(
+                listen_config,
+                self.config.listen_in_worker,
+                &self.reload_sender,
+            )?;
+        }
+        self.server_stats.set_online();
+        Ok(())
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+        self.server_stats.set_offline();
+    }
+}
+
+impl BaseServer for DnsServer {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.r#type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+#[async_trait]
+impl AcceptTcpServer for DnsServer {
+    async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
+        let client_addr = cc_info.client_addr();
+        self.server_stats.add_conn();
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        if cc_info
+            .tcp_sock_set_raw_opts(&self.config.tcp_misc_opts, true)
+            .is_err()
+        {
+            return;
+        }
+
+        super::stream::serve_framed(&self.query_ctx, stream, &cc_info, DnsQueryProtocol::Tcp).await;
+    }
+}
+
+#[async_trait]
+impl AcceptQuicServer for DnsServer {
+    #[cfg(feature = "quic")]
+    async fn run_quic_task(&self, _connection: Connection, _cc_info: ClientConnectionInfo) {}
+}
+
+#[async_trait]
+impl Server for DnsServer {
+    fn escaper(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn get_server_stats(&self) -> Option<ArcServerStats> {
+        Some(self.server_stats.clone())
+    }
+
+    fn get_listen_stats(&self) -> Arc<ListenStats> {
+        Arc::clone(&self.listen_stats)
+    }
+
+    fn alive_count(&self) -> i32 {
+        self.server_stats.get_alive_count()
+    }
+
+    #[inline]
+    fn quit_policy(&self) -> &Arc<ServerQuitPolicy> {
+        &self.quit_policy
+    }
+
+    async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo) {
+        let client_addr = cc_info.client_addr();
+        self.server_stats.add_conn();
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        self.run_tls_task(stream, cc_info).await
+    }
+
+    async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
+        let client_addr = cc_info.client_addr();
+        self.server_stats.add_conn();
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        self.run_tls_task(stream, cc_info).await
+    }
+
+    fn receive_udp_packet(
+        &self,
+        socket: &Arc<UdpSocket>,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        self.server_stats.add_conn();
+        // the listen socket may be a dual stack one, always reply to the raw peer address
+        let peer_addr = client_addr;
+        let client_addr = SocketAddr::new(client_addr.ip().to_canonical(), client_addr.port());
+        let server_addr = SocketAddr::new(server_addr.ip().to_canonical(), server_addr.port());
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        // limit the concurrent queries, as a new task is spawned for each packet
+        let Some(permit) = self.query_ctx.try_acquire_query_permit() else {
+            self.listen_stats.add_dropped();
+            return;
+        };
+
+        let mut cc_info = ClientConnectionInfo::new(client_addr, server_addr);
+        cc_info.set_worker_id(worker_id);
+        let task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+
+        let query_ctx = self.query_ctx.clone();
+        let socket = socket.clone();
+        let packet = packet.to_vec();
+        tokio::spawn(async move {
+            if let Some(rsp) = query_ctx
+                .answer(&task_notes, DnsQueryProtocol::Udp, &packet)
+                .await
+            {
+                let _ = socket.send_to(&rsp.data, peer_addr).await;
+            }
+            drop(permit);
+        });
+    }
+}
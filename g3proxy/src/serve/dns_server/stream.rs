@@ -0,0 +1,89 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use g3_daemon::server::ClientConnectionInfo;
+
+use super::query::{DnsQueryContext, DnsQueryProtocol};
+use crate::serve::ServerTaskNotes;
+
+/// Serve dns messages framed with a 2-byte length prefix, as defined in RFC 1035 / RFC 7858
+pub(super) async fn serve_framed<S>(
+    ctx: &Arc<DnsQueryContext>,
+    mut stream: S,
+    cc_info: &ClientConnectionInfo,
+    protocol: DnsQueryProtocol,
+) where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let idle_timeout = ctx.config.tcp_idle_timeout;
+    let mut buf = Vec::with_capacity(512);
+    loop {
+        let len = match tokio::time::timeout(idle_timeout, stream.read_u16()).await {
+            Ok(Ok(len)) => len as usize,
+            Ok(Err(_)) | Err(_) => break,
+        };
+        if len == 0 {
+            break;
+        }
+
+        buf.resize(len, 0);
+        match tokio::time::timeout(idle_timeout, stream.read_exact(&mut buf)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(_)) | Err(_) => break,
+        }
+
+        let task_notes = ServerTaskNotes::new(cc_info.clone(), None, Duration::ZERO);
+        let Some(rsp) = ctx.answer(&task_notes, protocol, &buf).await else {
+            continue;
+        };
+        let Some(data) = encode_framed(&rsp.data) else {
+            break;
+        };
+        if stream.write_all(&data).await.is_err() {
+            break;
+        }
+        if stream.flush().await.is_err() {
+            break;
+        }
+    }
+
+    let _ = stream.shutdown().await;
+}
+
+/// Add the 2-byte length prefix, return None if the message is too large
+pub(super) fn encode_framed(msg: &[u8]) -> Option<Vec<u8>> {
+    let len = u16::try_from(msg.len()).ok()?;
+    let mut data = Vec::with_capacity(msg.len() + 2);
+    data.extend_from_slice(&len.to_be_bytes());
+    data.extend_from_slice(msg);
+    Some(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn framed() {
+        let data = encode_framed(b"abc").unwrap();
+        assert_eq!(data, b"\x00\x03abc");
+
+        let data = encode_framed(&[]).unwrap();
+        assert_eq!(data, b"\x00\x00");
+
+        let msg = vec![0u8; u16::MAX as usize];
+        let data = encode_framed(&msg).unwrap();
+        assert_eq!(&data[..2], b"\xff\xff");
+        assert_eq!(data.len(), msg.len() + 2);
+
+        let msg = vec![0u8; u16::MAX as usize + 1];
+        assert!(encode_framed(&msg).is_none());
+    }
+}
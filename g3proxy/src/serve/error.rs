@@ -114,8 +114,14 @@ pub(crate) enum ServerTaskError {
     ClosedEarlyByClient,
     #[error("canceled as user blocked")]
     CanceledAsUserBlocked,
+    #[error("canceled as user removed")]
+    CanceledAsUserRemoved,
+    #[error("canceled as user expired")]
+    CanceledAsUserExpired,
     #[error("canceled as server quit")]
     CanceledAsServerQuit,
+    #[error("canceled as task killed")]
+    CanceledAsTaskKilled,
     #[error("idle after {0:?} x {1}")]
     Idle(Duration, usize),
     #[error("{0} interception error: {1}")]
@@ -160,7 +166,10 @@ impl ServerTaskError {
             ServerTaskError::ClosedByClient => "ClosedByClient",
             ServerTaskError::ClosedEarlyByClient => "ClosedEarlyByClient",
             ServerTaskError::CanceledAsUserBlocked => "CanceledAsUserBlocked",
+            ServerTaskError::CanceledAsUserRemoved => "CanceledAsUserRemoved",
+            ServerTaskError::CanceledAsUserExpired => "CanceledAsUserExpired",
             ServerTaskError::CanceledAsServerQuit => "CanceledAsServerQuit",
+            ServerTaskError::CanceledAsTaskKilled => "CanceledAsTaskKilled",
             ServerTaskError::Idle(_, _) => "Idle",
             ServerTaskError::InterceptionError(_, _) => "InterceptionError",
             ServerTaskError::Finished => "Finished",
@@ -171,6 +180,17 @@ impl ServerTaskError {
 
 pub(crate) type ServerTaskResult<T> = Result<T, ServerTaskError>;
 
+impl From<IdleForceQuitReason> for ServerTaskError {
+    fn from(reason: IdleForceQuitReason) -> Self {
+        match reason {
+            IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
+            IdleForceQuitReason::UserRemoved => ServerTaskError::CanceledAsUserRemoved,
+            IdleForceQuitReason::UserExpired => ServerTaskError::CanceledAsUserExpired,
+            IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
+        }
+    }
+}
+
 impl From<ResolveError> for ServerTaskError {
     fn from(e: ResolveError) -> Self {
         if matches!(e, ResolveError::FromServer(_)) {
@@ -369,10 +389,7 @@ impl From<H1ReqmodAdaptationError> for ServerTaskError {
             H1ReqmodAdaptationError::HttpUpstreamWriteIdle => {
                 ServerTaskError::UpstreamAppTimeout("idle while writing")
             }
-            H1ReqmodAdaptationError::IdleForceQuit(reason) => match reason {
-                IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
-                IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
-            },
+            H1ReqmodAdaptationError::IdleForceQuit(reason) => ServerTaskError::from(reason),
             e => ServerTaskError::InternalAdapterError(anyhow!("reqmod: {e}")),
         }
     }
@@ -399,10 +416,7 @@ impl From<H1RespmodAdaptationError> for ServerTaskError {
             H1RespmodAdaptationError::HttpClientWriteIdle => {
                 ServerTaskError::ClientAppTimeout("idle while writing")
             }
-            H1RespmodAdaptationError::IdleForceQuit(reason) => match reason {
-                IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
-                IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
-            },
+            H1RespmodAdaptationError::IdleForceQuit(reason) => ServerTaskError::from(reason),
             e => ServerTaskError::InternalAdapterError(anyhow!("respmod: {e}")),
         }
     }
@@ -425,10 +439,7 @@ impl From<SmtpAdaptationError> for ServerTaskError {
             SmtpAdaptationError::SmtpUpstreamWriteIdle => {
                 ServerTaskError::UpstreamAppTimeout("idle while writing smtp mail message")
             }
-            SmtpAdaptationError::IdleForceQuit(reason) => match reason {
-                IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
-                IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
-            },
+            SmtpAdaptationError::IdleForceQuit(reason) => ServerTaskError::from(reason),
             e => ServerTaskError::InternalAdapterError(anyhow!("reqmod: {e}")),
         }
     }
@@ -448,11 +459,25 @@ impl From<ImapAdaptationError> for ServerTaskError {
             ImapAdaptationError::ImapUpstreamWriteIdle => {
                 ServerTaskError::UpstreamAppTimeout("idle while writing imap mail message")
             }
-            ImapAdaptationError::IdleForceQuit(reason) => match reason {
-                IdleForceQuitReason::UserBlocked => ServerTaskError::CanceledAsUserBlocked,
-                IdleForceQuitReason::ServerQuit => ServerTaskError::CanceledAsServerQuit,
-            },
+            ImapAdaptationError::IdleForceQuit(reason) => ServerTaskError::from(reason),
             e => ServerTaskError::InternalAdapterError(anyhow!("reqmod: {e}")),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn idle_force_quit() {
+        let e = ServerTaskError::from(IdleForceQuitReason::UserBlocked);
+        assert_eq!(e.brief(), "CanceledAsUserBlocked");
+        let e = ServerTaskError::from(IdleForceQuitReason::UserRemoved);
+        assert_eq!(e.brief(), "CanceledAsUserRemoved");
+        let e = ServerTaskError::from(IdleForceQuitReason::UserExpired);
+        assert_eq!(e.brief(), "CanceledAsUserExpired");
+        let e = ServerTaskError::from(IdleForceQuitReason::ServerQuit);
+        assert_eq!(e.brief(), "CanceledAsServerQuit");
+    }
+}
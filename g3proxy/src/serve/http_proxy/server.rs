@@ -40,16 +40,19 @@ use crate::auth::UserGroup;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::http_cache::HttpCache;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ServerRegistry, ServerStats, ServerTaskRegistry, WrapArcServer,
 };
 
 pub(crate) struct HttpProxyServer {
     config: Arc<HttpProxyServerConfig>,
     server_stats: Arc<HttpProxyServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    http_cache: Option<Arc<HttpCache>>,
     tls_acceptor: Option<TlsAcceptor>,
     tls_accept_timeout: Duration,
     tls_client_config: Arc<OpensslClientConfig>,
@@ -70,8 +73,10 @@ impl HttpProxyServer {
     fn new(
         config: Arc<HttpProxyServerConfig>,
         server_stats: Arc<HttpProxyServerStats>,
+        task_registry: Arc<ServerTaskRegistry>,
         listen_stats: Arc<ListenStats>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        http_cache: Option<Arc<HttpCache>>,
         version: usize,
     ) -> anyhow::Result<HttpProxyServer> {
         let reload_sender = crate::serve::new_reload_notify_channel();
@@ -118,8 +123,10 @@ impl HttpProxyServer {
         let server = HttpProxyServer {
             config,
             server_stats,
+            task_registry,
             listen_stats,
             tls_rolling_ticketer,
+            http_cache,
             tls_acceptor,
             tls_accept_timeout,
             tls_client_config: Arc::new(tls_client_config),
@@ -143,6 +150,7 @@ impl HttpProxyServer {
     ) -> anyhow::Result<ArcServerInternal> {
         let config = Arc::new(config);
         let server_stats = Arc::new(HttpProxyServerStats::new(config.name()));
+        let task_registry = Arc::new(ServerTaskRegistry::default());
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
@@ -153,9 +161,22 @@ impl HttpProxyServer {
         } else {
             None
         };
+        let http_cache = match &config.http_cache {
+            Some(c) => Some(Arc::new(
+                HttpCache::new(c).context("failed to create http cache")?,
+            )),
+            None => None,
+        };
 
-        let server =
-            HttpProxyServer::new(config, server_stats, listen_stats, tls_rolling_ticketer, 1)?;
+        let server = HttpProxyServer::new(
+            config,
+            server_stats,
+            task_registry,
+            listen_stats,
+            tls_rolling_ticketer,
+            http_cache,
+            1,
+        )?;
         Ok(Arc::new(server))
     }
 
@@ -163,6 +184,7 @@ impl HttpProxyServer {
         if let AnyServerConfig::HttpProxy(config) = config {
             let config = Arc::new(config);
             let server_stats = Arc::clone(&self.server_stats);
+            let task_registry = Arc::clone(&self.task_registry);
             let listen_stats = Arc::clone(&self.listen_stats);
 
             let tls_rolling_ticketer = if self.config.tls_ticketer.eq(&config.tls_ticketer) {
@@ -175,12 +197,23 @@ impl HttpProxyServer {
             } else {
                 None
             };
+            let http_cache = if self.config.http_cache.eq(&config.http_cache) {
+                self.http_cache.clone()
+            } else if let Some(c) = &config.http_cache {
+                Some(Arc::new(
+                    HttpCache::new(c).context("failed to create http cache")?,
+                ))
+            } else {
+                None
+            };
 
             let server = HttpProxyServer::new(
                 config,
                 server_stats,
+                task_registry,
                 listen_stats,
                 tls_rolling_ticketer,
+                http_cache,
                 self.reload_version + 1,
             )?;
             Ok(server)
@@ -197,9 +230,11 @@ impl HttpProxyServer {
         Arc::new(CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
+            http_cache: self.http_cache.clone(),
             cc_info,
             tls_client_config: self.tls_client_config.clone(),
             task_logger: self.task_logger.clone(),
@@ -464,6 +499,10 @@ impl Server for HttpProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
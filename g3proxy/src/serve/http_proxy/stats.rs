@@ -13,7 +13,8 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    ServerForbiddenSnapshot, ServerForbiddenStats, ServerHttpCacheSnapshot, ServerHttpCacheStats,
+    ServerPerTaskStats, ServerStats,
 };
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -27,6 +28,7 @@ pub(crate) struct HttpProxyServerStats {
     conn_total: AtomicU64,
 
     pub forbidden: ServerForbiddenStats,
+    pub http_cache: ServerHttpCacheStats,
 
     pub task_http_untrusted: ServerPerTaskStats,
     pub task_http_connect: ServerPerTaskStats,
@@ -47,6 +49,7 @@ impl HttpProxyServerStats {
             online: AtomicIsize::new(0),
             conn_total: AtomicU64::new(0),
             forbidden: Default::default(),
+            http_cache: Default::default(),
             task_http_untrusted: Default::default(),
             task_http_connect: Default::default(),
             task_http_forward: Default::default(),
@@ -128,6 +131,10 @@ impl ServerStats for HttpProxyServerStats {
         self.forbidden.snapshot()
     }
 
+    fn http_cache_snapshot(&self) -> Option<ServerHttpCacheSnapshot> {
+        Some(self.http_cache.snapshot())
+    }
+
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         Some(UntrustedTaskStatsSnapshot {
             task_total: self.task_http_untrusted.get_task_total(),
@@ -9,6 +9,7 @@ use std::time::Duration;
 
 use http::Version;
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
@@ -28,8 +29,8 @@ use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TcpConnection,
 };
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    RegisteredTask, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct HttpProxyConnectTask {
@@ -339,6 +340,13 @@ impl HttpProxyConnectTask {
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_http_connect.add_task();
         self.ctx.server_stats.task_http_connect.inc_alive_task();
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -593,4 +601,8 @@ impl StreamTransitTask for HttpProxyConnectTask {
     fn user(&self) -> Option<&User> {
         self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref())
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.task_notes.cancel_token()
+    }
 }
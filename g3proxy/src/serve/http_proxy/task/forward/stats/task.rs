@@ -6,6 +6,7 @@
 use g3_daemon::stat::task::TcpStreamConnectionStats;
 
 use crate::module::http_forward::HttpForwardTaskRemoteStats;
+use crate::serve::RegisteredTaskStats;
 
 #[derive(Default)]
 pub(crate) struct HttpForwardTaskStats {
@@ -22,3 +23,21 @@ impl HttpForwardTaskRemoteStats for HttpForwardTaskStats {
         self.ups.write.add_bytes(size);
     }
 }
+
+impl RegisteredTaskStats for HttpForwardTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.read.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.write.get_bytes()
+    }
+
+    fn upstream_read_bytes(&self) -> u64 {
+        self.ups.read.get_bytes()
+    }
+
+    fn upstream_write_bytes(&self) -> u64 {
+        self.ups.write.get_bytes()
+    }
+}
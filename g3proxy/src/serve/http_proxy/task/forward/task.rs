@@ -5,11 +5,12 @@
 
 use std::borrow::Cow;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use anyhow::anyhow;
+use bytes::Bytes;
 use futures_util::FutureExt;
-use http::header;
+use http::{Method, header};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use g3_http::client::HttpForwardRemoteResponse;
@@ -37,6 +38,10 @@ use super::{
 use crate::audit::AuditContext;
 use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
+use crate::module::http_cache::{
+    HttpCacheBodyCollector, HttpCacheEntry, HttpCacheLookup, HttpCacheRequestContext,
+    HttpCacheStatus,
+};
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
     HttpForwardTaskNotes, HttpProxyClientResponse,
@@ -46,8 +51,8 @@ use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
 use crate::serve::{
-    ServerIdleChecker, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
-    ServerTaskResult, ServerTaskStage,
+    RegisteredTask, ServerIdleChecker, ServerStats, ServerTaskError, ServerTaskForbiddenError,
+    ServerTaskNotes, ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct HttpProxyForwardTask<'a> {
@@ -62,6 +67,7 @@ pub(crate) struct HttpProxyForwardTask<'a> {
     http_notes: HttpForwardTaskNotes,
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<HttpForwardTaskStats>,
+    cache_ctx: Option<HttpCacheRequestContext>,
     max_idle_count: usize,
     started: bool,
 }
@@ -110,6 +116,7 @@ impl<'a> HttpProxyForwardTask<'a> {
             http_notes,
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(HttpForwardTaskStats::default()),
+            cache_ctx: None,
             max_idle_count,
             started: false,
         }
@@ -156,6 +163,19 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.should_close = true;
     }
 
+    async fn reply_gateway_timeout<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let rsp = HttpProxyClientResponse::gateway_timeout(self.req.version, self.should_close);
+        // no custom header is set
+        if rsp.reply_err_to_request(clt_w).await.is_err() {
+            self.should_close = true;
+        } else {
+            self.http_notes.rsp_status = rsp.status();
+        }
+    }
+
     async fn reply_connect_err<W>(&mut self, e: &TcpConnectError, clt_w: &mut W)
     where
         W: AsyncWrite + Unpin,
@@ -243,7 +263,16 @@ impl<'a> HttpProxyForwardTask<'a> {
         CDW: AsyncWrite + Send + Unpin,
     {
         self.pre_start();
-        let e = match self.run_forward(clt_r, clt_w, fwd_ctx).await {
+        let cancel_token = self.task_notes.cancel_token();
+        let r = tokio::select! {
+            r = self.run_forward(clt_r, clt_w, fwd_ctx) => r,
+            _ = cancel_token.cancelled() => {
+                // the response may be incomplete
+                self.should_close = true;
+                Err(self.task_notes.cancel_error())
+            }
+        };
+        let e = match r {
             Ok(()) => ServerTaskError::Finished,
             Err(e) => e,
         };
@@ -255,6 +284,13 @@ impl<'a> HttpProxyForwardTask<'a> {
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_http_forward.add_task();
         self.ctx.server_stats.task_http_forward.inc_alive_task();
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -611,6 +647,43 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
+        if !audit_task {
+            if let Some(cache) = &self.ctx.http_cache {
+                let mut cache_ctx = HttpCacheRequestContext::new(
+                    cache,
+                    self.ctx.escaper.name().as_str(),
+                    &self.upstream,
+                    self.is_https,
+                    self.req,
+                    &self.ctx.request_header_rule_names(&self.task_notes),
+                );
+                match cache_ctx.lookup(self.req).await {
+                    HttpCacheLookup::Fresh(entry, body) => {
+                        self.ctx.server_stats.http_cache.add_hit();
+                        self.http_notes.cache_status = Some(HttpCacheStatus::Hit);
+                        self.mark_relaying();
+                        let r = self.send_cached_response(clt_w, &entry, body).await;
+                        if r.is_err() || self.should_close {
+                            self.should_close = true;
+                            let _ = clt_w.shutdown().await;
+                        }
+                        return r;
+                    }
+                    HttpCacheLookup::Stale => {}
+                    HttpCacheLookup::Miss => {
+                        self.ctx.server_stats.http_cache.add_miss();
+                        self.http_notes.cache_status = Some(HttpCacheStatus::Miss);
+                        if cache_ctx.only_if_cached() {
+                            self.reply_gateway_timeout(clt_w).await;
+                            return Ok(());
+                        }
+                    }
+                    HttpCacheLookup::Bypass => {}
+                }
+                self.cache_ctx = Some(cache_ctx);
+            }
+        }
+
         fwd_ctx.prepare_connection(&self.upstream, self.is_https);
 
         if let Some(mut connection) = fwd_ctx
@@ -1148,9 +1221,15 @@ impl<'a> HttpProxyForwardTask<'a> {
         let ups_w = &mut ups_c.0;
         let ups_r = &mut ups_c.1;
 
+        let validation_req = self
+            .cache_ctx
+            .as_ref()
+            .and_then(|c| c.validation_request(self.req));
+        let req = validation_req.as_ref().unwrap_or(self.req);
+
         self.http_notes.retry_new_connection = true;
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(req, None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -1384,8 +1463,8 @@ impl<'a> HttpProxyForwardTask<'a> {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -1403,8 +1482,8 @@ impl<'a> HttpProxyForwardTask<'a> {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -1547,6 +1626,7 @@ impl<'a> HttpProxyForwardTask<'a> {
                                 adapter.set_client_username(name.clone());
                             }
                             adapter.set_respond_shared_headers(adaptation_respond_shared_headers);
+                            self.apply_response_header_rules(&mut rsp_header.end_to_end_headers);
                             let r = self
                                 .send_response_with_adaptation(
                                     clt_w,
@@ -1627,25 +1707,107 @@ impl<'a> HttpProxyForwardTask<'a> {
         &mut self,
         clt_w: &mut W,
         ups_r: &mut R,
-        rsp_header: &HttpForwardRemoteResponse,
+        rsp_header: &mut HttpForwardRemoteResponse,
     ) -> ServerTaskResult<()>
     where
         R: AsyncBufRead + Unpin,
         W: AsyncWrite + Unpin,
     {
+        let mut cache_ctx = self.cache_ctx.take();
+        let mut cache_limit = None;
+        if let Some(cache_ctx) = &mut cache_ctx {
+            if let Some(entry) = cache_ctx.revalidate(rsp_header).await {
+                self.ctx.server_stats.http_cache.add_revalidated();
+                self.http_notes.cache_status = Some(HttpCacheStatus::Revalidated);
+                let body = if self.req.method == Method::HEAD {
+                    Bytes::new()
+                } else {
+                    entry.load_body().await.map_err(|_| {
+                        ServerTaskError::InternalServerError("failed to load cached response body")
+                    })?
+                };
+                return self.send_cached_response(clt_w, &entry, body).await;
+            }
+            if cache_ctx.is_validating() {
+                self.ctx.server_stats.http_cache.add_miss();
+                self.http_notes.cache_status = Some(HttpCacheStatus::Expired);
+            }
+            cache_ctx.invalidate_for_unsafe(&self.req.method, rsp_header.code);
+            cache_limit = cache_ctx.check_storable(self.req, rsp_header);
+        }
+        self.apply_response_header_rules(&mut rsp_header.end_to_end_headers);
+
         self.send_error_response = false;
 
-        if let Some(body_type) = rsp_header.body_type(&self.req.method) {
+        let body_type = rsp_header.body_type(&self.req.method);
+        let collected = if let Some(body_type) = body_type {
             let mut buf = Vec::with_capacity(self.ctx.server_config.tcp_copy.buffer_size());
             rsp_header.serialize_to(&mut buf);
             self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
-            self.send_response_body(buf, clt_w, ups_r, body_type).await
+            self.send_response_body(buf, clt_w, ups_r, body_type, cache_limit)
+                .await?
         } else {
             self.send_response_header(clt_w, rsp_header).await?;
             self.http_notes.rsp_status = rsp_header.code;
             self.http_notes.mark_rsp_no_body();
-            Ok(())
+            cache_limit.map(|_| Vec::new())
+        };
+
+        if let Some(cache_ctx) = cache_ctx {
+            if let Some(data) = collected {
+                if cache_ctx
+                    .store(
+                        self.req,
+                        rsp_header,
+                        body_type,
+                        data,
+                        self.ctx.server_config.body_line_max_len,
+                    )
+                    .await
+                {
+                    self.ctx.server_stats.http_cache.add_stored();
+                }
+            }
         }
+        Ok(())
+    }
+
+    async fn send_cached_response<W>(
+        &mut self,
+        clt_w: &mut W,
+        entry: &HttpCacheEntry,
+        body: Bytes,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let now = SystemTime::now();
+        let keep_alive = !self.should_close;
+        self.send_error_response = false;
+
+        let mut headers = entry.headers.clone();
+        self.apply_response_header_rules(&mut headers);
+
+        let buf = if g3_http::cache::request_is_conditional(&self.req.end_to_end_headers)
+            && g3_http::cache::response_not_modified(&self.req.end_to_end_headers, &entry.headers)
+        {
+            self.http_notes.rsp_status = 304;
+            entry.serialize_not_modified(self.req.version, &headers, now, keep_alive)
+        } else {
+            self.http_notes.rsp_status = entry.code;
+            let mut buf = entry.serialize_header(self.req.version, &headers, now, keep_alive);
+            buf.extend_from_slice(&body);
+            buf
+        };
+        self.http_notes.mark_rsp_recv_hdr();
+        self.http_notes.mark_rsp_no_body();
+
+        clt_w
+            .write_all_flush(&buf)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
     }
 
     async fn send_response_body<R, W>(
@@ -1654,14 +1816,16 @@ impl<'a> HttpProxyForwardTask<'a> {
         clt_w: &mut W,
         ups_r: &mut R,
         body_type: HttpBodyType,
-    ) -> ServerTaskResult<()>
+        cache_limit: Option<usize>,
+    ) -> ServerTaskResult<Option<Vec<u8>>>
     where
         R: AsyncBufRead + Unpin,
         W: AsyncWrite + Unpin,
     {
         let header_len = header.len() as u64;
-        let mut body_reader =
+        let body_reader =
             HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        let mut body_reader = HttpCacheBodyCollector::new(body_reader, cache_limit);
 
         let mut ups_to_clt = StreamCopy::with_data(
             &mut body_reader,
@@ -1682,7 +1846,8 @@ impl<'a> HttpProxyForwardTask<'a> {
                         Ok(_) => {
                             self.http_notes.mark_rsp_recv_all();
                             // clt_w is already flushed
-                            Ok(())
+                            drop(ups_to_clt);
+                            Ok(body_reader.take_collected())
                         }
                         Err(StreamCopyError::ReadFailed(e)) => {
                             if ups_to_clt.copied_size() < header_len {
@@ -1704,11 +1869,11 @@ impl<'a> HttpProxyForwardTask<'a> {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
+                            if let Some(reason) = user.blocked_reason() {
                                 if ups_to_clt.copied_size() < header_len {
                                     let _ = ups_to_clt.write_flush().await; // flush rsp header to client
                                 }
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                                return Err(ServerTaskError::from(reason));
                             }
 
                         }
@@ -1727,11 +1892,11 @@ impl<'a> HttpProxyForwardTask<'a> {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
                             if ups_to_clt.copied_size() < header_len {
                                 let _ = ups_to_clt.write_flush().await; // flush rsp header to client
                             }
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -1746,6 +1911,11 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    fn apply_response_header_rules(&self, headers: &mut HttpHeaderMap) {
+        self.ctx
+            .apply_response_header_rules(&self.task_notes, &self.upstream, self.req, headers);
+    }
+
     fn update_response_header(&self, rsp: &mut HttpForwardRemoteResponse) {
         // append headers to hop-by-hop headers, so they will pass to client without adaptation
         if let Some(server_id) = &self.ctx.server_config.server_id {
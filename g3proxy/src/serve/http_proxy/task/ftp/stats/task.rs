@@ -6,6 +6,7 @@
 use g3_daemon::stat::task::{TcpStreamConnectionStats, TcpStreamHalfConnectionStats};
 
 use crate::module::ftp_over_http::{FtpTaskRemoteControlStats, FtpTaskRemoteTransferStats};
+use crate::serve::RegisteredTaskStats;
 
 #[derive(Default)]
 pub(crate) struct FtpOverHttpServerStats {
@@ -40,3 +41,21 @@ impl FtpTaskRemoteTransferStats for FtpOverHttpTaskStats {
         self.ftp_server.transfer_write.add_bytes(size);
     }
 }
+
+impl RegisteredTaskStats for FtpOverHttpTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.http_client.read.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.http_client.write.get_bytes()
+    }
+
+    fn upstream_read_bytes(&self) -> u64 {
+        self.ftp_server.control_read.get_bytes() + self.ftp_server.transfer_read.get_bytes()
+    }
+
+    fn upstream_write_bytes(&self) -> u64 {
+        self.ftp_server.control_write.get_bytes() + self.ftp_server.transfer_write.get_bytes()
+    }
+}
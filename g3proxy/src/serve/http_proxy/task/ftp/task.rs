@@ -32,8 +32,8 @@ use crate::module::ftp_over_http::{BoxFtpRemoteConnection, FtpOverHttpTaskNotes,
 use crate::module::http_forward::HttpProxyClientResponse;
 use crate::module::tcp_connect::{TcpConnectError, TcpConnectTaskConf};
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    RegisteredTask, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 type HttpProxyFtpClient = FtpClient<
@@ -128,7 +128,16 @@ impl<'a> FtpOverHttpTask<'a> {
         CDW: AsyncWrite + Send + Unpin,
     {
         self.pre_start();
-        let e = match self.run_ftp(clt_r, clt_w).await {
+        let cancel_token = self.task_notes.cancel_token();
+        let r = tokio::select! {
+            r = self.run_ftp(clt_r, clt_w) => r,
+            _ = cancel_token.cancelled() => {
+                // the response may be incomplete
+                self.should_close = true;
+                Err(self.task_notes.cancel_error())
+            }
+        };
+        let e = match r {
             Ok(()) => ServerTaskError::Finished,
             Err(e) => e,
         };
@@ -140,6 +149,13 @@ impl<'a> FtpOverHttpTask<'a> {
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_ftp_over_http.add_task();
         self.ctx.server_stats.task_ftp_over_http.inc_alive_task();
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                self.ftp_notes.upstream(),
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -1277,8 +1293,8 @@ impl<'a> FtpOverHttpTask<'a> {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -1296,8 +1312,8 @@ impl<'a> FtpOverHttpTask<'a> {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -1480,8 +1496,8 @@ impl<'a> FtpOverHttpTask<'a> {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -1499,8 +1515,8 @@ impl<'a> FtpOverHttpTask<'a> {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -483,6 +483,8 @@ where
             }
             _ => unreachable!(),
         };
+        self.ctx
+            .apply_request_header_rules(&task_notes, &req.upstream, &mut req.inner);
 
         match req.body_reader.take() {
             Some(stream_r) => {
@@ -6,30 +6,101 @@
 use std::sync::Arc;
 
 use anyhow::Context;
+use arc_swap::ArcSwapOption;
 
-use g3_types::net::{OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConfig};
+use g3_acme::AcmeCertHolder;
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::metrics::NodeName;
+use g3_types::net::{
+    Host, OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConfig,
+    RustlsServerConfigBuilder, UpstreamAddr,
+};
 
 use crate::config::server::http_rproxy::HttpHostConfig;
+use crate::serve::{DynamicUpstream, ServerTaskError, ServerUpstreamDiscoverStats};
+
+/// Tls server config which can be replaced when a new ACME certificate is issued
+struct HttpHostTlsServer {
+    config: Arc<HttpHostConfig>,
+    ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    inner: ArcSwapOption<RustlsServerConfig>,
+}
+
+impl HttpHostTlsServer {
+    fn build(
+        config: &Arc<HttpHostConfig>,
+        ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    ) -> anyhow::Result<Arc<Self>> {
+        let server = build_tls_server(config, ticketer.clone())?;
+        let holder = Arc::new(HttpHostTlsServer {
+            config: config.clone(),
+            ticketer,
+            inner: ArcSwapOption::from_pointee(server),
+        });
+        if let Some(acme) = &config.acme {
+            g3_acme::spawn_cert_updater(&holder, acme);
+        }
+        Ok(holder)
+    }
+}
+
+impl AcmeCertHolder for HttpHostTlsServer {
+    fn reload_acme_cert(&self) -> anyhow::Result<()> {
+        let server = build_tls_server(&self.config, self.ticketer.clone())?;
+        self.inner.store(server.map(Arc::new));
+        Ok(())
+    }
+}
+
+fn build_tls_server(
+    config: &HttpHostConfig,
+    ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+) -> anyhow::Result<Option<RustlsServerConfig>> {
+    let Some(acme) = &config.acme else {
+        return config
+            .tls_server_builder
+            .as_ref()
+            .map(|builder| {
+                builder
+                    .build_with_ticketer(ticketer)
+                    .context("failed to build tls server")
+            })
+            .transpose();
+    };
+
+    let mut builder = config
+        .tls_server_builder
+        .clone()
+        .unwrap_or_else(RustlsServerConfigBuilder::empty);
+    if let Some(pair) = crate::module::acme::load_rustls_cert_pair(acme)? {
+        builder.push_cert_pair(pair);
+    }
+    if builder.check().is_err() {
+        // the ACME certificate has not been issued yet
+        return Ok(None);
+    }
+    let server = builder
+        .build_with_ticketer(ticketer)
+        .context("failed to build tls server")?;
+    Ok(Some(server))
+}
 
 pub(crate) struct HttpHost {
     pub(super) config: Arc<HttpHostConfig>,
-    pub(super) tls_server: Option<RustlsServerConfig>,
+    tls_server: Arc<HttpHostTlsServer>,
     pub(super) tls_client: Option<OpensslClientConfig>,
+    upstream: DynamicUpstream,
 }
 
 impl HttpHost {
     pub(super) fn try_build(
         config: &Arc<HttpHostConfig>,
         ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        upstream_log_name: String,
+        discover_stats: Arc<ServerUpstreamDiscoverStats>,
+        old: Option<&HttpHost>,
     ) -> anyhow::Result<Self> {
-        let tls_server = if let Some(builder) = &config.tls_server_builder {
-            let server = builder
-                .build_with_ticketer(ticketer)
-                .context("failed to build tls server")?;
-            Some(server)
-        } else {
-            None
-        };
+        let tls_server = HttpHostTlsServer::build(config, ticketer)?;
 
         let tls_client = if let Some(builder) = &config.tls_client_builder {
             let client = builder.build().context("failed to build tls client")?;
@@ -38,10 +109,49 @@ impl HttpHost {
             None
         };
 
+        let upstream = DynamicUpstream::new(
+            upstream_log_name,
+            config.as_ref(),
+            discover_stats,
+            old.map(|h| &h.upstream),
+        )?;
+
         Ok(HttpHost {
             config: Arc::clone(config),
             tls_server,
             tls_client,
+            upstream,
         })
     }
+
+    #[inline]
+    pub(super) fn tls_server(&self) -> Option<Arc<RustlsServerConfig>> {
+        self.tls_server.inner.load_full()
+    }
+
+    #[inline]
+    pub(super) fn pick_upstream(
+        &self,
+        cc_info: &ClientConnectionInfo,
+    ) -> Result<UpstreamAddr, ServerTaskError> {
+        self.upstream.pick(cc_info)
+    }
+
+    pub(super) fn tls_name<'a>(&'a self, upstream: &'a UpstreamAddr) -> &'a Host {
+        if self.config.tls_name.is_empty() {
+            upstream.host()
+        } else {
+            &self.config.tls_name
+        }
+    }
+
+    #[inline]
+    pub(super) fn depend_on_discover(&self, name: &NodeName) -> bool {
+        self.upstream.depend_on_discover(name)
+    }
+
+    #[inline]
+    pub(super) fn update_discover(&self) {
+        self.upstream.update_discover();
+    }
 }
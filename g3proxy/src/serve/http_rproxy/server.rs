@@ -14,7 +14,7 @@ use log::debug;
 #[cfg(feature = "quic")]
 use quinn::Connection;
 use slog::Logger;
-use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::sync::{broadcast, mpsc};
 use tokio_rustls::LazyConfigAcceptor;
@@ -41,16 +41,19 @@ use crate::auth::UserGroup;
 use crate::config::server::http_rproxy::HttpRProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::http_cache::HttpCache;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ServerRegistry, ServerStats, ServerTaskRegistry, WrapArcServer,
 };
 
 pub(crate) struct HttpRProxyServer {
     config: Arc<HttpRProxyServerConfig>,
     server_stats: Arc<HttpRProxyServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    http_cache: Option<Arc<HttpCache>>,
     global_tls_server: Option<RustlsServerConfig>,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
@@ -71,6 +74,7 @@ impl HttpRProxyServer {
         listen_stats: Arc<ListenStats>,
         hosts: HostMatch<Arc<HttpHost>>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        http_cache: Option<Arc<HttpCache>>,
         version: usize,
     ) -> anyhow::Result<Self> {
         let reload_sender = crate::serve::new_reload_notify_channel();
@@ -105,8 +109,10 @@ impl HttpRProxyServer {
         let server = HttpRProxyServer {
             config,
             server_stats,
+            task_registry: Arc::new(ServerTaskRegistry::default()),
             listen_stats,
             tls_rolling_ticketer,
+            http_cache,
             global_tls_server,
             ingress_net_filter,
             reload_sender,
@@ -137,9 +143,21 @@ impl HttpRProxyServer {
         } else {
             None
         };
-        let hosts = config
-            .hosts
-            .try_build_arc(|c| HttpHost::try_build(c, tls_rolling_ticketer.clone()))?;
+        let hosts = config.hosts.try_build_arc(|c| {
+            HttpHost::try_build(
+                c,
+                tls_rolling_ticketer.clone(),
+                format!("server {}", config.name()),
+                server_stats.upstream_discover.clone(),
+                None,
+            )
+        })?;
+        let http_cache = match &config.http_cache {
+            Some(c) => Some(Arc::new(
+                HttpCache::new(c).context("failed to create http cache")?,
+            )),
+            None => None,
+        };
 
         let server = HttpRProxyServer::new(
             config,
@@ -147,6 +165,7 @@ impl HttpRProxyServer {
             listen_stats,
             hosts,
             tls_rolling_ticketer,
+            http_cache,
             1,
         )?;
         Ok(Arc::new(server))
@@ -168,19 +187,43 @@ impl HttpRProxyServer {
             } else {
                 None
             };
-            // TODO do update if host has runtime state
-            let hosts = config
-                .hosts
-                .try_build_arc(|c| HttpHost::try_build(c, tls_rolling_ticketer.clone()))?;
+            let hosts = config.hosts.try_build_arc(|c| {
+                // inherit the runtime state from the old host with the same config
+                let mut old_host = None;
+                self.hosts.for_each_value(|h| {
+                    if old_host.is_none() && h.config.as_ref().eq(c.as_ref()) {
+                        old_host = Some(Arc::clone(h));
+                    }
+                });
+                HttpHost::try_build(
+                    c,
+                    tls_rolling_ticketer.clone(),
+                    format!("server {}", config.name()),
+                    server_stats.upstream_discover.clone(),
+                    old_host.as_deref(),
+                )
+            })?;
+            let http_cache = if self.config.http_cache.eq(&config.http_cache) {
+                self.http_cache.clone()
+            } else if let Some(c) = &config.http_cache {
+                Some(Arc::new(
+                    HttpCache::new(c).context("failed to create http cache")?,
+                ))
+            } else {
+                None
+            };
 
-            let server = HttpRProxyServer::new(
+            let mut server = HttpRProxyServer::new(
                 config,
                 server_stats,
                 listen_stats,
                 hosts,
                 tls_rolling_ticketer,
+                http_cache,
                 self.reload_version + 1,
             )?;
+            // keep the live tasks of the old server
+            server.task_registry = Arc::clone(&self.task_registry);
             Ok(server)
         } else {
             Err(anyhow!(
@@ -195,9 +238,11 @@ impl HttpRProxyServer {
         Arc::new(CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
+            http_cache: self.http_cache.clone(),
             cc_info,
             task_logger: self.task_logger.clone(),
         })
@@ -276,6 +321,20 @@ impl ServerInternal for HttpRProxyServer {
         Ok(())
     }
 
+    fn _depend_on_discover(&self, name: &NodeName) -> bool {
+        let mut depend = false;
+        self.hosts.for_each_value(|h| {
+            if h.depend_on_discover(name) {
+                depend = true;
+            }
+        });
+        depend
+    }
+
+    fn _update_discover_in_place(&self) {
+        self.hosts.for_each_value(|h| h.update_discover());
+    }
+
     fn _reload_with_old_notifier(
         &self,
         config: AnyServerConfig,
@@ -347,6 +406,18 @@ impl AcceptTcpServer for HttpRProxyServer {
             match tokio::time::timeout(self.config.client_hello_recv_timeout, tls_acceptor).await {
                 Ok(Ok(start)) => {
                     let ch = start.client_hello();
+                    if let Some(tls_config) = g3_acme::rustls_tls_alpn_config(&ch) {
+                        // this is an ACME TLS-ALPN-01 validation connection, close after handshake
+                        if let Ok(Ok(mut stream)) = tokio::time::timeout(
+                            self.config.client_hello_recv_timeout,
+                            start.into_stream(tls_config),
+                        )
+                        .await
+                        {
+                            let _ = stream.shutdown().await;
+                        }
+                        return;
+                    }
                     let host = match ch.server_name() {
                         Some(host) => match UpstreamAddr::from_str(host) {
                             Ok(upstream) => self.hosts.get(upstream.host()),
@@ -355,8 +426,9 @@ impl AcceptTcpServer for HttpRProxyServer {
                         None => self.hosts.get_default(),
                     };
 
-                    match host
-                        .and_then(|c| c.tls_server.as_ref())
+                    let host_tls_server = host.and_then(|h| h.tls_server());
+                    match host_tls_server
+                        .as_deref()
                         .or(self.global_tls_server.as_ref())
                     {
                         Some(tls_config) => {
@@ -457,6 +529,10 @@ impl Server for HttpRProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
@@ -13,7 +13,8 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    ServerForbiddenSnapshot, ServerForbiddenStats, ServerHttpCacheSnapshot, ServerHttpCacheStats,
+    ServerPerTaskStats, ServerStats, ServerUpstreamDiscoverSnapshot, ServerUpstreamDiscoverStats,
 };
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -27,6 +28,8 @@ pub(crate) struct HttpRProxyServerStats {
     conn_total: AtomicU64,
 
     pub forbidden: ServerForbiddenStats,
+    pub http_cache: ServerHttpCacheStats,
+    pub upstream_discover: Arc<ServerUpstreamDiscoverStats>,
 
     pub task_http_untrusted: ServerPerTaskStats,
     pub task_http_forward: ServerPerTaskStats,
@@ -44,6 +47,8 @@ impl HttpRProxyServerStats {
             online: AtomicIsize::new(0),
             conn_total: AtomicU64::new(0),
             forbidden: Default::default(),
+            http_cache: Default::default(),
+            upstream_discover: Arc::new(Default::default()),
             task_http_untrusted: Default::default(),
             task_http_forward: Default::default(),
             io_http: Default::default(),
@@ -118,6 +123,14 @@ impl ServerStats for HttpRProxyServerStats {
         self.forbidden.snapshot()
     }
 
+    fn http_cache_snapshot(&self) -> Option<ServerHttpCacheSnapshot> {
+        Some(self.http_cache.snapshot())
+    }
+
+    fn upstream_discover_snapshot(&self) -> Option<ServerUpstreamDiscoverSnapshot> {
+        Some(self.upstream_discover.snapshot())
+    }
+
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         Some(UntrustedTaskStatsSnapshot {
             task_total: self.task_http_untrusted.get_task_total(),
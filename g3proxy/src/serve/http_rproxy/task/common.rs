@@ -7,23 +7,31 @@ use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
 
+use http::HeaderName;
 use slog::Logger;
 use tokio::time::Instant;
 
 use g3_daemon::server::ClientConnectionInfo;
+use g3_http::server::HttpProxyClientRequest;
 use g3_io_ext::{IdleWheel, OptionalInterval};
+use g3_types::net::{HttpHeaderMap, UpstreamAddr};
 
 use super::{HttpRProxyServerConfig, HttpRProxyServerStats};
+use crate::config::http_header::{HttpHeaderRule, HttpHeaderRules};
 use crate::escape::ArcEscaper;
-use crate::serve::ServerQuitPolicy;
+use crate::module::http_cache::HttpCache;
+use crate::module::http_header::{self, HttpHeaderRuleContext};
+use crate::serve::{ServerQuitPolicy, ServerTaskNotes, ServerTaskRegistry};
 
 #[derive(Clone)]
 pub(crate) struct CommonTaskContext {
     pub(crate) server_config: Arc<HttpRProxyServerConfig>,
     pub(crate) server_stats: Arc<HttpRProxyServerStats>,
+    pub(crate) task_registry: Arc<ServerTaskRegistry>,
     pub(crate) server_quit_policy: Arc<ServerQuitPolicy>,
     pub(crate) idle_wheel: Arc<IdleWheel>,
     pub(crate) escaper: ArcEscaper,
+    pub(crate) http_cache: Option<Arc<HttpCache>>,
     pub(crate) cc_info: ClientConnectionInfo,
     pub(crate) task_logger: Option<Logger>,
 }
@@ -44,6 +52,93 @@ impl CommonTaskContext {
         self.cc_info.server_addr()
     }
 
+    fn apply_http_header_rules<F>(
+        &self,
+        task_notes: &ServerTaskNotes,
+        upstream: &UpstreamAddr,
+        path: &str,
+        headers: &mut HttpHeaderMap,
+        select: F,
+    ) where
+        F: Fn(&HttpHeaderRules) -> &[HttpHeaderRule],
+    {
+        let server_rules = self
+            .server_config
+            .http_header_rules
+            .as_ref()
+            .map(|r| select(r))
+            .filter(|r| !r.is_empty());
+        let user_rules = task_notes
+            .user_ctx()
+            .and_then(|c| c.user_config().http_header_rules.as_ref())
+            .map(|r| select(r))
+            .filter(|r| !r.is_empty());
+        if server_rules.is_none() && user_rules.is_none() {
+            return;
+        }
+
+        let escaper = self.escaper.name();
+        let ctx = HttpHeaderRuleContext {
+            task_notes,
+            escaper: escaper.as_str(),
+            upstream,
+            path,
+        };
+        // server level rules go first, so they can be overridden by user level rules
+        if let Some(rules) = server_rules {
+            http_header::apply_rules(rules, headers, &ctx);
+        }
+        if let Some(rules) = user_rules {
+            http_header::apply_rules(rules, headers, &ctx);
+        }
+    }
+
+    /// Get the names of the request headers that may be changed by header rules
+    pub(crate) fn request_header_rule_names(
+        &self,
+        task_notes: &ServerTaskNotes,
+    ) -> Vec<HeaderName> {
+        let server_rules = self
+            .server_config
+            .http_header_rules
+            .as_ref()
+            .map(|r| r.request.as_slice())
+            .unwrap_or_default();
+        let user_rules = task_notes
+            .user_ctx()
+            .and_then(|c| c.user_config().http_header_rules.as_ref())
+            .map(|r| r.request.as_slice())
+            .unwrap_or_default();
+        http_header::rule_header_names(server_rules.iter().chain(user_rules))
+    }
+
+    pub(crate) fn apply_request_header_rules(
+        &self,
+        task_notes: &ServerTaskNotes,
+        upstream: &UpstreamAddr,
+        req: &mut HttpProxyClientRequest,
+    ) {
+        self.apply_http_header_rules(
+            task_notes,
+            upstream,
+            req.uri.path(),
+            &mut req.end_to_end_headers,
+            |r| &r.request,
+        );
+    }
+
+    pub(crate) fn apply_response_header_rules(
+        &self,
+        task_notes: &ServerTaskNotes,
+        upstream: &UpstreamAddr,
+        req: &HttpProxyClientRequest,
+        headers: &mut HttpHeaderMap,
+    ) {
+        self.apply_http_header_rules(task_notes, upstream, req.uri.path(), headers, |r| {
+            &r.response
+        });
+    }
+
     pub(super) fn log_flush_interval(&self) -> Option<Duration> {
         self.task_logger.as_ref()?;
         self.server_config.task_log_flush_interval
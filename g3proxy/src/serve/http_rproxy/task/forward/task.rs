@@ -5,10 +5,12 @@
 
 use std::borrow::Cow;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use anyhow::anyhow;
+use bytes::Bytes;
 use futures_util::FutureExt;
-use http::header;
+use http::{Method, header};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 
 use g3_http::client::HttpForwardRemoteResponse;
@@ -19,6 +21,7 @@ use g3_io_ext::{
     StreamCopyError,
 };
 use g3_types::acl::AclAction;
+use g3_types::net::{HttpHeaderMap, UpstreamAddr};
 
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpRProxyRequest};
 use super::{
@@ -27,6 +30,10 @@ use super::{
 };
 use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
+use crate::module::http_cache::{
+    HttpCacheBodyCollector, HttpCacheEntry, HttpCacheLookup, HttpCacheRequestContext,
+    HttpCacheStatus,
+};
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
     HttpForwardTaskNotes, HttpProxyClientResponse,
@@ -36,14 +43,17 @@ use crate::module::tcp_connect::{
 };
 use crate::serve::http_rproxy::host::HttpHost;
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    RegisteredTask, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct HttpRProxyForwardTask<'a> {
     ctx: Arc<CommonTaskContext>,
     host: Arc<HttpHost>,
+    upstream: UpstreamAddr,
+    upstream_error: Option<ServerTaskError>,
     req: &'a HttpProxyClientRequest,
+    req_upstream: &'a UpstreamAddr,
     is_https: bool,
     should_close: bool,
     send_error_response: bool,
@@ -51,6 +61,7 @@ pub(crate) struct HttpRProxyForwardTask<'a> {
     http_notes: HttpForwardTaskNotes,
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<HttpForwardTaskStats>,
+    cache_ctx: Option<HttpCacheRequestContext>,
     max_idle_count: usize,
     started: bool,
 }
@@ -69,8 +80,13 @@ impl<'a> HttpRProxyForwardTask<'a> {
         ctx: &Arc<CommonTaskContext>,
         req: &'a HttpRProxyRequest<impl AsyncRead>,
         host: Arc<HttpHost>,
+        upstream: Result<UpstreamAddr, ServerTaskError>,
         task_notes: ServerTaskNotes,
     ) -> Self {
+        let (upstream, upstream_error) = match upstream {
+            Ok(upstream) => (upstream, None),
+            Err(e) => (UpstreamAddr::empty(), Some(e)),
+        };
         let uri_log_max_chars = task_notes
             .user_ctx()
             .and_then(|c| c.user_config().log_uri_max_chars)
@@ -90,7 +106,10 @@ impl<'a> HttpRProxyForwardTask<'a> {
         HttpRProxyForwardTask {
             ctx: Arc::clone(ctx),
             host,
+            upstream,
+            upstream_error,
             req: &req.inner,
+            req_upstream: &req.upstream,
             is_https,
             should_close: !req.inner.keep_alive(),
             send_error_response: true,
@@ -98,6 +117,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             http_notes,
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(HttpForwardTaskStats::default()),
+            cache_ctx: None,
             max_idle_count,
             started: false,
         }
@@ -138,6 +158,19 @@ impl<'a> HttpRProxyForwardTask<'a> {
         self.should_close = true;
     }
 
+    async fn reply_gateway_timeout<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let rsp = HttpProxyClientResponse::gateway_timeout(self.req.version, self.should_close);
+        // no custom header is set
+        if rsp.reply_err_to_request(clt_w).await.is_err() {
+            self.should_close = true;
+        } else {
+            self.http_notes.rsp_status = rsp.status();
+        }
+    }
+
     async fn reply_connect_err<W>(&mut self, e: &TcpConnectError, clt_w: &mut W)
     where
         W: AsyncWrite + Unpin,
@@ -201,7 +234,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             .map(|v| v.to_str());
         Some(TaskLogForHttpForward {
             logger,
-            upstream: self.host.config.upstream(),
+            upstream: &self.upstream,
             task_notes: &self.task_notes,
             http_notes: &self.http_notes,
             http_user_agent,
@@ -223,7 +256,16 @@ impl<'a> HttpRProxyForwardTask<'a> {
         CDW: AsyncWrite + Unpin,
     {
         self.pre_start();
-        let e = match self.run_forward(clt_r, clt_w, fwd_ctx).await {
+        let cancel_token = self.task_notes.cancel_token();
+        let r = tokio::select! {
+            r = self.run_forward(clt_r, clt_w, fwd_ctx) => r,
+            _ = cancel_token.cancelled() => {
+                // the response may be incomplete
+                self.should_close = true;
+                Err(self.task_notes.cancel_error())
+            }
+        };
+        let e = match r {
             Ok(()) => ServerTaskError::Finished,
             Err(e) => e,
         };
@@ -235,6 +277,13 @@ impl<'a> HttpRProxyForwardTask<'a> {
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_http_forward.add_task();
         self.ctx.server_stats.task_http_forward.inc_alive_task();
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -469,7 +518,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 }
             }
 
-            let action = user_ctx.check_upstream(self.host.config.upstream());
+            let action = user_ctx.check_upstream(&self.upstream);
             self.handle_user_upstream_acl_action(action, clt_w).await?;
 
             if let Some(action) = user_ctx.check_http_user_agent(&self.req.end_to_end_headers) {
@@ -495,7 +544,47 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
-        fwd_ctx.prepare_connection(self.host.config.upstream(), self.is_https);
+        if let Some(e) = self.upstream_error.take() {
+            self.reply_task_err(&e, clt_w).await;
+            return Err(e);
+        }
+
+        if let Some(cache) = &self.ctx.http_cache {
+            let mut cache_ctx = HttpCacheRequestContext::new(
+                cache,
+                self.ctx.escaper.name().as_str(),
+                &self.upstream,
+                self.is_https,
+                self.req,
+                &self.ctx.request_header_rule_names(&self.task_notes),
+            );
+            match cache_ctx.lookup(self.req).await {
+                HttpCacheLookup::Fresh(entry, body) => {
+                    self.ctx.server_stats.http_cache.add_hit();
+                    self.http_notes.cache_status = Some(HttpCacheStatus::Hit);
+                    self.mark_relaying();
+                    let r = self.send_cached_response(clt_w, &entry, body).await;
+                    if r.is_err() || self.should_close {
+                        self.should_close = true;
+                        let _ = clt_w.shutdown().await;
+                    }
+                    return r;
+                }
+                HttpCacheLookup::Stale => {}
+                HttpCacheLookup::Miss => {
+                    self.ctx.server_stats.http_cache.add_miss();
+                    self.http_notes.cache_status = Some(HttpCacheStatus::Miss);
+                    if cache_ctx.only_if_cached() {
+                        self.reply_gateway_timeout(clt_w).await;
+                        return Ok(());
+                    }
+                }
+                HttpCacheLookup::Bypass => {}
+            }
+            self.cache_ctx = Some(cache_ctx);
+        }
+
+        fwd_ctx.prepare_connection(&self.upstream, self.is_https);
 
         if let Some(mut connection) = fwd_ctx
             .get_alive_connection(
@@ -519,9 +608,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 }
             }
 
-            connection
-                .0
-                .prepare_new(&self.task_notes, self.host.config.upstream());
+            connection.0.prepare_new(&self.task_notes, &self.upstream);
             self.mark_relaying();
 
             let r = self
@@ -613,9 +700,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
                     }
                 }
 
-                connection
-                    .0
-                    .prepare_new(&self.task_notes, self.host.config.upstream());
+                connection.0.prepare_new(&self.task_notes, &self.upstream);
                 self.mark_relaying();
                 Ok(connection)
             }
@@ -635,17 +720,17 @@ impl<'a> HttpRProxyForwardTask<'a> {
         if let Some(tls_client) = &self.host.tls_client {
             let task_conf = TlsConnectTaskConf {
                 tcp: TcpConnectTaskConf {
-                    upstream: self.host.config.upstream(),
+                    upstream: &self.upstream,
                 },
                 tls_config: tls_client,
-                tls_name: &self.host.config.tls_name,
+                tls_name: self.host.tls_name(&self.upstream),
             };
             fwd_ctx
                 .make_new_https_connection(&task_conf, &self.task_notes, self.task_stats.clone())
                 .await
         } else {
             let task_conf = TcpConnectTaskConf {
-                upstream: self.host.config.upstream(),
+                upstream: &self.upstream,
             };
             fwd_ctx
                 .make_new_http_connection(&task_conf, &self.task_notes, self.task_stats.clone())
@@ -763,9 +848,15 @@ impl<'a> HttpRProxyForwardTask<'a> {
         let ups_w = &mut ups_c.0;
         let ups_r = &mut ups_c.1;
 
+        let validation_req = self
+            .cache_ctx
+            .as_ref()
+            .and_then(|c| c.validation_request(self.req));
+        let req = validation_req.as_ref().unwrap_or(self.req);
+
         self.http_notes.retry_new_connection = true;
         ups_w
-            .send_request_header(self.req, None)
+            .send_request_header(req, None)
             .await
             .map_err(ServerTaskError::UpstreamWriteFailed)?;
         ups_w
@@ -806,7 +897,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         self.http_notes.mark_rsp_recv_hdr();
 
         self.update_response_header(&mut rsp_header);
-        self.send_response(clt_w, ups_r, &rsp_header).await?;
+        self.send_response(clt_w, ups_r, &mut rsp_header).await?;
 
         self.task_notes.stage = ServerTaskStage::Finished;
         Ok(Some(ups_c))
@@ -898,7 +989,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             self.http_notes.mark_rsp_recv_hdr();
             self.update_response_header(&mut rsp_header);
 
-            self.send_response(clt_w, ups_r, &rsp_header).await?;
+            self.send_response(clt_w, ups_r, &mut rsp_header).await?;
 
             self.task_notes.stage = ServerTaskStage::Finished;
             return Ok(Some(ups_c));
@@ -999,8 +1090,8 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -1018,8 +1109,8 @@ impl<'a> HttpRProxyForwardTask<'a> {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -1067,7 +1158,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         self.http_notes.mark_rsp_recv_hdr();
 
         self.update_response_header(&mut rsp_header);
-        self.send_response(clt_w, ups_r, &rsp_header).await?;
+        self.send_response(clt_w, ups_r, &mut rsp_header).await?;
 
         self.task_notes.stage = ServerTaskStage::Finished;
         if close_remote {
@@ -1125,7 +1216,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         &mut self,
         clt_w: &mut W,
         ups_r: &mut R,
-        rsp_header: &HttpForwardRemoteResponse,
+        rsp_header: &mut HttpForwardRemoteResponse,
     ) -> ServerTaskResult<()>
     where
         R: AsyncBufRead + Unpin,
@@ -1137,17 +1228,99 @@ impl<'a> HttpRProxyForwardTask<'a> {
         self.send_error_response = false;
         self.http_notes.origin_status = rsp_header.code;
 
-        if let Some(body_type) = rsp_header.body_type(&self.req.method) {
+        let mut cache_ctx = self.cache_ctx.take();
+        let mut cache_limit = None;
+        if let Some(cache_ctx) = &mut cache_ctx {
+            if let Some(entry) = cache_ctx.revalidate(rsp_header).await {
+                self.ctx.server_stats.http_cache.add_revalidated();
+                self.http_notes.cache_status = Some(HttpCacheStatus::Revalidated);
+                let body = if self.req.method == Method::HEAD {
+                    Bytes::new()
+                } else {
+                    entry.load_body().await.map_err(|_| {
+                        ServerTaskError::InternalServerError("failed to load cached response body")
+                    })?
+                };
+                return self.send_cached_response(clt_w, &entry, body).await;
+            }
+            if cache_ctx.is_validating() {
+                self.ctx.server_stats.http_cache.add_miss();
+                self.http_notes.cache_status = Some(HttpCacheStatus::Expired);
+            }
+            cache_ctx.invalidate_for_unsafe(&self.req.method, rsp_header.code);
+            cache_limit = cache_ctx.check_storable(self.req, rsp_header);
+        }
+        self.apply_response_header_rules(&mut rsp_header.end_to_end_headers);
+
+        let body_type = rsp_header.body_type(&self.req.method);
+        let collected = if let Some(body_type) = body_type {
             let mut buf = Vec::with_capacity(self.ctx.server_config.tcp_copy.buffer_size());
             rsp_header.serialize_to(&mut buf);
             self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
-            self.send_response_body(buf, clt_w, ups_r, body_type).await
+            self.send_response_body(buf, clt_w, ups_r, body_type, cache_limit)
+                .await?
         } else {
             self.send_response_header(clt_w, rsp_header).await?;
             self.http_notes.rsp_status = rsp_header.code;
             self.http_notes.mark_rsp_no_body();
-            Ok(())
+            cache_limit.map(|_| Vec::new())
+        };
+
+        if let Some(cache_ctx) = cache_ctx {
+            if let Some(data) = collected {
+                if cache_ctx
+                    .store(
+                        self.req,
+                        rsp_header,
+                        body_type,
+                        data,
+                        self.ctx.server_config.body_line_max_len,
+                    )
+                    .await
+                {
+                    self.ctx.server_stats.http_cache.add_stored();
+                }
+            }
         }
+        Ok(())
+    }
+
+    async fn send_cached_response<W>(
+        &mut self,
+        clt_w: &mut W,
+        entry: &HttpCacheEntry,
+        body: Bytes,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let now = SystemTime::now();
+        let keep_alive = !self.should_close;
+        self.send_error_response = false;
+
+        let mut headers = entry.headers.clone();
+        self.apply_response_header_rules(&mut headers);
+
+        let buf = if g3_http::cache::request_is_conditional(&self.req.end_to_end_headers)
+            && g3_http::cache::response_not_modified(&self.req.end_to_end_headers, &entry.headers)
+        {
+            self.http_notes.rsp_status = 304;
+            entry.serialize_not_modified(self.req.version, &headers, now, keep_alive)
+        } else {
+            self.http_notes.rsp_status = entry.code;
+            let mut buf = entry.serialize_header(self.req.version, &headers, now, keep_alive);
+            buf.extend_from_slice(&body);
+            buf
+        };
+        self.http_notes.mark_rsp_recv_hdr();
+        self.http_notes.mark_rsp_no_body();
+
+        clt_w
+            .write_all_flush(&buf)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
     }
 
     async fn send_response_body<R, W>(
@@ -1156,14 +1329,16 @@ impl<'a> HttpRProxyForwardTask<'a> {
         clt_w: &mut W,
         ups_r: &mut R,
         body_type: HttpBodyType,
-    ) -> ServerTaskResult<()>
+        cache_limit: Option<usize>,
+    ) -> ServerTaskResult<Option<Vec<u8>>>
     where
         R: AsyncBufRead + Unpin,
         W: AsyncWrite + Unpin,
     {
         let header_len = header.len() as u64;
-        let mut body_reader =
+        let body_reader =
             HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        let mut body_reader = HttpCacheBodyCollector::new(body_reader, cache_limit);
 
         let mut ups_to_clt = StreamCopy::with_data(
             &mut body_reader,
@@ -1184,7 +1359,8 @@ impl<'a> HttpRProxyForwardTask<'a> {
                         Ok(_) => {
                             self.http_notes.mark_rsp_recv_all();
                             // clt_w is already flushed
-                            Ok(())
+                            drop(ups_to_clt);
+                            Ok(body_reader.take_collected())
                         }
                         Err(StreamCopyError::ReadFailed(e)) => {
                             if ups_to_clt.copied_size() < header_len {
@@ -1206,11 +1382,11 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
+                            if let Some(reason) = user.blocked_reason() {
                                 if ups_to_clt.copied_size() < header_len {
                                     let _ = ups_to_clt.write_flush().await; // flush rsp header to client
                                 }
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -1228,11 +1404,11 @@ impl<'a> HttpRProxyForwardTask<'a> {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
                             if ups_to_clt.copied_size() < header_len {
                                 let _ = ups_to_clt.write_flush().await; // flush rsp header to client
                             }
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -1247,6 +1423,15 @@ impl<'a> HttpRProxyForwardTask<'a> {
         }
     }
 
+    fn apply_response_header_rules(&self, headers: &mut HttpHeaderMap) {
+        self.ctx.apply_response_header_rules(
+            &self.task_notes,
+            self.req_upstream,
+            self.req,
+            headers,
+        );
+    }
+
     fn update_response_header(&self, rsp: &mut HttpForwardRemoteResponse) {
         if self.should_close {
             rsp.set_no_keep_alive();
@@ -7,12 +7,14 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use ahash::AHashMap;
-use tokio::io::{AsyncRead, AsyncWrite};
+use http::Method;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::sync::mpsc;
 
+use g3_acme::AcmeChallengeStore;
 use g3_io_ext::{ArcLimitedWriterStats, LimitedWriter};
 use g3_types::auth::UserAuthError;
-use g3_types::net::{HttpAuth, HttpBasicAuth};
+use g3_types::net::{HttpAuth, HttpBasicAuth, UpstreamAddr};
 use g3_types::route::HostMatch;
 
 use super::protocol::{HttpClientWriter, HttpRProxyRequest};
@@ -25,7 +27,7 @@ use crate::auth::{UserContext, UserGroup, UserRequestStats};
 use crate::config::server::ServerConfig;
 use crate::module::http_forward::{BoxHttpForwardContext, HttpProxyClientResponse};
 use crate::serve::http_rproxy::host::HttpHost;
-use crate::serve::{ServerStats, ServerTaskNotes};
+use crate::serve::{ServerStats, ServerTaskError, ServerTaskNotes};
 
 struct UserData {
     req_stats: Arc<UserRequestStats>,
@@ -194,6 +196,18 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
+                    let acme_challenge = if req.inner.method == Method::GET {
+                        AcmeChallengeStore::global().get_http_response(req.inner.uri.path())
+                    } else {
+                        None
+                    };
+                    if let Some(key_authorization) = acme_challenge {
+                        self.reply_acme_challenge(&req, key_authorization).await;
+                        self.pipeline_stats.del_task();
+                        self.notify_reader_to_close();
+                        break;
+                    }
+
                     let res = match self.do_auth(&req) {
                         Ok(user_ctx) => {
                             self.req_count.consequent_auth_failed = 0;
@@ -263,14 +277,22 @@ where
         );
 
         if let Some(mut stream_w) = self.stream_writer.take() {
-            let mut audit_ctx = AuditContext::default();
-            // check in final escaper so we can use route escapers
-            let _ = self
-                .forward_context
-                .check_in_final_escaper(&task_notes, host.config.upstream(), &mut audit_ctx)
-                .await;
+            // the task will fail with the pick error if no upstream is available
+            let upstream = host.pick_upstream(&self.ctx.cc_info);
+
+            if let Ok(upstream) = &upstream {
+                let mut audit_ctx = AuditContext::default();
+                // check in final escaper so we can use route escapers
+                let _ = self
+                    .forward_context
+                    .check_in_final_escaper(&task_notes, upstream, &mut audit_ctx)
+                    .await;
+            }
 
-            match self.run_forward(&mut stream_w, req, host, task_notes).await {
+            match self
+                .run_forward(&mut stream_w, req, host, upstream, task_notes)
+                .await
+            {
                 LoopAction::Continue => {
                     self.reset_client_writer(stream_w);
                     LoopAction::Continue
@@ -282,6 +304,26 @@ where
         }
     }
 
+    /// Respond to the ACME HTTP-01 validation request, the connection will be closed
+    async fn reply_acme_challenge(
+        &mut self,
+        req: &HttpRProxyRequest<CDR>,
+        key_authorization: String,
+    ) {
+        if let Some(clt_w) = &mut self.stream_writer {
+            let rsp = HttpProxyClientResponse::sized_ok(
+                req.inner.version,
+                true,
+                key_authorization.len() as u64,
+                &mime::APPLICATION_OCTET_STREAM,
+            );
+            if rsp.reply_ok_header(clt_w).await.is_ok() {
+                let _ = clt_w.write_all(key_authorization.as_bytes()).await;
+                let _ = clt_w.flush().await;
+            }
+        }
+    }
+
     fn reset_client_writer(&mut self, mut stream_w: HttpClientWriter<CDW>) {
         stream_w.reset_stats(Arc::clone(&self.wrapper_stats));
         let limit_config = &self.ctx.server_config.tcp_sock_speed_limit;
@@ -387,14 +429,18 @@ where
         clt_w: &mut HttpClientWriter<CDW>,
         mut req: HttpRProxyRequest<CDR>,
         host: Arc<HttpHost>,
+        upstream: Result<UpstreamAddr, ServerTaskError>,
         task_notes: ServerTaskNotes,
     ) -> LoopAction {
+        self.ctx
+            .apply_request_header_rules(&task_notes, &req.upstream, &mut req.inner);
+
         match req.body_reader.take() {
             Some(stream_r) => {
                 // we have a body, or we need to close the connection
                 // we may need to send stream_r back if we have a body
                 let mut forward_task =
-                    HttpRProxyForwardTask::new(&self.ctx, &req, host, task_notes);
+                    HttpRProxyForwardTask::new(&self.ctx, &req, host, upstream, task_notes);
                 let mut clt_r = Some(stream_r);
                 forward_task
                     .run(&mut clt_r, clt_w, &mut self.forward_context)
@@ -416,7 +462,7 @@ where
             None => {
                 // no body, and the connection is expected to keep alive from the client side
                 let mut forward_task =
-                    HttpRProxyForwardTask::new(&self.ctx, &req, host, task_notes);
+                    HttpRProxyForwardTask::new(&self.ctx, &req, host, upstream, task_notes);
                 let mut clt_r = None;
                 forward_task
                     .run::<CDR, CDW>(&mut clt_r, clt_w, &mut self.forward_context)
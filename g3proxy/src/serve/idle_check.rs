@@ -48,8 +48,8 @@ impl IdleCheck for ServerIdleChecker {
 
     fn check_force_quit(&self) -> Option<IdleForceQuitReason> {
         if let Some(user) = &self.user {
-            if user.is_blocked() {
-                return Some(IdleForceQuitReason::UserBlocked);
+            if let Some(reason) = user.blocked_reason() {
+                return Some(reason);
             }
         }
 
@@ -3,16 +3,17 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
 #[cfg(feature = "quic")]
 use quinn::Connection;
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UdpSocket};
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats};
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ReceiveUdpServer};
 use g3_daemon::server::{
     BaseServer, ClientConnectionInfo, ReloadServer, ServerQuitPolicy, ServerReloadCommand,
 };
@@ -23,7 +24,7 @@ use crate::config::server::AnyServerConfig;
 
 mod registry;
 use registry::ServerRegistry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{get_config, get_names, get_or_insert_default};
 
 mod idle_check;
 pub(crate) use idle_check::ServerIdleChecker;
@@ -36,6 +37,7 @@ mod plain_quic_port;
 mod plain_tcp_port;
 mod plain_tls_port;
 
+mod dns_server;
 mod http_proxy;
 mod http_rproxy;
 mod sni_proxy;
@@ -49,24 +51,42 @@ mod tcp_stream;
 ))]
 mod tcp_tproxy;
 mod tls_stream;
+mod udp_stream;
+#[cfg(target_os = "linux")]
+mod udp_tproxy;
 
 mod error;
+mod stream_upstream;
 mod task;
+mod task_registry;
+mod udp_flow;
+mod upstream;
 
 pub(crate) use error::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+pub(crate) use stream_upstream::StreamUpstreamPicker;
 pub(crate) use task::{ServerTaskNotes, ServerTaskStage};
+pub(crate) use task_registry::{
+    RegisteredTask, RegisteredTaskGuard, RegisteredTaskStats, ServerTaskRegistry,
+};
+pub(crate) use udp_flow::{
+    UdpFlowKey, UdpFlowLimit, UdpFlowQueue, UdpFlowTable, copy_packet_to_iov,
+};
+pub(crate) use upstream::{DynamicUpstream, UpstreamPickConfig};
 
 mod ops;
 pub(crate) use ops::{
     force_quit_offline_server, force_quit_offline_servers, foreach_server, get_server, reload,
-    stop_all, update_dependency_to_auditor, update_dependency_to_escaper,
-    update_dependency_to_user_group, wait_all_tasks,
+    stop_all, update_dependency_to_auditor, update_dependency_to_discover,
+    update_dependency_to_escaper, update_dependency_to_resolver, update_dependency_to_user_group,
+    wait_all_tasks,
 };
 pub use ops::{spawn_all, spawn_offline_clean};
 
 mod stats;
 pub(crate) use stats::{
-    ArcServerStats, ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    ArcServerStats, ServerForbiddenSnapshot, ServerForbiddenStats, ServerHttpCacheSnapshot,
+    ServerHttpCacheStats, ServerPerTaskStats, ServerStats, ServerUpstreamDiscoverSnapshot,
+    ServerUpstreamDiscoverStats,
 };
 
 #[async_trait]
@@ -79,6 +99,9 @@ pub(crate) trait Server: BaseServer + AcceptTcpServer + AcceptQuicServer {
         None
     }
     fn get_listen_stats(&self) -> Arc<ListenStats>;
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        None
+    }
 
     fn alive_count(&self) -> i32;
     fn quit_policy(&self) -> &Arc<ServerQuitPolicy>;
@@ -86,6 +109,16 @@ pub(crate) trait Server: BaseServer + AcceptTcpServer + AcceptQuicServer {
     async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo);
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo);
+
+    fn receive_udp_packet(
+        &self,
+        _socket: &Arc<UdpSocket>,
+        _packet: &[u8],
+        _client_addr: SocketAddr,
+        _server_addr: SocketAddr,
+        _worker_id: Option<usize>,
+    ) {
+    }
 }
 
 trait ServerInternal: Server {
@@ -101,6 +134,16 @@ trait ServerInternal: Server {
     fn _update_user_group_in_place(&self);
     fn _update_audit_handle_in_place(&self) -> anyhow::Result<()>;
 
+    fn _depend_on_resolver(&self, _name: &NodeName) -> bool {
+        false
+    }
+    fn _update_resolver_in_place(&self) {}
+
+    fn _depend_on_discover(&self, _name: &NodeName) -> bool {
+        false
+    }
+    fn _update_discover_in_place(&self) {}
+
     fn _reload_with_old_notifier(
         &self,
         config: AnyServerConfig,
@@ -157,6 +200,20 @@ impl AcceptQuicServer for WrapArcServer {
     }
 }
 
+impl ReceiveUdpServer for WrapArcServer {
+    fn receive_udp_packet(
+        &self,
+        socket: &Arc<UdpSocket>,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        self.0
+            .receive_udp_packet(socket, packet, client_addr, server_addr, worker_id)
+    }
+}
+
 fn new_reload_notify_channel() -> broadcast::Sender<ServerReloadCommand> {
     broadcast::Sender::new(16)
 }
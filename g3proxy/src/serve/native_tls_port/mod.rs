@@ -7,7 +7,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
-use arc_swap::ArcSwap;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use async_trait::async_trait;
 use log::debug;
 use openssl::ssl::Ssl;
@@ -17,13 +17,17 @@ use tokio::net::TcpStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
+use g3_acme::{AcmeCertHolder, AcmeConfig};
 use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::haproxy::{ProxyProtocolV1Reader, ProxyProtocolV2Reader};
 use g3_openssl::{SslAcceptor, SslStream};
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslServerConfig, OpensslTicketKey, ProxyProtocolVersion, RollingTicketer};
+use g3_types::net::{
+    OpensslServerConfig, OpensslServerConfigBuilder, OpensslTicketKey, ProxyProtocolVersion,
+    RollingTicketer,
+};
 
 use crate::config::server::native_tls_port::NativeTlsPortConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
@@ -32,11 +36,85 @@ use crate::serve::{
     WrapArcServer,
 };
 
+/// Tls server config which can be replaced when a new ACME certificate is issued
+struct NativeTlsServer {
+    config: Option<OpensslServerConfigBuilder>,
+    acme: Option<Arc<AcmeConfig>>,
+    ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    inner: ArcSwapOption<OpensslServerConfig>,
+}
+
+impl NativeTlsServer {
+    fn build(
+        config: &NativeTlsPortConfig,
+        ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    ) -> anyhow::Result<Arc<Self>> {
+        let server = build_tls_server(
+            config.server_tls_config.as_ref(),
+            config.acme.as_deref(),
+            ticketer.clone(),
+        )?;
+        let holder = Arc::new(NativeTlsServer {
+            config: config.server_tls_config.clone(),
+            acme: config.acme.clone(),
+            ticketer,
+            inner: ArcSwapOption::from_pointee(server),
+        });
+        if let Some(acme) = &config.acme {
+            g3_acme::spawn_cert_updater(&holder, acme);
+        }
+        Ok(holder)
+    }
+}
+
+impl AcmeCertHolder for NativeTlsServer {
+    fn reload_acme_cert(&self) -> anyhow::Result<()> {
+        let server = build_tls_server(
+            self.config.as_ref(),
+            self.acme.as_deref(),
+            self.ticketer.clone(),
+        )?;
+        self.inner.store(server.map(Arc::new));
+        Ok(())
+    }
+}
+
+fn build_tls_server(
+    config: Option<&OpensslServerConfigBuilder>,
+    acme: Option<&AcmeConfig>,
+    ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+) -> anyhow::Result<Option<OpensslServerConfig>> {
+    let Some(acme) = acme else {
+        let Some(builder) = config else {
+            return Err(anyhow!("no tls server config set"));
+        };
+        let server = builder
+            .build_with_ticketer(ticketer)
+            .context("failed to build tls server config")?;
+        return Ok(Some(server));
+    };
+
+    let mut builder = config
+        .cloned()
+        .unwrap_or_else(OpensslServerConfigBuilder::empty);
+    if let Some(pair) = crate::module::acme::load_openssl_cert_pair(acme)? {
+        builder.push_cert_pair(pair)?;
+    }
+    if builder.check().is_err() {
+        // the ACME certificate has not been issued yet
+        return Ok(None);
+    }
+    let server = builder
+        .build_with_ticketer(ticketer)
+        .context("failed to build tls server config")?;
+    Ok(Some(server))
+}
+
 pub(crate) struct NativeTlsPort {
     config: NativeTlsPortConfig,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
-    tls_server_config: OpensslServerConfig,
+    tls_server: Arc<NativeTlsServer>,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
 
@@ -58,13 +136,7 @@ impl NativeTlsPort {
     {
         let reload_sender = crate::serve::new_reload_notify_channel();
 
-        let tls_server_config = if let Some(builder) = &config.server_tls_config {
-            builder
-                .build_with_ticketer(tls_rolling_ticketer.clone())
-                .context("failed to build tls server config")?
-        } else {
-            return Err(anyhow!("no tls server config set"));
-        };
+        let tls_server = NativeTlsServer::build(&config, tls_rolling_ticketer.clone())?;
 
         let ingress_net_filter = config
             .ingress_net_filter
@@ -77,7 +149,7 @@ impl NativeTlsPort {
             config,
             listen_stats,
             tls_rolling_ticketer,
-            tls_server_config,
+            tls_server,
             ingress_net_filter,
             reload_sender,
             next_server: ArcSwap::new(next_server),
@@ -163,7 +235,12 @@ impl NativeTlsPort {
     }
 
     async fn run_task(&self, mut stream: TcpStream, mut cc_info: ClientConnectionInfo) {
-        let Ok(ssl) = Ssl::new(&self.tls_server_config.ssl_context) else {
+        let Some(tls_server_config) = self.tls_server.inner.load_full() else {
+            // no certificate available yet
+            self.listen_stats.add_dropped();
+            return;
+        };
+        let Ok(ssl) = Ssl::new(&tls_server_config.ssl_context) else {
             self.listen_stats.add_dropped();
             return;
         };
@@ -196,7 +273,7 @@ impl NativeTlsPort {
             None => {}
         }
 
-        let Ok(ssl_acceptor) = SslAcceptor::new(ssl, stream, self.tls_server_config.accept_timeout)
+        let Ok(ssl_acceptor) = SslAcceptor::new(ssl, stream, tls_server_config.accept_timeout)
         else {
             self.listen_stats.add_dropped();
             return;
@@ -26,6 +26,7 @@ use super::plain_quic_port::PlainQuicPort;
 use super::plain_tcp_port::PlainTcpPort;
 use super::plain_tls_port::PlainTlsPort;
 
+use super::dns_server::DnsServer;
 use super::http_proxy::HttpProxyServer;
 use super::http_rproxy::HttpRProxyServer;
 use super::sni_proxy::SniProxyServer;
@@ -39,6 +40,9 @@ use super::tcp_stream::TcpStreamServer;
 ))]
 use super::tcp_tproxy::TcpTProxyServer;
 use super::tls_stream::TlsStreamServer;
+use super::udp_stream::UdpStreamServer;
+#[cfg(target_os = "linux")]
+use super::udp_tproxy::UdpTProxyServer;
 
 static SERVER_OPS_LOCK: Mutex<()> = Mutex::const_new(());
 
@@ -202,6 +206,54 @@ pub(crate) async fn update_dependency_to_escaper(escaper: &NodeName, status: &st
     }
 }
 
+pub(crate) async fn update_dependency_to_resolver(resolver: &NodeName, status: &str) {
+    let _guard = SERVER_OPS_LOCK.lock().await;
+
+    let mut names = Vec::<NodeName>::new();
+
+    registry::foreach_online(|name, server| {
+        if server._depend_on_resolver(resolver) {
+            names.push(name.clone());
+        }
+    });
+
+    if names.is_empty() {
+        return;
+    }
+
+    debug!("resolver {resolver} changed({status}), will reload server(s) {names:?}");
+    for name in names.iter() {
+        debug!("server {name}: will reload as it's using resolver {resolver}");
+        if let Err(e) = registry::reload_only_resolver(name) {
+            warn!("failed to reload server {name}: {e:?}");
+        }
+    }
+}
+
+pub(crate) async fn update_dependency_to_discover(discover: &NodeName, status: &str) {
+    let _guard = SERVER_OPS_LOCK.lock().await;
+
+    let mut names = Vec::<NodeName>::new();
+
+    registry::foreach_online(|name, server| {
+        if server._depend_on_discover(discover) {
+            names.push(name.clone());
+        }
+    });
+
+    if names.is_empty() {
+        return;
+    }
+
+    debug!("discover {discover} changed({status}), will reload server(s) {names:?}");
+    for name in names.iter() {
+        debug!("server {name}: will reload as it's using discover {discover}");
+        if let Err(e) = registry::reload_only_discover(name) {
+            warn!("failed to reload server {name}: {e:?}");
+        }
+    }
+}
+
 pub(crate) async fn update_dependency_to_user_group(user_group: &NodeName, status: &str) {
     let _guard = SERVER_OPS_LOCK.lock().await;
 
@@ -305,10 +357,14 @@ fn spawn_new_unlocked(config: AnyServerConfig) -> anyhow::Result<()> {
         ))]
         AnyServerConfig::TcpTProxy(c) => TcpTProxyServer::prepare_initial(c)?,
         AnyServerConfig::TlsStream(c) => TlsStreamServer::prepare_initial(c)?,
+        AnyServerConfig::UdpStream(c) => UdpStreamServer::prepare_initial(c)?,
+        #[cfg(target_os = "linux")]
+        AnyServerConfig::UdpTProxy(c) => UdpTProxyServer::prepare_initial(c)?,
         AnyServerConfig::SniProxy(c) => SniProxyServer::prepare_initial(c)?,
         AnyServerConfig::SocksProxy(c) => SocksProxyServer::prepare_initial(c)?,
         AnyServerConfig::HttpProxy(c) => HttpProxyServer::prepare_initial(c)?,
         AnyServerConfig::HttpRProxy(c) => HttpRProxyServer::prepare_initial(c)?,
+        AnyServerConfig::DnsServer(c) => DnsServer::prepare_initial(c)?,
     };
     registry::add(name.clone(), server)?;
     update_dependency_to_server_unlocked(&name, "spawned");
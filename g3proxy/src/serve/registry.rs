@@ -159,7 +159,7 @@ pub(crate) fn get_names() -> HashSet<NodeName> {
     sr.get_names()
 }
 
-pub(super) fn get_config(name: &NodeName) -> Option<AnyServerConfig> {
+pub(crate) fn get_config(name: &NodeName) -> Option<AnyServerConfig> {
     let sr = RUNTIME_SERVER_REGISTRY.lock().unwrap();
     sr.get_config(name)
 }
@@ -206,6 +206,18 @@ pub(super) fn reload_only_auditor(name: &NodeName) -> anyhow::Result<()> {
     server._update_audit_handle_in_place()
 }
 
+pub(super) fn reload_only_resolver(name: &NodeName) -> anyhow::Result<()> {
+    let server = check_get_server(name)?;
+    server._update_resolver_in_place();
+    Ok(())
+}
+
+pub(super) fn reload_only_discover(name: &NodeName) -> anyhow::Result<()> {
+    let server = check_get_server(name)?;
+    server._update_discover_in_place();
+    Ok(())
+}
+
 pub(super) fn reload_and_respawn(name: &NodeName, config: AnyServerConfig) -> anyhow::Result<()> {
     let mut sr = RUNTIME_SERVER_REGISTRY
         .lock()
@@ -31,12 +31,13 @@ use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ServerRegistry, ServerStats, ServerTaskRegistry, WrapArcServer,
 };
 
 pub(crate) struct SniProxyServer {
     config: Arc<SniProxyServerConfig>,
     server_stats: Arc<TcpStreamServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
     ingress_net_filter: Option<AclNetworkRule>,
     server_tcp_portmap: Arc<ProtocolPortMap>,
@@ -55,6 +56,7 @@ impl SniProxyServer {
     fn new(
         config: Arc<SniProxyServerConfig>,
         server_stats: Arc<TcpStreamServerStats>,
+        task_registry: Arc<ServerTaskRegistry>,
         listen_stats: Arc<ListenStats>,
         version: usize,
     ) -> anyhow::Result<SniProxyServer> {
@@ -79,6 +81,7 @@ impl SniProxyServer {
         let server = SniProxyServer {
             config,
             server_stats,
+            task_registry,
             listen_stats,
             ingress_net_filter,
             server_tcp_portmap,
@@ -100,9 +103,10 @@ impl SniProxyServer {
     ) -> anyhow::Result<ArcServerInternal> {
         let config = Arc::new(config);
         let server_stats = Arc::new(TcpStreamServerStats::new(config.name()));
+        let task_registry = Arc::new(ServerTaskRegistry::default());
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
-        let server = SniProxyServer::new(config, server_stats, listen_stats, 1)?;
+        let server = SniProxyServer::new(config, server_stats, task_registry, listen_stats, 1)?;
         Ok(Arc::new(server))
     }
 
@@ -110,10 +114,16 @@ impl SniProxyServer {
         if let AnyServerConfig::SniProxy(config) = config {
             let config = Arc::new(config);
             let server_stats = Arc::clone(&self.server_stats);
+            let task_registry = Arc::clone(&self.task_registry);
             let listen_stats = Arc::clone(&self.listen_stats);
 
-            let server =
-                SniProxyServer::new(config, server_stats, listen_stats, self.reload_version + 1)?;
+            let server = SniProxyServer::new(
+                config,
+                server_stats,
+                task_registry,
+                listen_stats,
+                self.reload_version + 1,
+            )?;
             Ok(server)
         } else {
             Err(anyhow!(
@@ -149,6 +159,7 @@ impl SniProxyServer {
         let ctx = CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
@@ -290,6 +301,10 @@ impl Server for SniProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
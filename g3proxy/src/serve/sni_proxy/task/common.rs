@@ -15,12 +15,13 @@ use g3_io_ext::IdleWheel;
 
 use crate::config::server::sni_proxy::SniProxyServerConfig;
 use crate::escape::ArcEscaper;
-use crate::serve::ServerQuitPolicy;
 use crate::serve::tcp_stream::TcpStreamServerStats;
+use crate::serve::{ServerQuitPolicy, ServerTaskRegistry};
 
 pub(crate) struct CommonTaskContext {
     pub(crate) server_config: Arc<SniProxyServerConfig>,
     pub(crate) server_stats: Arc<TcpStreamServerStats>,
+    pub(crate) task_registry: Arc<ServerTaskRegistry>,
     pub(crate) server_quit_policy: Arc<ServerQuitPolicy>,
     pub(crate) idle_wheel: Arc<IdleWheel>,
     pub(crate) escaper: ArcEscaper,
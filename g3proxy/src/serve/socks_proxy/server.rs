@@ -34,12 +34,13 @@ use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ServerRegistry, ServerStats, ServerTaskRegistry, WrapArcServer,
 };
 
 pub(crate) struct SocksProxyServer {
     config: Arc<SocksProxyServerConfig>,
     server_stats: Arc<SocksProxyServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
     ingress_net_filter: Option<Arc<AclNetworkRule>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
@@ -58,6 +59,7 @@ impl SocksProxyServer {
     fn new(
         config: Arc<SocksProxyServerConfig>,
         server_stats: Arc<SocksProxyServerStats>,
+        task_registry: Arc<ServerTaskRegistry>,
         listen_stats: Arc<ListenStats>,
         version: usize,
     ) -> anyhow::Result<SocksProxyServer> {
@@ -85,6 +87,7 @@ impl SocksProxyServer {
         let server = SocksProxyServer {
             config,
             server_stats,
+            task_registry,
             listen_stats,
             ingress_net_filter,
             dst_host_filter,
@@ -106,9 +109,10 @@ impl SocksProxyServer {
     ) -> anyhow::Result<ArcServerInternal> {
         let config = Arc::new(config);
         let server_stats = Arc::new(SocksProxyServerStats::new(config.name()));
+        let task_registry = Arc::new(ServerTaskRegistry::default());
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
-        let server = SocksProxyServer::new(config, server_stats, listen_stats, 1)?;
+        let server = SocksProxyServer::new(config, server_stats, task_registry, listen_stats, 1)?;
         Ok(Arc::new(server))
     }
 
@@ -116,10 +120,16 @@ impl SocksProxyServer {
         if let AnyServerConfig::SocksProxy(config) = config {
             let config = Arc::new(config);
             let server_stats = Arc::clone(&self.server_stats);
+            let task_registry = Arc::clone(&self.task_registry);
             let listen_stats = Arc::clone(&self.listen_stats);
 
-            let server =
-                SocksProxyServer::new(config, server_stats, listen_stats, self.reload_version + 1)?;
+            let server = SocksProxyServer::new(
+                config,
+                server_stats,
+                task_registry,
+                listen_stats,
+                self.reload_version + 1,
+            )?;
             Ok(server)
         } else {
             Err(anyhow!(
@@ -166,6 +176,7 @@ impl SocksProxyServer {
         let ctx = CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
@@ -303,6 +314,10 @@ impl Server for SocksProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
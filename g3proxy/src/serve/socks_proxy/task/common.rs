@@ -19,12 +19,15 @@ use g3_types::net::UpstreamAddr;
 
 use super::{SocksProxyServerConfig, SocksProxyServerStats};
 use crate::escape::ArcEscaper;
-use crate::serve::{ServerQuitPolicy, ServerTaskError, ServerTaskNotes, ServerTaskResult};
+use crate::serve::{
+    ServerQuitPolicy, ServerTaskError, ServerTaskNotes, ServerTaskRegistry, ServerTaskResult,
+};
 
 #[derive(Clone)]
 pub(crate) struct CommonTaskContext {
     pub(crate) server_config: Arc<SocksProxyServerConfig>,
     pub(crate) server_stats: Arc<SocksProxyServerStats>,
+    pub(crate) task_registry: Arc<ServerTaskRegistry>,
     pub(crate) server_quit_policy: Arc<ServerQuitPolicy>,
     pub(crate) idle_wheel: Arc<IdleWheel>,
     pub(crate) escaper: ArcEscaper,
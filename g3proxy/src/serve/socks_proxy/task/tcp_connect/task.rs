@@ -9,6 +9,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
@@ -25,8 +26,8 @@ use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    RegisteredTask, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct SocksProxyTcpConnectTask {
@@ -115,6 +116,13 @@ impl SocksProxyTcpConnectTask {
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_tcp_connect.add_task();
         self.ctx.server_stats.task_tcp_connect.inc_alive_task();
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -517,4 +525,8 @@ impl StreamTransitTask for SocksProxyTcpConnectTask {
     fn user(&self) -> Option<&User> {
         self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref())
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.task_notes.cancel_token()
+    }
 }
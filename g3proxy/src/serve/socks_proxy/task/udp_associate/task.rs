@@ -29,8 +29,8 @@ use crate::log::escape::udp_sendto::EscapeLogForUdpRelaySendto;
 use crate::log::task::udp_associate::TaskLogForUdpAssociate;
 use crate::module::udp_relay::{UdpRelayTaskConf, UdpRelayTaskNotes};
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    RegisteredTask, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct SocksProxyUdpAssociateTask {
@@ -249,6 +249,13 @@ impl SocksProxyUdpAssociateTask {
 
         let (clt_r, clt_w, ups_r, ups_w, escape_logger) =
             self.split_all(&mut clt_tcp_r, clt_socket).await?;
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.initial_peer,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         self.task_notes.mark_relaying();
         if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -288,10 +295,15 @@ impl SocksProxyUdpAssociateTask {
         let mut log_interval = self.ctx.get_log_interval();
         let mut idle_count = 0;
         let mut buf: [u8; 4] = [0; 4];
+        let cancel_token = self.task_notes.cancel_token();
         loop {
             tokio::select! {
                 biased;
 
+                _ = cancel_token.cancelled() => {
+                    return Err(self.task_notes.cancel_error());
+                }
+
                 r = clt_tcp_r.read(&mut buf) => {
                     return match r {
                         Ok(0) => Ok(()),
@@ -348,8 +360,8 @@ impl SocksProxyUdpAssociateTask {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -364,8 +376,8 @@ impl SocksProxyUdpAssociateTask {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
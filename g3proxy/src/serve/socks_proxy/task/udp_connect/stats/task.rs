@@ -6,6 +6,7 @@
 use g3_daemon::stat::task::UdpConnectConnectionStats;
 
 use crate::module::udp_connect::UdpConnectTaskRemoteStats;
+use crate::serve::RegisteredTaskStats;
 
 #[derive(Default)]
 pub(crate) struct UdpConnectTaskStats {
@@ -30,3 +31,21 @@ impl UdpConnectTaskRemoteStats for UdpConnectTaskStats {
         self.ups.send.add_packets(n);
     }
 }
+
+impl RegisteredTaskStats for UdpConnectTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.recv.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.send.get_bytes()
+    }
+
+    fn upstream_read_bytes(&self) -> u64 {
+        self.ups.recv.get_bytes()
+    }
+
+    fn upstream_write_bytes(&self) -> u64 {
+        self.ups.send.get_bytes()
+    }
+}
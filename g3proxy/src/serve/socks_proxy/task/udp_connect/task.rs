@@ -29,8 +29,8 @@ use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
 use crate::log::task::udp_connect::TaskLogForUdpConnect;
 use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    RegisteredTask, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct SocksProxyUdpConnectTask {
@@ -83,8 +83,8 @@ impl SocksProxyUdpConnectTask {
             .map(|logger| TaskLogForUdpConnect {
                 logger,
                 task_notes: &self.task_notes,
-                tcp_server_addr: self.ctx.server_addr(),
-                tcp_client_addr: self.ctx.client_addr(),
+                tcp_server_addr: Some(self.ctx.server_addr()),
+                tcp_client_addr: Some(self.ctx.client_addr()),
                 udp_listen_addr: self.udp_listen_addr,
                 udp_client_addr: self.udp_client_addr,
                 upstream: self.upstream.as_ref(),
@@ -298,6 +298,14 @@ impl SocksProxyUdpConnectTask {
 
         let (clt_r, clt_w, ups_r, ups_w, escape_logger) =
             self.split_all(&mut clt_tcp_r, clt_socket).await?;
+        let upstream = self.upstream.clone().unwrap_or_else(UpstreamAddr::empty);
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         self.task_notes.mark_relaying();
         if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -337,10 +345,15 @@ impl SocksProxyUdpConnectTask {
         let mut log_interval = self.ctx.get_log_interval();
         let mut idle_count = 0;
         let mut buf: [u8; 4] = [0; 4];
+        let cancel_token = self.task_notes.cancel_token();
         loop {
             tokio::select! {
                 biased;
 
+                _ = cancel_token.cancelled() => {
+                    return Err(self.task_notes.cancel_error());
+                }
+
                 r = clt_tcp_r.read(&mut buf) => {
                     return match r {
                         Ok(0) => Ok(()),
@@ -397,8 +410,8 @@ impl SocksProxyUdpConnectTask {
 
                         if let Some(user_ctx) = self.task_notes.user_ctx() {
                             let user = user_ctx.user();
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            if let Some(reason) = user.blocked_reason() {
+                                return Err(ServerTaskError::from(reason));
                             }
                         }
 
@@ -413,8 +426,8 @@ impl SocksProxyUdpConnectTask {
                     }
 
                     if let Some(user_ctx) = self.task_notes.user_ctx() {
-                        if user_ctx.user().is_blocked() {
-                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        if let Some(reason) = user_ctx.user().blocked_reason() {
+                            return Err(ServerTaskError::from(reason));
                         }
                     }
 
@@ -478,6 +491,7 @@ impl SocksProxyUdpConnectTask {
             .recv_first_packet(clt_tcp_r, &mut clt_r, &mut buf)
             .await?;
         self.udp_client_addr = Some(udp_client_addr);
+        self.upstream = Some(upstream.clone());
 
         if let Some(user_ctx) = self.task_notes.user_ctx_mut() {
             // set user site by using the upstream address of the first packet
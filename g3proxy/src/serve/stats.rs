@@ -40,6 +40,14 @@ pub(crate) trait ServerStats {
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         None
     }
+
+    fn http_cache_snapshot(&self) -> Option<ServerHttpCacheSnapshot> {
+        None
+    }
+
+    fn upstream_discover_snapshot(&self) -> Option<ServerUpstreamDiscoverSnapshot> {
+        None
+    }
 }
 
 pub(crate) type ArcServerStats = Arc<dyn ServerStats + Send + Sync>;
@@ -80,6 +88,86 @@ impl ServerForbiddenStats {
     }
 }
 
+#[derive(Default)]
+pub(crate) struct ServerHttpCacheSnapshot {
+    pub(crate) hit: u64,
+    pub(crate) miss: u64,
+    pub(crate) revalidated: u64,
+    pub(crate) stored: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct ServerHttpCacheStats {
+    hit: AtomicU64,
+    miss: AtomicU64,
+    revalidated: AtomicU64,
+    stored: AtomicU64,
+}
+
+impl ServerHttpCacheStats {
+    pub(crate) fn add_hit(&self) {
+        self.hit.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_miss(&self) {
+        self.miss.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_revalidated(&self) {
+        self.revalidated.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_stored(&self) {
+        self.stored.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ServerHttpCacheSnapshot {
+        ServerHttpCacheSnapshot {
+            hit: self.hit.load(Ordering::Relaxed),
+            miss: self.miss.load(Ordering::Relaxed),
+            revalidated: self.revalidated.load(Ordering::Relaxed),
+            stored: self.stored.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ServerUpstreamDiscoverSnapshot {
+    pub(crate) updated: u64,
+    pub(crate) failed: u64,
+    pub(crate) added: u64,
+    pub(crate) removed: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct ServerUpstreamDiscoverStats {
+    updated: AtomicU64,
+    failed: AtomicU64,
+    added: AtomicU64,
+    removed: AtomicU64,
+}
+
+impl ServerUpstreamDiscoverStats {
+    pub(crate) fn add_updated(&self, added: usize, removed: usize) {
+        self.updated.fetch_add(1, Ordering::Relaxed);
+        self.added.fetch_add(added as u64, Ordering::Relaxed);
+        self.removed.fetch_add(removed as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_failed(&self) {
+        self.failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ServerUpstreamDiscoverSnapshot {
+        ServerUpstreamDiscoverSnapshot {
+            updated: self.updated.load(Ordering::Relaxed),
+            failed: self.failed.load(Ordering::Relaxed),
+            added: self.added.load(Ordering::Relaxed),
+            removed: self.removed.load(Ordering::Relaxed),
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct ServerPerTaskStats {
     task_total: AtomicU64,
@@ -0,0 +1,206 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::collection::{SelectivePickPolicy, SelectiveVec, SelectiveVecBuilder};
+use g3_types::net::{UpstreamAddr, WeightedUpstreamAddr};
+
+use crate::config::server::UpstreamAffinityConfig;
+
+/// Client address to upstream address mapping, with a sliding ttl for each entry
+struct UpstreamAffinityTable {
+    config: UpstreamAffinityConfig,
+    inner: Mutex<HashMap<IpAddr, (UpstreamAddr, Instant)>>,
+}
+
+impl UpstreamAffinityTable {
+    fn new(config: UpstreamAffinityConfig) -> Self {
+        UpstreamAffinityTable {
+            config,
+            inner: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Create a new table and keep the entries from the old one if the upstream is still valid
+    fn inherit<F>(config: UpstreamAffinityConfig, old: &Self, is_valid: F) -> Self
+    where
+        F: Fn(&UpstreamAddr) -> bool,
+    {
+        let now = Instant::now();
+        let old_map = old.inner.lock().unwrap();
+        let mut map = HashMap::with_capacity(old_map.len().min(config.max_entries));
+        for (ip, (upstream, expire)) in old_map.iter() {
+            if map.len() >= config.max_entries {
+                break;
+            }
+            if *expire > now && is_valid(upstream) {
+                map.insert(*ip, (upstream.clone(), *expire));
+            }
+        }
+        drop(old_map);
+
+        UpstreamAffinityTable {
+            config,
+            inner: Mutex::new(map),
+        }
+    }
+
+    fn get(&self, ip: IpAddr, now: Instant) -> Option<UpstreamAddr> {
+        let mut map = self.inner.lock().unwrap();
+        let (upstream, expire) = map.get_mut(&ip)?;
+        if *expire > now {
+            *expire = now + self.config.ttl;
+            Some(upstream.clone())
+        } else {
+            map.remove(&ip);
+            None
+        }
+    }
+
+    fn insert(&self, ip: IpAddr, upstream: UpstreamAddr, now: Instant) {
+        let mut map = self.inner.lock().unwrap();
+        if map.len() >= self.config.max_entries {
+            map.retain(|_, (_, expire)| *expire > now);
+            if map.len() >= self.config.max_entries {
+                return;
+            }
+        }
+        map.insert(ip, (upstream, now + self.config.ttl));
+    }
+}
+
+struct UpstreamSlowStart {
+    duration: Duration,
+    /// the nodes that are not in slow start
+    warm_nodes: Option<SelectiveVec<WeightedUpstreamAddr>>,
+}
+
+/// Upstream selection with optional slow start and session affinity
+pub(crate) struct StreamUpstreamPicker {
+    upstream: Vec<WeightedUpstreamAddr>,
+    nodes: SelectiveVec<WeightedUpstreamAddr>,
+    pick_policy: SelectivePickPolicy,
+    /// the time when each upstream is added, None if it's there since the server is created
+    added_time: HashMap<UpstreamAddr, Option<Instant>>,
+    slow_start: Option<UpstreamSlowStart>,
+    affinity: Option<UpstreamAffinityTable>,
+}
+
+impl StreamUpstreamPicker {
+    pub(crate) fn new(
+        upstream: &[WeightedUpstreamAddr],
+        pick_policy: SelectivePickPolicy,
+        slow_start: Duration,
+        affinity: Option<&UpstreamAffinityConfig>,
+        old: Option<&StreamUpstreamPicker>,
+    ) -> anyhow::Result<Self> {
+        let now = Instant::now();
+
+        let mut added_time = HashMap::with_capacity(upstream.len());
+        for node in upstream {
+            let time = match old {
+                Some(old) => match old.added_time.get(node.inner()) {
+                    Some(time) => *time,
+                    None => Some(now),
+                },
+                None => None,
+            };
+            added_time.insert(node.inner().clone(), time);
+        }
+
+        let mut nodes_builder = SelectiveVecBuilder::with_capacity(upstream.len());
+        let mut warm_nodes_builder = SelectiveVecBuilder::with_capacity(upstream.len());
+        for node in upstream {
+            nodes_builder.insert(node.clone());
+            if !matches!(added_time.get(node.inner()), Some(Some(t)) if now - *t < slow_start) {
+                warm_nodes_builder.insert(node.clone());
+            }
+        }
+        let nodes = nodes_builder
+            .build()
+            .ok_or_else(|| anyhow!("no upstream addr set"))?;
+
+        let slow_start = if slow_start.is_zero() {
+            None
+        } else {
+            Some(UpstreamSlowStart {
+                duration: slow_start,
+                warm_nodes: warm_nodes_builder.build(),
+            })
+        };
+
+        let affinity = affinity.map(|config| match old.and_then(|o| o.affinity.as_ref()) {
+            Some(old_table) => {
+                UpstreamAffinityTable::inherit(config.clone(), old_table, |upstream| {
+                    added_time.contains_key(upstream)
+                })
+            }
+            None => UpstreamAffinityTable::new(config.clone()),
+        });
+
+        Ok(StreamUpstreamPicker {
+            upstream: upstream.to_vec(),
+            nodes,
+            pick_policy,
+            added_time,
+            slow_start,
+            affinity,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn upstream(&self) -> &[WeightedUpstreamAddr] {
+        &self.upstream
+    }
+
+    fn slow_start_skip(&self, upstream: &UpstreamAddr, now: Instant) -> bool {
+        let Some(slow_start) = &self.slow_start else {
+            return false;
+        };
+        if slow_start.warm_nodes.is_none() {
+            return false;
+        }
+        let Some(Some(added)) = self.added_time.get(upstream) else {
+            return false;
+        };
+        let elapsed = now.saturating_duration_since(*added);
+        if elapsed >= slow_start.duration {
+            return false;
+        }
+        let factor = elapsed.as_secs_f64() / slow_start.duration.as_secs_f64();
+        rand::random::<f64>() >= factor
+    }
+
+    pub(crate) fn pick(&self, cc_info: &ClientConnectionInfo) -> UpstreamAddr {
+        let now = Instant::now();
+        let client_ip = cc_info.client_ip();
+
+        if let Some(table) = &self.affinity {
+            if let Some(upstream) = table.get(client_ip, now) {
+                return upstream;
+            }
+        }
+
+        let mut node = g3_daemon::server::select_consistent(&self.nodes, self.pick_policy, cc_info);
+        if self.slow_start_skip(node.inner(), now) {
+            if let Some(warm_nodes) = self.slow_start.as_ref().and_then(|s| s.warm_nodes.as_ref()) {
+                node = g3_daemon::server::select_consistent(warm_nodes, self.pick_policy, cc_info);
+            }
+        }
+        let upstream = node.inner().clone();
+
+        if let Some(table) = &self.affinity {
+            table.insert(client_ip, upstream.clone(), now);
+        }
+        upstream
+    }
+}
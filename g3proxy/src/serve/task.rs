@@ -9,11 +9,13 @@ use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use g3_daemon::server::ClientConnectionInfo;
 use g3_types::limit::GaugeSemaphorePermit;
 
+use super::{RegisteredTaskGuard, ServerTaskError};
 use crate::auth::UserContext;
 use crate::escape::EgressPathSelection;
 
@@ -59,6 +61,7 @@ pub(crate) struct ServerTaskNotes {
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
     /// the following fields should not be cloned
     pub(crate) user_req_alive_permit: Option<GaugeSemaphorePermit>,
+    pub(crate) registered_task: Option<RegisteredTaskGuard>,
 }
 
 impl ServerTaskNotes {
@@ -89,6 +92,7 @@ impl ServerTaskNotes {
             ready_time: Duration::default(),
             egress_path_selection,
             user_req_alive_permit: None,
+            registered_task: None,
         }
     }
 
@@ -133,6 +137,35 @@ impl ServerTaskNotes {
             .or(self.egress_path_selection.as_ref())
     }
 
+    /// Check if the task has been killed through the control API
+    pub(crate) fn is_canceled(&self) -> bool {
+        self.registered_task
+            .as_ref()
+            .map(|g| g.task().is_canceled())
+            .unwrap_or(false)
+    }
+
+    /// Get the token to wait for the kill operation through the control API or the user revoke,
+    /// it will never be canceled if the task is neither registered nor authenticated
+    pub(crate) fn cancel_token(&self) -> CancellationToken {
+        if let Some(g) = &self.registered_task {
+            g.task().cancel_token().clone()
+        } else if let Some(ctx) = &self.user_ctx {
+            ctx.user().revoke_token()
+        } else {
+            CancellationToken::default()
+        }
+    }
+
+    /// Get the error to return after the cancel token is canceled
+    pub(crate) fn cancel_error(&self) -> ServerTaskError {
+        self.user_ctx
+            .as_ref()
+            .and_then(|ctx| ctx.user().blocked_reason())
+            .map(ServerTaskError::from)
+            .unwrap_or(ServerTaskError::CanceledAsTaskKilled)
+    }
+
     #[inline]
     pub(crate) fn task_created_instant(&self) -> Instant {
         self.create_ins
@@ -0,0 +1,310 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use foldhash::HashMap;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use g3_daemon::stat::task::TcpStreamTaskStats;
+use g3_types::metrics::NodeName;
+use g3_types::net::UpstreamAddr;
+
+use super::ServerTaskNotes;
+
+/// The traffic stats of a registered task
+pub(crate) trait RegisteredTaskStats: Send + Sync {
+    fn client_read_bytes(&self) -> u64;
+    fn client_write_bytes(&self) -> u64;
+    fn upstream_read_bytes(&self) -> u64;
+    fn upstream_write_bytes(&self) -> u64;
+}
+
+impl RegisteredTaskStats for TcpStreamTaskStats {
+    fn client_read_bytes(&self) -> u64 {
+        self.clt.read.get_bytes()
+    }
+
+    fn client_write_bytes(&self) -> u64 {
+        self.clt.write.get_bytes()
+    }
+
+    fn upstream_read_bytes(&self) -> u64 {
+        self.ups.read.get_bytes()
+    }
+
+    fn upstream_write_bytes(&self) -> u64 {
+        self.ups.write.get_bytes()
+    }
+}
+
+/// A live task that can be listed and canceled through the control API
+pub(crate) struct RegisteredTask {
+    id: Uuid,
+    user: Option<Arc<str>>,
+    client_addr: SocketAddr,
+    upstream: UpstreamAddr,
+    escaper: NodeName,
+    start_at: DateTime<Utc>,
+    create_ins: Instant,
+    stats: Arc<dyn RegisteredTaskStats>,
+    cancel_token: CancellationToken,
+}
+
+impl RegisteredTask {
+    pub(crate) fn new(
+        task_notes: &ServerTaskNotes,
+        upstream: &UpstreamAddr,
+        escaper: &NodeName,
+        stats: Arc<dyn RegisteredTaskStats>,
+    ) -> Self {
+        // the task will also be canceled when the user is revoked
+        let cancel_token = task_notes
+            .user_ctx()
+            .map(|ctx| ctx.user().revoke_token().child_token())
+            .unwrap_or_default();
+        RegisteredTask {
+            id: task_notes.id,
+            user: task_notes.user_ctx().map(|ctx| ctx.user_name().clone()),
+            client_addr: task_notes.client_addr(),
+            upstream: upstream.clone(),
+            escaper: escaper.clone(),
+            start_at: task_notes.start_at,
+            create_ins: task_notes.task_created_instant(),
+            stats,
+            cancel_token,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    #[inline]
+    pub(crate) fn user(&self) -> Option<&str> {
+        self.user.as_deref()
+    }
+
+    #[inline]
+    pub(crate) fn client_addr(&self) -> SocketAddr {
+        self.client_addr
+    }
+
+    #[inline]
+    pub(crate) fn upstream(&self) -> &UpstreamAddr {
+        &self.upstream
+    }
+
+    #[inline]
+    pub(crate) fn escaper(&self) -> &NodeName {
+        &self.escaper
+    }
+
+    #[inline]
+    pub(crate) fn start_at(&self) -> &DateTime<Utc> {
+        &self.start_at
+    }
+
+    #[inline]
+    pub(crate) fn age(&self) -> Duration {
+        self.create_ins.elapsed()
+    }
+
+    #[inline]
+    pub(crate) fn stats(&self) -> &dyn RegisteredTaskStats {
+        self.stats.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn is_canceled(&self) -> bool {
+        self.cancel_token.is_cancelled()
+    }
+
+    /// Get the token that will be canceled when the task is killed or the user is revoked
+    #[inline]
+    pub(crate) fn cancel_token(&self) -> &CancellationToken {
+        &self.cancel_token
+    }
+
+    fn cancel(&self) {
+        self.cancel_token.cancel();
+    }
+
+    fn match_upstream(&self, upstream: &str) -> bool {
+        self.upstream.to_string() == upstream || self.upstream.host().to_string() == upstream
+    }
+}
+
+/// The registry of all live tasks on a single server
+///
+/// The tasks will wait on the cancel token while relaying, so they will quit as soon as they
+/// are canceled.
+#[derive(Default)]
+pub(crate) struct ServerTaskRegistry {
+    inner: Mutex<HashMap<Uuid, Arc<RegisteredTask>>>,
+}
+
+impl ServerTaskRegistry {
+    pub(crate) fn register(self: &Arc<Self>, task: RegisteredTask) -> RegisteredTaskGuard {
+        let task = Arc::new(task);
+        let mut map = self.inner.lock().unwrap();
+        map.insert(task.id, task.clone());
+        RegisteredTaskGuard {
+            registry: self.clone(),
+            task,
+        }
+    }
+
+    fn unregister(&self, id: &Uuid) {
+        let mut map = self.inner.lock().unwrap();
+        map.remove(id);
+    }
+
+    pub(crate) fn list(
+        &self,
+        user: Option<&str>,
+        upstream: Option<&str>,
+    ) -> Vec<Arc<RegisteredTask>> {
+        let map = self.inner.lock().unwrap();
+        let mut tasks: Vec<Arc<RegisteredTask>> = map
+            .values()
+            .filter(|t| user.map(|u| t.user() == Some(u)).unwrap_or(true))
+            .filter(|t| upstream.map(|u| t.match_upstream(u)).unwrap_or(true))
+            .cloned()
+            .collect();
+        drop(map);
+        tasks.sort_by_key(|t| t.create_ins);
+        tasks
+    }
+
+    pub(crate) fn cancel(&self, id: &Uuid) -> bool {
+        let map = self.inner.lock().unwrap();
+        if let Some(task) = map.get(id) {
+            task.cancel();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn cancel_user(&self, user: &str) -> usize {
+        let map = self.inner.lock().unwrap();
+        let mut count = 0;
+        for task in map.values() {
+            if task.user() == Some(user) {
+                task.cancel();
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+/// The task will be removed from the registry when this guard is dropped
+pub(crate) struct RegisteredTaskGuard {
+    registry: Arc<ServerTaskRegistry>,
+    task: Arc<RegisteredTask>,
+}
+
+impl RegisteredTaskGuard {
+    #[inline]
+    pub(crate) fn task(&self) -> &Arc<RegisteredTask> {
+        &self.task
+    }
+}
+
+impl Drop for RegisteredTaskGuard {
+    fn drop(&mut self) {
+        self.registry.unregister(&self.task.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_daemon::server::ClientConnectionInfo;
+
+    fn new_task(upstream: &UpstreamAddr) -> RegisteredTask {
+        let cc_info = ClientConnectionInfo::new(
+            "127.0.0.1:8080".parse().unwrap(),
+            "127.0.0.1:1234".parse().unwrap(),
+        );
+        let task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+        RegisteredTask::new(
+            &task_notes,
+            upstream,
+            &NodeName::default(),
+            Arc::new(TcpStreamTaskStats::default()),
+        )
+    }
+
+    #[test]
+    fn register_and_cancel() {
+        let registry = Arc::new(ServerTaskRegistry::default());
+        let upstream = UpstreamAddr::from_host_str_and_port("www.example.com", 443).unwrap();
+
+        let guard1 = registry.register(new_task(&upstream));
+        let guard2 = registry.register(new_task(&upstream));
+        assert_eq!(registry.list(None, None).len(), 2);
+        assert_eq!(registry.list(None, Some("www.example.com")).len(), 2);
+        assert_eq!(registry.list(None, Some("www.example.com:443")).len(), 2);
+        assert!(registry.list(None, Some("www.example.net")).is_empty());
+        assert!(registry.list(Some("root"), None).is_empty());
+        assert_eq!(registry.cancel_user("root"), 0);
+
+        assert!(registry.cancel(guard1.task().id()));
+        assert!(guard1.task().is_canceled());
+        assert!(!guard2.task().is_canceled());
+
+        let id = *guard1.task().id();
+        drop(guard1);
+        assert_eq!(registry.list(None, None).len(), 1);
+        assert!(!registry.cancel(&id));
+        drop(guard2);
+        assert_eq!(registry.list(None, None).len(), 0);
+    }
+
+    #[tokio::test]
+    async fn wake_on_cancel() {
+        let registry = Arc::new(ServerTaskRegistry::default());
+        let upstream = UpstreamAddr::from_host_str_and_port("www.example.com", 443).unwrap();
+
+        let cc_info = ClientConnectionInfo::new(
+            "127.0.0.1:8080".parse().unwrap(),
+            "127.0.0.1:1234".parse().unwrap(),
+        );
+        let mut task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+        // not registered tasks can not be canceled
+        assert!(!task_notes.is_canceled());
+        let cancel_token = task_notes.cancel_token();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), cancel_token.cancelled())
+                .await
+                .is_err()
+        );
+
+        let guard = registry.register(new_task(&upstream));
+        let id = *guard.task().id();
+        task_notes.registered_task = Some(guard);
+        let cancel_token = task_notes.cancel_token();
+        let waiter = tokio::spawn(async move { cancel_token.cancelled().await });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        // no need to wait for the idle check
+        assert!(registry.cancel(&id));
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(task_notes.is_canceled());
+    }
+}
@@ -15,11 +15,12 @@ use g3_types::net::OpensslClientConfig;
 use super::stats::TcpStreamServerStats;
 use crate::config::server::tcp_stream::TcpStreamServerConfig;
 use crate::escape::ArcEscaper;
-use crate::serve::ServerQuitPolicy;
+use crate::serve::{ServerQuitPolicy, ServerTaskRegistry};
 
 pub(super) struct CommonTaskContext {
     pub(super) server_config: Arc<TcpStreamServerConfig>,
     pub(super) server_stats: Arc<TcpStreamServerStats>,
+    pub(super) task_registry: Arc<ServerTaskRegistry>,
     pub(super) server_quit_policy: Arc<ServerQuitPolicy>,
     pub(super) idle_wheel: Arc<IdleWheel>,
     pub(super) escaper: ArcEscaper,
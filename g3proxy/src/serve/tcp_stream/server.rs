@@ -22,9 +22,8 @@ use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerExt, ServerReloa
 use g3_io_ext::{AsyncStream, IdleWheel};
 use g3_openssl::SslStream;
 use g3_types::acl::{AclAction, AclNetworkRule};
-use g3_types::collection::{SelectiveVec, SelectiveVecBuilder};
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslClientConfig, UpstreamAddr, WeightedUpstreamAddr};
+use g3_types::net::{OpensslClientConfig, UpstreamAddr};
 
 use super::common::CommonTaskContext;
 use super::stats::TcpStreamServerStats;
@@ -34,15 +33,17 @@ use crate::config::server::tcp_stream::TcpStreamServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::{
-    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ArcServer, ArcServerInternal, ArcServerStats, DynamicUpstream, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, ServerStats, ServerTaskError, ServerTaskRegistry,
+    WrapArcServer,
 };
 
 pub(crate) struct TcpStreamServer {
     config: Arc<TcpStreamServerConfig>,
     server_stats: Arc<TcpStreamServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
-    upstream: SelectiveVec<WeightedUpstreamAddr>,
+    upstream: DynamicUpstream,
     tls_client_config: Option<Arc<OpensslClientConfig>>,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
@@ -59,18 +60,19 @@ impl TcpStreamServer {
     fn new(
         config: Arc<TcpStreamServerConfig>,
         server_stats: Arc<TcpStreamServerStats>,
+        task_registry: Arc<ServerTaskRegistry>,
         listen_stats: Arc<ListenStats>,
         version: usize,
+        old_upstream: Option<&DynamicUpstream>,
     ) -> anyhow::Result<TcpStreamServer> {
         let reload_sender = crate::serve::new_reload_notify_channel();
 
-        let mut nodes_builder = SelectiveVecBuilder::new();
-        for node in &config.upstream {
-            nodes_builder.insert(node.clone());
-        }
-        let upstream = nodes_builder
-            .build()
-            .ok_or_else(|| anyhow!("no upstream addr set"))?;
+        let upstream = DynamicUpstream::new(
+            format!("server {}", config.name()),
+            config.as_ref(),
+            server_stats.upstream_discover.clone(),
+            old_upstream,
+        )?;
 
         let tls_client_config = if let Some(builder) = &config.client_tls_config {
             let tls_config = builder
@@ -97,6 +99,7 @@ impl TcpStreamServer {
         let server = TcpStreamServer {
             config,
             server_stats,
+            task_registry,
             listen_stats,
             upstream,
             tls_client_config,
@@ -118,9 +121,11 @@ impl TcpStreamServer {
     ) -> anyhow::Result<ArcServerInternal> {
         let config = Arc::new(config);
         let server_stats = Arc::new(TcpStreamServerStats::new(config.name()));
+        let task_registry = Arc::new(ServerTaskRegistry::default());
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
-        let server = TcpStreamServer::new(config, server_stats, listen_stats, 1)?;
+        let server =
+            TcpStreamServer::new(config, server_stats, task_registry, listen_stats, 1, None)?;
         Ok(Arc::new(server))
     }
 
@@ -128,10 +133,17 @@ impl TcpStreamServer {
         if let AnyServerConfig::TcpStream(config) = config {
             let config = Arc::new(config);
             let server_stats = Arc::clone(&self.server_stats);
+            let task_registry = Arc::clone(&self.task_registry);
             let listen_stats = Arc::clone(&self.listen_stats);
 
-            let server =
-                TcpStreamServer::new(config, server_stats, listen_stats, self.reload_version + 1)?;
+            let server = TcpStreamServer::new(
+                config,
+                server_stats,
+                task_registry,
+                listen_stats,
+                self.reload_version + 1,
+                Some(&self.upstream),
+            )?;
             Ok(server)
         } else {
             Err(anyhow!(
@@ -166,13 +178,13 @@ impl TcpStreamServer {
     fn get_ctx_and_upstream(
         &self,
         cc_info: ClientConnectionInfo,
-    ) -> (CommonTaskContext, &UpstreamAddr) {
-        let upstream =
-            self.select_consistent(&self.upstream, self.config.upstream_pick_policy, &cc_info);
+    ) -> (CommonTaskContext, Result<UpstreamAddr, ServerTaskError>) {
+        let upstream = self.upstream.pick(&cc_info);
 
         let ctx = CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
@@ -181,7 +193,7 @@ impl TcpStreamServer {
             task_logger: self.task_logger.clone(),
         };
 
-        (ctx, upstream.inner())
+        (ctx, upstream)
     }
 
     async fn run_task_with_stream<T>(&self, stream: T, cc_info: ClientConnectionInfo)
@@ -243,6 +255,14 @@ impl ServerInternal for TcpStreamServer {
         Ok(())
     }
 
+    fn _depend_on_discover(&self, name: &NodeName) -> bool {
+        self.upstream.depend_on_discover(name)
+    }
+
+    fn _update_discover_in_place(&self) {
+        self.upstream.update_discover();
+    }
+
     fn _reload_with_old_notifier(
         &self,
         config: AnyServerConfig,
@@ -368,6 +388,10 @@ impl Server for TcpStreamServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
@@ -12,7 +12,10 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
-use crate::serve::{ServerForbiddenSnapshot, ServerForbiddenStats, ServerStats};
+use crate::serve::{
+    ServerForbiddenSnapshot, ServerForbiddenStats, ServerStats, ServerUpstreamDiscoverSnapshot,
+    ServerUpstreamDiscoverStats,
+};
 
 pub(crate) struct TcpStreamServerStats {
     name: NodeName,
@@ -27,6 +30,7 @@ pub(crate) struct TcpStreamServerStats {
     task_alive_count: AtomicI32,
 
     tcp: TcpIoStats,
+    pub(crate) upstream_discover: Arc<ServerUpstreamDiscoverStats>,
     pub(crate) forbidden: ServerForbiddenStats,
 }
 
@@ -41,6 +45,7 @@ impl TcpStreamServerStats {
             task_total: AtomicU64::new(0),
             task_alive_count: AtomicI32::new(0),
             tcp: Default::default(),
+            upstream_discover: Arc::new(Default::default()),
             forbidden: Default::default(),
         }
     }
@@ -132,4 +137,8 @@ impl ServerStats for TcpStreamServerStats {
     fn forbidden_stats(&self) -> ServerForbiddenSnapshot {
         self.forbidden.snapshot()
     }
+
+    fn upstream_discover_snapshot(&self) -> Option<ServerUpstreamDiscoverSnapshot> {
+        Some(self.upstream_discover.snapshot())
+    }
 }
@@ -7,6 +7,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
@@ -20,11 +21,14 @@ use crate::auth::User;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
-use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::serve::{
+    RegisteredTask, ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage,
+};
 
 pub(super) struct TcpStreamTask {
     ctx: CommonTaskContext,
     upstream: UpstreamAddr,
+    upstream_error: Option<ServerTaskError>,
     tcp_notes: TcpConnectTaskNotes,
     task_notes: ServerTaskNotes,
     task_stats: Arc<TcpStreamTaskStats>,
@@ -35,13 +39,18 @@ pub(super) struct TcpStreamTask {
 impl TcpStreamTask {
     pub(super) fn new(
         ctx: CommonTaskContext,
-        upstream: &UpstreamAddr,
+        upstream: Result<UpstreamAddr, ServerTaskError>,
         audit_ctx: AuditContext,
     ) -> Self {
+        let (upstream, upstream_error) = match upstream {
+            Ok(upstream) => (upstream, None),
+            Err(e) => (UpstreamAddr::empty(), Some(e)),
+        };
         let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, Duration::ZERO);
         TcpStreamTask {
             ctx,
-            upstream: upstream.clone(),
+            upstream,
+            upstream_error,
             tcp_notes: TcpConnectTaskNotes::default(),
             task_notes,
             task_stats: Arc::new(TcpStreamTaskStats::default()),
@@ -84,6 +93,13 @@ impl TcpStreamTask {
 
     fn pre_start(&mut self) {
         self._alive_guard = Some(self.ctx.server_stats.add_task());
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if self.ctx.server_config.flush_task_log_on_created {
             if let Some(log_ctx) = self.get_log_context() {
@@ -105,6 +121,10 @@ impl TcpStreamTask {
                 ServerTaskError::InternalServerError("failed to set client socket options")
             })?;
 
+        if let Some(e) = self.upstream_error.take() {
+            return Err(e);
+        }
+
         self.task_notes.stage = ServerTaskStage::Connecting;
         let (ups_r, ups_w) = if let Some(tls_client_config) = &self.ctx.tls_client_config {
             let tls_name = self
@@ -282,4 +302,8 @@ impl StreamTransitTask for TcpStreamTask {
     fn user(&self) -> Option<&User> {
         None
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.task_notes.cancel_token()
+    }
 }
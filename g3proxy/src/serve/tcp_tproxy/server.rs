@@ -32,12 +32,13 @@ use crate::escape::ArcEscaper;
 use crate::serve::tcp_stream::TcpStreamServerStats;
 use crate::serve::{
     ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ServerRegistry, ServerStats, ServerTaskRegistry, WrapArcServer,
 };
 
 pub(crate) struct TcpTProxyServer {
     config: Arc<TcpTProxyServerConfig>,
     server_stats: Arc<TcpStreamServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
@@ -54,6 +55,7 @@ impl TcpTProxyServer {
     fn new(
         config: Arc<TcpTProxyServerConfig>,
         server_stats: Arc<TcpStreamServerStats>,
+        task_registry: Arc<ServerTaskRegistry>,
         listen_stats: Arc<ListenStats>,
         version: usize,
     ) -> anyhow::Result<Self> {
@@ -75,6 +77,7 @@ impl TcpTProxyServer {
         let server = TcpTProxyServer {
             config,
             server_stats,
+            task_registry,
             listen_stats,
             ingress_net_filter,
             reload_sender,
@@ -94,9 +97,10 @@ impl TcpTProxyServer {
     ) -> anyhow::Result<ArcServerInternal> {
         let config = Arc::new(config);
         let server_stats = Arc::new(TcpStreamServerStats::new(config.name()));
+        let task_registry = Arc::new(ServerTaskRegistry::default());
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
-        let server = TcpTProxyServer::new(config, server_stats, listen_stats, 1)?;
+        let server = TcpTProxyServer::new(config, server_stats, task_registry, listen_stats, 1)?;
         Ok(Arc::new(server))
     }
 
@@ -104,10 +108,16 @@ impl TcpTProxyServer {
         if let AnyServerConfig::TcpTProxy(config) = config {
             let config = Arc::new(config);
             let server_stats = Arc::clone(&self.server_stats);
+            let task_registry = Arc::clone(&self.task_registry);
             let listen_stats = Arc::clone(&self.listen_stats);
 
-            let server =
-                TcpTProxyServer::new(config, server_stats, listen_stats, self.reload_version + 1)?;
+            let server = TcpTProxyServer::new(
+                config,
+                server_stats,
+                task_registry,
+                listen_stats,
+                self.reload_version + 1,
+            )?;
             Ok(server)
         } else {
             Err(anyhow!(
@@ -143,6 +153,7 @@ impl TcpTProxyServer {
         let ctx = CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
@@ -280,6 +291,10 @@ impl Server for TcpTProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
@@ -8,6 +8,7 @@ use std::time::Duration;
 
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
@@ -21,7 +22,9 @@ use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
 use crate::serve::tcp_stream::{TcpStreamServerAliveTaskGuard, TcpStreamTaskCltWrapperStats};
-use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::serve::{
+    RegisteredTask, ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage,
+};
 
 pub(super) struct TProxyStreamTask {
     ctx: CommonTaskContext,
@@ -77,6 +80,13 @@ impl TProxyStreamTask {
 
     fn pre_start(&mut self) {
         self._alive_guard = Some(self.ctx.server_stats.add_task());
+        self.task_notes.registered_task =
+            Some(self.ctx.task_registry.register(RegisteredTask::new(
+                &self.task_notes,
+                &self.upstream,
+                self.ctx.escaper.name(),
+                self.task_stats.clone(),
+            )));
 
         if self.ctx.server_config.flush_task_log_on_created {
             if let Some(log_ctx) = self.get_log_context() {
@@ -243,4 +253,8 @@ impl StreamTransitTask for TProxyStreamTask {
     fn user(&self) -> Option<&User> {
         None
     }
+
+    fn cancel_token(&self) -> CancellationToken {
+        self.task_notes.cancel_token()
+    }
 }
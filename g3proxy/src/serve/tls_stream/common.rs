@@ -14,12 +14,13 @@ use g3_types::net::OpensslClientConfig;
 
 use crate::config::server::tls_stream::TlsStreamServerConfig;
 use crate::escape::ArcEscaper;
-use crate::serve::ServerQuitPolicy;
 use crate::serve::tcp_stream::TcpStreamServerStats;
+use crate::serve::{ServerQuitPolicy, ServerTaskRegistry};
 
 pub(super) struct CommonTaskContext {
     pub(super) server_config: Arc<TlsStreamServerConfig>,
     pub(super) server_stats: Arc<TcpStreamServerStats>,
+    pub(super) task_registry: Arc<ServerTaskRegistry>,
     pub(super) server_quit_policy: Arc<ServerQuitPolicy>,
     pub(super) idle_wheel: Arc<IdleWheel>,
     pub(super) escaper: ArcEscaper,
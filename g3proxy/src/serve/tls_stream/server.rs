@@ -23,11 +23,9 @@ use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerExt, ServerReloa
 use g3_io_ext::IdleWheel;
 use g3_openssl::SslStream;
 use g3_types::acl::{AclAction, AclNetworkRule};
-use g3_types::collection::{SelectiveVec, SelectiveVecBuilder};
 use g3_types::metrics::NodeName;
 use g3_types::net::{
     OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConnectionExt,
-    WeightedUpstreamAddr,
 };
 
 use super::common::CommonTaskContext;
@@ -38,15 +36,16 @@ use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::tcp_stream::TcpStreamServerStats;
 use crate::serve::{
-    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ArcServer, ArcServerInternal, ArcServerStats, DynamicUpstream, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, ServerStats, ServerTaskRegistry, WrapArcServer,
 };
 
 pub(crate) struct TlsStreamServer {
     config: Arc<TlsStreamServerConfig>,
     server_stats: Arc<TcpStreamServerStats>,
+    task_registry: Arc<ServerTaskRegistry>,
     listen_stats: Arc<ListenStats>,
-    upstream: SelectiveVec<WeightedUpstreamAddr>,
+    upstream: DynamicUpstream,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     tls_acceptor: TlsAcceptor,
     tls_accept_timeout: Duration,
@@ -66,19 +65,20 @@ impl TlsStreamServer {
     fn new(
         config: Arc<TlsStreamServerConfig>,
         server_stats: Arc<TcpStreamServerStats>,
+        task_registry: Arc<ServerTaskRegistry>,
         listen_stats: Arc<ListenStats>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
         version: usize,
+        old_upstream: Option<&DynamicUpstream>,
     ) -> anyhow::Result<Self> {
         let reload_sender = crate::serve::new_reload_notify_channel();
 
-        let mut nodes_builder = SelectiveVecBuilder::new();
-        for node in &config.upstream {
-            nodes_builder.insert(node.clone());
-        }
-        let upstream = nodes_builder
-            .build()
-            .ok_or_else(|| anyhow!("no upstream addr set"))?;
+        let upstream = DynamicUpstream::new(
+            format!("server {}", config.name()),
+            config.as_ref(),
+            server_stats.upstream_discover.clone(),
+            old_upstream,
+        )?;
 
         let tls_server_config = config
             .server_tls_config
@@ -111,6 +111,7 @@ impl TlsStreamServer {
         let server = TlsStreamServer {
             config,
             server_stats,
+            task_registry,
             listen_stats,
             upstream,
             tls_rolling_ticketer,
@@ -135,6 +136,7 @@ impl TlsStreamServer {
     ) -> anyhow::Result<ArcServerInternal> {
         let config = Arc::new(config);
         let server_stats = Arc::new(TcpStreamServerStats::new(config.name()));
+        let task_registry = Arc::new(ServerTaskRegistry::default());
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
@@ -146,8 +148,15 @@ impl TlsStreamServer {
             None
         };
 
-        let server =
-            TlsStreamServer::new(config, server_stats, listen_stats, tls_rolling_ticketer, 1)?;
+        let server = TlsStreamServer::new(
+            config,
+            server_stats,
+            task_registry,
+            listen_stats,
+            tls_rolling_ticketer,
+            1,
+            None,
+        )?;
         Ok(Arc::new(server))
     }
 
@@ -155,6 +164,7 @@ impl TlsStreamServer {
         if let AnyServerConfig::TlsStream(config) = config {
             let config = Arc::new(config);
             let server_stats = Arc::clone(&self.server_stats);
+            let task_registry = Arc::clone(&self.task_registry);
             let listen_stats = Arc::clone(&self.listen_stats);
 
             let tls_rolling_ticketer = if self.config.tls_ticketer.eq(&config.tls_ticketer) {
@@ -171,9 +181,11 @@ impl TlsStreamServer {
             let server = TlsStreamServer::new(
                 config,
                 server_stats,
+                task_registry,
                 listen_stats,
                 tls_rolling_ticketer,
                 self.reload_version + 1,
+                Some(&self.upstream),
             )?;
             Ok(server)
         } else {
@@ -207,12 +219,12 @@ impl TlsStreamServer {
     }
 
     async fn run_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo) {
-        let upstream =
-            self.select_consistent(&self.upstream, self.config.upstream_pick_policy, &cc_info);
+        let upstream = self.upstream.pick(&cc_info);
 
         let ctx = CommonTaskContext {
             server_config: self.config.clone(),
             server_stats: self.server_stats.clone(),
+            task_registry: self.task_registry.clone(),
             server_quit_policy: self.quit_policy.clone(),
             idle_wheel: self.idle_wheel.clone(),
             escaper: self.escaper.load().as_ref().clone(),
@@ -221,7 +233,7 @@ impl TlsStreamServer {
             task_logger: self.task_logger.clone(),
         };
 
-        TlsStreamTask::new(ctx, upstream.inner(), self.audit_context())
+        TlsStreamTask::new(ctx, upstream, self.audit_context())
             .into_running(stream)
             .await;
     }
@@ -256,6 +268,14 @@ impl ServerInternal for TlsStreamServer {
         Ok(())
     }
 
+    fn _depend_on_discover(&self, name: &NodeName) -> bool {
+        self.upstream.depend_on_discover(name)
+    }
+
+    fn _update_discover_in_place(&self) {
+        self.upstream.update_discover();
+    }
+
     fn _reload_with_old_notifier(
         &self,
         config: AnyServerConfig,
@@ -383,6 +403,10 @@ impl Server for TlsStreamServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn get_task_registry(&self) -> Option<&Arc<ServerTaskRegistry>> {
+        Some(&self.task_registry)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
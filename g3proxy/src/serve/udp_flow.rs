@@ -0,0 +1,339 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::io::IoSliceMut;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+/// `(client address, server address)`
+pub(crate) type UdpFlowKey = (SocketAddr, SocketAddr);
+
+struct UdpFlow {
+    task_id: Uuid,
+    sender: mpsc::Sender<Bytes>,
+}
+
+/// The limit on the number of alive flows, 0 means no limit.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct UdpFlowLimit {
+    pub(crate) max_flows: usize,
+    pub(crate) max_flows_per_client: usize,
+}
+
+#[derive(Default)]
+struct UdpFlowTableInner {
+    flows: HashMap<UdpFlowKey, UdpFlow>,
+    client_flows: HashMap<IpAddr, usize>,
+}
+
+impl UdpFlowTableInner {
+    fn remove(&mut self, key: &UdpFlowKey) {
+        if self.flows.remove(key).is_none() {
+            return;
+        }
+        let ip = key.0.ip();
+        if let Some(count) = self.client_flows.get_mut(&ip) {
+            *count -= 1;
+            if *count == 0 {
+                self.client_flows.remove(&ip);
+            }
+        }
+    }
+
+    fn limit_reached(&self, key: &UdpFlowKey, limit: &UdpFlowLimit) -> bool {
+        if self.flows.contains_key(key) {
+            // the closed flow will be replaced
+            return false;
+        }
+        if limit.max_flows > 0 && self.flows.len() >= limit.max_flows {
+            return true;
+        }
+        if limit.max_flows_per_client > 0 {
+            let count = self.client_flows.get(&key.0.ip()).copied().unwrap_or(0);
+            if count >= limit.max_flows_per_client {
+                return true;
+            }
+        }
+        false
+    }
+}
+
+/// Dispatch datagrams received by the shared listen socket to per flow tasks.
+#[derive(Default)]
+pub(crate) struct UdpFlowTable {
+    inner: Mutex<UdpFlowTableInner>,
+}
+
+impl UdpFlowTable {
+    /// Forward the packet to the existing flow.
+    ///
+    /// Return false if there is no alive flow for this key, the packet should be used to create
+    /// a new flow then.
+    pub(crate) fn forward(&self, key: &UdpFlowKey, packet: &[u8]) -> bool {
+        let mut table = self.inner.lock().unwrap();
+        let Some(flow) = table.flows.get(key) else {
+            return false;
+        };
+        match flow.sender.try_send(Bytes::copy_from_slice(packet)) {
+            Ok(_) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => true, // drop it as the flow is busy
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                table.remove(key);
+                false
+            }
+        }
+    }
+
+    /// Add a new flow with the first packet queued.
+    ///
+    /// Return None if the flow limit has been reached, the packet should be dropped then.
+    pub(crate) fn insert(
+        &self,
+        key: UdpFlowKey,
+        task_id: Uuid,
+        packet: &[u8],
+        queue_size: usize,
+        limit: &UdpFlowLimit,
+    ) -> Option<UdpFlowQueue> {
+        let mut table = self.inner.lock().unwrap();
+        if table.limit_reached(&key, limit) {
+            return None;
+        }
+
+        let (sender, receiver) = mpsc::channel(queue_size);
+        let _ = sender.try_send(Bytes::copy_from_slice(packet));
+
+        if table
+            .flows
+            .insert(key, UdpFlow { task_id, sender })
+            .is_none()
+        {
+            *table.client_flows.entry(key.0.ip()).or_insert(0) += 1;
+        }
+        Some(UdpFlowQueue {
+            receiver: Some(receiver),
+        })
+    }
+
+    pub(crate) fn remove(&self, key: &UdpFlowKey, task_id: &Uuid) {
+        let mut table = self.inner.lock().unwrap();
+        if table
+            .flows
+            .get(key)
+            .map(|f| f.task_id.eq(task_id))
+            .unwrap_or(false)
+        {
+            table.remove(key);
+        }
+    }
+}
+
+pub(crate) struct UdpFlowQueue {
+    receiver: Option<mpsc::Receiver<Bytes>>,
+}
+
+impl UdpFlowQueue {
+    /// Return `Ready(None)` if the flow has been removed from the table.
+    pub(crate) fn poll_packet(&mut self, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        let Some(receiver) = self.receiver.as_mut() else {
+            return Poll::Ready(None);
+        };
+        match receiver.poll_recv(cx) {
+            Poll::Ready(Some(packet)) => Poll::Ready(Some(packet)),
+            Poll::Ready(None) => {
+                self.receiver = None;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    pub(crate) fn try_packet(&mut self) -> Option<Bytes> {
+        self.receiver.as_mut()?.try_recv().ok()
+    }
+}
+
+pub(crate) fn copy_packet_to_iov(packet: &[u8], iov: &mut [IoSliceMut<'_>]) -> usize {
+    let mut copied = 0;
+    for buf in iov {
+        let left = &packet[copied..];
+        if left.is_empty() {
+            break;
+        }
+        let n = left.len().min(buf.len());
+        buf[..n].copy_from_slice(&left[..n]);
+        copied += n;
+    }
+    copied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future::poll_fn;
+    use std::str::FromStr;
+
+    const NO_LIMIT: UdpFlowLimit = UdpFlowLimit {
+        max_flows: 0,
+        max_flows_per_client: 0,
+    };
+
+    fn flow_key() -> UdpFlowKey {
+        (
+            SocketAddr::from_str("192.168.1.2:40000").unwrap(),
+            SocketAddr::from_str("192.168.1.1:53").unwrap(),
+        )
+    }
+
+    #[test]
+    fn copy_to_iov() {
+        let packet = b"0123456789";
+
+        let mut buf1 = [0u8; 4];
+        let mut buf2 = [0u8; 4];
+        let mut buf3 = [0u8; 4];
+        let mut iov = [
+            IoSliceMut::new(&mut buf1),
+            IoSliceMut::new(&mut buf2),
+            IoSliceMut::new(&mut buf3),
+        ];
+        assert_eq!(copy_packet_to_iov(packet, &mut iov), 10);
+        assert_eq!(&buf1, b"0123");
+        assert_eq!(&buf2, b"4567");
+        assert_eq!(&buf3[..2], b"89");
+
+        let mut buf1 = [0u8; 4];
+        let mut iov = [IoSliceMut::new(&mut buf1)];
+        assert_eq!(copy_packet_to_iov(packet, &mut iov), 4);
+        assert_eq!(&buf1, b"0123");
+
+        let mut buf1 = [0u8; 4];
+        let mut iov = [IoSliceMut::new(&mut buf1)];
+        assert_eq!(copy_packet_to_iov(b"", &mut iov), 0);
+    }
+
+    #[tokio::test]
+    async fn forward() {
+        let table = UdpFlowTable::default();
+        let key = flow_key();
+        assert!(!table.forward(&key, b"first"));
+
+        let task_id = Uuid::new_v4();
+        let mut queue = table.insert(key, task_id, b"first", 2, &NO_LIMIT).unwrap();
+        assert!(table.forward(&key, b"second"));
+        // the queue is full, the packet will be dropped
+        assert!(table.forward(&key, b"third"));
+
+        let p = poll_fn(|cx| queue.poll_packet(cx)).await.unwrap();
+        assert_eq!(p.as_ref(), b"first");
+        assert_eq!(queue.try_packet().unwrap().as_ref(), b"second");
+        assert!(queue.try_packet().is_none());
+
+        let other_key = (key.0, SocketAddr::from_str("192.168.1.1:54").unwrap());
+        assert!(!table.forward(&other_key, b"other"));
+    }
+
+    #[tokio::test]
+    async fn remove() {
+        let table = UdpFlowTable::default();
+        let key = flow_key();
+
+        let task_id = Uuid::new_v4();
+        let mut queue = table.insert(key, task_id, b"first", 4, &NO_LIMIT).unwrap();
+
+        // a different task won't remove the flow
+        table.remove(&key, &Uuid::new_v4());
+        assert!(table.forward(&key, b"second"));
+
+        table.remove(&key, &task_id);
+        assert!(!table.forward(&key, b"third"));
+
+        assert_eq!(queue.try_packet().unwrap().as_ref(), b"first");
+        assert_eq!(queue.try_packet().unwrap().as_ref(), b"second");
+        // the sender has been dropped along with the flow
+        assert!(poll_fn(|cx| queue.poll_packet(cx)).await.is_none());
+        assert!(poll_fn(|cx| queue.poll_packet(cx)).await.is_none());
+    }
+
+    #[test]
+    fn replace_closed() {
+        let table = UdpFlowTable::default();
+        let key = flow_key();
+
+        let queue = table
+            .insert(key, Uuid::new_v4(), b"first", 4, &NO_LIMIT)
+            .unwrap();
+        drop(queue);
+        // the closed flow will be removed
+        assert!(!table.forward(&key, b"second"));
+        assert!(!table.forward(&key, b"third"));
+
+        let task_id = Uuid::new_v4();
+        let mut queue = table.insert(key, task_id, b"first", 4, &NO_LIMIT).unwrap();
+        assert!(table.forward(&key, b"second"));
+        assert_eq!(queue.try_packet().unwrap().as_ref(), b"first");
+        assert_eq!(queue.try_packet().unwrap().as_ref(), b"second");
+    }
+
+    #[test]
+    fn flow_limit() {
+        let table = UdpFlowTable::default();
+        let limit = UdpFlowLimit {
+            max_flows: 3,
+            max_flows_per_client: 2,
+        };
+        let server = SocketAddr::from_str("192.168.1.1:53").unwrap();
+        let client1 = |port| (SocketAddr::new([192, 168, 1, 2].into(), port), server);
+        let client2 = |port| (SocketAddr::new([192, 168, 1, 3].into(), port), server);
+
+        let task1 = Uuid::new_v4();
+        let _q1 = table.insert(client1(1), task1, b"1", 4, &limit).unwrap();
+        let q2 = table.insert(client1(2), Uuid::new_v4(), b"2", 4, &limit);
+        assert!(q2.is_some());
+        // reach the per client limit
+        assert!(
+            table
+                .insert(client1(3), Uuid::new_v4(), b"3", 4, &limit)
+                .is_none()
+        );
+
+        let q3 = table.insert(client2(1), Uuid::new_v4(), b"1", 4, &limit);
+        assert!(q3.is_some());
+        // reach the total limit
+        assert!(
+            table
+                .insert(client2(2), Uuid::new_v4(), b"2", 4, &limit)
+                .is_none()
+        );
+
+        // the slot will be released after the flow is removed
+        table.remove(&client1(1), &task1);
+        assert!(
+            table
+                .insert(client2(2), Uuid::new_v4(), b"2", 4, &limit)
+                .is_some()
+        );
+        assert!(
+            table
+                .insert(client1(3), Uuid::new_v4(), b"3", 4, &limit)
+                .is_none()
+        );
+
+        // a closed flow can always be replaced
+        let closed = table.insert(client1(3), Uuid::new_v4(), b"3", 4, &NO_LIMIT);
+        drop(closed);
+        assert!(
+            table
+                .insert(client1(3), Uuid::new_v4(), b"3", 4, &limit)
+                .is_some()
+        );
+    }
+}
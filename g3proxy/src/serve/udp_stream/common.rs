@@ -0,0 +1,46 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use slog::Logger;
+use tokio::net::UdpSocket;
+use tokio::time::Instant;
+
+use g3_io_ext::{IdleWheel, OptionalInterval};
+
+use super::stats::UdpStreamServerStats;
+use crate::config::server::udp_stream::UdpStreamServerConfig;
+use crate::escape::ArcEscaper;
+use crate::serve::{ServerQuitPolicy, UdpFlowTable};
+
+pub(super) struct CommonTaskContext {
+    pub(super) server_config: Arc<UdpStreamServerConfig>,
+    pub(super) server_stats: Arc<UdpStreamServerStats>,
+    pub(super) server_quit_policy: Arc<ServerQuitPolicy>,
+    pub(super) flow_table: Arc<UdpFlowTable>,
+    pub(super) idle_wheel: Arc<IdleWheel>,
+    pub(super) escaper: ArcEscaper,
+    pub(super) listen_socket: Arc<UdpSocket>,
+    pub(super) task_logger: Option<Logger>,
+}
+
+impl CommonTaskContext {
+    fn log_flush_interval(&self) -> Option<Duration> {
+        self.task_logger.as_ref()?;
+        self.server_config.task_log_flush_interval
+    }
+
+    pub(super) fn get_log_interval(&self) -> OptionalInterval {
+        self.log_flush_interval()
+            .map(|log_interval| {
+                let log_interval =
+                    tokio::time::interval_at(Instant::now() + log_interval, log_interval);
+                OptionalInterval::with(log_interval)
+            })
+            .unwrap_or_default()
+    }
+}
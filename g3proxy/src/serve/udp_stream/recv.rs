@@ -0,0 +1,184 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use std::io::IoSliceMut;
+use std::net::SocketAddr;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+
+use g3_io_ext::{AsyncUdpRecv, UdpCopyClientError, UdpCopyClientRecv};
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::{UdpCopyPacket, UdpCopyPacketMeta};
+use g3_io_sys::udp::RecvMsgHdr;
+
+use crate::serve::{UdpFlowQueue, copy_packet_to_iov};
+
+/// Receive packets of a single udp flow, all of which are received by the shared listen socket
+/// and then dispatched to us through the flow queue.
+pub(super) struct UdpStreamFlowRecv {
+    queue: UdpFlowQueue,
+    client_addr: SocketAddr,
+}
+
+impl UdpStreamFlowRecv {
+    pub(super) fn new(queue: UdpFlowQueue, client_addr: SocketAddr) -> Self {
+        UdpStreamFlowRecv { queue, client_addr }
+    }
+
+    fn poll_queue(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<Bytes>> {
+        match ready!(self.queue.poll_packet(cx)) {
+            Some(packet) => Poll::Ready(Ok(packet)),
+            None => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "udp flow has been removed",
+            ))),
+        }
+    }
+}
+
+impl AsyncUdpRecv for UdpStreamFlowRecv {
+    fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        let packet = ready!(self.poll_queue(cx))?;
+        let n = packet.len().min(buf.len());
+        buf[..n].copy_from_slice(&packet[..n]);
+        Poll::Ready(Ok((n, self.client_addr)))
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let packet = ready!(self.poll_queue(cx))?;
+        let n = packet.len().min(buf.len());
+        buf[..n].copy_from_slice(&packet[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_recvmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        hdr: &mut RecvMsgHdr<'_, C>,
+    ) -> Poll<io::Result<()>> {
+        let packet = ready!(self.poll_queue(cx))?;
+        hdr.n_recv = copy_packet_to_iov(&packet, &mut hdr.iov);
+        Poll::Ready(Ok(()))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_batch_recvmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        hdr_v: &mut [RecvMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        if hdr_v.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let packet = ready!(self.poll_queue(cx))?;
+        hdr_v[0].n_recv = copy_packet_to_iov(&packet, &mut hdr_v[0].iov);
+        let mut count = 1;
+        for hdr in &mut hdr_v[1..] {
+            let Some(packet) = self.queue.try_packet() else {
+                break;
+            };
+            hdr.n_recv = copy_packet_to_iov(&packet, &mut hdr.iov);
+            count += 1;
+        }
+        Poll::Ready(Ok(count))
+    }
+}
+
+pub(super) struct UdpStreamClientRecv<T> {
+    inner: T,
+}
+
+impl<T> UdpStreamClientRecv<T>
+where
+    T: AsyncUdpRecv,
+{
+    pub(super) fn new(inner: T) -> Self {
+        UdpStreamClientRecv { inner }
+    }
+}
+
+impl<T> UdpCopyClientRecv for UdpStreamClientRecv<T>
+where
+    T: AsyncUdpRecv + Send,
+{
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyClientError>> {
+        let nr = ready!(self.inner.poll_recv(cx, buf)).map_err(UdpCopyClientError::RecvFailed)?;
+        Poll::Ready(Ok((0, nr)))
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut hdr_v: Vec<RecvMsgHdr<1>> = packets
+            .iter_mut()
+            .map(|p| RecvMsgHdr::new([IoSliceMut::new(p.buf_mut())]))
+            .collect();
+
+        let count = ready!(self.inner.poll_batch_recvmsg(cx, &mut hdr_v))
+            .map_err(UdpCopyClientError::RecvFailed)?;
+
+        let r: Vec<UdpCopyPacketMeta> = hdr_v
+            .iter()
+            .take(count)
+            .map(|h| UdpCopyPacketMeta::new(&h.iov[0], 0, h.n_recv))
+            .collect();
+        drop(hdr_v);
+        for (m, p) in r.into_iter().zip(packets.iter_mut()) {
+            m.set_packet(p);
+        }
+
+        Poll::Ready(Ok(count))
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, IoSlice};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use tokio::net::UdpSocket;
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+    target_os = "solaris",
+))]
+use g3_io_ext::UdpCopyPacket;
+use g3_io_ext::{AsyncUdpSend, UdpCopyClientError, UdpCopyClientSend, UdpSocketExt};
+use g3_io_sys::udp::SendMsgHdr;
+
+/// Send reply packets through the shared listen socket.
+///
+/// The socket is not connected, so the target address should always be set.
+pub(super) struct UdpStreamListenSend(Arc<UdpSocket>);
+
+impl UdpStreamListenSend {
+    pub(super) fn new(socket: Arc<UdpSocket>) -> Self {
+        UdpStreamListenSend(socket)
+    }
+}
+
+impl AsyncUdpSend for UdpStreamListenSend {
+    fn poll_send_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        target: SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_send_to(cx, buf, target)
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.0.poll_send(cx, buf)
+    }
+
+    fn poll_sendmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        hdr: &SendMsgHdr<'_, C>,
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_sendmsg(cx, hdr)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris",
+    ))]
+    fn poll_batch_sendmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        msgs: &mut [SendMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_batch_sendmsg(cx, msgs)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn poll_batch_sendmsg_x<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        msgs: &mut [SendMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_batch_sendmsg_x(cx, msgs)
+    }
+}
+
+/// Send reply packets to the client of the flow.
+///
+/// The source ip is pinned to the one the client sent to, as the listen socket may be bound to
+/// a wildcard address.
+pub(super) struct UdpStreamClientSend<T> {
+    inner: T,
+    target: SocketAddr,
+    #[cfg_attr(not(any(target_os = "linux", target_os = "android")), allow(unused))]
+    src_ip: IpAddr,
+}
+
+impl<T> UdpStreamClientSend<T>
+where
+    T: AsyncUdpSend,
+{
+    pub(super) fn new(inner: T, target: SocketAddr, src_ip: IpAddr) -> Self {
+        UdpStreamClientSend {
+            inner,
+            target,
+            src_ip,
+        }
+    }
+
+    fn build_msg<'a>(&self, buf: &'a [u8]) -> SendMsgHdr<'a, 1> {
+        #[cfg_attr(
+            not(any(target_os = "linux", target_os = "android")),
+            allow(unused_mut)
+        )]
+        let mut hdr = SendMsgHdr::new([IoSlice::new(buf)], Some(self.target));
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if !self.src_ip.is_unspecified() {
+            hdr.set_src_ip(self.src_ip);
+        }
+        hdr
+    }
+
+    fn check_send_count(nw: usize) -> Poll<Result<usize, UdpCopyClientError>> {
+        if nw == 0 {
+            Poll::Ready(Err(UdpCopyClientError::SendFailed(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero byte into sender",
+            ))))
+        } else {
+            Poll::Ready(Ok(nw))
+        }
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+        target_os = "solaris",
+    ))]
+    fn build_msgs<'a>(&self, packets: &'a [UdpCopyPacket]) -> Vec<SendMsgHdr<'a, 1>> {
+        packets
+            .iter()
+            .map(|p| self.build_msg(p.payload()))
+            .collect()
+    }
+}
+
+impl<T> UdpCopyClientSend for UdpStreamClientSend<T>
+where
+    T: AsyncUdpSend + Send,
+{
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let hdr = self.build_msg(buf);
+        let nw =
+            ready!(self.inner.poll_sendmsg(cx, &hdr)).map_err(UdpCopyClientError::SendFailed)?;
+        Self::check_send_count(nw)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "solaris",
+    ))]
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut msgs = self.build_msgs(packets);
+        let count = ready!(self.inner.poll_batch_sendmsg(cx, &mut msgs))
+            .map_err(UdpCopyClientError::SendFailed)?;
+        Self::check_send_count(count)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut msgs = self.build_msgs(packets);
+        let count = ready!(self.inner.poll_batch_sendmsg_x(cx, &mut msgs))
+            .map_err(UdpCopyClientError::SendFailed)?;
+        Self::check_send_count(count)
+    }
+}
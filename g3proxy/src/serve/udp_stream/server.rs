@@ -0,0 +1,390 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use arc_swap::{ArcSwap, ArcSwapOption};
+use async_trait::async_trait;
+use log::warn;
+#[cfg(feature = "quic")]
+use quinn::Connection;
+use slog::Logger;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::broadcast;
+use tokio_rustls::server::TlsStream;
+
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ReceiveUdpRuntime};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerExt, ServerReloadCommand};
+use g3_io_ext::IdleWheel;
+use g3_openssl::SslStream;
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::collection::{SelectiveVec, SelectiveVecBuilder};
+use g3_types::metrics::NodeName;
+use g3_types::net::WeightedUpstreamAddr;
+
+use super::common::CommonTaskContext;
+use super::stats::UdpStreamServerStats;
+use super::task::UdpStreamTask;
+use crate::audit::AuditHandle;
+use crate::config::server::udp_stream::UdpStreamServerConfig;
+use crate::config::server::{AnyServerConfig, ServerConfig};
+use crate::escape::ArcEscaper;
+use crate::serve::{
+    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
+    ServerRegistry, ServerStats, ServerTaskNotes, UdpFlowKey, UdpFlowTable, WrapArcServer,
+};
+
+pub(crate) struct UdpStreamServer {
+    config: Arc<UdpStreamServerConfig>,
+    server_stats: Arc<UdpStreamServerStats>,
+    listen_stats: Arc<ListenStats>,
+    flow_table: Arc<UdpFlowTable>,
+    upstream: SelectiveVec<WeightedUpstreamAddr>,
+    ingress_net_filter: Option<AclNetworkRule>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+    task_logger: Option<Logger>,
+
+    escaper: ArcSwap<ArcEscaper>,
+    audit_handle: ArcSwapOption<AuditHandle>,
+    quit_policy: Arc<ServerQuitPolicy>,
+    idle_wheel: Arc<IdleWheel>,
+    reload_version: usize,
+}
+
+impl UdpStreamServer {
+    fn new(
+        config: Arc<UdpStreamServerConfig>,
+        server_stats: Arc<UdpStreamServerStats>,
+        listen_stats: Arc<ListenStats>,
+        flow_table: Arc<UdpFlowTable>,
+        version: usize,
+    ) -> anyhow::Result<Self> {
+        let reload_sender = crate::serve::new_reload_notify_channel();
+
+        let mut nodes_builder = SelectiveVecBuilder::new();
+        for node in &config.upstream {
+            nodes_builder.insert(node.clone());
+        }
+        let upstream = nodes_builder
+            .build()
+            .ok_or_else(|| anyhow!("no upstream addr set"))?;
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+
+        let task_logger = config.get_task_logger();
+        let idle_wheel = IdleWheel::spawn(config.task_idle_check_duration);
+
+        server_stats.set_extra_tags(config.extra_metrics_tags.clone());
+
+        let escaper = Arc::new(crate::escape::get_or_insert_default(config.escaper()));
+        let audit_handle = get_audit_handle(&config)?;
+
+        let server = UdpStreamServer {
+            config,
+            server_stats,
+            listen_stats,
+            flow_table,
+            upstream,
+            ingress_net_filter,
+            reload_sender,
+            task_logger,
+            escaper: ArcSwap::new(escaper),
+            audit_handle: ArcSwapOption::new(audit_handle),
+            quit_policy: Arc::new(ServerQuitPolicy::default()),
+            idle_wheel,
+            reload_version: version,
+        };
+
+        Ok(server)
+    }
+
+    pub(crate) fn prepare_initial(
+        config: UdpStreamServerConfig,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let config = Arc::new(config);
+        let server_stats = Arc::new(UdpStreamServerStats::new(config.name()));
+        let listen_stats = Arc::new(ListenStats::new(config.name()));
+        let flow_table = Arc::new(UdpFlowTable::default());
+
+        let server = UdpStreamServer::new(config, server_stats, listen_stats, flow_table, 1)?;
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyServerConfig) -> anyhow::Result<Self> {
+        if let AnyServerConfig::UdpStream(config) = config {
+            let config = Arc::new(config);
+            let server_stats = Arc::clone(&self.server_stats);
+            let listen_stats = Arc::clone(&self.listen_stats);
+            let flow_table = Arc::clone(&self.flow_table);
+
+            let server = UdpStreamServer::new(
+                config,
+                server_stats,
+                listen_stats,
+                flow_table,
+                self.reload_version + 1,
+            )?;
+            Ok(server)
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.r#type(),
+                config.r#type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    self.listen_stats.add_dropped();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn spawn_flow(
+        &self,
+        socket: &Arc<UdpSocket>,
+        packet: &[u8],
+        flow_key: UdpFlowKey,
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        let mut cc_info = ClientConnectionInfo::new(client_addr, server_addr);
+        cc_info.set_worker_id(worker_id);
+        let upstream =
+            self.select_consistent(&self.upstream, self.config.upstream_pick_policy, &cc_info);
+        let task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+
+        let Some(queue) = self.flow_table.insert(
+            flow_key,
+            task_notes.id,
+            packet,
+            self.config.flow_queue_size,
+            &self.config.flow_limit,
+        ) else {
+            // drop the packet as there are too many flows
+            self.listen_stats.add_dropped();
+            return;
+        };
+
+        let ctx = CommonTaskContext {
+            server_config: self.config.clone(),
+            server_stats: self.server_stats.clone(),
+            server_quit_policy: self.quit_policy.clone(),
+            flow_table: self.flow_table.clone(),
+            idle_wheel: self.idle_wheel.clone(),
+            escaper: self.escaper.load().as_ref().clone(),
+            listen_socket: socket.clone(),
+            task_logger: self.task_logger.clone(),
+        };
+        let task = UdpStreamTask::new(
+            ctx,
+            flow_key,
+            task_notes,
+            upstream.inner(),
+            self.audit_handle.load_full(),
+        );
+        tokio::spawn(task.into_running(queue));
+    }
+}
+
+/// Only flow logging is supported for udp flows, warn if the auditor wants more than that
+fn get_audit_handle(config: &UdpStreamServerConfig) -> anyhow::Result<Option<Arc<AuditHandle>>> {
+    let audit_handle = config.get_audit_handle()?;
+    if let Some(handle) = &audit_handle {
+        if handle.icap_reqmod_client().is_some()
+            || handle.icap_respmod_client().is_some()
+            || handle.stream_detour_client().is_some()
+        {
+            warn!(
+                "server {}: only flow logging is supported for udp flows, \
+                 the icap and stream detour services of auditor {} will be ignored",
+                config.name(),
+                config.auditor()
+            );
+        }
+    }
+    Ok(audit_handle)
+}
+
+impl ServerInternal for UdpStreamServer {
+    fn _clone_config(&self) -> AnyServerConfig {
+        AnyServerConfig::UdpStream(self.config.as_ref().clone())
+    }
+
+    fn _depend_on_server(&self, _name: &NodeName) -> bool {
+        false
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_next_servers_in_place(&self) {}
+
+    fn _update_escaper_in_place(&self) {
+        let escaper = crate::escape::get_or_insert_default(self.config.escaper());
+        self.escaper.store(Arc::new(escaper));
+    }
+
+    fn _update_user_group_in_place(&self) {}
+
+    fn _update_audit_handle_in_place(&self) -> anyhow::Result<()> {
+        let audit_handle = get_audit_handle(&self.config)?;
+        self.audit_handle.store(audit_handle);
+        Ok(())
+    }
+
+    fn _reload_with_old_notifier(
+        &self,
+        config: AnyServerConfig,
+        _registry: &mut ServerRegistry,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(
+        &self,
+        config: AnyServerConfig,
+        _registry: &mut ServerRegistry,
+    ) -> anyhow::Result<ArcServerInternal> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, server: ArcServer) -> anyhow::Result<()> {
+        let runtime = ReceiveUdpRuntime::new(WrapArcServer(server), self.config.listen.clone());
+        runtime
+            .run_all_instances(self.config.listen_in_worker, &self.reload_sender)
+            .map(|_| self.server_stats.set_online())
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+        self.server_stats.set_offline();
+    }
+}
+
+impl BaseServer for UdpStreamServer {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        self.config.r#type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+impl ServerExt for UdpStreamServer {}
+
+#[async_trait]
+impl AcceptTcpServer for UdpStreamServer {
+    async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {}
+}
+
+#[async_trait]
+impl AcceptQuicServer for UdpStreamServer {
+    #[cfg(feature = "quic")]
+    async fn run_quic_task(&self, _connection: Connection, _cc_info: ClientConnectionInfo) {}
+}
+
+#[async_trait]
+impl Server for UdpStreamServer {
+    fn escaper(&self) -> &NodeName {
+        self.config.escaper()
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        self.config.auditor()
+    }
+
+    fn get_server_stats(&self) -> Option<ArcServerStats> {
+        Some(self.server_stats.clone())
+    }
+
+    fn get_listen_stats(&self) -> Arc<ListenStats> {
+        Arc::clone(&self.listen_stats)
+    }
+
+    fn alive_count(&self) -> i32 {
+        self.server_stats.get_alive_count()
+    }
+
+    #[inline]
+    fn quit_policy(&self) -> &Arc<ServerQuitPolicy> {
+        &self.quit_policy
+    }
+
+    async fn run_rustls_task(&self, _stream: TlsStream<TcpStream>, _cc_info: ClientConnectionInfo) {
+    }
+
+    async fn run_openssl_task(
+        &self,
+        _stream: SslStream<TcpStream>,
+        _cc_info: ClientConnectionInfo,
+    ) {
+    }
+
+    fn receive_udp_packet(
+        &self,
+        socket: &Arc<UdpSocket>,
+        packet: &[u8],
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        worker_id: Option<usize>,
+    ) {
+        let flow_key = (client_addr, server_addr);
+        if self.flow_table.forward(&flow_key, packet) {
+            return;
+        }
+
+        self.server_stats.add_conn();
+        // the listen socket may be a dual stack one, but we should always reply with the raw
+        // addresses recorded in the flow key
+        let client_addr = SocketAddr::new(client_addr.ip().to_canonical(), client_addr.port());
+        let server_addr = SocketAddr::new(server_addr.ip().to_canonical(), server_addr.port());
+        if self.drop_early(client_addr) {
+            return;
+        }
+
+        self.spawn_flow(
+            socket,
+            packet,
+            flow_key,
+            client_addr,
+            server_addr,
+            worker_id,
+        );
+    }
+}
@@ -0,0 +1,12 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod server;
+mod task;
+mod wrapper;
+
+pub(crate) use server::{UdpStreamServerAliveTaskGuard, UdpStreamServerStats};
+pub(crate) use task::UdpStreamTaskStats;
+pub(crate) use wrapper::UdpStreamTaskCltWrapperStats;
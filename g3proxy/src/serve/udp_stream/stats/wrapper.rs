@@ -0,0 +1,50 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use g3_io_ext::{LimitedRecvStats, LimitedSendStats};
+
+use super::{UdpStreamServerStats, UdpStreamTaskStats};
+
+pub(crate) struct UdpStreamTaskCltWrapperStats {
+    server: Arc<UdpStreamServerStats>,
+    task: Arc<UdpStreamTaskStats>,
+}
+
+impl UdpStreamTaskCltWrapperStats {
+    pub(crate) fn new(server: &Arc<UdpStreamServerStats>, task: &Arc<UdpStreamTaskStats>) -> Self {
+        UdpStreamTaskCltWrapperStats {
+            server: Arc::clone(server),
+            task: Arc::clone(task),
+        }
+    }
+}
+
+impl LimitedRecvStats for UdpStreamTaskCltWrapperStats {
+    fn add_recv_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.udp.add_in_bytes(size);
+        self.task.clt.recv.add_bytes(size);
+    }
+
+    fn add_recv_packets(&self, n: usize) {
+        self.server.udp.add_in_packets(n);
+        self.task.clt.recv.add_packets(n);
+    }
+}
+
+impl LimitedSendStats for UdpStreamTaskCltWrapperStats {
+    fn add_send_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.udp.add_out_bytes(size);
+        self.task.clt.send.add_bytes(size);
+    }
+
+    fn add_send_packets(&self, n: usize) {
+        self.server.udp.add_out_packets(n);
+        self.task.clt.send.add_packets(n);
+    }
+}
@@ -0,0 +1,289 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use slog::Logger;
+
+use g3_io_ext::{
+    LimitedUdpRecv, LimitedUdpSend, UdpCopyClientRecv, UdpCopyClientSend, UdpCopyClientToRemote,
+    UdpCopyError, UdpCopyRemoteRecv, UdpCopyRemoteSend, UdpCopyRemoteToClient,
+};
+use g3_types::net::UpstreamAddr;
+
+use super::common::CommonTaskContext;
+use super::recv::{UdpStreamClientRecv, UdpStreamFlowRecv};
+use super::send::{UdpStreamClientSend, UdpStreamListenSend};
+use super::stats::{
+    UdpStreamServerAliveTaskGuard, UdpStreamTaskCltWrapperStats, UdpStreamTaskStats,
+};
+use crate::audit::AuditHandle;
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::inspect::InspectSource;
+use crate::log::inspect::udp::UdpFlowInspectLog;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{
+    ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage, UdpFlowKey, UdpFlowQueue,
+};
+
+pub(super) struct UdpStreamTask {
+    ctx: CommonTaskContext,
+    flow_key: UdpFlowKey,
+    upstream: UpstreamAddr,
+    audit_handle: Option<Arc<AuditHandle>>,
+    udp_notes: UdpConnectTaskNotes,
+    task_notes: ServerTaskNotes,
+    task_stats: Arc<UdpStreamTaskStats>,
+    _alive_guard: Option<UdpStreamServerAliveTaskGuard>,
+}
+
+impl UdpStreamTask {
+    pub(super) fn new(
+        ctx: CommonTaskContext,
+        flow_key: UdpFlowKey,
+        task_notes: ServerTaskNotes,
+        upstream: &UpstreamAddr,
+        audit_handle: Option<Arc<AuditHandle>>,
+    ) -> Self {
+        UdpStreamTask {
+            ctx,
+            flow_key,
+            upstream: upstream.clone(),
+            audit_handle,
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_notes,
+            task_stats: Arc::new(UdpStreamTaskStats::default()),
+            _alive_guard: None,
+        }
+    }
+
+    #[inline]
+    fn client_addr(&self) -> SocketAddr {
+        self.task_notes.client_addr()
+    }
+
+    #[inline]
+    fn server_addr(&self) -> SocketAddr {
+        self.task_notes.server_addr()
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
+        self.ctx
+            .task_logger
+            .as_ref()
+            .map(|logger| TaskLogForUdpConnect {
+                logger,
+                task_notes: &self.task_notes,
+                tcp_server_addr: None,
+                tcp_client_addr: None,
+                udp_listen_addr: Some(self.server_addr()),
+                udp_client_addr: Some(self.client_addr()),
+                upstream: Some(&self.upstream),
+                udp_notes: &self.udp_notes,
+                client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+                client_rd_packets: self.task_stats.clt.recv.get_packets(),
+                client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+                client_wr_packets: self.task_stats.clt.send.get_packets(),
+                remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+                remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+                remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+                remote_wr_packets: self.task_stats.ups.send.get_packets(),
+            })
+    }
+
+    pub(super) async fn into_running(mut self, queue: UdpFlowQueue) {
+        self.pre_start();
+        let e = match self.run(queue).await {
+            Ok(_) => ServerTaskError::Finished,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+        self.ctx
+            .flow_table
+            .remove(&self.flow_key, &self.task_notes.id);
+    }
+
+    fn pre_start(&mut self) {
+        self._alive_guard = Some(self.ctx.server_stats.add_task());
+
+        if self.ctx.server_config.flush_task_log_on_created {
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log_created();
+            }
+        }
+    }
+
+    async fn run(&mut self, queue: UdpFlowQueue) -> ServerTaskResult<()> {
+        self.task_notes.stage = ServerTaskStage::Preparing;
+        let (clt_r, clt_w) = self.setup_clt(queue);
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        let (ups_r, ups_w, escape_logger) = self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await?;
+        self.task_notes.stage = ServerTaskStage::Connected;
+
+        if self.ctx.server_config.flush_task_log_on_connected {
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log_connected();
+            }
+        }
+
+        // only flow logging is supported, the datagrams won't be sent to the audit services
+        if let Some(audit_handle) = &self.audit_handle {
+            if audit_handle.do_task_audit() {
+                if let Some(logger) = audit_handle.inspect_logger() {
+                    UdpFlowInspectLog {
+                        task_id: &self.task_notes.id,
+                        upstream: &self.upstream,
+                    }
+                    .log(logger, InspectSource::UdpStream);
+                }
+            }
+        }
+
+        self.task_notes.mark_relaying();
+        self.run_relay(
+            Box::new(clt_r),
+            Box::new(clt_w),
+            ups_r,
+            ups_w,
+            escape_logger,
+        )
+        .await
+    }
+
+    fn setup_clt(
+        &self,
+        queue: UdpFlowQueue,
+    ) -> (
+        UdpStreamClientRecv<LimitedUdpRecv<UdpStreamFlowRecv>>,
+        UdpStreamClientSend<LimitedUdpSend<UdpStreamListenSend>>,
+    ) {
+        let limit_config = &self.ctx.server_config.udp_sock_speed_limit;
+        let wrapper_stats = Arc::new(UdpStreamTaskCltWrapperStats::new(
+            &self.ctx.server_stats,
+            &self.task_stats,
+        ));
+
+        let clt_r = LimitedUdpRecv::local_limited(
+            UdpStreamFlowRecv::new(queue, self.client_addr()),
+            limit_config.shift_millis,
+            limit_config.max_north_packets,
+            limit_config.max_north_bytes,
+            wrapper_stats.clone(),
+        );
+        let clt_w = LimitedUdpSend::local_limited(
+            UdpStreamListenSend::new(self.ctx.listen_socket.clone()),
+            limit_config.shift_millis,
+            limit_config.max_south_packets,
+            limit_config.max_south_bytes,
+            wrapper_stats,
+        );
+
+        (
+            UdpStreamClientRecv::new(clt_r),
+            UdpStreamClientSend::new(clt_w, self.flow_key.0, self.flow_key.1.ip()),
+        )
+    }
+
+    async fn run_relay(
+        &mut self,
+        mut clt_r: Box<dyn UdpCopyClientRecv + Unpin + Send>,
+        mut clt_w: Box<dyn UdpCopyClientSend + Unpin + Send>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+        escape_logger: Option<Logger>,
+    ) -> ServerTaskResult<()> {
+        let task_id = &self.task_notes.id;
+
+        let mut c_to_r =
+            UdpCopyClientToRemote::new(&mut *clt_r, &mut *ups_w, self.ctx.server_config.udp_relay);
+        let mut r_to_c =
+            UdpCopyRemoteToClient::new(&mut *clt_w, &mut *ups_r, self.ctx.server_config.udp_relay);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut c_to_r => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = escape_logger {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(&logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                r = &mut r_to_c => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = escape_logger {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(&logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if c_to_r.is_idle() && r_to_c.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.ctx.server_config.task_idle_max_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        c_to_r.reset_active();
+                        r_to_c.reset_active();
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod common;
+mod recv;
+mod send;
+mod server;
+mod stats;
+mod task;
+
+pub(crate) use server::UdpTProxyServer;
@@ -0,0 +1,161 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, IoSliceMut};
+use std::net::SocketAddr;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+
+use g3_io_ext::{
+    AsyncUdpRecv, UdpCopyClientError, UdpCopyClientRecv, UdpCopyPacket, UdpCopyPacketMeta,
+    UdpRecvHalf,
+};
+use g3_io_sys::udp::RecvMsgHdr;
+
+use crate::serve::{UdpFlowQueue, copy_packet_to_iov};
+
+/// Receive packets of a single udp flow.
+///
+/// The first packets of the flow are received by the listen socket and forwarded to us through
+/// the flow queue, later packets will be delivered to the connected reply socket directly.
+pub(super) struct UdpTProxyFlowRecv {
+    queue: UdpFlowQueue,
+    socket: UdpRecvHalf,
+    client_addr: SocketAddr,
+}
+
+impl UdpTProxyFlowRecv {
+    pub(super) fn new(queue: UdpFlowQueue, socket: UdpRecvHalf, client_addr: SocketAddr) -> Self {
+        UdpTProxyFlowRecv {
+            queue,
+            socket,
+            client_addr,
+        }
+    }
+
+    fn poll_queue(&mut self, cx: &mut Context<'_>) -> Option<Bytes> {
+        match self.queue.poll_packet(cx) {
+            Poll::Ready(Some(packet)) => Some(packet),
+            Poll::Ready(None) | Poll::Pending => None,
+        }
+    }
+}
+
+impl AsyncUdpRecv for UdpTProxyFlowRecv {
+    fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        if let Some(packet) = self.poll_queue(cx) {
+            let n = packet.len().min(buf.len());
+            buf[..n].copy_from_slice(&packet[..n]);
+            return Poll::Ready(Ok((n, self.client_addr)));
+        }
+        self.socket.poll_recv_from(cx, buf)
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if let Some(packet) = self.poll_queue(cx) {
+            let n = packet.len().min(buf.len());
+            buf[..n].copy_from_slice(&packet[..n]);
+            return Poll::Ready(Ok(n));
+        }
+        self.socket.poll_recv(cx, buf)
+    }
+
+    fn poll_recvmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        hdr: &mut RecvMsgHdr<'_, C>,
+    ) -> Poll<io::Result<()>> {
+        if let Some(packet) = self.poll_queue(cx) {
+            hdr.n_recv = copy_packet_to_iov(&packet, &mut hdr.iov);
+            return Poll::Ready(Ok(()));
+        }
+        self.socket.poll_recvmsg(cx, hdr)
+    }
+
+    fn poll_batch_recvmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        hdr_v: &mut [RecvMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        if hdr_v.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if let Some(packet) = self.poll_queue(cx) {
+            hdr_v[0].n_recv = copy_packet_to_iov(&packet, &mut hdr_v[0].iov);
+            let mut count = 1;
+            for hdr in &mut hdr_v[1..] {
+                let Some(packet) = self.queue.try_packet() else {
+                    break;
+                };
+                hdr.n_recv = copy_packet_to_iov(&packet, &mut hdr.iov);
+                count += 1;
+            }
+            return Poll::Ready(Ok(count));
+        }
+        self.socket.poll_batch_recvmsg(cx, hdr_v)
+    }
+}
+
+pub(super) struct UdpTProxyClientRecv<T> {
+    inner: T,
+}
+
+impl<T> UdpTProxyClientRecv<T>
+where
+    T: AsyncUdpRecv,
+{
+    pub(super) fn new(inner: T) -> Self {
+        UdpTProxyClientRecv { inner }
+    }
+}
+
+impl<T> UdpCopyClientRecv for UdpTProxyClientRecv<T>
+where
+    T: AsyncUdpRecv + Send,
+{
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyClientError>> {
+        let nr = ready!(self.inner.poll_recv(cx, buf)).map_err(UdpCopyClientError::RecvFailed)?;
+        Poll::Ready(Ok((0, nr)))
+    }
+
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut hdr_v: Vec<RecvMsgHdr<1>> = packets
+            .iter_mut()
+            .map(|p| RecvMsgHdr::new([IoSliceMut::new(p.buf_mut())]))
+            .collect();
+
+        let count = ready!(self.inner.poll_batch_recvmsg(cx, &mut hdr_v))
+            .map_err(UdpCopyClientError::RecvFailed)?;
+
+        let r: Vec<UdpCopyPacketMeta> = hdr_v
+            .iter()
+            .take(count)
+            .map(|h| UdpCopyPacketMeta::new(&h.iov[0], 0, h.n_recv))
+            .collect();
+        drop(hdr_v);
+        for (m, p) in r.into_iter().zip(packets.iter_mut()) {
+            m.set_packet(p);
+        }
+
+        Poll::Ready(Ok(count))
+    }
+}
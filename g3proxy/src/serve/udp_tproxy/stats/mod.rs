@@ -0,0 +1,12 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod server;
+mod task;
+mod wrapper;
+
+pub(crate) use server::{UdpTProxyServerAliveTaskGuard, UdpTProxyServerStats};
+pub(crate) use task::UdpTProxyTaskStats;
+pub(crate) use wrapper::UdpTProxyTaskCltWrapperStats;
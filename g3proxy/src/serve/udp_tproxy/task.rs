@@ -0,0 +1,283 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use slog::Logger;
+use tokio::net::UdpSocket;
+
+use g3_io_ext::{
+    LimitedUdpRecv, LimitedUdpSend, UdpCopyClientRecv, UdpCopyClientSend, UdpCopyClientToRemote,
+    UdpCopyError, UdpCopyRemoteRecv, UdpCopyRemoteSend, UdpCopyRemoteToClient,
+};
+use g3_types::net::UpstreamAddr;
+
+use super::common::CommonTaskContext;
+use super::recv::{UdpTProxyClientRecv, UdpTProxyFlowRecv};
+use super::send::UdpTProxyClientSend;
+use super::stats::{
+    UdpTProxyServerAliveTaskGuard, UdpTProxyTaskCltWrapperStats, UdpTProxyTaskStats,
+};
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{
+    ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage, UdpFlowKey, UdpFlowQueue,
+};
+
+type UdpTProxyCltRecv = UdpTProxyClientRecv<LimitedUdpRecv<UdpTProxyFlowRecv>>;
+type UdpTProxyCltSend = UdpTProxyClientSend<LimitedUdpSend<g3_io_ext::UdpSendHalf>>;
+
+pub(super) struct UdpTProxyTask {
+    ctx: CommonTaskContext,
+    upstream: UpstreamAddr,
+    udp_notes: UdpConnectTaskNotes,
+    task_notes: ServerTaskNotes,
+    task_stats: Arc<UdpTProxyTaskStats>,
+    _alive_guard: Option<UdpTProxyServerAliveTaskGuard>,
+}
+
+impl UdpTProxyTask {
+    pub(super) fn new(ctx: CommonTaskContext, task_notes: ServerTaskNotes) -> Self {
+        let upstream = UpstreamAddr::from(task_notes.server_addr());
+        UdpTProxyTask {
+            ctx,
+            upstream,
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_notes,
+            task_stats: Arc::new(UdpTProxyTaskStats::default()),
+            _alive_guard: None,
+        }
+    }
+
+    #[inline]
+    fn client_addr(&self) -> SocketAddr {
+        self.task_notes.client_addr()
+    }
+
+    #[inline]
+    fn server_addr(&self) -> SocketAddr {
+        self.task_notes.server_addr()
+    }
+
+    fn flow_key(&self) -> UdpFlowKey {
+        (self.client_addr(), self.server_addr())
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
+        self.ctx
+            .task_logger
+            .as_ref()
+            .map(|logger| TaskLogForUdpConnect {
+                logger,
+                task_notes: &self.task_notes,
+                tcp_server_addr: None,
+                tcp_client_addr: None,
+                udp_listen_addr: Some(self.server_addr()),
+                udp_client_addr: Some(self.client_addr()),
+                upstream: Some(&self.upstream),
+                udp_notes: &self.udp_notes,
+                client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+                client_rd_packets: self.task_stats.clt.recv.get_packets(),
+                client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+                client_wr_packets: self.task_stats.clt.send.get_packets(),
+                remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+                remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+                remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+                remote_wr_packets: self.task_stats.ups.send.get_packets(),
+            })
+    }
+
+    pub(super) async fn into_running(mut self, queue: UdpFlowQueue) {
+        self.pre_start();
+        let e = match self.run(queue).await {
+            Ok(_) => ServerTaskError::Finished,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+        self.ctx
+            .flow_table
+            .remove(&self.flow_key(), &self.task_notes.id);
+    }
+
+    fn pre_start(&mut self) {
+        self._alive_guard = Some(self.ctx.server_stats.add_task());
+
+        if self.ctx.server_config.flush_task_log_on_created {
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log_created();
+            }
+        }
+    }
+
+    async fn run(&mut self, queue: UdpFlowQueue) -> ServerTaskResult<()> {
+        self.task_notes.stage = ServerTaskStage::Preparing;
+        let (clt_r, clt_w) = self.setup_clt(queue)?;
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        let (ups_r, ups_w, escape_logger) = self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await?;
+        self.task_notes.stage = ServerTaskStage::Connected;
+
+        if self.ctx.server_config.flush_task_log_on_connected {
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log_connected();
+            }
+        }
+
+        self.task_notes.mark_relaying();
+        self.run_relay(
+            Box::new(clt_r),
+            Box::new(clt_w),
+            ups_r,
+            ups_w,
+            escape_logger,
+        )
+        .await
+    }
+
+    fn setup_clt(
+        &self,
+        queue: UdpFlowQueue,
+    ) -> ServerTaskResult<(UdpTProxyCltRecv, UdpTProxyCltSend)> {
+        let socket = g3_socket::udp::new_std_transparent_reply_socket(
+            self.server_addr(),
+            self.client_addr(),
+            self.ctx.server_config.listen.socket_buffer(),
+            self.ctx.server_config.udp_misc_opts,
+        )
+        .map_err(|_| {
+            ServerTaskError::InternalServerError("failed to setup udp transparent reply socket")
+        })?;
+        let socket = UdpSocket::from_std(socket).map_err(|_| {
+            ServerTaskError::InternalServerError("failed to register udp transparent reply socket")
+        })?;
+        let (clt_r, clt_w) = g3_io_ext::split_udp(socket);
+
+        let limit_config = &self.ctx.server_config.udp_sock_speed_limit;
+        let wrapper_stats = Arc::new(UdpTProxyTaskCltWrapperStats::new(
+            &self.ctx.server_stats,
+            &self.task_stats,
+        ));
+
+        let clt_r = LimitedUdpRecv::local_limited(
+            UdpTProxyFlowRecv::new(queue, clt_r, self.client_addr()),
+            limit_config.shift_millis,
+            limit_config.max_north_packets,
+            limit_config.max_north_bytes,
+            wrapper_stats.clone(),
+        );
+        let clt_w = LimitedUdpSend::local_limited(
+            clt_w,
+            limit_config.shift_millis,
+            limit_config.max_south_packets,
+            limit_config.max_south_bytes,
+            wrapper_stats,
+        );
+
+        Ok((
+            UdpTProxyClientRecv::new(clt_r),
+            UdpTProxyClientSend::new(clt_w),
+        ))
+    }
+
+    async fn run_relay(
+        &mut self,
+        mut clt_r: Box<dyn UdpCopyClientRecv + Unpin + Send>,
+        mut clt_w: Box<dyn UdpCopyClientSend + Unpin + Send>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send>,
+        escape_logger: Option<Logger>,
+    ) -> ServerTaskResult<()> {
+        let task_id = &self.task_notes.id;
+
+        let mut c_to_r =
+            UdpCopyClientToRemote::new(&mut *clt_r, &mut *ups_w, self.ctx.server_config.udp_relay);
+        let mut r_to_c =
+            UdpCopyRemoteToClient::new(&mut *clt_w, &mut *ups_r, self.ctx.server_config.udp_relay);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut c_to_r => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = escape_logger {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(&logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                r = &mut r_to_c => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            if let Some(logger) = escape_logger {
+                                EscapeLogForUdpConnectSendTo {
+                                    task_id,
+                                    upstream: Some(&self.upstream),
+                                    udp_notes: &self.udp_notes,
+                                }
+                                .log(&logger, &e);
+                            }
+                            Err(e.into())
+                        },
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if c_to_r.is_idle() && r_to_c.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.ctx.server_config.task_idle_max_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        c_to_r.reset_active();
+                        r_to_c.reset_active();
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use arc_swap::ArcSwapOption;
+use log::{info, warn};
+use tokio::task::AbortHandle;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_discover::DiscoverRegisterData;
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::metrics::NodeName;
+use g3_types::net::{UpstreamAddr, WeightedUpstreamAddr};
+
+use super::UpstreamPickConfig;
+use crate::config::server::UpstreamAffinityConfig;
+use crate::discover::DiscoverResult;
+use crate::serve::{ServerTaskError, ServerUpstreamDiscoverStats, StreamUpstreamPicker};
+
+struct UpstreamPickSettings {
+    pick_policy: SelectivePickPolicy,
+    slow_start: Duration,
+    affinity: Option<UpstreamAffinityConfig>,
+}
+
+impl UpstreamPickSettings {
+    fn build_picker(
+        &self,
+        upstream: &[WeightedUpstreamAddr],
+        old: Option<&StreamUpstreamPicker>,
+    ) -> anyhow::Result<StreamUpstreamPicker> {
+        StreamUpstreamPicker::new(
+            upstream,
+            self.pick_policy,
+            self.slow_start,
+            self.affinity.as_ref(),
+            old,
+        )
+    }
+}
+
+struct UpstreamDiscoverWatcher {
+    handle: AbortHandle,
+}
+
+impl Drop for UpstreamDiscoverWatcher {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+struct UpstreamDiscoverUpdater {
+    log_name: Arc<str>,
+    discover: NodeName,
+    settings: Arc<UpstreamPickSettings>,
+    picker: Arc<ArcSwapOption<StreamUpstreamPicker>>,
+    stats: Arc<ServerUpstreamDiscoverStats>,
+}
+
+impl UpstreamDiscoverUpdater {
+    fn update(&self, result: &DiscoverResult) {
+        let upstream = match result {
+            Ok(upstream) => upstream,
+            Err(e) => {
+                warn!(
+                    "{}: failed to get upstream from discover {}: {e:?}",
+                    self.log_name, self.discover
+                );
+                self.stats.add_failed();
+                return;
+            }
+        };
+        if upstream.is_empty() {
+            warn!(
+                "{}: empty upstream returned from discover {}, keep the old ones",
+                self.log_name, self.discover
+            );
+            return;
+        }
+
+        let old_picker = self.picker.load_full();
+        let (added, removed) = match &old_picker {
+            Some(old) => {
+                let old_map: HashMap<&UpstreamAddr, f64> = old
+                    .upstream()
+                    .iter()
+                    .map(|v| (v.inner(), v.weight()))
+                    .collect();
+                let new_map: HashMap<&UpstreamAddr, f64> =
+                    upstream.iter().map(|v| (v.inner(), v.weight())).collect();
+                if old_map == new_map {
+                    return;
+                }
+                let added = new_map.keys().filter(|k| !old_map.contains_key(*k)).count();
+                let removed = old_map.keys().filter(|k| !new_map.contains_key(*k)).count();
+                (added, removed)
+            }
+            None => (upstream.len(), 0),
+        };
+
+        match self.settings.build_picker(upstream, old_picker.as_deref()) {
+            Ok(picker) => {
+                self.picker.store(Some(Arc::new(picker)));
+                info!(
+                    "{}: upstream updated by discover {}: {} addrs, {added} added, {removed} removed",
+                    self.log_name,
+                    self.discover,
+                    upstream.len()
+                );
+                self.stats.add_updated(added, removed);
+            }
+            Err(e) => {
+                warn!(
+                    "{}: failed to update upstream from discover {}: {e:?}",
+                    self.log_name, self.discover
+                );
+                self.stats.add_failed();
+            }
+        }
+    }
+}
+
+/// Upstream addresses that may be static or be updated at runtime by a discover
+pub(crate) struct DynamicUpstream {
+    log_name: Arc<str>,
+    settings: Arc<UpstreamPickSettings>,
+    discover: Option<(NodeName, DiscoverRegisterData)>,
+    picker: Arc<ArcSwapOption<StreamUpstreamPicker>>,
+    stats: Arc<ServerUpstreamDiscoverStats>,
+    watcher: Mutex<Option<UpstreamDiscoverWatcher>>,
+}
+
+impl DynamicUpstream {
+    /// Create a new one, the state of `old` will be inherited if the upstream source is not changed
+    pub(crate) fn new<C: UpstreamPickConfig>(
+        log_name: String,
+        config: &C,
+        stats: Arc<ServerUpstreamDiscoverStats>,
+        old: Option<&DynamicUpstream>,
+    ) -> anyhow::Result<Self> {
+        let settings = UpstreamPickSettings {
+            pick_policy: config.upstream_pick_policy(),
+            slow_start: config.upstream_slow_start(),
+            affinity: config.upstream_affinity().cloned(),
+        };
+        let old_picker = old.and_then(|o| o.picker.load_full());
+
+        let discover = config
+            .upstream_discover()
+            .map(|(name, data)| (name.clone(), data.clone()));
+        let picker = match &discover {
+            Some(_) => match old_picker {
+                Some(old_picker) if old.map(|o| &o.discover) == Some(&discover) => {
+                    let picker = settings.build_picker(old_picker.upstream(), Some(&old_picker))?;
+                    Some(Arc::new(picker))
+                }
+                _ => None,
+            },
+            None => {
+                let picker = settings.build_picker(config.upstream(), old_picker.as_deref())?;
+                Some(Arc::new(picker))
+            }
+        };
+
+        let upstream = DynamicUpstream {
+            log_name: Arc::from(log_name),
+            settings: Arc::new(settings),
+            discover,
+            picker: Arc::new(ArcSwapOption::new(picker)),
+            stats,
+            watcher: Mutex::new(None),
+        };
+        upstream.update_discover();
+        Ok(upstream)
+    }
+
+    pub(crate) fn depend_on_discover(&self, name: &NodeName) -> bool {
+        self.discover
+            .as_ref()
+            .map(|(n, _)| n == name)
+            .unwrap_or(false)
+    }
+
+    /// Register to the discover again and start a new watcher if discover is in use
+    pub(crate) fn update_discover(&self) {
+        let Some((name, data)) = &self.discover else {
+            return;
+        };
+
+        let mut watcher = self.watcher.lock().unwrap();
+        // always stop the old watcher
+        watcher.take();
+
+        let mut receiver = match crate::discover::get_discover(name)
+            .and_then(|discover| discover.register_data(data))
+        {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                warn!(
+                    "{}: failed to register to discover {name}: {e:?}",
+                    self.log_name
+                );
+                self.stats.add_failed();
+                return;
+            }
+        };
+
+        let updater = UpstreamDiscoverUpdater {
+            log_name: self.log_name.clone(),
+            discover: name.clone(),
+            settings: self.settings.clone(),
+            picker: self.picker.clone(),
+            stats: self.stats.clone(),
+        };
+        let handle = tokio::spawn(async move {
+            while receiver.changed().await.is_ok() {
+                updater.update(&receiver.borrow_and_update());
+            }
+        });
+        *watcher = Some(UpstreamDiscoverWatcher {
+            handle: handle.abort_handle(),
+        });
+    }
+
+    /// Pick an upstream, or fail if no upstream has been discovered yet
+    pub(crate) fn pick(
+        &self,
+        cc_info: &ClientConnectionInfo,
+    ) -> Result<UpstreamAddr, ServerTaskError> {
+        match self.picker.load().as_ref() {
+            Some(picker) => Ok(picker.pick(cc_info)),
+            None => Err(ServerTaskError::UpstreamNotAvailable),
+        }
+    }
+}
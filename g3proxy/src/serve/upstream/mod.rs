@@ -0,0 +1,28 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use g3_discover::DiscoverRegisterData;
+use g3_types::collection::SelectivePickPolicy;
+use g3_types::metrics::NodeName;
+use g3_types::net::WeightedUpstreamAddr;
+
+use crate::config::server::UpstreamAffinityConfig;
+
+mod dynamic;
+pub(crate) use dynamic::DynamicUpstream;
+
+pub(crate) trait UpstreamPickConfig {
+    fn upstream(&self) -> &[WeightedUpstreamAddr];
+    fn upstream_pick_policy(&self) -> SelectivePickPolicy;
+    fn upstream_slow_start(&self) -> Duration {
+        Duration::ZERO
+    }
+    fn upstream_affinity(&self) -> Option<&UpstreamAffinityConfig> {
+        None
+    }
+    fn upstream_discover(&self) -> Option<(&NodeName, &DiscoverRegisterData)>;
+}
@@ -17,10 +17,13 @@ use crate::resolve::ResolverStats;
 
 const TAG_KEY_RESOLVER: &str = "resolver";
 const TAG_KEY_RR_TYPE: &str = "rr_type";
+const RR_TYPE_HTTPS: &str = "HTTPS";
 
 const METRIC_NAME_QUERY_TOTAL: &str = "resolver.query.total";
 const METRIC_NAME_QUERY_CACHED: &str = "resolver.query.cached";
 const METRIC_NAME_QUERY_TRASHED: &str = "resolver.query.trashed";
+const METRIC_NAME_QUERY_STALE: &str = "resolver.query.stale";
+const METRIC_NAME_QUERY_PREFETCH: &str = "resolver.query.prefetch";
 const METRIC_NAME_QUERY_DRIVER: &str = "resolver.query.driver.total";
 const METRIC_NAME_QUERY_DRIVER_TIMEOUT: &str = "resolver.query.driver.timeout";
 const METRIC_NAME_QUERY_DRIVER_REFUSED: &str = "resolver.query.driver.refused";
@@ -82,7 +85,7 @@ fn emit_to_statsd(client: &mut StatsdClient, stats: &ResolverStats, snap: &mut R
         &inner_stats.query_a,
         &mut snap.query_a,
         &common_tags,
-        ResolveQueryType::A,
+        ResolveQueryType::A.as_str(),
     );
 
     emit_query_stats_to_statsd(
@@ -90,21 +93,36 @@ fn emit_to_statsd(client: &mut StatsdClient, stats: &ResolverStats, snap: &mut R
         &inner_stats.query_aaaa,
         &mut snap.query_aaaa,
         &common_tags,
-        ResolveQueryType::Aaaa,
+        ResolveQueryType::Aaaa.as_str(),
+    );
+
+    emit_query_stats_to_statsd(
+        client,
+        &inner_stats.query_https,
+        &mut snap.query_https,
+        &common_tags,
+        RR_TYPE_HTTPS,
     );
 
     emit_memory_stats_to_statsd(
         client,
         &inner_stats.memory_a,
         &common_tags,
-        ResolveQueryType::A,
+        ResolveQueryType::A.as_str(),
     );
 
     emit_memory_stats_to_statsd(
         client,
         &inner_stats.memory_aaaa,
         &common_tags,
-        ResolveQueryType::Aaaa,
+        ResolveQueryType::Aaaa.as_str(),
+    );
+
+    emit_memory_stats_to_statsd(
+        client,
+        &inner_stats.memory_https,
+        &common_tags,
+        RR_TYPE_HTTPS,
     );
 }
 
@@ -113,14 +131,12 @@ fn emit_query_stats_to_statsd(
     stats: &ResolverQuerySnapshot,
     snap: &mut ResolverQuerySnapshot,
     common_tags: &StatsdTagGroup,
-    rr_type: ResolveQueryType,
+    rr_type: &str,
 ) {
     if stats.total == 0 && snap.total == 0 {
         return;
     }
 
-    let rr_type = rr_type.as_str();
-
     let new_value = stats.total;
     let diff_value = new_value.wrapping_sub(snap.total);
     client
@@ -145,6 +161,8 @@ fn emit_query_stats_to_statsd(
 
     emit_query_stats_u64!(cached, METRIC_NAME_QUERY_CACHED);
     emit_query_stats_u64!(trashed, METRIC_NAME_QUERY_TRASHED);
+    emit_query_stats_u64!(stale, METRIC_NAME_QUERY_STALE);
+    emit_query_stats_u64!(prefetch, METRIC_NAME_QUERY_PREFETCH);
     emit_query_stats_u64!(driver, METRIC_NAME_QUERY_DRIVER);
     emit_query_stats_u64!(driver_timeout, METRIC_NAME_QUERY_DRIVER_TIMEOUT);
     emit_query_stats_u64!(driver_refused, METRIC_NAME_QUERY_DRIVER_REFUSED);
@@ -159,7 +177,7 @@ fn emit_memory_stats_to_statsd(
     client: &mut StatsdClient,
     snap: &ResolverMemorySnapshot,
     common_tags: &StatsdTagGroup,
-    rr_type: ResolveQueryType,
+    rr_type: &str,
 ) {
     macro_rules! emit_field {
         ($field:ident, $name:expr) => {
@@ -12,7 +12,10 @@ use g3_daemon::metrics::{
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::{GlobalStatsMap, TcpIoSnapshot, UdpIoSnapshot};
 
-use crate::serve::{ArcServerStats, ServerForbiddenSnapshot};
+use crate::serve::{
+    ArcServerStats, ServerForbiddenSnapshot, ServerHttpCacheSnapshot,
+    ServerUpstreamDiscoverSnapshot,
+};
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
 const METRIC_NAME_SERVER_CONN_TOTAL: &str = "server.connection.total";
@@ -28,6 +31,14 @@ const METRIC_NAME_SERVER_IO_OUT_PACKETS: &str = "server.traffic.out.packets";
 const METRIC_NAME_SERVER_UNTRUSTED_TASK_TOTAL: &str = "server.task.untrusted_total";
 const METRIC_NAME_SERVER_UNTRUSTED_TASK_ALIVE: &str = "server.task.untrusted_alive";
 const METRIC_NAME_SERVER_IO_UNTRUSTED_IN_BYTES: &str = "server.traffic.untrusted_in.bytes";
+const METRIC_NAME_SERVER_HTTP_CACHE_HIT: &str = "server.http_cache.hit";
+const METRIC_NAME_SERVER_HTTP_CACHE_MISS: &str = "server.http_cache.miss";
+const METRIC_NAME_SERVER_HTTP_CACHE_REVALIDATED: &str = "server.http_cache.revalidated";
+const METRIC_NAME_SERVER_HTTP_CACHE_STORED: &str = "server.http_cache.stored";
+const METRIC_NAME_SERVER_UPSTREAM_DISCOVER_UPDATED: &str = "server.upstream.discover.updated";
+const METRIC_NAME_SERVER_UPSTREAM_DISCOVER_FAILED: &str = "server.upstream.discover.failed";
+const METRIC_NAME_SERVER_UPSTREAM_DISCOVER_ADDED: &str = "server.upstream.discover.added";
+const METRIC_NAME_SERVER_UPSTREAM_DISCOVER_REMOVED: &str = "server.upstream.discover.removed";
 
 type ServerStatsValue = (ArcServerStats, ServerSnapshot);
 type ListenStatsValue = (Arc<ListenStats>, ListenSnapshot);
@@ -45,6 +56,8 @@ struct ServerSnapshot {
     tcp: TcpIoSnapshot,
     udp: UdpIoSnapshot,
     untrusted: UntrustedTaskStatsSnapshot,
+    http_cache: ServerHttpCacheSnapshot,
+    upstream_discover: ServerUpstreamDiscoverSnapshot,
 }
 
 pub(in crate::stat) fn sync_stats() {
@@ -129,6 +142,19 @@ fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &m
     if let Some(untrusted_stats) = stats.untrusted_snapshot() {
         emit_untrusted_stats(client, untrusted_stats, &mut snap.untrusted, &common_tags);
     }
+
+    if let Some(http_cache_stats) = stats.http_cache_snapshot() {
+        emit_http_cache_stats(client, http_cache_stats, &mut snap.http_cache, &common_tags);
+    }
+
+    if let Some(discover_stats) = stats.upstream_discover_snapshot() {
+        emit_upstream_discover_stats(
+            client,
+            discover_stats,
+            &mut snap.upstream_discover,
+            &common_tags,
+        );
+    }
 }
 
 fn emit_forbidden_stats(
@@ -155,6 +181,56 @@ fn emit_forbidden_stats(
     emit_forbid_stats_u64!(user_blocked, METRIC_NAME_SERVER_FORBIDDEN_USER_BLOCKED);
 }
 
+fn emit_http_cache_stats(
+    client: &mut StatsdClient,
+    stats: ServerHttpCacheSnapshot,
+    snap: &mut ServerHttpCacheSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    macro_rules! emit_cache_stats_u64 {
+        ($id:ident, $name:expr) => {
+            let new_value = stats.$id;
+            if new_value != 0 || snap.$id != 0 {
+                let diff_value = new_value.wrapping_sub(snap.$id);
+                client
+                    .count_with_tags($name, diff_value, common_tags)
+                    .send();
+                snap.$id = new_value;
+            }
+        };
+    }
+
+    emit_cache_stats_u64!(hit, METRIC_NAME_SERVER_HTTP_CACHE_HIT);
+    emit_cache_stats_u64!(miss, METRIC_NAME_SERVER_HTTP_CACHE_MISS);
+    emit_cache_stats_u64!(revalidated, METRIC_NAME_SERVER_HTTP_CACHE_REVALIDATED);
+    emit_cache_stats_u64!(stored, METRIC_NAME_SERVER_HTTP_CACHE_STORED);
+}
+
+fn emit_upstream_discover_stats(
+    client: &mut StatsdClient,
+    stats: ServerUpstreamDiscoverSnapshot,
+    snap: &mut ServerUpstreamDiscoverSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    macro_rules! emit_discover_stats_u64 {
+        ($id:ident, $name:expr) => {
+            let new_value = stats.$id;
+            if new_value != 0 || snap.$id != 0 {
+                let diff_value = new_value.wrapping_sub(snap.$id);
+                client
+                    .count_with_tags($name, diff_value, common_tags)
+                    .send();
+                snap.$id = new_value;
+            }
+        };
+    }
+
+    emit_discover_stats_u64!(updated, METRIC_NAME_SERVER_UPSTREAM_DISCOVER_UPDATED);
+    emit_discover_stats_u64!(failed, METRIC_NAME_SERVER_UPSTREAM_DISCOVER_FAILED);
+    emit_discover_stats_u64!(added, METRIC_NAME_SERVER_UPSTREAM_DISCOVER_ADDED);
+    emit_discover_stats_u64!(removed, METRIC_NAME_SERVER_UPSTREAM_DISCOVER_REMOVED);
+}
+
 fn emit_tcp_io_to_statsd(
     client: &mut StatsdClient,
     stats: TcpIoSnapshot,
@@ -27,11 +27,15 @@ fn build_cli_args() -> Command {
         .subcommand(proc::commands::force_quit())
         .subcommand(proc::commands::force_quit_all())
         .subcommand(proc::commands::list())
+        .subcommand(proc::commands::dump_config())
+        .subcommand(proc::commands::diff_config())
+        .subcommand(proc::commands::validate_config())
         .subcommand(proc::commands::reload_user_group())
         .subcommand(proc::commands::reload_resolver())
         .subcommand(proc::commands::reload_auditor())
         .subcommand(proc::commands::reload_escaper())
         .subcommand(proc::commands::reload_server())
+        .subcommand(proc::commands::reload_discover())
         .subcommand(user_group::command())
         .subcommand(resolver::command())
         .subcommand(escaper::command())
@@ -67,6 +71,9 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_FORCE_QUIT => proc::force_quit(&proc_control, args).await,
                 proc::COMMAND_FORCE_QUIT_ALL => proc::force_quit_all(&proc_control).await,
                 proc::COMMAND_LIST => proc::list(&proc_control, args).await,
+                proc::COMMAND_DUMP_CONFIG => proc::dump_config(&proc_control, args).await,
+                proc::COMMAND_DIFF_CONFIG => proc::diff_config(&proc_control).await,
+                proc::COMMAND_VALIDATE_CONFIG => proc::validate_config(&proc_control).await,
                 proc::COMMAND_RELOAD_USER_GROUP => {
                     proc::reload_user_group(&proc_control, args).await
                 }
@@ -74,6 +81,7 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_RELOAD_AUDITOR => proc::reload_auditor(&proc_control, args).await,
                 proc::COMMAND_RELOAD_ESCAPER => proc::reload_escaper(&proc_control, args).await,
                 proc::COMMAND_RELOAD_SERVER => proc::reload_server(&proc_control, args).await,
+                proc::COMMAND_RELOAD_DISCOVER => proc::reload_discover(&proc_control, args).await,
                 user_group::COMMAND => user_group::run(&proc_control, args).await,
                 resolver::COMMAND => resolver::run(&proc_control, args).await,
                 escaper::COMMAND => escaper::run(&proc_control, args).await,
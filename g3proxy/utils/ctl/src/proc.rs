@@ -3,14 +3,16 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use anyhow::anyhow;
 use clap::ArgMatches;
 
-use g3_ctl::CommandResult;
+use g3_ctl::{CommandError, CommandResult};
 
 use g3proxy_proto::escaper_capnp::escaper_control;
-use g3proxy_proto::proc_capnp::proc_control;
+use g3proxy_proto::proc_capnp::{config_change, proc_control};
 use g3proxy_proto::resolver_capnp::resolver_control;
 use g3proxy_proto::server_capnp::server_control;
+use g3proxy_proto::types_capnp::operation_result;
 use g3proxy_proto::user_group_capnp::user_group_control;
 
 use crate::common::{parse_fetch_result, parse_operation_result};
@@ -30,12 +32,18 @@ const RESOURCE_VALUE_RESOLVER: &str = "resolver";
 const RESOURCE_VALUE_AUDITOR: &str = "auditor";
 const RESOURCE_VALUE_ESCAPER: &str = "escaper";
 const RESOURCE_VALUE_SERVER: &str = "server";
+const RESOURCE_VALUE_DISCOVER: &str = "discover";
+
+pub const COMMAND_DUMP_CONFIG: &str = "dump-config";
+pub const COMMAND_DIFF_CONFIG: &str = "diff-config";
+pub const COMMAND_VALIDATE_CONFIG: &str = "validate-config";
 
 pub const COMMAND_RELOAD_USER_GROUP: &str = "reload-user-group";
 pub const COMMAND_RELOAD_RESOLVER: &str = "reload-resolver";
 pub const COMMAND_RELOAD_AUDITOR: &str = "reload-auditor";
 pub const COMMAND_RELOAD_ESCAPER: &str = "reload-escaper";
 pub const COMMAND_RELOAD_SERVER: &str = "reload-server";
+pub const COMMAND_RELOAD_DISCOVER: &str = "reload-discover";
 
 const SUBCOMMAND_ARG_NAME: &str = "name";
 
@@ -77,11 +85,42 @@ pub mod commands {
                     RESOURCE_VALUE_AUDITOR,
                     RESOURCE_VALUE_ESCAPER,
                     RESOURCE_VALUE_SERVER,
+                    RESOURCE_VALUE_DISCOVER,
                 ])
                 .ignore_case(true),
         )
     }
 
+    pub fn dump_config() -> Command {
+        Command::new(COMMAND_DUMP_CONFIG)
+            .about("Dump the running config of the resource as yaml, with secrets redacted")
+            .arg(
+                Arg::new(COMMAND_LIST_ARG_RESOURCE)
+                    .required(true)
+                    .num_args(1)
+                    .value_parser([
+                        RESOURCE_VALUE_USER_GROUP,
+                        RESOURCE_VALUE_RESOLVER,
+                        RESOURCE_VALUE_AUDITOR,
+                        RESOURCE_VALUE_ESCAPER,
+                        RESOURCE_VALUE_SERVER,
+                    ])
+                    .ignore_case(true),
+            )
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+    }
+
+    pub fn diff_config() -> Command {
+        Command::new(COMMAND_DIFF_CONFIG)
+            .about("Compare the running config with the config files, and show what will be changed on reload")
+    }
+
+    pub fn validate_config() -> Command {
+        Command::new(COMMAND_VALIDATE_CONFIG).about(
+            "Validate the config files, and show all the objects that will be reloaded without applying",
+        )
+    }
+
     pub fn reload_user_group() -> Command {
         Command::new(COMMAND_RELOAD_USER_GROUP)
             .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
@@ -106,6 +145,11 @@ pub mod commands {
         Command::new(COMMAND_RELOAD_SERVER)
             .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
     }
+
+    pub fn reload_discover() -> Command {
+        Command::new(COMMAND_RELOAD_DISCOVER)
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+    }
 }
 
 pub async fn version(client: &proc_control::Client) -> CommandResult<()> {
@@ -151,6 +195,7 @@ pub async fn list(client: &proc_control::Client, args: &ArgMatches) -> CommandRe
         RESOURCE_VALUE_AUDITOR => list_auditor(client).await,
         RESOURCE_VALUE_ESCAPER => list_escaper(client).await,
         RESOURCE_VALUE_SERVER => list_server(client).await,
+        RESOURCE_VALUE_DISCOVER => list_discover(client).await,
         _ => unreachable!(),
     }
 }
@@ -185,6 +230,146 @@ async fn list_server(client: &proc_control::Client) -> CommandResult<()> {
     g3_ctl::print_result_list(rsp.get()?.get_result()?)
 }
 
+async fn list_discover(client: &proc_control::Client) -> CommandResult<()> {
+    let req = client.list_discover_request();
+    let rsp = req.send().promise.await?;
+    g3_ctl::print_result_list(rsp.get()?.get_result()?)
+}
+
+pub async fn dump_config(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    match args
+        .get_one::<String>(COMMAND_LIST_ARG_RESOURCE)
+        .unwrap()
+        .as_str()
+    {
+        RESOURCE_VALUE_USER_GROUP => dump_user_group_config(client, name).await,
+        RESOURCE_VALUE_RESOLVER => dump_resolver_config(client, name).await,
+        RESOURCE_VALUE_AUDITOR => dump_auditor_config(client, name).await,
+        RESOURCE_VALUE_ESCAPER => dump_escaper_config(client, name).await,
+        RESOURCE_VALUE_SERVER => dump_server_config(client, name).await,
+        _ => unreachable!(),
+    }
+}
+
+fn print_config(config: capnp::text::Reader<'_>) -> CommandResult<()> {
+    let config = config.to_str().map_err(|reason| CommandError::Utf8 {
+        field: "config",
+        reason,
+    })?;
+    println!("{}", config.trim_end());
+    Ok(())
+}
+
+async fn dump_user_group_config(client: &proc_control::Client, name: &str) -> CommandResult<()> {
+    let mut req = client.dump_user_group_config_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    print_config(parse_fetch_result(rsp.get()?.get_result()?)?)
+}
+
+async fn dump_resolver_config(client: &proc_control::Client, name: &str) -> CommandResult<()> {
+    let mut req = client.dump_resolver_config_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    print_config(parse_fetch_result(rsp.get()?.get_result()?)?)
+}
+
+async fn dump_auditor_config(client: &proc_control::Client, name: &str) -> CommandResult<()> {
+    let mut req = client.dump_auditor_config_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    print_config(parse_fetch_result(rsp.get()?.get_result()?)?)
+}
+
+async fn dump_escaper_config(client: &proc_control::Client, name: &str) -> CommandResult<()> {
+    let mut req = client.dump_escaper_config_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    print_config(parse_fetch_result(rsp.get()?.get_result()?)?)
+}
+
+async fn dump_server_config(client: &proc_control::Client, name: &str) -> CommandResult<()> {
+    let mut req = client.dump_server_config_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    print_config(parse_fetch_result(rsp.get()?.get_result()?)?)
+}
+
+fn print_config_change(change: config_change::Reader<'_>) -> CommandResult<()> {
+    let text = |field: &'static str, r: capnp::Result<capnp::text::Reader<'_>>| {
+        r?.to_str()
+            .map(|s| s.to_string())
+            .map_err(|reason| CommandError::Utf8 { field, reason })
+    };
+    let kind = text("kind", change.get_kind())?;
+    let name = text("name", change.get_name())?;
+    match change.get_action()? {
+        config_change::Action::Added => println!("+ {kind} {name}"),
+        config_change::Action::Removed => println!("- {kind} {name}"),
+        config_change::Action::Modified => {
+            let mut keys = Vec::new();
+            for key in change.get_changed_keys()?.iter() {
+                keys.push(text("changed_keys", key)?);
+            }
+            println!("~ {kind} {name}: {}", keys.join(", "));
+        }
+        config_change::Action::Dependent => {
+            let depend_kind = text("depend_kind", change.get_depend_kind())?;
+            let depend_name = text("depend_name", change.get_depend_name())?;
+            println!("* {kind} {name}: depends on {depend_kind} {depend_name}");
+        }
+    }
+    Ok(())
+}
+
+pub async fn diff_config(client: &proc_control::Client) -> CommandResult<()> {
+    let req = client.diff_config_request();
+    let rsp = req.send().promise.await?;
+    let rsp = rsp.get()?;
+    if let operation_result::Which::Err(err) = rsp.get_result()?.which().unwrap() {
+        let e = err?;
+        return Err(CommandError::api_error(e.get_code(), e.get_reason()?));
+    }
+    let changes = rsp.get_changes()?;
+    if changes.is_empty() {
+        println!("no change");
+        return Ok(());
+    }
+    for change in changes.iter() {
+        print_config_change(change)?;
+    }
+    Ok(())
+}
+
+pub async fn validate_config(client: &proc_control::Client) -> CommandResult<()> {
+    let req = client.validate_config_request();
+    let rsp = req.send().promise.await?;
+    let rsp = rsp.get()?;
+    if let operation_result::Which::Err(err) = rsp.get_result()?.which().unwrap() {
+        let e = err?;
+        return Err(CommandError::api_error(e.get_code(), e.get_reason()?));
+    }
+    let errors = rsp.get_errors()?;
+    for e in errors.iter() {
+        let e = e?.to_str().map_err(|reason| CommandError::Utf8 {
+            field: "errors",
+            reason,
+        })?;
+        println!("error: {e}");
+    }
+    for change in rsp.get_changes()?.iter() {
+        print_config_change(change)?;
+    }
+    if !errors.is_empty() {
+        return Err(CommandError::Cli(anyhow!(
+            "{} error(s) found in config",
+            errors.len()
+        )));
+    }
+    Ok(())
+}
+
 pub async fn reload_user_group(
     client: &proc_control::Client,
     args: &ArgMatches,
@@ -231,6 +416,17 @@ pub async fn reload_server(client: &proc_control::Client, args: &ArgMatches) ->
     parse_operation_result(rsp.get()?.get_result()?)
 }
 
+pub async fn reload_discover(
+    client: &proc_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    let mut req = client.reload_discover_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub(crate) async fn get_user_group(
     client: &proc_control::Client,
     name: &str,
@@ -6,22 +6,69 @@
 use clap::{Arg, ArgMatches, Command};
 use futures_util::future::TryFutureExt;
 
-use g3_ctl::CommandResult;
+use g3_ctl::{CommandError, CommandResult};
 
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::server_capnp::server_control;
 
+use super::common::parse_operation_result;
+
 pub const COMMAND: &str = "server";
 
 const COMMAND_ARG_NAME: &str = "name";
 
 const SUBCOMMAND_STATUS: &str = "status";
+const SUBCOMMAND_LIST_TASKS: &str = "list-tasks";
+const SUBCOMMAND_CANCEL_TASK: &str = "cancel-task";
+const SUBCOMMAND_CANCEL_USER_TASKS: &str = "cancel-user-tasks";
+
+const SUBCOMMAND_ARG_USER: &str = "user";
+const SUBCOMMAND_ARG_UPSTREAM: &str = "upstream";
+const SUBCOMMAND_ARG_ID: &str = "id";
 
 pub fn command() -> Command {
     Command::new(COMMAND)
         .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
         .subcommand_required(true)
         .subcommand(Command::new(SUBCOMMAND_STATUS))
+        .subcommand(
+            Command::new(SUBCOMMAND_LIST_TASKS)
+                .about("List live tasks")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_USER)
+                        .help("Only show tasks of this user")
+                        .value_name("USERNAME")
+                        .num_args(1)
+                        .long("user"),
+                )
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_UPSTREAM)
+                        .help("Only show tasks to this upstream host or host:port")
+                        .value_name("UPSTREAM")
+                        .num_args(1)
+                        .long("upstream"),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_CANCEL_TASK)
+                .about("Cancel a live task")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_ID)
+                        .value_name("TASK ID")
+                        .required(true)
+                        .num_args(1),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_CANCEL_USER_TASKS)
+                .about("Cancel all live tasks of a user")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_USER)
+                        .value_name("USERNAME")
+                        .required(true)
+                        .num_args(1),
+                ),
+        )
 }
 
 async fn status(client: &server_control::Client) -> CommandResult<()> {
@@ -35,16 +82,103 @@ async fn status(client: &server_control::Client) -> CommandResult<()> {
     Ok(())
 }
 
+fn text_field<'a>(
+    field: &'static str,
+    reader: capnp::Result<capnp::text::Reader<'a>>,
+) -> CommandResult<&'a str> {
+    reader?
+        .to_str()
+        .map_err(|reason| CommandError::Utf8 { field, reason })
+}
+
+async fn list_tasks(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let mut req = client.list_tasks_request();
+    if let Some(user) = args.get_one::<String>(SUBCOMMAND_ARG_USER) {
+        req.get().set_user(user.as_str());
+    }
+    if let Some(upstream) = args.get_one::<String>(SUBCOMMAND_ARG_UPSTREAM) {
+        req.get().set_upstream(upstream.as_str());
+    }
+    let rsp = req.send().promise.await?;
+    let tasks = rsp.get()?.get_result()?;
+    for task in tasks.iter() {
+        println!("{}", text_field("id", task.get_id())?);
+        let user = text_field("user", task.get_user())?;
+        if !user.is_empty() {
+            println!("  user: {user}");
+        }
+        println!(
+            "  client: {}",
+            text_field("client_addr", task.get_client_addr())?
+        );
+        println!(
+            "  upstream: {}",
+            text_field("upstream", task.get_upstream())?
+        );
+        println!("  escaper: {}", text_field("escaper", task.get_escaper())?);
+        println!(
+            "  start at: {}",
+            text_field("start_at", task.get_start_at())?
+        );
+        println!("  age: {}ms", task.get_age_millis());
+        println!(
+            "  client bytes: read {} / write {}",
+            task.get_client_read_bytes(),
+            task.get_client_write_bytes()
+        );
+        println!(
+            "  upstream bytes: read {} / write {}",
+            task.get_upstream_read_bytes(),
+            task.get_upstream_write_bytes()
+        );
+    }
+    Ok(())
+}
+
+async fn cancel_task(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let id = args.get_one::<String>(SUBCOMMAND_ARG_ID).unwrap();
+    let mut req = client.cancel_task_request();
+    req.get().set_id(id.as_str());
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn cancel_user_tasks(
+    client: &server_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let user = args.get_one::<String>(SUBCOMMAND_ARG_USER).unwrap();
+    let mut req = client.cancel_user_tasks_request();
+    req.get().set_user(user.as_str());
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
     let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
 
-    let (subcommand, _) = args.subcommand().unwrap();
+    let (subcommand, args) = args.subcommand().unwrap();
     match subcommand {
         SUBCOMMAND_STATUS => {
             super::proc::get_server(client, name)
                 .and_then(|server| async move { status(&server).await })
                 .await
         }
+        SUBCOMMAND_LIST_TASKS => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { list_tasks(&server, args).await })
+                .await
+        }
+        SUBCOMMAND_CANCEL_TASK => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { cancel_task(&server, args).await })
+                .await
+        }
+        SUBCOMMAND_CANCEL_USER_TASKS => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { cancel_user_tasks(&server, args).await })
+                .await
+        }
         _ => unreachable!(),
     }
 }
@@ -0,0 +1,85 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use log::debug;
+use serde_json::{Map, Value};
+
+use g3_daemon::control::{HttpControlError, HttpControlHandler, HttpControlResult};
+use g3_types::metrics::NodeName;
+
+const KIND_IMPORTER: &str = "importers";
+const KIND_COLLECTOR: &str = "collectors";
+const KIND_EXPORTER: &str = "exporters";
+
+pub struct HttpController {}
+
+impl HttpController {
+    pub fn start() -> anyhow::Result<()> {
+        g3_daemon::control::HttpController::start(HttpControlImpl)
+    }
+
+    pub(super) async fn abort() {
+        debug!("aborting http controller");
+        g3_daemon::control::HttpController::abort().await;
+    }
+}
+
+struct HttpControlImpl;
+
+fn get_names(kind: &str) -> HttpControlResult<HashSet<NodeName>> {
+    match kind {
+        KIND_IMPORTER => Ok(crate::import::get_names()),
+        KIND_COLLECTOR => Ok(crate::collect::get_names()),
+        KIND_EXPORTER => Ok(crate::export::get_names()),
+        _ => Err(HttpControlError::unknown_kind(kind)),
+    }
+}
+
+fn check_name(kind: &str, name: &str) -> HttpControlResult<NodeName> {
+    let name = unsafe { NodeName::new_unchecked(name) };
+    if get_names(kind)?.contains(&name) {
+        Ok(name)
+    } else {
+        Err(HttpControlError::not_found(kind, name.as_str()))
+    }
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    fn version(&self) -> &'static str {
+        crate::build::VERSION
+    }
+
+    fn list(&self, kind: &str) -> HttpControlResult<Vec<String>> {
+        let mut names: Vec<String> = get_names(kind)?
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn status(&self, kind: &str, name: &str) -> HttpControlResult<Map<String, Value>> {
+        let name = check_name(kind, name)?;
+
+        let mut map = Map::new();
+        map.insert("name".to_string(), Value::from(name.as_str()));
+        Ok(map)
+    }
+
+    async fn reload(&self, kind: &str, name: &str) -> HttpControlResult<()> {
+        let name = check_name(kind, name)?.to_string();
+        let r = match kind {
+            KIND_IMPORTER => super::bridge::reload_importer(name, None).await,
+            KIND_COLLECTOR => super::bridge::reload_collector(name, None).await,
+            KIND_EXPORTER => super::bridge::reload_exporter(name, None).await,
+            _ => return Err(HttpControlError::unknown_kind(kind)),
+        };
+        r.map_err(HttpControlError::Failed)
+    }
+}
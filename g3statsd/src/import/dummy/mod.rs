@@ -7,6 +7,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use tokio::net::UdpSocket;
 #[cfg(unix)]
 use tokio::net::unix::SocketAddr as UnixSocketAddr;
 use tokio::sync::broadcast;
@@ -119,6 +120,7 @@ impl BaseServer for DummyImporter {
 impl ReceiveUdpServer for DummyImporter {
     fn receive_udp_packet(
         &self,
+        _socket: &Arc<UdpSocket>,
         _packet: &[u8],
         _client_addr: SocketAddr,
         _server_addr: SocketAddr,
@@ -6,6 +6,7 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
+use tokio::net::UdpSocket;
 #[cfg(unix)]
 use tokio::net::unix::SocketAddr as UnixSocketAddr;
 use tokio::sync::broadcast;
@@ -90,13 +91,14 @@ impl ReloadServer for WrapArcImporter {
 impl ReceiveUdpServer for WrapArcImporter {
     fn receive_udp_packet(
         &self,
+        socket: &Arc<UdpSocket>,
         packet: &[u8],
         client_addr: SocketAddr,
         server_addr: SocketAddr,
         worker_id: Option<usize>,
     ) {
         self.0
-            .receive_udp_packet(packet, client_addr, server_addr, worker_id)
+            .receive_udp_packet(socket, packet, client_addr, server_addr, worker_id)
     }
 }
 
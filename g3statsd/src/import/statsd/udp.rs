@@ -10,6 +10,7 @@ use anyhow::anyhow;
 use arc_swap::ArcSwap;
 use chrono::Utc;
 use log::debug;
+use tokio::net::UdpSocket;
 #[cfg(unix)]
 use tokio::net::unix::SocketAddr as UnixSocketAddr;
 use tokio::sync::broadcast;
@@ -161,6 +162,7 @@ impl BaseServer for StatsdUdpImporter {
 impl ReceiveUdpServer for StatsdUdpImporter {
     fn receive_udp_packet(
         &self,
+        _socket: &Arc<UdpSocket>,
         packet: &[u8],
         client_addr: SocketAddr,
         _server_addr: SocketAddr,
@@ -133,6 +133,7 @@ impl BaseServer for StatsdUnixImporter {
 impl ReceiveUdpServer for StatsdUnixImporter {
     fn receive_udp_packet(
         &self,
+        _socket: &Arc<tokio::net::UdpSocket>,
         _packet: &[u8],
         _client_addr: std::net::SocketAddr,
         _server_addr: std::net::SocketAddr,
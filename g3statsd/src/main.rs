@@ -11,6 +11,10 @@ use g3_daemon::control::{QuitAction, UpgradeAction};
 use g3statsd::opts::ProcArgs;
 
 fn main() -> anyhow::Result<()> {
+    rustls::crypto::ring::default_provider()
+        .install_default()
+        .unwrap();
+
     let Some(proc_args) =
         g3statsd::opts::parse_clap().context("failed to parse command line options")?
     else {
@@ -73,6 +77,7 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
                 daemon_ctl.await;
             });
         }
+        g3statsd::control::HttpController::start().context("failed to start http controller")?;
         g3statsd::control::QuitActor::tokio_spawn_run();
 
         g3statsd::signal::register().context("failed to setup signal handler")?;
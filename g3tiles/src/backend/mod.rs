@@ -81,6 +81,7 @@ pub(crate) trait BackendExt: Backend {
             SelectivePickPolicy::Random => nodes.pick_random(),
             SelectivePickPolicy::Serial => nodes.pick_serial(),
             SelectivePickPolicy::RoundRobin => nodes.pick_round_robin(),
+            SelectivePickPolicy::WeightedRoundRobin => nodes.pick_weighted_round_robin(),
             SelectivePickPolicy::Ketama => {
                 let key = ConsistentKey {
                     client_ip: task_notes.client_ip(),
@@ -9,6 +9,7 @@ use std::time::Duration;
 use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
+use g3_discover::DiscoverRegisterData;
 use g3_histogram::HistogramMetricsConfig;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{
@@ -20,7 +21,6 @@ use g3_yaml::YamlDocPosition;
 const BACKEND_CONFIG_TYPE: &str = "KeylessQuic";
 
 use super::{AnyBackendConfig, BackendConfig, BackendConfigDiffAction};
-use crate::config::discover::DiscoverRegisterData;
 use crate::module::keyless::MultiplexedUpstreamConnectionConfig;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
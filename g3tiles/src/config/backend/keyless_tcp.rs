@@ -10,13 +10,13 @@ use anyhow::{Context, anyhow};
 use rustls_pki_types::ServerName;
 use yaml_rust::{Yaml, yaml};
 
+use g3_discover::DiscoverRegisterData;
 use g3_histogram::HistogramMetricsConfig;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{ConnectionPoolConfig, RustlsClientConfigBuilder, TcpKeepAliveConfig};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyBackendConfig, BackendConfig, BackendConfigDiffAction};
-use crate::config::discover::DiscoverRegisterData;
 use crate::module::keyless::MultiplexedUpstreamConnectionConfig;
 
 const BACKEND_CONFIG_TYPE: &str = "KeylessTcp";
@@ -8,6 +8,9 @@ use std::path::Path;
 use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
+use g3_discover::{
+    DiscoverConfig, DiscoverConfigDiffAction, HostResolverDiscoverConfig, StaticAddrDiscoverConfig,
+};
 use g3_macros::AnyConfig;
 use g3_types::metrics::NodeName;
 use g3_yaml::{HybridParser, YamlDocPosition};
@@ -15,43 +18,29 @@ use g3_yaml::{HybridParser, YamlDocPosition};
 mod registry;
 pub(crate) use registry::{clear, get_all};
 
-pub(crate) mod host_resolver;
-pub(crate) mod static_addr;
-
 const CONFIG_KEY_DISCOVER_TYPE: &str = "type";
-const CONFIG_KEY_DISCOVER_NAME: &str = "name";
-
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub(crate) enum DiscoverRegisterData {
-    Null,
-    Yaml(Yaml),
-    #[allow(unused)]
-    Json(serde_json::Value),
-}
-
-pub(crate) enum DiscoverConfigDiffAction {
-    NoAction,
-    SpawnNew,
-    #[allow(unused)]
-    UpdateInPlace,
-}
-
-pub(crate) trait DiscoverConfig {
-    fn name(&self) -> &NodeName;
-    fn position(&self) -> Option<YamlDocPosition>;
-    fn r#type(&self) -> &'static str;
-
-    fn diff_action(&self, new: &AnyDiscoverConfig) -> DiscoverConfigDiffAction;
-}
 
 #[derive(Clone, AnyConfig)]
 #[def_fn(name, &NodeName)]
 #[def_fn(r#type, &'static str)]
 #[def_fn(position, Option<YamlDocPosition>)]
-#[def_fn(diff_action, &Self, DiscoverConfigDiffAction)]
 pub(crate) enum AnyDiscoverConfig {
-    StaticAddr(static_addr::StaticAddrDiscoverConfig),
-    HostResolver(host_resolver::HostResolverDiscoverConfig),
+    StaticAddr(StaticAddrDiscoverConfig),
+    HostResolver(HostResolverDiscoverConfig),
+}
+
+impl AnyDiscoverConfig {
+    pub(crate) fn diff_action(&self, new: &Self) -> DiscoverConfigDiffAction {
+        match (self, new) {
+            (AnyDiscoverConfig::StaticAddr(old), AnyDiscoverConfig::StaticAddr(new)) => {
+                old.diff_action(new)
+            }
+            (AnyDiscoverConfig::HostResolver(old), AnyDiscoverConfig::HostResolver(new)) => {
+                old.diff_action(new)
+            }
+            _ => DiscoverConfigDiffAction::SpawnNew,
+        }
+    }
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -82,14 +71,13 @@ fn load_discover(
     let discover_type = g3_yaml::hash_get_required_str(map, CONFIG_KEY_DISCOVER_TYPE)?;
     match g3_yaml::key::normalize(discover_type).as_str() {
         "static_addr" | "staticaddr" => {
-            let discover = static_addr::StaticAddrDiscoverConfig::parse_yaml_conf(map, position)
+            let discover = StaticAddrDiscoverConfig::parse_yaml_conf(map, position)
                 .context("failed to load this StaticAddr discover")?;
             Ok(AnyDiscoverConfig::StaticAddr(discover))
         }
         "host_resolver" | "hostresolver" => {
-            let discover =
-                host_resolver::HostResolverDiscoverConfig::parse_yaml_conf(map, position)
-                    .context("failed to load this HostResolver discover")?;
+            let discover = HostResolverDiscoverConfig::parse_yaml_conf(map, position)
+                .context("failed to load this HostResolver discover")?;
             Ok(AnyDiscoverConfig::HostResolver(discover))
         }
         _ => Err(anyhow!("unsupported discover type {}", discover_type)),
@@ -3,6 +3,10 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
 use anyhow::{Context, anyhow};
 use openssl::ex_data::Index;
 use openssl::ssl::{
@@ -12,9 +16,9 @@ use openssl::ssl::{
 use openssl::stack::Stack;
 use openssl::x509::X509;
 use openssl::x509::store::X509StoreBuilder;
-use std::sync::Arc;
 use yaml_rust::Yaml;
 
+use g3_acme::{AcmeChallengeType, AcmeConfig};
 use g3_types::collection::NamedValue;
 use g3_types::limit::RateLimitQuotaConfig;
 use g3_types::metrics::NodeName;
@@ -25,6 +29,8 @@ use g3_types::net::{
 use g3_types::route::AlpnMatch;
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
+use crate::module::cert::{CertFilesStamp, OcspStapleCache};
+
 #[cfg(feature = "vendored-tongsuo")]
 use g3_types::net::OpensslTlcpCertificatePair;
 
@@ -32,8 +38,12 @@ use g3_types::net::OpensslTlcpCertificatePair;
 pub(crate) struct OpensslHostConfig {
     name: String,
     cert_pairs: Vec<OpensslCertificatePair>,
+    cert_pairs_source: Option<(Yaml, PathBuf)>,
+    pub(crate) acme: Option<Arc<AcmeConfig>>,
     #[cfg(feature = "vendored-tongsuo")]
     tlcp_cert_pairs: Vec<OpensslTlcpCertificatePair>,
+    pub(crate) cert_reload_interval: Option<Duration>,
+    pub(crate) ocsp_stapling: bool,
     client_auth: bool,
     client_auth_certs: Vec<Vec<u8>>,
     session_id_context: String,
@@ -60,6 +70,47 @@ impl NamedValue for OpensslHostConfig {
 }
 
 impl OpensslHostConfig {
+    /// Get the configured cert pairs, and the one issued by ACME if it has been stored
+    pub(crate) fn load_cert_pairs(&self) -> anyhow::Result<Vec<OpensslCertificatePair>> {
+        let mut cert_pairs = self.cert_pairs.clone();
+        self.add_acme_cert_pair(&mut cert_pairs)?;
+        Ok(cert_pairs)
+    }
+
+    /// Get the modification time of the files the cert pairs are loaded from
+    pub(crate) fn cert_files_stamp(&self) -> CertFilesStamp {
+        CertFilesStamp::collect(self.cert_pairs_source.as_ref(), self.acme.as_deref())
+    }
+
+    /// Load the cert pairs again from the config source, so the changed files will take effect
+    pub(crate) fn reload_cert_pairs(&self) -> anyhow::Result<Vec<OpensslCertificatePair>> {
+        let mut cert_pairs = match &self.cert_pairs_source {
+            Some((value, lookup_dir)) => parse_cert_pairs(value, lookup_dir)?,
+            None => self.cert_pairs.clone(),
+        };
+        self.add_acme_cert_pair(&mut cert_pairs)?;
+        Ok(cert_pairs)
+    }
+
+    fn add_acme_cert_pair(
+        &self,
+        cert_pairs: &mut Vec<OpensslCertificatePair>,
+    ) -> anyhow::Result<()> {
+        let Some(acme) = &self.acme else {
+            return Ok(());
+        };
+        let Some((certs, key)) = acme.load_certificate()? else {
+            return Ok(());
+        };
+        let mut pair = OpensslCertificatePair::default();
+        pair.set_certificates(certs)
+            .context("invalid ACME certificate")?;
+        pair.set_private_key(key)
+            .context("invalid ACME private key")?;
+        cert_pairs.push(pair);
+        Ok(())
+    }
+
     fn set_client_auth_certificates(&mut self, certs: Vec<X509>) -> anyhow::Result<()> {
         for (i, cert) in certs.into_iter().enumerate() {
             let bytes = cert
@@ -121,9 +172,11 @@ impl OpensslHostConfig {
 
     pub(crate) fn build_ssl_context(
         &self,
+        cert_pairs: &[OpensslCertificatePair],
         ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        ocsp_cache: Option<&Arc<OcspStapleCache>>,
     ) -> anyhow::Result<Option<SslContext>> {
-        if self.cert_pairs.is_empty() {
+        if cert_pairs.is_empty() {
             return Ok(None);
         }
 
@@ -163,10 +216,13 @@ impl OpensslHostConfig {
         // ssl_builder.set_mode() // TODO do we need it?
         // ssl_builder.set_options() // TODO do we need it?
 
-        for (i, pair) in self.cert_pairs.iter().enumerate() {
+        for (i, pair) in cert_pairs.iter().enumerate() {
             pair.add_to_server_ssl_context(&mut ssl_builder, &mut id_ctx)
                 .context(format!("failed to add cert pair #{i} to ssl context"))?;
         }
+        if let Some(cache) = ocsp_cache {
+            cache.add_to_server_ssl_context(&mut ssl_builder)?;
+        }
 
         id_ctx
             .build_set(&mut ssl_builder)
@@ -257,6 +313,15 @@ impl OpensslHostConfig {
     }
 }
 
+fn parse_cert_pairs(
+    value: &Yaml,
+    lookup_dir: &Path,
+) -> anyhow::Result<Vec<OpensslCertificatePair>> {
+    g3_yaml::value::as_list(value, |v| {
+        g3_yaml::value::as_openssl_certificate_pair(v, Some(lookup_dir))
+    })
+}
+
 fn set_ticket_key_callback(
     builder: &mut SslAcceptorBuilder,
     ticket_key_index: Index<SslContext, Arc<RollingTicketer<OpensslTicketKey>>>,
@@ -295,12 +360,27 @@ impl YamlMapCallback for OpensslHostConfig {
             }
             "cert_pairs" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
-                self.cert_pairs = g3_yaml::value::as_list(value, |v| {
-                    g3_yaml::value::as_openssl_certificate_pair(v, Some(lookup_dir))
-                })
-                .context(format!(
+                self.cert_pairs = parse_cert_pairs(value, lookup_dir).context(format!(
                     "invalid openssl cert pair list value for key {key}"
                 ))?;
+                self.cert_pairs_source = Some((value.clone(), lookup_dir.to_path_buf()));
+                Ok(())
+            }
+            "acme" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                let config = AcmeConfig::parse_yaml(value, Some(lookup_dir))
+                    .context(format!("invalid acme config value for key {key}"))?;
+                self.acme = Some(Arc::new(config));
+                Ok(())
+            }
+            "cert_reload_interval" => {
+                let interval = g3_yaml::humanize::as_duration(value)
+                    .context(format!("invalid humanize duration value for key {key}"))?;
+                self.cert_reload_interval = Some(interval).filter(|d| !d.is_zero());
+                Ok(())
+            }
+            "ocsp_stapling" | "enable_ocsp_stapling" => {
+                self.ocsp_stapling = g3_yaml::value::as_bool(value)?;
                 Ok(())
             }
             #[cfg(feature = "vendored-tongsuo")]
@@ -374,13 +454,19 @@ impl YamlMapCallback for OpensslHostConfig {
         if self.name.is_empty() {
             return Err(anyhow!("no name set"));
         }
-        #[cfg(not(feature = "vendored-tongsuo"))]
-        if self.cert_pairs.is_empty() {
-            return Err(anyhow!("no certificate set"));
-        }
-        #[cfg(feature = "vendored-tongsuo")]
-        if self.cert_pairs.is_empty() && self.tlcp_cert_pairs.is_empty() {
-            return Err(anyhow!("neither tls nor tlcp certificate set"));
+        if let Some(acme) = &self.acme {
+            if acme.challenge_type() == AcmeChallengeType::Http01 {
+                return Err(anyhow!("acme http-01 challenge is not supported"));
+            }
+        } else {
+            #[cfg(not(feature = "vendored-tongsuo"))]
+            if self.cert_pairs.is_empty() {
+                return Err(anyhow!("no certificate set"));
+            }
+            #[cfg(feature = "vendored-tongsuo")]
+            if self.cert_pairs.is_empty() && self.tlcp_cert_pairs.is_empty() {
+                return Err(anyhow!("neither tls nor tlcp certificate set"));
+            }
         }
         if self.backends.is_empty() {
             return Err(anyhow!("no backend service set"));
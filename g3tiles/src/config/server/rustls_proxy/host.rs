@@ -3,29 +3,37 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
-use rustls::server::WebPkiClientVerifier;
+use rustls::server::{ResolvesServerCert, WebPkiClientVerifier};
 use rustls::{RootCertStore, ServerConfig};
-use rustls_pki_types::CertificateDer;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
 use yaml_rust::Yaml;
 
+use g3_acme::{AcmeChallengeType, AcmeConfig};
 use g3_types::collection::NamedValue;
 use g3_types::limit::RateLimitQuotaConfig;
 use g3_types::metrics::NodeName;
 use g3_types::net::{
-    MultipleCertResolver, OpensslTicketKey, RollingTicketer, RustlsCertificatePair,
+    OpensslTicketKey, RollingTicketer, RustlsCertificatePair, RustlsCertificatePairBuilder,
     RustlsServerConfigExt, TcpSockSpeedLimitConfig,
 };
 use g3_types::route::AlpnMatch;
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
+use crate::module::cert::CertFilesStamp;
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct RustlsHostConfig {
     name: String,
     cert_pairs: Vec<RustlsCertificatePair>,
+    cert_pairs_source: Option<(Yaml, PathBuf)>,
+    pub(crate) acme: Option<Arc<AcmeConfig>>,
+    pub(crate) cert_reload_interval: Option<Duration>,
+    pub(crate) ocsp_stapling: bool,
     client_auth: bool,
     client_auth_certs: Vec<CertificateDer<'static>>,
     use_session_ticket: bool,
@@ -43,6 +51,10 @@ impl Default for RustlsHostConfig {
         RustlsHostConfig {
             name: String::new(),
             cert_pairs: Vec::with_capacity(1),
+            cert_pairs_source: None,
+            acme: None,
+            cert_reload_interval: None,
+            ocsp_stapling: false,
             client_auth: false,
             client_auth_certs: Vec::new(),
             use_session_ticket: true,
@@ -71,8 +83,59 @@ impl NamedValue for RustlsHostConfig {
 }
 
 impl RustlsHostConfig {
+    /// Get the configured cert pairs, and the one issued by ACME if it has been stored
+    pub(crate) fn load_cert_pairs(&self) -> anyhow::Result<Vec<RustlsCertificatePair>> {
+        let mut cert_pairs = self.cert_pairs.clone();
+        self.add_acme_cert_pair(&mut cert_pairs)?;
+        Ok(cert_pairs)
+    }
+
+    /// Get the modification time of the files the cert pairs are loaded from
+    pub(crate) fn cert_files_stamp(&self) -> CertFilesStamp {
+        CertFilesStamp::collect(self.cert_pairs_source.as_ref(), self.acme.as_deref())
+    }
+
+    /// Load the cert pairs again from the config source, so the changed files will take effect
+    pub(crate) fn reload_cert_pairs(&self) -> anyhow::Result<Vec<RustlsCertificatePair>> {
+        let mut cert_pairs = match &self.cert_pairs_source {
+            Some((value, lookup_dir)) => parse_cert_pairs(value, lookup_dir)?,
+            None => self.cert_pairs.clone(),
+        };
+        self.add_acme_cert_pair(&mut cert_pairs)?;
+        Ok(cert_pairs)
+    }
+
+    fn add_acme_cert_pair(
+        &self,
+        cert_pairs: &mut Vec<RustlsCertificatePair>,
+    ) -> anyhow::Result<()> {
+        let Some(acme) = &self.acme else {
+            return Ok(());
+        };
+        let Some((certs, key)) = acme.load_certificate()? else {
+            return Ok(());
+        };
+        let mut cert_ders = Vec::with_capacity(certs.len());
+        for (i, cert) in certs.iter().enumerate() {
+            let der = cert
+                .to_der()
+                .map_err(|e| anyhow!("failed to encode ACME certificate #{i}: {e}"))?;
+            cert_ders.push(CertificateDer::from(der));
+        }
+        let key_der = key
+            .private_key_to_pkcs8()
+            .map_err(|e| anyhow!("failed to encode ACME private key: {e}"))?;
+        let mut builder = RustlsCertificatePairBuilder::default();
+        builder.set_certs(cert_ders);
+        builder.set_key(PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_der)));
+        let pair = builder.build().context("invalid ACME cert pair")?;
+        cert_pairs.push(pair);
+        Ok(())
+    }
+
     pub(crate) fn build_tls_config(
         &self,
+        cert_resolver: Arc<dyn ResolvesServerCert>,
         tls_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Arc<ServerConfig>> {
         let config_builder = ServerConfig::builder();
@@ -102,13 +165,7 @@ impl RustlsHostConfig {
             config_builder.with_no_client_auth()
         };
 
-        let mut cert_resolver = MultipleCertResolver::with_capacity(self.cert_pairs.len());
-        for (i, pair) in self.cert_pairs.iter().enumerate() {
-            cert_resolver
-                .push_cert_pair(pair)
-                .context(format!("failed to add cert pair {i}"))?;
-        }
-        let mut config = config_builder.with_cert_resolver(Arc::new(cert_resolver));
+        let mut config = config_builder.with_cert_resolver(cert_resolver);
 
         config.set_session_cache(self.no_session_cache);
         config.set_session_ticketer(self.use_session_ticket, tls_ticketer)?;
@@ -123,6 +180,12 @@ impl RustlsHostConfig {
     }
 }
 
+fn parse_cert_pairs(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Vec<RustlsCertificatePair>> {
+    g3_yaml::value::as_list(value, |v| {
+        g3_yaml::value::as_rustls_certificate_pair(v, Some(lookup_dir))
+    })
+}
+
 impl YamlMapCallback for RustlsHostConfig {
     fn type_name(&self) -> &'static str {
         "RustlsHostConfig"
@@ -141,10 +204,26 @@ impl YamlMapCallback for RustlsHostConfig {
             }
             "cert_pairs" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
-                self.cert_pairs = g3_yaml::value::as_list(value, |v| {
-                    g3_yaml::value::as_rustls_certificate_pair(v, Some(lookup_dir))
-                })
-                .context(format!("invalid rustls cert pair list value for key {key}"))?;
+                self.cert_pairs = parse_cert_pairs(value, lookup_dir)
+                    .context(format!("invalid rustls cert pair list value for key {key}"))?;
+                self.cert_pairs_source = Some((value.clone(), lookup_dir.to_path_buf()));
+                Ok(())
+            }
+            "acme" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                let config = AcmeConfig::parse_yaml(value, Some(lookup_dir))
+                    .context(format!("invalid acme config value for key {key}"))?;
+                self.acme = Some(Arc::new(config));
+                Ok(())
+            }
+            "cert_reload_interval" => {
+                let interval = g3_yaml::humanize::as_duration(value)
+                    .context(format!("invalid humanize duration value for key {key}"))?;
+                self.cert_reload_interval = Some(interval).filter(|d| !d.is_zero());
+                Ok(())
+            }
+            "ocsp_stapling" | "enable_ocsp_stapling" => {
+                self.ocsp_stapling = g3_yaml::value::as_bool(value)?;
                 Ok(())
             }
             "enable_client_auth" => {
@@ -215,7 +294,11 @@ impl YamlMapCallback for RustlsHostConfig {
         if self.name.is_empty() {
             return Err(anyhow!("no name set"));
         }
-        if self.cert_pairs.is_empty() {
+        if let Some(acme) = &self.acme {
+            if acme.challenge_type() == AcmeChallengeType::Http01 {
+                return Err(anyhow!("acme http-01 challenge is not supported"));
+            }
+        } else if self.cert_pairs.is_empty() {
             return Err(anyhow!("no certificate set"));
         }
         if self.backends.is_empty() {
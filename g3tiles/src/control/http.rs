@@ -0,0 +1,114 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use log::debug;
+use serde_json::{Map, Value};
+
+use g3_daemon::control::{HttpControlError, HttpControlHandler, HttpControlResult};
+use g3_types::metrics::NodeName;
+
+const KIND_SERVER: &str = "servers";
+const KIND_DISCOVER: &str = "discovers";
+const KIND_BACKEND: &str = "backends";
+
+pub struct HttpController {}
+
+impl HttpController {
+    pub fn start() -> anyhow::Result<()> {
+        g3_daemon::control::HttpController::start(HttpControlImpl)
+    }
+
+    pub(super) async fn abort() {
+        debug!("aborting http controller");
+        g3_daemon::control::HttpController::abort().await;
+    }
+}
+
+struct HttpControlImpl;
+
+fn get_names(kind: &str) -> HttpControlResult<HashSet<NodeName>> {
+    match kind {
+        KIND_SERVER => Ok(crate::serve::get_names()),
+        KIND_DISCOVER => Ok(crate::discover::get_names()),
+        KIND_BACKEND => Ok(crate::backend::get_names()),
+        _ => Err(HttpControlError::unknown_kind(kind)),
+    }
+}
+
+fn check_name(kind: &str, name: &str) -> HttpControlResult<NodeName> {
+    let name = unsafe { NodeName::new_unchecked(name) };
+    if get_names(kind)?.contains(&name) {
+        Ok(name)
+    } else {
+        Err(HttpControlError::not_found(kind, name.as_str()))
+    }
+}
+
+#[async_trait]
+impl HttpControlHandler for HttpControlImpl {
+    fn version(&self) -> &'static str {
+        crate::build::VERSION
+    }
+
+    fn list(&self, kind: &str) -> HttpControlResult<Vec<String>> {
+        let mut names: Vec<String> = get_names(kind)?
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn status(&self, kind: &str, name: &str) -> HttpControlResult<Map<String, Value>> {
+        let name = check_name(kind, name)?;
+
+        let mut map = Map::new();
+        map.insert("name".to_string(), Value::from(name.as_str()));
+        match kind {
+            KIND_SERVER => {
+                let server = crate::serve::get_server(&name)?;
+                map.insert("type".to_string(), Value::from(server.r#type()));
+                if let Some(stats) = server.get_server_stats() {
+                    map.insert("online".to_string(), Value::from(stats.is_online()));
+                    map.insert(
+                        "alive_task_count".to_string(),
+                        Value::from(stats.alive_count()),
+                    );
+                    map.insert(
+                        "total_conn_count".to_string(),
+                        Value::from(stats.conn_total()),
+                    );
+                    map.insert(
+                        "total_task_count".to_string(),
+                        Value::from(stats.task_total()),
+                    );
+                }
+            }
+            KIND_BACKEND => {
+                let backend = crate::backend::get_backend(&name)?;
+                map.insert(
+                    "alive_connection".to_string(),
+                    Value::from(backend.alive_connection()),
+                );
+            }
+            _ => {}
+        }
+        Ok(map)
+    }
+
+    async fn reload(&self, kind: &str, name: &str) -> HttpControlResult<()> {
+        let name = check_name(kind, name)?.to_string();
+        let r = match kind {
+            KIND_SERVER => super::bridge::reload_server(name, None).await,
+            KIND_DISCOVER => super::bridge::reload_discover(name, None).await,
+            KIND_BACKEND => super::bridge::reload_backend(name, None).await,
+            _ => return Err(HttpControlError::unknown_kind(kind)),
+        };
+        r.map_err(HttpControlError::Failed)
+    }
+}
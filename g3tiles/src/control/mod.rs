@@ -14,4 +14,7 @@ pub use upgrade::UpgradeActor;
 mod local;
 pub use local::{DaemonController, UniqueController};
 
+mod http;
+pub use http::HttpController;
+
 pub mod capnp;
@@ -6,17 +6,14 @@
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use anyhow::anyhow;
 use tokio::sync::watch;
 use yaml_rust::Yaml;
 
+use g3_discover::DiscoverRegisterData;
 use g3_types::collection::WeightedValue;
 use g3_types::metrics::NodeName;
 
-use crate::config::discover::{AnyDiscoverConfig, DiscoverRegisterData};
-
-mod host_resolver;
-mod static_addr;
+use crate::config::discover::AnyDiscoverConfig;
 
 mod ops;
 pub use ops::load_all;
@@ -25,8 +22,7 @@ pub(crate) use ops::{get_discover, reload};
 mod registry;
 pub(crate) use registry::get_names;
 
-pub(crate) type DiscoveredData = Vec<WeightedValue<SocketAddr>>;
-pub(crate) type DiscoverResult = anyhow::Result<DiscoveredData>;
+pub(crate) type DiscoverResult = g3_discover::DiscoverResult<WeightedValue<SocketAddr>>;
 
 pub(crate) trait Discover {
     fn name(&self) -> &NodeName;
@@ -37,20 +33,32 @@ pub(crate) trait Discover {
         &self,
         data: &DiscoverRegisterData,
     ) -> anyhow::Result<watch::Receiver<DiscoverResult>> {
-        match data {
-            DiscoverRegisterData::Null => Err(anyhow!("no valid register data found")),
-            DiscoverRegisterData::Yaml(v) => self.register_yaml(v),
-            DiscoverRegisterData::Json(_) => {
-                todo!()
-            }
-        }
+        self.register_yaml(data.as_yaml()?)
     }
 }
 
 trait DiscoverInternal: Discover {
     fn _clone_config(&self) -> AnyDiscoverConfig;
-    fn _update_config_in_place(&self, config: AnyDiscoverConfig) -> anyhow::Result<()>;
 }
 
 pub(crate) type ArcDiscover = Arc<dyn Discover + Send + Sync>;
 type ArcDiscoverInternal = Arc<dyn DiscoverInternal + Send + Sync>;
+
+impl Discover for AnyDiscoverConfig {
+    fn name(&self) -> &NodeName {
+        AnyDiscoverConfig::name(self)
+    }
+
+    fn register_yaml(&self, data: &Yaml) -> anyhow::Result<watch::Receiver<DiscoverResult>> {
+        match self {
+            AnyDiscoverConfig::StaticAddr(c) => c.register_yaml(data),
+            AnyDiscoverConfig::HostResolver(c) => c.register_yaml(data),
+        }
+    }
+}
+
+impl DiscoverInternal for AnyDiscoverConfig {
+    fn _clone_config(&self) -> AnyDiscoverConfig {
+        self.clone()
+    }
+}
@@ -4,19 +4,18 @@
  */
 
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use log::debug;
 use tokio::sync::Mutex;
 
+use g3_discover::DiscoverConfigDiffAction;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
 use super::{ArcDiscover, registry};
-use crate::config::discover::{AnyDiscoverConfig, DiscoverConfigDiffAction};
-
-use super::host_resolver::HostResolverDiscover;
-use super::static_addr::StaticAddrDiscover;
+use crate::config::discover::AnyDiscoverConfig;
 
 static DISCOVER_OPS_LOCK: Mutex<()> = Mutex::const_new(());
 
@@ -118,20 +117,12 @@ async fn reload_unlocked(old: AnyDiscoverConfig, new: AnyDiscoverConfig) -> anyh
             debug!("discover {name} reload: will create a totally new one");
             spawn_new_unlocked(new).await
         }
-        DiscoverConfigDiffAction::UpdateInPlace => {
-            debug!("discover {name} reload: will update the existed in place");
-            registry::update_config_in_place(name, new)
-        }
     }
 }
 
 async fn spawn_new_unlocked(config: AnyDiscoverConfig) -> anyhow::Result<()> {
     let name = config.name().clone();
-    let discover = match config {
-        AnyDiscoverConfig::StaticAddr(c) => StaticAddrDiscover::new_obj(c),
-        AnyDiscoverConfig::HostResolver(c) => HostResolverDiscover::new_obj(c),
-    };
-    registry::add(name.clone(), discover);
+    registry::add(name.clone(), Arc::new(config));
     crate::backend::update_dependency_to_discover(&name, "spawned").await;
     Ok(())
 }
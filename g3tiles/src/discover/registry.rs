@@ -6,7 +6,6 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 
-use anyhow::anyhow;
 use foldhash::fast::FixedState;
 
 use g3_types::metrics::NodeName;
@@ -45,14 +44,3 @@ pub(super) fn get_config(name: &NodeName) -> Option<AnyDiscoverConfig> {
     let ht = RUNTIME_DISCOVER_REGISTRY.lock().unwrap();
     ht.get(name).map(|g| g._clone_config())
 }
-
-pub(super) fn update_config_in_place(
-    name: &NodeName,
-    config: AnyDiscoverConfig,
-) -> anyhow::Result<()> {
-    if let Some(discover) = get(name) {
-        discover._update_config_in_place(config)
-    } else {
-        Err(anyhow!("no discover with name {name} found"))
-    }
-}
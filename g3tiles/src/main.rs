@@ -110,6 +110,7 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
                 daemon_ctl.await;
             });
         }
+        g3tiles::control::HttpController::start().context("failed to start http controller")?;
         g3tiles::control::QuitActor::tokio_spawn_run();
 
         g3tiles::signal::register().context("failed to setup signal handler")?;
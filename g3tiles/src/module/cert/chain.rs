@@ -0,0 +1,75 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+use openssl::asn1::Asn1Time;
+use openssl::x509::X509;
+
+/// The parsed certificate chain of a local cert pair
+pub(crate) struct TlsCertChain {
+    leaf_der: Vec<u8>,
+    leaf: X509,
+    chain: Vec<X509>,
+    not_after: i64,
+    has_ocsp_responder: bool,
+}
+
+impl TlsCertChain {
+    pub(crate) fn parse<T: AsRef<[u8]>>(leaf_der: &[u8], chain_der: &[T]) -> anyhow::Result<Self> {
+        let leaf =
+            X509::from_der(leaf_der).map_err(|e| anyhow!("invalid leaf certificate: {e}"))?;
+        let mut chain = Vec::with_capacity(chain_der.len());
+        for (i, der) in chain_der.iter().enumerate() {
+            let cert = X509::from_der(der.as_ref())
+                .map_err(|e| anyhow!("invalid chain certificate #{i}: {e}"))?;
+            chain.push(cert);
+        }
+
+        let epoch = Asn1Time::from_unix(0).map_err(|e| anyhow!("failed to get epoch time: {e}"))?;
+        let diff = epoch
+            .diff(leaf.not_after())
+            .map_err(|e| anyhow!("invalid not after time: {e}"))?;
+        let not_after = i64::from(diff.days) * 86400 + i64::from(diff.secs);
+
+        let has_ocsp_responder = leaf
+            .ocsp_responders()
+            .map(|v| !v.is_empty())
+            .unwrap_or(false);
+
+        Ok(TlsCertChain {
+            leaf_der: leaf_der.to_vec(),
+            leaf,
+            chain,
+            not_after,
+            has_ocsp_responder,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn leaf_der(&self) -> &[u8] {
+        &self.leaf_der
+    }
+
+    #[inline]
+    pub(super) fn leaf(&self) -> &X509 {
+        &self.leaf
+    }
+
+    #[inline]
+    pub(super) fn chain(&self) -> &[X509] {
+        &self.chain
+    }
+
+    /// The unix timestamp of the expire time
+    #[inline]
+    pub(crate) fn not_after(&self) -> i64 {
+        self.not_after
+    }
+
+    #[inline]
+    pub(super) fn has_ocsp_responder(&self) -> bool {
+        self.has_ocsp_responder
+    }
+}
@@ -0,0 +1,16 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod chain;
+pub(crate) use chain::TlsCertChain;
+
+mod ocsp;
+pub(crate) use ocsp::OcspStapleCache;
+
+mod stamp;
+pub(crate) use stamp::CertFilesStamp;
+
+mod update;
+pub(crate) use update::{TlsCertHolder, spawn_cert_updater};
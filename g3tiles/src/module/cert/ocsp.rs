@@ -0,0 +1,207 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use log::{debug, warn};
+use openssl::ssl::SslContextBuilder;
+
+use g3_types::net::OpensslOcspStaple;
+
+use super::TlsCertChain;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+const FAILED_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Cache of the OCSP responses for local certificates, keyed by the DER of the leaf certificate
+#[derive(Default)]
+pub(crate) struct OcspStapleCache {
+    staples: ArcSwap<AHashMap<Vec<u8>, OpensslOcspStaple>>,
+    retry: Mutex<AHashMap<Vec<u8>, Instant>>,
+}
+
+impl OcspStapleCache {
+    pub(crate) fn get(&self, leaf_der: &[u8]) -> Option<OpensslOcspStaple> {
+        self.staples
+            .load()
+            .get(leaf_der)
+            .filter(|s| !s.is_expired())
+            .cloned()
+    }
+
+    /// Staple the cached OCSP response in the handshake if the client requested it
+    pub(crate) fn add_to_server_ssl_context(
+        self: &Arc<Self>,
+        ssl_builder: &mut SslContextBuilder,
+    ) -> anyhow::Result<()> {
+        let cache = self.clone();
+        ssl_builder
+            .set_status_callback(move |ssl| {
+                let Some(staple) = ssl
+                    .certificate()
+                    .and_then(|cert| cert.to_der().ok())
+                    .and_then(|der| cache.get(&der))
+                else {
+                    return Ok(false);
+                };
+                ssl.set_ocsp_status(staple.as_der())?;
+                Ok(true)
+            })
+            .map_err(|e| anyhow!("failed to set OCSP status callback: {e}"))
+    }
+
+    /// Fetch new OCSP responses for the certificates if needed.
+    /// This is a blocking call and should be run in a blocking thread.
+    /// Return true if the cached responses have been changed.
+    pub(crate) fn refresh(&self, host: &str, certs: &[TlsCertChain]) -> bool {
+        self.refresh_with(host, certs, |cert| {
+            OpensslOcspStaple::fetch(cert.leaf(), cert.chain(), FETCH_TIMEOUT)
+        })
+    }
+
+    fn refresh_with<F>(&self, host: &str, certs: &[TlsCertChain], fetch: F) -> bool
+    where
+        F: Fn(&TlsCertChain) -> anyhow::Result<OpensslOcspStaple>,
+    {
+        let old_staples = self.staples.load();
+        let mut retry = self.retry.lock().unwrap();
+        let now = Instant::now();
+
+        let mut changed = false;
+        let mut new_staples = AHashMap::with_capacity(certs.len());
+        for cert in certs {
+            if !cert.has_ocsp_responder() {
+                continue;
+            }
+            let key = cert.leaf_der();
+
+            let old_staple = old_staples.get(key).filter(|s| !s.is_expired());
+            let wait_retry = retry.get(key).map(|t| *t > now).unwrap_or(false);
+            if let Some(staple) = old_staple.filter(|s| wait_retry || !s.need_refresh()) {
+                new_staples.insert(key.to_vec(), staple.clone());
+                continue;
+            }
+            if wait_retry {
+                continue;
+            }
+
+            match fetch(cert) {
+                Ok(staple) => {
+                    if staple.is_good() {
+                        debug!("host {host}: got new OCSP response");
+                    } else {
+                        warn!("host {host}: the certificate has been revoked");
+                    }
+                    retry.remove(key);
+                    new_staples.insert(key.to_vec(), staple);
+                    changed = true;
+                }
+                Err(e) => {
+                    warn!("host {host}: failed to fetch OCSP response: {e:?}");
+                    retry.insert(key.to_vec(), now + FAILED_RETRY_INTERVAL);
+                    if let Some(staple) = old_staple {
+                        new_staples.insert(key.to_vec(), staple.clone());
+                    }
+                }
+            }
+        }
+        retry.retain(|k, _| certs.iter().any(|c| c.leaf_der() == k));
+
+        if changed || new_staples.len() != old_staples.len() {
+            self.staples.store(Arc::new(new_staples));
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    use openssl::x509::X509;
+
+    use g3_types::net::revocation_test_util::{CA_CERT, LEAF_CERT, OCSP_GOOD};
+
+    fn load_chain() -> TlsCertChain {
+        let leaf = X509::from_pem(LEAF_CERT).unwrap().to_der().unwrap();
+        let ca = X509::from_pem(CA_CERT).unwrap().to_der().unwrap();
+        TlsCertChain::parse(&leaf, &[ca]).unwrap()
+    }
+
+    fn good_staple(cert: &TlsCertChain) -> anyhow::Result<OpensslOcspStaple> {
+        OpensslOcspStaple::parse(OCSP_GOOD.to_vec(), cert.leaf(), &cert.chain()[0])
+    }
+
+    #[test]
+    fn retry_after_failure() {
+        let cache = OcspStapleCache::default();
+        let certs = [load_chain()];
+        let key = certs[0].leaf_der();
+        let count = Cell::new(0);
+        let fail = |_: &TlsCertChain| {
+            count.set(count.get() + 1);
+            Err(anyhow!("fetch failed"))
+        };
+
+        assert!(!cache.refresh_with("test", &certs, fail));
+        assert_eq!(count.get(), 1);
+        assert!(cache.get(key).is_none());
+
+        // no fetch before the retry time
+        assert!(!cache.refresh_with("test", &certs, fail));
+        assert_eq!(count.get(), 1);
+
+        cache
+            .retry
+            .lock()
+            .unwrap()
+            .insert(key.to_vec(), Instant::now() - Duration::from_secs(1));
+        assert!(!cache.refresh_with("test", &certs, fail));
+        assert_eq!(count.get(), 2);
+
+        // the retry time will be cleared after success
+        cache.retry.lock().unwrap().clear();
+        assert!(cache.refresh_with("test", &certs, good_staple));
+        assert!(cache.get(key).unwrap().is_good());
+        assert!(cache.retry.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn keep_until_refresh() {
+        let cache = OcspStapleCache::default();
+        let certs = [load_chain()];
+        let key = certs[0].leaf_der();
+
+        assert!(cache.refresh_with("test", &certs, good_staple));
+        assert!(cache.get(key).is_some());
+
+        // no refresh needed
+        let count = Cell::new(0);
+        let fetch = |cert: &TlsCertChain| {
+            count.set(count.get() + 1);
+            good_staple(cert)
+        };
+        assert!(!cache.refresh_with("test", &certs, fetch));
+        assert_eq!(count.get(), 0);
+        assert!(cache.get(key).is_some());
+
+        // the staple will be dropped if the certificate has been removed
+        cache
+            .retry
+            .lock()
+            .unwrap()
+            .insert(key.to_vec(), Instant::now());
+        assert!(cache.refresh_with("test", &[], fetch));
+        assert!(cache.get(key).is_none());
+        assert!(cache.retry.lock().unwrap().is_empty());
+    }
+}
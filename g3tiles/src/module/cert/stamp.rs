@@ -0,0 +1,111 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use yaml_rust::Yaml;
+
+use g3_acme::{AcmeConfig, AcmeStorage};
+
+/// The modification time of all the local files the certificates are loaded from
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct CertFilesStamp {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl CertFilesStamp {
+    /// Collect the files referenced in the cert pairs config value, and the ACME storage files.
+    /// This is a blocking call.
+    pub(crate) fn collect(source: Option<&(Yaml, PathBuf)>, acme: Option<&AcmeConfig>) -> Self {
+        let mut paths = Vec::new();
+        if let Some((value, lookup_dir)) = source {
+            collect_file_paths(value, lookup_dir, &mut paths);
+        }
+        if let Some(acme) = acme {
+            let storage = AcmeStorage::new(acme);
+            paths.push(storage.cert_file());
+            paths.push(storage.key_file());
+        }
+        paths.sort();
+        paths.dedup();
+
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, mtime)
+            })
+            .collect();
+        CertFilesStamp { files }
+    }
+}
+
+fn collect_file_paths(value: &Yaml, lookup_dir: &Path, paths: &mut Vec<PathBuf>) {
+    match value {
+        Yaml::String(s) => {
+            // skip inline PEM contents
+            if s.trim_start().starts_with("--") {
+                return;
+            }
+            if let Ok(path) = g3_yaml::value::as_file_path(value, lookup_dir, false) {
+                paths.push(path);
+            }
+        }
+        Yaml::Array(seq) => seq
+            .iter()
+            .for_each(|v| collect_file_paths(v, lookup_dir, paths)),
+        Yaml::Hash(map) => map
+            .values()
+            .for_each(|v| collect_file_paths(v, lookup_dir, paths)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    #[test]
+    fn collect() {
+        let dir = std::env::temp_dir().join(format!("g3tiles-cert-stamp-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let cert_file = dir.join("a.crt");
+        let key_file = dir.join("a.key");
+        File::create(&cert_file).unwrap();
+        File::create(&key_file).unwrap();
+
+        let value = yaml_rust::YamlLoader::load_from_str(
+            r#"
+            - certificate: a.crt
+              private_key: a.key
+            - certificate: "-----BEGIN CERTIFICATE-----"
+              private_key: not-existed.key
+            "#,
+        )
+        .unwrap()
+        .remove(0);
+        let source = (value, dir.clone());
+
+        let stamp = CertFilesStamp::collect(Some(&source), None);
+        assert_eq!(stamp.files.len(), 2);
+        assert_eq!(stamp.files[0].0, cert_file);
+        assert_eq!(stamp.files[1].0, key_file);
+        assert_eq!(stamp, CertFilesStamp::collect(Some(&source), None));
+
+        let f = File::options().write(true).open(&key_file).unwrap();
+        f.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert_ne!(stamp, CertFilesStamp::collect(Some(&source), None));
+
+        assert_eq!(
+            CertFilesStamp::collect(None, None),
+            CertFilesStamp::default()
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
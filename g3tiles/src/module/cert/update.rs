@@ -0,0 +1,125 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{info, warn};
+use tokio::time::{Instant, MissedTickBehavior};
+
+use super::{CertFilesStamp, OcspStapleCache, TlsCertChain};
+
+const OCSP_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(crate) trait TlsCertHolder: Send + Sync + 'static {
+    fn host_name(&self) -> &str;
+
+    /// Get the modification time of the certificate files. This is a blocking call.
+    fn cert_files_stamp(&self) -> CertFilesStamp;
+
+    /// Reload the cert pairs from the config source, and update the TLS context if changed.
+    /// This is a blocking call. Return true if the certificates have been changed.
+    fn reload_certs(&self) -> anyhow::Result<bool>;
+
+    fn cert_chains(&self) -> Arc<Vec<TlsCertChain>>;
+
+    fn ocsp_cache(&self) -> Option<&Arc<OcspStapleCache>>;
+
+    /// Called after the cached OCSP responses have been changed
+    fn ocsp_updated(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Spawn the background task to reload certificates and refresh OCSP responses.
+/// The task will quit after the holder has been dropped.
+pub(crate) fn spawn_cert_updater<T: TlsCertHolder>(
+    holder: &Arc<T>,
+    reload_interval: Option<Duration>,
+) {
+    let has_ocsp = holder.ocsp_cache().is_some();
+    let check_interval = match reload_interval {
+        Some(d) if has_ocsp => d.min(OCSP_CHECK_INTERVAL),
+        Some(d) => d,
+        None if has_ocsp => OCSP_CHECK_INTERVAL,
+        None => return,
+    };
+
+    let mut stamp = reload_interval.map(|_| holder.cert_files_stamp());
+    let holder = Arc::downgrade(holder);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(check_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut next_reload = reload_interval.map(|d| Instant::now() + d);
+
+        loop {
+            interval.tick().await;
+            let Some(holder) = holder.upgrade() else {
+                break;
+            };
+
+            let now = Instant::now();
+            let reload = match (next_reload, reload_interval) {
+                (Some(t), Some(d)) if t <= now => {
+                    next_reload = Some(now + d);
+                    true
+                }
+                _ => false,
+            };
+            if !reload && !has_ocsp {
+                continue;
+            }
+
+            let old_stamp = stamp.take();
+            match tokio::task::spawn_blocking(move || {
+                update_once(holder.as_ref(), reload, old_stamp)
+            })
+            .await
+            {
+                Ok(new_stamp) => stamp = new_stamp,
+                Err(e) => warn!("failed to run tls cert update job: {e}"),
+            }
+        }
+    });
+}
+
+/// Return the stamp of the certificate files which have been loaded successfully
+fn update_once<T: TlsCertHolder>(
+    holder: &T,
+    reload: bool,
+    mut stamp: Option<CertFilesStamp>,
+) -> Option<CertFilesStamp> {
+    let host = holder.host_name();
+    if reload {
+        // only reload if the files have been changed
+        let new_stamp = holder.cert_files_stamp();
+        if stamp.as_ref() != Some(&new_stamp) {
+            match holder.reload_certs() {
+                Ok(true) => {
+                    info!("host {host}: new certificates loaded");
+                    stamp = Some(new_stamp);
+                }
+                Ok(false) => stamp = Some(new_stamp),
+                Err(e) => warn!("host {host}: failed to reload certificates: {e:?}"),
+            }
+        }
+    }
+
+    refresh_ocsp(holder);
+    stamp
+}
+
+fn refresh_ocsp<T: TlsCertHolder>(holder: &T) {
+    let host = holder.host_name();
+    let Some(cache) = holder.ocsp_cache() else {
+        return;
+    };
+    if !cache.refresh(host, &holder.cert_chains()) {
+        return;
+    }
+    if let Err(e) = holder.ocsp_updated() {
+        warn!("host {host}: failed to update OCSP responses: {e:?}");
+    }
+}
@@ -6,3 +6,5 @@
 pub(crate) mod stream;
 
 pub(crate) mod keyless;
+
+pub(crate) mod cert;
@@ -59,6 +59,9 @@ pub(crate) trait Server: BaseServer + AcceptTcpServer + AcceptQuicServer {
     fn quit_policy(&self) -> &Arc<ServerQuitPolicy>;
 
     fn update_backend(&self, name: &NodeName);
+
+    /// Call `f` with the host name and the earliest expire time (unix timestamp) of its certificates
+    fn foreach_host_cert_expire(&self, _f: &mut dyn FnMut(&str, i64)) {}
 }
 
 trait ServerInternal: Server {
@@ -3,26 +3,119 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
-use arc_swap::ArcSwap;
+use anyhow::Context;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use governor::{RateLimiter, clock::DefaultClock, state::InMemoryState, state::NotKeyed};
 use openssl::ssl::SslContext;
 
+use g3_acme::AcmeCertHolder;
 use g3_types::collection::NamedValue;
 use g3_types::limit::{GaugeSemaphore, GaugeSemaphorePermit};
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslTicketKey, RollingTicketer};
+use g3_types::net::{OpensslCertificatePair, OpensslTicketKey, RollingTicketer};
 use g3_types::route::AlpnMatch;
 
 use crate::backend::ArcBackend;
 use crate::config::server::openssl_proxy::OpensslHostConfig;
+use crate::module::cert::{CertFilesStamp, OcspStapleCache, TlsCertChain, TlsCertHolder};
+
+struct OpensslHostCert {
+    config: Arc<OpensslHostConfig>,
+    ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    ocsp_cache: Option<Arc<OcspStapleCache>>,
+    cert_pairs: Mutex<Vec<OpensslCertificatePair>>,
+    cert_chains: ArcSwap<Vec<TlsCertChain>>,
+    ssl_context: ArcSwapOption<SslContext>,
+}
+
+impl OpensslHostCert {
+    fn build(
+        config: &Arc<OpensslHostConfig>,
+        ticketer: &Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        ocsp_cache: Option<Arc<OcspStapleCache>>,
+    ) -> anyhow::Result<Arc<Self>> {
+        let cert_pairs = config.load_cert_pairs()?;
+        let ssl_context =
+            config.build_ssl_context(&cert_pairs, ticketer.clone(), ocsp_cache.as_ref())?;
+        let cert_chains = parse_cert_chains(&cert_pairs)?;
+
+        let cert = Arc::new(OpensslHostCert {
+            config: config.clone(),
+            ticketer: ticketer.clone(),
+            ocsp_cache,
+            cert_pairs: Mutex::new(cert_pairs),
+            cert_chains: ArcSwap::from_pointee(cert_chains),
+            ssl_context: ArcSwapOption::from_pointee(ssl_context),
+        });
+        crate::module::cert::spawn_cert_updater(&cert, config.cert_reload_interval);
+        if let Some(acme) = &config.acme {
+            g3_acme::spawn_cert_updater(&cert, acme);
+        }
+        Ok(cert)
+    }
+}
+
+impl AcmeCertHolder for OpensslHostCert {
+    fn reload_acme_cert(&self) -> anyhow::Result<()> {
+        self.reload_certs().map(|_| ())
+    }
+}
+
+impl TlsCertHolder for OpensslHostCert {
+    fn host_name(&self) -> &str {
+        self.config.name()
+    }
+
+    fn cert_files_stamp(&self) -> CertFilesStamp {
+        self.config.cert_files_stamp()
+    }
+
+    fn reload_certs(&self) -> anyhow::Result<bool> {
+        let new_pairs = self.config.reload_cert_pairs()?;
+        let mut cert_pairs = self.cert_pairs.lock().unwrap();
+        if new_pairs.eq(&*cert_pairs) {
+            return Ok(false);
+        }
+
+        let ssl_context = self.config.build_ssl_context(
+            &new_pairs,
+            self.ticketer.clone(),
+            self.ocsp_cache.as_ref(),
+        )?;
+        let cert_chains = parse_cert_chains(&new_pairs)?;
+        self.cert_chains.store(Arc::new(cert_chains));
+        // existing connections will keep using the old context
+        self.ssl_context.store(ssl_context.map(Arc::new));
+        *cert_pairs = new_pairs;
+        Ok(true)
+    }
+
+    fn cert_chains(&self) -> Arc<Vec<TlsCertChain>> {
+        self.cert_chains.load_full()
+    }
+
+    fn ocsp_cache(&self) -> Option<&Arc<OcspStapleCache>> {
+        self.ocsp_cache.as_ref()
+    }
+}
+
+fn parse_cert_chains(cert_pairs: &[OpensslCertificatePair]) -> anyhow::Result<Vec<TlsCertChain>> {
+    let mut cert_chains = Vec::with_capacity(cert_pairs.len());
+    for (i, pair) in cert_pairs.iter().enumerate() {
+        let chain = TlsCertChain::parse(pair.leaf_cert_der(), pair.chain_certs_der())
+            .context(format!("invalid cert pair #{i}"))?;
+        cert_chains.push(chain);
+    }
+    Ok(cert_chains)
+}
 
 pub(crate) struct OpensslHost {
     pub(super) config: Arc<OpensslHostConfig>,
-    pub(super) ssl_context: Option<SslContext>,
+    cert: Arc<OpensslHostCert>,
     #[cfg(feature = "vendored-tongsuo")]
-    pub(super) tlcp_context: Option<SslContext>,
+    pub(super) tlcp_context: Option<Arc<SslContext>>,
     req_alive_sem: Option<GaugeSemaphore>,
     request_rate_limit: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     pub(crate) backends: Arc<ArcSwap<AlpnMatch<ArcBackend>>>,
@@ -33,9 +126,14 @@ impl OpensslHost {
         config: &Arc<OpensslHostConfig>,
         tls_ticketer: &Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
-        let ssl_context = config.build_ssl_context(tls_ticketer.clone())?;
+        let ocsp_cache = config
+            .ocsp_stapling
+            .then(|| Arc::new(OcspStapleCache::default()));
+        let cert = OpensslHostCert::build(config, tls_ticketer, ocsp_cache)?;
         #[cfg(feature = "vendored-tongsuo")]
-        let tlcp_context = config.build_tlcp_context(tls_ticketer.clone())?;
+        let tlcp_context = config
+            .build_tlcp_context(tls_ticketer.clone())?
+            .map(Arc::new);
 
         let backends = config.backends.build(crate::backend::get_or_insert_default);
 
@@ -47,7 +145,7 @@ impl OpensslHost {
 
         Ok(OpensslHost {
             config: config.clone(),
-            ssl_context,
+            cert,
             #[cfg(feature = "vendored-tongsuo")]
             tlcp_context,
             req_alive_sem,
@@ -61,9 +159,18 @@ impl OpensslHost {
         config: Arc<OpensslHostConfig>,
         tls_ticketer: &Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
-        let ssl_context = config.build_ssl_context(tls_ticketer.clone())?;
+        // keep the fetched OCSP responses
+        let ocsp_cache = config.ocsp_stapling.then(|| {
+            self.cert
+                .ocsp_cache
+                .clone()
+                .unwrap_or_else(|| Arc::new(OcspStapleCache::default()))
+        });
+        let cert = OpensslHostCert::build(&config, tls_ticketer, ocsp_cache)?;
         #[cfg(feature = "vendored-tongsuo")]
-        let tlcp_context = config.build_tlcp_context(tls_ticketer.clone())?;
+        let tlcp_context = config
+            .build_tlcp_context(tls_ticketer.clone())?
+            .map(Arc::new);
 
         let request_rate_limit = if let Some(quota) = &config.request_rate_limit {
             if let Some(old_limiter) = &self.request_rate_limit {
@@ -96,7 +203,7 @@ impl OpensslHost {
 
         let new_host = OpensslHost {
             config,
-            ssl_context,
+            cert,
             #[cfg(feature = "vendored-tongsuo")]
             tlcp_context,
             req_alive_sem,
@@ -107,6 +214,20 @@ impl OpensslHost {
         Ok(new_host)
     }
 
+    pub(super) fn ssl_context(&self) -> Option<Arc<SslContext>> {
+        self.cert.ssl_context.load_full()
+    }
+
+    /// Get the earliest expire time of the certificates, as unix timestamp
+    pub(super) fn cert_expire_time(&self) -> Option<i64> {
+        self.cert
+            .cert_chains
+            .load()
+            .iter()
+            .map(|c| c.not_after())
+            .min()
+    }
+
     pub(super) fn check_rate_limit(&self) -> Result<(), ()> {
         if let Some(limit) = &self.request_rate_limit {
             if limit.check().is_err() {
@@ -19,6 +19,7 @@ use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTc
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::IdleWheel;
 use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::collection::NamedValue;
 use g3_types::metrics::NodeName;
 use g3_types::net::{OpensslTicketKey, RollingTicketer};
 use g3_types::route::HostMatch;
@@ -319,4 +320,13 @@ impl Server for OpensslProxyServer {
             }
         }
     }
+
+    fn foreach_host_cert_expire(&self, f: &mut dyn FnMut(&str, i64)) {
+        let host_map = self.hosts.get_all_values();
+        for host in host_map.values() {
+            if let Some(expire) = host.cert_expire_time() {
+                f(host.name(), expire);
+            }
+        }
+    }
 }
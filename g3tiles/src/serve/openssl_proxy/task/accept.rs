@@ -21,7 +21,7 @@ use g3_dpi::parser::tls::{
 use g3_io_ext::{LimitedStream, OnceBufReader};
 use g3_openssl::{SslAcceptor, SslStream};
 use g3_types::limit::GaugeSemaphorePermit;
-use g3_types::net::{Host, TlsServerName};
+use g3_types::net::{Host, TlsAlpn, TlsServerName};
 use g3_types::route::HostMatch;
 
 use super::{CommonTaskContext, OpensslRelayTask};
@@ -32,6 +32,7 @@ pub(crate) struct OpensslAcceptTask {
     ctx: CommonTaskContext,
     hosts: Arc<HostMatch<Arc<OpensslHost>>>,
     alive_permit: Option<GaugeSemaphorePermit>,
+    acme_tls_alpn_context: Option<Arc<SslContext>>,
 }
 
 impl OpensslAcceptTask {
@@ -40,6 +41,7 @@ impl OpensslAcceptTask {
             ctx,
             hosts,
             alive_permit: None,
+            acme_tls_alpn_context: None,
         }
     }
 
@@ -73,6 +75,12 @@ impl OpensslAcceptTask {
                     }
                 };
 
+                if self.acme_tls_alpn_context.is_some() {
+                    // this is an ACME TLS-ALPN-01 validation connection, close after handshake
+                    let _ = ssl_stream.shutdown().await;
+                    return;
+                }
+
                 if ssl_stream.ssl().session_reused() {
                     // Quick ACK is needed with session resumption
                     self.ctx.cc_info.tcp_sock_try_quick_ack();
@@ -178,6 +186,7 @@ impl OpensslAcceptTask {
             Ok(Some(data)) => {
                 let sni = TlsServerName::from_extension_value(data)
                     .map_err(|_| anyhow!("invalid server name in tls client hello message"))?;
+                self.check_acme_tls_alpn(&ch, sni.as_ref());
                 let host = Host::from(sni);
                 let Some(host) = self.hosts.get(&host) else {
                     return Err(anyhow!("no tls config found for server named {host}"));
@@ -192,6 +201,17 @@ impl OpensslAcceptTask {
         }
     }
 
+    fn check_acme_tls_alpn(&mut self, ch: &ClientHello<'_>, server_name: &str) {
+        let Ok(Some(data)) = ch.get_ext(ExtensionType::ApplicationLayerProtocolNegotiation) else {
+            return;
+        };
+        let Ok(alpn) = TlsAlpn::from_extension_value(data) else {
+            return;
+        };
+        self.acme_tls_alpn_context =
+            g3_acme::openssl_tls_alpn_context(server_name, &alpn).map(Arc::new);
+    }
+
     async fn handshake<S>(
         &mut self,
         host: &OpensslHost,
@@ -207,13 +227,15 @@ impl OpensslAcceptTask {
             .acquire_request_semaphore()
             .map_err(|_| anyhow!("host level alive limit reached"))?;
 
-        let ssl_context = if legacy_version.is_tlcp() {
+        let ssl_context = if self.acme_tls_alpn_context.is_some() {
+            self.acme_tls_alpn_context.clone()
+        } else if legacy_version.is_tlcp() {
             #[cfg(not(feature = "vendored-tongsuo"))]
             return Err(anyhow!("tlcp protocol is not supported"));
             #[cfg(feature = "vendored-tongsuo")]
-            host.tlcp_context.as_ref()
+            host.tlcp_context.clone()
         } else {
-            host.ssl_context.as_ref()
+            host.ssl_context()
         };
         let Some(ssl_context) = ssl_context else {
             return Err(anyhow!(
@@ -223,7 +245,7 @@ impl OpensslAcceptTask {
         };
 
         let ssl = self
-            .build_ssl(ssl_context)
+            .build_ssl(&ssl_context)
             .map_err(|e| anyhow!("failed to create SSL instance: {e}"))?;
         let acceptor = SslAcceptor::new(ssl, stream, self.ctx.server_config.accept_timeout)
             .map_err(|e| anyhow!("failed to create new ssl acceptor: {e}"))?;
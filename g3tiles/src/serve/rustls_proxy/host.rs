@@ -3,24 +3,154 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use anyhow::Context;
 use arc_swap::ArcSwap;
 use governor::{RateLimiter, clock::DefaultClock, state::InMemoryState, state::NotKeyed};
 use rustls::ServerConfig;
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
 
+use g3_acme::AcmeCertHolder;
 use g3_types::collection::NamedValue;
 use g3_types::limit::{GaugeSemaphore, GaugeSemaphorePermit};
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslTicketKey, RollingTicketer};
+use g3_types::net::{
+    MultipleCertResolver, OpensslTicketKey, RollingTicketer, RustlsCertificatePair,
+};
 use g3_types::route::AlpnMatch;
 
 use crate::backend::ArcBackend;
 use crate::config::server::rustls_proxy::RustlsHostConfig;
+use crate::module::cert::{CertFilesStamp, OcspStapleCache, TlsCertChain, TlsCertHolder};
+
+/// Cert resolver which can be replaced at runtime
+#[derive(Debug)]
+struct RustlsHostCertResolver {
+    inner: ArcSwap<MultipleCertResolver>,
+}
+
+impl ResolvesServerCert for RustlsHostCertResolver {
+    fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        self.inner.load().resolve(client_hello)
+    }
+}
+
+struct RustlsHostCert {
+    config: Arc<RustlsHostConfig>,
+    ocsp_cache: Option<Arc<OcspStapleCache>>,
+    cert_pairs: Mutex<Vec<RustlsCertificatePair>>,
+    cert_chains: ArcSwap<Vec<TlsCertChain>>,
+    resolver: Arc<RustlsHostCertResolver>,
+}
+
+impl RustlsHostCert {
+    fn build(
+        config: &Arc<RustlsHostConfig>,
+        ocsp_cache: Option<Arc<OcspStapleCache>>,
+    ) -> anyhow::Result<Arc<Self>> {
+        let cert_pairs = config.load_cert_pairs()?;
+        let resolver = build_cert_resolver(&cert_pairs, ocsp_cache.as_deref())?;
+        let cert_chains = parse_cert_chains(&cert_pairs)?;
+
+        let cert = Arc::new(RustlsHostCert {
+            config: config.clone(),
+            ocsp_cache,
+            cert_pairs: Mutex::new(cert_pairs),
+            cert_chains: ArcSwap::from_pointee(cert_chains),
+            resolver: Arc::new(RustlsHostCertResolver {
+                inner: ArcSwap::from_pointee(resolver),
+            }),
+        });
+        crate::module::cert::spawn_cert_updater(&cert, config.cert_reload_interval);
+        if let Some(acme) = &config.acme {
+            g3_acme::spawn_cert_updater(&cert, acme);
+        }
+        Ok(cert)
+    }
+}
+
+impl AcmeCertHolder for RustlsHostCert {
+    fn reload_acme_cert(&self) -> anyhow::Result<()> {
+        self.reload_certs().map(|_| ())
+    }
+}
+
+impl TlsCertHolder for RustlsHostCert {
+    fn host_name(&self) -> &str {
+        self.config.name()
+    }
+
+    fn cert_files_stamp(&self) -> CertFilesStamp {
+        self.config.cert_files_stamp()
+    }
+
+    fn reload_certs(&self) -> anyhow::Result<bool> {
+        let new_pairs = self.config.reload_cert_pairs()?;
+        let mut cert_pairs = self.cert_pairs.lock().unwrap();
+        if new_pairs.eq(&*cert_pairs) {
+            return Ok(false);
+        }
+
+        let resolver = build_cert_resolver(&new_pairs, self.ocsp_cache.as_deref())?;
+        let cert_chains = parse_cert_chains(&new_pairs)?;
+        self.cert_chains.store(Arc::new(cert_chains));
+        self.resolver.inner.store(Arc::new(resolver));
+        *cert_pairs = new_pairs;
+        Ok(true)
+    }
+
+    fn cert_chains(&self) -> Arc<Vec<TlsCertChain>> {
+        self.cert_chains.load_full()
+    }
+
+    fn ocsp_cache(&self) -> Option<&Arc<OcspStapleCache>> {
+        self.ocsp_cache.as_ref()
+    }
+
+    fn ocsp_updated(&self) -> anyhow::Result<()> {
+        let cert_pairs = self.cert_pairs.lock().unwrap();
+        let resolver = build_cert_resolver(&cert_pairs, self.ocsp_cache.as_deref())?;
+        self.resolver.inner.store(Arc::new(resolver));
+        Ok(())
+    }
+}
+
+fn build_cert_resolver(
+    cert_pairs: &[RustlsCertificatePair],
+    ocsp_cache: Option<&OcspStapleCache>,
+) -> anyhow::Result<MultipleCertResolver> {
+    let mut resolver = MultipleCertResolver::with_capacity(cert_pairs.len());
+    for (i, pair) in cert_pairs.iter().enumerate() {
+        let ocsp = ocsp_cache
+            .zip(pair.certs_ref().first())
+            .and_then(|(cache, leaf)| cache.get(leaf.as_ref()))
+            .map(|staple| staple.as_der().to_vec());
+        resolver
+            .push_cert_pair_with_ocsp(pair, ocsp)
+            .context(format!("failed to add cert pair {i}"))?;
+    }
+    Ok(resolver)
+}
+
+fn parse_cert_chains(cert_pairs: &[RustlsCertificatePair]) -> anyhow::Result<Vec<TlsCertChain>> {
+    let mut cert_chains = Vec::with_capacity(cert_pairs.len());
+    for (i, pair) in cert_pairs.iter().enumerate() {
+        let Some((leaf, chain)) = pair.certs_ref().split_first() else {
+            continue;
+        };
+        let chain =
+            TlsCertChain::parse(leaf.as_ref(), chain).context(format!("invalid cert pair #{i}"))?;
+        cert_chains.push(chain);
+    }
+    Ok(cert_chains)
+}
 
 pub(crate) struct RustlsHost {
     pub(super) config: Arc<RustlsHostConfig>,
     pub(super) tls_config: Arc<ServerConfig>,
+    cert: Arc<RustlsHostCert>,
     req_alive_sem: Option<GaugeSemaphore>,
     request_rate_limit: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     pub(crate) backends: Arc<ArcSwap<AlpnMatch<ArcBackend>>>,
@@ -31,7 +161,11 @@ impl RustlsHost {
         config: &Arc<RustlsHostConfig>,
         tls_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
-        let tls_config = config.build_tls_config(tls_ticketer)?;
+        let ocsp_cache = config
+            .ocsp_stapling
+            .then(|| Arc::new(OcspStapleCache::default()));
+        let cert = RustlsHostCert::build(config, ocsp_cache)?;
+        let tls_config = config.build_tls_config(cert.resolver.clone(), tls_ticketer)?;
 
         let backends = config.backends.build(crate::backend::get_or_insert_default);
 
@@ -44,6 +178,7 @@ impl RustlsHost {
         Ok(RustlsHost {
             config: config.clone(),
             tls_config,
+            cert,
             req_alive_sem,
             request_rate_limit,
             backends: Arc::new(ArcSwap::from_pointee(backends)),
@@ -55,7 +190,15 @@ impl RustlsHost {
         config: Arc<RustlsHostConfig>,
         tls_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
-        let tls_config = config.build_tls_config(tls_ticketer)?;
+        // keep the fetched OCSP responses
+        let ocsp_cache = config.ocsp_stapling.then(|| {
+            self.cert
+                .ocsp_cache
+                .clone()
+                .unwrap_or_else(|| Arc::new(OcspStapleCache::default()))
+        });
+        let cert = RustlsHostCert::build(&config, ocsp_cache)?;
+        let tls_config = config.build_tls_config(cert.resolver.clone(), tls_ticketer)?;
 
         let request_rate_limit = if let Some(quota) = &config.request_rate_limit {
             if let Some(old_limiter) = &self.request_rate_limit {
@@ -89,6 +232,7 @@ impl RustlsHost {
         let new_host = RustlsHost {
             config,
             tls_config,
+            cert,
             req_alive_sem,
             request_rate_limit,
             backends: self.backends.clone(), // use the old container
@@ -97,6 +241,16 @@ impl RustlsHost {
         Ok(new_host)
     }
 
+    /// Get the earliest expire time of the certificates, as unix timestamp
+    pub(super) fn cert_expire_time(&self) -> Option<i64> {
+        self.cert
+            .cert_chains
+            .load()
+            .iter()
+            .map(|c| c.not_after())
+            .min()
+    }
+
     pub(super) fn check_rate_limit(&self) -> Result<(), ()> {
         if let Some(limit) = &self.request_rate_limit {
             if limit.check().is_err() {
@@ -108,6 +108,17 @@ impl RustlsAcceptTask {
 
                 let host = self.get_host(&client_hello, hosts)?;
 
+                if let Some(tls_config) = g3_acme::rustls_tls_alpn_config(&client_hello) {
+                    // this is an ACME TLS-ALPN-01 validation connection, close after handshake
+                    let accept = d.into_stream(tls_config);
+                    if let Ok(Ok(mut s)) =
+                        tokio::time::timeout(host.config.accept_timeout, accept).await
+                    {
+                        let _ = s.shutdown().await;
+                    }
+                    return None;
+                }
+
                 if host.check_rate_limit().is_err() {
                     return None;
                 }
@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use foldhash::fast::FixedState;
 
@@ -24,6 +25,9 @@ const METRIC_NAME_SERVER_IO_IN_BYTES: &str = "server.traffic.in.bytes";
 const METRIC_NAME_SERVER_IO_IN_PACKETS: &str = "server.traffic.in.packets";
 const METRIC_NAME_SERVER_IO_OUT_BYTES: &str = "server.traffic.out.bytes";
 const METRIC_NAME_SERVER_IO_OUT_PACKETS: &str = "server.traffic.out.packets";
+const METRIC_NAME_SERVER_TLS_CERT_EXPIRE: &str = "server.tls.cert.expire";
+
+const TAG_KEY_HOST: &str = "host";
 
 type ServerStatsValue = (ArcServerStats, ServerSnapshot);
 type ListenStatsValue = (Arc<ListenStats>, ListenSnapshot);
@@ -79,6 +83,38 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(stats) > 1
     });
+    drop(listen_stats_map);
+
+    emit_cert_stats(client);
+}
+
+fn emit_cert_stats(client: &mut StatsdClient) {
+    let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+        return;
+    };
+    let now = now.as_secs() as i64;
+
+    crate::serve::foreach_server(|_, server| {
+        let Some(stats) = server.get_server_stats() else {
+            return;
+        };
+        let mut common_tags = StatsdTagGroup::default();
+        common_tags.add_server_tags(stats.name(), stats.is_online(), stats.stat_id());
+        if let Some(tags) = stats.load_extra_tags() {
+            common_tags.add_static_tags(&tags);
+        }
+
+        server.foreach_host_cert_expire(&mut |host, expire| {
+            client
+                .gauge_with_tags(
+                    METRIC_NAME_SERVER_TLS_CERT_EXPIRE,
+                    expire - now,
+                    &common_tags,
+                )
+                .with_tag(TAG_KEY_HOST, host)
+                .send();
+        });
+    });
 }
 
 fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &mut ServerSnapshot) {
@@ -0,0 +1,261 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use anyhow::anyhow;
+use openssl::asn1::{Asn1Integer, Asn1Object, Asn1OctetString, Asn1Time};
+use openssl::bn::{BigNum, MsbOption};
+use openssl::hash::{MessageDigest, hash};
+use openssl::pkey::{PKey, Private};
+use openssl::x509::extension::SubjectAlternativeName;
+use openssl::x509::{X509, X509Builder, X509Extension, X509NameBuilder};
+
+use crate::AcmeChallengeType;
+
+/// The ALPN protocol name used by the TLS-ALPN-01 challenge, see RFC 8737
+pub const ACME_TLS_ALPN_PROTOCOL: &str = "acme-tls/1";
+
+/// The url path prefix used by the HTTP-01 challenge, see RFC 8555 Section 8.3
+pub const ACME_HTTP_CHALLENGE_PATH_PREFIX: &str = "/.well-known/acme-challenge/";
+
+/// the id-pe-acmeIdentifier extension OID
+const ACME_IDENTIFIER_OID: &str = "1.3.6.1.5.5.7.1.31";
+
+/// The self-signed certificate which should be presented in the TLS-ALPN-01 challenge handshake
+pub struct AcmeTlsAlpnCert {
+    cert: X509,
+    key: PKey<Private>,
+}
+
+impl AcmeTlsAlpnCert {
+    fn new(domain: &str, key_authorization: &str) -> anyhow::Result<Self> {
+        let key = crate::key::new_ec256_key()?;
+
+        let mut builder =
+            X509Builder::new().map_err(|e| anyhow!("failed to create x509 builder: {e}"))?;
+        builder
+            .set_version(2)
+            .map_err(|e| anyhow!("failed to set x509 version: {e}"))?;
+        let mut serial = BigNum::new().map_err(|e| anyhow!("failed to create bn: {e}"))?;
+        serial
+            .rand(128, MsbOption::MAYBE_ZERO, false)
+            .map_err(|e| anyhow!("failed to generate serial number: {e}"))?;
+        let serial =
+            Asn1Integer::from_bn(&serial).map_err(|e| anyhow!("invalid serial number: {e}"))?;
+        builder
+            .set_serial_number(&serial)
+            .map_err(|e| anyhow!("failed to set serial number: {e}"))?;
+
+        let mut name = X509NameBuilder::new().map_err(|e| anyhow!("failed to create name: {e}"))?;
+        name.append_entry_by_text("CN", domain)
+            .map_err(|e| anyhow!("failed to set common name: {e}"))?;
+        let name = name.build();
+        builder
+            .set_subject_name(&name)
+            .map_err(|e| anyhow!("failed to set subject name: {e}"))?;
+        builder
+            .set_issuer_name(&name)
+            .map_err(|e| anyhow!("failed to set issuer name: {e}"))?;
+
+        let not_before =
+            Asn1Time::days_from_now(0).map_err(|e| anyhow!("failed to get time: {e}"))?;
+        let not_after =
+            Asn1Time::days_from_now(7).map_err(|e| anyhow!("failed to get time: {e}"))?;
+        builder
+            .set_not_before(&not_before)
+            .map_err(|e| anyhow!("failed to set not before: {e}"))?;
+        builder
+            .set_not_after(&not_after)
+            .map_err(|e| anyhow!("failed to set not after: {e}"))?;
+        builder
+            .set_pubkey(&key)
+            .map_err(|e| anyhow!("failed to set public key: {e}"))?;
+
+        let san = SubjectAlternativeName::new()
+            .dns(domain)
+            .build(&builder.x509v3_context(None, None))
+            .map_err(|e| anyhow!("failed to build SAN extension: {e}"))?;
+        builder
+            .append_extension(san)
+            .map_err(|e| anyhow!("failed to add SAN extension: {e}"))?;
+
+        // the extension value is the DER encoded OCTET STRING of the SHA-256 digest
+        let digest = hash(MessageDigest::sha256(), key_authorization.as_bytes())
+            .map_err(|e| anyhow!("failed to hash key authorization: {e}"))?;
+        let mut value = Vec::with_capacity(2 + digest.len());
+        value.push(0x04);
+        value.push(digest.len() as u8);
+        value.extend_from_slice(&digest);
+        let oid = Asn1Object::from_str(ACME_IDENTIFIER_OID)
+            .map_err(|e| anyhow!("invalid acmeIdentifier oid: {e}"))?;
+        let value = Asn1OctetString::new_from_bytes(&value)
+            .map_err(|e| anyhow!("failed to create octet string: {e}"))?;
+        let ext = X509Extension::new_from_der(&oid, true, &value)
+            .map_err(|e| anyhow!("failed to build acmeIdentifier extension: {e}"))?;
+        builder
+            .append_extension(ext)
+            .map_err(|e| anyhow!("failed to add acmeIdentifier extension: {e}"))?;
+
+        builder
+            .sign(&key, MessageDigest::sha256())
+            .map_err(|e| anyhow!("failed to sign the certificate: {e}"))?;
+        Ok(AcmeTlsAlpnCert {
+            cert: builder.build(),
+            key,
+        })
+    }
+
+    #[inline]
+    pub fn certificate(&self) -> &X509 {
+        &self.cert
+    }
+
+    #[inline]
+    pub fn private_key(&self) -> &PKey<Private> {
+        &self.key
+    }
+}
+
+static GLOBAL_STORE: LazyLock<Arc<AcmeChallengeStore>> =
+    LazyLock::new(|| Arc::new(AcmeChallengeStore::default()));
+
+/// The store of the pending challenge responses, which should be shared with the servers
+#[derive(Default)]
+pub struct AcmeChallengeStore {
+    http: Mutex<HashMap<String, String>>,
+    tls_alpn: Mutex<HashMap<String, Arc<AcmeTlsAlpnCert>>>,
+}
+
+impl AcmeChallengeStore {
+    /// The store shared by all servers in this process, so the HTTP-01 challenge
+    /// for a tls port can be responded by another http server
+    pub fn global() -> &'static Arc<AcmeChallengeStore> {
+        &GLOBAL_STORE
+    }
+
+    /// Get the key authorization for the HTTP-01 challenge token
+    pub fn get_http_key_authorization(&self, token: &str) -> Option<String> {
+        self.http.lock().unwrap().get(token).cloned()
+    }
+
+    /// Get the HTTP-01 challenge response body for the request path
+    pub fn get_http_response(&self, path: &str) -> Option<String> {
+        let token = path.strip_prefix(ACME_HTTP_CHALLENGE_PATH_PREFIX)?;
+        self.get_http_key_authorization(token)
+    }
+
+    /// Get the TLS-ALPN-01 challenge certificate for the domain
+    pub fn get_tls_alpn_cert(&self, domain: &str) -> Option<Arc<AcmeTlsAlpnCert>> {
+        let tls_alpn = self.tls_alpn.lock().unwrap();
+        if tls_alpn.is_empty() {
+            return None;
+        }
+        tls_alpn.get(&domain.to_lowercase()).cloned()
+    }
+
+    pub(crate) fn add(
+        &self,
+        challenge_type: AcmeChallengeType,
+        domain: &str,
+        token: &str,
+        key_authorization: String,
+    ) -> anyhow::Result<AcmeChallengeGuard<'_>> {
+        let key = match challenge_type {
+            AcmeChallengeType::Http01 => {
+                self.http
+                    .lock()
+                    .unwrap()
+                    .insert(token.to_string(), key_authorization);
+                token.to_string()
+            }
+            AcmeChallengeType::TlsAlpn01 => {
+                let domain = domain.to_lowercase();
+                let cert = AcmeTlsAlpnCert::new(&domain, &key_authorization)?;
+                self.tls_alpn
+                    .lock()
+                    .unwrap()
+                    .insert(domain.clone(), Arc::new(cert));
+                domain
+            }
+        };
+        Ok(AcmeChallengeGuard {
+            store: self,
+            challenge_type,
+            key,
+        })
+    }
+}
+
+/// Remove the challenge response from the store on drop
+pub(crate) struct AcmeChallengeGuard<'a> {
+    store: &'a AcmeChallengeStore,
+    challenge_type: AcmeChallengeType,
+    key: String,
+}
+
+impl Drop for AcmeChallengeGuard<'_> {
+    fn drop(&mut self) {
+        match self.challenge_type {
+            AcmeChallengeType::Http01 => {
+                self.store.http.lock().unwrap().remove(&self.key);
+            }
+            AcmeChallengeType::TlsAlpn01 => {
+                self.store.tls_alpn.lock().unwrap().remove(&self.key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_challenge() {
+        let store = AcmeChallengeStore::default();
+        let guard = store
+            .add(
+                AcmeChallengeType::Http01,
+                "example.net",
+                "token",
+                "token.thumbprint".to_string(),
+            )
+            .unwrap();
+        assert_eq!(
+            store
+                .get_http_response("/.well-known/acme-challenge/token")
+                .unwrap(),
+            "token.thumbprint"
+        );
+        assert!(store.get_http_response("/token").is_none());
+        drop(guard);
+        assert!(store.get_http_key_authorization("token").is_none());
+    }
+
+    #[test]
+    fn tls_alpn_challenge() {
+        let store = AcmeChallengeStore::default();
+        let guard = store
+            .add(
+                AcmeChallengeType::TlsAlpn01,
+                "Example.net",
+                "token",
+                "token.thumbprint".to_string(),
+            )
+            .unwrap();
+        let cert = store.get_tls_alpn_cert("example.NET").unwrap();
+        let der = cert.certificate().to_der().unwrap();
+        let digest = hash(MessageDigest::sha256(), b"token.thumbprint").unwrap();
+        let mut ext_value = vec![0x04, 0x20];
+        ext_value.extend_from_slice(&digest);
+        assert!(der.windows(ext_value.len()).any(|w| w == ext_value));
+        let names = cert.certificate().subject_alt_names().unwrap();
+        assert_eq!(names.get(0).unwrap().dnsname(), Some("example.net"));
+        drop(guard);
+        assert!(store.get_tls_alpn_cert("example.net").is_none());
+    }
+}
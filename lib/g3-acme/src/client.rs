@@ -0,0 +1,611 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use base64::prelude::*;
+use log::debug;
+use openssl::hash::MessageDigest;
+use openssl::pkey::{PKey, Private};
+use openssl::stack::Stack;
+use openssl::x509::extension::SubjectAlternativeName;
+use openssl::x509::{X509NameBuilder, X509ReqBuilder};
+use serde_json::Value;
+use tokio::time::Instant;
+use url::Url;
+
+use crate::http::{
+    AcmeHttpClient, AcmeHttpResponse, CONTENT_TYPE_JOSE_JSON, CONTENT_TYPE_PEM_CHAIN,
+};
+use crate::{AcmeAccountKey, AcmeChallengeStore, AcmeChallengeType, AcmeConfig};
+
+const BAD_NONCE_MAX_RETRIES: usize = 3;
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+fn get_str<'a>(v: &'a Value, key: &str) -> anyhow::Result<&'a str> {
+    v.get(key)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("no valid {key} field found"))
+}
+
+fn get_url(v: &Value, key: &str) -> anyhow::Result<Url> {
+    let s = get_str(v, key)?;
+    Url::parse(s).map_err(|e| anyhow!("invalid url {s} for field {key}: {e}"))
+}
+
+pub(crate) struct AcmeCertificate {
+    pub(crate) cert_pem: Vec<u8>,
+    pub(crate) key_pem: Vec<u8>,
+}
+
+struct AcmeDirectory {
+    new_nonce: Url,
+    new_account: Url,
+    new_order: Url,
+}
+
+impl AcmeDirectory {
+    fn parse(v: &Value) -> anyhow::Result<Self> {
+        Ok(AcmeDirectory {
+            new_nonce: get_url(v, "newNonce")?,
+            new_account: get_url(v, "newAccount")?,
+            new_order: get_url(v, "newOrder")?,
+        })
+    }
+}
+
+pub(crate) struct AcmeClient {
+    http: AcmeHttpClient,
+    key: AcmeAccountKey,
+    directory: AcmeDirectory,
+    account: String,
+    nonce: Option<String>,
+    validation_timeout: Duration,
+}
+
+impl AcmeClient {
+    /// Fetch the directory and register (or find) the account for the key
+    pub(crate) async fn new(config: &AcmeConfig, key: AcmeAccountKey) -> anyhow::Result<Self> {
+        let tls_client = config
+            .tls_client()
+            .build()
+            .context("failed to build tls client")?;
+        let http = AcmeHttpClient::new(tls_client, config.request_timeout);
+
+        let rsp = http
+            .get(config.directory_url())
+            .await
+            .context("failed to fetch the directory")?;
+        if !rsp.is_success() {
+            return Err(rsp.problem().context("failed to fetch the directory"));
+        }
+        let directory = AcmeDirectory::parse(&rsp.json()?).context("invalid directory object")?;
+
+        let mut client = AcmeClient {
+            http,
+            key,
+            directory,
+            account: String::new(),
+            nonce: None,
+            validation_timeout: config.validation_timeout,
+        };
+        client.register_account(config).await?;
+        Ok(client)
+    }
+
+    #[inline]
+    pub(crate) fn account(&self) -> &str {
+        &self.account
+    }
+
+    async fn register_account(&mut self, config: &AcmeConfig) -> anyhow::Result<()> {
+        let payload = serde_json::json!({
+            "termsOfServiceAgreed": config.terms_of_service_agreed(),
+            "contact": config.contacts(),
+        });
+        let url = self.directory.new_account.clone();
+        let rsp = self
+            .post(&url, Some(&payload), CONTENT_TYPE_JOSE_JSON)
+            .await
+            .context("failed to register account")?;
+        let Some(location) = rsp.location() else {
+            return Err(anyhow!("no account url returned"));
+        };
+        self.account = location.to_string();
+        debug!("using ACME account {}", self.account);
+        Ok(())
+    }
+
+    async fn new_nonce(&mut self) -> anyhow::Result<String> {
+        if let Some(nonce) = self.nonce.take() {
+            return Ok(nonce);
+        }
+        let rsp = self
+            .http
+            .head(&self.directory.new_nonce)
+            .await
+            .context("failed to get new nonce")?;
+        rsp.replay_nonce()
+            .map(|s| s.to_string())
+            .ok_or_else(|| anyhow!("no nonce returned in newNonce response"))
+    }
+
+    async fn post(
+        &mut self,
+        url: &Url,
+        payload: Option<&Value>,
+        accept: &str,
+    ) -> anyhow::Result<AcmeHttpResponse> {
+        let mut retry = 0;
+        loop {
+            let nonce = self.new_nonce().await?;
+            let kid = Some(self.account.as_str()).filter(|s| !s.is_empty());
+            let body = self.key.sign(url.as_str(), &nonce, kid, payload)?;
+            let rsp = self.http.post(url, &body, accept).await?;
+            self.nonce = rsp.replay_nonce().map(|s| s.to_string());
+            if rsp.is_success() {
+                return Ok(rsp);
+            }
+
+            retry += 1;
+            let bad_nonce = rsp
+                .problem_type()
+                .map(|t| t == "urn:ietf:params:acme:error:badNonce")
+                .unwrap_or(false);
+            if !bad_nonce || retry > BAD_NONCE_MAX_RETRIES {
+                return Err(rsp.problem());
+            }
+        }
+    }
+
+    async fn post_as_get(&mut self, url: &Url) -> anyhow::Result<AcmeHttpResponse> {
+        self.post(url, None, CONTENT_TYPE_JOSE_JSON).await
+    }
+
+    /// Poll the resource until it's status is `target`
+    async fn poll_status(
+        &mut self,
+        url: &Url,
+        target: &str,
+        deadline: Instant,
+    ) -> anyhow::Result<Value> {
+        let mut wait = DEFAULT_POLL_INTERVAL;
+        loop {
+            let rsp = self.post_as_get(url).await?;
+            let v = rsp.json()?;
+            let status = get_str(&v, "status")?;
+            if status == target {
+                return Ok(v);
+            }
+            match status {
+                "pending" | "processing" | "ready" => {}
+                _ => return Err(anyhow!("unexpected status {status}: {}", error_detail(&v))),
+            }
+
+            if let Some(retry_after) = rsp.retry_after() {
+                wait = retry_after.min(MAX_POLL_INTERVAL);
+            }
+            if Instant::now() + wait > deadline {
+                return Err(anyhow!("timed out to wait for status {target}"));
+            }
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Run the whole order process and get the new certificate
+    pub(crate) async fn issue(
+        &mut self,
+        domains: &[String],
+        challenge_type: AcmeChallengeType,
+        challenges: &AcmeChallengeStore,
+    ) -> anyhow::Result<AcmeCertificate> {
+        let identifiers = domains
+            .iter()
+            .map(|d| serde_json::json!({"type": "dns", "value": d}))
+            .collect::<Vec<_>>();
+        let payload = serde_json::json!({ "identifiers": identifiers });
+        let url = self.directory.new_order.clone();
+        let rsp = self
+            .post(&url, Some(&payload), CONTENT_TYPE_JOSE_JSON)
+            .await
+            .context("failed to create new order")?;
+        let order_url = rsp
+            .location()
+            .ok_or_else(|| anyhow!("no order url returned"))?;
+        let order_url =
+            Url::parse(order_url).map_err(|e| anyhow!("invalid order url {order_url}: {e}"))?;
+        let order = rsp.json()?;
+
+        let authorizations = order
+            .get("authorizations")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| anyhow!("no authorizations found in the order"))?;
+        for authz in authorizations {
+            let Some(url) = authz.as_str() else {
+                return Err(anyhow!("invalid authorization url {authz}"));
+            };
+            let url =
+                Url::parse(url).map_err(|e| anyhow!("invalid authorization url {url}: {e}"))?;
+            self.authorize(&url, challenge_type, challenges)
+                .await
+                .context(format!("authorization {url} failed"))?;
+        }
+
+        let deadline = Instant::now() + self.validation_timeout;
+        self.poll_status(&order_url, "ready", deadline)
+            .await
+            .context("the order is not ready")?;
+
+        let key = crate::key::new_ec256_key()?;
+        let csr = build_csr(domains, &key)?;
+        let finalize = get_url(&order, "finalize")?;
+        let payload = serde_json::json!({ "csr": BASE64_URL_SAFE_NO_PAD.encode(csr) });
+        self.post(&finalize, Some(&payload), CONTENT_TYPE_JOSE_JSON)
+            .await
+            .context("failed to finalize the order")?;
+
+        let deadline = Instant::now() + self.validation_timeout;
+        let order = self
+            .poll_status(&order_url, "valid", deadline)
+            .await
+            .context("the order is not valid")?;
+        let cert_url = get_url(&order, "certificate")?;
+        let rsp = self
+            .post(&cert_url, None, CONTENT_TYPE_PEM_CHAIN)
+            .await
+            .context("failed to download the certificate")?;
+
+        let key_pem = key
+            .private_key_to_pem_pkcs8()
+            .map_err(|e| anyhow!("failed to encode private key: {e}"))?;
+        Ok(AcmeCertificate {
+            cert_pem: rsp.body,
+            key_pem,
+        })
+    }
+
+    async fn authorize(
+        &mut self,
+        url: &Url,
+        challenge_type: AcmeChallengeType,
+        challenges: &AcmeChallengeStore,
+    ) -> anyhow::Result<()> {
+        let authz = self.post_as_get(url).await?.json()?;
+        if get_str(&authz, "status")? == "valid" {
+            return Ok(());
+        }
+        let domain = authz
+            .get("identifier")
+            .map(|v| get_str(v, "value"))
+            .transpose()?
+            .ok_or_else(|| anyhow!("no identifier found"))?;
+        let challenge = authz
+            .get("challenges")
+            .and_then(|v| v.as_array())
+            .and_then(|a| {
+                a.iter().find(|c| {
+                    c.get("type").and_then(|v| v.as_str()) == Some(challenge_type.as_str())
+                })
+            })
+            .ok_or_else(|| anyhow!("no {} challenge offered", challenge_type.as_str()))?;
+        let token = get_str(challenge, "token")?;
+        let challenge_url = get_url(challenge, "url")?;
+
+        let key_authorization = self.key.key_authorization(token);
+        let _guard = challenges.add(challenge_type, domain, token, key_authorization)?;
+        debug!(
+            "responding to {} challenge for {domain}",
+            challenge_type.as_str()
+        );
+        self.post(
+            &challenge_url,
+            Some(&serde_json::json!({})),
+            CONTENT_TYPE_JOSE_JSON,
+        )
+        .await
+        .context("failed to respond to the challenge")?;
+
+        let deadline = Instant::now() + self.validation_timeout;
+        self.poll_status(url, "valid", deadline).await?;
+        Ok(())
+    }
+}
+
+fn error_detail(v: &Value) -> String {
+    if let Some(detail) = v.get("error").and_then(|e| e.get("detail")) {
+        return detail.to_string();
+    }
+    // the error of an authorization is in the challenge object
+    v.get("challenges")
+        .and_then(|v| v.as_array())
+        .and_then(|a| a.iter().find_map(|c| c.get("error")))
+        .and_then(|e| e.get("detail"))
+        .map(|d| d.to_string())
+        .unwrap_or_default()
+}
+
+fn build_csr(domains: &[String], key: &PKey<Private>) -> anyhow::Result<Vec<u8>> {
+    let mut builder = X509ReqBuilder::new().map_err(|e| anyhow!("failed to create csr: {e}"))?;
+    builder
+        .set_pubkey(key)
+        .map_err(|e| anyhow!("failed to set public key: {e}"))?;
+
+    if let Some(domain) = domains.first() {
+        let mut name = X509NameBuilder::new().map_err(|e| anyhow!("failed to create name: {e}"))?;
+        name.append_entry_by_text("CN", domain)
+            .map_err(|e| anyhow!("failed to set common name: {e}"))?;
+        builder
+            .set_subject_name(&name.build())
+            .map_err(|e| anyhow!("failed to set subject name: {e}"))?;
+    }
+
+    let mut san = SubjectAlternativeName::new();
+    for domain in domains {
+        san.dns(domain);
+    }
+    let san = san
+        .build(&builder.x509v3_context(None))
+        .map_err(|e| anyhow!("failed to build SAN extension: {e}"))?;
+    let mut extensions = Stack::new().map_err(|e| anyhow!("failed to create stack: {e}"))?;
+    extensions
+        .push(san)
+        .map_err(|e| anyhow!("failed to push SAN extension: {e}"))?;
+    builder
+        .add_extensions(&extensions)
+        .map_err(|e| anyhow!("failed to add extensions: {e}"))?;
+
+    builder
+        .sign(key, MessageDigest::sha256())
+        .map_err(|e| anyhow!("failed to sign csr: {e}"))?;
+    builder
+        .build()
+        .to_der()
+        .map_err(|e| anyhow!("failed to encode csr: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+
+    use openssl::x509::X509Req;
+
+    struct MockResponse {
+        code: u16,
+        location: Option<String>,
+        body: String,
+    }
+
+    impl MockResponse {
+        fn json(code: u16, body: Value) -> Self {
+            MockResponse {
+                code,
+                location: None,
+                body: body.to_string(),
+            }
+        }
+
+        fn with_location(mut self, location: String) -> Self {
+            self.location = Some(location);
+            self
+        }
+    }
+
+    /// Serve one request per connection, and return the request line and body of them
+    fn spawn_server(
+        listener: TcpListener,
+        responses: Vec<MockResponse>,
+    ) -> JoinHandle<Vec<(String, Vec<u8>)>> {
+        std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (i, rsp) in responses.into_iter().enumerate() {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(v) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = v.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+                requests.push((request_line.trim_end().to_string(), body));
+
+                let mut stream = reader.into_inner();
+                let mut header = format!(
+                    "HTTP/1.1 {} Mock\r\nReplay-Nonce: nonce-{i}\r\nContent-Length: {}\r\n",
+                    rsp.code,
+                    rsp.body.len()
+                );
+                if let Some(location) = rsp.location {
+                    header.push_str(&format!("Location: {location}\r\n"));
+                }
+                header.push_str("Connection: close\r\n\r\n");
+                stream.write_all(header.as_bytes()).unwrap();
+                stream.write_all(rsp.body.as_bytes()).unwrap();
+            }
+            requests
+        })
+    }
+
+    fn jws_nonce_and_payload(body: &[u8]) -> (String, Value) {
+        let jws: Value = serde_json::from_slice(body).unwrap();
+        let protected = BASE64_URL_SAFE_NO_PAD
+            .decode(jws["protected"].as_str().unwrap())
+            .unwrap();
+        let protected: Value = serde_json::from_slice(&protected).unwrap();
+        let payload = BASE64_URL_SAFE_NO_PAD
+            .decode(jws["payload"].as_str().unwrap())
+            .unwrap();
+        let payload = if payload.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&payload).unwrap()
+        };
+        (protected["nonce"].as_str().unwrap().to_string(), payload)
+    }
+
+    #[tokio::test]
+    async fn register_and_issue() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let directory = serde_json::json!({
+            "newNonce": format!("{base}/nonce"),
+            "newAccount": format!("{base}/acct"),
+            "newOrder": format!("{base}/order"),
+        });
+        let pending_order = serde_json::json!({
+            "status": "pending",
+            "authorizations": [format!("{base}/authz/1")],
+            "finalize": format!("{base}/finalize/1"),
+        });
+        let bad_nonce = serde_json::json!({
+            "type": "urn:ietf:params:acme:error:badNonce",
+            "detail": "invalid nonce",
+        });
+        let cert_pem = "-----BEGIN CERTIFICATE-----\nMA==\n-----END CERTIFICATE-----\n";
+        let responses = vec![
+            MockResponse::json(200, directory),
+            MockResponse::json(200, serde_json::json!({})),
+            MockResponse::json(400, bad_nonce),
+            MockResponse::json(201, serde_json::json!({"status": "valid"}))
+                .with_location(format!("{base}/acct/1")),
+            MockResponse::json(201, pending_order).with_location(format!("{base}/order/1")),
+            MockResponse::json(200, serde_json::json!({"status": "valid"})),
+            MockResponse::json(200, serde_json::json!({"status": "ready"})),
+            MockResponse::json(200, serde_json::json!({"status": "processing"})),
+            MockResponse::json(
+                200,
+                serde_json::json!({"status": "valid", "certificate": format!("{base}/cert/1")}),
+            ),
+            MockResponse {
+                code: 200,
+                location: None,
+                body: cert_pem.to_string(),
+            },
+        ];
+        let server = spawn_server(listener, responses);
+
+        let mut config = AcmeConfig::default();
+        config
+            .set_directory_url(Url::parse(&format!("{base}/dir")).unwrap())
+            .unwrap();
+        config.set_terms_of_service_agreed(true);
+        config.add_contact("admin@example.net");
+        config.add_domain("example.net");
+        config.add_domain("www.example.net");
+
+        let key = AcmeAccountKey::generate().unwrap();
+        let mut client = AcmeClient::new(&config, key).await.unwrap();
+        assert_eq!(client.account(), format!("{base}/acct/1"));
+
+        let challenges = AcmeChallengeStore::default();
+        let cert = client
+            .issue(config.domains(), AcmeChallengeType::Http01, &challenges)
+            .await
+            .unwrap();
+        assert_eq!(cert.cert_pem, cert_pem.as_bytes());
+        let key = PKey::private_key_from_pem(&cert.key_pem).unwrap();
+
+        let requests = server.join().unwrap();
+        let lines = requests.iter().map(|r| r.0.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "GET /dir HTTP/1.1",
+                "HEAD /nonce HTTP/1.1",
+                "POST /acct HTTP/1.1",
+                "POST /acct HTTP/1.1",
+                "POST /order HTTP/1.1",
+                "POST /authz/1 HTTP/1.1",
+                "POST /order/1 HTTP/1.1",
+                "POST /finalize/1 HTTP/1.1",
+                "POST /order/1 HTTP/1.1",
+                "POST /cert/1 HTTP/1.1",
+            ]
+        );
+
+        // the nonce returned in the badNonce response should be used in the retry
+        let (nonce, payload) = jws_nonce_and_payload(&requests[2].1);
+        assert_eq!(nonce, "nonce-1");
+        assert_eq!(payload["termsOfServiceAgreed"], true);
+        assert_eq!(payload["contact"][0], "mailto:admin@example.net");
+        let (nonce, _) = jws_nonce_and_payload(&requests[3].1);
+        assert_eq!(nonce, "nonce-2");
+
+        let (_, payload) = jws_nonce_and_payload(&requests[4].1);
+        assert_eq!(payload["identifiers"][1]["value"], "www.example.net");
+        let (_, payload) = jws_nonce_and_payload(&requests[5].1);
+        assert_eq!(payload, Value::Null);
+
+        let (_, payload) = jws_nonce_and_payload(&requests[7].1);
+        let csr = BASE64_URL_SAFE_NO_PAD
+            .decode(payload["csr"].as_str().unwrap())
+            .unwrap();
+        let csr = X509Req::from_der(&csr).unwrap();
+        assert!(csr.verify(&key).unwrap());
+    }
+
+    #[tokio::test]
+    async fn invalid_directory() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let responses = vec![MockResponse::json(
+            200,
+            serde_json::json!({"newNonce": format!("{base}/nonce")}),
+        )];
+        let server = spawn_server(listener, responses);
+
+        let mut config = AcmeConfig::default();
+        config
+            .set_directory_url(Url::parse(&format!("{base}/dir")).unwrap())
+            .unwrap();
+        let key = AcmeAccountKey::generate().unwrap();
+        assert!(AcmeClient::new(&config, key).await.is_err());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn csr() {
+        let key = crate::key::new_ec256_key().unwrap();
+        let domains = vec!["example.net".to_string(), "www.example.net".to_string()];
+        let der = build_csr(&domains, &key).unwrap();
+        let csr = X509Req::from_der(&der).unwrap();
+        assert!(csr.verify(&key).unwrap());
+        let cn = csr
+            .subject_name()
+            .entries()
+            .next()
+            .unwrap()
+            .data()
+            .as_utf8()
+            .unwrap();
+        assert_eq!(cn.to_string(), "example.net");
+        assert!(der.windows(15).any(|w| w == b"www.example.net"));
+    }
+
+    #[test]
+    fn error_details() {
+        let v = serde_json::json!({"status": "invalid", "error": {"detail": "order failed"}});
+        assert_eq!(error_detail(&v), "\"order failed\"");
+        let v = serde_json::json!({
+            "status": "invalid",
+            "challenges": [{"type": "http-01"}, {"type": "tls-alpn-01", "error": {"detail": "refused"}}],
+        });
+        assert_eq!(error_detail(&v), "\"refused\"");
+        assert_eq!(error_detail(&serde_json::json!({})), "");
+    }
+}
@@ -0,0 +1,281 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use openssl::pkey::{PKey, Private};
+use openssl::x509::X509;
+use url::Url;
+
+use g3_types::net::OpensslClientConfigBuilder;
+
+use crate::AcmeStorage;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+const LETS_ENCRYPT_DIRECTORY_URL: &str = "https://acme-v02.api.letsencrypt.org/directory";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AcmeChallengeType {
+    Http01,
+    #[default]
+    TlsAlpn01,
+}
+
+impl AcmeChallengeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AcmeChallengeType::Http01 => "http-01",
+            AcmeChallengeType::TlsAlpn01 => "tls-alpn-01",
+        }
+    }
+}
+
+impl FromStr for AcmeChallengeType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "http-01" | "http01" | "http" => Ok(AcmeChallengeType::Http01),
+            "tls-alpn-01" | "tls-alpn01" | "tls-alpn" => Ok(AcmeChallengeType::TlsAlpn01),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AcmeConfig {
+    directory_url: Url,
+    contacts: Vec<String>,
+    terms_of_service_agreed: bool,
+    storage_dir: PathBuf,
+    domains: Vec<String>,
+    challenge_type: AcmeChallengeType,
+    tls_client: OpensslClientConfigBuilder,
+    pub(crate) request_timeout: Duration,
+    pub(crate) validation_timeout: Duration,
+    renew_before: Duration,
+    check_interval: Duration,
+    retry_interval: Duration,
+}
+
+impl Default for AcmeConfig {
+    fn default() -> Self {
+        AcmeConfig {
+            directory_url: Url::parse(LETS_ENCRYPT_DIRECTORY_URL).unwrap(),
+            contacts: Vec::new(),
+            terms_of_service_agreed: false,
+            storage_dir: PathBuf::new(),
+            domains: Vec::new(),
+            challenge_type: AcmeChallengeType::default(),
+            tls_client: OpensslClientConfigBuilder::with_cache_for_one_site(),
+            request_timeout: Duration::from_secs(30),
+            validation_timeout: Duration::from_secs(120),
+            renew_before: Duration::from_secs(30 * 86400),
+            check_interval: Duration::from_secs(12 * 3600),
+            retry_interval: Duration::from_secs(600),
+        }
+    }
+}
+
+impl AcmeConfig {
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.storage_dir.as_os_str().is_empty() {
+            return Err(anyhow!("no storage dir set"));
+        }
+        if self.domains.is_empty() {
+            return Err(anyhow!("no domain set"));
+        }
+        if let Some(domain) = self.domains.iter().find(|d| d.starts_with("*.")) {
+            return Err(anyhow!(
+                "wildcard domain {domain} is not supported by {} challenge",
+                self.challenge_type.as_str()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn set_directory_url(&mut self, url: Url) -> anyhow::Result<()> {
+        match url.scheme() {
+            "https" | "http" => {
+                self.directory_url = url;
+                Ok(())
+            }
+            s => Err(anyhow!("unsupported url scheme {s}")),
+        }
+    }
+
+    #[inline]
+    pub fn directory_url(&self) -> &Url {
+        &self.directory_url
+    }
+
+    pub fn add_contact(&mut self, contact: &str) {
+        if contact.contains(':') {
+            self.contacts.push(contact.to_string());
+        } else {
+            self.contacts.push(format!("mailto:{contact}"));
+        }
+    }
+
+    #[inline]
+    pub(crate) fn contacts(&self) -> &[String] {
+        &self.contacts
+    }
+
+    #[inline]
+    pub fn set_terms_of_service_agreed(&mut self, agreed: bool) {
+        self.terms_of_service_agreed = agreed;
+    }
+
+    #[inline]
+    pub(crate) fn terms_of_service_agreed(&self) -> bool {
+        self.terms_of_service_agreed
+    }
+
+    #[inline]
+    pub fn set_storage_dir(&mut self, dir: PathBuf) {
+        self.storage_dir = dir;
+    }
+
+    #[inline]
+    pub fn storage_dir(&self) -> &Path {
+        &self.storage_dir
+    }
+
+    pub fn add_domain(&mut self, domain: &str) {
+        let domain = domain.to_lowercase();
+        if !self.domains.contains(&domain) {
+            self.domains.push(domain);
+        }
+    }
+
+    #[inline]
+    pub fn domains(&self) -> &[String] {
+        &self.domains
+    }
+
+    #[inline]
+    pub fn set_challenge_type(&mut self, challenge_type: AcmeChallengeType) {
+        self.challenge_type = challenge_type;
+    }
+
+    #[inline]
+    pub fn challenge_type(&self) -> AcmeChallengeType {
+        self.challenge_type
+    }
+
+    #[inline]
+    pub fn set_tls_client(&mut self, tls_client: OpensslClientConfigBuilder) {
+        self.tls_client = tls_client;
+    }
+
+    #[inline]
+    pub(crate) fn tls_client(&self) -> &OpensslClientConfigBuilder {
+        &self.tls_client
+    }
+
+    #[inline]
+    pub fn set_request_timeout(&mut self, timeout: Duration) {
+        self.request_timeout = timeout;
+    }
+
+    #[inline]
+    pub fn set_validation_timeout(&mut self, timeout: Duration) {
+        self.validation_timeout = timeout;
+    }
+
+    #[inline]
+    pub fn set_renew_before(&mut self, duration: Duration) {
+        self.renew_before = duration;
+    }
+
+    #[inline]
+    pub(crate) fn renew_before(&self) -> Duration {
+        self.renew_before
+    }
+
+    #[inline]
+    pub fn set_check_interval(&mut self, interval: Duration) {
+        self.check_interval = interval;
+    }
+
+    /// The interval to check the stored certificate for renewal
+    #[inline]
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    #[inline]
+    pub fn set_retry_interval(&mut self, interval: Duration) {
+        self.retry_interval = interval;
+    }
+
+    /// The interval to retry after a failed issuance
+    #[inline]
+    pub fn retry_interval(&self) -> Duration {
+        self.retry_interval
+    }
+
+    /// Load the issued certificate chain and the private key from the storage dir.
+    /// `None` will be returned if no certificate has been issued yet.
+    pub fn load_certificate(&self) -> anyhow::Result<Option<(Vec<X509>, PKey<Private>)>> {
+        AcmeStorage::new(self).load_certificate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenge_type() {
+        assert_eq!(
+            AcmeChallengeType::from_str("HTTP_01").unwrap(),
+            AcmeChallengeType::Http01
+        );
+        assert_eq!(
+            AcmeChallengeType::from_str("tls-alpn").unwrap(),
+            AcmeChallengeType::TlsAlpn01
+        );
+        assert!(AcmeChallengeType::from_str("dns-01").is_err());
+        assert_eq!(AcmeChallengeType::default().as_str(), "tls-alpn-01");
+    }
+
+    #[test]
+    fn set_values() {
+        let mut config = AcmeConfig::default();
+        assert!(config.check().is_err());
+
+        config.set_storage_dir(PathBuf::from("/tmp/acme"));
+        assert!(config.check().is_err());
+
+        config.add_domain("Example.net");
+        config.add_domain("example.NET");
+        config.add_domain("www.example.net");
+        assert_eq!(config.domains(), ["example.net", "www.example.net"]);
+        config.check().unwrap();
+
+        config.add_contact("admin@example.net");
+        config.add_contact("mailto:ops@example.net");
+        assert_eq!(
+            config.contacts(),
+            ["mailto:admin@example.net", "mailto:ops@example.net"]
+        );
+
+        let url = Url::parse("ftp://example.net/directory").unwrap();
+        assert!(config.set_directory_url(url).is_err());
+        let url = Url::parse("http://127.0.0.1:14000/dir").unwrap();
+        config.set_directory_url(url.clone()).unwrap();
+        assert_eq!(config.directory_url(), &url);
+
+        config.add_domain("*.example.net");
+        assert!(config.check().is_err());
+    }
+}
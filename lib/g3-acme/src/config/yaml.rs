@@ -0,0 +1,189 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
+use super::{AcmeChallengeType, AcmeConfig};
+
+impl AcmeConfig {
+    pub fn parse_yaml(value: &Yaml, lookup_dir: Option<&Path>) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut config = AcmeConfig::default();
+
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "directory" | "directory_url" | "server" => {
+                        let url = g3_yaml::value::as_url(v)
+                            .context(format!("invalid url value for key {k}"))?;
+                        config.set_directory_url(url)
+                    }
+                    "contact" | "contacts" | "email" => {
+                        let contacts = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                            .context(format!("invalid contact list value for key {k}"))?;
+                        for contact in contacts {
+                            config.add_contact(&contact);
+                        }
+                        Ok(())
+                    }
+                    "terms_of_service_agreed" | "agree_tos" => {
+                        let agreed = g3_yaml::value::as_bool(v)?;
+                        config.set_terms_of_service_agreed(agreed);
+                        Ok(())
+                    }
+                    "storage" | "storage_dir" => {
+                        let dir = match lookup_dir {
+                            Some(lookup_dir) => g3_yaml::value::as_dir_path(v, lookup_dir, true),
+                            None => g3_yaml::value::as_absolute_path(v),
+                        }
+                        .context(format!("invalid dir path value for key {k}"))?;
+                        config.set_storage_dir(dir);
+                        Ok(())
+                    }
+                    "domain" | "domains" => {
+                        let domains = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)
+                            .context(format!("invalid domain list value for key {k}"))?;
+                        for domain in domains {
+                            config.add_domain(&domain);
+                        }
+                        Ok(())
+                    }
+                    "challenge" | "challenge_type" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        let challenge_type = AcmeChallengeType::from_str(&s)
+                            .map_err(|_| anyhow!("unsupported challenge type {s}"))?;
+                        config.set_challenge_type(challenge_type);
+                        Ok(())
+                    }
+                    "tls_client" => {
+                        let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                            v, lookup_dir,
+                        )
+                        .context(format!(
+                            "invalid openssl tls client config value for key {k}"
+                        ))?;
+                        config.set_tls_client(builder);
+                        Ok(())
+                    }
+                    "request_timeout" => {
+                        let timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_request_timeout(timeout);
+                        Ok(())
+                    }
+                    "validation_timeout" => {
+                        let timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_validation_timeout(timeout);
+                        Ok(())
+                    }
+                    "renew_before" => {
+                        let duration = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_renew_before(duration);
+                        Ok(())
+                    }
+                    "check_interval" => {
+                        let interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_check_interval(interval);
+                        Ok(())
+                    }
+                    "retry_interval" => {
+                        let interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_retry_interval(interval);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+
+                config.check()?;
+                Ok(config)
+            }
+            _ => Err(anyhow!("yaml value type for 'AcmeConfig' should be 'map'")),
+        }
+    }
+}
+
+impl YamlDump for AcmeConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("directory_url", &self.directory_url)
+            .set("contacts", &self.contacts)
+            .set("terms_of_service_agreed", &self.terms_of_service_agreed)
+            .set("storage_dir", &self.storage_dir)
+            .set("domains", &self.domains)
+            .set("challenge_type", self.challenge_type.as_str())
+            .set("tls_client", &self.tls_client)
+            .set("request_timeout", &self.request_timeout)
+            .set("validation_timeout", &self.validation_timeout)
+            .set("renew_before", &self.renew_before)
+            .set("check_interval", &self.check_interval)
+            .set("retry_interval", &self.retry_interval);
+        map.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse() {
+        let value = YamlLoader::load_from_str(
+            r#"
+            directory: http://127.0.0.1:14000/dir
+            email: admin@example.net
+            agree_tos: true
+            storage: /tmp/acme
+            domains:
+              - Example.net
+              - www.example.net
+            challenge: http-01
+            renew_before: 10d
+            retry_interval: 5m
+            "#,
+        )
+        .unwrap()
+        .remove(0);
+        let config = AcmeConfig::parse_yaml(&value, None).unwrap();
+        assert_eq!(
+            config.directory_url().as_str(),
+            "http://127.0.0.1:14000/dir"
+        );
+        assert_eq!(config.contacts(), ["mailto:admin@example.net"]);
+        assert!(config.terms_of_service_agreed());
+        assert_eq!(config.storage_dir(), Path::new("/tmp/acme"));
+        assert_eq!(config.domains(), ["example.net", "www.example.net"]);
+        assert_eq!(config.challenge_type(), AcmeChallengeType::Http01);
+        assert_eq!(config.renew_before(), Duration::from_secs(10 * 86400));
+        assert_eq!(config.retry_interval(), Duration::from_secs(300));
+
+        let dumped = config.dump_yaml(false);
+        assert_eq!(AcmeConfig::parse_yaml(&dumped, None).unwrap(), config);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in [
+            "storage: /tmp/acme",
+            "domain: example.net",
+            "{storage: /tmp/acme, domain: example.net, challenge: dns-01}",
+            "{storage: /tmp/acme, domain: example.net, unknown: 1}",
+            "[example.net]",
+        ] {
+            let value = YamlLoader::load_from_str(s).unwrap().remove(0);
+            assert!(AcmeConfig::parse_yaml(&value, None).is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,246 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::Method;
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use url::{Position, Url};
+
+use g3_http::HttpBodyReader;
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_openssl::SslConnector;
+use g3_types::net::{Host, HttpHeaderMap, OpensslClientConfig};
+
+const RESPONSE_HEADER_MAX_SIZE: usize = 16384;
+const RESPONSE_BODY_MAX_SIZE: u64 = 1 << 20;
+
+pub(crate) const CONTENT_TYPE_JOSE_JSON: &str = "application/jose+json";
+pub(crate) const CONTENT_TYPE_PEM_CHAIN: &str = "application/pem-certificate-chain";
+
+pub(crate) struct AcmeHttpResponse {
+    pub(crate) code: u16,
+    headers: HttpHeaderMap,
+    pub(crate) body: Vec<u8>,
+}
+
+impl AcmeHttpResponse {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|v| v.to_str())
+    }
+
+    #[inline]
+    pub(crate) fn is_success(&self) -> bool {
+        (200..300).contains(&self.code)
+    }
+
+    #[inline]
+    pub(crate) fn location(&self) -> Option<&str> {
+        self.header("location")
+    }
+
+    #[inline]
+    pub(crate) fn replay_nonce(&self) -> Option<&str> {
+        self.header("replay-nonce")
+    }
+
+    /// Only the delay-seconds form is supported
+    pub(crate) fn retry_after(&self) -> Option<Duration> {
+        self.header("retry-after")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+    }
+
+    pub(crate) fn json(&self) -> anyhow::Result<Value> {
+        serde_json::from_slice(&self.body).map_err(|e| anyhow!("invalid json response body: {e}"))
+    }
+
+    /// The type of the problem document, see RFC 8555 Section 6.7
+    pub(crate) fn problem_type(&self) -> Option<String> {
+        let v = self.json().ok()?;
+        v.get("type")
+            .and_then(|t| t.as_str())
+            .map(|t| t.to_string())
+    }
+
+    pub(crate) fn problem(&self) -> anyhow::Error {
+        match self.json() {
+            Ok(v) => {
+                let r#type = v.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+                let detail = v.get("detail").and_then(|t| t.as_str()).unwrap_or_default();
+                anyhow!("error response {}: {type} {detail}", self.code)
+            }
+            Err(_) => anyhow!("error response {}", self.code),
+        }
+    }
+}
+
+pub(crate) struct AcmeHttpClient {
+    tls_client: OpensslClientConfig,
+    timeout: Duration,
+}
+
+impl AcmeHttpClient {
+    pub(crate) fn new(tls_client: OpensslClientConfig, timeout: Duration) -> Self {
+        AcmeHttpClient {
+            tls_client,
+            timeout,
+        }
+    }
+
+    pub(crate) async fn get(&self, url: &Url) -> anyhow::Result<AcmeHttpResponse> {
+        self.request(Method::GET, url, None, "application/json")
+            .await
+    }
+
+    pub(crate) async fn head(&self, url: &Url) -> anyhow::Result<AcmeHttpResponse> {
+        self.request(Method::HEAD, url, None, "*/*").await
+    }
+
+    pub(crate) async fn post(
+        &self,
+        url: &Url,
+        body: &[u8],
+        accept: &str,
+    ) -> anyhow::Result<AcmeHttpResponse> {
+        self.request(Method::POST, url, Some(body), accept).await
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<&[u8]>,
+        accept: &str,
+    ) -> anyhow::Result<AcmeHttpResponse> {
+        tokio::time::timeout(self.timeout, self.do_request(method, url, body, accept))
+            .await
+            .map_err(|_| anyhow!("timed out to request {url}"))?
+    }
+
+    async fn do_request(
+        &self,
+        method: Method,
+        url: &Url,
+        body: Option<&[u8]>,
+        accept: &str,
+    ) -> anyhow::Result<AcmeHttpResponse> {
+        let port = url
+            .port_or_known_default()
+            .ok_or_else(|| anyhow!("no port found in url {url}"))?;
+        let (host, stream) = match url.host() {
+            Some(url::Host::Domain(domain)) => {
+                let stream = TcpStream::connect((domain, port))
+                    .await
+                    .map_err(|e| anyhow!("failed to connect to {domain}:{port}: {e}"))?;
+                (Host::Domain(Arc::from(domain)), stream)
+            }
+            Some(url::Host::Ipv4(ip)) => {
+                let ip = IpAddr::V4(ip);
+                (Host::Ip(ip), connect_ip(ip, port).await?)
+            }
+            Some(url::Host::Ipv6(ip)) => {
+                let ip = IpAddr::V6(ip);
+                (Host::Ip(ip), connect_ip(ip, port).await?)
+            }
+            None => return Err(anyhow!("no host found in url {url}")),
+        };
+
+        match url.scheme() {
+            "https" => {
+                let ssl = self
+                    .tls_client
+                    .build_ssl(&host, port)
+                    .map_err(|e| anyhow!("failed to build ssl: {e}"))?;
+                let connector = SslConnector::new(ssl, stream)
+                    .map_err(|e| anyhow!("failed to create ssl connector: {e}"))?;
+                let tls_stream = connector
+                    .connect()
+                    .await
+                    .map_err(|e| anyhow!("tls handshake with {host} failed: {e}"))?;
+                send_request(tls_stream, method, url, body, accept).await
+            }
+            "http" => send_request(stream, method, url, body, accept).await,
+            s => Err(anyhow!("unsupported url scheme {s}")),
+        }
+    }
+}
+
+async fn connect_ip(ip: IpAddr, port: u16) -> anyhow::Result<TcpStream> {
+    let addr = SocketAddr::new(ip, port);
+    TcpStream::connect(addr)
+        .await
+        .map_err(|e| anyhow!("failed to connect to {addr}: {e}"))
+}
+
+async fn send_request<S>(
+    mut stream: S,
+    method: Method,
+    url: &Url,
+    body: Option<&[u8]>,
+    accept: &str,
+) -> anyhow::Result<AcmeHttpResponse>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut buf = Vec::with_capacity(1024 + body.map(|b| b.len()).unwrap_or_default());
+    let _ = write!(
+        buf,
+        "{method} {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         User-Agent: g3-acme/{}\r\n\
+         Accept: {accept}\r\n\
+         Connection: close\r\n",
+        &url[Position::BeforePath..Position::AfterQuery],
+        &url[Position::BeforeHost..Position::AfterPort],
+        env!("CARGO_PKG_VERSION"),
+    );
+    if let Some(body) = body {
+        let _ = write!(
+            buf,
+            "Content-Type: {CONTENT_TYPE_JOSE_JSON}\r\nContent-Length: {}\r\n",
+            body.len()
+        );
+    }
+    buf.extend_from_slice(b"\r\n");
+    if let Some(body) = body {
+        buf.extend_from_slice(body);
+    }
+    stream
+        .write_all(&buf)
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+    stream
+        .flush()
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+
+    let mut reader = BufReader::new(stream);
+    let rsp =
+        HttpForwardRemoteResponse::parse(&mut reader, &method, false, RESPONSE_HEADER_MAX_SIZE)
+            .await
+            .map_err(|e| anyhow!("failed to read response header: {e}"))?;
+
+    let mut body = Vec::new();
+    if let Some(body_type) = rsp.body_type(&method) {
+        HttpBodyReader::new(&mut reader, body_type, 1024)
+            .take(RESPONSE_BODY_MAX_SIZE)
+            .read_to_end(&mut body)
+            .await
+            .map_err(|e| anyhow!("failed to read response body: {e}"))?;
+    }
+
+    Ok(AcmeHttpResponse {
+        code: rsp.code,
+        headers: rsp.end_to_end_headers,
+        body,
+    })
+}
@@ -0,0 +1,214 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+use base64::prelude::*;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::{MessageDigest, hash};
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::sign::Signer;
+use serde_json::Value;
+
+const P256_COORDINATE_SIZE: i32 = 32;
+
+pub(crate) fn new_ec256_key() -> anyhow::Result<PKey<Private>> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+        .map_err(|e| anyhow!("failed to get P-256 curve: {e}"))?;
+    let ec_key = EcKey::generate(&group).map_err(|e| anyhow!("failed to generate EC key: {e}"))?;
+    PKey::from_ec_key(ec_key).map_err(|e| anyhow!("failed to convert EC key: {e}"))
+}
+
+/// The ACME account key, which is used to sign all the JWS requests
+pub(crate) struct AcmeAccountKey {
+    pkey: PKey<Private>,
+    jwk: String,
+    thumbprint: String,
+}
+
+impl AcmeAccountKey {
+    pub(crate) fn generate() -> anyhow::Result<Self> {
+        let pkey = new_ec256_key()?;
+        AcmeAccountKey::new(pkey)
+    }
+
+    pub(crate) fn from_pem(pem: &[u8]) -> anyhow::Result<Self> {
+        let pkey =
+            PKey::private_key_from_pem(pem).map_err(|e| anyhow!("invalid private key pem: {e}"))?;
+        AcmeAccountKey::new(pkey)
+    }
+
+    pub(crate) fn to_pem(&self) -> anyhow::Result<Vec<u8>> {
+        self.pkey
+            .private_key_to_pem_pkcs8()
+            .map_err(|e| anyhow!("failed to encode private key: {e}"))
+    }
+
+    fn new(pkey: PKey<Private>) -> anyhow::Result<Self> {
+        let ec_key = pkey
+            .ec_key()
+            .map_err(|_| anyhow!("the account key should be an EC key"))?;
+        let group = ec_key.group();
+        if group.curve_name() != Some(Nid::X9_62_PRIME256V1) {
+            return Err(anyhow!("the account key should be a P-256 key"));
+        }
+
+        let mut ctx = BigNumContext::new().map_err(|e| anyhow!("failed to create bn ctx: {e}"))?;
+        let mut x = BigNum::new().map_err(|e| anyhow!("failed to create bn: {e}"))?;
+        let mut y = BigNum::new().map_err(|e| anyhow!("failed to create bn: {e}"))?;
+        ec_key
+            .public_key()
+            .affine_coordinates_gfp(group, &mut x, &mut y, &mut ctx)
+            .map_err(|e| anyhow!("failed to get public key coordinates: {e}"))?;
+        let x = x
+            .to_vec_padded(P256_COORDINATE_SIZE)
+            .map_err(|e| anyhow!("failed to encode x coordinate: {e}"))?;
+        let y = y
+            .to_vec_padded(P256_COORDINATE_SIZE)
+            .map_err(|e| anyhow!("failed to encode y coordinate: {e}"))?;
+
+        // the members should be in lexicographic order for the thumbprint, see RFC 7638
+        let jwk = format!(
+            r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#,
+            BASE64_URL_SAFE_NO_PAD.encode(x),
+            BASE64_URL_SAFE_NO_PAD.encode(y)
+        );
+        let digest = hash(MessageDigest::sha256(), jwk.as_bytes())
+            .map_err(|e| anyhow!("failed to compute jwk thumbprint: {e}"))?;
+        let thumbprint = BASE64_URL_SAFE_NO_PAD.encode(digest);
+
+        Ok(AcmeAccountKey {
+            pkey,
+            jwk,
+            thumbprint,
+        })
+    }
+
+    /// The key authorization string for the challenge token, see RFC 8555 Section 8.1
+    pub(crate) fn key_authorization(&self, token: &str) -> String {
+        format!("{token}.{}", self.thumbprint)
+    }
+
+    /// Build the flattened JWS request body.
+    /// The `jwk` will be embedded if `kid` is not set, and an empty payload will be used if
+    /// `payload` is not set, which is the POST-as-GET request.
+    pub(crate) fn sign(
+        &self,
+        url: &str,
+        nonce: &str,
+        kid: Option<&str>,
+        payload: Option<&Value>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut protected = serde_json::json!({
+            "alg": "ES256",
+            "nonce": nonce,
+            "url": url,
+        });
+        match kid {
+            Some(kid) => protected["kid"] = Value::String(kid.to_string()),
+            None => {
+                protected["jwk"] =
+                    serde_json::from_str(&self.jwk).map_err(|e| anyhow!("invalid jwk json: {e}"))?
+            }
+        }
+        let protected = BASE64_URL_SAFE_NO_PAD.encode(protected.to_string());
+        let payload = match payload {
+            Some(v) => BASE64_URL_SAFE_NO_PAD.encode(v.to_string()),
+            None => String::new(),
+        };
+
+        let mut signer = Signer::new(MessageDigest::sha256(), &self.pkey)
+            .map_err(|e| anyhow!("failed to create signer: {e}"))?;
+        signer
+            .update(protected.as_bytes())
+            .and_then(|_| signer.update(b"."))
+            .and_then(|_| signer.update(payload.as_bytes()))
+            .map_err(|e| anyhow!("failed to update signer: {e}"))?;
+        let der = signer
+            .sign_to_vec()
+            .map_err(|e| anyhow!("failed to sign: {e}"))?;
+        // JWS uses the raw R || S format for ECDSA signatures
+        let sig = EcdsaSig::from_der(&der).map_err(|e| anyhow!("invalid ECDSA signature: {e}"))?;
+        let mut signature = sig
+            .r()
+            .to_vec_padded(P256_COORDINATE_SIZE)
+            .map_err(|e| anyhow!("failed to encode signature: {e}"))?;
+        let s = sig
+            .s()
+            .to_vec_padded(P256_COORDINATE_SIZE)
+            .map_err(|e| anyhow!("failed to encode signature: {e}"))?;
+        signature.extend_from_slice(&s);
+
+        let body = serde_json::json!({
+            "protected": protected,
+            "payload": payload,
+            "signature": BASE64_URL_SAFE_NO_PAD.encode(signature),
+        });
+        Ok(body.to_string().into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::ecdsa::EcdsaSig;
+
+    #[test]
+    fn sign_and_verify() {
+        let key = AcmeAccountKey::generate().unwrap();
+        let pem = key.to_pem().unwrap();
+        let key = AcmeAccountKey::from_pem(&pem).unwrap();
+        assert_eq!(key.thumbprint.len(), 43);
+        assert!(key.key_authorization("token").starts_with("token."));
+
+        let payload = serde_json::json!({"termsOfServiceAgreed": true});
+        let body = key
+            .sign(
+                "https://example.net/acme/new-acct",
+                "nonce",
+                None,
+                Some(&payload),
+            )
+            .unwrap();
+        let jws: Value = serde_json::from_slice(&body).unwrap();
+        let protected = jws["protected"].as_str().unwrap();
+        let payload = jws["payload"].as_str().unwrap();
+
+        let header = BASE64_URL_SAFE_NO_PAD.decode(protected).unwrap();
+        let header: Value = serde_json::from_slice(&header).unwrap();
+        assert_eq!(header["alg"], "ES256");
+        assert_eq!(header["nonce"], "nonce");
+        assert_eq!(header["jwk"]["kty"], "EC");
+        assert!(header.get("kid").is_none());
+
+        let signature = BASE64_URL_SAFE_NO_PAD
+            .decode(jws["signature"].as_str().unwrap())
+            .unwrap();
+        assert_eq!(signature.len(), 64);
+        let r = BigNum::from_slice(&signature[..32]).unwrap();
+        let s = BigNum::from_slice(&signature[32..]).unwrap();
+        let sig = EcdsaSig::from_private_components(r, s).unwrap();
+        let digest = hash(
+            MessageDigest::sha256(),
+            format!("{protected}.{payload}").as_bytes(),
+        )
+        .unwrap();
+        let ec_key = key.pkey.ec_key().unwrap();
+        assert!(sig.verify(&digest, &ec_key).unwrap());
+
+        let body = key
+            .sign(
+                "https://example.net/acme/order/1",
+                "nonce",
+                Some("kid"),
+                None,
+            )
+            .unwrap();
+        let jws: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(jws["payload"], "");
+    }
+}
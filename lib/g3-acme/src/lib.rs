@@ -0,0 +1,39 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! ACME (RFC 8555) client to obtain and renew certificates.
+//!
+//! The HTTP-01 and TLS-ALPN-01 (RFC 8737) challenges are supported,
+//! the servers should respond to them with the help of [AcmeChallengeStore].
+
+mod config;
+pub use config::{AcmeChallengeType, AcmeConfig};
+
+mod key;
+use key::AcmeAccountKey;
+
+mod http;
+
+mod client;
+use client::AcmeClient;
+
+mod challenge;
+pub use challenge::{
+    ACME_HTTP_CHALLENGE_PATH_PREFIX, ACME_TLS_ALPN_PROTOCOL, AcmeChallengeStore, AcmeTlsAlpnCert,
+};
+
+mod storage;
+pub use storage::AcmeStorage;
+
+mod manager;
+pub use manager::AcmeCertManager;
+
+mod updater;
+pub use updater::{AcmeCertHolder, spawn_cert_updater};
+
+mod tls;
+pub use tls::openssl_tls_alpn_context;
+#[cfg(feature = "rustls")]
+pub use tls::rustls_tls_alpn_config;
@@ -0,0 +1,282 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use log::info;
+use openssl::asn1::Asn1Time;
+use openssl::x509::X509Ref;
+
+use crate::{AcmeChallengeStore, AcmeClient, AcmeConfig, AcmeStorage};
+
+type SharedLock = tokio::sync::Mutex<()>;
+
+/// The locks shared by all managers in this process, keyed by the storage path they protect.
+/// The entries will be removed after all managers using them have been dropped.
+static SHARED_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Weak<SharedLock>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn shared_lock(path: &Path) -> Arc<SharedLock> {
+    let mut locks = SHARED_LOCKS.lock().unwrap();
+    if let Some(lock) = locks.get(path).and_then(|l| l.upgrade()) {
+        return lock;
+    }
+    locks.retain(|_, l| l.strong_count() > 0);
+    let lock = Arc::new(SharedLock::default());
+    locks.insert(path.to_path_buf(), Arc::downgrade(&lock));
+    lock
+}
+
+async fn run_blocking<T, F>(f: F) -> anyhow::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| anyhow!("failed to run storage job: {e}"))?
+}
+
+/// Obtain and renew the certificate for the configured domains.
+/// The issued certificate will be saved in the storage dir.
+///
+/// Managers sharing the same storage dir will use the same account key, and managers for
+/// the same certificate (the same storage dir and first domain) won't place orders at the
+/// same time, so it's safe to have more than one manager for the same config, like after reload.
+pub struct AcmeCertManager {
+    config: Arc<AcmeConfig>,
+    storage: AcmeStorage,
+    challenges: Arc<AcmeChallengeStore>,
+    account_lock: Arc<SharedLock>,
+    order_lock: Arc<SharedLock>,
+}
+
+impl AcmeCertManager {
+    pub fn new(config: Arc<AcmeConfig>, challenges: Arc<AcmeChallengeStore>) -> Self {
+        let storage = AcmeStorage::new(&config);
+        let account_lock = shared_lock(storage.dir());
+        let order_lock = shared_lock(storage.cert_dir());
+        AcmeCertManager {
+            config,
+            storage,
+            challenges,
+            account_lock,
+            order_lock,
+        }
+    }
+
+    #[inline]
+    pub fn config(&self) -> &AcmeConfig {
+        &self.config
+    }
+
+    /// Check the stored certificate, and request a new one if it's missing, going to expire,
+    /// or not covering all the configured domains.
+    /// Return true if a new certificate has been saved.
+    pub async fn renew_if_needed(&self) -> anyhow::Result<bool> {
+        let _order_guard = self.order_lock.lock().await;
+
+        let storage = self.storage.clone();
+        let config = self.config.clone();
+        let need_renew =
+            run_blocking(move || need_renew(&storage, config.domains(), config.renew_before()))
+                .await?;
+        if let Some(reason) = need_renew {
+            info!(
+                "requesting new ACME certificate for {}: {reason}",
+                self.config.domains().join(",")
+            );
+            self.renew().await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    async fn renew(&self) -> anyhow::Result<()> {
+        let mut client = {
+            let _account_guard = self.account_lock.lock().await;
+
+            let storage = self.storage.clone();
+            let key = run_blocking(move || storage.load_or_create_account_key()).await?;
+            let client = AcmeClient::new(&self.config, key)
+                .await
+                .context("failed to setup ACME client")?;
+
+            let storage = self.storage.clone();
+            let directory = self.config.directory_url().to_string();
+            let account = client.account().to_string();
+            run_blocking(move || storage.save_account_info(&directory, &account)).await?;
+            client
+        };
+
+        let cert = client
+            .issue(
+                self.config.domains(),
+                self.config.challenge_type(),
+                &self.challenges,
+            )
+            .await?;
+        let storage = self.storage.clone();
+        run_blocking(move || storage.save_certificate(&cert.cert_pem, &cert.key_pem)).await?;
+        info!(
+            "new ACME certificate saved to {}",
+            self.storage.cert_file().display()
+        );
+        Ok(())
+    }
+}
+
+/// Check the stored certificate and return the reason if a new one is needed.
+/// This is a blocking call.
+fn need_renew(
+    storage: &AcmeStorage,
+    domains: &[String],
+    renew_before: Duration,
+) -> anyhow::Result<Option<&'static str>> {
+    let Some((certs, _key)) = storage.load_certificate()? else {
+        return Ok(Some("no certificate found"));
+    };
+    let leaf = &certs[0];
+    if !domains.iter().all(|d| cert_has_domain(leaf, d)) {
+        return Ok(Some("domains changed"));
+    }
+    let now = Asn1Time::days_from_now(0).map_err(|e| anyhow!("failed to get time: {e}"))?;
+    let diff = now
+        .diff(leaf.not_after())
+        .map_err(|e| anyhow!("invalid not after time: {e}"))?;
+    let remaining = i64::from(diff.days) * 86400 + i64::from(diff.secs);
+    let renew_before = i64::try_from(renew_before.as_secs()).unwrap_or(i64::MAX);
+    if remaining <= renew_before {
+        return Ok(Some("going to expire"));
+    }
+    Ok(None)
+}
+
+fn cert_has_domain(cert: &X509Ref, domain: &str) -> bool {
+    let Some(names) = cert.subject_alt_names() else {
+        return false;
+    };
+    names
+        .iter()
+        .filter_map(|n| n.dnsname())
+        .any(|n| n.eq_ignore_ascii_case(domain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::hash::MessageDigest;
+    use openssl::x509::X509Builder;
+    use openssl::x509::extension::SubjectAlternativeName;
+
+    fn new_cert_pem(domains: &[&str], days: u32) -> (Vec<u8>, Vec<u8>) {
+        let key = crate::key::new_ec256_key().unwrap();
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(days).unwrap())
+            .unwrap();
+        let mut san = SubjectAlternativeName::new();
+        for domain in domains {
+            san.dns(domain);
+        }
+        let san = san.build(&builder.x509v3_context(None, None)).unwrap();
+        builder.append_extension(san).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        (
+            builder.build().to_pem().unwrap(),
+            key.private_key_to_pem_pkcs8().unwrap(),
+        )
+    }
+
+    fn new_storage(name: &str, domains: &[&str]) -> (PathBuf, AcmeStorage) {
+        let dir =
+            std::env::temp_dir().join(format!("g3-acme-manager-{name}-{}", std::process::id()));
+        let mut config = AcmeConfig::default();
+        config.set_storage_dir(dir.clone());
+        for domain in domains {
+            config.add_domain(domain);
+        }
+        (dir, AcmeStorage::new(&config))
+    }
+
+    #[test]
+    fn check_renew() {
+        let domains = ["example.net", "www.example.net"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>();
+        let renew_before = Duration::from_secs(30 * 86400);
+        let (dir, storage) = new_storage("renew", &["example.net"]);
+
+        assert_eq!(
+            need_renew(&storage, &domains, renew_before).unwrap(),
+            Some("no certificate found")
+        );
+
+        let (cert, key) = new_cert_pem(&["example.net", "WWW.example.net"], 90);
+        storage.save_certificate(&cert, &key).unwrap();
+        assert_eq!(need_renew(&storage, &domains, renew_before).unwrap(), None);
+
+        let (cert, key) = new_cert_pem(&["example.net"], 90);
+        storage.save_certificate(&cert, &key).unwrap();
+        assert_eq!(
+            need_renew(&storage, &domains, renew_before).unwrap(),
+            Some("domains changed")
+        );
+
+        let (cert, key) = new_cert_pem(&["example.net", "www.example.net"], 10);
+        storage.save_certificate(&cert, &key).unwrap();
+        assert_eq!(
+            need_renew(&storage, &domains, renew_before).unwrap(),
+            Some("going to expire")
+        );
+
+        std::fs::write(storage.cert_file(), b"invalid").unwrap();
+        assert!(need_renew(&storage, &domains, renew_before).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lock_sharing() {
+        let dir = std::env::temp_dir().join(format!("g3-acme-manager-lock-{}", std::process::id()));
+        let challenges = Arc::new(AcmeChallengeStore::default());
+        let new_manager = |domain: &str| {
+            let mut config = AcmeConfig::default();
+            config.set_storage_dir(dir.clone());
+            config.add_domain(domain);
+            AcmeCertManager::new(Arc::new(config), challenges.clone())
+        };
+
+        let m1 = new_manager("a.example.net");
+        let m2 = new_manager("a.example.net");
+        let m3 = new_manager("b.example.net");
+        assert!(Arc::ptr_eq(&m1.account_lock, &m3.account_lock));
+        assert!(Arc::ptr_eq(&m1.order_lock, &m2.order_lock));
+        assert!(!Arc::ptr_eq(&m1.order_lock, &m3.order_lock));
+        assert!(!Arc::ptr_eq(&m1.account_lock, &m1.order_lock));
+
+        let order_lock = Arc::downgrade(&m3.order_lock);
+        drop(m3);
+        assert!(order_lock.upgrade().is_none());
+        let m3 = new_manager("b.example.net");
+        assert!(m3.order_lock.try_lock().is_ok());
+        let _guard = m1.order_lock.try_lock().unwrap();
+        assert!(m2.order_lock.try_lock().is_err());
+
+        // the storage dir is not expected to be created, remove it in case it is
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,264 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+use openssl::pkey::{PKey, Private};
+use openssl::x509::X509;
+
+use crate::{AcmeAccountKey, AcmeConfig};
+
+const ACCOUNT_KEY_FILE: &str = "account.key";
+const ACCOUNT_INFO_FILE: &str = "account.json";
+const CERT_DIR: &str = "certs";
+const CERT_FILE: &str = "cert.pem";
+const KEY_FILE: &str = "key.pem";
+
+/// The on disk storage of the ACME account and the issued certificates.
+///
+/// The layout of the storage dir is:
+///  - account.key: the PKCS#8 PEM encoded account private key
+///  - account.json: the account url and the directory url, for reference only
+///  - certs/<first domain>/cert.pem: the PEM encoded certificate chain
+///  - certs/<first domain>/key.pem: the PKCS#8 PEM encoded private key of the certificate
+///
+/// All the methods do blocking file I/O.
+#[derive(Clone)]
+pub struct AcmeStorage {
+    dir: PathBuf,
+    cert_dir: PathBuf,
+}
+
+impl AcmeStorage {
+    pub fn new(config: &AcmeConfig) -> Self {
+        let dir = config.storage_dir().to_path_buf();
+        let cert_name = config
+            .domains()
+            .first()
+            .map(|s| s.as_str())
+            .unwrap_or_default();
+        let cert_dir = dir.join(CERT_DIR).join(cert_name);
+        AcmeStorage { dir, cert_dir }
+    }
+
+    pub fn cert_file(&self) -> PathBuf {
+        self.cert_dir.join(CERT_FILE)
+    }
+
+    pub fn key_file(&self) -> PathBuf {
+        self.cert_dir.join(KEY_FILE)
+    }
+
+    #[inline]
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    #[inline]
+    pub(crate) fn cert_dir(&self) -> &Path {
+        &self.cert_dir
+    }
+
+    pub(crate) fn load_or_create_account_key(&self) -> anyhow::Result<AcmeAccountKey> {
+        let path = self.dir.join(ACCOUNT_KEY_FILE);
+        match std::fs::read(&path) {
+            Ok(pem) => AcmeAccountKey::from_pem(&pem)
+                .map_err(|e| anyhow!("invalid account key file {}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let key = AcmeAccountKey::generate()?;
+                write_file(&path, &key.to_pem()?, true)?;
+                Ok(key)
+            }
+            Err(e) => Err(anyhow!(
+                "failed to read account key file {}: {e}",
+                path.display()
+            )),
+        }
+    }
+
+    pub(crate) fn save_account_info(&self, directory: &str, account: &str) -> anyhow::Result<()> {
+        let info = serde_json::json!({
+            "directory": directory,
+            "account": account,
+        });
+        write_file(
+            &self.dir.join(ACCOUNT_INFO_FILE),
+            info.to_string().as_bytes(),
+            false,
+        )
+    }
+
+    /// Load the stored certificate chain and private key, return `None` if not existed
+    pub(crate) fn load_certificate(&self) -> anyhow::Result<Option<(Vec<X509>, PKey<Private>)>> {
+        let cert_file = self.cert_file();
+        let cert_pem = match std::fs::read(&cert_file) {
+            Ok(pem) => pem,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(anyhow!(
+                    "failed to read certificate file {}: {e}",
+                    cert_file.display()
+                ));
+            }
+        };
+        let key_file = self.key_file();
+        let key_pem = std::fs::read(&key_file).map_err(|e| {
+            anyhow!(
+                "failed to read private key file {}: {e}",
+                key_file.display()
+            )
+        })?;
+
+        let certs = X509::stack_from_pem(&cert_pem)
+            .map_err(|e| anyhow!("invalid certificate file {}: {e}", cert_file.display()))?;
+        if certs.is_empty() {
+            return Err(anyhow!(
+                "no certificate found in file {}",
+                cert_file.display()
+            ));
+        }
+        let key = PKey::private_key_from_pem(&key_pem)
+            .map_err(|e| anyhow!("invalid private key file {}: {e}", key_file.display()))?;
+        Ok(Some((certs, key)))
+    }
+
+    /// Save the issued certificate chain and the private key.
+    /// The key file is written first, so the certificate file will always be the newest one.
+    pub(crate) fn save_certificate(&self, cert_pem: &[u8], key_pem: &[u8]) -> anyhow::Result<()> {
+        std::fs::create_dir_all(&self.cert_dir).map_err(|e| {
+            anyhow!(
+                "failed to create certificate dir {}: {e}",
+                self.cert_dir.display()
+            )
+        })?;
+        write_file(&self.key_file(), key_pem, true)?;
+        write_file(&self.cert_file(), cert_pem, false)
+    }
+}
+
+/// Write to a temp file and then rename to the target path, so the readers won't see partial data
+fn write_file(path: &Path, data: &[u8], private: bool) -> anyhow::Result<()> {
+    let mut tmp_path = path.as_os_str().to_os_string();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut file = open_file(&tmp_path, private)
+        .map_err(|e| anyhow!("failed to create file {}: {e}", tmp_path.display()))?;
+    file.write_all(data)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| anyhow!("failed to write file {}: {e}", tmp_path.display()))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| anyhow!("failed to rename to file {}: {e}", path.display()))
+}
+
+fn open_file(path: &Path, private: bool) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_storage(name: &str) -> (PathBuf, AcmeStorage) {
+        let dir =
+            std::env::temp_dir().join(format!("g3-acme-storage-{name}-{}", std::process::id()));
+        let mut config = AcmeConfig::default();
+        config.set_storage_dir(dir.clone());
+        config.add_domain("Example.net");
+        config.add_domain("www.example.net");
+        (dir, AcmeStorage::new(&config))
+    }
+
+    #[test]
+    fn layout() {
+        let (dir, storage) = new_storage("layout");
+        assert_eq!(storage.dir(), dir.as_path());
+        assert_eq!(
+            storage.cert_file(),
+            dir.join("certs").join("example.net").join("cert.pem")
+        );
+        assert_eq!(
+            storage.key_file(),
+            dir.join("certs").join("example.net").join("key.pem")
+        );
+    }
+
+    #[test]
+    fn account_key() {
+        let (dir, storage) = new_storage("account");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let key = storage.load_or_create_account_key().unwrap();
+        let key_file = dir.join(ACCOUNT_KEY_FILE);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(&key_file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let loaded = storage.load_or_create_account_key().unwrap();
+        assert_eq!(loaded.to_pem().unwrap(), key.to_pem().unwrap());
+
+        storage
+            .save_account_info("https://example.net/dir", "https://example.net/acct/1")
+            .unwrap();
+        let info = std::fs::read(dir.join(ACCOUNT_INFO_FILE)).unwrap();
+        let info: serde_json::Value = serde_json::from_slice(&info).unwrap();
+        assert_eq!(info["account"], "https://example.net/acct/1");
+
+        std::fs::write(&key_file, b"invalid").unwrap();
+        assert!(storage.load_or_create_account_key().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn certificate() {
+        let (dir, storage) = new_storage("cert");
+        assert!(storage.load_certificate().unwrap().is_none());
+
+        let key = crate::key::new_ec256_key().unwrap();
+        let challenge = crate::AcmeChallengeStore::default();
+        let _guard = challenge
+            .add(
+                crate::AcmeChallengeType::TlsAlpn01,
+                "example.net",
+                "token",
+                "token.thumbprint".to_string(),
+            )
+            .unwrap();
+        let cert = challenge.get_tls_alpn_cert("example.net").unwrap();
+        let cert_pem = cert.certificate().to_pem().unwrap();
+        let key_pem = key.private_key_to_pem_pkcs8().unwrap();
+
+        storage.save_certificate(&cert_pem, &key_pem).unwrap();
+        assert!(!dir.join("certs/example.net/cert.pem.tmp").exists());
+        let (certs, loaded_key) = storage.load_certificate().unwrap().unwrap();
+        assert_eq!(certs.len(), 1);
+        assert_eq!(certs[0].to_pem().unwrap(), cert_pem);
+        assert!(loaded_key.public_eq(&key));
+
+        std::fs::write(storage.cert_file(), b"").unwrap();
+        assert!(storage.load_certificate().is_err());
+        std::fs::remove_file(storage.key_file()).unwrap();
+        assert!(storage.load_certificate().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+#[cfg(feature = "rustls")]
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use log::warn;
+use openssl::ssl::{AlpnError, SslContext, SslContextBuilder, SslMethod};
+#[cfg(feature = "rustls")]
+use rustls::ServerConfig;
+#[cfg(feature = "rustls")]
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+#[cfg(feature = "rustls")]
+use rustls::server::ClientHello;
+
+use g3_types::net::TlsAlpn;
+
+use crate::{ACME_TLS_ALPN_PROTOCOL, AcmeChallengeStore};
+
+const ACME_TLS_ALPN_WIRE: &[u8] = b"\x0aacme-tls/1";
+
+/// Get the rustls server config to respond to the pending TLS-ALPN-01 challenge
+/// in the global challenge store
+#[cfg(feature = "rustls")]
+pub fn rustls_tls_alpn_config(client_hello: &ClientHello) -> Option<Arc<ServerConfig>> {
+    let mut client_alpn = client_hello.alpn()?;
+    if !client_alpn.any(|p| p == ACME_TLS_ALPN_PROTOCOL.as_bytes()) {
+        return None;
+    }
+    let cert = AcmeChallengeStore::global().get_tls_alpn_cert(client_hello.server_name()?)?;
+
+    let build = || -> anyhow::Result<Arc<ServerConfig>> {
+        let cert_der = cert
+            .certificate()
+            .to_der()
+            .map_err(|e| anyhow!("failed to encode certificate: {e}"))?;
+        let key_der = cert
+            .private_key()
+            .private_key_to_pkcs8()
+            .map_err(|e| anyhow!("failed to encode private key: {e}"))?;
+        let mut config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(cert_der)],
+                PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key_der)),
+            )
+            .map_err(|e| anyhow!("failed to build server config: {e}"))?;
+        config.alpn_protocols = vec![ACME_TLS_ALPN_PROTOCOL.as_bytes().to_vec()];
+        Ok(Arc::new(config))
+    };
+    build()
+        .inspect_err(|e| warn!("failed to build ACME TLS-ALPN-01 tls config: {e:?}"))
+        .ok()
+}
+
+/// Get the openssl context to respond to the pending TLS-ALPN-01 challenge
+/// in the global challenge store
+pub fn openssl_tls_alpn_context(server_name: &str, client_alpn: &TlsAlpn) -> Option<SslContext> {
+    if client_alpn
+        .retain_clone(|p| p == ACME_TLS_ALPN_PROTOCOL.as_bytes())
+        .is_empty()
+    {
+        return None;
+    }
+    let cert = AcmeChallengeStore::global().get_tls_alpn_cert(server_name)?;
+
+    let build = || -> anyhow::Result<SslContext> {
+        let mut builder = SslContextBuilder::new(SslMethod::tls_server())
+            .map_err(|e| anyhow!("failed to create ssl context builder: {e}"))?;
+        builder
+            .set_certificate(cert.certificate())
+            .map_err(|e| anyhow!("failed to set certificate: {e}"))?;
+        builder
+            .set_private_key(cert.private_key())
+            .map_err(|e| anyhow!("failed to set private key: {e}"))?;
+        builder.set_alpn_select_callback(|_, client| {
+            openssl::ssl::select_next_proto(ACME_TLS_ALPN_WIRE, client).ok_or(AlpnError::NOACK)
+        });
+        Ok(builder.build())
+    };
+    build()
+        .inspect_err(|e| warn!("failed to build ACME TLS-ALPN-01 ssl context: {e:?}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AcmeChallengeType;
+
+    #[test]
+    fn openssl_context() {
+        let acme_alpn = TlsAlpn::from_extension_value(b"\x00\x0e\x02h2\x0aacme-tls/1").unwrap();
+        let h2_alpn = TlsAlpn::from_extension_value(b"\x00\x03\x02h2").unwrap();
+        assert!(openssl_tls_alpn_context("alpn.example.net", &acme_alpn).is_none());
+
+        let guard = AcmeChallengeStore::global()
+            .add(
+                AcmeChallengeType::TlsAlpn01,
+                "alpn.example.net",
+                "token",
+                "token.thumbprint".to_string(),
+            )
+            .unwrap();
+        assert!(openssl_tls_alpn_context("alpn.example.net", &acme_alpn).is_some());
+        assert!(openssl_tls_alpn_context("alpn.example.net", &h2_alpn).is_none());
+        assert!(openssl_tls_alpn_context("other.example.net", &acme_alpn).is_none());
+        drop(guard);
+        assert!(openssl_tls_alpn_context("alpn.example.net", &acme_alpn).is_none());
+    }
+}
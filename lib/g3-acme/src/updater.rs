@@ -0,0 +1,57 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use log::{info, warn};
+
+use crate::{AcmeCertManager, AcmeChallengeStore, AcmeConfig};
+
+pub trait AcmeCertHolder: Send + Sync + 'static {
+    /// Rebuild the tls config with the newly issued certificate. This is a blocking call.
+    fn reload_acme_cert(&self) -> anyhow::Result<()>;
+}
+
+/// Spawn the background task to obtain and renew the certificate by ACME,
+/// using the global challenge store.
+/// The holder will reload the certificate after a new one has been issued.
+/// The task will quit after the holder has been dropped.
+pub fn spawn_cert_updater<T: AcmeCertHolder>(holder: &Arc<T>, config: &Arc<AcmeConfig>) {
+    let manager = AcmeCertManager::new(config.clone(), AcmeChallengeStore::global().clone());
+    let holder = Arc::downgrade(holder);
+    tokio::spawn(async move {
+        let name = manager
+            .config()
+            .domains()
+            .first()
+            .cloned()
+            .unwrap_or_default();
+        loop {
+            if holder.strong_count() == 0 {
+                break;
+            }
+
+            let wait = match manager.renew_if_needed().await {
+                Ok(true) => {
+                    let Some(holder) = holder.upgrade() else {
+                        break;
+                    };
+                    match tokio::task::spawn_blocking(move || holder.reload_acme_cert()).await {
+                        Ok(Ok(_)) => info!("ACME certificate for {name} loaded"),
+                        Ok(Err(e)) => warn!("failed to load ACME certificate for {name}: {e:?}"),
+                        Err(e) => warn!("failed to run ACME certificate reload job: {e}"),
+                    }
+                    manager.config().check_interval()
+                }
+                Ok(false) => manager.config().check_interval(),
+                Err(e) => {
+                    warn!("failed to get ACME certificate for {name}: {e:?}");
+                    manager.config().retry_interval()
+                }
+            };
+            tokio::time::sleep(wait).await;
+        }
+    });
+}
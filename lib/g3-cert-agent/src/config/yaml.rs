@@ -6,6 +6,8 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use super::CertAgentConfig;
 
 impl CertAgentConfig {
@@ -84,3 +86,18 @@ impl CertAgentConfig {
         }
     }
 }
+
+impl YamlDump for CertAgentConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("cache_request_batch_count", &self.cache_request_batch_count)
+            .set("cache_request_timeout", &self.cache_request_timeout)
+            .set("cache_vanish_wait", &self.cache_vanish_wait)
+            .set("query_peer_addr", &self.query_peer_addr)
+            .set("query_socket_buffer", &self.query_socket_buffer)
+            .set("query_wait_timeout", &self.query_wait_timeout)
+            .set("protective_cache_ttl", &self.protective_cache_ttl)
+            .set("maximum_cache_ttl", &self.maximum_cache_ttl);
+        map.build()
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_types::net::{RustlsServerConfigBuilder, TcpListenConfig};
+use g3_types::sync::GlobalInit;
+
+use super::GeneralControllerConfig;
+
+const DEFAULT_MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Clone)]
+pub(crate) struct HttpControllerConfig {
+    pub(crate) general: GeneralControllerConfig,
+    pub(crate) listen: TcpListenConfig,
+    pub(crate) tls_server: Option<RustlsServerConfigBuilder>,
+    pub(crate) bearer_tokens: Vec<String>,
+    pub(crate) max_header_size: usize,
+    pub(crate) max_body_size: usize,
+}
+
+static HTTP_CONTROLLER_CONFIG: GlobalInit<Option<Arc<HttpControllerConfig>>> =
+    GlobalInit::new(None);
+
+impl HttpControllerConfig {
+    fn new(listen: TcpListenConfig) -> Self {
+        HttpControllerConfig {
+            general: GeneralControllerConfig::new(),
+            listen,
+            tls_server: None,
+            bearer_tokens: Vec::new(),
+            max_header_size: 4096,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    pub(crate) fn get() -> Option<Arc<HttpControllerConfig>> {
+        HTTP_CONTROLLER_CONFIG.as_ref().clone()
+    }
+
+    pub(crate) fn set_default(v: &Yaml) -> anyhow::Result<()> {
+        match v {
+            Yaml::Hash(map) => {
+                let lookup_dir = crate::config::get_lookup_dir(None)?;
+                let mut config = HttpControllerConfig::new(TcpListenConfig::default());
+                let mut listen_set = false;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "listen" => {
+                        config.listen = g3_yaml::value::as_tcp_listen_config(v)
+                            .context(format!("invalid tcp listen config value for key {k}"))?;
+                        listen_set = true;
+                        Ok(())
+                    }
+                    "tls_server" => {
+                        let builder =
+                            g3_yaml::value::as_rustls_server_config_builder(v, Some(lookup_dir))
+                                .context(format!(
+                                    "invalid rustls server config value for key {k}"
+                                ))?;
+                        config.tls_server = Some(builder);
+                        Ok(())
+                    }
+                    "bearer_token" | "bearer_tokens" => {
+                        config.bearer_tokens =
+                            g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                                .context(format!("invalid bearer token list value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_header_size" => {
+                        config.max_header_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_body_size" => {
+                        config.max_body_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "recv_timeout" | "send_timeout" => config.general.set(k, v),
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                if !listen_set {
+                    return Err(anyhow!("no listen address set"));
+                }
+                config.check()?;
+                HTTP_CONTROLLER_CONFIG.set(Some(Arc::new(config)));
+                Ok(())
+            }
+            Yaml::Null => Ok(()),
+            _ => Err(anyhow!("root value type should be hash")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        self.listen.check()?;
+        if self.bearer_tokens.iter().any(|s| s.is_empty()) {
+            return Err(anyhow!("empty bearer token is not allowed"));
+        }
+        let client_auth = self
+            .tls_server
+            .as_ref()
+            .map(|builder| builder.client_auth())
+            .unwrap_or(false);
+        if self.bearer_tokens.is_empty() && !client_auth {
+            return Err(anyhow!(
+                "either bearer token or tls client auth should be configured"
+            ));
+        }
+        if self.tls_server.is_none() && !self.listen.address().ip().is_loopback() {
+            // the bearer tokens would be sent in plain text
+            return Err(anyhow!(
+                "tls server should be configured when listen on non-loopback address"
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+
+    fn new_config(addr: &str, tokens: &[&str]) -> HttpControllerConfig {
+        let addr = SocketAddr::from_str(addr).unwrap();
+        let mut config = HttpControllerConfig::new(TcpListenConfig::new(addr));
+        config.bearer_tokens = tokens.iter().map(|s| s.to_string()).collect();
+        config
+    }
+
+    #[test]
+    fn check_auth_set() {
+        let mut config = new_config("127.0.0.1:8080", &["abc"]);
+        assert!(config.check().is_ok());
+
+        let mut config = new_config("[::1]:8080", &["abc", "def"]);
+        assert!(config.check().is_ok());
+
+        let mut config = new_config("127.0.0.1:8080", &[]);
+        assert!(config.check().is_err());
+
+        let mut config = new_config("127.0.0.1:8080", &["abc", ""]);
+        assert!(config.check().is_err());
+    }
+
+    #[test]
+    fn check_plain_listen() {
+        let mut config = new_config("0.0.0.0:8080", &["abc"]);
+        assert!(config.check().is_err());
+
+        let mut config = new_config("[::]:8080", &["abc"]);
+        assert!(config.check().is_err());
+
+        let mut config = new_config("192.0.2.1:8080", &["abc"]);
+        assert!(config.check().is_err());
+
+        let mut config = new_config("127.0.0.1:0", &["abc"]);
+        assert!(config.check().is_err());
+    }
+}
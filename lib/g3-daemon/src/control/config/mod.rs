@@ -6,6 +6,8 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+#[cfg(feature = "http-control")]
+mod http;
 mod local;
 
 const DEFAULT_RECV_TIMEOUT: u64 = 30;
@@ -50,6 +52,8 @@ impl GeneralControllerConfig {
     }
 }
 
+#[cfg(feature = "http-control")]
+pub(crate) use http::HttpControllerConfig;
 pub(crate) use local::LocalControllerConfig;
 
 pub fn load(v: &Yaml) -> anyhow::Result<()> {
@@ -57,6 +61,8 @@ pub fn load(v: &Yaml) -> anyhow::Result<()> {
         Yaml::Hash(map) => {
             g3_yaml::foreach_kv(map, |k, v| match k {
                 "local" => LocalControllerConfig::set_default(v),
+                #[cfg(feature = "http-control")]
+                "http" => HttpControllerConfig::set_default(v),
                 _ => Err(anyhow!("invalid key '{k}'")),
             })?;
             Ok(())
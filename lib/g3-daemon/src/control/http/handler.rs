@@ -0,0 +1,68 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use async_trait::async_trait;
+use http::StatusCode;
+use serde_json::{Map, Value};
+
+pub enum HttpControlError {
+    NotFound(String),
+    BadRequest(String),
+    Failed(anyhow::Error),
+}
+
+impl HttpControlError {
+    pub fn unknown_kind(kind: &str) -> Self {
+        HttpControlError::NotFound(format!("unknown resource type {kind}"))
+    }
+
+    pub fn not_found(kind: &str, name: &str) -> Self {
+        HttpControlError::NotFound(format!("no {kind} named {name} found"))
+    }
+
+    pub(super) fn status_code(&self) -> StatusCode {
+        match self {
+            HttpControlError::NotFound(_) => StatusCode::NOT_FOUND,
+            HttpControlError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            HttpControlError::Failed(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub(super) fn reason(&self) -> String {
+        match self {
+            HttpControlError::NotFound(s) | HttpControlError::BadRequest(s) => s.clone(),
+            HttpControlError::Failed(e) => format!("{e:?}"),
+        }
+    }
+}
+
+impl From<anyhow::Error> for HttpControlError {
+    fn from(e: anyhow::Error) -> Self {
+        HttpControlError::Failed(e)
+    }
+}
+
+pub type HttpControlResult<T> = Result<T, HttpControlError>;
+
+/// The daemon specific operations exposed by the http controller.
+///
+/// The `kind` argument is the resource type segment in the request path, like `servers`,
+/// and the handler should return [`HttpControlError::NotFound`] for unknown ones.
+#[async_trait]
+pub trait HttpControlHandler: Send + Sync {
+    fn version(&self) -> &'static str;
+
+    fn list(&self, kind: &str) -> HttpControlResult<Vec<String>>;
+
+    fn status(&self, kind: &str, name: &str) -> HttpControlResult<Map<String, Value>>;
+
+    async fn reload(&self, kind: &str, name: &str) -> HttpControlResult<()>;
+
+    async fn publish(&self, kind: &str, _name: &str, _data: String) -> HttpControlResult<()> {
+        Err(HttpControlError::BadRequest(format!(
+            "publish is not supported for {kind}"
+        )))
+    }
+}
@@ -0,0 +1,145 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, anyhow};
+use log::{debug, warn};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+use tokio_rustls::TlsAcceptor;
+
+use g3_types::net::RustlsServerConfig;
+
+use super::config::HttpControllerConfig;
+
+mod handler;
+pub use handler::{HttpControlError, HttpControlHandler, HttpControlResult};
+
+mod serve;
+use serve::HttpControlConnection;
+
+static HTTP_CONTROLLER_ABORT_CHANNEL: Mutex<Option<oneshot::Sender<oneshot::Sender<()>>>> =
+    Mutex::new(None);
+
+pub struct HttpController {}
+
+impl HttpController {
+    /// Start the http controller if it's configured
+    pub fn start<H>(handler: H) -> anyhow::Result<()>
+    where
+        H: HttpControlHandler + 'static,
+    {
+        let Some(config) = HttpControllerConfig::get() else {
+            return Ok(());
+        };
+
+        let tls_config = match &config.tls_server {
+            Some(builder) => Some(
+                builder
+                    .build()
+                    .context("failed to build tls server config")?,
+            ),
+            None => None,
+        };
+
+        let mut abort_channel = HTTP_CONTROLLER_ABORT_CHANNEL.lock().unwrap();
+        if abort_channel.is_some() {
+            return Err(anyhow!("http controller already existed"));
+        }
+
+        let listener = g3_socket::tcp::new_std_listener(&config.listen)
+            .map_err(|e| anyhow!("failed to listen on {}: {e}", config.listen.address()))?;
+        let listener = TcpListener::from_std(listener)
+            .map_err(|e| anyhow!("failed to convert listener: {e}"))?;
+
+        let (sender, receiver) = oneshot::channel();
+        *abort_channel = Some(sender);
+        let handler = Arc::new(handler);
+        tokio::spawn(run(listener, config, tls_config, handler, receiver));
+        debug!("http controller started");
+        Ok(())
+    }
+
+    pub async fn abort() {
+        let (sender, receiver) = oneshot::channel();
+
+        let Some(quit_sender) = HTTP_CONTROLLER_ABORT_CHANNEL.lock().unwrap().take() else {
+            return;
+        };
+        if quit_sender.send(sender).is_ok() {
+            let _ = receiver.await;
+        }
+    }
+}
+
+async fn run<H>(
+    listener: TcpListener,
+    config: Arc<HttpControllerConfig>,
+    tls_config: Option<RustlsServerConfig>,
+    handler: Arc<H>,
+    mut abort_receiver: oneshot::Receiver<oneshot::Sender<()>>,
+) where
+    H: HttpControlHandler + 'static,
+{
+    loop {
+        tokio::select! {
+            biased;
+
+            r = &mut abort_receiver => {
+                drop(listener);
+                if let Ok(finish_sender) = r {
+                    let _ = finish_sender.send(());
+                }
+                debug!("http controller aborted");
+                break;
+            }
+            r = listener.accept() => {
+                match r {
+                    Ok((stream, peer_addr)) => {
+                        debug!("new http control connection from {peer_addr}");
+                        let ctx = HttpControlConnection::new(config.clone(), handler.clone());
+                        let tls_config = tls_config.clone();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(ctx, stream, tls_config).await {
+                                warn!("error handle http control client {peer_addr}: {e:?}");
+                            }
+                        });
+                    }
+                    Err(e) => {
+                        warn!("http controller accept: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn handle_connection<H>(
+    ctx: HttpControlConnection<H>,
+    stream: TcpStream,
+    tls_config: Option<RustlsServerConfig>,
+) -> anyhow::Result<()>
+where
+    H: HttpControlHandler + 'static,
+{
+    match tls_config {
+        Some(tls_config) => {
+            let acceptor = TlsAcceptor::from(tls_config.driver);
+            let tls_stream = match tokio::time::timeout(
+                tls_config.accept_timeout,
+                acceptor.accept(stream),
+            )
+            .await
+            {
+                Ok(Ok(s)) => s,
+                Ok(Err(e)) => return Err(anyhow!("tls handshake failed: {e}")),
+                Err(_) => return Err(anyhow!("tls handshake timed out")),
+            };
+            ctx.run(tls_stream).await
+        }
+        None => ctx.run(stream).await,
+    }
+}
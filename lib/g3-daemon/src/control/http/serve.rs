@@ -0,0 +1,426 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::{Method, StatusCode, header};
+use serde_json::{Map, Value};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, BufReader};
+
+use g3_http::HttpBodyReader;
+use g3_http::server::{HttpRequestParseError, HttpTransparentRequest};
+use g3_io_ext::LimitedWriteExt;
+
+use super::{HttpControlHandler, HttpControlResult};
+use crate::control::config::HttpControllerConfig;
+
+const API_PATH_PREFIX: &str = "/api/v1/";
+
+struct HttpControlResponse {
+    code: StatusCode,
+    body: Value,
+}
+
+impl HttpControlResponse {
+    fn ok(body: Value) -> Self {
+        HttpControlResponse {
+            code: StatusCode::OK,
+            body,
+        }
+    }
+
+    fn success() -> Self {
+        HttpControlResponse::ok(operation_ok())
+    }
+
+    fn error(code: StatusCode, reason: String) -> Self {
+        let mut map = Map::with_capacity(1);
+        map.insert("error".to_string(), Value::String(reason));
+        HttpControlResponse {
+            code,
+            body: Value::Object(map),
+        }
+    }
+
+    fn from_result<T, F>(r: HttpControlResult<T>, f: F) -> Self
+    where
+        F: FnOnce(T) -> Value,
+    {
+        match r {
+            Ok(v) => HttpControlResponse::ok(f(v)),
+            Err(e) => HttpControlResponse::error(e.status_code(), e.reason()),
+        }
+    }
+
+    fn serialize(&self, keep_alive: bool) -> Vec<u8> {
+        let body = self.body.to_string();
+        let mut buf = Vec::with_capacity(256 + body.len());
+        buf.extend_from_slice(
+            format!(
+                "HTTP/1.1 {} {}\r\n",
+                self.code.as_u16(),
+                self.code.canonical_reason().unwrap_or_default()
+            )
+            .as_bytes(),
+        );
+        buf.extend_from_slice(b"Content-Type: application/json\r\n");
+        buf.extend_from_slice(format!("Content-Length: {}\r\n", body.len()).as_bytes());
+        if self.code == StatusCode::UNAUTHORIZED {
+            buf.extend_from_slice(b"WWW-Authenticate: Bearer\r\n");
+        }
+        if keep_alive {
+            buf.extend_from_slice(b"Connection: keep-alive\r\n");
+        } else {
+            buf.extend_from_slice(b"Connection: close\r\n");
+        }
+        buf.extend_from_slice(b"\r\n");
+        buf.extend_from_slice(body.as_bytes());
+        buf
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum HttpControlRoute<'a> {
+    Version,
+    Offline,
+    List(&'a str),
+    Status(&'a str, &'a str),
+    Reload(&'a str, &'a str),
+    Publish(&'a str, &'a str),
+    MethodNotAllowed,
+    NotFound,
+}
+
+impl<'a> HttpControlRoute<'a> {
+    fn new(method: &Method, path: &'a str) -> Self {
+        let Some(path) = path.strip_prefix(API_PATH_PREFIX) else {
+            return HttpControlRoute::NotFound;
+        };
+        let path = path.trim_end_matches('/');
+        if path.is_empty() {
+            return HttpControlRoute::NotFound;
+        }
+        let segments: Vec<&str> = path.split('/').collect();
+
+        match (method, segments.as_slice()) {
+            (&Method::GET, ["version"]) => HttpControlRoute::Version,
+            (&Method::POST, ["offline"]) => HttpControlRoute::Offline,
+            (_, ["version" | "offline"]) => HttpControlRoute::MethodNotAllowed,
+            (&Method::GET, [kind]) if !kind.is_empty() => HttpControlRoute::List(kind),
+            (&Method::GET, [kind, name]) => HttpControlRoute::Status(kind, name),
+            (&Method::POST, [kind, name, "reload"]) => HttpControlRoute::Reload(kind, name),
+            (&Method::POST, [kind, name, "publish"]) => HttpControlRoute::Publish(kind, name),
+            (_, [_] | [_, _] | [_, _, "reload" | "publish"]) => HttpControlRoute::MethodNotAllowed,
+            _ => HttpControlRoute::NotFound,
+        }
+    }
+}
+
+fn operation_ok() -> Value {
+    let mut map = Map::with_capacity(1);
+    map.insert("ok".to_string(), Value::String("success".to_string()));
+    Value::Object(map)
+}
+
+pub(super) struct HttpControlConnection<H: HttpControlHandler> {
+    config: Arc<HttpControllerConfig>,
+    handler: Arc<H>,
+}
+
+impl<H: HttpControlHandler> HttpControlConnection<H> {
+    pub(super) fn new(config: Arc<HttpControllerConfig>, handler: Arc<H>) -> Self {
+        HttpControlConnection { config, handler }
+    }
+
+    pub(super) async fn run<S>(self, stream: S) -> anyhow::Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let recv_timeout = Duration::from_secs(self.config.general.recv_timeout);
+        let send_timeout = Duration::from_secs(self.config.general.send_timeout);
+
+        let (r, mut w) = tokio::io::split(stream);
+        let mut reader = BufReader::new(r);
+        loop {
+            let req = match tokio::time::timeout(
+                recv_timeout,
+                HttpTransparentRequest::parse(&mut reader, self.config.max_header_size, false),
+            )
+            .await
+            {
+                Ok(Ok((req, _))) => req,
+                Ok(Err(HttpRequestParseError::ClientClosed)) => return Ok(()),
+                Ok(Err(e)) => {
+                    let rsp = HttpControlResponse::error(
+                        e.status_code().unwrap_or(StatusCode::BAD_REQUEST),
+                        e.to_string(),
+                    );
+                    let _ = tokio::time::timeout(
+                        send_timeout,
+                        w.write_all_flush(&rsp.serialize(false)),
+                    )
+                    .await;
+                    return Err(anyhow!("invalid request: {e}"));
+                }
+                Err(_) => return Ok(()),
+            };
+
+            if !check_auth(&self.config.bearer_tokens, &req) {
+                let rsp = HttpControlResponse::error(
+                    StatusCode::UNAUTHORIZED,
+                    "unauthorized".to_string(),
+                );
+                let _ =
+                    tokio::time::timeout(send_timeout, w.write_all_flush(&rsp.serialize(false)))
+                        .await;
+                return Ok(());
+            }
+
+            let mut body = Vec::new();
+            if let Some(body_type) = req.body_type() {
+                let mut body_reader = HttpBodyReader::new(&mut reader, body_type, 1024)
+                    .take(self.config.max_body_size as u64 + 1);
+                match tokio::time::timeout(recv_timeout, body_reader.read_to_end(&mut body)).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => return Err(anyhow!("failed to read request body: {e}")),
+                    Err(_) => return Err(anyhow!("timeout to read request body")),
+                }
+                if body.len() > self.config.max_body_size {
+                    let rsp = HttpControlResponse::error(
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        "too large request body".to_string(),
+                    );
+                    let _ = tokio::time::timeout(
+                        send_timeout,
+                        w.write_all_flush(&rsp.serialize(false)),
+                    )
+                    .await;
+                    return Ok(());
+                }
+            }
+
+            let rsp = self.handle(&req, body).await;
+
+            let keep_alive = req.keep_alive();
+            match tokio::time::timeout(send_timeout, w.write_all_flush(&rsp.serialize(keep_alive)))
+                .await
+            {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => return Err(anyhow!("failed to send response: {e}")),
+                Err(_) => return Err(anyhow!("timeout to send response")),
+            }
+            if !keep_alive {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn handle(&self, req: &HttpTransparentRequest, body: Vec<u8>) -> HttpControlResponse {
+        match HttpControlRoute::new(&req.method, req.uri.path()) {
+            HttpControlRoute::Version => {
+                let mut map = Map::with_capacity(1);
+                map.insert(
+                    "version".to_string(),
+                    Value::String(self.handler.version().to_string()),
+                );
+                HttpControlResponse::ok(Value::Object(map))
+            }
+            HttpControlRoute::Offline => {
+                crate::control::quit::start_graceful_shutdown().await;
+                HttpControlResponse::success()
+            }
+            HttpControlRoute::List(kind) => {
+                HttpControlResponse::from_result(self.handler.list(kind), |names| {
+                    Value::Array(names.into_iter().map(Value::String).collect())
+                })
+            }
+            HttpControlRoute::Status(kind, name) => {
+                HttpControlResponse::from_result(self.handler.status(kind, name), Value::Object)
+            }
+            HttpControlRoute::Reload(kind, name) => {
+                HttpControlResponse::from_result(self.handler.reload(kind, name).await, |_| {
+                    operation_ok()
+                })
+            }
+            HttpControlRoute::Publish(kind, name) => match String::from_utf8(body) {
+                Ok(data) => HttpControlResponse::from_result(
+                    self.handler.publish(kind, name, data).await,
+                    |_| operation_ok(),
+                ),
+                Err(_) => HttpControlResponse::error(
+                    StatusCode::BAD_REQUEST,
+                    "request body is not valid utf-8 string".to_string(),
+                ),
+            },
+            HttpControlRoute::MethodNotAllowed => HttpControlResponse::error(
+                StatusCode::METHOD_NOT_ALLOWED,
+                format!("method {} is not allowed", req.method),
+            ),
+            HttpControlRoute::NotFound => {
+                HttpControlResponse::error(StatusCode::NOT_FOUND, "unknown path".to_string())
+            }
+        }
+    }
+}
+
+fn check_auth(bearer_tokens: &[String], req: &HttpTransparentRequest) -> bool {
+    if bearer_tokens.is_empty() {
+        // client cert has already been verified in tls handshake
+        return true;
+    }
+
+    let Some(value) = req.end_to_end_headers.get(header::AUTHORIZATION) else {
+        return false;
+    };
+    let Some((scheme, token)) = value.to_str().split_once(' ') else {
+        return false;
+    };
+    if !scheme.eq_ignore_ascii_case("bearer") {
+        return false;
+    }
+    let token = token.trim();
+    bearer_tokens
+        .iter()
+        .any(|s| constant_time_eq(s.as_bytes(), token.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn parse_request(data: &[u8]) -> HttpTransparentRequest {
+        let mut reader = BufReader::new(data);
+        let (req, _) = HttpTransparentRequest::parse(&mut reader, 4096, false)
+            .await
+            .unwrap();
+        req
+    }
+
+    #[test]
+    fn constant_time() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+        assert!(!constant_time_eq(b"ab", b"abc"));
+        assert!(!constant_time_eq(b"", b"a"));
+    }
+
+    #[tokio::test]
+    async fn auth() {
+        let tokens = vec!["token-a".to_string(), "token-b".to_string()];
+
+        let req = parse_request(b"GET /api/v1/version HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(!check_auth(&tokens, &req));
+        assert!(check_auth(&[], &req));
+
+        let req =
+            parse_request(b"GET /api/v1/version HTTP/1.1\r\nAuthorization: Bearer token-b\r\n\r\n")
+                .await;
+        assert!(check_auth(&tokens, &req));
+
+        let req = parse_request(
+            b"GET /api/v1/version HTTP/1.1\r\nAuthorization: bearer  token-a \r\n\r\n",
+        )
+        .await;
+        assert!(check_auth(&tokens, &req));
+
+        let req =
+            parse_request(b"GET /api/v1/version HTTP/1.1\r\nAuthorization: Bearer token-c\r\n\r\n")
+                .await;
+        assert!(!check_auth(&tokens, &req));
+
+        let req = parse_request(
+            b"GET /api/v1/version HTTP/1.1\r\nAuthorization: Basic dG9rZW4tYQ==\r\n\r\n",
+        )
+        .await;
+        assert!(!check_auth(&tokens, &req));
+
+        let req =
+            parse_request(b"GET /api/v1/version HTTP/1.1\r\nAuthorization: token-a\r\n\r\n").await;
+        assert!(!check_auth(&tokens, &req));
+    }
+
+    #[test]
+    fn route() {
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/version"),
+            HttpControlRoute::Version
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::POST, "/api/v1/offline"),
+            HttpControlRoute::Offline
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/servers"),
+            HttpControlRoute::List("servers")
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/servers/"),
+            HttpControlRoute::List("servers")
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/servers/http"),
+            HttpControlRoute::Status("servers", "http")
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::POST, "/api/v1/servers/http/reload"),
+            HttpControlRoute::Reload("servers", "http")
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::POST, "/api/v1/user-groups/default/publish"),
+            HttpControlRoute::Publish("user-groups", "default")
+        );
+    }
+
+    #[test]
+    fn route_error() {
+        assert_eq!(
+            HttpControlRoute::new(&Method::POST, "/api/v1/version"),
+            HttpControlRoute::MethodNotAllowed
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/offline"),
+            HttpControlRoute::MethodNotAllowed
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::DELETE, "/api/v1/servers/http"),
+            HttpControlRoute::MethodNotAllowed
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/servers/http/reload"),
+            HttpControlRoute::MethodNotAllowed
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/"),
+            HttpControlRoute::NotFound
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v2/version"),
+            HttpControlRoute::NotFound
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/version"),
+            HttpControlRoute::NotFound
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::POST, "/api/v1/servers/http/stop"),
+            HttpControlRoute::NotFound
+        );
+        assert_eq!(
+            HttpControlRoute::new(&Method::GET, "/api/v1/servers/http/reload/x"),
+            HttpControlRoute::NotFound
+        );
+    }
+}
@@ -19,6 +19,11 @@ pub mod panic;
 
 pub mod capnp;
 
+#[cfg(feature = "http-control")]
+mod http;
+#[cfg(feature = "http-control")]
+pub use http::{HttpControlError, HttpControlHandler, HttpControlResult, HttpController};
+
 pub mod config;
 use config::{GeneralControllerConfig, LocalControllerConfig};
 
@@ -16,6 +16,7 @@ static THREAD_HANDLE: Mutex<Option<JoinHandle<()>>> = Mutex::new(None);
 enum Msg {
     CancelShutdown,
     ReleaseController(oneshot::Sender<()>),
+    SaveResolverCache(oneshot::Sender<()>),
     ConfirmShutdown,
 }
 
@@ -44,6 +45,21 @@ pub async fn release_old_controller() {
     }
 }
 
+/// Ask the old daemon to save its resolver cache snapshots, so the new daemon can load them
+pub async fn save_old_resolver_cache() {
+    let msg_channel = MSG_CHANNEL.lock().unwrap().clone();
+    if let Some(sender) = msg_channel {
+        let (done_sender, done_receiver) = oneshot::channel();
+        if sender
+            .send(Msg::SaveResolverCache(done_sender))
+            .await
+            .is_ok()
+        {
+            let _ = done_receiver.await;
+        }
+    }
+}
+
 pub fn finish() {
     let msg_channel = MSG_CHANNEL.lock().unwrap().take();
     if let Some(sender) = msg_channel {
@@ -65,6 +81,10 @@ pub trait UpgradeAction: Sized {
     #[allow(async_fn_in_trait)]
     async fn release_controller(&self) -> anyhow::Result<()>;
     #[allow(async_fn_in_trait)]
+    async fn save_resolver_cache(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+    #[allow(async_fn_in_trait)]
     async fn confirm_shutdown(&self) -> anyhow::Result<()>;
 
     fn connect_to_old_daemon() {
@@ -108,6 +128,12 @@ async fn connect_run<T: UpgradeAction>(
                         }
                         let _ = finish_sender.send(());
                     }
+                    Msg::SaveResolverCache(finish_sender) => {
+                        if let Err(e) = action.save_resolver_cache().await {
+                            warn!("SaveResolverCache upgrade request failed: {e}");
+                        }
+                        let _ = finish_sender.send(());
+                    }
                     Msg::ConfirmShutdown => return action.confirm_shutdown().await,
                 }
             }
@@ -6,6 +6,7 @@
 use std::future::poll_fn;
 use std::io::{self, IoSliceMut};
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 use log::{info, warn};
 use tokio::net::UdpSocket;
@@ -19,8 +20,10 @@ use g3_types::net::UdpListenConfig;
 use crate::server::{BaseServer, ReloadServer, ServerReloadCommand};
 
 pub trait ReceiveUdpServer: BaseServer {
+    /// the listen socket can be used to send reply packets back to the client
     fn receive_udp_packet(
         &self,
+        socket: &Arc<UdpSocket>,
         packet: &[u8],
         client_addr: SocketAddr,
         server_addr: SocketAddr,
@@ -90,7 +93,7 @@ where
 
     async fn run(
         mut self,
-        socket: UdpSocket,
+        socket: Arc<UdpSocket>,
         listen_addr: SocketAddr,
         mut server_reload_channel: broadcast::Receiver<ServerReloadCommand>,
     ) {
@@ -129,7 +132,7 @@ where
                     match r {
                         Ok((len, peer_addr, local_addr)) => {
                             // TODO add stats
-                            self.server.receive_udp_packet(&buf[..len], peer_addr, local_addr, self.worker_id);
+                            self.server.receive_udp_packet(&socket, &buf[..len], peer_addr, local_addr, self.worker_id);
                         }
                         Err(e) => {
                             warn!("SRT[{}_v{}#{}] error receiving data from socket, error: {e}",
@@ -156,7 +159,10 @@ where
         let peer_addr = hdr
             .src_addr()
             .ok_or_else(|| io::Error::other("unable to get peer address"))?;
-        let local_addr = hdr.dst_addr(listen_addr);
+        // transparent listen sockets should report the original destination address
+        let local_addr = hdr
+            .orig_dst_addr()
+            .unwrap_or_else(|| hdr.dst_addr(listen_addr));
 
         Ok((hdr.n_recv, peer_addr, local_addr))
     }
@@ -184,7 +190,8 @@ where
             match UdpSocket::from_std(socket) {
                 Ok(socket) => {
                     self.pre_start();
-                    self.run(socket, listen_addr, server_reload_channel).await;
+                    self.run(Arc::new(socket), listen_addr, server_reload_channel)
+                        .await;
                 }
                 Err(e) => {
                     warn!(
@@ -12,4 +12,4 @@ mod connection;
 pub use connection::ClientConnectionInfo;
 
 mod runtime;
-pub use runtime::{BaseServer, ReloadServer, ServerExt, ServerReloadCommand};
+pub use runtime::{BaseServer, ReloadServer, ServerExt, ServerReloadCommand, select_consistent};
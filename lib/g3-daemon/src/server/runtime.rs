@@ -36,37 +36,50 @@ pub trait ServerExt: BaseServer {
     where
         T: SelectiveItem,
     {
-        #[derive(Hash)]
-        struct ConsistentKey {
-            client_ip: IpAddr,
-            server_ip: IpAddr,
-        }
+        select_consistent(nodes, pick_policy, cc_info)
+    }
+}
 
-        match pick_policy {
-            SelectivePickPolicy::Random => nodes.pick_random(),
-            SelectivePickPolicy::Serial => nodes.pick_serial(),
-            SelectivePickPolicy::RoundRobin => nodes.pick_round_robin(),
-            SelectivePickPolicy::Ketama => {
-                let key = ConsistentKey {
-                    client_ip: cc_info.client_ip(),
-                    server_ip: cc_info.server_ip(),
-                };
-                nodes.pick_ketama(&key)
-            }
-            SelectivePickPolicy::Rendezvous => {
-                let key = ConsistentKey {
-                    client_ip: cc_info.client_ip(),
-                    server_ip: cc_info.server_ip(),
-                };
-                nodes.pick_rendezvous(&key)
-            }
-            SelectivePickPolicy::JumpHash => {
-                let key = ConsistentKey {
-                    client_ip: cc_info.client_ip(),
-                    server_ip: cc_info.server_ip(),
-                };
-                nodes.pick_jump(&key)
-            }
+/// Select a node using the client and server ip in `cc_info` as the key for consistent hash
+pub fn select_consistent<'a, T>(
+    nodes: &'a SelectiveVec<T>,
+    pick_policy: SelectivePickPolicy,
+    cc_info: &ClientConnectionInfo,
+) -> &'a T
+where
+    T: SelectiveItem,
+{
+    #[derive(Hash)]
+    struct ConsistentKey {
+        client_ip: IpAddr,
+        server_ip: IpAddr,
+    }
+
+    match pick_policy {
+        SelectivePickPolicy::Random => nodes.pick_random(),
+        SelectivePickPolicy::Serial => nodes.pick_serial(),
+        SelectivePickPolicy::RoundRobin => nodes.pick_round_robin(),
+        SelectivePickPolicy::WeightedRoundRobin => nodes.pick_weighted_round_robin(),
+        SelectivePickPolicy::Ketama => {
+            let key = ConsistentKey {
+                client_ip: cc_info.client_ip(),
+                server_ip: cc_info.server_ip(),
+            };
+            nodes.pick_ketama(&key)
+        }
+        SelectivePickPolicy::Rendezvous => {
+            let key = ConsistentKey {
+                client_ip: cc_info.client_ip(),
+                server_ip: cc_info.server_ip(),
+            };
+            nodes.pick_rendezvous(&key)
+        }
+        SelectivePickPolicy::JumpHash => {
+            let key = ConsistentKey {
+                client_ip: cc_info.client_ip(),
+                server_ip: cc_info.server_ip(),
+            };
+            nodes.pick_jump(&key)
         }
     }
 }
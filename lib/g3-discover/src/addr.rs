@@ -0,0 +1,86 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_types::collection::WeightedValue;
+use g3_types::net::{UpstreamAddr, WeightedUpstreamAddr};
+
+/// The weighted address type that the daemon wants to get from the discover
+pub trait DiscoveredAddr: Send + Sync + Sized + 'static {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self>;
+    fn from_socket_addr(addr: SocketAddr) -> Self;
+
+    /// Parse a single or a list of weighted addresses
+    fn parse_yaml_list(value: &Yaml) -> anyhow::Result<Vec<Self>> {
+        match value {
+            Yaml::Array(seq) => {
+                let mut addrs = Vec::with_capacity(seq.len());
+                for (i, v) in seq.iter().enumerate() {
+                    let addr =
+                        Self::parse_yaml(v).context(format!("invalid address value for #{i}"))?;
+                    addrs.push(addr);
+                }
+                Ok(addrs)
+            }
+            v => {
+                let addr = Self::parse_yaml(v).context("invalid address value")?;
+                Ok(vec![addr])
+            }
+        }
+    }
+}
+
+impl DiscoveredAddr for WeightedValue<SocketAddr> {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        g3_yaml::value::as_weighted_sockaddr(value)
+    }
+
+    fn from_socket_addr(addr: SocketAddr) -> Self {
+        WeightedValue::new(addr)
+    }
+}
+
+impl DiscoveredAddr for WeightedUpstreamAddr {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let addr = g3_yaml::value::as_weighted_upstream_addr(value, 0)?;
+        if addr.inner().port() == 0 {
+            return Err(anyhow!("port is not set"));
+        }
+        Ok(addr)
+    }
+
+    fn from_socket_addr(addr: SocketAddr) -> Self {
+        WeightedUpstreamAddr::new(UpstreamAddr::from(addr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_list() {
+        let v = YamlLoader::load_from_str("[127.0.0.1:80, {addr: 'a.example.net:443', weight: 2}]")
+            .unwrap()
+            .pop()
+            .unwrap();
+        let addrs = WeightedUpstreamAddr::parse_yaml_list(&v).unwrap();
+        assert_eq!(addrs.len(), 2);
+        assert_eq!(addrs[1].inner().to_string(), "a.example.net:443");
+        assert_eq!(addrs[1].weight(), 2.0);
+        assert!(WeightedValue::<SocketAddr>::parse_yaml_list(&v).is_err());
+
+        let v = Yaml::String("a.example.net".to_string());
+        assert!(WeightedUpstreamAddr::parse_yaml_list(&v).is_err());
+        let v = Yaml::String("127.0.0.1:80".to_string());
+        let addrs = WeightedValue::<SocketAddr>::parse_yaml_list(&v).unwrap();
+        assert_eq!(addrs[0].inner().port(), 80);
+    }
+}
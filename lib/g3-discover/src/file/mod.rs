@@ -0,0 +1,193 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, anyhow};
+use tokio::sync::watch;
+use yaml_rust::{Yaml, YamlLoader};
+
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use crate::{DiscoverConfig, DiscoverConfigDiffAction, DiscoverResult, DiscoveredAddr};
+
+mod yaml;
+
+const DISCOVER_CONFIG_TYPE: &str = "File";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileDiscoverConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    check_interval: Duration,
+}
+
+impl FileDiscoverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        FileDiscoverConfig {
+            name: NodeName::default(),
+            position,
+            check_interval: Duration::from_secs(10),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.check_interval.is_zero() {
+            return Err(anyhow!("check interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    /// Watch the file in the register data, and load the addresses in it when it's modified
+    pub fn register_yaml<T: DiscoveredAddr>(
+        &self,
+        data: &Yaml,
+    ) -> anyhow::Result<watch::Receiver<DiscoverResult<T>>> {
+        let path = self
+            .parse_yaml_data(data)
+            .context(format!("invalid input data for discover {}", self.name))?;
+        let check_interval = self.check_interval;
+        let (sender, receiver) = watch::channel(Ok(Vec::new()));
+        tokio::spawn(async move {
+            // the modification time of the last successfully loaded file
+            let mut last_modified: Option<SystemTime> = None;
+            let mut last_failed = false;
+            loop {
+                match tokio::fs::metadata(&path).await.and_then(|m| m.modified()) {
+                    Ok(modified) => {
+                        if last_modified != Some(modified) {
+                            match load_file(&path).await {
+                                Ok(addrs) => {
+                                    last_modified = Some(modified);
+                                    last_failed = false;
+                                    let _ = sender.send_replace(Ok(addrs));
+                                }
+                                Err(e) => {
+                                    if !last_failed {
+                                        last_failed = true;
+                                        let _ = sender.send_replace(Err(e));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        if !last_failed {
+                            last_modified = None;
+                            last_failed = true;
+                            let _ = sender.send_replace(Err(anyhow!(
+                                "failed to get modification time of file {}: {e}",
+                                path.display()
+                            )));
+                        }
+                    }
+                }
+                match tokio::time::timeout(check_interval, sender.closed()).await {
+                    Ok(_) => break,
+                    Err(_) => continue,
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+async fn load_file<T: DiscoveredAddr>(path: &Path) -> DiscoverResult<T> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+    let docs = YamlLoader::load_from_str(&content)
+        .map_err(|e| anyhow!("invalid yaml file {}: {e}", path.display()))?;
+    match docs.first() {
+        Some(doc) => {
+            T::parse_yaml_list(doc).context(format!("invalid content in file {}", path.display()))
+        }
+        None => Ok(Vec::new()),
+    }
+}
+
+impl DiscoverConfig for FileDiscoverConfig {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        DISCOVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &Self) -> DiscoverConfigDiffAction {
+        if self.check_interval != new.check_interval {
+            return DiscoverConfigDiffAction::SpawnNew;
+        }
+
+        DiscoverConfigDiffAction::NoAction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::WeightedUpstreamAddr;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn watch_file() {
+        let dir = std::env::temp_dir().join(format!("g3-discover-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("upstream.yaml");
+        std::fs::write(
+            &path,
+            "- 127.0.0.1:80\n- {addr: 'a.example.net:443', weight: 2}\n",
+        )
+        .unwrap();
+
+        let mut config = FileDiscoverConfig::new(None);
+        config.name = NodeName::from_str("file").unwrap();
+        config.check_interval = Duration::from_millis(10);
+
+        let data = Yaml::String(path.display().to_string());
+        let mut receiver = config.register_yaml::<WeightedUpstreamAddr>(&data).unwrap();
+        receiver.changed().await.unwrap();
+        {
+            let addrs = receiver.borrow_and_update();
+            let addrs = addrs.as_ref().unwrap();
+            assert_eq!(addrs.len(), 2);
+            assert_eq!(addrs[1].weight(), 2.0);
+        }
+
+        std::fs::write(&path, "- 127.0.0.1:8080\n").unwrap();
+        // make sure the modification time is changed
+        let modified = SystemTime::now() + Duration::from_secs(1);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        receiver.changed().await.unwrap();
+        {
+            let addrs = receiver.borrow_and_update();
+            let addrs = addrs.as_ref().unwrap();
+            assert_eq!(addrs.len(), 1);
+            assert_eq!(addrs[0].inner().port(), 8080);
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        receiver.changed().await.unwrap();
+        assert!(receiver.borrow_and_update().is_err());
+    }
+}
@@ -0,0 +1,55 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::PathBuf;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_yaml::YamlDocPosition;
+
+use super::FileDiscoverConfig;
+
+impl FileDiscoverConfig {
+    pub fn parse_yaml_conf(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut discover = FileDiscoverConfig::new(position);
+        g3_yaml::foreach_kv(map, |k, v| discover.set_yaml(k, v))?;
+        discover.check()?;
+        Ok(discover)
+    }
+
+    fn set_yaml(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            crate::CONFIG_KEY_DISCOVER_TYPE => Ok(()),
+            crate::CONFIG_KEY_DISCOVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "check_interval" => {
+                self.check_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    /// Get the path of the file to watch, relative paths are relative to the discover config file
+    pub(super) fn parse_yaml_data(&self, input: &Yaml) -> anyhow::Result<PathBuf> {
+        let Yaml::String(s) = input else {
+            return Err(anyhow!("the yaml value type should be 'string'"));
+        };
+        let path = PathBuf::from(s);
+        if path.is_absolute() {
+            Ok(path)
+        } else {
+            let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+            Ok(lookup_dir.join(path))
+        }
+    }
+}
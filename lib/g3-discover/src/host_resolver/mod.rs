@@ -0,0 +1,100 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use tokio::sync::watch;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use crate::{DiscoverConfig, DiscoverConfigDiffAction, DiscoverResult, DiscoveredAddr};
+
+mod yaml;
+
+const DISCOVER_CONFIG_TYPE: &str = "HostResolver";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HostResolverDiscoverConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    refresh_interval: Duration,
+}
+
+impl HostResolverDiscoverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        HostResolverDiscoverConfig {
+            name: NodeName::default(),
+            position,
+            refresh_interval: Duration::from_secs(60),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.refresh_interval.is_zero() {
+            return Err(anyhow!("refresh interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    /// Resolve the domain in the register data by the host resolver periodically
+    pub fn register_yaml<T: DiscoveredAddr>(
+        &self,
+        data: &Yaml,
+    ) -> anyhow::Result<watch::Receiver<DiscoverResult<T>>> {
+        let upstream = self
+            .parse_yaml_data(data)
+            .context(format!("invalid input data for discover {}", self.name))?;
+        let refresh_interval = self.refresh_interval;
+        let (sender, receiver) = watch::channel(Ok(Vec::new()));
+        tokio::spawn(async move {
+            let addr = upstream.to_string();
+            loop {
+                let _ = match tokio::net::lookup_host(&addr).await {
+                    Ok(iter) => {
+                        let addrs: Vec<_> = iter.map(T::from_socket_addr).collect();
+                        sender.send_replace(Ok(addrs))
+                    }
+                    Err(e) => sender.send_replace(Err(anyhow::Error::new(e))),
+                };
+                match tokio::time::timeout(refresh_interval, sender.closed()).await {
+                    Ok(_) => break,
+                    Err(_) => continue,
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+impl DiscoverConfig for HostResolverDiscoverConfig {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        DISCOVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &Self) -> DiscoverConfigDiffAction {
+        if self.refresh_interval != new.refresh_interval {
+            return DiscoverConfigDiffAction::SpawnNew;
+        }
+
+        DiscoverConfigDiffAction::NoAction
+    }
+}
@@ -0,0 +1,48 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::net::UpstreamAddr;
+use g3_yaml::YamlDocPosition;
+
+use super::HostResolverDiscoverConfig;
+
+impl HostResolverDiscoverConfig {
+    pub fn parse_yaml_conf(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut discover = HostResolverDiscoverConfig::new(position);
+        g3_yaml::foreach_kv(map, |k, v| discover.set_yaml(k, v))?;
+        discover.check()?;
+        Ok(discover)
+    }
+
+    fn set_yaml(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            crate::CONFIG_KEY_DISCOVER_TYPE => Ok(()),
+            crate::CONFIG_KEY_DISCOVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "refresh_interval" => {
+                self.refresh_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    pub(super) fn parse_yaml_data(&self, input: &Yaml) -> anyhow::Result<UpstreamAddr> {
+        let addr = g3_yaml::value::as_upstream_addr(input, 0)?;
+        if addr.port() == 0 {
+            return Err(anyhow!("port is not set"));
+        }
+        Ok(addr)
+    }
+}
@@ -0,0 +1,72 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! Upstream address discover drivers shared by the daemons.
+//!
+//! The daemons keep their own registry of discover objects, and use the drivers here
+//! to get the upstream addresses for each registered data.
+
+use anyhow::anyhow;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+use g3_yaml::dump::YamlDump;
+
+mod addr;
+pub use addr::DiscoveredAddr;
+
+pub mod file;
+pub mod host_resolver;
+pub mod srv;
+pub mod static_addr;
+
+pub use file::FileDiscoverConfig;
+pub use host_resolver::HostResolverDiscoverConfig;
+pub use srv::SrvDiscoverConfig;
+pub use static_addr::StaticAddrDiscoverConfig;
+
+const CONFIG_KEY_DISCOVER_TYPE: &str = "type";
+const CONFIG_KEY_DISCOVER_NAME: &str = "name";
+
+pub type DiscoverResult<T> = anyhow::Result<Vec<T>>;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum DiscoverRegisterData {
+    #[default]
+    Null,
+    Yaml(Yaml),
+}
+
+impl DiscoverRegisterData {
+    pub fn as_yaml(&self) -> anyhow::Result<&Yaml> {
+        match self {
+            DiscoverRegisterData::Null => Err(anyhow!("no valid register data found")),
+            DiscoverRegisterData::Yaml(v) => Ok(v),
+        }
+    }
+}
+
+impl YamlDump for DiscoverRegisterData {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        match self {
+            DiscoverRegisterData::Null => Yaml::Null,
+            DiscoverRegisterData::Yaml(v) => v.clone(),
+        }
+    }
+}
+
+pub enum DiscoverConfigDiffAction {
+    NoAction,
+    SpawnNew,
+}
+
+pub trait DiscoverConfig {
+    fn name(&self) -> &NodeName;
+    fn position(&self) -> Option<YamlDocPosition>;
+    fn r#type(&self) -> &'static str;
+
+    fn diff_action(&self, new: &Self) -> DiscoverConfigDiffAction;
+}
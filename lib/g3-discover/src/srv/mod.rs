@@ -0,0 +1,263 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use hickory_client::client::{Client, ClientHandle};
+use hickory_proto::BufDnsStreamHandle;
+use hickory_proto::rr::{DNSClass, Name, RData, RecordType};
+use tokio::sync::watch;
+use yaml_rust::Yaml;
+
+use g3_socket::{BindAddr, TcpConnectInfo, UdpConnectInfo};
+use g3_types::metrics::NodeName;
+use g3_types::net::{UpstreamAddr, WeightedUpstreamAddr};
+use g3_yaml::YamlDocPosition;
+
+use crate::{DiscoverConfig, DiscoverConfigDiffAction, DiscoverResult};
+
+mod yaml;
+
+const DISCOVER_CONFIG_TYPE: &str = "Srv";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SrvDiscoverConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    server: Option<SocketAddr>,
+    query_timeout: Duration,
+    refresh_interval: Duration,
+}
+
+impl SrvDiscoverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        SrvDiscoverConfig {
+            name: NodeName::default(),
+            position,
+            server: None,
+            query_timeout: Duration::from_secs(5),
+            refresh_interval: Duration::from_secs(60),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.server.is_none() {
+            return Err(anyhow!("server is not set"));
+        }
+        if self.refresh_interval.is_zero() {
+            return Err(anyhow!("refresh interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    /// Query the SRV record in the register data periodically
+    pub fn register_yaml(
+        &self,
+        data: &Yaml,
+    ) -> anyhow::Result<watch::Receiver<DiscoverResult<WeightedUpstreamAddr>>> {
+        let domain = self
+            .parse_yaml_data(data)
+            .context(format!("invalid input data for discover {}", self.name))?;
+        let mut name = Name::from_ascii(&domain)
+            .map_err(|e| anyhow!("invalid srv record name {domain}: {e}"))?;
+        // always use FQDN format such like "_http._tcp.example.com."
+        name.set_fqdn(true);
+
+        let Some(server) = self.server else {
+            return Err(anyhow!("no dns server set for discover {}", self.name));
+        };
+        let query_timeout = self.query_timeout;
+        let refresh_interval = self.refresh_interval;
+        let (sender, receiver) = watch::channel(Ok(Vec::new()));
+        tokio::spawn(async move {
+            loop {
+                let r = match tokio::time::timeout(
+                    query_timeout,
+                    query_srv(server, &name, query_timeout),
+                )
+                .await
+                {
+                    Ok(Ok(addrs)) => Ok(addrs),
+                    Ok(Err(e)) => Err(e.context(format!("failed to query srv record {name}"))),
+                    Err(_) => Err(anyhow!("timed out to query srv record {name}")),
+                };
+                let _ = sender.send_replace(r);
+                match tokio::time::timeout(refresh_interval, sender.closed()).await {
+                    Ok(_) => break,
+                    Err(_) => continue,
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+async fn new_udp_client(server: SocketAddr, timeout: Duration) -> anyhow::Result<Client> {
+    let connect_info = UdpConnectInfo {
+        server,
+        bind: BindAddr::None,
+        buf_conf: Default::default(),
+        misc_opts: Default::default(),
+    };
+    let client_connect = g3_hickory_client::io::udp::connect(connect_info, timeout);
+
+    let (client, bg) = Client::connect(Box::pin(client_connect))
+        .await
+        .map_err(|e| anyhow!("failed to create udp async client: {e}"))?;
+    tokio::spawn(bg);
+    Ok(client)
+}
+
+async fn new_tcp_client(server: SocketAddr, timeout: Duration) -> anyhow::Result<Client> {
+    let connect_info = TcpConnectInfo {
+        server,
+        bind: BindAddr::None,
+        keepalive: Default::default(),
+        misc_opts: Default::default(),
+    };
+    let (message_sender, outbound_messages) = BufDnsStreamHandle::new(server);
+    let tcp_connect = g3_hickory_client::io::tcp::connect(connect_info, outbound_messages, timeout);
+
+    let (client, bg) = Client::with_timeout(Box::pin(tcp_connect), message_sender, timeout, None)
+        .await
+        .map_err(|e| anyhow!("failed to create tcp async client: {e}"))?;
+    tokio::spawn(bg);
+    Ok(client)
+}
+
+async fn query_srv(
+    server: SocketAddr,
+    name: &Name,
+    timeout: Duration,
+) -> DiscoverResult<WeightedUpstreamAddr> {
+    let mut client = new_udp_client(server, timeout).await?;
+    let mut rsp = client
+        .query(name.clone(), DNSClass::IN, RecordType::SRV)
+        .await
+        .map_err(|e| anyhow!("dns query over udp failed: {e}"))?;
+    if rsp.truncated() {
+        let mut client = new_tcp_client(server, timeout).await?;
+        rsp = client
+            .query(name.clone(), DNSClass::IN, RecordType::SRV)
+            .await
+            .map_err(|e| anyhow!("dns query over tcp failed: {e}"))?;
+    }
+    let (mut msg, _) = rsp.into_parts();
+
+    // only use the targets with the lowest priority, see RFC 2782
+    let mut min_priority = u16::MAX;
+    let mut records = Vec::new();
+    for r in msg.take_answers() {
+        let RData::SRV(srv) = r.data() else {
+            continue;
+        };
+        // a target of "." means the service is decidedly not available
+        if srv.target().is_root() {
+            continue;
+        }
+        if srv.priority() < min_priority {
+            min_priority = srv.priority();
+            records.clear();
+        } else if srv.priority() > min_priority {
+            continue;
+        }
+
+        let mut target = srv.target().to_ascii();
+        if target.ends_with('.') {
+            target.pop();
+        }
+        // targets with weight 0 should have a very small chance to be selected
+        let weight = if srv.weight() == 0 {
+            0.1
+        } else {
+            srv.weight() as f64
+        };
+        records.push(WeightedUpstreamAddr::with_weight(
+            UpstreamAddr::from_host_str_and_port(&target, srv.port())?,
+            weight,
+        ));
+    }
+    Ok(records)
+}
+
+impl DiscoverConfig for SrvDiscoverConfig {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    #[inline]
+    fn r#type(&self) -> &'static str {
+        DISCOVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &Self) -> DiscoverConfigDiffAction {
+        if self.server != new.server
+            || self.query_timeout != new.query_timeout
+            || self.refresh_interval != new.refresh_interval
+        {
+            return DiscoverConfigDiffAction::SpawnNew;
+        }
+
+        DiscoverConfigDiffAction::NoAction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hickory_proto::op::{Message, MessageType};
+    use hickory_proto::rr::Record;
+    use hickory_proto::rr::rdata::SRV;
+    use tokio::net::UdpSocket;
+
+    #[tokio::test]
+    async fn query_local_server() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server = socket.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 4096];
+            let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+            let req = Message::from_vec(&buf[..len]).unwrap();
+            let name = req.queries()[0].name().clone();
+
+            let mut rsp = Message::new();
+            rsp.set_id(req.id())
+                .set_message_type(MessageType::Response)
+                .add_query(req.queries()[0].clone());
+            let add_srv = |rsp: &mut Message, priority: u16, weight: u16, target: &str| {
+                let target = Name::from_ascii(target).unwrap();
+                let srv = SRV::new(priority, weight, 8080, target);
+                rsp.add_answer(Record::from_rdata(name.clone(), 60, RData::SRV(srv)));
+            };
+            add_srv(&mut rsp, 10, 5, "a.example.net.");
+            add_srv(&mut rsp, 10, 0, "b.example.net.");
+            add_srv(&mut rsp, 20, 5, "c.example.net.");
+            add_srv(&mut rsp, 0, 5, ".");
+            socket.send_to(&rsp.to_vec().unwrap(), peer).await.unwrap();
+        });
+
+        let name = Name::from_ascii("_http._tcp.example.net.").unwrap();
+        let addrs = query_srv(server, &name, Duration::from_secs(2))
+            .await
+            .unwrap();
+        assert_eq!(addrs.len(), 2);
+        assert_eq!(addrs[0].inner().to_string(), "a.example.net:8080");
+        assert_eq!(addrs[0].weight(), 5.0);
+        assert_eq!(addrs[1].inner().to_string(), "b.example.net:8080");
+        assert_eq!(addrs[1].weight(), 0.1);
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_yaml::YamlDocPosition;
+
+use super::SrvDiscoverConfig;
+
+impl SrvDiscoverConfig {
+    pub fn parse_yaml_conf(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut discover = SrvDiscoverConfig::new(position);
+        g3_yaml::foreach_kv(map, |k, v| discover.set_yaml(k, v))?;
+        discover.check()?;
+        Ok(discover)
+    }
+
+    fn set_yaml(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            crate::CONFIG_KEY_DISCOVER_TYPE => Ok(()),
+            crate::CONFIG_KEY_DISCOVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "server" => {
+                let server = match g3_yaml::value::as_ipaddr(v) {
+                    Ok(ip) => SocketAddr::new(ip, 53),
+                    Err(_) => g3_yaml::value::as_sockaddr(v)
+                        .context(format!("invalid ip or socket address value for key {k}"))?,
+                };
+                self.server = Some(server);
+                Ok(())
+            }
+            "query_timeout" => {
+                self.query_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "refresh_interval" => {
+                self.refresh_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    /// Get the SRV record name to query, such as "_http._tcp.example.com"
+    pub(super) fn parse_yaml_data(&self, input: &Yaml) -> anyhow::Result<String> {
+        g3_yaml::value::as_domain(input)
+    }
+}
@@ -1,32 +1,23 @@
 /*
  * SPDX-License-Identifier: Apache-2.0
- * Copyright 2024-2025 ByteDance and/or its affiliates.
+ * Copyright 2025 ByteDance and/or its affiliates.
  */
 
-use std::net::SocketAddr;
+use anyhow::{Context, anyhow};
+use tokio::sync::watch;
+use yaml_rust::Yaml;
 
-use anyhow::anyhow;
-
-use g3_types::collection::WeightedValue;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
-use super::{
-    AnyDiscoverConfig, CONFIG_KEY_DISCOVER_NAME, CONFIG_KEY_DISCOVER_TYPE, DiscoverConfig,
-    DiscoverConfigDiffAction,
-};
+use crate::{DiscoverConfig, DiscoverConfigDiffAction, DiscoverResult, DiscoveredAddr};
 
 mod yaml;
 
 const DISCOVER_CONFIG_TYPE: &str = "StaticAddr";
 
-#[derive(Default, PartialEq, Eq)]
-pub(crate) struct StaticAddrDiscoverInput {
-    pub(crate) inner: Vec<WeightedValue<SocketAddr>>,
-}
-
-#[derive(Clone)]
-pub(crate) struct StaticAddrDiscoverConfig {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaticAddrDiscoverConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
 }
@@ -45,6 +36,19 @@ impl StaticAddrDiscoverConfig {
         }
         Ok(())
     }
+
+    /// The addresses in the register data will be returned as is
+    pub fn register_yaml<T: DiscoveredAddr>(
+        &self,
+        data: &Yaml,
+    ) -> anyhow::Result<watch::Receiver<DiscoverResult<T>>> {
+        let addrs = T::parse_yaml_list(data)
+            .context(format!("invalid input data for discover {}", self.name))?;
+        let (sender, mut receiver) = watch::channel(Ok(addrs));
+        receiver.mark_changed();
+        tokio::spawn(async move { sender.closed().await });
+        Ok(receiver)
+    }
 }
 
 impl DiscoverConfig for StaticAddrDiscoverConfig {
@@ -63,11 +67,7 @@ impl DiscoverConfig for StaticAddrDiscoverConfig {
         DISCOVER_CONFIG_TYPE
     }
 
-    fn diff_action(&self, new: &AnyDiscoverConfig) -> DiscoverConfigDiffAction {
-        let AnyDiscoverConfig::StaticAddr(_new) = new else {
-            return DiscoverConfigDiffAction::SpawnNew;
-        };
-
+    fn diff_action(&self, _new: &Self) -> DiscoverConfigDiffAction {
         DiscoverConfigDiffAction::NoAction
     }
 }
@@ -0,0 +1,34 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+use yaml_rust::{Yaml, yaml};
+
+use g3_yaml::YamlDocPosition;
+
+use super::StaticAddrDiscoverConfig;
+
+impl StaticAddrDiscoverConfig {
+    pub fn parse_yaml_conf(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut discover = StaticAddrDiscoverConfig::new(position);
+        g3_yaml::foreach_kv(map, |k, v| discover.set_yaml(k, v))?;
+        discover.check()?;
+        Ok(discover)
+    }
+
+    fn set_yaml(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            crate::CONFIG_KEY_DISCOVER_TYPE => Ok(()),
+            crate::CONFIG_KEY_DISCOVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+}
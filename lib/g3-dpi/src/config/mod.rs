@@ -26,6 +26,9 @@ pub use smtp::SmtpInterceptionConfig;
 mod imap;
 pub use imap::ImapInterceptionConfig;
 
+mod websocket;
+pub use websocket::WebSocketInterceptionConfig;
+
 #[derive(Clone)]
 pub struct ProtocolInspectPolicyBuilder {
     missed_action: ProtocolInspectAction,
@@ -54,6 +57,11 @@ impl ProtocolInspectPolicyBuilder {
         self.missed_action = missed_action;
     }
 
+    #[inline]
+    pub fn missed_action(&self) -> ProtocolInspectAction {
+        self.missed_action
+    }
+
     pub fn build(&self) -> ProtocolInspectPolicy {
         ProtocolInspectPolicy {
             exact: self.exact.clone(),
@@ -121,7 +129,7 @@ pub enum ProtocolInspectAction {
 }
 
 impl ProtocolInspectAction {
-    fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::Block => "block",
             Self::Intercept => "intercept",
@@ -38,4 +38,24 @@ impl ProtocolInspectionSizeLimit {
     pub fn set_nats_server_info_line(&mut self, size: usize) {
         self.nats_server_info_line = size;
     }
+
+    #[inline]
+    pub fn ftp_server_greeting_msg(&self) -> usize {
+        self.ftp_server_greeting_msg
+    }
+
+    #[inline]
+    pub fn http_client_request_uri(&self) -> usize {
+        self.http_client_request_uri
+    }
+
+    #[inline]
+    pub fn imap_server_greeting_msg(&self) -> usize {
+        self.imap_server_greeting_msg
+    }
+
+    #[inline]
+    pub fn nats_server_info_line(&self) -> usize {
+        self.nats_server_info_line
+    }
 }
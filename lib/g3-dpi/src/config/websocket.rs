@@ -0,0 +1,23 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebSocketInterceptionConfig {
+    pub inspect_message: bool,
+    pub message_buffer_max_size: usize,
+    pub icap_adaptation_timeout: Duration,
+}
+
+impl Default for WebSocketInterceptionConfig {
+    fn default() -> Self {
+        WebSocketInterceptionConfig {
+            inspect_message: false,
+            message_buffer_max_size: 1 << 20, // 1MiB
+            icap_adaptation_timeout: Duration::from_secs(10),
+        }
+    }
+}
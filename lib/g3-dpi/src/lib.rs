@@ -16,7 +16,7 @@ mod config;
 pub use config::{
     H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, ProtocolInspectAction,
     ProtocolInspectPolicy, ProtocolInspectPolicyBuilder, ProtocolInspectionConfig,
-    ProtocolInspectionSizeLimit, SmtpInterceptionConfig,
+    ProtocolInspectionSizeLimit, SmtpInterceptionConfig, WebSocketInterceptionConfig,
 };
 
 pub mod parser;
@@ -5,5 +5,7 @@
 
 pub mod tls;
 
+pub mod websocket;
+
 #[cfg(feature = "quic")]
 pub mod quic;
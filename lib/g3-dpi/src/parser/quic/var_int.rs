@@ -9,6 +9,21 @@ pub struct VarInt {
 }
 
 impl VarInt {
+    /// The max value that can be encoded
+    pub const MAX: u64 = (1 << 62) - 1;
+
+    /// Create a new variant-length int value, `None` will be returned if it's larger than `MAX`
+    pub fn new(value: u64) -> Option<Self> {
+        let encoded_len = match value {
+            0..0x40 => 1,
+            0x40..0x4000 => 2,
+            0x4000..0x4000_0000 => 4,
+            0x4000_0000..=Self::MAX => 8,
+            _ => return None,
+        };
+        Some(VarInt { value, encoded_len })
+    }
+
     /// Try to parse a variant-length int value from the buffer
     pub fn try_parse(data: &[u8]) -> Option<Self> {
         if data.is_empty() {
@@ -63,4 +78,61 @@ impl VarInt {
     pub fn value(&self) -> u64 {
         self.value
     }
+
+    /// Encode the value to the buffer, return the encoded length.
+    /// `None` will be returned if the buffer is too small
+    pub fn encode(&self, buf: &mut [u8]) -> Option<usize> {
+        let buf = buf.get_mut(..self.encoded_len)?;
+        match self.encoded_len {
+            1 => buf[0] = self.value as u8,
+            2 => buf.copy_from_slice(&(0x4000 | self.value as u16).to_be_bytes()),
+            4 => buf.copy_from_slice(&(0x8000_0000 | self.value as u32).to_be_bytes()),
+            _ => buf.copy_from_slice(&(0xC000_0000_0000_0000 | self.value).to_be_bytes()),
+        }
+        Some(self.encoded_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_parse() {
+        let mut buf = [0u8; 8];
+        for (value, len) in [
+            (0, 1),
+            (0x3f, 1),
+            (0x40, 2),
+            (0x3fff, 2),
+            (0x4000, 4),
+            (0x3fff_ffff, 4),
+            (0x4000_0000, 8),
+            (VarInt::MAX, 8),
+        ] {
+            let v = VarInt::new(value).unwrap();
+            assert_eq!(v.encoded_len(), len);
+            assert_eq!(v.encode(&mut buf), Some(len));
+            let p = VarInt::try_parse(&buf[..len]).unwrap();
+            assert_eq!(p.value(), value);
+            assert_eq!(p.encoded_len(), len);
+            assert!(VarInt::try_parse(&buf[..len - 1]).is_none());
+        }
+
+        assert!(VarInt::new(VarInt::MAX + 1).is_none());
+        let v = VarInt::new(0x4000).unwrap();
+        assert!(v.encode(&mut buf[..3]).is_none());
+    }
+
+    #[test]
+    fn rfc9000_sample() {
+        let v = VarInt::new(151_288_809_941_952_652).unwrap();
+        let mut buf = [0u8; 8];
+        v.encode(&mut buf).unwrap();
+        assert_eq!(buf, [0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c]);
+
+        let v = VarInt::new(15_293).unwrap();
+        assert_eq!(v.encode(&mut buf), Some(2));
+        assert_eq!(buf[..2], [0x7b, 0xbd]);
+    }
 }
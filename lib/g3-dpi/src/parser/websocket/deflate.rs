@@ -0,0 +1,162 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use flate2::{Decompress, DecompressError, FlushDecompress, Status};
+use thiserror::Error;
+
+/// The negotiated parameters of the permessage-deflate extension, see RFC 7692
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PerMessageDeflateParams {
+    pub server_no_context_takeover: bool,
+    pub client_no_context_takeover: bool,
+}
+
+impl PerMessageDeflateParams {
+    /// Parse from the Sec-WebSocket-Extensions header value in the handshake response
+    pub fn parse_negotiated(value: &str) -> Option<Self> {
+        for extension in value.split(',') {
+            let mut parts = extension.split(';').map(|s| s.trim());
+            if !parts
+                .next()
+                .map(|name| name.eq_ignore_ascii_case("permessage-deflate"))
+                .unwrap_or(false)
+            {
+                continue;
+            }
+
+            let mut params = PerMessageDeflateParams::default();
+            for param in parts {
+                let name = param
+                    .split_once('=')
+                    .map(|(k, _)| k.trim())
+                    .unwrap_or(param);
+                if name.eq_ignore_ascii_case("server_no_context_takeover") {
+                    params.server_no_context_takeover = true;
+                } else if name.eq_ignore_ascii_case("client_no_context_takeover") {
+                    params.client_no_context_takeover = true;
+                }
+            }
+            return Some(params);
+        }
+        None
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DeflateDecodeError {
+    #[error("decoded size exceeded the limit {0}")]
+    TooLarge(usize),
+    #[error("invalid deflate data: {0}")]
+    InvalidData(#[from] DecompressError),
+}
+
+/// The decoder for compressed messages in one direction
+///
+/// The sliding window is shared between messages if context takeover is allowed,
+/// so all compressed messages in the same direction should be decoded in order.
+pub struct PerMessageDeflateDecoder {
+    inner: Decompress,
+    no_context_takeover: bool,
+}
+
+impl PerMessageDeflateDecoder {
+    const MESSAGE_TAIL: [u8; 4] = [0x00, 0x00, 0xff, 0xff];
+
+    pub fn new(no_context_takeover: bool) -> Self {
+        PerMessageDeflateDecoder {
+            inner: Decompress::new(false),
+            no_context_takeover,
+        }
+    }
+
+    pub fn decode(&mut self, data: &[u8], max_size: usize) -> Result<Vec<u8>, DeflateDecodeError> {
+        let mut input = Vec::with_capacity(data.len() + Self::MESSAGE_TAIL.len());
+        input.extend_from_slice(data);
+        input.extend_from_slice(&Self::MESSAGE_TAIL);
+
+        let mut output = Vec::with_capacity((data.len() * 2).max(64).min(max_size + 1));
+        let mut consumed = 0;
+        loop {
+            if output.len() == output.capacity() {
+                if output.len() > max_size {
+                    return Err(DeflateDecodeError::TooLarge(max_size));
+                }
+                let additional = output.capacity().min(max_size + 1 - output.len());
+                output.reserve_exact(additional);
+            }
+
+            let in_before = self.inner.total_in();
+            let out_before = output.len();
+            let status = self.inner.decompress_vec(
+                &input[consumed..],
+                &mut output,
+                FlushDecompress::Sync,
+            )?;
+            consumed += (self.inner.total_in() - in_before) as usize;
+
+            if status == Status::StreamEnd {
+                break;
+            }
+            if consumed >= input.len() && output.len() < output.capacity() {
+                break;
+            }
+            if self.inner.total_in() == in_before && output.len() == out_before {
+                // no progress can be made
+                break;
+            }
+        }
+        if output.len() > max_size {
+            return Err(DeflateDecodeError::TooLarge(max_size));
+        }
+
+        if self.no_context_takeover {
+            self.inner.reset(false);
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_params() {
+        let params = PerMessageDeflateParams::parse_negotiated("permessage-deflate").unwrap();
+        assert!(!params.server_no_context_takeover);
+        assert!(!params.client_no_context_takeover);
+
+        let params = PerMessageDeflateParams::parse_negotiated(
+            "x-webkit-deflate-frame, permessage-deflate; server_no_context_takeover; client_max_window_bits=10",
+        )
+        .unwrap();
+        assert!(params.server_no_context_takeover);
+        assert!(!params.client_no_context_takeover);
+
+        assert!(PerMessageDeflateParams::parse_negotiated("x-webkit-deflate-frame").is_none());
+    }
+
+    #[test]
+    fn decode() {
+        // "Hello" compressed, from RFC 7692 section 7.2.3.1
+        let data = [0xf2, 0x48, 0xcd, 0xc9, 0xc9, 0x07, 0x00];
+        // the same message using the previous sliding window, from RFC 7692 section 7.2.3.2
+        let data2 = [0xf2, 0x00, 0x11, 0x00, 0x00];
+
+        let mut decoder = PerMessageDeflateDecoder::new(false);
+        assert_eq!(decoder.decode(&data, 1024).unwrap(), b"Hello");
+        assert_eq!(decoder.decode(&data2, 1024).unwrap(), b"Hello");
+
+        let mut decoder = PerMessageDeflateDecoder::new(true);
+        assert_eq!(decoder.decode(&data, 1024).unwrap(), b"Hello");
+        assert!(decoder.decode(&data2, 1024).is_err());
+
+        let mut decoder = PerMessageDeflateDecoder::new(true);
+        assert!(matches!(
+            decoder.decode(&data, 4),
+            Err(DeflateDecodeError::TooLarge(4))
+        ));
+    }
+}
@@ -0,0 +1,252 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt;
+
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OpCode {
+    Continuation = 0x0,
+    Text = 0x1,
+    Binary = 0x2,
+    Close = 0x8,
+    Ping = 0x9,
+    Pong = 0xA,
+}
+
+impl OpCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpCode::Continuation => "continuation",
+            OpCode::Text => "text",
+            OpCode::Binary => "binary",
+            OpCode::Close => "close",
+            OpCode::Ping => "ping",
+            OpCode::Pong => "pong",
+        }
+    }
+
+    #[inline]
+    pub fn is_control(&self) -> bool {
+        (*self as u8) & 0x08 != 0
+    }
+}
+
+impl fmt::Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl TryFrom<u8> for OpCode {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x0 => Ok(OpCode::Continuation),
+            0x1 => Ok(OpCode::Text),
+            0x2 => Ok(OpCode::Binary),
+            0x8 => Ok(OpCode::Close),
+            0x9 => Ok(OpCode::Ping),
+            0xA => Ok(OpCode::Pong),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum FrameParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("invalid opcode {0}")]
+    InvalidOpCode(u8),
+    #[error("invalid payload length")]
+    InvalidPayloadLength,
+    #[error("fragmented control frame")]
+    FragmentedControlFrame,
+    #[error("too large control frame")]
+    TooLargeControlFrame,
+}
+
+pub struct FrameHeader {
+    pub fin: bool,
+    pub rsv1: bool,
+    pub opcode: OpCode,
+    pub mask_key: Option<[u8; 4]>,
+    pub payload_len: u64,
+}
+
+impl FrameHeader {
+    pub const MIN_SIZE: usize = 2;
+    pub const MAX_SIZE: usize = 14;
+    const CONTROL_PAYLOAD_MAX_LEN: u64 = 125;
+
+    /// Get the full header size from the first two bytes
+    pub fn header_size(data: &[u8; 2]) -> usize {
+        let mut size = 2;
+        if data[1] & 0x80 != 0 {
+            size += 4;
+        }
+        match data[1] & 0x7F {
+            126 => size + 2,
+            127 => size + 8,
+            _ => size,
+        }
+    }
+
+    /// Parse the frame header and return the header size
+    pub fn parse(data: &[u8]) -> Result<(Self, usize), FrameParseError> {
+        if data.len() < Self::MIN_SIZE {
+            return Err(FrameParseError::NeedMoreData(Self::MIN_SIZE - data.len()));
+        }
+        let header_size = Self::header_size(&[data[0], data[1]]);
+        if data.len() < header_size {
+            return Err(FrameParseError::NeedMoreData(header_size - data.len()));
+        }
+
+        let fin = data[0] & 0x80 != 0;
+        let rsv1 = data[0] & 0x40 != 0;
+        let Ok(opcode) = OpCode::try_from(data[0] & 0x0F) else {
+            return Err(FrameParseError::InvalidOpCode(data[0] & 0x0F));
+        };
+        let masked = data[1] & 0x80 != 0;
+
+        let mut offset = 2;
+        let payload_len = match data[1] & 0x7F {
+            126 => {
+                let len = u16::from_be_bytes([data[2], data[3]]);
+                offset += 2;
+                len as u64
+            }
+            127 => {
+                let mut len_bytes = [0u8; 8];
+                len_bytes.copy_from_slice(&data[2..10]);
+                offset += 8;
+                let len = u64::from_be_bytes(len_bytes);
+                if len & 0x8000_0000_0000_0000 != 0 {
+                    return Err(FrameParseError::InvalidPayloadLength);
+                }
+                len
+            }
+            n => n as u64,
+        };
+
+        let mask_key = if masked {
+            let mut key = [0u8; 4];
+            key.copy_from_slice(&data[offset..offset + 4]);
+            Some(key)
+        } else {
+            None
+        };
+
+        if opcode.is_control() {
+            if !fin {
+                return Err(FrameParseError::FragmentedControlFrame);
+            }
+            if payload_len > Self::CONTROL_PAYLOAD_MAX_LEN {
+                return Err(FrameParseError::TooLargeControlFrame);
+            }
+        }
+
+        Ok((
+            FrameHeader {
+                fin,
+                rsv1,
+                opcode,
+                mask_key,
+                payload_len,
+            },
+            header_size,
+        ))
+    }
+}
+
+/// Unmask the payload data in place, the offset is the position of the data in the frame payload
+pub fn unmask_payload(data: &mut [u8], mask_key: [u8; 4], offset: u64) {
+    let start = (offset % 4) as usize;
+    for (i, b) in data.iter_mut().enumerate() {
+        *b ^= mask_key[(start + i) % 4];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_small() {
+        // unmasked text frame "Hello"
+        let data = [0x81, 0x05, 0x48, 0x65, 0x6c, 0x6c, 0x6f];
+        let (header, size) = FrameHeader::parse(&data).unwrap();
+        assert_eq!(size, 2);
+        assert!(header.fin);
+        assert!(!header.rsv1);
+        assert_eq!(header.opcode, OpCode::Text);
+        assert!(header.mask_key.is_none());
+        assert_eq!(header.payload_len, 5);
+
+        // masked text frame "Hello"
+        let data = [
+            0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ];
+        let (header, size) = FrameHeader::parse(&data).unwrap();
+        assert_eq!(size, 6);
+        let mask_key = header.mask_key.unwrap();
+        let mut payload = data[6..].to_vec();
+        unmask_payload(&mut payload[..2], mask_key, 0);
+        unmask_payload(&mut payload[2..], mask_key, 2);
+        assert_eq!(&payload, b"Hello");
+    }
+
+    #[test]
+    fn parse_extended_len() {
+        let data = [0x82, 0x7E, 0x01, 0x00];
+        let (header, size) = FrameHeader::parse(&data).unwrap();
+        assert_eq!(size, 4);
+        assert_eq!(header.opcode, OpCode::Binary);
+        assert_eq!(header.payload_len, 256);
+
+        let data = [0x02, 0x7F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00];
+        let (header, size) = FrameHeader::parse(&data).unwrap();
+        assert_eq!(size, 10);
+        assert!(!header.fin);
+        assert_eq!(header.payload_len, 65536);
+
+        let data = [0x02, 0x7F, 0x80, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00];
+        assert_eq!(
+            FrameHeader::parse(&data).err(),
+            Some(FrameParseError::InvalidPayloadLength)
+        );
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let data = [0x82, 0xFE, 0x01];
+        assert_eq!(
+            FrameHeader::parse(&data).err(),
+            Some(FrameParseError::NeedMoreData(5))
+        );
+
+        let data = [0x83, 0x00];
+        assert_eq!(
+            FrameHeader::parse(&data).err(),
+            Some(FrameParseError::InvalidOpCode(3))
+        );
+
+        let data = [0x09, 0x00];
+        assert_eq!(
+            FrameHeader::parse(&data).err(),
+            Some(FrameParseError::FragmentedControlFrame)
+        );
+
+        let data = [0x89, 0x7E, 0x00, 0x80];
+        assert_eq!(
+            FrameHeader::parse(&data).err(),
+            Some(FrameParseError::TooLargeControlFrame)
+        );
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use thiserror::Error;
+
+use super::{FrameHeader, OpCode};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MessageAssembleError {
+    #[error("unexpected continuation frame")]
+    UnexpectedContinuation,
+    #[error("new data frame found before the end of previous message")]
+    UnfinishedMessage,
+    #[error("rsv1 bit set in control or continuation frame")]
+    InvalidRsv1,
+}
+
+pub struct Message {
+    pub opcode: OpCode,
+    /// the rsv1 bit is set in the first frame, which means permessage-deflate is used
+    pub compressed: bool,
+    pub frame_count: usize,
+    pub payload_size: u64,
+    /// the unmasked payload data, will be None if not collected or the size exceeded the limit
+    pub payload: Option<Vec<u8>>,
+}
+
+/// Reassemble the fragmented data frames, control frames should be handled by the caller
+pub struct MessageAssembler {
+    max_buffer_size: usize,
+    message: Option<Message>,
+    frame_fin: bool,
+    frame_mask_key: Option<[u8; 4]>,
+    frame_offset: u64,
+}
+
+impl MessageAssembler {
+    pub fn new(max_buffer_size: usize) -> Self {
+        MessageAssembler {
+            max_buffer_size,
+            message: None,
+            frame_fin: false,
+            frame_mask_key: None,
+            frame_offset: 0,
+        }
+    }
+
+    /// Get the unfinished message
+    #[inline]
+    pub fn message(&self) -> Option<&Message> {
+        self.message.as_ref()
+    }
+
+    /// Start a new frame, return false if this is a control frame
+    ///
+    /// The payload data will be collected only if `collect` is true and this is the first frame
+    pub fn start_frame(
+        &mut self,
+        header: &FrameHeader,
+        collect: bool,
+    ) -> Result<bool, MessageAssembleError> {
+        if header.opcode.is_control() {
+            if header.rsv1 {
+                return Err(MessageAssembleError::InvalidRsv1);
+            }
+            return Ok(false);
+        }
+
+        match header.opcode {
+            OpCode::Continuation => {
+                if self.message.is_none() {
+                    return Err(MessageAssembleError::UnexpectedContinuation);
+                }
+                if header.rsv1 {
+                    return Err(MessageAssembleError::InvalidRsv1);
+                }
+            }
+            opcode => {
+                if self.message.is_some() {
+                    return Err(MessageAssembleError::UnfinishedMessage);
+                }
+                self.message = Some(Message {
+                    opcode,
+                    compressed: header.rsv1,
+                    frame_count: 0,
+                    payload_size: 0,
+                    payload: collect.then(Vec::new),
+                });
+            }
+        }
+
+        if let Some(message) = &mut self.message {
+            message.frame_count += 1;
+        }
+        self.frame_fin = header.fin;
+        self.frame_mask_key = header.mask_key;
+        self.frame_offset = 0;
+        Ok(true)
+    }
+
+    /// Push the raw payload data of the current data frame
+    pub fn push_payload(&mut self, data: &[u8]) {
+        let Some(message) = &mut self.message else {
+            return;
+        };
+
+        if let Some(buf) = &mut message.payload {
+            if buf.len() + data.len() > self.max_buffer_size {
+                message.payload = None;
+            } else {
+                let start = buf.len();
+                buf.extend_from_slice(data);
+                if let Some(mask_key) = self.frame_mask_key {
+                    super::unmask_payload(&mut buf[start..], mask_key, self.frame_offset);
+                }
+            }
+        }
+        message.payload_size += data.len() as u64;
+        self.frame_offset += data.len() as u64;
+    }
+
+    /// End the current data frame, the message will be returned if this is the final frame
+    pub fn end_frame(&mut self) -> Option<Message> {
+        if self.frame_fin {
+            self.message.take()
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_header(data: &[u8]) -> FrameHeader {
+        FrameHeader::parse(data).unwrap().0
+    }
+
+    #[test]
+    fn fragmented() {
+        let mut assembler = MessageAssembler::new(1024);
+
+        // masked text frame "Hel" without fin
+        let header = parse_header(&[0x01, 0x83, 0x37, 0xfa, 0x21, 0x3d]);
+        assert!(assembler.start_frame(&header, true).unwrap());
+        assembler.push_payload(&[0x7f]);
+        assembler.push_payload(&[0x9f, 0x4d]);
+        assert!(assembler.end_frame().is_none());
+
+        // ping frame in the middle
+        let header = parse_header(&[0x89, 0x00]);
+        assert!(!assembler.start_frame(&header, true).unwrap());
+
+        // masked continuation frame "lo" with fin
+        let header = parse_header(&[0x80, 0x82, 0x37, 0xfa, 0x21, 0x3d]);
+        assert!(assembler.start_frame(&header, true).unwrap());
+        assembler.push_payload(&[0x5b, 0x95]);
+        let message = assembler.end_frame().unwrap();
+        assert_eq!(message.opcode, OpCode::Text);
+        assert!(!message.compressed);
+        assert_eq!(message.frame_count, 2);
+        assert_eq!(message.payload_size, 5);
+        assert_eq!(message.payload.unwrap(), b"Hello");
+        assert!(assembler.message().is_none());
+    }
+
+    #[test]
+    fn exceed_limit() {
+        let mut assembler = MessageAssembler::new(4);
+
+        let header = parse_header(&[0xC2, 0x05]);
+        assert!(assembler.start_frame(&header, true).unwrap());
+        assembler.push_payload(b"Hello");
+        let message = assembler.end_frame().unwrap();
+        assert_eq!(message.opcode, OpCode::Binary);
+        assert!(message.compressed);
+        assert_eq!(message.payload_size, 5);
+        assert!(message.payload.is_none());
+    }
+
+    #[test]
+    fn invalid_sequence() {
+        let mut assembler = MessageAssembler::new(1024);
+
+        let header = parse_header(&[0x80, 0x00]);
+        assert_eq!(
+            assembler.start_frame(&header, false).err(),
+            Some(MessageAssembleError::UnexpectedContinuation)
+        );
+
+        let header = parse_header(&[0x01, 0x00]);
+        assert!(assembler.start_frame(&header, false).unwrap());
+        assert!(assembler.end_frame().is_none());
+        let header = parse_header(&[0x81, 0x00]);
+        assert_eq!(
+            assembler.start_frame(&header, false).err(),
+            Some(MessageAssembleError::UnfinishedMessage)
+        );
+
+        let header = parse_header(&[0xC0, 0x00]);
+        assert_eq!(
+            assembler.start_frame(&header, false).err(),
+            Some(MessageAssembleError::InvalidRsv1)
+        );
+    }
+}
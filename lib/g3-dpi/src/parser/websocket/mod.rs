@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod frame;
+pub use frame::{FrameHeader, FrameParseError, OpCode, unmask_payload};
+
+mod message;
+pub use message::{Message, MessageAssembleError, MessageAssembler};
+
+mod deflate;
+pub use deflate::{DeflateDecodeError, PerMessageDeflateDecoder, PerMessageDeflateParams};
@@ -71,6 +71,42 @@ impl MaybeProtocol {
     }
 }
 
+impl MaybeProtocol {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            MaybeProtocol::Http => "http",
+            MaybeProtocol::Smtp => "smtp",
+            MaybeProtocol::Odmr => "odmr",
+            MaybeProtocol::Ssh => "ssh",
+            MaybeProtocol::Ftp => "ftp",
+            MaybeProtocol::Dns => "dns",
+            MaybeProtocol::Pop3 => "pop3",
+            MaybeProtocol::Nntp => "nntp",
+            MaybeProtocol::Nnsp => "nnsp",
+            MaybeProtocol::Imap => "imap",
+            MaybeProtocol::Rtsp => "rtsp",
+            MaybeProtocol::Mqtt => "mqtt",
+            MaybeProtocol::Stomp => "stomp",
+            MaybeProtocol::Smpp => "smpp",
+            MaybeProtocol::Rtmp => "rtmp",
+            MaybeProtocol::Nats => "nats",
+            MaybeProtocol::BitTorrent => "bittorrent",
+            MaybeProtocol::Https => "https",
+            MaybeProtocol::Submissions => "submissions",
+            MaybeProtocol::Pop3s => "pop3s",
+            MaybeProtocol::Nntps => "nntps",
+            MaybeProtocol::Imaps => "imaps",
+            MaybeProtocol::Rtsps => "rtsps",
+            MaybeProtocol::SecureMqtt => "secure-mqtt",
+            MaybeProtocol::Ssmpp => "ssmpp",
+            MaybeProtocol::Rtmps => "rtmps",
+            MaybeProtocol::DnsOverTls => "dot",
+            MaybeProtocol::Ssl => "ssl",
+            MaybeProtocol::_MaxSize => "_max",
+        }
+    }
+}
+
 impl FromStr for MaybeProtocol {
     type Err = ();
 
@@ -81,6 +117,7 @@ impl FromStr for MaybeProtocol {
             "odmr" => Ok(MaybeProtocol::Odmr),
             "ssh" => Ok(MaybeProtocol::Ssh),
             "ftp" => Ok(MaybeProtocol::Ftp),
+            "dns" => Ok(MaybeProtocol::Dns),
             "pop3" => Ok(MaybeProtocol::Pop3),
             "nntp" => Ok(MaybeProtocol::Nntp),
             "nnsp" => Ok(MaybeProtocol::Nnsp),
@@ -23,12 +23,13 @@ impl Default for ProtocolPortMapValue {
 }
 
 impl ProtocolPortMapValue {
-    pub(crate) fn check_ssl(&self) -> bool {
+    #[inline]
+    pub fn check_ssl(&self) -> bool {
         self.check_ssl
     }
 
     #[inline]
-    pub(crate) fn protocols(&self) -> &[MaybeProtocol] {
+    pub fn protocols(&self) -> &[MaybeProtocol] {
         self.protocols.as_slice()
     }
 
@@ -153,4 +154,8 @@ impl ProtocolPortMap {
     pub fn get(&self, port: u16) -> Option<&ProtocolPortMapValue> {
         self.inner.get(&port)
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &ProtocolPortMapValue)> {
+        self.inner.iter().map(|(p, v)| (*p, v))
+    }
 }
@@ -6,6 +6,8 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use super::{FtpClientConfig, FtpControlConfig, FtpTransferConfig};
 
 impl FtpControlConfig {
@@ -112,3 +114,36 @@ impl FtpClientConfig {
         }
     }
 }
+
+impl YamlDump for FtpControlConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("max_line_len", &self.max_line_len)
+            .set("max_multi_lines", &self.max_multi_lines)
+            .set("command_timeout", &self.command_timeout);
+        map.build()
+    }
+}
+
+impl YamlDump for FtpTransferConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("list_max_line_len", &self.list_max_line_len)
+            .set("list_max_entries", &self.list_max_entries)
+            .set("list_all_timeout", &self.list_all_timeout)
+            .set("end_wait_timeout", &self.end_wait_timeout);
+        map.build()
+    }
+}
+
+impl YamlDump for FtpClientConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("control", &self.control)
+            .set("transfer", &self.transfer)
+            .set("connect_timeout", &self.connect_timeout)
+            .set("greeting_timeout", &self.greeting_timeout)
+            .set("always_try_epsv", &self.always_try_epsv);
+        map.build()
+    }
+}
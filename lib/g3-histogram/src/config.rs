@@ -31,6 +31,11 @@ impl HistogramMetricsConfig {
         self.quantile_list = list;
     }
 
+    #[inline]
+    pub fn quantile_list(&self) -> &BTreeSet<Quantile> {
+        &self.quantile_list
+    }
+
     #[inline]
     pub fn set_rotate_interval(&mut self, dur: Duration) {
         self.rotate_interval = dur;
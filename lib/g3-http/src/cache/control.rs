@@ -0,0 +1,202 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use http::header;
+
+use g3_types::net::HttpHeaderMap;
+
+/// Iterate over all cache directives in the value, quoted values are kept as is
+fn foreach_directive<F>(value: &str, mut f: F)
+where
+    F: FnMut(&str, Option<&str>),
+{
+    let mut in_quote = false;
+    let mut start = 0;
+    let bytes = value.as_bytes();
+    for i in 0..=bytes.len() {
+        if i < bytes.len() {
+            match bytes[i] {
+                b'"' => {
+                    in_quote = !in_quote;
+                    continue;
+                }
+                b',' if !in_quote => {}
+                _ => continue,
+            }
+        }
+
+        let item = value[start..i].trim();
+        start = i + 1;
+        if item.is_empty() {
+            continue;
+        }
+        match item.split_once('=') {
+            Some((k, v)) => f(k.trim(), Some(v.trim().trim_matches('"'))),
+            None => f(item, None),
+        }
+    }
+}
+
+fn parse_seconds(v: Option<&str>) -> Option<u64> {
+    let v = v?;
+    // a delta-seconds value larger than the max value should be treated as the max value
+    match v.parse::<u64>() {
+        Ok(n) => Some(n),
+        Err(_) if !v.is_empty() && v.bytes().all(|c| c.is_ascii_digit()) => Some(u64::MAX),
+        Err(_) => None,
+    }
+}
+
+/// Cache directives in response, see RFC 9111 Section 5.2.2
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ResponseCacheControl {
+    pub no_store: bool,
+    pub no_cache: bool,
+    pub private: bool,
+    pub public: bool,
+    pub must_revalidate: bool,
+    pub proxy_revalidate: bool,
+    pub max_age: Option<u64>,
+    pub s_maxage: Option<u64>,
+}
+
+impl ResponseCacheControl {
+    pub fn parse(headers: &HttpHeaderMap) -> Self {
+        let mut cc = ResponseCacheControl::default();
+        for v in headers.get_all(header::CACHE_CONTROL) {
+            foreach_directive(v.to_str(), |k, v| {
+                match k.to_ascii_lowercase().as_str() {
+                    "no-store" => cc.no_store = true,
+                    // the qualified form is also handled as unqualified, which is allowed
+                    "no-cache" => cc.no_cache = true,
+                    // the qualified form is also handled as unqualified, as we are a shared cache
+                    "private" => cc.private = true,
+                    "public" => cc.public = true,
+                    "must-revalidate" => cc.must_revalidate = true,
+                    "proxy-revalidate" => cc.proxy_revalidate = true,
+                    "max-age" => cc.max_age = parse_seconds(v),
+                    "s-maxage" => cc.s_maxage = parse_seconds(v),
+                    _ => {}
+                }
+            });
+        }
+        if !headers.contains_key(header::CACHE_CONTROL) && pragma_no_cache(headers) {
+            cc.no_cache = true;
+        }
+        cc
+    }
+
+    /// Check if stale responses should never be served without validation
+    pub fn revalidate_required(&self) -> bool {
+        // s-maxage also implies proxy-revalidate
+        self.must_revalidate || self.proxy_revalidate || self.s_maxage.is_some()
+    }
+}
+
+/// Cache directives in request, see RFC 9111 Section 5.2.1
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RequestCacheControl {
+    pub no_store: bool,
+    pub no_cache: bool,
+    pub only_if_cached: bool,
+    pub max_age: Option<u64>,
+    /// the value will be u64::MAX if no value is set
+    pub max_stale: Option<u64>,
+    pub min_fresh: Option<u64>,
+}
+
+impl RequestCacheControl {
+    pub fn parse(headers: &HttpHeaderMap) -> Self {
+        let mut cc = RequestCacheControl::default();
+        for v in headers.get_all(header::CACHE_CONTROL) {
+            foreach_directive(v.to_str(), |k, v| match k.to_ascii_lowercase().as_str() {
+                "no-store" => cc.no_store = true,
+                "no-cache" => cc.no_cache = true,
+                "only-if-cached" => cc.only_if_cached = true,
+                "max-age" => cc.max_age = parse_seconds(v),
+                "max-stale" => cc.max_stale = Some(parse_seconds(v).unwrap_or(u64::MAX)),
+                "min-fresh" => cc.min_fresh = parse_seconds(v),
+                _ => {}
+            });
+        }
+        if !headers.contains_key(header::CACHE_CONTROL) && pragma_no_cache(headers) {
+            cc.no_cache = true;
+        }
+        cc
+    }
+}
+
+fn pragma_no_cache(headers: &HttpHeaderMap) -> bool {
+    headers.get_all(header::PRAGMA).into_iter().any(|v| {
+        v.to_str()
+            .split(',')
+            .any(|s| s.trim().eq_ignore_ascii_case("no-cache"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use std::str::FromStr;
+
+    fn headers(lines: &[(&'static str, &str)]) -> HttpHeaderMap {
+        let mut map = HttpHeaderMap::default();
+        for (name, value) in lines {
+            map.append(
+                http::HeaderName::from_static(name),
+                HttpHeaderValue::from_str(value).unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn response_directives() {
+        let map = headers(&[
+            ("cache-control", "public, max-age=3600"),
+            ("cache-control", "s-maxage=\"60\", Must-Revalidate"),
+        ]);
+        let cc = ResponseCacheControl::parse(&map);
+        assert!(cc.public);
+        assert!(cc.must_revalidate);
+        assert_eq!(cc.max_age, Some(3600));
+        assert_eq!(cc.s_maxage, Some(60));
+        assert!(cc.revalidate_required());
+
+        let map = headers(&[("cache-control", "private=\"Set-Cookie, X-Foo\", no-cache")]);
+        let cc = ResponseCacheControl::parse(&map);
+        assert!(cc.private);
+        assert!(cc.no_cache);
+        assert!(!cc.revalidate_required());
+
+        let map = headers(&[("cache-control", "max-age=99999999999999999999999")]);
+        let cc = ResponseCacheControl::parse(&map);
+        assert_eq!(cc.max_age, Some(u64::MAX));
+
+        let map = headers(&[("cache-control", "max-age=abc")]);
+        let cc = ResponseCacheControl::parse(&map);
+        assert_eq!(cc.max_age, None);
+    }
+
+    #[test]
+    fn request_directives() {
+        let map = headers(&[("cache-control", "max-stale, min-fresh=10, only-if-cached")]);
+        let cc = RequestCacheControl::parse(&map);
+        assert_eq!(cc.max_stale, Some(u64::MAX));
+        assert_eq!(cc.min_fresh, Some(10));
+        assert!(cc.only_if_cached);
+        assert!(!cc.no_cache);
+
+        let map = headers(&[("pragma", "no-cache")]);
+        let cc = RequestCacheControl::parse(&map);
+        assert!(cc.no_cache);
+
+        let map = headers(&[("pragma", "no-cache"), ("cache-control", "max-age=0")]);
+        let cc = RequestCacheControl::parse(&map);
+        assert!(!cc.no_cache);
+        assert_eq!(cc.max_age, Some(0));
+    }
+}
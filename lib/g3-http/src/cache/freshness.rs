@@ -0,0 +1,268 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::{Duration, SystemTime};
+
+use http::header;
+
+use g3_types::net::HttpHeaderMap;
+
+use super::{RequestCacheControl, ResponseCacheControl};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpCacheFreshState {
+    Fresh,
+    /// stale but allowed to be served by the request
+    StaleAllowed,
+    Stale,
+}
+
+/// Freshness info of a stored response, see RFC 9111 Section 4.2
+#[derive(Clone, Debug)]
+pub struct HttpCacheFreshness {
+    lifetime: Duration,
+    corrected_initial_age: Duration,
+    response_time: SystemTime,
+    no_cache: bool,
+    revalidate_required: bool,
+}
+
+fn header_time(headers: &HttpHeaderMap, name: header::HeaderName) -> Option<SystemTime> {
+    headers
+        .get(name)
+        .and_then(|v| httpdate::parse_http_date(v.to_str()).ok())
+}
+
+impl HttpCacheFreshness {
+    /// Calculate the freshness for a response
+    ///
+    /// `request_time` is the local time when the request was sent,
+    /// `response_time` is the local time when the response header was received.
+    pub fn new(
+        rsp_code: u16,
+        rsp_headers: &HttpHeaderMap,
+        rsp_cc: &ResponseCacheControl,
+        request_time: SystemTime,
+        response_time: SystemTime,
+        heuristic_max_lifetime: Duration,
+    ) -> Self {
+        let date = header_time(rsp_headers, header::DATE).unwrap_or(response_time);
+
+        let lifetime = if let Some(secs) = rsp_cc.s_maxage.or(rsp_cc.max_age) {
+            Duration::from_secs(secs)
+        } else if rsp_headers.contains_key(header::EXPIRES) {
+            // invalid values, especially "0", represent a time in the past
+            header_time(rsp_headers, header::EXPIRES)
+                .and_then(|expires| expires.duration_since(date).ok())
+                .unwrap_or_default()
+        } else if rsp_cc.public || super::heuristically_cacheable(rsp_code) {
+            // use 10% of the time since last modification, see RFC 9111 Section 4.2.2
+            header_time(rsp_headers, header::LAST_MODIFIED)
+                .and_then(|last_modified| date.duration_since(last_modified).ok())
+                .map(|d| (d / 10).min(heuristic_max_lifetime))
+                .unwrap_or_default()
+        } else {
+            Duration::ZERO
+        };
+
+        let apparent_age = response_time.duration_since(date).unwrap_or_default();
+        let response_delay = response_time
+            .duration_since(request_time)
+            .unwrap_or_default();
+        let age_value = rsp_headers
+            .get(header::AGE)
+            .and_then(|v| v.to_str().trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        let corrected_age_value = age_value.saturating_add(response_delay);
+
+        HttpCacheFreshness {
+            lifetime,
+            corrected_initial_age: apparent_age.max(corrected_age_value),
+            response_time,
+            no_cache: rsp_cc.no_cache,
+            revalidate_required: rsp_cc.revalidate_required(),
+        }
+    }
+
+    #[inline]
+    pub fn lifetime(&self) -> Duration {
+        self.lifetime
+    }
+
+    /// Get the current age, which should be set in the Age header when serving
+    pub fn current_age(&self, now: SystemTime) -> Duration {
+        let resident_time = now.duration_since(self.response_time).unwrap_or_default();
+        self.corrected_initial_age.saturating_add(resident_time)
+    }
+
+    /// Check if the stored response can be served without validation, see RFC 9111 Section 4.2
+    pub fn check(&self, now: SystemTime, req_cc: &RequestCacheControl) -> HttpCacheFreshState {
+        if self.no_cache || req_cc.no_cache {
+            return HttpCacheFreshState::Stale;
+        }
+
+        let age = self.current_age(now);
+        if req_cc
+            .max_age
+            .is_some_and(|max_age| age > Duration::from_secs(max_age))
+        {
+            return HttpCacheFreshState::Stale;
+        }
+        let mut lifetime = self.lifetime;
+        if let Some(min_fresh) = req_cc.min_fresh {
+            lifetime = lifetime.saturating_sub(Duration::from_secs(min_fresh));
+        }
+        if lifetime > age {
+            return HttpCacheFreshState::Fresh;
+        }
+
+        if self.revalidate_required {
+            return HttpCacheFreshState::Stale;
+        }
+        if let Some(max_stale) = req_cc.max_stale {
+            let staleness = age - lifetime;
+            if staleness <= Duration::from_secs(max_stale) {
+                return HttpCacheFreshState::StaleAllowed;
+            }
+        }
+        HttpCacheFreshState::Stale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use http::HeaderName;
+    use std::str::FromStr;
+
+    const HEURISTIC_MAX: Duration = Duration::from_secs(86400);
+
+    fn headers(lines: &[(&'static str, String)]) -> HttpHeaderMap {
+        let mut map = HttpHeaderMap::default();
+        for (name, value) in lines {
+            map.append(
+                HeaderName::from_static(name),
+                HttpHeaderValue::from_str(value).unwrap(),
+            );
+        }
+        map
+    }
+
+    fn freshness(code: u16, rsp: &HttpHeaderMap, now: SystemTime) -> HttpCacheFreshness {
+        let cc = ResponseCacheControl::parse(rsp);
+        HttpCacheFreshness::new(code, rsp, &cc, now, now, HEURISTIC_MAX)
+    }
+
+    #[test]
+    fn max_age() {
+        let now = SystemTime::now();
+        let rsp = headers(&[
+            ("date", httpdate::fmt_http_date(now)),
+            ("cache-control", "max-age=60, s-maxage=30".to_string()),
+            ("age", "10".to_string()),
+        ]);
+        let f = freshness(200, &rsp, now);
+        assert_eq!(f.lifetime(), Duration::from_secs(30));
+        assert_eq!(f.current_age(now), Duration::from_secs(10));
+
+        let req_cc = RequestCacheControl::default();
+        assert_eq!(f.check(now, &req_cc), HttpCacheFreshState::Fresh);
+        let later = now + Duration::from_secs(20);
+        // s-maxage implies proxy-revalidate
+        let req_cc = RequestCacheControl {
+            max_stale: Some(u64::MAX),
+            ..Default::default()
+        };
+        assert_eq!(f.check(later, &req_cc), HttpCacheFreshState::Stale);
+
+        let req_cc = RequestCacheControl {
+            max_age: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(f.check(now, &req_cc), HttpCacheFreshState::Stale);
+    }
+
+    #[test]
+    fn max_stale() {
+        let now = SystemTime::now();
+        let rsp = headers(&[("cache-control", "max-age=60".to_string())]);
+        let f = freshness(200, &rsp, now);
+        let later = now + Duration::from_secs(90);
+
+        let req_cc = RequestCacheControl::default();
+        assert_eq!(f.check(later, &req_cc), HttpCacheFreshState::Stale);
+        let req_cc = RequestCacheControl {
+            max_stale: Some(60),
+            ..Default::default()
+        };
+        assert_eq!(f.check(later, &req_cc), HttpCacheFreshState::StaleAllowed);
+        let req_cc = RequestCacheControl {
+            max_stale: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(f.check(later, &req_cc), HttpCacheFreshState::Stale);
+    }
+
+    #[test]
+    fn expires() {
+        let now = SystemTime::now();
+        let rsp = headers(&[
+            ("date", httpdate::fmt_http_date(now)),
+            (
+                "expires",
+                httpdate::fmt_http_date(now + Duration::from_secs(120)),
+            ),
+        ]);
+        let f = freshness(200, &rsp, now);
+        assert_eq!(f.lifetime(), Duration::from_secs(120));
+
+        let rsp = headers(&[("expires", "0".to_string())]);
+        let f = freshness(200, &rsp, now);
+        assert_eq!(f.lifetime(), Duration::ZERO);
+        assert_eq!(
+            f.check(now, &RequestCacheControl::default()),
+            HttpCacheFreshState::Stale
+        );
+    }
+
+    #[test]
+    fn heuristic() {
+        let now = SystemTime::now();
+        let rsp = headers(&[
+            ("date", httpdate::fmt_http_date(now)),
+            (
+                "last-modified",
+                httpdate::fmt_http_date(now - Duration::from_secs(1000)),
+            ),
+        ]);
+        let f = freshness(200, &rsp, now);
+        assert_eq!(f.lifetime(), Duration::from_secs(100));
+        let f = freshness(302, &rsp, now);
+        assert_eq!(f.lifetime(), Duration::ZERO);
+
+        let rsp = headers(&[
+            ("date", httpdate::fmt_http_date(now)),
+            (
+                "last-modified",
+                httpdate::fmt_http_date(now - Duration::from_secs(100 * 86400)),
+            ),
+        ]);
+        let f = freshness(200, &rsp, now);
+        assert_eq!(f.lifetime(), HEURISTIC_MAX);
+    }
+
+    #[test]
+    fn no_cache() {
+        let now = SystemTime::now();
+        let rsp = headers(&[("cache-control", "no-cache, max-age=60".to_string())]);
+        let f = freshness(200, &rsp, now);
+        assert_eq!(
+            f.check(now, &RequestCacheControl::default()),
+            HttpCacheFreshState::Stale
+        );
+    }
+}
@@ -0,0 +1,16 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod control;
+pub use control::{RequestCacheControl, ResponseCacheControl};
+
+mod freshness;
+pub use freshness::{HttpCacheFreshState, HttpCacheFreshness};
+
+mod policy;
+pub use policy::{
+    heuristically_cacheable, request_cacheable, request_is_conditional, response_not_modified,
+    response_storable, update_stored_headers, vary_names, vary_values,
+};
@@ -0,0 +1,299 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use http::{HeaderName, Method, header};
+
+use g3_types::net::HttpHeaderMap;
+
+use super::ResponseCacheControl;
+
+/// Status codes that are defined as heuristically cacheable, see RFC 9110 Section 15.1
+pub fn heuristically_cacheable(code: u16) -> bool {
+    matches!(
+        code,
+        200 | 203 | 204 | 206 | 300 | 301 | 308 | 404 | 405 | 410 | 414 | 501
+    )
+}
+
+/// Check if the request may be served by or stored to a shared cache
+pub fn request_cacheable(method: &Method, headers: &HttpHeaderMap) -> bool {
+    if method != Method::GET && method != Method::HEAD {
+        return false;
+    }
+    // partial content is not supported
+    !headers.contains_key(header::RANGE)
+}
+
+/// Check if the request has conditional headers that should be evaluated by the origin
+pub fn request_is_conditional(headers: &HttpHeaderMap) -> bool {
+    headers.contains_key(header::IF_NONE_MATCH)
+        || headers.contains_key(header::IF_MODIFIED_SINCE)
+        || headers.contains_key(header::IF_MATCH)
+        || headers.contains_key(header::IF_UNMODIFIED_SINCE)
+        || headers.contains_key(header::IF_RANGE)
+}
+
+/// Check if the response can be stored in a shared cache, see RFC 9111 Section 3
+pub fn response_storable(
+    req_method: &Method,
+    req_headers: &HttpHeaderMap,
+    rsp_code: u16,
+    rsp_headers: &HttpHeaderMap,
+    rsp_cc: &ResponseCacheControl,
+) -> bool {
+    if req_method != Method::GET {
+        return false;
+    }
+    // partial content is not supported
+    if rsp_code < 200 || rsp_code == 206 || rsp_code == 304 {
+        return false;
+    }
+    if rsp_cc.no_store || rsp_cc.private {
+        return false;
+    }
+    if req_headers.contains_key(header::AUTHORIZATION)
+        && !(rsp_cc.public || rsp_cc.must_revalidate || rsp_cc.s_maxage.is_some())
+    {
+        return false;
+    }
+    // responses that set cookies are private to the client
+    if rsp_headers.contains_key(header::SET_COOKIE) {
+        return false;
+    }
+    if vary_names(rsp_headers).is_none() {
+        return false;
+    }
+
+    rsp_cc.public
+        || rsp_cc.max_age.is_some()
+        || rsp_cc.s_maxage.is_some()
+        || rsp_headers.contains_key(header::EXPIRES)
+        || heuristically_cacheable(rsp_code)
+}
+
+/// Get the header names in Vary, None will be returned if the response varies on everything
+pub fn vary_names(rsp_headers: &HttpHeaderMap) -> Option<Vec<HeaderName>> {
+    let mut names = Vec::new();
+    for v in rsp_headers.get_all(header::VARY) {
+        for s in v.to_str().split(',') {
+            let s = s.trim();
+            if s.is_empty() {
+                continue;
+            }
+            if s == "*" {
+                return None;
+            }
+            match HeaderName::from_bytes(s.as_bytes()) {
+                Ok(name) if !names.contains(&name) => names.push(name),
+                _ => {}
+            }
+        }
+    }
+    Some(names)
+}
+
+/// Get the normalized request header values selected by the Vary header names
+pub fn vary_values(names: &[HeaderName], req_headers: &HttpHeaderMap) -> Vec<Option<String>> {
+    names
+        .iter()
+        .map(|name| {
+            let mut value: Option<String> = None;
+            for v in req_headers.get_all(name) {
+                for s in v.to_str().split(',') {
+                    let s = s.trim();
+                    if s.is_empty() {
+                        continue;
+                    }
+                    match &mut value {
+                        Some(value) => {
+                            value.push_str(", ");
+                            value.push_str(s);
+                        }
+                        None => value = Some(s.to_string()),
+                    }
+                }
+            }
+            value
+        })
+        .collect()
+}
+
+fn etag_matches(etag: &str, list: &str) -> bool {
+    // weak comparison, see RFC 9110 Section 8.8.3.2
+    let etag = etag.trim().trim_start_matches("W/");
+    list.split(',').any(|s| {
+        let s = s.trim();
+        s == "*" || s.trim_start_matches("W/") == etag
+    })
+}
+
+/// Check if the stored response is not modified for the conditional request,
+/// see RFC 9111 Section 4.3.2
+pub fn response_not_modified(req_headers: &HttpHeaderMap, rsp_headers: &HttpHeaderMap) -> bool {
+    if req_headers.contains_key(header::IF_NONE_MATCH) {
+        let Some(etag) = rsp_headers.get(header::ETAG) else {
+            return false;
+        };
+        return req_headers
+            .get_all(header::IF_NONE_MATCH)
+            .into_iter()
+            .any(|v| etag_matches(etag.to_str(), v.to_str()));
+    }
+
+    let Some(since) = req_headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|v| httpdate::parse_http_date(v.to_str()).ok())
+    else {
+        return false;
+    };
+    let last_modified = rsp_headers
+        .get(header::LAST_MODIFIED)
+        .or_else(|| rsp_headers.get(header::DATE))
+        .and_then(|v| httpdate::parse_http_date(v.to_str()).ok());
+    match last_modified {
+        Some(t) => t <= since,
+        None => false,
+    }
+}
+
+/// Update the stored response headers by the headers in a 304 response,
+/// see RFC 9111 Section 3.2
+pub fn update_stored_headers(stored: &mut HttpHeaderMap, new: &HttpHeaderMap) {
+    let mut updated: Vec<HeaderName> = Vec::new();
+    new.for_each(|name, value| {
+        if *name == header::CONTENT_LENGTH
+            || *name == header::CONTENT_ENCODING
+            || *name == header::CONTENT_RANGE
+            || *name == header::TRANSFER_ENCODING
+        {
+            return;
+        }
+        if !updated.contains(name) {
+            stored.remove(name);
+            updated.push(name.clone());
+        }
+        stored.append(name.clone(), value.clone());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use std::str::FromStr;
+
+    fn headers(lines: &[(&'static str, &str)]) -> HttpHeaderMap {
+        let mut map = HttpHeaderMap::default();
+        for (name, value) in lines {
+            map.append(
+                HeaderName::from_static(name),
+                HttpHeaderValue::from_str(value).unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn storable() {
+        let req = headers(&[]);
+        let rsp = headers(&[("cache-control", "max-age=60")]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(response_storable(&Method::GET, &req, 200, &rsp, &cc));
+        assert!(!response_storable(&Method::HEAD, &req, 200, &rsp, &cc));
+        assert!(!response_storable(&Method::POST, &req, 200, &rsp, &cc));
+        assert!(!response_storable(&Method::GET, &req, 206, &rsp, &cc));
+        assert!(response_storable(&Method::GET, &req, 302, &rsp, &cc));
+
+        let rsp = headers(&[]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(response_storable(&Method::GET, &req, 404, &rsp, &cc));
+        assert!(!response_storable(&Method::GET, &req, 302, &rsp, &cc));
+
+        let rsp = headers(&[("cache-control", "private, max-age=60")]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(!response_storable(&Method::GET, &req, 200, &rsp, &cc));
+
+        let rsp = headers(&[("cache-control", "max-age=60"), ("set-cookie", "a=b")]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(!response_storable(&Method::GET, &req, 200, &rsp, &cc));
+
+        let rsp = headers(&[("vary", "*")]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(!response_storable(&Method::GET, &req, 200, &rsp, &cc));
+    }
+
+    #[test]
+    fn storable_with_auth() {
+        let req = headers(&[("authorization", "Basic dDp0")]);
+        let rsp = headers(&[("cache-control", "max-age=60")]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(!response_storable(&Method::GET, &req, 200, &rsp, &cc));
+
+        let rsp = headers(&[("cache-control", "public, max-age=60")]);
+        let cc = ResponseCacheControl::parse(&rsp);
+        assert!(response_storable(&Method::GET, &req, 200, &rsp, &cc));
+    }
+
+    #[test]
+    fn vary() {
+        let rsp = headers(&[
+            ("vary", "Accept-Encoding, accept-language"),
+            ("vary", "accept-encoding"),
+        ]);
+        let names = vary_names(&rsp).unwrap();
+        assert_eq!(names.len(), 2);
+
+        let req = headers(&[("accept-encoding", "gzip,br"), ("accept-encoding", "zstd")]);
+        let values = vary_values(&names, &req);
+        assert_eq!(values[0].as_deref(), Some("gzip, br, zstd"));
+        assert_eq!(values[1], None);
+    }
+
+    #[test]
+    fn not_modified() {
+        let rsp = headers(&[
+            ("etag", "W/\"abc\""),
+            ("last-modified", "Fri, 11 Nov 2022 03:22:03 GMT"),
+        ]);
+
+        let req = headers(&[("if-none-match", "\"xyz\", \"abc\"")]);
+        assert!(response_not_modified(&req, &rsp));
+        let req = headers(&[("if-none-match", "\"xyz\"")]);
+        assert!(!response_not_modified(&req, &rsp));
+        let req = headers(&[
+            ("if-none-match", "\"xyz\""),
+            ("if-modified-since", "Fri, 11 Nov 2022 03:22:03 GMT"),
+        ]);
+        assert!(!response_not_modified(&req, &rsp));
+
+        let req = headers(&[("if-modified-since", "Fri, 11 Nov 2022 03:22:03 GMT")]);
+        assert!(response_not_modified(&req, &rsp));
+        let req = headers(&[("if-modified-since", "Thu, 10 Nov 2022 03:22:03 GMT")]);
+        assert!(!response_not_modified(&req, &rsp));
+    }
+
+    #[test]
+    fn update_headers() {
+        let mut stored = headers(&[
+            ("etag", "\"abc\""),
+            ("content-length", "4"),
+            ("cache-control", "max-age=60"),
+        ]);
+        let new = headers(&[
+            ("cache-control", "max-age=120"),
+            ("content-length", "0"),
+            ("x-new", "1"),
+            ("x-new", "2"),
+        ]);
+        update_stored_headers(&mut stored, &new);
+        assert_eq!(stored.get(header::ETAG).unwrap().to_str(), "\"abc\"");
+        assert_eq!(stored.get(header::CONTENT_LENGTH).unwrap().to_str(), "4");
+        assert_eq!(
+            stored.get(header::CACHE_CONTROL).unwrap().to_str(),
+            "max-age=120"
+        );
+        assert_eq!(stored.get_all("x-new").into_iter().count(), 2);
+    }
+}
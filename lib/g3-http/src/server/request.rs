@@ -136,6 +136,46 @@ impl HttpProxyClientRequest {
         }
     }
 
+    /// Build a request without body to validate a stored response,
+    /// the conditional headers from the client will be replaced by the validators
+    pub fn build_cache_validation(
+        &self,
+        etag: Option<&HttpHeaderValue>,
+        last_modified: Option<&HttpHeaderValue>,
+    ) -> Self {
+        let mut end_to_end_headers = self.end_to_end_headers.clone();
+        end_to_end_headers.remove(header::IF_MATCH);
+        end_to_end_headers.remove(header::IF_NONE_MATCH);
+        end_to_end_headers.remove(header::IF_MODIFIED_SINCE);
+        end_to_end_headers.remove(header::IF_UNMODIFIED_SINCE);
+        end_to_end_headers.remove(header::IF_RANGE);
+        if let Some(v) = etag {
+            end_to_end_headers.insert(header::IF_NONE_MATCH, v.clone());
+        }
+        if let Some(v) = last_modified {
+            end_to_end_headers.insert(header::IF_MODIFIED_SINCE, v.clone());
+        }
+        let mut hop_by_hop_headers = self.hop_by_hop_headers.clone();
+        hop_by_hop_headers.remove(header::TRANSFER_ENCODING);
+        HttpProxyClientRequest {
+            version: self.version,
+            method: self.method.clone(),
+            uri: self.uri.clone(),
+            end_to_end_headers,
+            hop_by_hop_headers,
+            auth_info: HttpAuth::None,
+            host: self.host.clone(),
+            original_connection_name: self.original_connection_name.clone(),
+            extra_connection_headers: self.extra_connection_headers.clone(),
+            origin_header_size: self.origin_header_size,
+            keep_alive: self.keep_alive,
+            content_length: 0,
+            chunked_transfer: false,
+            has_transfer_encoding: false,
+            has_content_length: false,
+        }
+    }
+
     #[inline]
     pub fn origin_header_size(&self) -> usize {
         self.origin_header_size
@@ -3,6 +3,8 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::fmt::Write;
+use std::net::IpAddr;
 use std::str::FromStr;
 
 use http::Uri;
@@ -19,6 +21,25 @@ pub enum HttpMasque {
 }
 
 impl HttpMasque {
+    /// Build the path of the default connect-udp URI template
+    /// `/.well-known/masque/udp/{target_host}/{target_port}/`,
+    /// colons in IPv6 address will be percent-encoded as required by RFC 9298
+    pub fn udp_path(target: &UpstreamAddr) -> String {
+        let mut path = String::with_capacity(64);
+        path.push_str("/.well-known/masque/udp/");
+        match target.host() {
+            Host::Ip(IpAddr::V6(ip6)) => {
+                let ip6 = ip6.to_string();
+                path.push_str(&ip6.replace(':', "%3A"));
+            }
+            host => {
+                let _ = write!(path, "{host}");
+            }
+        }
+        let _ = write!(path, "/{}/", target.port());
+        path
+    }
+
     pub(super) fn new_udp(host: &str, port: &str) -> Result<Self, UriParseError> {
         let host = Host::from_str(host).map_err(|_| UriParseError::NotValidHost("target_host"))?;
         let port = u16::from_str(port).map_err(|_| UriParseError::NotValidPort("target_port"))?;
@@ -47,3 +68,29 @@ impl HttpMasque {
         Ok(HttpMasque::Http(uri))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn udp_path() {
+        let target = UpstreamAddr::from_str("192.0.2.6:443").unwrap();
+        assert_eq!(
+            HttpMasque::udp_path(&target),
+            "/.well-known/masque/udp/192.0.2.6/443/"
+        );
+
+        let target = UpstreamAddr::from_str("[2001:db8::42]:53").unwrap();
+        assert_eq!(
+            HttpMasque::udp_path(&target),
+            "/.well-known/masque/udp/2001%3Adb8%3A%3A42/53/"
+        );
+
+        let target = UpstreamAddr::from_str("target.example.com:8443").unwrap();
+        assert_eq!(
+            HttpMasque::udp_path(&target),
+            "/.well-known/masque/udp/target.example.com/8443/"
+        );
+    }
+}
@@ -23,6 +23,8 @@ pub mod mail;
 pub mod imap;
 pub mod smtp;
 
+pub mod websocket;
+
 #[derive(Clone)]
 pub struct IcapReqmodClient {
     inner: Arc<IcapServiceClient>,
@@ -0,0 +1,32 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+
+use thiserror::Error;
+
+use g3_http::client::HttpResponseParseError;
+use g3_http::server::HttpRequestParseError;
+
+use crate::reqmod::IcapReqmodParseError;
+use crate::respmod::IcapRespmodParseError;
+
+#[derive(Debug, Error)]
+pub enum WebSocketAdaptationError {
+    #[error("write to icap server failed: {0:?}")]
+    IcapServerWriteFailed(io::Error),
+    #[error("read from icap server failed: {0:?}")]
+    IcapServerReadFailed(io::Error),
+    #[error("invalid reqmod response from icap server: {0}")]
+    InvalidIcapReqmodResponse(#[from] IcapReqmodParseError),
+    #[error("invalid respmod response from icap server: {0}")]
+    InvalidIcapRespmodResponse(#[from] IcapRespmodParseError),
+    #[error("invalid http response from icap server: {0}")]
+    InvalidIcapServerHttpResponse(#[from] HttpResponseParseError),
+    #[error("invalid http request from icap server: {0}")]
+    InvalidIcapServerHttpRequest(#[from] HttpRequestParseError),
+    #[error("error response from icap server: {0} {1}")]
+    IcapServerErrorResponse(u16, String),
+}
@@ -0,0 +1,248 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{IoSlice, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::BufMut;
+use tokio::io::{AsyncBufRead, AsyncReadExt, AsyncWriteExt};
+
+use g3_http::HttpBodyDecodeReader;
+use g3_http::client::HttpAdaptedResponse;
+use g3_http::server::HttpAdaptedRequest;
+use g3_io_ext::LimitedWriteExt;
+
+use super::{IcapReqmodClient, IcapReqmodResponsePayload};
+use crate::reqmod::response::ReqmodResponse;
+use crate::{IcapClientConnection, IcapServiceClient, IcapServiceOptions};
+
+mod error;
+pub use error::WebSocketAdaptationError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebSocketAdaptationAction {
+    Forward,
+    Block,
+}
+
+/// A complete and decompressed websocket data message
+pub struct WebSocketMessageForAdaptation<'a> {
+    pub host: &'a str,
+    pub resource_name: &'a str,
+    pub text: bool,
+    pub payload: &'a [u8],
+}
+
+impl WebSocketMessageForAdaptation<'_> {
+    pub(crate) fn content_type(&self) -> &'static str {
+        if self.text {
+            "text/plain; charset=utf-8"
+        } else {
+            "application/octet-stream"
+        }
+    }
+
+    pub(crate) fn push_chunked_body(&self, buf: &mut Vec<u8>) {
+        if !self.payload.is_empty() {
+            let _ = write!(buf, "{:x}\r\n", self.payload.len());
+        }
+    }
+
+    pub(crate) fn chunked_end(&self) -> &'static [u8] {
+        if self.payload.is_empty() {
+            b"0\r\n\r\n"
+        } else {
+            b"\r\n0\r\n\r\n"
+        }
+    }
+}
+
+/// Read the adapted message body and check if it's the same as the original one
+pub(crate) async fn check_adapted_body<R>(
+    reader: &mut R,
+    original: &[u8],
+) -> Result<(bool, bool), WebSocketAdaptationError>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut body_reader = HttpBodyDecodeReader::new_chunked(reader, 256);
+    let mut adapted = Vec::with_capacity(original.len());
+    (&mut body_reader)
+        .take(original.len() as u64 + 1)
+        .read_to_end(&mut adapted)
+        .await
+        .map_err(WebSocketAdaptationError::IcapServerReadFailed)?;
+    Ok((adapted == original, body_reader.finished()))
+}
+
+impl IcapReqmodClient {
+    pub async fn websocket_message_adaptor(&self) -> anyhow::Result<WebSocketMessageAdapter> {
+        let icap_client = self.inner.clone();
+        let (icap_connection, icap_options) = icap_client.fetch_connection().await?;
+        Ok(WebSocketMessageAdapter {
+            icap_client,
+            icap_connection,
+            icap_options,
+            client_addr: None,
+            client_username: None,
+        })
+    }
+}
+
+/// Adapter for websocket messages sent by the client
+pub struct WebSocketMessageAdapter {
+    icap_client: Arc<IcapServiceClient>,
+    icap_connection: IcapClientConnection,
+    icap_options: Arc<IcapServiceOptions>,
+    client_addr: Option<SocketAddr>,
+    client_username: Option<Arc<str>>,
+}
+
+impl WebSocketMessageAdapter {
+    pub fn set_client_addr(&mut self, addr: SocketAddr) {
+        self.client_addr = Some(addr);
+    }
+
+    pub fn set_client_username(&mut self, user: Arc<str>) {
+        self.client_username = Some(user);
+    }
+
+    fn build_http_header(&self, message: &WebSocketMessageForAdaptation<'_>) -> Vec<u8> {
+        let mut header = Vec::with_capacity(128);
+        let _ = write!(
+            header,
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            message.resource_name,
+            message.host,
+            message.content_type(),
+            message.payload.len()
+        );
+        header
+    }
+
+    fn build_icap_header(&self, http_header_len: usize) -> Vec<u8> {
+        let mut header = Vec::with_capacity(self.icap_client.partial_request_header.len() + 128);
+        header.extend_from_slice(&self.icap_client.partial_request_header);
+        header.put_slice(b"X-Transformed-From: WebSocket\r\n");
+        if let Some(addr) = self.client_addr {
+            crate::serialize::add_client_addr(&mut header, addr);
+        }
+        if let Some(user) = &self.client_username {
+            crate::serialize::add_client_username(&mut header, user);
+        }
+        if self.icap_options.support_204 {
+            header.put_slice(b"Allow: 204\r\n");
+        }
+        let _ = write!(
+            header,
+            "Encapsulated: req-hdr=0, req-body={http_header_len}\r\n",
+        );
+        header.put_slice(b"\r\n");
+        header
+    }
+
+    pub async fn adapt(
+        mut self,
+        message: &WebSocketMessageForAdaptation<'_>,
+    ) -> Result<WebSocketAdaptationAction, WebSocketAdaptationError> {
+        let http_header = self.build_http_header(message);
+        let icap_header = self.build_icap_header(http_header.len());
+        let mut chunked_header = Vec::with_capacity(16);
+        message.push_chunked_body(&mut chunked_header);
+
+        let icap_w = &mut self.icap_connection.writer;
+        icap_w
+            .write_all_vectored([
+                IoSlice::new(&icap_header),
+                IoSlice::new(&http_header),
+                IoSlice::new(&chunked_header),
+                IoSlice::new(message.payload),
+                IoSlice::new(message.chunked_end()),
+            ])
+            .await
+            .map_err(WebSocketAdaptationError::IcapServerWriteFailed)?;
+        icap_w
+            .flush()
+            .await
+            .map_err(WebSocketAdaptationError::IcapServerWriteFailed)?;
+        self.icap_connection.mark_writer_finished();
+
+        let rsp = ReqmodResponse::parse(
+            &mut self.icap_connection.reader,
+            self.icap_client.config.icap_max_header_size,
+            &self.icap_client.config.respond_shared_names,
+        )
+        .await?;
+
+        match rsp.code {
+            204 => {
+                self.save_connection(rsp.keep_alive);
+                Ok(WebSocketAdaptationAction::Forward)
+            }
+            206 => Err(WebSocketAdaptationError::IcapServerErrorResponse(
+                rsp.code, rsp.reason,
+            )),
+            n if (200..300).contains(&n) => match rsp.payload {
+                IcapReqmodResponsePayload::NoPayload => {
+                    self.save_connection(rsp.keep_alive);
+                    Err(WebSocketAdaptationError::IcapServerErrorResponse(
+                        rsp.code, rsp.reason,
+                    ))
+                }
+                IcapReqmodResponsePayload::HttpRequestWithoutBody(header_size) => {
+                    HttpAdaptedRequest::parse(&mut self.icap_connection.reader, header_size, true)
+                        .await?;
+                    self.save_connection(rsp.keep_alive);
+                    if message.payload.is_empty() {
+                        Ok(WebSocketAdaptationAction::Forward)
+                    } else {
+                        Ok(WebSocketAdaptationAction::Block)
+                    }
+                }
+                IcapReqmodResponsePayload::HttpRequestWithBody(header_size) => {
+                    HttpAdaptedRequest::parse(&mut self.icap_connection.reader, header_size, true)
+                        .await?;
+                    let (unmodified, finished) =
+                        check_adapted_body(&mut self.icap_connection.reader, message.payload)
+                            .await?;
+                    if finished {
+                        self.save_connection(rsp.keep_alive);
+                    }
+                    // modification of websocket messages is not supported
+                    if unmodified {
+                        Ok(WebSocketAdaptationAction::Forward)
+                    } else {
+                        Ok(WebSocketAdaptationAction::Block)
+                    }
+                }
+                IcapReqmodResponsePayload::HttpResponseWithoutBody(header_size) => {
+                    HttpAdaptedResponse::parse(&mut self.icap_connection.reader, header_size)
+                        .await?;
+                    self.save_connection(rsp.keep_alive);
+                    Ok(WebSocketAdaptationAction::Block)
+                }
+                IcapReqmodResponsePayload::HttpResponseWithBody(_) => {
+                    Ok(WebSocketAdaptationAction::Block)
+                }
+            },
+            _ => {
+                if rsp.payload == IcapReqmodResponsePayload::NoPayload {
+                    self.save_connection(rsp.keep_alive);
+                }
+                Err(WebSocketAdaptationError::IcapServerErrorResponse(
+                    rsp.code, rsp.reason,
+                ))
+            }
+        }
+    }
+
+    fn save_connection(mut self, keep_alive: bool) {
+        self.icap_connection.mark_reader_finished();
+        if keep_alive {
+            self.icap_client.save_connection(self.icap_connection);
+        }
+    }
+}
@@ -18,6 +18,8 @@ mod response;
 pub mod h1;
 pub mod h2;
 
+pub mod websocket;
+
 #[derive(Clone)]
 pub struct IcapRespmodClient {
     inner: Arc<IcapServiceClient>,
@@ -0,0 +1,199 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{IoSlice, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::BufMut;
+use http::StatusCode;
+use tokio::io::AsyncWriteExt;
+
+use g3_http::client::HttpAdaptedResponse;
+use g3_io_ext::LimitedWriteExt;
+
+use super::{IcapRespmodClient, IcapRespmodResponsePayload};
+use crate::reqmod::websocket::{
+    WebSocketAdaptationAction, WebSocketAdaptationError, WebSocketMessageForAdaptation,
+};
+use crate::respmod::response::RespmodResponse;
+use crate::{IcapClientConnection, IcapServiceClient, IcapServiceOptions};
+
+impl IcapRespmodClient {
+    pub async fn websocket_message_adaptor(&self) -> anyhow::Result<WebSocketMessageAdapter> {
+        let icap_client = self.inner.clone();
+        let (icap_connection, icap_options) = icap_client.fetch_connection().await?;
+        Ok(WebSocketMessageAdapter {
+            icap_client,
+            icap_connection,
+            icap_options,
+            client_addr: None,
+            client_username: None,
+        })
+    }
+}
+
+/// Adapter for websocket messages sent by the server
+pub struct WebSocketMessageAdapter {
+    icap_client: Arc<IcapServiceClient>,
+    icap_connection: IcapClientConnection,
+    icap_options: Arc<IcapServiceOptions>,
+    client_addr: Option<SocketAddr>,
+    client_username: Option<Arc<str>>,
+}
+
+impl WebSocketMessageAdapter {
+    pub fn set_client_addr(&mut self, addr: SocketAddr) {
+        self.client_addr = Some(addr);
+    }
+
+    pub fn set_client_username(&mut self, user: Arc<str>) {
+        self.client_username = Some(user);
+    }
+
+    fn build_http_req_header(&self, message: &WebSocketMessageForAdaptation<'_>) -> Vec<u8> {
+        let mut header = Vec::with_capacity(64);
+        let _ = write!(
+            header,
+            "GET {} HTTP/1.1\r\nHost: {}\r\n\r\n",
+            message.resource_name, message.host,
+        );
+        header
+    }
+
+    fn build_http_rsp_header(&self, message: &WebSocketMessageForAdaptation<'_>) -> Vec<u8> {
+        let mut header = Vec::with_capacity(96);
+        let _ = write!(
+            header,
+            "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\n\r\n",
+            message.content_type(),
+            message.payload.len()
+        );
+        header
+    }
+
+    fn build_icap_header(&self, http_req_header_len: usize, http_rsp_header_len: usize) -> Vec<u8> {
+        let mut header = Vec::with_capacity(self.icap_client.partial_request_header.len() + 128);
+        header.extend_from_slice(&self.icap_client.partial_request_header);
+        header.put_slice(b"X-Transformed-From: WebSocket\r\n");
+        if let Some(addr) = self.client_addr {
+            crate::serialize::add_client_addr(&mut header, addr);
+        }
+        if let Some(user) = &self.client_username {
+            crate::serialize::add_client_username(&mut header, user);
+        }
+        if self.icap_options.support_204 {
+            header.put_slice(b"Allow: 204\r\n");
+        }
+        let _ = write!(
+            header,
+            "Encapsulated: req-hdr=0, res-hdr={http_req_header_len}, res-body={}\r\n",
+            http_req_header_len + http_rsp_header_len
+        );
+        header.put_slice(b"\r\n");
+        header
+    }
+
+    pub async fn adapt(
+        mut self,
+        message: &WebSocketMessageForAdaptation<'_>,
+    ) -> Result<WebSocketAdaptationAction, WebSocketAdaptationError> {
+        let http_req_header = self.build_http_req_header(message);
+        let http_rsp_header = self.build_http_rsp_header(message);
+        let icap_header = self.build_icap_header(http_req_header.len(), http_rsp_header.len());
+        let mut chunked_header = Vec::with_capacity(16);
+        message.push_chunked_body(&mut chunked_header);
+
+        let icap_w = &mut self.icap_connection.writer;
+        icap_w
+            .write_all_vectored([
+                IoSlice::new(&icap_header),
+                IoSlice::new(&http_req_header),
+                IoSlice::new(&http_rsp_header),
+                IoSlice::new(&chunked_header),
+                IoSlice::new(message.payload),
+                IoSlice::new(message.chunked_end()),
+            ])
+            .await
+            .map_err(WebSocketAdaptationError::IcapServerWriteFailed)?;
+        icap_w
+            .flush()
+            .await
+            .map_err(WebSocketAdaptationError::IcapServerWriteFailed)?;
+        self.icap_connection.mark_writer_finished();
+
+        let rsp = RespmodResponse::parse(
+            &mut self.icap_connection.reader,
+            self.icap_client.config.icap_max_header_size,
+        )
+        .await?;
+
+        match rsp.code {
+            204 => {
+                self.save_connection(rsp.keep_alive);
+                Ok(WebSocketAdaptationAction::Forward)
+            }
+            206 => Err(WebSocketAdaptationError::IcapServerErrorResponse(
+                rsp.code, rsp.reason,
+            )),
+            n if (200..300).contains(&n) => match rsp.payload {
+                IcapRespmodResponsePayload::NoPayload => {
+                    self.save_connection(rsp.keep_alive);
+                    Err(WebSocketAdaptationError::IcapServerErrorResponse(
+                        rsp.code, rsp.reason,
+                    ))
+                }
+                IcapRespmodResponsePayload::HttpResponseWithoutBody(header_size) => {
+                    let http_rsp =
+                        HttpAdaptedResponse::parse(&mut self.icap_connection.reader, header_size)
+                            .await?;
+                    self.save_connection(rsp.keep_alive);
+                    if http_rsp.status == StatusCode::OK && message.payload.is_empty() {
+                        Ok(WebSocketAdaptationAction::Forward)
+                    } else {
+                        Ok(WebSocketAdaptationAction::Block)
+                    }
+                }
+                IcapRespmodResponsePayload::HttpResponseWithBody(header_size) => {
+                    let http_rsp =
+                        HttpAdaptedResponse::parse(&mut self.icap_connection.reader, header_size)
+                            .await?;
+                    if http_rsp.status != StatusCode::OK {
+                        return Ok(WebSocketAdaptationAction::Block);
+                    }
+                    let (unmodified, finished) = crate::reqmod::websocket::check_adapted_body(
+                        &mut self.icap_connection.reader,
+                        message.payload,
+                    )
+                    .await?;
+                    if finished {
+                        self.save_connection(rsp.keep_alive);
+                    }
+                    // modification of websocket messages is not supported
+                    if unmodified {
+                        Ok(WebSocketAdaptationAction::Forward)
+                    } else {
+                        Ok(WebSocketAdaptationAction::Block)
+                    }
+                }
+            },
+            _ => {
+                if rsp.payload == IcapRespmodResponsePayload::NoPayload {
+                    self.save_connection(rsp.keep_alive);
+                }
+                Err(WebSocketAdaptationError::IcapServerErrorResponse(
+                    rsp.code, rsp.reason,
+                ))
+            }
+        }
+    }
+
+    fn save_connection(mut self, keep_alive: bool) {
+        self.icap_connection.mark_reader_finished();
+        if keep_alive {
+            self.icap_client.save_connection(self.icap_connection);
+        }
+    }
+}
@@ -10,6 +10,9 @@ use anyhow::{Context, anyhow};
 use url::Url;
 use yaml_rust::{Yaml, yaml};
 
+use g3_types::net::HttpAuth;
+use g3_yaml::dump::{REDACTED_VALUE, YamlDump, YamlMapDumper};
+
 use super::{IcapMethod, IcapServiceConfig};
 
 impl IcapServiceConfig {
@@ -124,3 +127,40 @@ impl IcapServiceConfig {
         }
     }
 }
+
+impl IcapServiceConfig {
+    /// the url with the auth info that has been stripped in the constructor
+    fn dump_url(&self, redact: bool) -> Yaml {
+        let mut url = self.url.clone();
+        if let HttpAuth::Basic(auth) = &self.auth {
+            let password = if redact {
+                REDACTED_VALUE
+            } else {
+                auth.password.as_original()
+            };
+            if url.set_username(auth.username.as_original()).is_err()
+                || url.set_password(Some(password)).is_err()
+            {
+                return Yaml::Null;
+            }
+        }
+        Yaml::String(url.to_string())
+    }
+}
+
+impl YamlDump for IcapServiceConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set_yaml("url", self.dump_url(redact))
+            .set("tls_client", &self.tls_client)
+            .set("tls_name", &self.tls_name)
+            .set("tcp_keepalive", &self.tcp_keepalive)
+            .set("connection_pool", &self.connection_pool)
+            .set("icap_max_header_size", &self.icap_max_header_size)
+            .set("no_preview", &self.disable_preview)
+            .set("preview_data_read_timeout", &self.preview_data_read_timeout)
+            .set("respond_shared_names", &self.respond_shared_names)
+            .set("bypass", &self.bypass);
+        map.build()
+    }
+}
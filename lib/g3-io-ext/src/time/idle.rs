@@ -42,6 +42,8 @@ impl IdleInterval {
 #[derive(Clone, Copy, Debug)]
 pub enum IdleForceQuitReason {
     UserBlocked,
+    UserRemoved,
+    UserExpired,
     ServerQuit,
 }
 
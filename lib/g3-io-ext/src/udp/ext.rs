@@ -405,6 +405,61 @@ mod tests {
         assert_eq!(&recv_msg1[..msg_1.len()], msg_1);
     }
 
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[tokio::test]
+    async fn send_src_ip_v4() {
+        let listen_config = UdpListenConfig::new(SocketAddr::from_str("0.0.0.0:0").unwrap());
+        let s_sock = g3_socket::udp::new_std_bind_listen(&listen_config).unwrap();
+        let s_sock = UdpSocket::from_std(s_sock).unwrap();
+        let s_addr = s_sock.local_addr().unwrap();
+
+        let c_sock = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let c_addr = c_sock.local_addr().unwrap();
+
+        // any address in 127.0.0.0/8 is local, so use a different one than the client used
+        let src_ip = IpAddr::from_str("127.0.0.2").unwrap();
+        let msg_1 = b"abcd";
+        let mut hdr = SendMsgHdr::new([IoSlice::new(msg_1)], Some(c_addr));
+        hdr.set_src_ip(src_ip);
+        let nw = poll_fn(|cx| s_sock.poll_sendmsg(cx, &hdr)).await.unwrap();
+        assert_eq!(nw, msg_1.len());
+
+        let mut recv_msg1 = [0u8; 16];
+        let (nr, peer_addr) = c_sock.recv_from(&mut recv_msg1).await.unwrap();
+        assert_eq!(nr, msg_1.len());
+        assert_eq!(peer_addr, SocketAddr::new(src_ip, s_addr.port()));
+        assert_eq!(&recv_msg1[..nr], msg_1);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[tokio::test]
+    async fn send_src_ip_mapped_v4() {
+        let mut listen_config = UdpListenConfig::new(SocketAddr::from_str("[::]:0").unwrap());
+        listen_config.set_ipv6_only(false);
+        let s_sock = g3_socket::udp::new_std_bind_listen(&listen_config).unwrap();
+        let s_sock = UdpSocket::from_std(s_sock).unwrap();
+        let s_addr = s_sock.local_addr().unwrap();
+
+        let c_sock = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let c_addr = c_sock.local_addr().unwrap();
+        let mapped_c_addr =
+            SocketAddr::new(IpAddr::from_str("::ffff:127.0.0.1").unwrap(), c_addr.port());
+
+        let msg_1 = b"abcd";
+        let mut hdr = SendMsgHdr::new([IoSlice::new(msg_1)], Some(mapped_c_addr));
+        hdr.set_src_ip(IpAddr::from_str("::ffff:127.0.0.2").unwrap());
+        let nw = poll_fn(|cx| s_sock.poll_sendmsg(cx, &hdr)).await.unwrap();
+        assert_eq!(nw, msg_1.len());
+
+        let mut recv_msg1 = [0u8; 16];
+        let (nr, peer_addr) = c_sock.recv_from(&mut recv_msg1).await.unwrap();
+        assert_eq!(nr, msg_1.len());
+        assert_eq!(
+            peer_addr,
+            SocketAddr::new(IpAddr::from_str("127.0.0.2").unwrap(), s_addr.port())
+        );
+    }
+
     #[cfg(not(target_os = "openbsd"))]
     #[tokio::test]
     async fn recv_ancillary_mapped_v4() {
@@ -73,7 +73,17 @@ impl LimitedUdpRelayConfig {
         self.yield_size = yield_size.max(MINIMUM_UDP_RELAY_YIELD_SIZE);
     }
 
+    #[inline]
+    pub fn yield_size(&self) -> usize {
+        self.yield_size
+    }
+
     pub fn set_batch_size(&mut self, batch_size: usize) {
         self.batch_size = batch_size;
     }
+
+    #[inline]
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
 }
@@ -5,6 +5,8 @@
 
 use std::io;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6};
 
 use super::{RecvAncillaryBuffer, RecvAncillaryData};
 
@@ -130,6 +132,23 @@ impl RecvAncillaryBuffer {
                         let ip4 = Ipv4Addr::from(u32::from_be(ipaddr.s_addr));
                         data.set_recv_dst_addr(IpAddr::V4(ip4));
                     }
+                    #[cfg(any(target_os = "linux", target_os = "android"))]
+                    libc::IP_ORIGDSTADDR => {
+                        if payload.len() < size_of::<libc::sockaddr_in>() {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "no enough msg data for struct sockaddr_in",
+                            ));
+                        }
+                        let addr: &libc::sockaddr_in = unsafe {
+                            (payload.as_ptr() as *const libc::sockaddr_in)
+                                .as_ref()
+                                .unwrap()
+                        };
+                        let ip4 = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+                        let port = u16::from_be(addr.sin_port);
+                        data.set_orig_dst_addr(SocketAddr::V4(SocketAddrV4::new(ip4, port)));
+                    }
                     _ => {}
                 },
                 libc::IPPROTO_IPV6 => match hdr.cmsg_type {
@@ -150,6 +169,28 @@ impl RecvAncillaryBuffer {
                         let ip6 = Ipv6Addr::from(pktinfo.ipi6_addr.s6_addr);
                         data.set_recv_dst_addr(IpAddr::V6(ip6));
                     }
+                    #[cfg(any(target_os = "linux", target_os = "android"))]
+                    libc::IPV6_ORIGDSTADDR => {
+                        if payload.len() < size_of::<libc::sockaddr_in6>() {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidData,
+                                "no enough msg data for struct sockaddr_in6",
+                            ));
+                        }
+                        let addr: &libc::sockaddr_in6 = unsafe {
+                            (payload.as_ptr() as *const libc::sockaddr_in6)
+                                .as_ref()
+                                .unwrap()
+                        };
+                        let ip6 = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+                        let port = u16::from_be(addr.sin6_port);
+                        data.set_orig_dst_addr(SocketAddr::V6(SocketAddrV6::new(
+                            ip6,
+                            port,
+                            addr.sin6_flowinfo,
+                            addr.sin6_scope_id,
+                        )));
+                    }
                     _ => {}
                 },
                 _ => {}
@@ -159,3 +200,120 @@ impl RecvAncillaryBuffer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod tests {
+    use super::*;
+    use crate::udp::RecvMsgHdr;
+    use std::io::IoSliceMut;
+    use std::str::FromStr;
+
+    struct ControlBuf {
+        // keep the cmsg headers aligned
+        buf: Vec<u64>,
+        len: usize,
+    }
+
+    impl ControlBuf {
+        fn new() -> Self {
+            ControlBuf {
+                buf: vec![0u64; 64],
+                len: 0,
+            }
+        }
+
+        fn push<T>(&mut self, level: libc::c_int, ty: libc::c_int, value: &T) {
+            let mut hdr: libc::cmsghdr = unsafe { std::mem::zeroed() };
+            hdr.cmsg_len = cmsg_len(size_of::<T>()) as _;
+            hdr.cmsg_level = level;
+            hdr.cmsg_type = ty;
+
+            let dst = unsafe {
+                std::slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut u8, self.buf.len() * 8)
+            };
+            let hdr_bytes = unsafe {
+                std::slice::from_raw_parts(
+                    &hdr as *const libc::cmsghdr as *const u8,
+                    size_of::<libc::cmsghdr>(),
+                )
+            };
+            let value_bytes = unsafe {
+                std::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>())
+            };
+            dst[self.len..self.len + hdr_bytes.len()].copy_from_slice(hdr_bytes);
+            let data_offset = self.len + CMSG_HDR_SIZE;
+            dst[data_offset..data_offset + value_bytes.len()].copy_from_slice(value_bytes);
+            self.len += cmsg_space(size_of::<T>());
+        }
+
+        fn as_bytes(&self) -> &[u8] {
+            unsafe { std::slice::from_raw_parts(self.buf.as_ptr() as *const u8, self.len) }
+        }
+    }
+
+    #[test]
+    fn orig_dst_v4() {
+        let mut pktinfo: libc::in_pktinfo = unsafe { std::mem::zeroed() };
+        pktinfo.ipi_ifindex = 2;
+        pktinfo.ipi_addr.s_addr = u32::from(Ipv4Addr::new(192, 168, 1, 1)).to_be();
+
+        let mut orig: libc::sockaddr_in = unsafe { std::mem::zeroed() };
+        orig.sin_family = libc::AF_INET as _;
+        orig.sin_port = 53u16.to_be();
+        orig.sin_addr.s_addr = u32::from(Ipv4Addr::new(10, 0, 0, 1)).to_be();
+
+        let mut control = ControlBuf::new();
+        control.push(libc::IPPROTO_IP, libc::IP_PKTINFO, &pktinfo);
+        control.push(libc::IPPROTO_IP, libc::IP_ORIGDSTADDR, &orig);
+
+        let mut buf = [0u8; 16];
+        let mut hdr = RecvMsgHdr::new([IoSliceMut::new(&mut buf)]);
+        RecvAncillaryBuffer::parse_buf(control.as_bytes(), &mut hdr).unwrap();
+
+        let listen_addr = SocketAddr::from_str("0.0.0.0:8053").unwrap();
+        assert_eq!(hdr.interface_id(), Some(2));
+        assert_eq!(
+            hdr.dst_addr(listen_addr),
+            SocketAddr::from_str("192.168.1.1:8053").unwrap()
+        );
+        assert_eq!(
+            hdr.orig_dst_addr(),
+            Some(SocketAddr::from_str("10.0.0.1:53").unwrap())
+        );
+    }
+
+    #[test]
+    fn orig_dst_v6() {
+        let mut orig: libc::sockaddr_in6 = unsafe { std::mem::zeroed() };
+        orig.sin6_family = libc::AF_INET6 as _;
+        orig.sin6_port = 443u16.to_be();
+        orig.sin6_addr.s6_addr = Ipv6Addr::from_str("2001:db8::1").unwrap().octets();
+
+        let mut control = ControlBuf::new();
+        control.push(libc::IPPROTO_IPV6, libc::IPV6_ORIGDSTADDR, &orig);
+
+        let mut buf = [0u8; 16];
+        let mut hdr = RecvMsgHdr::new([IoSliceMut::new(&mut buf)]);
+        RecvAncillaryBuffer::parse_buf(control.as_bytes(), &mut hdr).unwrap();
+
+        let listen_addr = SocketAddr::from_str("[::]:8443").unwrap();
+        assert_eq!(hdr.dst_addr(listen_addr), listen_addr);
+        assert_eq!(
+            hdr.orig_dst_addr(),
+            Some(SocketAddr::from_str("[2001:db8::1]:443").unwrap())
+        );
+    }
+
+    #[test]
+    fn orig_dst_truncated() {
+        let port = 53u16;
+        let mut control = ControlBuf::new();
+        control.push(libc::IPPROTO_IP, libc::IP_ORIGDSTADDR, &port);
+
+        let mut buf = [0u8; 16];
+        let mut hdr = RecvMsgHdr::new([IoSliceMut::new(&mut buf)]);
+        assert!(RecvAncillaryBuffer::parse_buf(control.as_bytes(), &mut hdr).is_err());
+        assert!(hdr.orig_dst_addr().is_none());
+    }
+}
@@ -47,6 +47,7 @@ pub struct RecvMsgHdr<'a, const C: usize> {
     pub n_recv: usize,
     c_addr: UnsafeCell<RawSocketAddr>,
     dst_ip: Option<IpAddr>,
+    orig_dst_addr: Option<SocketAddr>,
     interface_id: Option<u32>,
 }
 
@@ -59,6 +60,10 @@ impl<const C: usize> RecvAncillaryData for RecvMsgHdr<'_, C> {
         self.dst_ip = Some(addr);
     }
 
+    fn set_orig_dst_addr(&mut self, addr: SocketAddr) {
+        self.orig_dst_addr = Some(addr);
+    }
+
     fn set_timestamp(&mut self, _ts: Duration) {}
 }
 
@@ -69,6 +74,7 @@ impl<'a, const C: usize> RecvMsgHdr<'a, C> {
             n_recv: 0,
             c_addr: UnsafeCell::new(RawSocketAddr::default()),
             dst_ip: None,
+            orig_dst_addr: None,
             interface_id: None,
         }
     }
@@ -83,6 +89,13 @@ impl<'a, const C: usize> RecvMsgHdr<'a, C> {
         self.dst_ip
     }
 
+    /// the original destination address of transparent proxy sockets, only available on Linux
+    #[inline]
+    pub fn orig_dst_addr(&self) -> Option<SocketAddr> {
+        self.orig_dst_addr
+    }
+
+    /// the local address that received the packet
     pub fn dst_addr(&self, local_addr: SocketAddr) -> SocketAddr {
         self.dst_ip
             .map(|ip| SocketAddr::new(ip, local_addr.port()))
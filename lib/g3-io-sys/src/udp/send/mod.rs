@@ -5,12 +5,16 @@
 
 use std::cell::UnsafeCell;
 use std::io::IoSlice;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::net::IpAddr;
 use std::net::SocketAddr;
 
 use crate::RawSocketAddr;
 
 #[cfg(unix)]
 mod unix;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use unix::SendSrcIpControl;
 #[cfg(unix)]
 pub use unix::*;
 #[cfg(windows)]
@@ -43,6 +47,8 @@ pub use buf::with_sendmsg_x_buf;
 pub struct SendMsgHdr<'a, const C: usize> {
     pub iov: [IoSlice<'a>; C],
     c_addr: Option<UnsafeCell<RawSocketAddr>>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    src_ip: Option<SendSrcIpControl>,
     pub n_send: usize,
 }
 
@@ -52,9 +58,21 @@ impl<'a, const C: usize> SendMsgHdr<'a, C> {
         SendMsgHdr {
             iov,
             c_addr,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            src_ip: None,
             n_send: 0,
         }
     }
+
+    /// Set the source ip address of the packet, which is useful for sockets bound to wildcard
+    /// addresses.
+    ///
+    /// The ip should be of the same type as the one received on the socket, i.e. ipv4-mapped
+    /// ipv6 address for dual stack sockets.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn set_src_ip(&mut self, ip: IpAddr) {
+        self.src_ip = Some(SendSrcIpControl::new(ip));
+    }
 }
 
 impl<'a, const C: usize> AsRef<[IoSlice<'a>]> for SendMsgHdr<'a, C> {
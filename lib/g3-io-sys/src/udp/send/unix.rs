@@ -3,11 +3,58 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use std::net::IpAddr;
 use std::os::fd::AsRawFd;
 use std::{io, mem, ptr};
 
 use super::SendMsgHdr;
 
+/// The control message to set the source address of the packet
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(super) struct SendSrcIpControl {
+    // keep the cmsg header aligned
+    buf: [u64; 8],
+    len: usize,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl SendSrcIpControl {
+    pub(super) fn new(ip: IpAddr) -> Self {
+        let mut control = SendSrcIpControl {
+            buf: [0u64; 8],
+            len: 0,
+        };
+        unsafe {
+            let mut h = mem::zeroed::<libc::msghdr>();
+            h.msg_control = control.buf.as_mut_ptr() as _;
+            h.msg_controllen = mem::size_of_val(&control.buf) as _;
+            let cmsg = libc::CMSG_FIRSTHDR(&h);
+            match ip {
+                IpAddr::V4(ip4) => {
+                    let mut pktinfo = mem::zeroed::<libc::in_pktinfo>();
+                    pktinfo.ipi_spec_dst.s_addr = u32::from(ip4).to_be();
+                    (*cmsg).cmsg_level = libc::IPPROTO_IP;
+                    (*cmsg).cmsg_type = libc::IP_PKTINFO;
+                    (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<libc::in_pktinfo>() as _) as _;
+                    ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut libc::in_pktinfo, pktinfo);
+                    control.len = libc::CMSG_SPACE(size_of::<libc::in_pktinfo>() as _) as _;
+                }
+                IpAddr::V6(ip6) => {
+                    let mut pktinfo = mem::zeroed::<libc::in6_pktinfo>();
+                    pktinfo.ipi6_addr.s6_addr = ip6.octets();
+                    (*cmsg).cmsg_level = libc::IPPROTO_IPV6;
+                    (*cmsg).cmsg_type = libc::IPV6_PKTINFO;
+                    (*cmsg).cmsg_len = libc::CMSG_LEN(size_of::<libc::in6_pktinfo>() as _) as _;
+                    ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut libc::in6_pktinfo, pktinfo);
+                    control.len = libc::CMSG_SPACE(size_of::<libc::in6_pktinfo>() as _) as _;
+                }
+            }
+        }
+        control
+    }
+}
+
 impl<'a, const C: usize> SendMsgHdr<'a, C> {
     /// # Safety
     ///
@@ -27,6 +74,12 @@ impl<'a, const C: usize> SendMsgHdr<'a, C> {
             h.msg_namelen = c_addr_len as _;
             h.msg_iov = self.iov.as_ptr() as _;
             h.msg_iovlen = C as _;
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            if let Some(control) = &self.src_ip {
+                // the control buffer is read only for sendmsg
+                h.msg_control = control.buf.as_ptr() as *mut _;
+                h.msg_controllen = control.len as _;
+            }
             h
         }
     }
@@ -6,6 +6,8 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use crate::IpLocateServiceConfig;
 
 impl IpLocateServiceConfig {
@@ -78,3 +80,17 @@ impl IpLocateServiceConfig {
         }
     }
 }
+
+impl YamlDump for IpLocateServiceConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("cache_request_batch_count", &self.cache_request_batch_count)
+            .set("cache_request_timeout", &self.cache_request_timeout)
+            .set("query_peer_addr", &self.query_peer_addr)
+            .set("query_socket_buffer", &self.query_socket_buffer)
+            .set("query_wait_timeout", &self.query_wait_timeout)
+            .set("default_expire_ttl", &self.default_expire_ttl)
+            .set("maximum_expire_ttl", &self.maximum_expire_ttl);
+        map.build()
+    }
+}
@@ -13,4 +13,6 @@ mod ssl;
 pub use ssl::SslAsyncModeExt;
 #[cfg(not(libressl))]
 pub use ssl::SslLazyAcceptor;
-pub use ssl::{SslAcceptor, SslConnector, SslError, SslInfoCallbackWhere, SslStream};
+pub use ssl::{
+    SslAcceptor, SslConnector, SslError, SslInfoCallbackWhere, SslPostHandshakeExt, SslStream,
+};
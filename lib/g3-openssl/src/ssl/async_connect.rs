@@ -11,7 +11,10 @@ use openssl::error::ErrorStack;
 use openssl::ssl::{self, ErrorCode, Ssl};
 use tokio::io::{AsyncRead, AsyncWrite};
 
-use super::{AsyncEnginePoller, ConvertSslError, SslErrorAction, SslIoWrapper, SslStream};
+use super::{
+    AsyncEnginePoller, ConvertSslError, SslErrorAction, SslIoWrapper, SslStream,
+    take_post_handshake_check,
+};
 
 pub struct SslConnector<S> {
     inner: ssl::SslStream<SslIoWrapper<S>>,
@@ -68,6 +71,9 @@ impl<S: AsyncRead + AsyncWrite + Unpin> SslConnector<S> {
 
     pub async fn connect(mut self) -> io::Result<SslStream<S>> {
         future::poll_fn(|cx| self.poll_connect(cx)).await?;
+        if let Some(check) = take_post_handshake_check(self.inner.ssl()) {
+            check.await?;
+        }
         Ok(SslStream::new(self.inner, None))
     }
 }
@@ -23,6 +23,10 @@ pub use async_mode::SslAsyncModeExt;
 mod stream;
 pub use stream::SslStream;
 
+mod post_handshake;
+pub use post_handshake::SslPostHandshakeExt;
+use post_handshake::take_post_handshake_check;
+
 #[cfg_attr(not(feature = "async-job"), path = "accept.rs")]
 #[cfg_attr(feature = "async-job", path = "async_accept.rs")]
 mod accept;
@@ -0,0 +1,55 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::{Mutex, OnceLock};
+
+use openssl::error::ErrorStack;
+use openssl::ex_data::Index;
+use openssl::ssl::{Ssl, SslRef};
+
+type BoxedCheck = Pin<Box<dyn Future<Output = io::Result<()>> + Send>>;
+
+struct PostHandshakeCheck(Mutex<Option<BoxedCheck>>);
+
+static CHECK_INDEX: OnceLock<Index<Ssl, PostHandshakeCheck>> = OnceLock::new();
+
+fn check_index() -> Result<Index<Ssl, PostHandshakeCheck>, ErrorStack> {
+    if let Some(index) = CHECK_INDEX.get() {
+        return Ok(*index);
+    }
+    let index = Ssl::new_ex_index()?;
+    Ok(*CHECK_INDEX.get_or_init(|| index))
+}
+
+pub trait SslPostHandshakeExt {
+    /// Set an async check which will be run by the connector after the handshake finished.
+    /// The connection will be dropped if the check returns error.
+    ///
+    /// This can be used in the synchronous callbacks to wait for things that can't be done
+    /// in place, like fetching certificate status from the network.
+    fn set_post_handshake_check<F>(&mut self, check: F) -> Result<(), ErrorStack>
+    where
+        F: Future<Output = io::Result<()>> + Send + 'static;
+}
+
+impl SslPostHandshakeExt for SslRef {
+    fn set_post_handshake_check<F>(&mut self, check: F) -> Result<(), ErrorStack>
+    where
+        F: Future<Output = io::Result<()>> + Send + 'static,
+    {
+        let index = check_index()?;
+        self.set_ex_data(index, PostHandshakeCheck(Mutex::new(Some(Box::pin(check)))));
+        Ok(())
+    }
+}
+
+pub(super) fn take_post_handshake_check(ssl: &SslRef) -> Option<BoxedCheck> {
+    let index = CHECK_INDEX.get()?;
+    let check = ssl.ex_data(*index)?;
+    check.0.lock().unwrap().take()
+}
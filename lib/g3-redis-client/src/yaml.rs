@@ -8,6 +8,8 @@ use std::path::Path;
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::YamlMapDumper;
+
 use super::RedisClientConfigBuilder;
 
 impl RedisClientConfigBuilder {
@@ -68,4 +70,15 @@ impl RedisClientConfigBuilder {
             _ => Err(anyhow!("invalid key {}", k)),
         }
     }
+
+    pub fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("addr", &self.addr)
+            .set("tls_client", &self.tls_client)
+            .set("tls_name", &self.tls_name)
+            .set("db", &self.db)
+            .set("username", &self.username)
+            .set_secret("password", &self.password)
+            .set("connect_timeout", &self.connect_timeout)
+            .set("response_timeout", &self.response_timeout);
+    }
 }
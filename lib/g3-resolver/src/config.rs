@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use super::AnyResolveDriverConfig;
@@ -15,6 +16,8 @@ const RESOLVER_CACHE_INITIAL_CAPACITY: usize = 10;
 const RESOLVER_BATCH_REQUEST_COUNT: usize = 10;
 const RESOLVER_PROTECTIVE_QUERY_TIMEOUT: Duration = Duration::from_secs(60);
 const RESOLVER_GRACEFUL_STOP_WAIT: Duration = Duration::from_secs(30);
+const RESOLVER_PREFETCH_MIN_HITS: usize = 3;
+const RESOLVER_SERVE_STALE_CLIENT_TIMEOUT: Duration = Duration::from_millis(1800);
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ResolverRuntimeConfig {
@@ -22,6 +25,18 @@ pub struct ResolverRuntimeConfig {
     pub batch_request_count: usize,
     pub protective_query_timeout: Duration,
     pub graceful_stop_wait: Duration,
+    /// max time that an expired record can still be served after its original ttl, 0 to disable
+    pub serve_stale_max_age: Duration,
+    /// time to wait for the refresh of an expired record before serving it as stale, 0 to wait until done
+    pub serve_stale_client_timeout: Duration,
+    /// refresh cached records in the background within this time before they expire, 0 to disable
+    pub prefetch_window: Duration,
+    /// min hits of a cached record before it can be prefetched
+    pub prefetch_min_hits: usize,
+    /// file to save the cache snapshot, which will be loaded at startup
+    pub cache_snapshot_path: Option<PathBuf>,
+    /// interval to save the cache snapshot periodically, 0 to save it only on shutdown
+    pub cache_snapshot_interval: Duration,
 }
 
 impl Default for ResolverRuntimeConfig {
@@ -31,6 +46,12 @@ impl Default for ResolverRuntimeConfig {
             batch_request_count: RESOLVER_BATCH_REQUEST_COUNT,
             protective_query_timeout: RESOLVER_PROTECTIVE_QUERY_TIMEOUT,
             graceful_stop_wait: RESOLVER_GRACEFUL_STOP_WAIT,
+            serve_stale_max_age: Duration::ZERO,
+            serve_stale_client_timeout: RESOLVER_SERVE_STALE_CLIENT_TIMEOUT,
+            prefetch_window: Duration::ZERO,
+            prefetch_min_hits: RESOLVER_PREFETCH_MIN_HITS,
+            cache_snapshot_path: None,
+            cache_snapshot_interval: Duration::ZERO,
         }
     }
 }
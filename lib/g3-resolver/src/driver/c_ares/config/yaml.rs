@@ -6,6 +6,8 @@
 use anyhow::anyhow;
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::YamlMapDumper;
+
 use super::CAresDriverConfig;
 
 impl CAresDriverConfig {
@@ -71,4 +73,22 @@ impl CAresDriverConfig {
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
+
+    pub fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("server", &self.get_servers())
+            .set("each_timeout", &self.each_timeout)
+            .set("each_tries", &self.each_tries);
+        #[cfg(cares1_22)]
+        map.set("max_timeout", &self.max_timeout);
+        #[cfg(cares1_20)]
+        map.set("udp_max_quires", &self.udp_max_queries);
+        map.set("round_robin", &self.round_robin)
+            .set("socket_send_buffer_size", &self.so_send_buf_size)
+            .set("socket_recv_buffer_size", &self.so_recv_buf_size)
+            .set("bind_ipv4", &self.bind_v4)
+            .set("bind_ipv6", &self.bind_v6)
+            .set("negative_ttl", &self.negative_ttl)
+            .set("positive_min_ttl", &self.positive_min_ttl)
+            .set("positive_max_ttl", &self.positive_max_ttl);
+    }
 }
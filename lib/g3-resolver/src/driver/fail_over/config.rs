@@ -38,6 +38,21 @@ impl FailOverDriverStaticConfig {
     pub fn set_retry_empty_record(&mut self, retry: bool) {
         self.retry_empty_record = retry;
     }
+
+    #[inline]
+    pub fn get_fallback_delay(&self) -> Duration {
+        self.fallback_delay
+    }
+
+    #[inline]
+    pub fn get_negative_ttl(&self) -> u32 {
+        self.negative_ttl
+    }
+
+    #[inline]
+    pub fn get_retry_empty_record(&self) -> bool {
+        self.retry_empty_record
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -13,7 +13,7 @@ use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
 use crate::{
     ResolveDriver, ResolveJob, ResolveJobRecvResult, ResolveLocalError, ResolvedRecord,
-    ResolvedRecordSource, ResolverHandle,
+    ResolvedRecordSource, ResolvedServiceRecord, ResolverHandle,
 };
 
 pub(super) struct FailOverResolver {
@@ -196,4 +196,52 @@ impl ResolveDriver for FailOverResolver {
             let _ = sender.send(ResolveDriverResponse::V6(record)); // TODO log error
         });
     }
+
+    fn query_https(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let job_primary = self
+            .primary
+            .as_ref()
+            .and_then(|handle| handle.get_https(domain.clone()).ok());
+        let job_standby = self
+            .standby
+            .as_ref()
+            .and_then(|handle| handle.get_https(domain.clone()).ok());
+        let fallback_delay = self.conf.fallback_delay;
+        let negative_ttl = self.conf.negative_ttl;
+        let job_timeout = config.protective_query_timeout;
+        tokio::spawn(async move {
+            let resolve = async {
+                if let Some(mut job) = job_primary {
+                    // only use the standby one if the primary one failed
+                    if let Ok(Ok((r, _))) = tokio::time::timeout(fallback_delay, job.recv()).await {
+                        if r.is_ok() || job_standby.is_none() {
+                            return r.as_ref().clone();
+                        }
+                    }
+                }
+                match job_standby {
+                    Some(mut job) => match job.recv().await {
+                        Ok((r, _)) => r.as_ref().clone(),
+                        Err(e) => {
+                            ResolvedServiceRecord::failed(domain.clone(), negative_ttl, e.into())
+                        }
+                    },
+                    None => ResolvedServiceRecord::failed(
+                        domain.clone(),
+                        negative_ttl,
+                        ResolveLocalError::NoResolverRunning.into(),
+                    ),
+                }
+            };
+            let record = tokio::time::timeout(job_timeout, resolve)
+                .await
+                .unwrap_or_else(|_| ResolvedServiceRecord::timed_out(domain, negative_ttl));
+            let _ = sender.send(ResolveDriverResponse::Https(record));
+        });
+    }
 }
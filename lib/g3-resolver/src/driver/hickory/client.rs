@@ -12,6 +12,7 @@ use anyhow::anyhow;
 use async_recursion::async_recursion;
 use hickory_client::client::{Client, ClientHandle};
 use hickory_proto::BufDnsStreamHandle;
+use hickory_proto::rr::rdata::svcb::{SVCB, SvcParamValue};
 use hickory_proto::rr::{DNSClass, Name, RData, RecordType};
 use rustls::ClientConfig;
 use rustls_pki_types::ServerName;
@@ -20,7 +21,9 @@ use tokio::sync::mpsc;
 use g3_socket::{BindAddr, TcpConnectInfo, UdpConnectInfo};
 use g3_types::net::{DnsEncryptionConfig, DnsEncryptionProtocol, TcpMiscSockOpts, UdpMiscSockOpts};
 
-use crate::{ResolveDriverError, ResolveError, ResolvedRecord};
+use crate::{
+    ResolveDriverError, ResolveError, ResolvedRecord, ResolvedServiceRecord, ServiceBinding,
+};
 
 #[derive(Clone)]
 pub(super) struct DnsRequest {
@@ -42,6 +45,91 @@ impl DnsRequest {
             rtype: RecordType::A,
         }
     }
+
+    pub(super) fn query_https(domain: Arc<str>) -> Self {
+        DnsRequest {
+            domain,
+            rtype: RecordType::HTTPS,
+        }
+    }
+}
+
+pub(super) enum DnsResponder {
+    Address(mpsc::Sender<ResolvedRecord>),
+    Service(mpsc::Sender<ResolvedServiceRecord>),
+}
+
+pub(super) trait DnsResult: Send + Sized + 'static {
+    fn responder(sender: mpsc::Sender<Self>) -> DnsResponder;
+    fn failed(domain: Arc<str>, ttl: u32, e: ResolveError) -> Self;
+    fn timed_out(domain: Arc<str>, ttl: u32) -> Self;
+    fn is_ok(&self) -> bool;
+}
+
+impl DnsResult for ResolvedRecord {
+    fn responder(sender: mpsc::Sender<Self>) -> DnsResponder {
+        DnsResponder::Address(sender)
+    }
+
+    fn failed(domain: Arc<str>, ttl: u32, e: ResolveError) -> Self {
+        ResolvedRecord::failed(domain, ttl, e)
+    }
+
+    fn timed_out(domain: Arc<str>, ttl: u32) -> Self {
+        ResolvedRecord::timed_out(domain, ttl)
+    }
+
+    fn is_ok(&self) -> bool {
+        self.is_ok()
+    }
+}
+
+impl DnsResult for ResolvedServiceRecord {
+    fn responder(sender: mpsc::Sender<Self>) -> DnsResponder {
+        DnsResponder::Service(sender)
+    }
+
+    fn failed(domain: Arc<str>, ttl: u32, e: ResolveError) -> Self {
+        ResolvedServiceRecord::failed(domain, ttl, e)
+    }
+
+    fn timed_out(domain: Arc<str>, ttl: u32) -> Self {
+        ResolvedServiceRecord::timed_out(domain, ttl)
+    }
+
+    fn is_ok(&self) -> bool {
+        self.is_ok()
+    }
+}
+
+fn service_binding(svcb: &SVCB) -> ServiceBinding {
+    let target = svcb.target_name();
+    let target = if target.is_root() {
+        None
+    } else {
+        let mut name = target.to_ascii();
+        if name.ends_with('.') {
+            name.pop();
+        }
+        Some(Arc::from(name))
+    };
+    let mut binding = ServiceBinding {
+        priority: svcb.svc_priority(),
+        target,
+        ..Default::default()
+    };
+    for (_key, value) in svcb.svc_params() {
+        match value {
+            SvcParamValue::Alpn(v) => binding.alpn.clone_from(&v.0),
+            SvcParamValue::NoDefaultAlpn => binding.no_default_alpn = true,
+            SvcParamValue::Port(v) => binding.port = Some(*v),
+            SvcParamValue::Ipv4Hint(v) => binding.ipv4_hint = v.0.iter().map(|a| a.0).collect(),
+            SvcParamValue::Ipv6Hint(v) => binding.ipv6_hint = v.0.iter().map(|a| a.0).collect(),
+            SvcParamValue::EchConfigList(v) => binding.ech_config = Some(Arc::from(v.0.as_slice())),
+            _ => {}
+        }
+    }
+    binding
 }
 
 #[derive(Default)]
@@ -76,10 +164,7 @@ impl HickoryClient {
         })
     }
 
-    pub(super) async fn run(
-        mut self,
-        req_receiver: flume::Receiver<(DnsRequest, mpsc::Sender<ResolvedRecord>)>,
-    ) {
+    pub(super) async fn run(mut self, req_receiver: flume::Receiver<(DnsRequest, DnsResponder)>) {
         let (client_sender, mut client_receiver) = mpsc::channel(1);
         let mut check_interval = tokio::time::interval(Duration::from_secs(60));
         loop {
@@ -87,7 +172,7 @@ impl HickoryClient {
                 biased;
 
                 r = req_receiver.recv_async() => {
-                    let Ok((req, rsp_sender)) = r else {
+                    let Ok((req, responder)) = r else {
                         break;
                     };
                     let client_job = HickoryClientJob {
@@ -97,10 +182,20 @@ impl HickoryClient {
                         try_truncated: self.config.retry_tcp(),
                     };
                     let async_client = self.client.clone();
-                    tokio::spawn(async move {
-                        let r = client_job.run(async_client, req).await;
-                        let _ = rsp_sender.send(r).await;
-                    });
+                    match responder {
+                        DnsResponder::Address(rsp_sender) => {
+                            tokio::spawn(async move {
+                                let r = client_job.run(async_client, req).await;
+                                let _ = rsp_sender.send(r).await;
+                            });
+                        }
+                        DnsResponder::Service(rsp_sender) => {
+                            tokio::spawn(async move {
+                                let r = client_job.run_https(async_client, req).await;
+                                let _ = rsp_sender.send(r).await;
+                            });
+                        }
+                    }
                 }
                 _ = check_interval.tick() => {
                     if self.state.clear_failed() > 0 {
@@ -218,6 +313,97 @@ impl HickoryClientJob {
             }
         }
     }
+
+    #[async_recursion]
+    async fn run_https(
+        mut self,
+        mut async_client: Client,
+        req: DnsRequest,
+    ) -> ResolvedServiceRecord {
+        let Ok(mut name) = Name::from_ascii(&req.domain) else {
+            return ResolvedServiceRecord::failed(
+                req.domain,
+                self.config.negative_ttl,
+                ResolveDriverError::BadName.into(),
+            );
+        };
+        // always use FQDN format such like "www.example.com."
+        name.set_fqdn(true);
+
+        loop {
+            match async_client
+                .query(name.clone(), DNSClass::IN, req.rtype)
+                .await
+            {
+                Ok(rsp) => {
+                    let (mut msg, _) = rsp.into_parts();
+
+                    let response_code = msg.response_code();
+                    if let Some(e) = ResolveError::from_response_code(response_code) {
+                        return ResolvedServiceRecord::failed(
+                            req.domain,
+                            self.config.negative_ttl,
+                            e,
+                        );
+                    }
+
+                    if msg.truncated() && self.try_truncated {
+                        self.try_truncated = false;
+                        if let Ok(client) = self.config.new_dns_over_tcp_client().await {
+                            return self.run_https(client, req).await;
+                        }
+                    }
+
+                    let mut has_cname = false;
+                    let mut bindings = Vec::with_capacity(2);
+                    let mut ttl = 0;
+                    for r in msg.take_answers() {
+                        match r.data() {
+                            RData::HTTPS(v) => {
+                                ttl = r.ttl();
+                                bindings.push(service_binding(v));
+                            }
+                            RData::CNAME(v) => {
+                                if name.eq(r.name()) {
+                                    has_cname = true;
+                                    name = v.0.clone();
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    return if bindings.is_empty() {
+                        if has_cname {
+                            continue;
+                        }
+                        ResolvedServiceRecord::empty(req.domain, self.config.negative_ttl)
+                    } else {
+                        ResolvedServiceRecord::resolved(
+                            req.domain,
+                            ttl,
+                            self.config.positive_min_ttl,
+                            self.config.positive_max_ttl,
+                            bindings,
+                        )
+                    };
+                }
+                Err(e) => {
+                    self.state.add_failed();
+                    self.try_failed -= 1;
+                    if self.try_failed > 0 {
+                        if let Ok(client) = self.config.build_async_client().await {
+                            return self.run_https(client, req).await;
+                        }
+                    }
+                    return ResolvedServiceRecord::failed(
+                        req.domain,
+                        self.config.negative_ttl,
+                        e.into(),
+                    );
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
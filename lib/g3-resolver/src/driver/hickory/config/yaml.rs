@@ -9,6 +9,7 @@ use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
 use g3_socket::BindAddr;
+use g3_yaml::dump::YamlMapDumper;
 
 use super::HickoryDriverConfig;
 
@@ -99,4 +100,35 @@ impl HickoryDriverConfig {
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
+
+    pub fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        map.set("server", &self.servers)
+            .set("server_port", &self.server_port)
+            .set("encryption", &self.encryption)
+            .set("connect_timeout", &self.connect_timeout)
+            .set("request_timeout", &self.request_timeout)
+            .set("each_timeout", &self.each_timeout)
+            .set("each_tries", &self.each_tries);
+        match self.bind_addr {
+            BindAddr::None => {}
+            BindAddr::Ip(ip) => {
+                map.set("bind_ip", &ip);
+            }
+            #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "macos",
+                target_os = "illumos",
+                target_os = "solaris"
+            ))]
+            BindAddr::Interface(interface) => {
+                map.set("bind_interface", &interface);
+            }
+        }
+        map.set("tcp_misc_opts", &self.tcp_misc_opts)
+            .set("udp_misc_opts", &self.udp_misc_opts)
+            .set("positive_min_ttl", &self.positive_min_ttl)
+            .set("positive_max_ttl", &self.positive_max_ttl)
+            .set("negative_ttl", &self.negative_ttl);
+    }
 }
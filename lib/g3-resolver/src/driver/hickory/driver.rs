@@ -9,17 +9,17 @@ use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::time::Instant;
 
-use super::DnsRequest;
+use super::{DnsRequest, DnsResponder, DnsResult};
 use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
-use crate::{ResolveDriver, ResolveDriverError, ResolveLocalError, ResolvedRecord};
+use crate::{ResolveDriver, ResolveDriverError, ResolveLocalError, ResolvedServiceRecord};
 
 #[derive(Clone)]
 pub struct HickoryResolver {
     each_timeout: Duration,
     retry_interval: Duration,
     negative_min_ttl: u32,
-    clients: Vec<flume::Sender<(DnsRequest, mpsc::Sender<ResolvedRecord>)>>,
+    clients: Vec<flume::Sender<(DnsRequest, DnsResponder)>>,
 }
 
 impl ResolveDriver for HickoryResolver {
@@ -54,18 +54,34 @@ impl ResolveDriver for HickoryResolver {
             let _ = sender.send(ResolveDriverResponse::V6(r));
         });
     }
+
+    fn query_https(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let request = DnsRequest::query_https(domain.clone());
+
+        let job = self.clone();
+        let timeout = config.protective_query_timeout;
+        tokio::spawn(async move {
+            let r: ResolvedServiceRecord = run_timed(job, timeout, domain, request).await;
+            let _ = sender.send(ResolveDriverResponse::Https(r));
+        });
+    }
 }
 
-async fn run_timed(
+async fn run_timed<R: DnsResult>(
     job: HickoryResolver,
     timeout: Duration,
     domain: Arc<str>,
     request: DnsRequest,
-) -> ResolvedRecord {
+) -> R {
     let error_ttl = job.negative_min_ttl;
     match tokio::time::timeout(timeout, job.run(domain.clone(), request)).await {
         Ok(r) => r,
-        Err(_) => ResolvedRecord::timed_out(domain, error_ttl),
+        Err(_) => R::timed_out(domain, error_ttl),
     }
 }
 
@@ -83,34 +99,31 @@ impl HickoryResolver {
         }
     }
 
-    pub(super) fn push_client(
-        &mut self,
-        req_sender: flume::Sender<(DnsRequest, mpsc::Sender<ResolvedRecord>)>,
-    ) {
+    pub(super) fn push_client(&mut self, req_sender: flume::Sender<(DnsRequest, DnsResponder)>) {
         self.clients.push(req_sender);
     }
 
-    async fn run(self, domain: Arc<str>, request: DnsRequest) -> ResolvedRecord {
-        let (rsp_sender, mut rsp_receiver) = mpsc::channel::<ResolvedRecord>(1);
+    async fn run<R: DnsResult>(self, domain: Arc<str>, request: DnsRequest) -> R {
+        let (rsp_sender, mut rsp_receiver) = mpsc::channel::<R>(1);
 
         let mut wait_left = self.clients.len();
         let mut clients = self.clients.into_iter();
         let Some(client) = clients.next() else {
-            return ResolvedRecord::failed(
+            return R::failed(
                 domain,
                 self.negative_min_ttl,
                 ResolveLocalError::NoResolverRunning.into(),
             );
         };
         if client
-            .send_async((request.clone(), rsp_sender.clone()))
+            .send_async((request.clone(), R::responder(rsp_sender.clone())))
             .await
             .is_err()
         {
             wait_left -= 1;
         }
 
-        let mut last_err: Option<ResolvedRecord> = None;
+        let mut last_err: Option<R> = None;
         let mut interval =
             tokio::time::interval_at(Instant::now() + self.retry_interval, self.retry_interval);
         loop {
@@ -131,7 +144,8 @@ impl HickoryResolver {
                 }
                 _ = interval.tick() => {
                     if let Some(client) = clients.next() {
-                        if client.try_send((request.clone(), rsp_sender.clone())).is_err() {
+                        let responder = R::responder(rsp_sender.clone());
+                        if client.try_send((request.clone(), responder)).is_err() {
                             wait_left -= 1;
                         }
                     } else {
@@ -145,19 +159,19 @@ impl HickoryResolver {
         let end_err = if let Some(d) = self.each_timeout.checked_sub(self.retry_interval) {
             match tokio::time::timeout(d, rsp_receiver.recv()).await {
                 Ok(Some(v)) => return v,
-                Ok(None) => ResolvedRecord::failed(
+                Ok(None) => R::failed(
                     domain,
                     self.negative_min_ttl,
                     ResolveDriverError::Internal("no response received".to_string()).into(),
                 ),
-                Err(_) => ResolvedRecord::failed(
+                Err(_) => R::failed(
                     domain,
                     self.negative_min_ttl,
                     ResolveDriverError::Timeout.into(),
                 ),
             }
         } else {
-            ResolvedRecord::failed(
+            R::failed(
                 domain,
                 self.negative_min_ttl,
                 ResolveDriverError::Timeout.into(),
@@ -7,7 +7,7 @@ mod config;
 pub use config::HickoryDriverConfig;
 
 mod client;
-use client::{DnsRequest, HickoryClient, HickoryClientConfig};
+use client::{DnsRequest, DnsResponder, DnsResult, HickoryClient, HickoryClientConfig};
 
 mod driver;
 use driver::HickoryResolver;
@@ -0,0 +1,140 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwap;
+
+use super::{HostsResolver, HostsTable};
+use crate::{BoxResolverDriver, ResolverHandle};
+
+const DEFAULT_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+const DEFAULT_TTL: u32 = 30;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostsDriverConfig {
+    path: Option<PathBuf>,
+    records: BTreeMap<String, Vec<IpAddr>>,
+    pub(super) check_interval: Duration,
+    ttl: u32,
+    negative_ttl: u32,
+    fallback_handle: Option<ResolverHandle>,
+}
+
+impl Default for HostsDriverConfig {
+    fn default() -> Self {
+        HostsDriverConfig {
+            path: None,
+            records: BTreeMap::new(),
+            check_interval: DEFAULT_CHECK_INTERVAL,
+            ttl: DEFAULT_TTL,
+            negative_ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+            fallback_handle: None,
+        }
+    }
+}
+
+impl HostsDriverConfig {
+    /// Set the /etc/hosts style file, which will be reloaded if changed
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = Some(path);
+    }
+
+    #[inline]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Add static records, the name can be a wildcard one like `*.example.net`
+    pub fn add_record(&mut self, name: String, ips: Vec<IpAddr>) {
+        self.records.entry(name).or_default().extend(ips);
+    }
+
+    #[inline]
+    pub fn records(&self) -> &BTreeMap<String, Vec<IpAddr>> {
+        &self.records
+    }
+
+    pub fn set_check_interval(&mut self, interval: Duration) {
+        self.check_interval = interval;
+    }
+
+    #[inline]
+    pub fn check_interval(&self) -> Duration {
+        self.check_interval
+    }
+
+    pub fn set_ttl(&mut self, ttl: u32) {
+        self.ttl = ttl;
+    }
+
+    #[inline]
+    pub fn ttl(&self) -> u32 {
+        self.ttl
+    }
+
+    pub fn set_negative_ttl(&mut self, ttl: u32) {
+        self.negative_ttl = ttl;
+    }
+
+    #[inline]
+    pub fn negative_ttl(&self) -> u32 {
+        self.negative_ttl
+    }
+
+    pub fn set_fallback_handle(&mut self, handle: Option<ResolverHandle>) {
+        self.fallback_handle = handle;
+    }
+
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.path.is_none() && self.records.is_empty() {
+            return Err(anyhow!("neither hosts file path nor static records is set"));
+        }
+        if self.check_interval.is_zero() {
+            return Err(anyhow!("check interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    pub(super) fn load_table(&self) -> anyhow::Result<HostsTable> {
+        let mut table = HostsTable::default();
+        if let Some(path) = &self.path {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("failed to read hosts file {}: {e}", path.display()))?;
+            table
+                .add_hosts_content(&content)
+                .context(format!("invalid hosts file {}", path.display()))?;
+        }
+        table.add_records(&self.records);
+        Ok(table)
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<BoxResolverDriver> {
+        let table = self.load_table()?;
+        let table = Arc::new(ArcSwap::from_pointee(table));
+
+        let watcher = self.path.as_ref().map(|path| {
+            let table = table.clone();
+            let config = Arc::new(self.clone());
+            let path = path.clone();
+            tokio::spawn(async move {
+                super::driver::watch_file(config, path, table).await;
+            })
+        });
+
+        Ok(Box::new(HostsResolver {
+            table,
+            watcher,
+            ttl: self.ttl,
+            negative_ttl: self.negative_ttl,
+            fallback: self.fallback_handle.clone(),
+        }))
+    }
+}
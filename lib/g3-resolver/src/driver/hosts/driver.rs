@@ -0,0 +1,180 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use arc_swap::ArcSwap;
+use log::{info, warn};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::{HostsDriverConfig, HostsTable};
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{
+    ResolveDriver, ResolveError, ResolveQueryType, ResolveServerError, ResolvedRecord,
+    ResolvedServiceRecord, ResolverHandle,
+};
+
+pub(super) struct HostsResolver {
+    pub(super) table: Arc<ArcSwap<HostsTable>>,
+    pub(super) watcher: Option<JoinHandle<()>>,
+    pub(super) ttl: u32,
+    pub(super) negative_ttl: u32,
+    pub(super) fallback: Option<ResolverHandle>,
+}
+
+impl Drop for HostsResolver {
+    fn drop(&mut self) {
+        if let Some(handle) = self.watcher.take() {
+            handle.abort();
+        }
+    }
+}
+
+async fn get_mtime(path: &Path) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .ok()
+        .and_then(|m| m.modified().ok())
+}
+
+pub(super) async fn watch_file(
+    config: Arc<HostsDriverConfig>,
+    path: PathBuf,
+    table: Arc<ArcSwap<HostsTable>>,
+) {
+    let mut last_mtime = get_mtime(&path).await;
+    let mut interval = tokio::time::interval(config.check_interval);
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+
+        let mtime = get_mtime(&path).await;
+        if mtime.is_none() || mtime == last_mtime {
+            continue;
+        }
+        last_mtime = mtime;
+
+        let load_config = config.clone();
+        match tokio::task::spawn_blocking(move || load_config.load_table()).await {
+            Ok(Ok(new_table)) => {
+                info!("hosts file {} reloaded", path.display());
+                table.store(Arc::new(new_table));
+            }
+            Ok(Err(e)) => warn!("failed to reload hosts file {}: {e:?}", path.display()),
+            Err(e) => warn!(
+                "failed to join reload task for hosts file {}: {e}",
+                path.display()
+            ),
+        }
+    }
+}
+
+fn build_response(query_type: ResolveQueryType, record: ResolvedRecord) -> ResolveDriverResponse {
+    match query_type {
+        ResolveQueryType::A => ResolveDriverResponse::V4(record),
+        ResolveQueryType::Aaaa => ResolveDriverResponse::V6(record),
+    }
+}
+
+impl HostsResolver {
+    fn query(
+        &self,
+        domain: Arc<str>,
+        query_type: ResolveQueryType,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let table = self.table.load();
+        let record = match table.get(&domain) {
+            Some(entry) => {
+                let ips = match query_type {
+                    ResolveQueryType::A => entry.v4.clone(),
+                    ResolveQueryType::Aaaa => entry.v6.clone(),
+                };
+                if ips.is_empty() {
+                    ResolvedRecord::empty(domain, self.ttl)
+                } else {
+                    ResolvedRecord::resolved(domain, self.ttl, self.ttl, self.ttl, ips)
+                }
+            }
+            None => {
+                if let Some(handle) = &self.fallback {
+                    let job = match query_type {
+                        ResolveQueryType::A => handle.get_v4(domain.clone()),
+                        ResolveQueryType::Aaaa => handle.get_v6(domain.clone()),
+                    };
+                    let timeout = config.protective_query_timeout;
+                    let negative_ttl = self.negative_ttl;
+                    tokio::spawn(async move {
+                        let r = match job {
+                            Ok(mut job) => match tokio::time::timeout(timeout, job.recv()).await {
+                                Ok(Ok((r, _))) => r.as_ref().clone(),
+                                Ok(Err(e)) => {
+                                    ResolvedRecord::failed(domain, negative_ttl, e.into())
+                                }
+                                Err(_) => ResolvedRecord::timed_out(domain, negative_ttl),
+                            },
+                            Err(e) => ResolvedRecord::failed(domain, negative_ttl, e.into()),
+                        };
+                        let _ = sender.send(build_response(query_type, r));
+                    });
+                    return;
+                }
+                ResolvedRecord::failed(
+                    domain,
+                    self.negative_ttl,
+                    ResolveError::FromServer(ResolveServerError::NotFound),
+                )
+            }
+        };
+        let _ = sender.send(build_response(query_type, record));
+    }
+}
+
+impl ResolveDriver for HostsResolver {
+    fn query_v4(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        self.query(domain, ResolveQueryType::A, config, sender);
+    }
+
+    fn query_v6(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        self.query(domain, ResolveQueryType::Aaaa, config, sender);
+    }
+
+    fn query_https(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let table = self.table.load();
+        if table.get(&domain).is_some() || self.fallback.is_none() {
+            // the address hints should not override the hosts entries
+            let record = ResolvedServiceRecord::empty(domain, self.ttl);
+            let _ = sender.send(ResolveDriverResponse::Https(record));
+            return;
+        }
+        super::super::forward_https_query(
+            self.fallback.as_ref(),
+            domain,
+            config,
+            self.negative_ttl,
+            sender,
+        );
+    }
+}
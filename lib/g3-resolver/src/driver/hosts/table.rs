@@ -0,0 +1,168 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+
+#[derive(Default)]
+pub(super) struct HostsEntry {
+    pub(super) v4: Vec<IpAddr>,
+    pub(super) v6: Vec<IpAddr>,
+}
+
+impl HostsEntry {
+    fn add_ip(&mut self, ip: IpAddr) {
+        let ip = ip.to_canonical();
+        let list = if ip.is_ipv4() {
+            &mut self.v4
+        } else {
+            &mut self.v6
+        };
+        if !list.contains(&ip) {
+            list.push(ip);
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct HostsTable {
+    exact: AHashMap<String, HostsEntry>,
+    wildcard: AHashMap<String, HostsEntry>,
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+impl HostsTable {
+    pub(super) fn add(&mut self, name: &str, ip: IpAddr) {
+        if let Some(parent) = name.strip_prefix("*.") {
+            self.wildcard
+                .entry(normalize_name(parent))
+                .or_default()
+                .add_ip(ip);
+        } else {
+            self.exact
+                .entry(normalize_name(name))
+                .or_default()
+                .add_ip(ip);
+        }
+    }
+
+    pub(super) fn add_records(&mut self, records: &BTreeMap<String, Vec<IpAddr>>) {
+        for (name, ips) in records {
+            for ip in ips {
+                self.add(name, *ip);
+            }
+        }
+    }
+
+    /// Parse /etc/hosts style content, the format for each line is:
+    ///   <ip> <name> [<name> ...] [# comment]
+    pub(super) fn add_hosts_content(&mut self, content: &str) -> anyhow::Result<()> {
+        for (i, line) in content.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((s, _)) => s,
+                None => line,
+            };
+            let mut iter = line.split_ascii_whitespace();
+            let Some(ip) = iter.next() else {
+                continue;
+            };
+            // the scope id for link local ipv6 addresses is not supported
+            let ip = IpAddr::from_str(ip)
+                .map_err(|e| anyhow!("invalid ip address {ip} at line {}: {e}", i + 1))?;
+            let mut found = false;
+            for name in iter {
+                self.add(name, ip);
+                found = true;
+            }
+            if !found {
+                return Err(anyhow!("no host names found at line {}", i + 1));
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn get(&self, domain: &str) -> Option<&HostsEntry> {
+        let domain = normalize_name(domain);
+        if let Some(entry) = self.exact.get(&domain) {
+            return Some(entry);
+        }
+        if self.wildcard.is_empty() {
+            return None;
+        }
+
+        let mut parent = domain.as_str();
+        while let Some((_, p)) = parent.split_once('.') {
+            if let Some(entry) = self.wildcard.get(p) {
+                return Some(entry);
+            }
+            parent = p;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn hosts_content() {
+        let content = r#"
+# comment line
+127.0.0.1 localhost localhost.localdomain
+::1       localhost # inline comment
+10.0.0.1  *.corp.example   Corp.Example.
+"#;
+        let mut table = HostsTable::default();
+        table.add_hosts_content(content).unwrap();
+
+        let entry = table.get("localhost").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]);
+        assert_eq!(entry.v6, vec![IpAddr::V6(Ipv6Addr::LOCALHOST)]);
+        assert!(table.get("localhost.localdomain").is_some());
+
+        let entry = table.get("corp.example").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+        let entry = table.get("a.b.corp.example.").unwrap();
+        assert_eq!(entry.v4, vec![IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1))]);
+        assert!(entry.v6.is_empty());
+        assert!(table.get("xcorp.example").is_none());
+        assert!(table.get("example").is_none());
+    }
+
+    #[test]
+    fn hosts_content_err() {
+        let mut table = HostsTable::default();
+        assert!(table.add_hosts_content("127.0.0.1\n").is_err());
+        assert!(table.add_hosts_content("localhost 127.0.0.1\n").is_err());
+    }
+
+    #[test]
+    fn records() {
+        let mut records = BTreeMap::new();
+        records.insert(
+            "*.example.net".to_string(),
+            vec![
+                IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1)),
+                IpAddr::V6(Ipv6Addr::from_str("2001:db8::1").unwrap()),
+            ],
+        );
+        let mut table = HostsTable::default();
+        table.add_records(&records);
+
+        let entry = table.get("www.example.net").unwrap();
+        assert_eq!(entry.v4.len(), 1);
+        assert_eq!(entry.v6.len(), 1);
+        assert!(table.get("example.net").is_none());
+    }
+}
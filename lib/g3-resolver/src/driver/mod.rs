@@ -7,10 +7,15 @@ use std::sync::Arc;
 
 use tokio::sync::mpsc;
 
-use crate::config::ResolverRuntimeConfig;
+use crate::config::{RESOLVER_MINIMUM_CACHE_TTL, ResolverRuntimeConfig};
 use crate::message::ResolveDriverResponse;
+use crate::{ResolveLocalError, ResolvedServiceRecord, ResolverHandle};
 
 pub mod fail_over;
+pub mod hosts;
+
+#[cfg(feature = "route-domain")]
+pub mod route_domain;
 
 #[cfg(feature = "c-ares")]
 pub mod c_ares;
@@ -21,6 +26,9 @@ pub mod hickory;
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyResolveDriverConfig {
     FailOver(fail_over::FailOverDriverConfig),
+    Hosts(hosts::HostsDriverConfig),
+    #[cfg(feature = "route-domain")]
+    RouteDomain(route_domain::RouteDomainDriverConfig),
     #[cfg(feature = "c-ares")]
     CAres(c_ares::CAresDriverConfig),
     #[cfg(feature = "hickory")]
@@ -31,6 +39,9 @@ impl AnyResolveDriverConfig {
     pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<Box<dyn ResolveDriver>> {
         match self {
             AnyResolveDriverConfig::FailOver(c) => Ok(c.spawn_resolver_driver()),
+            AnyResolveDriverConfig::Hosts(c) => c.spawn_resolver_driver(),
+            #[cfg(feature = "route-domain")]
+            AnyResolveDriverConfig::RouteDomain(c) => Ok(c.spawn_resolver_driver()),
             #[cfg(feature = "c-ares")]
             AnyResolveDriverConfig::CAres(c) => c.spawn_resolver_driver(),
             #[cfg(feature = "hickory")]
@@ -52,6 +63,42 @@ pub(crate) trait ResolveDriver {
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     );
+    fn query_https(
+        &self,
+        domain: Arc<str>,
+        _config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        // return an empty record if the driver doesn't support HTTPS record
+        let record = ResolvedServiceRecord::empty(domain, RESOLVER_MINIMUM_CACHE_TTL);
+        let _ = sender.send(ResolveDriverResponse::Https(record));
+    }
 }
 
 pub(crate) type BoxResolverDriver = Box<dyn ResolveDriver>;
+
+/// forward the HTTPS query to the next resolver
+fn forward_https_query(
+    handle: Option<&ResolverHandle>,
+    domain: Arc<str>,
+    config: &ResolverRuntimeConfig,
+    negative_ttl: u32,
+    sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+) {
+    let job = match handle {
+        Some(handle) => handle.get_https(domain.clone()),
+        None => Err(ResolveLocalError::NoResolverRunning),
+    };
+    let timeout = config.protective_query_timeout;
+    tokio::spawn(async move {
+        let r = match job {
+            Ok(mut job) => match tokio::time::timeout(timeout, job.recv()).await {
+                Ok(Ok((r, _))) => r.as_ref().clone(),
+                Ok(Err(e)) => ResolvedServiceRecord::failed(domain, negative_ttl, e.into()),
+                Err(_) => ResolvedServiceRecord::timed_out(domain, negative_ttl),
+            },
+            Err(e) => ResolvedServiceRecord::failed(domain, negative_ttl, e.into()),
+        };
+        let _ = sender.send(ResolveDriverResponse::Https(r));
+    });
+}
@@ -0,0 +1,78 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use regex::Regex;
+
+use g3_types::acl::{AclChildDomainRuleBuilder, AclExactHostRule, AclRegexSetRuleBuilder};
+
+use super::{RouteDomainNext, RouteDomainResolver};
+use crate::{BoxResolverDriver, ResolverHandle};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RouteDomainDriverConfig {
+    default_handle: Option<ResolverHandle>,
+    next_handles: Vec<Option<ResolverHandle>>,
+    exact_match: AclExactHostRule<RouteDomainNext>,
+    child_match: AclChildDomainRuleBuilder<RouteDomainNext>,
+    regex_match: AclRegexSetRuleBuilder<RouteDomainNext>,
+    negative_ttl: u32,
+}
+
+impl Default for RouteDomainDriverConfig {
+    fn default() -> Self {
+        RouteDomainDriverConfig {
+            default_handle: None,
+            next_handles: Vec::new(),
+            exact_match: AclExactHostRule::new(RouteDomainNext::Default),
+            child_match: AclChildDomainRuleBuilder::new(RouteDomainNext::Default),
+            regex_match: AclRegexSetRuleBuilder::new(RouteDomainNext::Default),
+            negative_ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+        }
+    }
+}
+
+impl RouteDomainDriverConfig {
+    pub fn set_default_handle(&mut self, handle: Option<ResolverHandle>) {
+        self.default_handle = handle;
+    }
+
+    /// Add a next resolver handle, and return the index to be used in match rules
+    pub fn add_next_handle(&mut self, handle: Option<ResolverHandle>) -> usize {
+        self.next_handles.push(handle);
+        self.next_handles.len() - 1
+    }
+
+    pub fn add_exact_match(&mut self, domain: Arc<str>, next: usize) {
+        self.exact_match
+            .add_domain(domain, RouteDomainNext::Index(next));
+    }
+
+    pub fn add_child_match(&mut self, domain: &str, next: usize) {
+        self.child_match
+            .add_node(domain, RouteDomainNext::Index(next));
+    }
+
+    pub fn add_regex_match(&mut self, regex: &Regex, next: usize) {
+        self.regex_match
+            .add_regex(regex, RouteDomainNext::Index(next));
+    }
+
+    pub fn set_negative_ttl(&mut self, ttl: u32) {
+        self.negative_ttl = ttl;
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> BoxResolverDriver {
+        Box::new(RouteDomainResolver {
+            default_handle: self.default_handle.clone(),
+            next_handles: self.next_handles.clone(),
+            exact_match: self.exact_match.clone(),
+            child_match: self.child_match.build(),
+            regex_match: self.regex_match.build(),
+            negative_ttl: self.negative_ttl,
+        })
+    }
+}
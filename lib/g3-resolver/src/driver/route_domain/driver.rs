@@ -0,0 +1,120 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use g3_types::acl::{AclChildDomainRule, AclExactHostRule, AclRegexSetRule};
+
+use super::RouteDomainNext;
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{ResolveDriver, ResolveJob, ResolveLocalError, ResolvedRecord, ResolverHandle};
+
+pub(super) struct RouteDomainResolver {
+    pub(super) default_handle: Option<ResolverHandle>,
+    pub(super) next_handles: Vec<Option<ResolverHandle>>,
+    pub(super) exact_match: AclExactHostRule<RouteDomainNext>,
+    pub(super) child_match: AclChildDomainRule<RouteDomainNext>,
+    pub(super) regex_match: AclRegexSetRule<RouteDomainNext>,
+    pub(super) negative_ttl: u32,
+}
+
+impl RouteDomainResolver {
+    fn select_next(&self, domain: &str) -> Option<&ResolverHandle> {
+        let (found, next) = self.exact_match.check_domain(domain);
+        let next = if found {
+            next
+        } else {
+            let (found, next) = self.child_match.check(domain);
+            if found {
+                next
+            } else {
+                let (_, next) = self.regex_match.check(domain);
+                next
+            }
+        };
+
+        match next {
+            RouteDomainNext::Index(i) => self.next_handles.get(i).and_then(|h| h.as_ref()),
+            RouteDomainNext::Default => self.default_handle.as_ref(),
+        }
+    }
+
+    fn new_job(&self, domain: Arc<str>, config: &ResolverRuntimeConfig) -> RouteDomainResolverJob {
+        RouteDomainResolverJob {
+            domain,
+            inner: None,
+            job_timeout: config.protective_query_timeout,
+            negative_ttl: self.negative_ttl,
+        }
+    }
+}
+
+struct RouteDomainResolverJob {
+    domain: Arc<str>,
+    inner: Option<Result<ResolveJob, ResolveLocalError>>,
+    job_timeout: Duration,
+    negative_ttl: u32,
+}
+
+impl RouteDomainResolverJob {
+    async fn resolve(self) -> ResolvedRecord {
+        let r = match self.inner {
+            Some(Ok(mut job)) => match tokio::time::timeout(self.job_timeout, job.recv()).await {
+                Ok(r) => r,
+                Err(_) => return ResolvedRecord::timed_out(self.domain, self.negative_ttl),
+            },
+            Some(Err(e)) => Err(e),
+            None => Err(ResolveLocalError::NoResolverRunning),
+        };
+        match r {
+            Ok((r, _)) => r.as_ref().clone(),
+            Err(e) => ResolvedRecord::failed(self.domain, self.negative_ttl, e.into()),
+        }
+    }
+}
+
+impl ResolveDriver for RouteDomainResolver {
+    fn query_v4(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let mut job = self.new_job(domain.clone(), config);
+        job.inner = self.select_next(&domain).map(|h| h.get_v4(domain));
+        tokio::spawn(async move {
+            let record = job.resolve().await;
+            let _ = sender.send(ResolveDriverResponse::V4(record));
+        });
+    }
+
+    fn query_v6(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let mut job = self.new_job(domain.clone(), config);
+        job.inner = self.select_next(&domain).map(|h| h.get_v6(domain));
+        tokio::spawn(async move {
+            let record = job.resolve().await;
+            let _ = sender.send(ResolveDriverResponse::V6(record));
+        });
+    }
+
+    fn query_https(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let handle = self.select_next(&domain);
+        super::super::forward_https_query(handle, domain, config, self.negative_ttl, sender);
+    }
+}
@@ -0,0 +1,24 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use g3_types::acl::{ActionContract, OrderedActionContract};
+
+mod config;
+pub use config::RouteDomainDriverConfig;
+
+mod driver;
+use driver::RouteDomainResolver;
+
+/// The next resolver selected by the match rules
+///
+/// The regex match rule will select the one with the smallest index if multiple regex matched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum RouteDomainNext {
+    Index(usize),
+    Default,
+}
+
+impl ActionContract for RouteDomainNext {}
+impl OrderedActionContract for RouteDomainNext {}
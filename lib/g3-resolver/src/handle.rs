@@ -10,7 +10,7 @@ use std::task::{Context, Poll};
 
 use tokio::sync::{mpsc, oneshot};
 
-use super::{ArcResolvedRecord, ResolveLocalError, ResolvedRecordSource};
+use super::{ArcResolvedRecord, ArcResolvedServiceRecord, ResolveLocalError, ResolvedRecordSource};
 use crate::message::ResolveDriverRequest;
 
 #[derive(Clone, Debug)]
@@ -52,16 +52,30 @@ impl ResolverHandle {
             Err(_) => Err(ResolveLocalError::NoResolverRunning),
         }
     }
+
+    /// Query the HTTPS record (RFC 9460) of the domain
+    pub fn get_https(
+        &self,
+        domain: Arc<str>,
+    ) -> Result<ResolveJob<ArcResolvedServiceRecord>, ResolveLocalError> {
+        let (sender, receiver) = oneshot::channel();
+        let req = ResolveDriverRequest::GetHttps(domain, sender);
+        let sender = self.req_sender.clone();
+        match sender.send(req) {
+            Ok(_) => Ok(ResolveJob { receiver }),
+            Err(_) => Err(ResolveLocalError::NoResolverRunning),
+        }
+    }
 }
 
-pub struct ResolveJob {
-    receiver: oneshot::Receiver<(ArcResolvedRecord, ResolvedRecordSource)>,
+pub struct ResolveJob<R = ArcResolvedRecord> {
+    receiver: oneshot::Receiver<(R, ResolvedRecordSource)>,
 }
-pub type ResolveJobRecvResult =
-    Result<(ArcResolvedRecord, ResolvedRecordSource), ResolveLocalError>;
+pub type ResolveJobRecvResult<R = ArcResolvedRecord> =
+    Result<(R, ResolvedRecordSource), ResolveLocalError>;
 
-impl ResolveJob {
-    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<ResolveJobRecvResult> {
+impl<R> ResolveJob<R> {
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<ResolveJobRecvResult<R>> {
         match Pin::new(&mut self.receiver).poll(cx) {
             Poll::Pending => Poll::Pending,
             Poll::Ready(Ok(ret)) => Poll::Ready(Ok(ret)),
@@ -69,7 +83,7 @@ impl ResolveJob {
         }
     }
 
-    pub async fn recv(&mut self) -> ResolveJobRecvResult {
+    pub async fn recv(&mut self) -> ResolveJobRecvResult<R> {
         poll_fn(|cx| self.poll_recv(cx)).await
     }
 }
@@ -16,6 +16,8 @@ mod query;
 mod record;
 mod resolver;
 mod runtime;
+mod service;
+mod snapshot;
 mod stats;
 
 pub use config::{ResolverConfig, ResolverRuntimeConfig};
@@ -24,4 +26,7 @@ pub use handle::{ResolveJob, ResolveJobRecvResult, ResolverHandle};
 pub use query::ResolveQueryType;
 pub use record::{ArcResolvedRecord, ResolvedRecord, ResolvedRecordSource};
 pub use resolver::{Resolver, ResolverBuilder};
+pub use service::{
+    ArcResolvedServiceRecord, ResolvedServiceRecord, ServiceBinding, https_query_name,
+};
 pub use stats::{ResolverMemorySnapshot, ResolverQuerySnapshot, ResolverSnapshot, ResolverStats};
@@ -7,12 +7,16 @@ use std::sync::Arc;
 
 use tokio::sync::oneshot;
 
-use super::{ArcResolvedRecord, ResolvedRecord, ResolvedRecordSource, ResolverConfig};
+use super::{
+    ArcResolvedRecord, ArcResolvedServiceRecord, ResolvedRecord, ResolvedRecordSource,
+    ResolvedServiceRecord, ResolverConfig,
+};
 
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub(crate) enum ResolverCommand {
     Quit,
     Update(Box<ResolverConfig>),
+    SaveSnapshot(oneshot::Sender<anyhow::Result<()>>),
 }
 
 pub(crate) enum ResolveDriverRequest {
@@ -24,9 +28,14 @@ pub(crate) enum ResolveDriverRequest {
         Arc<str>,
         oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>,
     ),
+    GetHttps(
+        Arc<str>,
+        oneshot::Sender<(ArcResolvedServiceRecord, ResolvedRecordSource)>,
+    ),
 }
 
 pub(crate) enum ResolveDriverResponse {
     V4(ResolvedRecord),
     V6(ResolvedRecord),
+    Https(ResolvedServiceRecord),
 }
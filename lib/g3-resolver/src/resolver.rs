@@ -7,8 +7,9 @@ use std::io;
 use std::sync::Arc;
 use std::thread::JoinHandle;
 
+use anyhow::anyhow;
 use log::warn;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
 use super::ResolverStats;
 use crate::config::ResolverConfig;
@@ -109,6 +110,23 @@ impl Resolver {
         Ok(())
     }
 
+    /// Save the cache snapshot now if the snapshot file is configured
+    pub fn save_cache_snapshot(&self) -> impl Future<Output = anyhow::Result<()>> + Send + 'static {
+        let (sender, receiver) = oneshot::channel();
+        let sent = self
+            .ctl_sender
+            .send(ResolverCommand::SaveSnapshot(sender))
+            .is_ok();
+        async move {
+            if !sent {
+                return Err(anyhow!("resolver runtime has quit"));
+            }
+            receiver
+                .await
+                .map_err(|_| anyhow!("resolver runtime has quit"))?
+        }
+    }
+
     fn stop(&self) {
         let _ = self.ctl_sender.send(ResolverCommand::Quit);
     }
@@ -7,26 +7,97 @@ use std::collections::hash_map;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use ahash::AHashMap;
-use log::{trace, warn};
+use anyhow::anyhow;
+use log::{debug, trace, warn};
 use tokio::sync::{mpsc, oneshot};
-use tokio::time::Instant;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, Interval};
 use tokio_util::time::{DelayQueue, delay_queue};
 
 use super::stats::{ResolverMemoryStats, ResolverStats};
-use super::{ArcResolvedRecord, BoxResolverDriver, ResolvedRecordSource, ResolverConfig};
+use super::{
+    ArcResolvedRecord, ArcResolvedServiceRecord, BoxResolverDriver, ResolvedRecordSource,
+    ResolverConfig, ResolverRuntimeConfig,
+};
+use crate::ResolveQueryType;
 use crate::message::{ResolveDriverRequest, ResolveDriverResponse, ResolverCommand};
+use crate::snapshot::{CacheSnapshot, CacheSnapshotRecords, CacheSnapshotSaver};
+
+/// the time to serve stale records at once after a failed refresh, see RFC 8767 section 5
+const SERVE_STALE_FAILURE_RECHECK: Duration = Duration::from_secs(30);
 
 struct CachedRecord {
     inner: ArcResolvedRecord,
     expire_at: Instant,
     expire_key: Option<delay_queue::Key>,
+    hits: usize,
+}
+
+impl CachedRecord {
+    fn need_prefetch(&self, config: &ResolverRuntimeConfig) -> bool {
+        if config.prefetch_window.is_zero()
+            || self.hits < config.prefetch_min_hits
+            || !self.inner.is_usable()
+        {
+            return false;
+        }
+        self.expire_at.saturating_duration_since(Instant::now()) <= config.prefetch_window
+    }
 }
 
 struct TrashedRecord {
     inner: ArcResolvedRecord,
+    stale_at: Instant,
     vanish_at: Instant,
+    recheck_at: Option<Instant>,
+}
+
+impl TrashedRecord {
+    fn new(r: CachedRecord, serve_stale_max_age: Duration) -> Option<Self> {
+        let serve_stale = r.inner.is_usable() && !serve_stale_max_age.is_zero();
+        let stale_at = match r.inner.vanish {
+            Some(vanish) => vanish,
+            None if serve_stale => r.expire_at,
+            None => return None,
+        };
+        let vanish_at = if serve_stale {
+            stale_at
+                .checked_add(serve_stale_max_age)
+                .unwrap_or(stale_at)
+        } else {
+            stale_at
+        };
+        Some(TrashedRecord {
+            inner: r.inner,
+            stale_at,
+            vanish_at,
+            recheck_at: None,
+        })
+    }
+
+    /// the original ttl has passed, and we are serving it as a stale record
+    fn is_stale(&self, now: Instant) -> bool {
+        now >= self.stale_at
+    }
+
+    /// the last refresh of the stale record failed, and it's too early to retry
+    fn skip_refresh(&self, now: Instant) -> bool {
+        self.recheck_at.map(|t| now < t).unwrap_or(false)
+    }
+
+    fn set_refresh_failed(&mut self, now: Instant) {
+        if self.is_stale(now) {
+            self.recheck_at = now.checked_add(SERVE_STALE_FAILURE_RECHECK);
+        }
+    }
+}
+
+struct CachedServiceRecord {
+    inner: ArcResolvedServiceRecord,
+    expire_key: delay_queue::Key,
 }
 
 pub(crate) struct ResolverRuntime {
@@ -44,7 +115,16 @@ pub(crate) struct ResolverRuntime {
     doing_v6: AHashMap<Arc<str>, Vec<oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>>>,
     trash_v4: AHashMap<Arc<str>, TrashedRecord>,
     trash_v6: AHashMap<Arc<str>, TrashedRecord>,
+    stale_timeout_v4: DelayQueue<Arc<str>>,
+    stale_timeout_v6: DelayQueue<Arc<str>>,
+    expired_https: DelayQueue<Arc<str>>,
+    cache_https: AHashMap<Arc<str>, CachedServiceRecord>,
+    doing_https:
+        AHashMap<Arc<str>, Vec<oneshot::Sender<(ArcResolvedServiceRecord, ResolvedRecordSource)>>>,
     driver: Option<BoxResolverDriver>,
+    snapshot_interval: Option<Interval>,
+    snapshot_loader: Option<JoinHandle<anyhow::Result<CacheSnapshotRecords>>>,
+    snapshot_saver: CacheSnapshotSaver,
 }
 
 impl Drop for ResolverRuntime {
@@ -63,7 +143,7 @@ impl ResolverRuntime {
     ) -> Self {
         let initial_cache_capacity = config.runtime.initial_cache_capacity;
         let (rsp_sender, rsp_receiver) = mpsc::unbounded_channel();
-        ResolverRuntime {
+        let mut runtime = ResolverRuntime {
             config,
             stats,
             req_receiver,
@@ -78,7 +158,118 @@ impl ResolverRuntime {
             doing_v6: AHashMap::with_capacity(initial_cache_capacity),
             trash_v4: AHashMap::with_capacity(initial_cache_capacity),
             trash_v6: AHashMap::with_capacity(initial_cache_capacity),
+            stale_timeout_v4: DelayQueue::new(),
+            stale_timeout_v6: DelayQueue::new(),
+            expired_https: DelayQueue::new(),
+            cache_https: AHashMap::new(),
+            doing_https: AHashMap::new(),
             driver: None,
+            snapshot_interval: None,
+            snapshot_loader: None,
+            snapshot_saver: CacheSnapshotSaver::default(),
+        };
+        runtime.load_snapshot();
+        runtime.reset_snapshot_interval();
+        runtime
+    }
+
+    fn load_snapshot(&mut self) {
+        if let Some(path) = &self.config.runtime.cache_snapshot_path {
+            self.snapshot_loader = Some(crate::snapshot::spawn_load(path.clone()));
+        }
+    }
+
+    fn poll_snapshot_loader(&mut self, cx: &mut Context<'_>) {
+        let Some(loader) = &mut self.snapshot_loader else {
+            return;
+        };
+        let Poll::Ready(r) = Pin::new(loader).poll(cx) else {
+            return;
+        };
+        self.snapshot_loader = None;
+        let records = match r {
+            Ok(Ok(records)) => records,
+            Ok(Err(e)) => {
+                warn!(
+                    "resolver {}: failed to load cache snapshot: {e:?}",
+                    self.config.name
+                );
+                return;
+            }
+            Err(e) => {
+                warn!(
+                    "resolver {}: cache snapshot load task failed: {e}",
+                    self.config.name
+                );
+                return;
+            }
+        };
+        debug!(
+            "resolver {}: loaded {} records from cache snapshot",
+            self.config.name,
+            records.len(),
+        );
+        for (query_type, record) in records {
+            let Some(expire_at) = record.expire else {
+                continue;
+            };
+            // the records queried after startup are newer
+            let (cache, expire_queue) = match query_type {
+                ResolveQueryType::A => (&mut self.cache_v4, &mut self.expired_v4),
+                ResolveQueryType::Aaaa => (&mut self.cache_v6, &mut self.expired_v6),
+            };
+            if cache.contains_key(&record.domain) {
+                continue;
+            }
+            Self::update_cache(cache, expire_queue, Arc::new(record), expire_at);
+        }
+        self.update_mem_stats();
+    }
+
+    /// Build the snapshot, the file will be written in a blocking thread
+    fn save_snapshot<F>(&mut self, on_finish: F)
+    where
+        F: FnOnce(anyhow::Result<()>) + Send + 'static,
+    {
+        let Some(path) = &self.config.runtime.cache_snapshot_path else {
+            on_finish(Ok(()));
+            return;
+        };
+        if self.snapshot_loader.is_some() {
+            // don't overwrite the snapshot file before it's loaded
+            on_finish(Err(anyhow!("the cache snapshot is still being loaded")));
+            return;
+        }
+        let now = Instant::now();
+        let mut snapshot = CacheSnapshot::new();
+        for r in self.cache_v4.values() {
+            snapshot.add_record(ResolveQueryType::A, &r.inner, r.expire_at, now);
+        }
+        for r in self.cache_v6.values() {
+            snapshot.add_record(ResolveQueryType::Aaaa, &r.inner, r.expire_at, now);
+        }
+        self.snapshot_saver
+            .spawn_save(snapshot, path.clone(), on_finish);
+    }
+
+    fn save_snapshot_and_log(&mut self) {
+        let name = self.config.name.clone();
+        self.save_snapshot(move |r| {
+            if let Err(e) = r {
+                warn!("resolver {name}: failed to save cache snapshot: {e:?}");
+            }
+        });
+    }
+
+    fn reset_snapshot_interval(&mut self) {
+        let interval = self.config.runtime.cache_snapshot_interval;
+        if self.config.runtime.cache_snapshot_path.is_none() || interval.is_zero() {
+            self.snapshot_interval = None;
+        } else {
+            self.snapshot_interval = Some(tokio::time::interval_at(
+                Instant::now() + interval,
+                interval,
+            ));
         }
     }
 
@@ -87,12 +278,24 @@ impl ResolverRuntime {
             ResolverCommand::Update(config) => match config.driver.spawn_resolver_driver() {
                 Ok(driver) => {
                     self.driver = Some(driver);
+                    let snapshot_changed = self.config.runtime.cache_snapshot_path
+                        != config.runtime.cache_snapshot_path
+                        || self.config.runtime.cache_snapshot_interval
+                            != config.runtime.cache_snapshot_interval;
                     self.config = *config;
+                    if snapshot_changed {
+                        self.reset_snapshot_interval();
+                    }
                 }
                 Err(e) => {
                     warn!("invalid resolver config {config:?} : {e}");
                 }
             },
+            ResolverCommand::SaveSnapshot(sender) => {
+                self.save_snapshot(move |r| {
+                    let _ = sender.send(r);
+                });
+            }
             ResolverCommand::Quit => {} // should be handled outside
         }
     }
@@ -116,6 +319,7 @@ impl ResolverRuntime {
                 v.inner = record;
                 v.expire_at = expire_at;
                 v.expire_key = Some(expire_key);
+                v.hits = 0;
             }
             hash_map::Entry::Vacant(v) => {
                 let expire_key = expire_queue.insert_at(record.domain.to_owned(), expire_at);
@@ -123,6 +327,26 @@ impl ResolverRuntime {
                     inner: record,
                     expire_at,
                     expire_key: Some(expire_key),
+                    hits: 0,
+                });
+            }
+        }
+    }
+
+    fn update_https_cache(&mut self, record: ArcResolvedServiceRecord, expire_at: Instant) {
+        match self.cache_https.entry(record.domain.clone()) {
+            hash_map::Entry::Occupied(mut o) => {
+                let v = o.get_mut();
+                self.expired_https.reset_at(&v.expire_key, expire_at);
+                v.inner = record;
+            }
+            hash_map::Entry::Vacant(v) => {
+                let expire_key = self
+                    .expired_https
+                    .insert_at(record.domain.clone(), expire_at);
+                v.insert(CachedServiceRecord {
+                    inner: record,
+                    expire_key,
                 });
             }
         }
@@ -130,12 +354,62 @@ impl ResolverRuntime {
 
     fn handle_rsp(&mut self, rsp: ResolveDriverResponse) {
         match rsp {
+            ResolveDriverResponse::Https(record) => {
+                self.stats.query_https.add_service_record(&record);
+                if !record.is_acceptable() {
+                    if let Some(v) = self.cache_https.get(&record.domain) {
+                        if v.inner.is_usable() {
+                            // keep the unexpired record if the new query failed
+                            if let Some(vec) = self.doing_https.remove(&record.domain) {
+                                self.stats.query_https.add_query_cached_n(vec.len());
+                                for sender in vec.into_iter() {
+                                    let _ =
+                                        sender.send((v.inner.clone(), ResolvedRecordSource::Cache));
+                                }
+                            }
+                            return;
+                        }
+                    }
+                }
+                let record = Arc::new(record);
+                if let Some(mut vec) = self.doing_https.remove(&record.domain) {
+                    if let Some(sender) = vec.pop() {
+                        let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Query));
+                        self.stats.query_https.add_query_cached_n(vec.len());
+                        for sender in vec.into_iter() {
+                            let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Cache));
+                        }
+                    }
+                }
+                if let Some(expire_at) = record.expire {
+                    self.update_https_cache(record, expire_at);
+                }
+            }
             ResolveDriverResponse::V4(record) => {
                 self.stats.query_a.add_record(&record);
                 if !record.is_acceptable() {
-                    if let Some(v) = self.trash_v4.get(&record.domain) {
+                    if let Some(v) = self.cache_v4.get(&record.domain) {
+                        if v.inner.is_usable() {
+                            // keep the unexpired record if the prefetch failed
+                            if let Some(vec) = self.doing_v4.remove(&record.domain) {
+                                self.stats.query_a.add_query_cached_n(vec.len());
+                                for sender in vec.into_iter() {
+                                    let _ =
+                                        sender.send((v.inner.clone(), ResolvedRecordSource::Cache));
+                                }
+                            }
+                            return;
+                        }
+                    }
+                    if let Some(v) = self.trash_v4.get_mut(&record.domain) {
+                        let now = Instant::now();
+                        v.set_refresh_failed(now);
                         if let Some(vec) = self.doing_v4.remove(&record.domain) {
-                            self.stats.query_a.add_query_trashed_n(vec.len());
+                            if v.is_stale(now) {
+                                self.stats.query_a.add_query_stale_n(vec.len());
+                            } else {
+                                self.stats.query_a.add_query_trashed_n(vec.len());
+                            }
                             for sender in vec.into_iter() {
                                 let _ = sender.send((v.inner.clone(), ResolvedRecordSource::Trash));
                             }
@@ -162,9 +436,28 @@ impl ResolverRuntime {
             ResolveDriverResponse::V6(record) => {
                 self.stats.query_aaaa.add_record(&record);
                 if !record.is_acceptable() {
-                    if let Some(v) = self.trash_v6.get(&record.domain) {
+                    if let Some(v) = self.cache_v6.get(&record.domain) {
+                        if v.inner.is_usable() {
+                            // keep the unexpired record if the prefetch failed
+                            if let Some(vec) = self.doing_v6.remove(&record.domain) {
+                                self.stats.query_aaaa.add_query_cached_n(vec.len());
+                                for sender in vec.into_iter() {
+                                    let _ =
+                                        sender.send((v.inner.clone(), ResolvedRecordSource::Cache));
+                                }
+                            }
+                            return;
+                        }
+                    }
+                    if let Some(v) = self.trash_v6.get_mut(&record.domain) {
+                        let now = Instant::now();
+                        v.set_refresh_failed(now);
                         if let Some(vec) = self.doing_v6.remove(&record.domain) {
-                            self.stats.query_aaaa.add_query_trashed_n(vec.len());
+                            if v.is_stale(now) {
+                                self.stats.query_aaaa.add_query_stale_n(vec.len());
+                            } else {
+                                self.stats.query_aaaa.add_query_trashed_n(vec.len());
+                            }
                             for sender in vec.into_iter() {
                                 let _ = sender.send((v.inner.clone(), ResolvedRecordSource::Trash));
                             }
@@ -194,53 +487,134 @@ impl ResolverRuntime {
     fn handle_expired_v4(&mut self, domain: &str) {
         trace!("clean expired v4 for domain {domain}");
         if let Some(r) = self.cache_v4.remove(domain) {
-            if let Some(vanish_at) = r.inner.vanish {
-                self.trash_v4.insert(
-                    r.inner.domain.clone(),
-                    TrashedRecord {
-                        inner: r.inner,
-                        vanish_at,
-                    },
-                );
+            if let Some(t) = TrashedRecord::new(r, self.config.runtime.serve_stale_max_age) {
+                self.trash_v4.insert(t.inner.domain.clone(), t);
             }
         }
     }
     fn handle_expired_v6(&mut self, domain: &str) {
         trace!("clean expired v6 for domain {domain}");
         if let Some(r) = self.cache_v6.remove(domain) {
-            if let Some(vanish_at) = r.inner.vanish {
-                self.trash_v6.insert(
-                    r.inner.domain.clone(),
-                    TrashedRecord {
-                        inner: r.inner,
-                        vanish_at,
-                    },
-                );
+            if let Some(t) = TrashedRecord::new(r, self.config.runtime.serve_stale_max_age) {
+                self.trash_v6.insert(t.inner.domain.clone(), t);
+            }
+        }
+    }
+
+    fn handle_stale_timeout_v4(&mut self, domain: &str) {
+        let Some(r) = self.trash_v4.get(domain) else {
+            return;
+        };
+        // serve the stale record to the waiting requests, and let the query continue
+        if let Some(vec) = self.doing_v4.get_mut(domain) {
+            self.stats.query_a.add_query_stale_n(vec.len());
+            for sender in vec.drain(..) {
+                let _ = sender.send((r.inner.clone(), ResolvedRecordSource::Trash));
+            }
+        }
+    }
+    fn handle_stale_timeout_v6(&mut self, domain: &str) {
+        let Some(r) = self.trash_v6.get(domain) else {
+            return;
+        };
+        // serve the stale record to the waiting requests, and let the query continue
+        if let Some(vec) = self.doing_v6.get_mut(domain) {
+            self.stats.query_aaaa.add_query_stale_n(vec.len());
+            for sender in vec.drain(..) {
+                let _ = sender.send((r.inner.clone(), ResolvedRecordSource::Trash));
             }
         }
     }
 
+    fn handle_expired_https(&mut self, domain: &str) {
+        trace!("clean expired https for domain {domain}");
+        self.cache_https.remove(domain);
+    }
+
     fn handle_req(&mut self, req: ResolveDriverRequest) {
         match req {
+            ResolveDriverRequest::GetHttps(domain, sender) => {
+                self.stats.query_https.add_query_total();
+                if let Some(r) = self.cache_https.get(&domain) {
+                    self.stats.query_https.add_query_cached();
+                    let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
+                    return;
+                }
+                match self.doing_https.entry(domain.clone()) {
+                    hash_map::Entry::Occupied(mut o) => {
+                        // there is a query already
+                        o.get_mut().push(sender);
+                    }
+                    hash_map::Entry::Vacant(v) => {
+                        v.insert(vec![sender]);
+                        if let Some(driver) = &self.driver {
+                            self.stats.query_https.add_query_driver();
+                            driver.query_https(
+                                domain,
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
+                        } else {
+                            unreachable!()
+                        }
+                    }
+                }
+            }
             ResolveDriverRequest::GetV4(domain, sender) => {
                 self.stats.query_a.add_query_total();
-                if let Some(r) = self.cache_v4.get(&domain) {
+                if let Some(r) = self.cache_v4.get_mut(&domain) {
                     self.stats.query_a.add_query_cached();
                     let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
-                    return;
-                }
-                if let Some(r) = self.trash_v4.get(&domain) {
-                    self.stats.query_a.add_query_trashed();
-                    let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
-                    self.doing_v4.entry(domain.clone()).or_insert_with(|| {
+                    r.hits += 1;
+                    if r.need_prefetch(&self.config.runtime) && !self.doing_v4.contains_key(&domain)
+                    {
                         if let Some(driver) = &self.driver {
+                            // reset the hits so we won't retry too often if the prefetch failed
+                            r.hits = 0;
+                            self.stats.query_a.add_query_prefetch();
                             self.stats.query_a.add_query_driver();
-                            driver.query_v4(domain, &self.config.runtime, self.rsp_sender.clone());
+                            driver.query_v4(
+                                domain.clone(),
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
+                            self.doing_v4.insert(domain, vec![]);
                         }
-                        vec![]
-                    });
+                    }
                     return;
                 }
+                if let Some(r) = self.trash_v4.get(&domain) {
+                    let now = Instant::now();
+                    if r.is_stale(now) {
+                        if r.skip_refresh(now) {
+                            self.stats.query_a.add_query_stale();
+                            let _ =
+                                sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
+                            return;
+                        }
+                        // refresh the stale record first, and only serve it if the refresh failed
+                        // or timed out, see RFC 8767
+                        let client_timeout = self.config.runtime.serve_stale_client_timeout;
+                        if !client_timeout.is_zero() {
+                            self.stale_timeout_v4.insert(domain.clone(), client_timeout);
+                        }
+                    } else {
+                        self.stats.query_a.add_query_trashed();
+                        let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
+                        self.doing_v4.entry(domain.clone()).or_insert_with(|| {
+                            if let Some(driver) = &self.driver {
+                                self.stats.query_a.add_query_driver();
+                                driver.query_v4(
+                                    domain,
+                                    &self.config.runtime,
+                                    self.rsp_sender.clone(),
+                                );
+                            }
+                            vec![]
+                        });
+                        return;
+                    }
+                }
                 match self.doing_v4.entry(domain.clone()) {
                     hash_map::Entry::Occupied(mut o) => {
                         // there is a query already
@@ -259,23 +633,59 @@ impl ResolverRuntime {
             }
             ResolveDriverRequest::GetV6(domain, sender) => {
                 self.stats.query_aaaa.add_query_total();
-                if let Some(r) = self.cache_v6.get(&domain) {
+                if let Some(r) = self.cache_v6.get_mut(&domain) {
                     self.stats.query_aaaa.add_query_cached();
                     let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
-                    return;
-                }
-                if let Some(r) = self.trash_v6.get(&domain) {
-                    self.stats.query_aaaa.add_query_trashed();
-                    let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
-                    self.doing_v6.entry(domain.clone()).or_insert_with(|| {
+                    r.hits += 1;
+                    if r.need_prefetch(&self.config.runtime) && !self.doing_v6.contains_key(&domain)
+                    {
                         if let Some(driver) = &self.driver {
+                            // reset the hits so we won't retry too often if the prefetch failed
+                            r.hits = 0;
+                            self.stats.query_aaaa.add_query_prefetch();
                             self.stats.query_aaaa.add_query_driver();
-                            driver.query_v6(domain, &self.config.runtime, self.rsp_sender.clone());
+                            driver.query_v6(
+                                domain.clone(),
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
+                            self.doing_v6.insert(domain, vec![]);
                         }
-                        vec![]
-                    });
+                    }
                     return;
                 }
+                if let Some(r) = self.trash_v6.get(&domain) {
+                    let now = Instant::now();
+                    if r.is_stale(now) {
+                        if r.skip_refresh(now) {
+                            self.stats.query_aaaa.add_query_stale();
+                            let _ =
+                                sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
+                            return;
+                        }
+                        // refresh the stale record first, and only serve it if the refresh failed
+                        // or timed out, see RFC 8767
+                        let client_timeout = self.config.runtime.serve_stale_client_timeout;
+                        if !client_timeout.is_zero() {
+                            self.stale_timeout_v6.insert(domain.clone(), client_timeout);
+                        }
+                    } else {
+                        self.stats.query_aaaa.add_query_trashed();
+                        let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
+                        self.doing_v6.entry(domain.clone()).or_insert_with(|| {
+                            if let Some(driver) = &self.driver {
+                                self.stats.query_aaaa.add_query_driver();
+                                driver.query_v6(
+                                    domain,
+                                    &self.config.runtime,
+                                    self.rsp_sender.clone(),
+                                );
+                            }
+                            vec![]
+                        });
+                        return;
+                    }
+                }
                 match self.doing_v6.entry(domain.clone()) {
                     hash_map::Entry::Occupied(mut o) => {
                         // there is a query already
@@ -322,6 +732,12 @@ impl ResolverRuntime {
             &self.doing_v6,
             &self.trash_v6,
         );
+
+        let stats = &self.stats.memory_https;
+        stats.set_cache_capacity(self.cache_https.capacity());
+        stats.set_cache_length(self.cache_https.len());
+        stats.set_doing_capacity(self.doing_https.capacity());
+        stats.set_doing_length(self.doing_https.len());
     }
 
     fn clean_trash(&mut self) {
@@ -344,8 +760,11 @@ impl ResolverRuntime {
                 Poll::Ready(Some(cmd)) => Some(cmd),
                 Poll::Ready(None) => break, // sender closed
             };
+            self.poll_snapshot_loader(cx);
+
             if let Some(cmd) = cmd {
                 if matches!(cmd, ResolverCommand::Quit) {
+                    self.save_snapshot_and_log();
                     break;
                 } else {
                     self.handle_cmd(cmd);
@@ -386,11 +805,41 @@ impl ResolverRuntime {
                     }
                 }
             }
+            loop {
+                match self.stale_timeout_v4.poll_expired(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => break, // all items fetched
+                    Poll::Ready(Some(t)) => self.handle_stale_timeout_v4(t.get_ref()),
+                }
+            }
+            loop {
+                match self.stale_timeout_v6.poll_expired(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => break, // all items fetched
+                    Poll::Ready(Some(t)) => self.handle_stale_timeout_v6(t.get_ref()),
+                }
+            }
+            loop {
+                match self.expired_https.poll_expired(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => break, // all items fetched
+                    Poll::Ready(Some(t)) => {
+                        update_mem_stats = true;
+                        self.handle_expired_https(t.get_ref());
+                    }
+                }
+            }
 
             if update_mem_stats {
                 self.update_mem_stats();
             }
 
+            if let Some(interval) = &mut self.snapshot_interval {
+                if interval.poll_tick(cx).is_ready() {
+                    self.save_snapshot_and_log();
+                }
+            }
+
             // handle request
             for _ in 1..self.config.runtime.batch_request_count {
                 let req = match self.req_receiver.poll_recv(cx) {
@@ -416,3 +865,248 @@ impl Future for ResolverRuntime {
         (*self).poll_loop(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::driver::{AnyResolveDriverConfig, ResolveDriver};
+    use crate::{ResolveError, ResolveLocalError, ResolvedRecord};
+
+    #[derive(Clone, Copy)]
+    enum MockAnswer {
+        Ip(IpAddr),
+        Fail,
+        Hang,
+    }
+
+    #[derive(Clone)]
+    struct MockDriver {
+        answer: Arc<Mutex<MockAnswer>>,
+        queries: Arc<AtomicUsize>,
+    }
+
+    impl MockDriver {
+        fn set_answer(&self, answer: MockAnswer) {
+            *self.answer.lock().unwrap() = answer;
+        }
+
+        fn queries(&self) -> usize {
+            self.queries.load(Ordering::Relaxed)
+        }
+
+        fn answer(&self, domain: Arc<str>) -> Option<ResolvedRecord> {
+            self.queries.fetch_add(1, Ordering::Relaxed);
+            match *self.answer.lock().unwrap() {
+                MockAnswer::Ip(ip) => Some(ResolvedRecord::resolved(domain, 2, 1, 10, vec![ip])),
+                MockAnswer::Fail => Some(ResolvedRecord::failed(
+                    domain,
+                    1,
+                    ResolveError::FromLocal(ResolveLocalError::DriverTimedOut),
+                )),
+                MockAnswer::Hang => None,
+            }
+        }
+    }
+
+    impl ResolveDriver for MockDriver {
+        fn query_v4(
+            &self,
+            domain: Arc<str>,
+            _config: &ResolverRuntimeConfig,
+            sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+        ) {
+            if let Some(r) = self.answer(domain) {
+                let _ = sender.send(ResolveDriverResponse::V4(r));
+            }
+        }
+
+        fn query_v6(
+            &self,
+            domain: Arc<str>,
+            _config: &ResolverRuntimeConfig,
+            sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+        ) {
+            if let Some(r) = self.answer(domain) {
+                let _ = sender.send(ResolveDriverResponse::V6(r));
+            }
+        }
+    }
+
+    struct TestRuntime {
+        driver: MockDriver,
+        stats: Arc<ResolverStats>,
+        req_sender: mpsc::UnboundedSender<ResolveDriverRequest>,
+        _ctl_sender: mpsc::UnboundedSender<ResolverCommand>,
+    }
+
+    impl TestRuntime {
+        fn spawn(runtime: ResolverRuntimeConfig, ip: IpAddr) -> Self {
+            let config = ResolverConfig {
+                name: "test".to_string(),
+                driver: AnyResolveDriverConfig::Hosts(Default::default()),
+                runtime,
+            };
+            let driver = MockDriver {
+                answer: Arc::new(Mutex::new(MockAnswer::Ip(ip))),
+                queries: Arc::new(AtomicUsize::new(0)),
+            };
+            let stats = Arc::new(ResolverStats::default());
+            let (req_sender, req_receiver) = mpsc::unbounded_channel();
+            let (ctl_sender, ctl_receiver) = mpsc::unbounded_channel();
+            let mut runtime =
+                ResolverRuntime::new(config, req_receiver, ctl_receiver, stats.clone());
+            runtime.driver = Some(Box::new(driver.clone()));
+            tokio::task::spawn_local(runtime);
+            TestRuntime {
+                driver,
+                stats,
+                req_sender,
+                _ctl_sender: ctl_sender,
+            }
+        }
+
+        async fn query(&self, domain: &str) -> (ArcResolvedRecord, ResolvedRecordSource) {
+            let (sender, receiver) = oneshot::channel();
+            self.req_sender
+                .send(ResolveDriverRequest::GetV4(Arc::from(domain), sender))
+                .unwrap();
+            receiver.await.unwrap()
+        }
+    }
+
+    /// run the test in a local set with paused time, as the runtime is not Send
+    fn run_test<F: Future>(f: F) -> F::Output {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .unwrap();
+        tokio::task::LocalSet::new().block_on(&rt, f)
+    }
+
+    const DOMAIN: &str = "www.example.net";
+    const IP1: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 0, 2, 1));
+    const IP2: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 0, 2, 2));
+
+    fn serve_stale_config(client_timeout: Duration) -> ResolverRuntimeConfig {
+        ResolverRuntimeConfig {
+            serve_stale_max_age: Duration::from_secs(60),
+            serve_stale_client_timeout: client_timeout,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn refresh_before_serve_stale() {
+        run_test(async {
+            let rt = TestRuntime::spawn(serve_stale_config(Duration::ZERO), IP1);
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Query));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP1]);
+
+            // the record expires after 1s and vanishes after 2s
+            tokio::time::sleep(Duration::from_secs(3)).await;
+
+            rt.driver.set_answer(MockAnswer::Ip(IP2));
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Query));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP2]);
+            assert_eq!(rt.driver.queries(), 2);
+            assert_eq!(rt.stats.snapshot().query_a.stale, 0);
+        });
+    }
+
+    #[test]
+    fn serve_stale_on_failure() {
+        run_test(async {
+            let rt = TestRuntime::spawn(serve_stale_config(Duration::ZERO), IP1);
+            rt.query(DOMAIN).await;
+            tokio::time::sleep(Duration::from_secs(3)).await;
+
+            rt.driver.set_answer(MockAnswer::Fail);
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Trash));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP1]);
+            assert_eq!(rt.driver.queries(), 2);
+
+            // no more refresh within the failure recheck time
+            let (_, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Trash));
+            assert_eq!(rt.driver.queries(), 2);
+            assert_eq!(rt.stats.snapshot().query_a.stale, 2);
+
+            tokio::time::sleep(SERVE_STALE_FAILURE_RECHECK).await;
+            rt.driver.set_answer(MockAnswer::Ip(IP2));
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Query));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP2]);
+            assert_eq!(rt.driver.queries(), 3);
+        });
+    }
+
+    #[test]
+    fn serve_stale_on_client_timeout() {
+        run_test(async {
+            let client_timeout = Duration::from_millis(1800);
+            let rt = TestRuntime::spawn(serve_stale_config(client_timeout), IP1);
+            rt.query(DOMAIN).await;
+            tokio::time::sleep(Duration::from_secs(3)).await;
+
+            rt.driver.set_answer(MockAnswer::Hang);
+            let start = Instant::now();
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(start.elapsed() >= client_timeout);
+            assert!(matches!(source, ResolvedRecordSource::Trash));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP1]);
+            assert_eq!(rt.stats.snapshot().query_a.stale, 1);
+        });
+    }
+
+    #[test]
+    fn serve_stale_max_age() {
+        run_test(async {
+            let rt = TestRuntime::spawn(serve_stale_config(Duration::ZERO), IP1);
+            rt.query(DOMAIN).await;
+            tokio::time::sleep(Duration::from_secs(63)).await;
+
+            rt.driver.set_answer(MockAnswer::Fail);
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Query));
+            assert!(r.is_err());
+            assert_eq!(rt.stats.snapshot().query_a.stale, 0);
+        });
+    }
+
+    #[test]
+    fn prefetch_hot_record() {
+        run_test(async {
+            let config = ResolverRuntimeConfig {
+                prefetch_window: Duration::from_secs(1),
+                prefetch_min_hits: 2,
+                ..Default::default()
+            };
+            let rt = TestRuntime::spawn(config, IP1);
+            rt.query(DOMAIN).await;
+            assert_eq!(rt.driver.queries(), 1);
+
+            rt.driver.set_answer(MockAnswer::Ip(IP2));
+            let (_, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Cache));
+            assert_eq!(rt.driver.queries(), 1);
+
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Cache));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP1]);
+            assert_eq!(rt.driver.queries(), 2);
+            assert_eq!(rt.stats.snapshot().query_a.prefetch, 1);
+
+            let (r, source) = rt.query(DOMAIN).await;
+            assert!(matches!(source, ResolvedRecordSource::Cache));
+            assert_eq!(r.result.as_ref().unwrap(), &[IP2]);
+        });
+    }
+}
@@ -0,0 +1,234 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::{ResolveError, ResolveLocalError, ResolveServerError};
+
+const DEFAULT_HTTPS_ALPN: &str = "http/1.1";
+const DEFAULT_HTTPS_PORT: u16 = 443;
+
+/// Get the HTTPS RR query name for the origin, with port prefix if not the default one.
+/// See RFC 9460 Section 9.1
+pub fn https_query_name(domain: &Arc<str>, port: u16) -> Arc<str> {
+    if port == DEFAULT_HTTPS_PORT {
+        domain.clone()
+    } else {
+        Arc::from(format!("_{port}._https.{domain}"))
+    }
+}
+
+/// The service binding parsed from a HTTPS or SVCB record, see RFC 9460
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServiceBinding {
+    pub priority: u16,
+    /// the target name, None if it's the same as the owner name
+    pub target: Option<Arc<str>>,
+    pub alpn: Vec<String>,
+    pub no_default_alpn: bool,
+    pub port: Option<u16>,
+    pub ipv4_hint: Vec<Ipv4Addr>,
+    pub ipv6_hint: Vec<Ipv6Addr>,
+    pub ech_config: Option<Arc<[u8]>>,
+}
+
+impl ServiceBinding {
+    pub fn is_alias_mode(&self) -> bool {
+        self.priority == 0
+    }
+
+    /// Get the ALPN protocols supported by the service, including the default one for HTTPS
+    pub fn alpn_protocols(&self) -> Vec<String> {
+        let mut protocols = self.alpn.clone();
+        if !self.no_default_alpn && !protocols.iter().any(|p| p == DEFAULT_HTTPS_ALPN) {
+            protocols.push(DEFAULT_HTTPS_ALPN.to_string());
+        }
+        protocols
+    }
+
+    pub fn has_address_hint(&self) -> bool {
+        !self.ipv4_hint.is_empty() || !self.ipv6_hint.is_empty()
+    }
+
+    /// Get the ipv4 and ipv6 address hints
+    pub fn address_hints(&self) -> (Vec<IpAddr>, Vec<IpAddr>) {
+        let ip4 = self.ipv4_hint.iter().map(|ip| IpAddr::V4(*ip)).collect();
+        let ip6 = self.ipv6_hint.iter().map(|ip| IpAddr::V6(*ip)).collect();
+        (ip4, ip6)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ResolvedServiceRecord {
+    pub domain: Arc<str>,
+    pub created: Instant,
+    pub expire: Option<Instant>,
+    pub result: Result<Vec<ServiceBinding>, ResolveError>,
+}
+
+pub type ArcResolvedServiceRecord = Arc<ResolvedServiceRecord>;
+
+impl ResolvedServiceRecord {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    pub fn is_usable(&self) -> bool {
+        self.result.as_ref().map(|v| !v.is_empty()).unwrap_or(false)
+    }
+
+    pub fn is_acceptable(&self) -> bool {
+        let Err(e) = self.result.as_ref() else {
+            return true;
+        };
+
+        matches!(e, ResolveError::FromServer(ResolveServerError::NotFound))
+    }
+
+    /// Get the service mode binding with the highest priority
+    pub fn best_service(&self) -> Option<&ServiceBinding> {
+        self.result
+            .as_ref()
+            .ok()
+            .and_then(|v| v.iter().find(|b| !b.is_alias_mode()))
+    }
+
+    /// Get the service mode binding with the highest priority which is served on the port
+    pub fn best_service_on_port(&self, port: u16) -> Option<&ServiceBinding> {
+        self.result.as_ref().ok().and_then(|v| {
+            v.iter()
+                .filter(|b| !b.is_alias_mode())
+                .find(|b| b.port.map(|p| p == port).unwrap_or(true))
+        })
+    }
+
+    pub fn timed_out(domain: Arc<str>, protective_cache_ttl: u32) -> Self {
+        ResolvedServiceRecord::failed(
+            domain,
+            protective_cache_ttl,
+            ResolveError::FromLocal(ResolveLocalError::DriverTimedOut),
+        )
+    }
+
+    pub fn resolved(
+        domain: Arc<str>,
+        ttl: u32,
+        min_ttl: u32,
+        max_ttl: u32,
+        mut bindings: Vec<ServiceBinding>,
+    ) -> Self {
+        bindings.sort_by_key(|b| b.priority);
+        let created = Instant::now();
+        let ttl = ttl.clamp(min_ttl, max_ttl.max(min_ttl));
+        let expire = created.checked_add(Duration::from_secs(ttl as u64));
+        ResolvedServiceRecord {
+            domain,
+            created,
+            expire,
+            result: Ok(bindings),
+        }
+    }
+
+    pub fn empty(domain: Arc<str>, expire_ttl: u32) -> Self {
+        let created = Instant::now();
+        let expire = created.checked_add(Duration::from_secs(expire_ttl as u64));
+        ResolvedServiceRecord {
+            domain,
+            created,
+            expire,
+            result: Ok(Vec::new()),
+        }
+    }
+
+    pub fn failed(domain: Arc<str>, protective_cache_ttl: u32, err: ResolveError) -> Self {
+        let created = Instant::now();
+        let expire = created.checked_add(Duration::from_secs(protective_cache_ttl as u64));
+        ResolvedServiceRecord {
+            domain,
+            created,
+            expire,
+            result: Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alpn_protocols() {
+        let mut b = ServiceBinding {
+            priority: 1,
+            alpn: vec!["h3".to_string(), "h2".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(b.alpn_protocols(), ["h3", "h2", "http/1.1"]);
+
+        b.no_default_alpn = true;
+        assert_eq!(b.alpn_protocols(), ["h3", "h2"]);
+
+        b.no_default_alpn = false;
+        b.alpn.push("http/1.1".to_string());
+        assert_eq!(b.alpn_protocols(), ["h3", "h2", "http/1.1"]);
+    }
+
+    #[test]
+    fn query_name() {
+        let domain = Arc::from("example.net");
+        assert_eq!(https_query_name(&domain, 443).as_ref(), "example.net");
+        assert_eq!(
+            https_query_name(&domain, 8443).as_ref(),
+            "_8443._https.example.net"
+        );
+    }
+
+    #[test]
+    fn best_service() {
+        let alias = ServiceBinding {
+            priority: 0,
+            target: Some(Arc::from("svc.example.net")),
+            ..Default::default()
+        };
+        let low = ServiceBinding {
+            priority: 2,
+            port: Some(8443),
+            ..Default::default()
+        };
+        let high = ServiceBinding {
+            priority: 1,
+            ..Default::default()
+        };
+        let r = ResolvedServiceRecord::resolved(
+            Arc::from("example.net"),
+            300,
+            30,
+            3600,
+            vec![low.clone(), alias.clone(), high.clone()],
+        );
+        assert!(r.is_usable());
+        assert_eq!(r.best_service(), Some(&high));
+        assert_eq!(r.best_service_on_port(443), Some(&high));
+
+        let r = ResolvedServiceRecord::resolved(
+            Arc::from("example.net"),
+            300,
+            30,
+            3600,
+            vec![low.clone(), alias],
+        );
+        assert_eq!(r.best_service_on_port(8443), Some(&low));
+        assert!(r.best_service_on_port(443).is_none());
+
+        let r = ResolvedServiceRecord::empty(Arc::from("example.net"), 30);
+        assert!(r.is_ok());
+        assert!(!r.is_usable());
+        assert!(r.best_service().is_none());
+    }
+}
@@ -0,0 +1,305 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, anyhow};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use super::{ResolveQueryType, ResolvedRecord};
+
+const SNAPSHOT_HEADER: &str = "# g3-resolver cache snapshot";
+
+/// The snapshot file is a text file, with a header line that contains the save time,
+/// and each of the following lines is a record in the format:
+///   <A|AAAA> <domain> <remaining ttl in seconds> <ip>[,<ip>...]
+pub(crate) struct CacheSnapshot {
+    content: String,
+}
+
+impl CacheSnapshot {
+    pub(crate) fn new() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        CacheSnapshot {
+            content: format!("{SNAPSHOT_HEADER} {now}\n"),
+        }
+    }
+
+    pub(crate) fn add_record(
+        &mut self,
+        query_type: ResolveQueryType,
+        record: &ResolvedRecord,
+        expire_at: Instant,
+        now: Instant,
+    ) {
+        let Ok(ips) = &record.result else {
+            return;
+        };
+        if ips.is_empty() {
+            return;
+        }
+        let ttl = expire_at.saturating_duration_since(now).as_secs();
+        if ttl == 0 {
+            return;
+        }
+
+        let _ = write!(
+            &mut self.content,
+            "{} {} {ttl} ",
+            query_type.as_str(),
+            record.domain
+        );
+        for (i, ip) in ips.iter().enumerate() {
+            if i > 0 {
+                self.content.push(',');
+            }
+            let _ = write!(&mut self.content, "{ip}");
+        }
+        self.content.push('\n');
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let mut tmp_path = OsString::from(path.as_os_str());
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+        std::fs::write(&tmp_path, self.content.as_bytes())
+            .map_err(|e| anyhow!("failed to write to file {}: {e}", tmp_path.display()))?;
+        std::fs::rename(&tmp_path, path)
+            .map_err(|e| anyhow!("failed to rename to file {}: {e}", path.display()))
+    }
+
+    fn load(path: &Path) -> anyhow::Result<CacheSnapshotRecords> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(anyhow!("failed to read file {}: {e}", path.display())),
+        };
+        Self::parse(&content, SystemTime::now(), Instant::now())
+    }
+
+    fn parse(
+        content: &str,
+        now: SystemTime,
+        now_ins: Instant,
+    ) -> anyhow::Result<CacheSnapshotRecords> {
+        let mut lines = content.lines();
+        let Some(header) = lines.next() else {
+            return Ok(Vec::new());
+        };
+        let saved_at = header
+            .strip_prefix(SNAPSHOT_HEADER)
+            .and_then(|s| u64::from_str(s.trim()).ok())
+            .ok_or_else(|| anyhow!("invalid snapshot header line"))?;
+        let elapsed = now
+            .duration_since(UNIX_EPOCH + Duration::from_secs(saved_at))
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut records = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let r = Self::parse_line(line, elapsed, now_ins)
+                .context(format!("invalid record at line {}", i + 2))?;
+            if let Some(r) = r {
+                records.push(r);
+            }
+        }
+        Ok(records)
+    }
+
+    fn parse_line(
+        line: &str,
+        elapsed: u64,
+        now: Instant,
+    ) -> anyhow::Result<Option<(ResolveQueryType, ResolvedRecord)>> {
+        let mut iter = line.split_ascii_whitespace();
+        let Some(query_type) = iter.next() else {
+            return Ok(None);
+        };
+        let query_type = match query_type {
+            "A" => ResolveQueryType::A,
+            "AAAA" => ResolveQueryType::Aaaa,
+            _ => return Err(anyhow!("invalid query type {query_type}")),
+        };
+        let domain = iter.next().ok_or_else(|| anyhow!("no domain found"))?;
+        let ttl = iter.next().ok_or_else(|| anyhow!("no ttl found"))?;
+        let ttl = u64::from_str(ttl).map_err(|e| anyhow!("invalid ttl {ttl}: {e}"))?;
+        let ips = iter.next().ok_or_else(|| anyhow!("no ip address found"))?;
+        let ips = ips
+            .split(',')
+            .map(|s| IpAddr::from_str(s).map_err(|e| anyhow!("invalid ip address {s}: {e}")))
+            .collect::<anyhow::Result<Vec<IpAddr>>>()?;
+
+        let ttl = ttl.saturating_sub(elapsed);
+        if ttl == 0 {
+            return Ok(None);
+        }
+        let expire = now.checked_add(Duration::from_secs(ttl));
+        Ok(Some((
+            query_type,
+            ResolvedRecord {
+                domain: Arc::from(domain),
+                created: now,
+                expire,
+                vanish: expire,
+                result: Ok(ips),
+            },
+        )))
+    }
+}
+
+pub(crate) type CacheSnapshotRecords = Vec<(ResolveQueryType, ResolvedRecord)>;
+
+/// Load the snapshot file in a blocking thread
+pub(crate) fn spawn_load(path: PathBuf) -> JoinHandle<anyhow::Result<CacheSnapshotRecords>> {
+    tokio::task::spawn_blocking(move || CacheSnapshot::load(&path))
+}
+
+/// Save the snapshots in blocking threads, and make sure that an older snapshot won't overwrite
+/// a newer one if the saves are finished out of order
+#[derive(Default)]
+pub(crate) struct CacheSnapshotSaver {
+    next_id: u64,
+    saved_id: Arc<Mutex<u64>>,
+}
+
+impl CacheSnapshotSaver {
+    pub(crate) fn spawn_save<F>(&mut self, snapshot: CacheSnapshot, path: PathBuf, on_finish: F)
+    where
+        F: FnOnce(anyhow::Result<()>) + Send + 'static,
+    {
+        self.next_id += 1;
+        let id = self.next_id;
+        let saved_id = self.saved_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut saved_id = saved_id.lock().unwrap();
+            if *saved_id > id {
+                on_finish(Ok(()));
+                return;
+            }
+            let r = snapshot.save(&path);
+            if r.is_ok() {
+                *saved_id = id;
+            }
+            on_finish(r);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let now = Instant::now();
+        let record = ResolvedRecord::resolved(
+            Arc::from("example.net"),
+            300,
+            30,
+            3600,
+            vec![
+                IpAddr::from_str("192.0.2.1").unwrap(),
+                IpAddr::from_str("192.0.2.2").unwrap(),
+            ],
+        );
+        let mut snapshot = CacheSnapshot::new();
+        snapshot.add_record(
+            ResolveQueryType::A,
+            &record,
+            now + Duration::from_secs(120),
+            now,
+        );
+        let empty = ResolvedRecord::empty(Arc::from("example.org"), 300);
+        snapshot.add_record(
+            ResolveQueryType::Aaaa,
+            &empty,
+            now + Duration::from_secs(120),
+            now,
+        );
+
+        let records =
+            CacheSnapshot::parse(&snapshot.content, SystemTime::now(), Instant::now()).unwrap();
+        assert_eq!(records.len(), 1);
+        let (query_type, r) = &records[0];
+        assert!(matches!(query_type, ResolveQueryType::A));
+        assert_eq!(r.domain.as_ref(), "example.net");
+        assert_eq!(r.result.as_ref().unwrap().len(), 2);
+
+        let later = SystemTime::now() + Duration::from_secs(200);
+        let records = CacheSnapshot::parse(&snapshot.content, later, Instant::now()).unwrap();
+        assert!(records.is_empty());
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(CacheSnapshot::parse("invalid\n", SystemTime::now(), Instant::now()).is_err());
+        let content = format!("{SNAPSHOT_HEADER} 0\nMX example.net 30 192.0.2.1\n");
+        assert!(CacheSnapshot::parse(&content, SystemTime::now(), Instant::now()).is_err());
+    }
+
+    #[tokio::test]
+    async fn save_and_load() {
+        let dir = std::env::temp_dir().join(format!("g3-resolver-snapshot-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("cache.txt");
+
+        let now = Instant::now();
+        let new_snapshot = |domain: &str| {
+            let record = ResolvedRecord::resolved(
+                Arc::from(domain),
+                300,
+                30,
+                3600,
+                vec![IpAddr::from_str("192.0.2.1").unwrap()],
+            );
+            let mut snapshot = CacheSnapshot::new();
+            snapshot.add_record(
+                ResolveQueryType::A,
+                &record,
+                now + Duration::from_secs(120),
+                now,
+            );
+            snapshot
+        };
+
+        let mut saver = CacheSnapshotSaver::default();
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        saver.spawn_save(new_snapshot("example.net"), path.clone(), move |r| {
+            let _ = sender.send(r);
+        });
+        receiver.await.unwrap().unwrap();
+
+        // an older snapshot finished later won't overwrite the newer one,
+        // so mark the one after the next as saved
+        *saver.saved_id.lock().unwrap() = saver.next_id + 2;
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        saver.spawn_save(new_snapshot("example.org"), path.clone(), move |r| {
+            let _ = sender.send(r);
+        });
+        receiver.await.unwrap().unwrap();
+
+        let records = spawn_load(path.clone()).await.unwrap().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].1.domain.as_ref(), "example.net");
+
+        let records = spawn_load(dir.join("not-existed.txt"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(records.is_empty());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
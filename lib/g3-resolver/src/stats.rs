@@ -7,6 +7,7 @@ use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 use super::{
     ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError, ResolvedRecord,
+    ResolvedServiceRecord,
 };
 
 #[derive(Default)]
@@ -15,6 +16,8 @@ pub struct ResolverQueryStats {
     query_cached: AtomicU64,
     query_driver: AtomicU64,
     query_trashed: AtomicU64,
+    query_stale: AtomicU64,
+    query_prefetch: AtomicU64,
     driver_timeout: AtomicU64,
     driver_refused: AtomicU64,
     driver_malformed: AtomicU64,
@@ -30,6 +33,8 @@ pub struct ResolverQuerySnapshot {
     pub cached: u64,
     pub driver: u64,
     pub trashed: u64,
+    pub stale: u64,
+    pub prefetch: u64,
     pub driver_timeout: u64,
     pub driver_refused: u64,
     pub driver_malformed: u64,
@@ -46,6 +51,8 @@ impl ResolverQueryStats {
             cached: self.query_cached.load(Ordering::Relaxed),
             driver: self.query_driver.load(Ordering::Relaxed),
             trashed: self.query_trashed.load(Ordering::Relaxed),
+            stale: self.query_stale.load(Ordering::Relaxed),
+            prefetch: self.query_prefetch.load(Ordering::Relaxed),
             driver_timeout: self.driver_timeout.load(Ordering::Relaxed),
             driver_refused: self.driver_refused.load(Ordering::Relaxed),
             driver_malformed: self.driver_malformed.load(Ordering::Relaxed),
@@ -84,6 +91,20 @@ impl ResolverQueryStats {
         }
     }
 
+    pub(crate) fn add_query_stale(&self) {
+        self.add_query_stale_n(1);
+    }
+
+    pub(crate) fn add_query_stale_n(&self, n: usize) {
+        if n > 0 {
+            self.query_stale.fetch_add(n as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn add_query_prefetch(&self) {
+        self.query_prefetch.fetch_add(1, Ordering::Relaxed);
+    }
+
     #[inline]
     fn add_driver_timeout(&self) {
         self.driver_timeout.fetch_add(1, Ordering::Relaxed);
@@ -125,6 +146,12 @@ impl ResolverQueryStats {
         }
     }
 
+    pub(crate) fn add_service_record(&self, record: &ResolvedServiceRecord) {
+        if let Err(e) = &record.result {
+            self.add_error(e);
+        }
+    }
+
     fn add_server_error(&self, e: &ResolveServerError) {
         match e {
             ResolveServerError::Refused => self.add_server_refused(),
@@ -217,8 +244,10 @@ impl ResolverMemoryStats {
 pub struct ResolverStats {
     pub(crate) query_a: ResolverQueryStats,
     pub(crate) query_aaaa: ResolverQueryStats,
+    pub(crate) query_https: ResolverQueryStats,
     pub(crate) memory_a: ResolverMemoryStats,
     pub(crate) memory_aaaa: ResolverMemoryStats,
+    pub(crate) memory_https: ResolverMemoryStats,
 }
 
 impl ResolverStats {
@@ -226,8 +255,10 @@ impl ResolverStats {
         ResolverSnapshot {
             query_a: self.query_a.snapshot(),
             query_aaaa: self.query_aaaa.snapshot(),
+            query_https: self.query_https.snapshot(),
             memory_a: self.memory_a.snapshot(),
             memory_aaaa: self.memory_aaaa.snapshot(),
+            memory_https: self.memory_https.snapshot(),
         }
     }
 }
@@ -236,6 +267,8 @@ impl ResolverStats {
 pub struct ResolverSnapshot {
     pub query_a: ResolverQuerySnapshot,
     pub query_aaaa: ResolverQuerySnapshot,
+    pub query_https: ResolverQuerySnapshot,
     pub memory_a: ResolverMemorySnapshot,
     pub memory_aaaa: ResolverMemorySnapshot,
+    pub memory_https: ResolverMemorySnapshot,
 }
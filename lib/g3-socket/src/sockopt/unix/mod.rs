@@ -13,6 +13,7 @@ mod linux;
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) use linux::{
     get_incoming_cpu, set_bind_address_no_port, set_incoming_cpu, set_ip_transparent_v6,
+    set_recv_ip_orig_dst_addr, set_recv_ipv6_orig_dst_addr,
 };
 
 #[cfg(target_os = "freebsd")]
@@ -112,6 +112,10 @@ pub fn new_std_bind_listen(config: &UdpListenConfig) -> io::Result<UdpSocket> {
     if let Some(enable) = config.is_ipv6only() {
         super::listen::set_only_v6(&socket, addr, enable)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        set_transparent_recv_orig_dst(&socket, family)?;
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     #[cfg(any(target_os = "linux", target_os = "android"))]
@@ -134,13 +138,18 @@ pub fn new_std_bind_listen(config: &UdpListenConfig) -> io::Result<UdpSocket> {
 }
 
 pub fn new_std_rebind_listen(config: &UdpListenConfig, addr: SocketAddr) -> io::Result<UdpSocket> {
-    let socket = new_udp_socket(AddressFamily::from(&addr), config.socket_buffer())?;
+    let family = AddressFamily::from(&addr);
+    let socket = new_udp_socket(family, config.socket_buffer())?;
     super::listen::set_addr_reuse(&socket, addr)?;
     // OpenBSD is always ipv6-only
     #[cfg(not(target_os = "openbsd"))]
     if let Some(enable) = config.is_ipv6only() {
         super::listen::set_only_v6(&socket, addr, enable)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        set_transparent_recv_orig_dst(&socket, family)?;
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     #[cfg(unix)]
@@ -151,6 +160,45 @@ pub fn new_std_rebind_listen(config: &UdpListenConfig, addr: SocketAddr) -> io::
     Ok(UdpSocket::from(socket))
 }
 
+#[cfg(target_os = "linux")]
+fn set_transparent_recv_orig_dst(socket: &Socket, family: AddressFamily) -> io::Result<()> {
+    match family {
+        AddressFamily::Ipv4 => {
+            socket.set_ip_transparent_v4(true)?;
+            crate::sockopt::set_recv_ip_orig_dst_addr(socket, true)
+        }
+        AddressFamily::Ipv6 => {
+            crate::sockopt::set_ip_transparent_v6(socket, true)?;
+            // also for ipv4-mapped addresses on dual stack sockets
+            crate::sockopt::set_recv_ip_orig_dst_addr(socket, true)?;
+            crate::sockopt::set_recv_ipv6_orig_dst_addr(socket, true)
+        }
+    }
+}
+
+/// Create a socket that bound to a non-local address and connected to the peer,
+/// which can be used to reply to clients of transparent proxy
+#[cfg(target_os = "linux")]
+pub fn new_std_transparent_reply_socket(
+    local_addr: SocketAddr,
+    peer_addr: SocketAddr,
+    buf_conf: SocketBufferConfig,
+    misc_opts: UdpMiscSockOpts,
+) -> io::Result<UdpSocket> {
+    let family = AddressFamily::from(&local_addr);
+    let socket = new_udp_socket(family, buf_conf)?;
+    // the listen socket may be bound to the same address
+    socket.set_reuse_address(true)?;
+    match family {
+        AddressFamily::Ipv4 => socket.set_ip_transparent_v4(true)?,
+        AddressFamily::Ipv6 => crate::sockopt::set_ip_transparent_v6(&socket, true)?,
+    }
+    socket.bind(&SockAddr::from(local_addr))?;
+    RawSocket::from(&socket).set_udp_misc_opts(local_addr, misc_opts)?;
+    socket.connect(&SockAddr::from(peer_addr))?;
+    Ok(UdpSocket::from(socket))
+}
+
 fn new_udp_socket(family: AddressFamily, buf_conf: SocketBufferConfig) -> io::Result<Socket> {
     let socket = new_nonblocking_udp_socket(family)?;
     RawSocket::from(&socket).set_buf_opts(buf_conf)?;
@@ -8,6 +8,8 @@ use std::path::Path;
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use super::TlsTicketConfig;
 use crate::source::TicketSourceConfig;
 
@@ -42,3 +44,13 @@ impl TlsTicketConfig {
         }
     }
 }
+
+impl YamlDump for TlsTicketConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("check_interval", &self.check_interval)
+            .set("local_lifetime", &self.local_lifetime)
+            .set("source", &self.remote_source);
+        map.build()
+    }
+}
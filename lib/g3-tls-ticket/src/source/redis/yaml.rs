@@ -7,6 +7,8 @@ use std::path::Path;
 
 use yaml_rust::yaml;
 
+use g3_yaml::dump::YamlMapDumper;
+
 use super::RedisSourceConfig;
 use crate::source::CONFIG_KEY_SOURCE_TYPE;
 
@@ -33,4 +35,10 @@ impl RedisSourceConfig {
         config.check()?;
         Ok(config)
     }
+
+    pub(crate) fn dump_yaml_kv(&self, map: &mut YamlMapDumper) {
+        self.redis.dump_yaml_kv(map);
+        map.set("enc_key", &self.enc_key_name)
+            .set("dec_set", &self.dec_set_name);
+    }
 }
@@ -8,6 +8,8 @@ use std::path::Path;
 use anyhow::anyhow;
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use super::{CONFIG_KEY_SOURCE_TYPE, TicketSourceConfig};
 
 impl TicketSourceConfig {
@@ -29,3 +31,16 @@ impl TicketSourceConfig {
         }
     }
 }
+
+impl YamlDump for TicketSourceConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        match self {
+            TicketSourceConfig::Redis(s) => {
+                map.set_yaml(CONFIG_KEY_SOURCE_TYPE, Yaml::String("redis".to_string()));
+                s.dump_yaml_kv(&mut map);
+            }
+        }
+        map.build()
+    }
+}
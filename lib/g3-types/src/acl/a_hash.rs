@@ -41,6 +41,10 @@ where
         self.missed_action = action;
     }
 
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&K, Action)> {
+        self.inner.iter().map(|(k, a)| (k, *a))
+    }
+
     pub fn check<Q>(&self, node: &Q) -> (bool, Action)
     where
         K: Borrow<Q>,
@@ -4,7 +4,7 @@
  */
 
 use super::{AclAction, AclRadixTrieRule, AclRadixTrieRuleBuilder, ActionContract};
-use crate::resolve::reverse_idna_domain;
+use crate::resolve::{reverse_idna_domain, reverse_to_idna_domain};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AclChildDomainRuleBuilder<Action = AclAction>(AclRadixTrieRuleBuilder<String, Action>);
@@ -30,6 +30,11 @@ impl<Action: ActionContract> AclChildDomainRuleBuilder<Action> {
         self.0.missed_action()
     }
 
+    /// Iterate over all the parent domains and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (String, Action)> {
+        self.0.iter().map(|(d, a)| (reverse_to_idna_domain(d), a))
+    }
+
     #[inline]
     pub fn build(&self) -> AclChildDomainRule<Action> {
         AclChildDomainRule(self.0.build())
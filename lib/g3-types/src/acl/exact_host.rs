@@ -55,6 +55,14 @@ impl<Action: ActionContract> AclExactHostRule<Action> {
         self.missed_action
     }
 
+    /// Iterate over all the hosts and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (Host, Action)> {
+        self.domain
+            .iter()
+            .map(|(d, a)| (Host::Domain(d.clone()), a))
+            .chain(self.ip.iter().map(|(ip, a)| (Host::Ip(*ip), a)))
+    }
+
     #[inline]
     pub fn check_domain(&self, domain: &str) -> (bool, Action) {
         self.domain.check(domain)
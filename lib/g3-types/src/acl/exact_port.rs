@@ -39,6 +39,16 @@ impl<Action: ActionContract> AclExactPortRule<Action> {
         self.0.set_missed_action(action);
     }
 
+    #[inline]
+    pub fn missed_action(&self) -> Action {
+        self.0.missed_action()
+    }
+
+    /// Iterate over all the ports and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (u16, Action)> {
+        self.0.iter().map(|(p, a)| (*p, a))
+    }
+
     #[inline]
     pub fn check_port(&self, port: &u16) -> (bool, Action) {
         self.0.check(port)
@@ -41,6 +41,15 @@ where
         self.missed_action = action;
     }
 
+    #[inline]
+    pub fn missed_action(&self) -> Action {
+        self.missed_action
+    }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&K, Action)> {
+        self.inner.iter().map(|(k, a)| (k, *a))
+    }
+
     pub fn check<Q>(&self, node: &Q) -> (bool, Action)
     where
         K: Borrow<Q>,
@@ -36,6 +36,11 @@ impl<Action: ActionContract> AclNetworkRuleBuilder<Action> {
         self.missed_action
     }
 
+    /// Iterate over all the networks and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (&IpNetwork, Action)> {
+        self.inner.iter().map(|(n, a)| (n, *a))
+    }
+
     #[inline]
     pub fn set_missed_action(&mut self, action: Action) {
         self.missed_action = action;
@@ -37,6 +37,11 @@ impl<Action: ActionContract> AclProxyRequestRule<Action> {
         self.missed_action
     }
 
+    /// Iterate over all the request types and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (ProxyRequestType, Action)> {
+        self.request.iter().map(|(r, a)| (*r, a))
+    }
+
     #[inline]
     pub fn check_request(&self, request: &ProxyRequestType) -> (bool, Action) {
         self.request.check(request)
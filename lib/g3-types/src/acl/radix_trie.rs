@@ -47,6 +47,10 @@ where
         self.missed_action
     }
 
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&K, Action)> {
+        self.inner.iter().map(|(k, a)| (k, *a))
+    }
+
     pub fn build(&self) -> AclRadixTrieRule<K, Action> {
         let mut trie = Trie::new();
 
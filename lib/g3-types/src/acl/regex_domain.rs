@@ -9,7 +9,7 @@ use radix_trie::{Trie, TrieCommon};
 use regex::Regex;
 
 use super::{AclAction, ActionContract, OrderedActionContract, RegexSetBuilder, RegexSetMatch};
-use crate::resolve::reverse_idna_domain;
+use crate::resolve::{reverse_idna_domain, reverse_to_idna_domain};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AclRegexDomainRuleBuilder<Action = AclAction> {
@@ -49,6 +49,19 @@ impl<Action: ActionContract> AclRegexDomainRuleBuilder<Action> {
     pub fn missed_action(&self) -> Action {
         self.missed_action
     }
+
+    /// Iterate over all the full match regex strings and their actions
+    pub fn full_regex_iter(&self) -> impl Iterator<Item = (&str, Action)> {
+        self.full_regex.iter()
+    }
+
+    /// Iterate over all the parent domains, prefix regex strings and their actions
+    pub fn prefix_regex_iter(&self) -> impl Iterator<Item = (String, &str, Action)> {
+        self.prefix_regex.iter().flat_map(|(d, set)| {
+            let parent = reverse_to_idna_domain(d);
+            set.iter().map(move |(r, a)| (parent.clone(), r, a))
+        })
+    }
 }
 
 impl<Action: OrderedActionContract> AclRegexDomainRuleBuilder<Action> {
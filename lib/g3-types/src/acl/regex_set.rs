@@ -26,6 +26,10 @@ impl<Action: ActionContract> RegexSetBuilder<Action> {
     pub(super) fn add_regex(&mut self, regex: &Regex, action: Action) {
         self.inner.insert(regex.as_str().to_string(), action);
     }
+
+    pub(super) fn iter(&self) -> impl Iterator<Item = (&str, Action)> {
+        self.inner.iter().map(|(r, a)| (r.as_str(), *a))
+    }
 }
 
 impl<Action: OrderedActionContract> RegexSetBuilder<Action> {
@@ -87,6 +91,11 @@ impl<Action: ActionContract> AclRegexSetRuleBuilder<Action> {
     pub fn missed_action(&self) -> Action {
         self.missed_action
     }
+
+    /// Iterate over all the regex strings and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Action)> {
+        self.inner.iter()
+    }
 }
 
 impl<Action: OrderedActionContract> AclRegexSetRuleBuilder<Action> {
@@ -31,6 +31,11 @@ impl<Action: ActionContract> AclUserAgentRule<Action> {
         self.missed_action
     }
 
+    /// Iterate over all the lowercase user-agent names and their actions
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Action)> {
+        self.inner.iter().map(|(n, a)| (n.as_str(), *a))
+    }
+
     #[inline]
     pub fn set_missed_action(&mut self, action: Action) {
         self.missed_action = action;
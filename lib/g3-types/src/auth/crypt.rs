@@ -27,6 +27,22 @@ enum HashValue {
 }
 
 impl HashValue {
+    fn name(&self) -> &'static str {
+        match self {
+            HashValue::Md5(_) => "md5",
+            HashValue::Sha1(_) => "sha1",
+            HashValue::Blake3(_) => "blake3",
+        }
+    }
+
+    fn to_hex(&self) -> String {
+        match self {
+            HashValue::Md5(v) => hex::encode(v),
+            HashValue::Sha1(v) => hex::encode(v),
+            HashValue::Blake3(v) => v.to_hex().to_string(),
+        }
+    }
+
     fn hash_match(&self, buf: &[u8]) -> Result<bool, ErrorStack> {
         match self {
             HashValue::Md5(v) => {
@@ -113,6 +129,15 @@ impl FastHashedPassPhrase {
         Ok(())
     }
 
+    pub fn salt_hex(&self) -> String {
+        hex::encode(self.salt)
+    }
+
+    /// Get the hash name and hex value of all the hashes
+    pub fn hash_hex_iter(&self) -> impl Iterator<Item = (&'static str, String)> {
+        self.values.iter().map(|v| (v.name(), v.to_hex()))
+    }
+
     pub fn verify(&self, pass: &str) -> Result<bool, ErrorStack> {
         HASH_TL_BUF.with_borrow_mut(|buf| {
             buf.extend_from_slice(pass.as_bytes());
@@ -152,4 +177,25 @@ mod tests {
 
         assert!(p.verify("IQ5ZhanWaop2cw").unwrap());
     }
+
+    #[test]
+    fn hex_values() {
+        let mut p = FastHashedPassPhrase::new("d950eeffd53f7189").unwrap();
+        p.push_md5("28cb2d22a1148a2c4c43d2c8eab0a202").unwrap();
+        p.push_sha1("0b39e984b59251425245e81241aebf7dbe197cc3")
+            .unwrap();
+
+        assert_eq!(p.salt_hex(), "d950eeffd53f7189");
+        let hashes: Vec<_> = p.hash_hex_iter().collect();
+        assert_eq!(
+            hashes,
+            vec![
+                ("md5", "28cb2d22a1148a2c4c43d2c8eab0a202".to_string()),
+                (
+                    "sha1",
+                    "0b39e984b59251425245e81241aebf7dbe197cc3".to_string()
+                ),
+            ]
+        );
+    }
 }
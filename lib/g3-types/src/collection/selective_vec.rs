@@ -6,7 +6,7 @@
 use std::cmp::Ordering;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::str::FromStr;
-use std::sync::atomic;
+use std::sync::{Mutex, atomic};
 
 use foldhash::fast::FixedState;
 use rand::seq::IndexedRandom;
@@ -17,11 +17,26 @@ pub enum SelectivePickPolicy {
     Random,
     Serial,
     RoundRobin,
+    WeightedRoundRobin,
     Ketama,
     Rendezvous,
     JumpHash,
 }
 
+impl SelectivePickPolicy {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            SelectivePickPolicy::Random => "random",
+            SelectivePickPolicy::Serial => "serial",
+            SelectivePickPolicy::RoundRobin => "round_robin",
+            SelectivePickPolicy::WeightedRoundRobin => "weighted_round_robin",
+            SelectivePickPolicy::Ketama => "ketama",
+            SelectivePickPolicy::Rendezvous => "rendezvous",
+            SelectivePickPolicy::JumpHash => "jump_hash",
+        }
+    }
+}
+
 impl FromStr for SelectivePickPolicy {
     type Err = ();
 
@@ -30,6 +45,9 @@ impl FromStr for SelectivePickPolicy {
             "random" => Ok(SelectivePickPolicy::Random),
             "serial" | "sequence" => Ok(SelectivePickPolicy::Serial),
             "roundrobin" | "rr" | "round_robin" => Ok(SelectivePickPolicy::RoundRobin),
+            "weightedroundrobin" | "wrr" | "weighted_round_robin" => {
+                Ok(SelectivePickPolicy::WeightedRoundRobin)
+            }
             "ketama" => Ok(SelectivePickPolicy::Ketama),
             "rendezvous" => Ok(SelectivePickPolicy::Rendezvous),
             "jump" | "jumphash" | "jump_hash" => Ok(SelectivePickPolicy::JumpHash),
@@ -89,12 +107,20 @@ impl<T: SelectiveItem> SelectiveVecBuilder<T> {
         });
 
         let ketama_ring = ketama_ring_create(&nodes);
+        let wrr_total: f64 = nodes.iter().map(|v| v.weight()).sum();
+        let wrr_current = if weighted && wrr_total.is_normal() {
+            Some(Mutex::new(vec![0f64; nodes.len()]))
+        } else {
+            None
+        };
 
         Some(SelectiveVec {
             weighted,
             inner: nodes,
             rr_id: atomic::AtomicUsize::new(0),
             ketama_ring,
+            wrr_total,
+            wrr_current,
         })
     }
 }
@@ -141,6 +167,9 @@ pub struct SelectiveVec<T: SelectiveItem> {
     inner: Vec<T>,
     rr_id: atomic::AtomicUsize,
     ketama_ring: Vec<(usize, u32)>,
+    wrr_total: f64,
+    /// the current weights for smooth weighted round robin, only set if weighted
+    wrr_current: Option<Mutex<Vec<f64>>>,
 }
 
 macro_rules! panic_on_empty {
@@ -236,6 +265,27 @@ impl<T: SelectiveItem> SelectiveVec<T> {
         }
     }
 
+    /// Round robin with weights considered, the nodes will be picked in a smooth way.
+    ///
+    /// This is the smooth weighted round robin algorithm used in nginx.
+    /// It is the same as `pick_round_robin` if all nodes have the same weight.
+    pub fn pick_weighted_round_robin(&self) -> &T {
+        let Some(current) = &self.wrr_current else {
+            return self.pick_round_robin();
+        };
+
+        let mut current = current.lock().unwrap();
+        let mut best = 0;
+        for (i, node) in self.inner.iter().enumerate() {
+            current[i] += node.weight();
+            if current[i] > current[best] {
+                best = i;
+            }
+        }
+        current[best] -= self.wrr_total;
+        &self.inner[best]
+    }
+
     pub fn pick_round_robin_n(&self, n: usize) -> Vec<&T> {
         match self.inner.len() {
             0 => panic_on_empty!(),
@@ -472,6 +522,7 @@ mod tests {
 
         assert!(node.eq(vec.pick_serial()));
         assert!(node.eq(vec.pick_round_robin()));
+        assert!(node.eq(vec.pick_weighted_round_robin()));
         assert!(node.eq(vec.pick_random()));
         assert!(node.eq(vec.pick_rendezvous("k")));
         assert!(node.eq(vec.pick_jump("k")));
@@ -813,4 +864,65 @@ mod tests {
         assert!(r1[0].eq(r2[0]));
         assert!(r1[1].eq(r2[1]));
     }
+
+    #[test]
+    fn pick_weighted_round_robin() {
+        let node1 = Node {
+            name: "node1".to_string(),
+            weight: 1f64,
+        };
+        let node2 = Node {
+            name: "node2".to_string(),
+            weight: 2f64,
+        };
+        let node3 = Node {
+            name: "node3".to_string(),
+            weight: 4f64,
+        };
+
+        let mut builder = SelectiveVecBuilder::with_capacity(3);
+        builder.insert(node1.clone());
+        builder.insert(node2.clone());
+        builder.insert(node3.clone());
+        let vec = builder.build().unwrap();
+
+        let mut picked = Vec::with_capacity(7);
+        for _ in 0..7 {
+            picked.push(vec.pick_weighted_round_robin().name.as_str());
+        }
+        assert_eq!(
+            picked,
+            [
+                "node3", "node2", "node3", "node1", "node3", "node2", "node3"
+            ]
+        );
+        // the next round is the same
+        assert!(node3.eq(vec.pick_weighted_round_robin()));
+        assert!(node2.eq(vec.pick_weighted_round_robin()));
+    }
+
+    #[test]
+    fn pick_weighted_round_robin_fraction() {
+        let node1 = Node {
+            name: "node1".to_string(),
+            weight: 0.3f64,
+        };
+        let node2 = Node {
+            name: "node2".to_string(),
+            weight: 0.7f64,
+        };
+
+        let mut builder = SelectiveVecBuilder::with_capacity(2);
+        builder.insert(node1.clone());
+        builder.insert(node2.clone());
+        let vec = builder.build().unwrap();
+
+        let mut node1_count = 0;
+        for _ in 0..1000 {
+            if node1.eq(vec.pick_weighted_round_robin()) {
+                node1_count += 1;
+            }
+        }
+        assert!((299..=301).contains(&node1_count));
+    }
 }
@@ -11,6 +11,15 @@ pub enum ConfigFileFormat {
     Json,
 }
 
+impl ConfigFileFormat {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ConfigFileFormat::Yaml => "yaml",
+            ConfigFileFormat::Json => "json",
+        }
+    }
+}
+
 impl FromStr for ConfigFileFormat {
     type Err = ();
 
@@ -132,6 +132,11 @@ impl DnsEncryptionConfigBuilder {
         self.tls_config = config_builder;
     }
 
+    #[inline]
+    pub fn tls_client_config(&self) -> &RustlsClientConfigBuilder {
+        &self.tls_config
+    }
+
     pub fn summary(&self) -> String {
         match &self.tls_name {
             ServerName::DnsName(n) => format!("{}({})", self.protocol.as_str(), n.as_ref()),
@@ -22,6 +22,15 @@ pub enum ProxyProtocolVersion {
     V2,
 }
 
+impl ProxyProtocolVersion {
+    pub const fn as_u8(&self) -> u8 {
+        match self {
+            ProxyProtocolVersion::V1 => 1,
+            ProxyProtocolVersion::V2 => 2,
+        }
+    }
+}
+
 impl FromStr for ProxyProtocolVersion {
     type Err = anyhow::Error;
 
@@ -49,6 +49,11 @@ impl HttpForwardCapability {
         self.forward_ftp_del = enable;
     }
 
+    #[inline]
+    pub fn forward_ftp_all(&self) -> bool {
+        self.forward_ftp_all
+    }
+
     pub fn forward_ftp(&self, method: &Method) -> bool {
         match *method {
             Method::GET => self.forward_ftp_get,
@@ -0,0 +1,74 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+/// Config for the pool of HTTP/2 connections to a next hop proxy,
+/// on which CONNECT tunnels are carried as streams
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Http2ConnectPoolConfig {
+    max_connections: usize,
+    max_streams: usize,
+    idle_timeout: Duration,
+    fallback_duration: Duration,
+}
+
+impl Default for Http2ConnectPoolConfig {
+    fn default() -> Self {
+        Http2ConnectPoolConfig {
+            max_connections: 4,
+            max_streams: 100,
+            idle_timeout: Duration::from_secs(60),
+            fallback_duration: Duration::from_secs(300),
+        }
+    }
+}
+
+impl Http2ConnectPoolConfig {
+    /// Set the max number of HTTP/2 connections to each peer
+    #[inline]
+    pub fn set_max_connections(&mut self, count: usize) {
+        self.max_connections = count;
+    }
+
+    #[inline]
+    pub fn max_connections(&self) -> usize {
+        self.max_connections
+    }
+
+    /// Set the max number of concurrent CONNECT streams on each connection,
+    /// the value announced by the peer will be used if it's smaller
+    #[inline]
+    pub fn set_max_streams(&mut self, count: usize) {
+        self.max_streams = count;
+    }
+
+    #[inline]
+    pub fn max_streams(&self) -> usize {
+        self.max_streams
+    }
+
+    /// Set the timeout for connections that have no active streams
+    #[inline]
+    pub fn set_idle_timeout(&mut self, timeout: Duration) {
+        self.idle_timeout = timeout;
+    }
+
+    #[inline]
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// Set how long to use HTTP/1.1 only for a peer after it failed to speak HTTP/2
+    #[inline]
+    pub fn set_fallback_duration(&mut self, duration: Duration) {
+        self.fallback_duration = duration;
+    }
+
+    #[inline]
+    pub fn fallback_duration(&self) -> Duration {
+        self.fallback_duration
+    }
+}
@@ -98,6 +98,16 @@ pub enum HttpForwardedHeaderType {
     Disable,
 }
 
+impl HttpForwardedHeaderType {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            HttpForwardedHeaderType::Classic => "classic",
+            HttpForwardedHeaderType::Standard => "standard",
+            HttpForwardedHeaderType::Disable => "disable",
+        }
+    }
+}
+
 impl FromStr for HttpForwardedHeaderType {
     type Err = ();
 
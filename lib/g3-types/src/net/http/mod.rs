@@ -5,6 +5,7 @@
 
 mod auth;
 mod capability;
+mod h2_connect;
 mod header;
 mod keepalive;
 mod proxy;
@@ -12,6 +13,7 @@ mod upgrade;
 
 pub use auth::{HttpAuth, HttpBasicAuth};
 pub use capability::*;
+pub use h2_connect::Http2ConnectPoolConfig;
 pub use header::*;
 pub use keepalive::HttpKeepAliveConfig;
 pub use proxy::HttpProxySubProtocol;
@@ -32,6 +32,21 @@ impl OpensslCertificatePair {
         !self.leaf_cert.is_empty()
     }
 
+    #[inline]
+    pub fn leaf_cert_der(&self) -> &[u8] {
+        &self.leaf_cert
+    }
+
+    #[inline]
+    pub fn chain_certs_der(&self) -> &[Vec<u8>] {
+        &self.chain_certs
+    }
+
+    #[inline]
+    pub fn private_key_der(&self) -> &[u8] {
+        &self.key
+    }
+
     pub fn set_certificates(&mut self, certs: Vec<X509>) -> anyhow::Result<()> {
         let certs_len = certs.len();
 
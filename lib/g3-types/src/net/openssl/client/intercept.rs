@@ -9,11 +9,11 @@ use anyhow::anyhow;
 use log::warn;
 use openssl::ssl::{Ssl, SslConnector, SslContext, SslContextBuilder, SslMethod, SslVerifyMode};
 use openssl::x509::X509;
-use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::store::{X509Store, X509StoreBuilder};
 
 use super::{
     DEFAULT_HANDSHAKE_TIMEOUT, MINIMAL_HANDSHAKE_TIMEOUT, OpensslClientSessionCache,
-    OpensslSessionCacheConfig,
+    OpensslRevocationConfig, OpensslSessionCacheConfig,
 };
 use crate::net::{TlsAlpn, TlsServerName, TlsVersion, UpstreamAddr};
 
@@ -93,6 +93,7 @@ pub struct OpensslInterceptionClientConfigBuilder {
     session_cache: OpensslSessionCacheConfig,
     supported_groups: String,
     use_ocsp_stapling: bool,
+    revocation: Option<OpensslRevocationConfig>,
     #[cfg(not(libressl))]
     enable_sct: bool,
     #[cfg(any(awslc, boringssl))]
@@ -113,6 +114,7 @@ impl Default for OpensslInterceptionClientConfigBuilder {
             session_cache: OpensslSessionCacheConfig::new_for_many(),
             supported_groups: String::default(),
             use_ocsp_stapling: false,
+            revocation: None,
             #[cfg(not(libressl))]
             enable_sct: false,
             #[cfg(any(awslc, boringssl))]
@@ -186,6 +188,11 @@ impl OpensslInterceptionClientConfigBuilder {
         self.use_ocsp_stapling = enable;
     }
 
+    #[inline]
+    pub fn set_revocation_config(&mut self, config: OpensslRevocationConfig) {
+        self.revocation = Some(config);
+    }
+
     #[inline]
     #[cfg(not(libressl))]
     pub fn set_enable_sct(&mut self, enable: bool) {
@@ -223,6 +230,108 @@ impl OpensslInterceptionClientConfigBuilder {
         self.insecure = enable;
     }
 
+    #[inline]
+    pub fn min_tls_version(&self) -> Option<TlsVersion> {
+        self.min_tls_version
+    }
+
+    #[inline]
+    pub fn max_tls_version(&self) -> Option<TlsVersion> {
+        self.max_tls_version
+    }
+
+    /// Get the DER encoded ca certificates
+    #[inline]
+    pub fn ca_certificates(&self) -> &[Vec<u8>] {
+        &self.ca_certs
+    }
+
+    #[inline]
+    pub fn no_default_ca_certificates(&self) -> bool {
+        self.no_default_ca_certs
+    }
+
+    #[inline]
+    pub fn handshake_timeout(&self) -> Duration {
+        self.handshake_timeout
+    }
+
+    #[inline]
+    pub fn no_session_cache(&self) -> bool {
+        self.session_cache.disabled()
+    }
+
+    #[inline]
+    pub fn use_builtin_session_cache(&self) -> bool {
+        self.session_cache.use_builtin()
+    }
+
+    #[inline]
+    pub fn session_cache_sites_count(&self) -> usize {
+        self.session_cache.sites_count()
+    }
+
+    #[inline]
+    pub fn session_cache_each_capacity(&self) -> usize {
+        self.session_cache.each_capacity()
+    }
+
+    #[inline]
+    pub fn supported_groups(&self) -> &str {
+        &self.supported_groups
+    }
+
+    #[inline]
+    pub fn use_ocsp_stapling(&self) -> bool {
+        self.use_ocsp_stapling
+    }
+
+    #[inline]
+    pub fn revocation_config(&self) -> Option<&OpensslRevocationConfig> {
+        self.revocation.as_ref()
+    }
+
+    #[cfg(not(libressl))]
+    #[inline]
+    pub fn enable_sct(&self) -> bool {
+        self.enable_sct
+    }
+
+    #[cfg(libressl)]
+    #[inline]
+    pub fn enable_sct(&self) -> bool {
+        false
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    #[inline]
+    pub fn enable_grease(&self) -> bool {
+        self.enable_grease
+    }
+
+    #[cfg(not(any(awslc, boringssl)))]
+    #[inline]
+    pub fn enable_grease(&self) -> bool {
+        false
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    #[inline]
+    pub fn permute_extensions(&self) -> bool {
+        self.permute_extensions
+    }
+
+    #[cfg(not(any(awslc, boringssl)))]
+    #[inline]
+    pub fn permute_extensions(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn insecure(&self) -> bool {
+        self.insecure
+    }
+
     fn set_verify(&self, builder: &mut SslContextBuilder) {
         if self.insecure {
             warn!(
@@ -248,10 +357,7 @@ impl OpensslInterceptionClientConfigBuilder {
         Ok(())
     }
 
-    fn build_set_verify_cert_store(
-        &self,
-        ctx_builder: &mut SslContextBuilder,
-    ) -> anyhow::Result<()> {
+    fn build_verify_cert_store(&self) -> anyhow::Result<X509Store> {
         let mut store_builder = X509StoreBuilder::new()
             .map_err(|e| anyhow!("failed to create ca cert store builder: {e}"))?;
         if !self.no_default_ca_certs {
@@ -265,12 +371,25 @@ impl OpensslInterceptionClientConfigBuilder {
                 .add_cert(ca_cert)
                 .map_err(|e| anyhow!("failed to add ca certificate #{i}: {e}"))?;
         }
+        Ok(store_builder.build())
+    }
+
+    fn build_set_verify_cert_store(
+        &self,
+        ctx_builder: &mut SslContextBuilder,
+    ) -> anyhow::Result<()> {
+        let store = self.build_verify_cert_store()?;
+        if let Some(config) = self.revocation.as_ref().filter(|_| !self.insecure) {
+            // the cert store will be shared with the revocation checker
+            ctx_builder.set_cert_store(store);
+            return config.set_for_client(ctx_builder);
+        }
         #[cfg(not(libressl))]
         ctx_builder
-            .set_verify_cert_store(store_builder.build())
+            .set_verify_cert_store(store)
             .map_err(|e| anyhow!("failed to set verify ca certs: {e}"))?;
         #[cfg(libressl)]
-        ctx_builder.set_cert_store(store_builder.build());
+        ctx_builder.set_cert_store(store);
         Ok(())
     }
 
@@ -311,7 +430,6 @@ impl OpensslInterceptionClientConfigBuilder {
 
         if self.use_ocsp_stapling {
             ctx_builder.enable_ocsp_stapling();
-            // TODO check OCSP response
         }
 
         if self.enable_sct {
@@ -359,7 +477,6 @@ impl OpensslInterceptionClientConfigBuilder {
             ctx_builder
                 .set_status_type(StatusType::OCSP)
                 .map_err(|e| anyhow!("failed to enable OCSP status request: {e}"))?;
-            // TODO check OCSP response
         }
 
         self.build_set_verify_cert_store(&mut ctx_builder)?;
@@ -393,7 +510,6 @@ impl OpensslInterceptionClientConfigBuilder {
             ctx_builder
                 .set_status_type(StatusType::OCSP)
                 .map_err(|e| anyhow!("failed to enable OCSP status request: {e}"))?;
-            // TODO check OCSP response
         }
 
         if self.enable_sct {
@@ -434,7 +550,6 @@ impl OpensslInterceptionClientConfigBuilder {
             ctx_builder
                 .set_status_type(StatusType::OCSP)
                 .map_err(|e| anyhow!("failed to enable OCSP status request: {e}"))?;
-            // TODO check OCSP response
         }
 
         if self.enable_sct {
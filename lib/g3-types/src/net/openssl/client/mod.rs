@@ -17,9 +17,11 @@ use openssl::ssl::{
     Ssl, SslConnector, SslConnectorBuilder, SslContext, SslMethod, SslVerifyMode, SslVersion,
 };
 use openssl::x509::X509;
-use openssl::x509::store::X509StoreBuilder;
+use openssl::x509::store::{X509Store, X509StoreBuilder};
 
-use super::{OpensslCertificatePair, OpensslProtocol, OpensslTlcpCertificatePair};
+use super::{
+    OpensslCertificatePair, OpensslProtocol, OpensslRevocationConfig, OpensslTlcpCertificatePair,
+};
 use crate::net::tls::AlpnProtocol;
 use crate::net::{Host, TlsAlpn, TlsServerName, TlsVersion, UpstreamAddr};
 
@@ -109,6 +111,7 @@ pub struct OpensslClientConfigBuilder {
     session_cache: OpensslSessionCacheConfig,
     supported_groups: String,
     use_ocsp_stapling: bool,
+    revocation: Option<OpensslRevocationConfig>,
     #[cfg(not(libressl))]
     enable_sct: bool,
     #[cfg(any(awslc, boringssl))]
@@ -134,6 +137,7 @@ impl Default for OpensslClientConfigBuilder {
             session_cache: OpensslSessionCacheConfig::default(),
             supported_groups: String::default(),
             use_ocsp_stapling: false,
+            revocation: None,
             #[cfg(not(libressl))]
             enable_sct: false,
             #[cfg(any(awslc, boringssl))]
@@ -266,6 +270,11 @@ impl OpensslClientConfigBuilder {
         self.use_ocsp_stapling = enable;
     }
 
+    #[inline]
+    pub fn set_revocation_config(&mut self, config: OpensslRevocationConfig) {
+        self.revocation = Some(config);
+    }
+
     #[inline]
     #[cfg(not(libressl))]
     pub fn set_enable_sct(&mut self, enable: bool) {
@@ -303,6 +312,133 @@ impl OpensslClientConfigBuilder {
         self.insecure = enable;
     }
 
+    #[inline]
+    pub fn min_tls_version(&self) -> Option<TlsVersion> {
+        self.min_tls_version
+    }
+
+    #[inline]
+    pub fn max_tls_version(&self) -> Option<TlsVersion> {
+        self.max_tls_version
+    }
+
+    /// Get the DER encoded ca certificates
+    #[inline]
+    pub fn ca_certificates(&self) -> &[Vec<u8>] {
+        &self.ca_certs
+    }
+
+    #[inline]
+    pub fn no_default_ca_certificates(&self) -> bool {
+        self.no_default_ca_certs
+    }
+
+    #[inline]
+    pub fn handshake_timeout(&self) -> Duration {
+        self.handshake_timeout
+    }
+
+    #[inline]
+    pub fn no_session_cache(&self) -> bool {
+        self.session_cache.disabled()
+    }
+
+    #[inline]
+    pub fn use_builtin_session_cache(&self) -> bool {
+        self.session_cache.use_builtin()
+    }
+
+    #[inline]
+    pub fn session_cache_sites_count(&self) -> usize {
+        self.session_cache.sites_count()
+    }
+
+    #[inline]
+    pub fn session_cache_each_capacity(&self) -> usize {
+        self.session_cache.each_capacity()
+    }
+
+    #[inline]
+    pub fn supported_groups(&self) -> &str {
+        &self.supported_groups
+    }
+
+    #[inline]
+    pub fn use_ocsp_stapling(&self) -> bool {
+        self.use_ocsp_stapling
+    }
+
+    #[inline]
+    pub fn revocation_config(&self) -> Option<&OpensslRevocationConfig> {
+        self.revocation.as_ref()
+    }
+
+    #[cfg(not(libressl))]
+    #[inline]
+    pub fn enable_sct(&self) -> bool {
+        self.enable_sct
+    }
+
+    #[cfg(libressl)]
+    #[inline]
+    pub fn enable_sct(&self) -> bool {
+        false
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    #[inline]
+    pub fn enable_grease(&self) -> bool {
+        self.enable_grease
+    }
+
+    #[cfg(not(any(awslc, boringssl)))]
+    #[inline]
+    pub fn enable_grease(&self) -> bool {
+        false
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    #[inline]
+    pub fn permute_extensions(&self) -> bool {
+        self.permute_extensions
+    }
+
+    #[cfg(not(any(awslc, boringssl)))]
+    #[inline]
+    pub fn permute_extensions(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    pub fn insecure(&self) -> bool {
+        self.insecure
+    }
+
+    #[inline]
+    pub fn protocol(&self) -> Option<OpensslProtocol> {
+        self.protocol
+    }
+
+    #[inline]
+    pub fn ciphers(&self) -> &[String] {
+        &self.ciphers
+    }
+
+    #[inline]
+    pub fn disable_sni(&self) -> bool {
+        self.disable_sni
+    }
+
+    #[inline]
+    pub fn cert_pair(&self) -> Option<&OpensslCertificatePair> {
+        self.client_cert_pair.as_ref()
+    }
+
+    #[inline]
+    pub fn tlcp_cert_pair(&self) -> Option<&OpensslTlcpCertificatePair> {
+        self.client_tlcp_cert_pair.as_ref()
+    }
+
     fn set_verify(&self, builder: &mut SslConnectorBuilder) {
         if self.insecure {
             warn!(
@@ -314,6 +450,23 @@ impl OpensslClientConfigBuilder {
         }
     }
 
+    fn build_verify_cert_store(&self) -> anyhow::Result<X509Store> {
+        let mut store_builder = X509StoreBuilder::new()
+            .map_err(|e| anyhow!("failed to create ca cert store builder: {e}"))?;
+        if !self.no_default_ca_certs {
+            store_builder
+                .set_default_paths()
+                .map_err(|e| anyhow!("failed to load default ca certs: {e}"))?;
+        }
+        for (i, cert) in self.ca_certs.iter().enumerate() {
+            let ca_cert = X509::from_der(cert.as_slice()).unwrap();
+            store_builder
+                .add_cert(ca_cert)
+                .map_err(|e| anyhow!("failed to add ca certificate #{i}: {e}"))?;
+        }
+        Ok(store_builder.build())
+    }
+
     #[cfg(tongsuo)]
     fn new_tlcp_builder(&self) -> anyhow::Result<SslConnectorBuilder> {
         let mut ctx_builder = SslConnector::builder(SslMethod::ntls_client())
@@ -487,7 +640,6 @@ impl OpensslClientConfigBuilder {
                 .map_err(|e| anyhow!("failed to enable OCSP status request: {e}"))?;
             #[cfg(any(awslc, boringssl))]
             ctx_builder.enable_ocsp_stapling();
-            // TODO check OCSP response
         }
 
         #[cfg(not(libressl))]
@@ -521,25 +673,22 @@ impl OpensslClientConfigBuilder {
             })
             .map_err(|e| anyhow!("failed to set cert decompression algorithm: {e}"))?;
 
-        let mut store_builder = X509StoreBuilder::new()
-            .map_err(|e| anyhow!("failed to create ca cert store builder: {e}"))?;
-        if !self.no_default_ca_certs {
-            store_builder
-                .set_default_paths()
-                .map_err(|e| anyhow!("failed to load default ca certs: {e}"))?;
-        }
-        for (i, cert) in self.ca_certs.iter().enumerate() {
-            let ca_cert = X509::from_der(cert.as_slice()).unwrap();
-            store_builder
-                .add_cert(ca_cert)
-                .map_err(|e| anyhow!("failed to add ca certificate #{i}: {e}"))?;
+        let store = self.build_verify_cert_store()?;
+        match self.revocation.as_ref().filter(|_| !self.insecure) {
+            Some(config) => {
+                // the cert store will be shared with the revocation checker
+                ctx_builder.set_cert_store(store);
+                config.set_for_client(&mut ctx_builder)?;
+            }
+            None => {
+                #[cfg(not(libressl))]
+                ctx_builder
+                    .set_verify_cert_store(store)
+                    .map_err(|e| anyhow!("failed to set verify ca certs: {e}"))?;
+                #[cfg(libressl)]
+                ctx_builder.set_cert_store(store);
+            }
         }
-        #[cfg(not(libressl))]
-        ctx_builder
-            .set_verify_cert_store(store_builder.build())
-            .map_err(|e| anyhow!("failed to set verify ca certs: {e}"))?;
-        #[cfg(libressl)]
-        ctx_builder.set_cert_store(store_builder.build());
 
         let session_cache = self.session_cache.set_for_client(&mut ctx_builder)?;
 
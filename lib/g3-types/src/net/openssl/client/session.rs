@@ -57,6 +57,22 @@ impl OpensslSessionCacheConfig {
         }
     }
 
+    pub(in crate::net::openssl) fn disabled(&self) -> bool {
+        self.method == OpensslSessionCacheMethod::Off
+    }
+
+    pub(in crate::net::openssl) fn use_builtin(&self) -> bool {
+        self.method == OpensslSessionCacheMethod::Builtin
+    }
+
+    pub(in crate::net::openssl) fn sites_count(&self) -> usize {
+        self.sites_count.get()
+    }
+
+    pub(in crate::net::openssl) fn each_capacity(&self) -> usize {
+        self.each_capacity.get()
+    }
+
     pub(in crate::net::openssl) fn set_no_session_cache(&mut self) {
         self.method = OpensslSessionCacheMethod::Off;
     }
@@ -16,6 +16,11 @@ pub use server::{
     OpensslTicketKey, OpensslTicketKeyBuilder,
 };
 
+mod revocation;
+#[cfg(feature = "test-util")]
+pub use revocation::test_util as revocation_test_util;
+pub use revocation::{OpensslOcspStaple, OpensslRevocationConfig, OpensslRevocationMode};
+
 mod cert_pair;
 pub use cert_pair::OpensslCertificatePair;
 
@@ -18,6 +18,20 @@ pub enum OpensslProtocol {
     Tlcp11,
 }
 
+impl OpensslProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpensslProtocol::Ssl3 => "ssl3",
+            OpensslProtocol::Tls1 => "tls1",
+            OpensslProtocol::Tls11 => "tls1.1",
+            OpensslProtocol::Tls12 => "tls1.2",
+            OpensslProtocol::Tls13 => "tls1.3",
+            #[cfg(tongsuo)]
+            OpensslProtocol::Tlcp11 => "tlcp1.1",
+        }
+    }
+}
+
 impl FromStr for OpensslProtocol {
     type Err = anyhow::Error;
 
@@ -0,0 +1,509 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::io;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use log::{debug, warn};
+use lru::LruCache;
+use openssl::asn1::Asn1Time;
+use openssl::hash::MessageDigest;
+use openssl::ocsp::{OcspCertId, OcspCertStatus, OcspFlag, OcspResponse, OcspResponseStatus};
+use openssl::ssl::{NameType, SslRef};
+use openssl::stack::StackRef;
+use openssl::x509::store::X509StoreRef;
+use openssl::x509::{CrlStatus, X509, X509Crl, X509Ref, X509VerifyResult};
+use tokio::sync::watch;
+
+use g3_openssl::SslPostHandshakeExt;
+
+use super::fetch::FetchJob;
+use super::{OCSP_VALIDITY_LEEWAY, OpensslRevocationConfig, OpensslRevocationMode};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum RevocationStatus {
+    Good,
+    Revoked,
+    Unknown,
+}
+
+impl RevocationStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RevocationStatus::Good => "good",
+            RevocationStatus::Revoked => "revoked",
+            RevocationStatus::Unknown => "unknown",
+        }
+    }
+}
+
+struct CachedStatus {
+    status: RevocationStatus,
+    expire: Instant,
+}
+
+/// A fetch in progress, which is shared by all handshakes with the same certificate
+pub(super) struct PendingStatus(watch::Receiver<Option<RevocationStatus>>);
+
+impl PendingStatus {
+    async fn wait(mut self) -> RevocationStatus {
+        match self.0.wait_for(|v| v.is_some()).await {
+            Ok(v) => v.unwrap_or(RevocationStatus::Unknown),
+            Err(_) => RevocationStatus::Unknown,
+        }
+    }
+}
+
+enum StatusCheck {
+    Ready(RevocationStatus),
+    Pending(PendingStatus),
+}
+
+pub(super) struct OpensslRevocationChecker {
+    mode: OpensslRevocationMode,
+    crl_list: Vec<X509Crl>,
+    pub(super) fetch_ocsp: bool,
+    pub(super) fetch_crl: bool,
+    pub(super) fetch_timeout: Duration,
+    cache_ttl: Duration,
+    cache: Mutex<LruCache<Vec<u8>, CachedStatus>>,
+    pending: Mutex<HashMap<Vec<u8>, watch::Receiver<Option<RevocationStatus>>>>,
+}
+
+impl OpensslRevocationChecker {
+    pub(super) fn new(config: &OpensslRevocationConfig, crl_list: Vec<X509Crl>) -> Self {
+        let capacity = NonZeroUsize::new(config.cache_capacity).unwrap_or(NonZeroUsize::MIN);
+        OpensslRevocationChecker {
+            mode: config.mode,
+            crl_list,
+            fetch_ocsp: config.fetch_ocsp,
+            fetch_crl: config.fetch_crl,
+            fetch_timeout: config.fetch_timeout,
+            cache_ttl: config.cache_ttl,
+            cache: Mutex::new(LruCache::new(capacity)),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check the peer certificate in the OCSP status callback.
+    /// Return false if the handshake should be terminated.
+    pub(super) fn check(self: &Arc<Self>, ssl: &mut SslRef) -> bool {
+        let server_name = ssl
+            .servername(NameType::HOST_NAME)
+            .unwrap_or_default()
+            .to_string();
+        match self.get_status(ssl) {
+            StatusCheck::Ready(status) => self.accept(status, &server_name),
+            StatusCheck::Pending(pending) => {
+                // never block the handshake callback, the connector will wait for the status
+                // after the handshake, and the connection will be dropped if not accepted
+                let checker = self.clone();
+                let r = ssl.set_post_handshake_check(async move {
+                    let status = pending.wait().await;
+                    if checker.accept(status, &server_name) {
+                        Ok(())
+                    } else {
+                        Err(io::Error::other(format!(
+                            "upstream certificate revocation status is {}",
+                            status.as_str()
+                        )))
+                    }
+                });
+                if let Err(e) = r {
+                    warn!("failed to set post handshake certificate revocation check: {e}");
+                    return false;
+                }
+                true
+            }
+        }
+    }
+
+    fn accept(&self, status: RevocationStatus, server_name: &str) -> bool {
+        match status {
+            RevocationStatus::Good => {}
+            RevocationStatus::Revoked => {
+                if self.mode == OpensslRevocationMode::LogOnly {
+                    warn!("upstream certificate for server name '{server_name}' has been revoked");
+                } else {
+                    debug!("upstream certificate for server name '{server_name}' has been revoked");
+                }
+            }
+            RevocationStatus::Unknown => {
+                debug!("no revocation status found for server name '{server_name}'")
+            }
+        }
+        accept_status(self.mode, status)
+    }
+
+    fn get_status(self: &Arc<Self>, ssl: &SslRef) -> StatusCheck {
+        let Some(leaf) = ssl.peer_certificate() else {
+            return StatusCheck::Ready(RevocationStatus::Unknown);
+        };
+        let Some(chain) = ssl.peer_cert_chain() else {
+            return StatusCheck::Ready(RevocationStatus::Unknown);
+        };
+        let Some(issuer) = chain
+            .iter()
+            .find(|c| c.issued(&leaf) == X509VerifyResult::OK)
+        else {
+            return StatusCheck::Ready(RevocationStatus::Unknown);
+        };
+        let Ok(key) = leaf.digest(MessageDigest::sha256()) else {
+            return StatusCheck::Ready(RevocationStatus::Unknown);
+        };
+        let key = key.to_vec();
+
+        let store = ssl.ssl_context().cert_store();
+        if let Some(data) = ssl.ocsp_status() {
+            let status = self.check_ocsp_response(data, &leaf, issuer, chain, store);
+            if status != RevocationStatus::Unknown {
+                self.cache_status(key, status, self.cache_ttl);
+                return StatusCheck::Ready(status);
+            }
+        }
+
+        // an unknown status in cache means that a fetch is in progress or has failed
+        let cached = self.cached_status(&key);
+        match cached {
+            Some(RevocationStatus::Unknown) | None => {}
+            Some(status) => return StatusCheck::Ready(status),
+        }
+
+        for crl in &self.crl_list {
+            if let Some(status) = check_crl(crl, &leaf, issuer) {
+                self.cache_status(key, status, self.cache_ttl);
+                return StatusCheck::Ready(status);
+            }
+        }
+
+        if cached.is_some() || !(self.fetch_ocsp || self.fetch_crl) {
+            return StatusCheck::Ready(RevocationStatus::Unknown);
+        }
+
+        let mut job = FetchJob::new(
+            self.clone(),
+            ssl.ssl_context().to_owned(),
+            key.clone(),
+            leaf.clone(),
+            issuer.to_owned(),
+            chain.iter().map(|c| c.to_owned()).collect(),
+        );
+        if self.mode == OpensslRevocationMode::HardFail {
+            // the handshake will fail if we don't wait for the status
+            let mut pending = self.pending.lock().unwrap();
+            // the sender will be dropped if the fetch job is not able to run
+            if let Some(receiver) = pending.get(&key).filter(|r| r.has_changed().is_ok()) {
+                return StatusCheck::Pending(PendingStatus(receiver.clone()));
+            }
+            let (sender, receiver) = watch::channel(None);
+            pending.insert(key, receiver.clone());
+            drop(pending);
+            job.set_notify(sender);
+            job.spawn();
+            StatusCheck::Pending(PendingStatus(receiver))
+        } else {
+            self.cache_status(key, RevocationStatus::Unknown, self.fetch_timeout * 2);
+            job.spawn();
+            StatusCheck::Ready(RevocationStatus::Unknown)
+        }
+    }
+
+    pub(super) fn remove_pending(&self, key: &[u8]) {
+        let mut pending = self.pending.lock().unwrap();
+        pending.remove(key);
+    }
+
+    pub(super) fn check_ocsp_response(
+        &self,
+        data: &[u8],
+        leaf: &X509Ref,
+        issuer: &X509Ref,
+        chain: &StackRef<X509>,
+        store: &X509StoreRef,
+    ) -> RevocationStatus {
+        let Ok(rsp) = OcspResponse::from_der(data) else {
+            return RevocationStatus::Unknown;
+        };
+        if rsp.status() != OcspResponseStatus::SUCCESSFUL {
+            return RevocationStatus::Unknown;
+        }
+        let Ok(basic) = rsp.basic() else {
+            return RevocationStatus::Unknown;
+        };
+        if basic.verify(chain, store, OcspFlag::empty()).is_err() {
+            return RevocationStatus::Unknown;
+        }
+        let Ok(id) = OcspCertId::from_cert(MessageDigest::sha1(), leaf, issuer) else {
+            return RevocationStatus::Unknown;
+        };
+        let Some(status) = basic.find_status(&id) else {
+            return RevocationStatus::Unknown;
+        };
+        if status.check_validity(OCSP_VALIDITY_LEEWAY, None).is_err() {
+            return RevocationStatus::Unknown;
+        }
+        match status.status {
+            OcspCertStatus::GOOD => RevocationStatus::Good,
+            OcspCertStatus::REVOKED => RevocationStatus::Revoked,
+            _ => RevocationStatus::Unknown,
+        }
+    }
+
+    fn cached_status(&self, key: &[u8]) -> Option<RevocationStatus> {
+        let mut cache = self.cache.lock().unwrap();
+        let v = cache.get(key)?;
+        if v.expire > Instant::now() {
+            Some(v.status)
+        } else {
+            cache.pop(key);
+            None
+        }
+    }
+
+    pub(super) fn cache_status(&self, key: Vec<u8>, status: RevocationStatus, ttl: Duration) {
+        let Some(expire) = Instant::now().checked_add(ttl) else {
+            return;
+        };
+        debug!("cache certificate revocation status {}", status.as_str());
+        let mut cache = self.cache.lock().unwrap();
+        cache.put(key, CachedStatus { status, expire });
+    }
+
+    #[inline]
+    pub(super) fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+}
+
+/// Return false if the handshake should be terminated
+fn accept_status(mode: OpensslRevocationMode, status: RevocationStatus) -> bool {
+    match (status, mode) {
+        (RevocationStatus::Good, _) => true,
+        (RevocationStatus::Revoked, OpensslRevocationMode::LogOnly) => true,
+        (RevocationStatus::Revoked, _) => false,
+        (RevocationStatus::Unknown, OpensslRevocationMode::HardFail) => false,
+        (RevocationStatus::Unknown, _) => true,
+    }
+}
+
+/// Check the certificate status in the CRL, None will be returned if the CRL is not usable
+pub(super) fn check_crl(crl: &X509Crl, leaf: &X509, issuer: &X509Ref) -> Option<RevocationStatus> {
+    let crl_issuer = crl.issuer_name().to_der().ok()?;
+    let cert_issuer = issuer.subject_name().to_der().ok()?;
+    if crl_issuer != cert_issuer {
+        return None;
+    }
+    let key = issuer.public_key().ok()?;
+    if !crl.verify(&key).ok()? {
+        return None;
+    }
+    if let Some(next_update) = crl.next_update() {
+        let now = Asn1Time::days_from_now(0).ok()?;
+        if next_update < now {
+            return None;
+        }
+    }
+
+    match crl.get_by_cert(leaf) {
+        CrlStatus::NotRevoked | CrlStatus::RemoveFromCrl(_) => Some(RevocationStatus::Good),
+        CrlStatus::Revoked(_) => Some(RevocationStatus::Revoked),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use g3_openssl::SslConnector;
+    use openssl::asn1::{Asn1Object, Asn1OctetString};
+    use openssl::bn::BigNum;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::nid::Nid;
+    use openssl::pkey::{PKey, Private};
+    use openssl::ssl::{SslAcceptor, SslMethod};
+    use openssl::x509::extension::{BasicConstraints, SubjectAlternativeName};
+    use openssl::x509::{X509Builder, X509Extension, X509NameBuilder};
+    use tokio::net::TcpStream;
+
+    use crate::net::{Host, OpensslClientConfigBuilder};
+
+    #[test]
+    fn accept() {
+        use OpensslRevocationMode::*;
+        use RevocationStatus::*;
+
+        for mode in [LogOnly, SoftFail, HardFail] {
+            assert!(accept_status(mode, Good));
+        }
+        assert!(accept_status(LogOnly, Revoked));
+        assert!(!accept_status(SoftFail, Revoked));
+        assert!(!accept_status(HardFail, Revoked));
+        assert!(accept_status(LogOnly, Unknown));
+        assert!(accept_status(SoftFail, Unknown));
+        assert!(!accept_status(HardFail, Unknown));
+    }
+
+    #[test]
+    fn cache() {
+        let mut config = OpensslRevocationConfig::default();
+        config.set_cache_capacity(1);
+        let checker = OpensslRevocationChecker::new(&config, Vec::new());
+
+        checker.cache_status(
+            b"a".to_vec(),
+            RevocationStatus::Good,
+            Duration::from_secs(60),
+        );
+        assert_eq!(checker.cached_status(b"a"), Some(RevocationStatus::Good));
+
+        // the least recently used one will be evicted
+        checker.cache_status(
+            b"b".to_vec(),
+            RevocationStatus::Revoked,
+            Duration::from_secs(60),
+        );
+        assert_eq!(checker.cached_status(b"a"), None);
+        assert_eq!(checker.cached_status(b"b"), Some(RevocationStatus::Revoked));
+
+        checker.cache_status(b"c".to_vec(), RevocationStatus::Unknown, Duration::ZERO);
+        assert_eq!(checker.cached_status(b"c"), None);
+    }
+
+    fn build_cert(
+        subject: &str,
+        key: &PKey<Private>,
+        issuer: Option<(&X509, &PKey<Private>)>,
+        ocsp_url: &str,
+    ) -> X509 {
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", subject).unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        let serial = BigNum::from_u32(rand::random::<u16>() as u32 + 1).unwrap();
+        builder
+            .set_serial_number(&serial.to_asn1_integer().unwrap())
+            .unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.set_pubkey(key).unwrap();
+        let (issuer_name, sign_key) = match issuer {
+            Some((cert, key)) => (cert.subject_name(), key),
+            None => {
+                builder
+                    .append_extension(BasicConstraints::new().critical().ca().build().unwrap())
+                    .unwrap();
+                (name.as_ref(), key)
+            }
+        };
+        builder.set_issuer_name(issuer_name).unwrap();
+        if issuer.is_some() {
+            let san = SubjectAlternativeName::new()
+                .dns(subject)
+                .build(&builder.x509v3_context(None, None))
+                .unwrap();
+            builder.append_extension(san).unwrap();
+
+            // AuthorityInfoAccess with a single OCSP responder url
+            let url = ocsp_url.as_bytes();
+            let mut access = vec![0x06, 0x08, 0x2b, 0x06, 0x01, 0x05, 0x05, 0x07, 0x30, 0x01];
+            access.extend_from_slice(&[0x86, url.len() as u8]);
+            access.extend_from_slice(url);
+            let mut der = vec![0x30, access.len() as u8 + 2, 0x30, access.len() as u8];
+            der.extend_from_slice(&access);
+            let oid = Asn1Object::from_str("1.3.6.1.5.5.7.1.1").unwrap();
+            let value = Asn1OctetString::new_from_bytes(&der).unwrap();
+            let aia = X509Extension::new_from_der(&oid, false, &value).unwrap();
+            builder.append_extension(aia).unwrap();
+        }
+        builder.sign(sign_key, MessageDigest::sha256()).unwrap();
+        builder.build()
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn hard_fail_slow_responder() {
+        // an OCSP responder which never responds
+        let responder = TcpListener::bind("127.0.0.1:0").unwrap();
+        let ocsp_url = format!("http://{}/ocsp", responder.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (_stream, _) = responder.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(4));
+        });
+
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let ca_key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let ca_cert = build_cert("g3 test ca", &ca_key, None, &ocsp_url);
+        let leaf_key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let leaf_cert = build_cert(
+            "test.example.net",
+            &leaf_key,
+            Some((&ca_cert, &ca_key)),
+            &ocsp_url,
+        );
+
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let mut acceptor = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).unwrap();
+        acceptor.set_private_key(&leaf_key).unwrap();
+        acceptor.set_certificate(&leaf_cert).unwrap();
+        acceptor.add_extra_chain_cert(ca_cert.clone()).unwrap();
+        let acceptor = acceptor.build();
+        std::thread::spawn(move || {
+            let (stream, _) = server.accept().unwrap();
+            if let Ok(mut stream) = acceptor.accept(stream) {
+                let mut buf = [0u8; 16];
+                let _ = stream.read(&mut buf);
+            }
+        });
+
+        let mut revocation = OpensslRevocationConfig::default();
+        revocation.set_mode(OpensslRevocationMode::HardFail);
+        revocation.set_fetch_ocsp(true);
+        revocation.set_fetch_timeout(Duration::from_secs(1));
+        let mut builder = OpensslClientConfigBuilder::with_cache_for_one_site();
+        builder.set_ca_certificates(vec![ca_cert]).unwrap();
+        builder.set_no_default_ca_certificates();
+        builder.set_revocation_config(revocation);
+        let client_config = builder.build().unwrap();
+        let ssl = client_config
+            .build_ssl(&Host::from_str("test.example.net").unwrap(), 443)
+            .unwrap();
+
+        // other tasks on the same runtime should keep running while waiting for the responder
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticks_c = ticks.clone();
+        let ticker = tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                ticks_c.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        let time_start = Instant::now();
+        let stream = TcpStream::connect(server_addr).await.unwrap();
+        let r = SslConnector::new(ssl, stream).unwrap().connect().await;
+        let elapsed = time_start.elapsed();
+        ticker.abort();
+
+        // the status is unknown when the fetch timed out
+        assert!(r.is_err());
+        assert!(elapsed >= Duration::from_millis(900));
+        assert!(elapsed < Duration::from_secs(3));
+        assert!(ticks.load(Ordering::Relaxed) >= 10);
+    }
+}
@@ -0,0 +1,436 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use log::{debug, warn};
+use openssl::hash::MessageDigest;
+use openssl::ocsp::{OcspCertId, OcspRequest};
+use openssl::ssl::SslContext;
+use openssl::stack::Stack;
+use openssl::x509::{X509, X509Crl, X509Ref};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::runtime::Handle;
+use tokio::sync::watch;
+use url::Url;
+
+use super::checker::{OpensslRevocationChecker, RevocationStatus, check_crl};
+
+const MAX_RESPONSE_SIZE: u64 = 16 << 20; // 16MB, large enough for most CRLs
+const FAILED_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+static FETCH_RUNTIME: OnceLock<Option<Handle>> = OnceLock::new();
+
+/// Get the runtime for all fetch tasks, which is running in its own thread,
+/// so it's safe to wait for the tasks in any other runtime
+fn fetch_runtime() -> Option<&'static Handle> {
+    FETCH_RUNTIME
+        .get_or_init(|| {
+            let rt = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(rt) => rt,
+                Err(e) => {
+                    warn!("failed to create certificate revocation fetch runtime: {e}");
+                    return None;
+                }
+            };
+            let handle = rt.handle().clone();
+            match std::thread::Builder::new()
+                .name("revocation-fetch".to_string())
+                .spawn(move || rt.block_on(std::future::pending::<()>()))
+            {
+                Ok(_) => Some(handle),
+                Err(e) => {
+                    warn!("failed to spawn certificate revocation fetch thread: {e}");
+                    None
+                }
+            }
+        })
+        .as_ref()
+}
+
+/// Run the future in the fetch runtime and wait for the result, at most `timeout`.
+/// None will be returned if timed out.
+///
+/// This is a blocking call, and it should not be called in async worker threads.
+pub(super) fn block_on_fetch<F>(future: F, timeout: Duration) -> Option<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let rt = fetch_runtime()?;
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+    rt.spawn(async move {
+        if let Ok(v) = tokio::time::timeout(timeout, future).await {
+            let _ = sender.send(v);
+        }
+    });
+    receiver.recv_timeout(timeout).ok()
+}
+
+pub(super) struct FetchJob {
+    checker: Arc<OpensslRevocationChecker>,
+    ssl_context: SslContext,
+    key: Vec<u8>,
+    leaf: X509,
+    issuer: X509,
+    chain: Vec<X509>,
+    notify: Option<watch::Sender<Option<RevocationStatus>>>,
+}
+
+impl FetchJob {
+    pub(super) fn new(
+        checker: Arc<OpensslRevocationChecker>,
+        ssl_context: SslContext,
+        key: Vec<u8>,
+        leaf: X509,
+        issuer: X509,
+        chain: Vec<X509>,
+    ) -> Self {
+        FetchJob {
+            checker,
+            ssl_context,
+            key,
+            leaf,
+            issuer,
+            chain,
+            notify: None,
+        }
+    }
+
+    /// Send the result to the waiters when the fetch finished
+    pub(super) fn set_notify(&mut self, sender: watch::Sender<Option<RevocationStatus>>) {
+        self.notify = Some(sender);
+    }
+
+    /// Fetch the revocation status in the background, the result will be saved in the checker cache
+    pub(super) fn spawn(self) {
+        let Some(rt) = fetch_runtime() else {
+            return;
+        };
+        rt.spawn(async move {
+            self.run().await;
+        });
+    }
+
+    async fn run(self) {
+        let checker = self.checker.clone();
+        let time_start = Instant::now();
+
+        let status = match tokio::time::timeout(checker.fetch_timeout, self.fetch()).await {
+            Ok(status) => status,
+            Err(_) => {
+                debug!("timed out to fetch certificate revocation status");
+                RevocationStatus::Unknown
+            }
+        };
+        debug!(
+            "fetched certificate revocation status in {:?}",
+            time_start.elapsed()
+        );
+
+        let ttl = if status == RevocationStatus::Unknown {
+            FAILED_RETRY_INTERVAL
+        } else {
+            checker.cache_ttl()
+        };
+        checker.cache_status(self.key.clone(), status, ttl);
+        if let Some(sender) = self.notify {
+            checker.remove_pending(&self.key);
+            let _ = sender.send(Some(status));
+        }
+    }
+
+    async fn fetch(&self) -> RevocationStatus {
+        if self.checker.fetch_ocsp {
+            match self.fetch_ocsp().await {
+                Ok(RevocationStatus::Unknown) => {}
+                Ok(status) => return status,
+                Err(e) => debug!("failed to fetch OCSP response: {e:?}"),
+            }
+        }
+        if self.checker.fetch_crl {
+            match self.fetch_crl().await {
+                Ok(status) => return status,
+                Err(e) => debug!("failed to fetch CRL: {e:?}"),
+            }
+        }
+        RevocationStatus::Unknown
+    }
+
+    async fn fetch_ocsp(&self) -> anyhow::Result<RevocationStatus> {
+        let responders: Vec<String> = self
+            .leaf
+            .ocsp_responders()
+            .map_err(|e| anyhow!("failed to get OCSP responders: {e}"))?
+            .iter()
+            .map(|url| url.to_string())
+            .collect();
+
+        let req = build_ocsp_request(&self.leaf, &self.issuer)?;
+
+        for url in responders {
+            let rsp = match http_fetch(&url, Some(("application/ocsp-request", &req))).await {
+                Ok(rsp) => rsp,
+                Err(e) => {
+                    debug!("failed to query OCSP responder {url}: {e:?}");
+                    continue;
+                }
+            };
+            let status = self.check_ocsp_response(&rsp)?;
+            if status != RevocationStatus::Unknown {
+                return Ok(status);
+            }
+        }
+        Ok(RevocationStatus::Unknown)
+    }
+
+    fn check_ocsp_response(&self, rsp: &[u8]) -> anyhow::Result<RevocationStatus> {
+        let mut chain = Stack::new().map_err(|e| anyhow!("failed to create cert stack: {e}"))?;
+        for cert in &self.chain {
+            chain
+                .push(cert.clone())
+                .map_err(|e| anyhow!("failed to push cert to stack: {e}"))?;
+        }
+        Ok(self.checker.check_ocsp_response(
+            rsp,
+            &self.leaf,
+            &self.issuer,
+            &chain,
+            self.ssl_context.cert_store(),
+        ))
+    }
+
+    async fn fetch_crl(&self) -> anyhow::Result<RevocationStatus> {
+        let Some(points) = self.leaf.crl_distribution_points() else {
+            return Ok(RevocationStatus::Unknown);
+        };
+        let urls: Vec<String> = points
+            .iter()
+            .filter_map(|point| point.distpoint().and_then(|v| v.fullname()))
+            .flat_map(|names| {
+                names
+                    .iter()
+                    .filter_map(|v| v.uri().map(|s| s.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        for url in urls {
+            let data = match http_fetch(&url, None).await {
+                Ok(data) => data,
+                Err(e) => {
+                    debug!("failed to download CRL from {url}: {e:?}");
+                    continue;
+                }
+            };
+            let crl = match X509Crl::from_der(&data).or_else(|_| X509Crl::from_pem(&data)) {
+                Ok(crl) => crl,
+                Err(e) => {
+                    debug!("invalid CRL downloaded from {url}: {e}");
+                    continue;
+                }
+            };
+            if let Some(status) = check_crl(&crl, &self.leaf, &self.issuer) {
+                return Ok(status);
+            }
+        }
+        Ok(RevocationStatus::Unknown)
+    }
+}
+
+pub(super) fn build_ocsp_request(leaf: &X509Ref, issuer: &X509Ref) -> anyhow::Result<Vec<u8>> {
+    let id = OcspCertId::from_cert(MessageDigest::sha1(), leaf, issuer)
+        .map_err(|e| anyhow!("failed to create OCSP cert id: {e}"))?;
+    let mut req = OcspRequest::new().map_err(|e| anyhow!("failed to create OCSP request: {e}"))?;
+    req.add_id(id)
+        .map_err(|e| anyhow!("failed to add cert id to OCSP request: {e}"))?;
+    req.to_der()
+        .map_err(|e| anyhow!("failed to encode OCSP request: {e}"))
+}
+
+/// Do a simple HTTP/1.0 request, only plain http url is supported,
+/// which is also the one used for OCSP responders and CRL distribution points.
+///
+/// There is no timeout inside, the caller should set an overall deadline for it.
+pub(super) async fn http_fetch(url: &str, body: Option<(&str, &[u8])>) -> anyhow::Result<Vec<u8>> {
+    let url = Url::parse(url).map_err(|e| anyhow!("invalid url: {e}"))?;
+    if url.scheme() != "http" {
+        return Err(anyhow!("unsupported url scheme {}", url.scheme()));
+    }
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("no host found in url"))?;
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let mut last_err = anyhow!("no address found for {host}");
+    let mut stream = None;
+    for addr in tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|e| anyhow!("failed to resolve {host}: {e}"))?
+    {
+        match TcpStream::connect(addr).await {
+            Ok(s) => {
+                stream = Some(s);
+                break;
+            }
+            Err(e) => last_err = anyhow!("failed to connect to {addr}: {e}"),
+        }
+    }
+    let Some(mut stream) = stream else {
+        return Err(last_err);
+    };
+
+    let path = &url[url::Position::BeforePath..url::Position::AfterQuery];
+    let host_header = match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+    let mut req = match body {
+        Some((content_type, data)) => {
+            let mut req = format!(
+                "POST {path} HTTP/1.0\r\nHost: {host_header}\r\nContent-Type: {content_type}\r\n\
+                 Content-Length: {}\r\n\r\n",
+                data.len()
+            )
+            .into_bytes();
+            req.extend_from_slice(data);
+            req
+        }
+        None => format!("GET {path} HTTP/1.0\r\nHost: {host_header}\r\n\r\n").into_bytes(),
+    };
+    stream
+        .write_all(&req)
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+    req.clear();
+
+    let mut rsp = req;
+    (&mut stream)
+        .take(MAX_RESPONSE_SIZE)
+        .read_to_end(&mut rsp)
+        .await
+        .map_err(|e| anyhow!("failed to read response: {e}"))?;
+    parse_http_response(rsp)
+}
+
+/// Get the body of a complete HTTP/1.0 response, only 200 is allowed as the status code
+fn parse_http_response(mut rsp: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    let header_end = memchr::memmem::find(&rsp, b"\r\n\r\n")
+        .ok_or_else(|| anyhow!("no complete response header found"))?;
+    let status_line = rsp
+        .split(|c| *c == b'\n')
+        .next()
+        .and_then(|line| std::str::from_utf8(line).ok())
+        .ok_or_else(|| anyhow!("invalid response status line"))?;
+    let code = status_line
+        .split_ascii_whitespace()
+        .nth(1)
+        .ok_or_else(|| anyhow!("no status code found in response"))?;
+    if code != "200" {
+        return Err(anyhow!("unexpected response status code {code}"));
+    }
+    Ok(rsp.split_off(header_end + 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    fn serve_once<F>(handle: F) -> String
+    where
+        F: FnOnce(std::net::TcpStream) + Send + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle(stream);
+        });
+        format!("http://{addr}/path?q=1")
+    }
+
+    fn read_request(stream: &mut std::net::TcpStream) -> String {
+        let mut buf = [0u8; 1024];
+        let mut req = Vec::new();
+        while memchr::memmem::find(&req, b"\r\n\r\n").is_none() {
+            let n = stream.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            req.extend_from_slice(&buf[..n]);
+        }
+        String::from_utf8(req).unwrap()
+    }
+
+    #[test]
+    fn parse_response() {
+        let rsp = b"HTTP/1.0 200 OK\r\nContent-Length: 4\r\n\r\ndata".to_vec();
+        assert_eq!(parse_http_response(rsp).unwrap(), b"data");
+
+        let rsp = b"HTTP/1.1 404 Not Found\r\n\r\n".to_vec();
+        assert!(parse_http_response(rsp).is_err());
+
+        let rsp = b"HTTP/1.0 200 OK\r\nContent-Length: 4\r\n".to_vec();
+        assert!(parse_http_response(rsp).is_err());
+    }
+
+    #[test]
+    fn fetch_ok() {
+        let url = serve_once(|mut stream| {
+            let req = read_request(&mut stream);
+            assert!(req.starts_with("GET /path?q=1 HTTP/1.0\r\n"));
+            stream
+                .write_all(b"HTTP/1.0 200 OK\r\n\r\ncrl-data")
+                .unwrap();
+        });
+        let data = block_on_fetch(
+            async move { http_fetch(&url, None).await },
+            Duration::from_secs(4),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(data, b"crl-data");
+    }
+
+    #[test]
+    fn fetch_unsupported() {
+        let r = block_on_fetch(
+            async { http_fetch("https://127.0.0.1/", None).await },
+            Duration::from_secs(1),
+        )
+        .unwrap();
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn fetch_deadline() {
+        // a slow server which never completes the response
+        let url = serve_once(|mut stream| {
+            let _ = read_request(&mut stream);
+            for _ in 0..50 {
+                if stream.write_all(b"H").is_err() {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+        });
+        let time_start = Instant::now();
+        let r = block_on_fetch(
+            async move { http_fetch(&url, None).await },
+            Duration::from_millis(500),
+        );
+        assert!(r.is_none());
+        assert!(time_start.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,226 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+#[cfg(any(awslc, boringssl))]
+use log::warn;
+use openssl::ssl::SslContextBuilder;
+use openssl::x509::X509Crl;
+
+#[cfg(not(any(awslc, boringssl)))]
+mod checker;
+
+#[cfg(not(any(awslc, boringssl)))]
+mod fetch;
+
+mod stapling;
+pub use stapling::OpensslOcspStaple;
+
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_util;
+
+const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(4);
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(3600);
+const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// the max allowed clock skew when checking the validity of OCSP responses
+#[cfg(not(any(awslc, boringssl)))]
+const OCSP_VALIDITY_LEEWAY: u32 = 300;
+
+/// How to handle the upstream certificate revocation check result
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum OpensslRevocationMode {
+    /// only log the revoked or unknown status, never fail the handshake
+    LogOnly,
+    /// fail the handshake if the certificate is revoked
+    #[default]
+    SoftFail,
+    /// fail the handshake if the certificate is revoked or the status is unknown
+    HardFail,
+}
+
+impl OpensslRevocationMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpensslRevocationMode::LogOnly => "log_only",
+            OpensslRevocationMode::SoftFail => "soft_fail",
+            OpensslRevocationMode::HardFail => "hard_fail",
+        }
+    }
+}
+
+impl FromStr for OpensslRevocationMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "log_only" | "log" => Ok(OpensslRevocationMode::LogOnly),
+            "soft_fail" | "soft" => Ok(OpensslRevocationMode::SoftFail),
+            "hard_fail" | "hard" => Ok(OpensslRevocationMode::HardFail),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpensslRevocationConfig {
+    mode: OpensslRevocationMode,
+    crl_list: Vec<Vec<u8>>,
+    fetch_ocsp: bool,
+    fetch_crl: bool,
+    fetch_timeout: Duration,
+    cache_ttl: Duration,
+    cache_capacity: usize,
+}
+
+impl Default for OpensslRevocationConfig {
+    fn default() -> Self {
+        OpensslRevocationConfig {
+            mode: OpensslRevocationMode::default(),
+            crl_list: Vec::new(),
+            fetch_ocsp: false,
+            fetch_crl: false,
+            fetch_timeout: DEFAULT_FETCH_TIMEOUT,
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache_capacity: DEFAULT_CACHE_CAPACITY,
+        }
+    }
+}
+
+impl OpensslRevocationConfig {
+    #[inline]
+    pub fn mode(&self) -> OpensslRevocationMode {
+        self.mode
+    }
+
+    #[inline]
+    pub fn set_mode(&mut self, mode: OpensslRevocationMode) {
+        self.mode = mode;
+    }
+
+    pub fn set_crl_list(&mut self, crl_list: Vec<X509Crl>) -> anyhow::Result<()> {
+        let mut all_der = Vec::with_capacity(crl_list.len());
+        for (i, crl) in crl_list.into_iter().enumerate() {
+            let bytes = crl
+                .to_der()
+                .map_err(|e| anyhow!("failed to encode crl #{i}: {e}"))?;
+            all_der.push(bytes);
+        }
+        self.crl_list = all_der;
+        Ok(())
+    }
+
+    /// Get the DER encoded CRL list
+    #[inline]
+    pub fn crl_list(&self) -> &[Vec<u8>] {
+        &self.crl_list
+    }
+
+    #[inline]
+    pub fn fetch_ocsp(&self) -> bool {
+        self.fetch_ocsp
+    }
+
+    #[inline]
+    pub fn fetch_crl(&self) -> bool {
+        self.fetch_crl
+    }
+
+    #[inline]
+    pub fn fetch_timeout(&self) -> Duration {
+        self.fetch_timeout
+    }
+
+    #[inline]
+    pub fn cache_ttl(&self) -> Duration {
+        self.cache_ttl
+    }
+
+    #[inline]
+    pub fn cache_capacity(&self) -> usize {
+        self.cache_capacity
+    }
+
+    #[inline]
+    pub fn set_fetch_ocsp(&mut self, enable: bool) {
+        self.fetch_ocsp = enable;
+    }
+
+    #[inline]
+    pub fn set_fetch_crl(&mut self, enable: bool) {
+        self.fetch_crl = enable;
+    }
+
+    #[inline]
+    pub fn set_fetch_timeout(&mut self, timeout: Duration) {
+        self.fetch_timeout = timeout;
+    }
+
+    #[inline]
+    pub fn set_cache_ttl(&mut self, ttl: Duration) {
+        self.cache_ttl = ttl;
+    }
+
+    #[inline]
+    pub fn set_cache_capacity(&mut self, capacity: usize) {
+        self.cache_capacity = capacity;
+    }
+
+    /// Install the revocation check as the OCSP status callback of the client context.
+    /// The ca certificates should be set as the cert store of the context,
+    /// which will also be used to verify the OCSP responses.
+    #[cfg(not(any(awslc, boringssl)))]
+    pub(super) fn set_for_client(&self, builder: &mut SslContextBuilder) -> anyhow::Result<()> {
+        use std::sync::Arc;
+
+        use openssl::ssl::StatusType;
+
+        let mut crl_list = Vec::with_capacity(self.crl_list.len());
+        for (i, der) in self.crl_list.iter().enumerate() {
+            let crl =
+                X509Crl::from_der(der).map_err(|e| anyhow!("failed to load crl #{i}: {e}"))?;
+            crl_list.push(crl);
+        }
+
+        let checker = Arc::new(checker::OpensslRevocationChecker::new(self, crl_list));
+        builder
+            .set_status_type(StatusType::OCSP)
+            .map_err(|e| anyhow!("failed to enable OCSP status request: {e}"))?;
+        builder
+            .set_status_callback(move |ssl| Ok(checker.check(ssl)))
+            .map_err(|e| anyhow!("failed to set OCSP status callback: {e}"))
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    pub(super) fn set_for_client(&self, _builder: &mut SslContextBuilder) -> anyhow::Result<()> {
+        warn!("certificate revocation check is not supported for BoringSSL variants");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_mode() {
+        assert_eq!(
+            OpensslRevocationMode::from_str("log-only").unwrap(),
+            OpensslRevocationMode::LogOnly
+        );
+        assert_eq!(
+            OpensslRevocationMode::from_str("Soft_Fail").unwrap(),
+            OpensslRevocationMode::SoftFail
+        );
+        assert_eq!(
+            OpensslRevocationMode::from_str("hard").unwrap(),
+            OpensslRevocationMode::HardFail
+        );
+        assert!(OpensslRevocationMode::from_str("strict").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::anyhow;
+use openssl::x509::{X509, X509Ref};
+
+/// the refresh interval if no nextUpdate is set in the OCSP response
+#[cfg(not(any(awslc, boringssl)))]
+const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+#[cfg(not(any(awslc, boringssl)))]
+const MINIMAL_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// An OCSP response for a local server certificate, which can be stapled in the TLS handshake
+#[derive(Clone, Debug)]
+pub struct OpensslOcspStaple {
+    der: Arc<[u8]>,
+    good: bool,
+    expire: Option<Instant>,
+    refresh: Instant,
+}
+
+impl OpensslOcspStaple {
+    /// Fetch the OCSP response from the responders set in the leaf certificate.
+    /// The issuer certificate should be present in `chain`.
+    ///
+    /// This is a blocking call, and it should be called in a blocking thread.
+    /// The `timeout` is the deadline for all the queries.
+    #[cfg(not(any(awslc, boringssl)))]
+    pub fn fetch(leaf: &X509Ref, chain: &[X509], timeout: Duration) -> anyhow::Result<Self> {
+        use openssl::x509::X509VerifyResult;
+
+        let issuer = chain
+            .iter()
+            .find(|c| c.issued(leaf) == X509VerifyResult::OK)
+            .ok_or_else(|| anyhow!("no issuer certificate found in the chain"))?;
+        let responders: Vec<String> = leaf
+            .ocsp_responders()
+            .map_err(|e| anyhow!("failed to get OCSP responders: {e}"))?
+            .iter()
+            .map(|url| url.to_string())
+            .collect();
+        let req = super::fetch::build_ocsp_request(leaf, issuer)?;
+
+        let deadline = Instant::now() + timeout;
+        let mut last_err = anyhow!("no OCSP responder found in the certificate");
+        for url in responders {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            if timeout.is_zero() {
+                return Err(last_err.context("timed out to query OCSP responders"));
+            }
+            let req = req.clone();
+            let fetch_url = url.clone();
+            let r = super::fetch::block_on_fetch(
+                async move {
+                    super::fetch::http_fetch(&fetch_url, Some(("application/ocsp-request", &req)))
+                        .await
+                },
+                timeout,
+            )
+            .unwrap_or_else(|| Err(anyhow!("timed out")))
+            .and_then(|rsp| OpensslOcspStaple::parse(rsp, leaf, issuer));
+            match r {
+                Ok(staple) => return Ok(staple),
+                Err(e) => last_err = e.context(format!("failed to query OCSP responder {url}")),
+            }
+        }
+        Err(last_err)
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    pub fn fetch(_leaf: &X509Ref, _chain: &[X509], _timeout: Duration) -> anyhow::Result<Self> {
+        Err(anyhow!("OCSP is not supported for BoringSSL variants"))
+    }
+
+    #[cfg(any(awslc, boringssl))]
+    pub fn parse(_data: Vec<u8>, _leaf: &X509Ref, _issuer: &X509Ref) -> anyhow::Result<Self> {
+        Err(anyhow!("OCSP is not supported for BoringSSL variants"))
+    }
+
+    /// Parse and verify the OCSP response for the leaf certificate
+    #[cfg(not(any(awslc, boringssl)))]
+    pub fn parse(data: Vec<u8>, leaf: &X509Ref, issuer: &X509Ref) -> anyhow::Result<Self> {
+        use openssl::hash::MessageDigest;
+        use openssl::ocsp::{
+            OcspCertId, OcspCertStatus, OcspFlag, OcspResponse, OcspResponseStatus,
+        };
+        use openssl::stack::Stack;
+        use openssl::x509::store::X509StoreBuilder;
+
+        let rsp =
+            OcspResponse::from_der(&data).map_err(|e| anyhow!("invalid OCSP response: {e}"))?;
+        if rsp.status() != OcspResponseStatus::SUCCESSFUL {
+            return Err(anyhow!("unsuccessful OCSP response: {:?}", rsp.status()));
+        }
+        let basic = rsp
+            .basic()
+            .map_err(|e| anyhow!("no basic OCSP response found: {e}"))?;
+
+        let mut certs = Stack::new().map_err(|e| anyhow!("failed to create cert stack: {e}"))?;
+        certs
+            .push(issuer.to_owned())
+            .map_err(|e| anyhow!("failed to push issuer cert to stack: {e}"))?;
+        let mut store_builder = X509StoreBuilder::new()
+            .map_err(|e| anyhow!("failed to create cert store builder: {e}"))?;
+        store_builder
+            .add_cert(issuer.to_owned())
+            .map_err(|e| anyhow!("failed to add issuer cert to store: {e}"))?;
+        // the issuer may be an intermediate ca, and the responder may be delegated by it
+        #[cfg(not(libressl))]
+        store_builder
+            .set_flags(openssl::x509::verify::X509VerifyFlags::PARTIAL_CHAIN)
+            .map_err(|e| anyhow!("failed to set cert store flags: {e}"))?;
+        let store = store_builder.build();
+        basic
+            .verify(&certs, &store, OcspFlag::TRUST_OTHER)
+            .map_err(|e| anyhow!("failed to verify OCSP response: {e}"))?;
+
+        let id = OcspCertId::from_cert(MessageDigest::sha1(), leaf, issuer)
+            .map_err(|e| anyhow!("failed to create OCSP cert id: {e}"))?;
+        let status = basic
+            .find_status(&id)
+            .ok_or_else(|| anyhow!("no status for the certificate found in OCSP response"))?;
+        status
+            .check_validity(super::OCSP_VALIDITY_LEEWAY, None)
+            .map_err(|e| anyhow!("OCSP response is out of date: {e}"))?;
+        let good = match status.status {
+            OcspCertStatus::GOOD => true,
+            OcspCertStatus::REVOKED => false,
+            _ => return Err(anyhow!("unknown certificate status in OCSP response")),
+        };
+
+        let now = Instant::now();
+        let (expire, refresh) = match next_update(&basic, &id) {
+            Some(time) => {
+                let valid = Duration::from_secs(seconds_until(time).unwrap_or(0));
+                // refresh in the middle of the remaining validity period
+                let refresh = (valid / 2).max(MINIMAL_REFRESH_INTERVAL);
+                (now.checked_add(valid), now + refresh)
+            }
+            None => (None, now + DEFAULT_REFRESH_INTERVAL),
+        };
+        Ok(OpensslOcspStaple {
+            der: Arc::from(data),
+            good,
+            expire,
+            refresh,
+        })
+    }
+
+    #[inline]
+    pub fn as_der(&self) -> &[u8] {
+        &self.der
+    }
+
+    /// Return false if the certificate has been revoked
+    #[inline]
+    pub fn is_good(&self) -> bool {
+        self.good
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expire.map(|t| t <= Instant::now()).unwrap_or(false)
+    }
+
+    pub fn need_refresh(&self) -> bool {
+        self.refresh <= Instant::now()
+    }
+}
+
+/// Get the nextUpdate time of the certificate status, which is optional in the OCSP response
+#[cfg(not(any(awslc, boringssl)))]
+fn next_update<'a>(
+    basic: &'a openssl::ocsp::OcspBasicResponseRef,
+    id: &openssl::ocsp::OcspCertIdRef,
+) -> Option<&'a openssl::asn1::Asn1GeneralizedTimeRef> {
+    use openssl::foreign_types::ForeignTypeRef;
+
+    let mut next_update = std::ptr::null_mut();
+    // SAFETY: all the other optional out params are allowed to be null,
+    // and `OcspStatus::next_update` can't be used as it may be built from a null pointer
+    let found = unsafe {
+        openssl_sys::OCSP_resp_find_status(
+            basic.as_ptr(),
+            id.as_ptr(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            &mut next_update,
+        )
+    };
+    if found != 1 || next_update.is_null() {
+        return None;
+    }
+    // SAFETY: the time is owned by the basic response
+    Some(unsafe { openssl::asn1::Asn1GeneralizedTimeRef::from_ptr(next_update) })
+}
+
+#[cfg(not(any(awslc, boringssl)))]
+fn seconds_until(time: &openssl::asn1::Asn1GeneralizedTimeRef) -> Option<u64> {
+    use openssl::asn1::{Asn1Time, Asn1TimeRef};
+    use openssl::foreign_types::ForeignTypeRef;
+
+    let now = Asn1Time::days_from_now(0).ok()?;
+    // SAFETY: ASN1_GENERALIZEDTIME and ASN1_TIME are both ASN1_STRING in OpenSSL
+    let time = unsafe { Asn1TimeRef::from_ptr(time.as_ptr().cast()) };
+    let diff = now.diff(time).ok()?;
+    let secs = i64::from(diff.days) * 86400 + i64::from(diff.secs);
+    u64::try_from(secs).ok()
+}
+
+#[cfg(test)]
+#[cfg(not(any(awslc, boringssl)))]
+mod tests {
+    use super::*;
+    use openssl::hash::MessageDigest;
+    use openssl::ocsp::{OcspCertId, OcspResponse};
+
+    use super::super::test_util::{
+        CA_CERT, LEAF_CERT, OCSP_GOOD, OCSP_NO_NEXT_UPDATE, OCSP_REVOKED,
+    };
+
+    fn load_certs() -> (X509, X509) {
+        (
+            X509::from_pem(LEAF_CERT).unwrap(),
+            X509::from_pem(CA_CERT).unwrap(),
+        )
+    }
+
+    #[test]
+    fn parse_good() {
+        let (leaf, issuer) = load_certs();
+        let staple = OpensslOcspStaple::parse(OCSP_GOOD.to_vec(), &leaf, &issuer).unwrap();
+        assert!(staple.is_good());
+        assert_eq!(staple.as_der(), OCSP_GOOD);
+        assert!(!staple.is_expired());
+        assert!(!staple.need_refresh());
+
+        // the next update time is about 100 years later
+        let expire = staple.expire.unwrap();
+        assert!(expire > Instant::now() + Duration::from_secs(99 * 365 * 86400));
+        assert!(staple.refresh < expire);
+    }
+
+    #[test]
+    fn parse_revoked() {
+        let (leaf, issuer) = load_certs();
+        let staple = OpensslOcspStaple::parse(OCSP_REVOKED.to_vec(), &leaf, &issuer).unwrap();
+        assert!(!staple.is_good());
+    }
+
+    #[test]
+    fn parse_no_next_update() {
+        let (leaf, issuer) = load_certs();
+        let staple =
+            OpensslOcspStaple::parse(OCSP_NO_NEXT_UPDATE.to_vec(), &leaf, &issuer).unwrap();
+        assert!(staple.is_good());
+        assert!(staple.expire.is_none());
+        assert!(!staple.is_expired());
+        assert!(staple.refresh <= Instant::now() + DEFAULT_REFRESH_INTERVAL);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let (leaf, issuer) = load_certs();
+        assert!(OpensslOcspStaple::parse(b"invalid".to_vec(), &leaf, &issuer).is_err());
+
+        // the response is not signed by this issuer
+        assert!(OpensslOcspStaple::parse(OCSP_GOOD.to_vec(), &leaf, &leaf).is_err());
+    }
+
+    #[test]
+    fn need_refresh() {
+        let now = Instant::now();
+        let mut staple = OpensslOcspStaple {
+            der: Arc::from(OCSP_GOOD),
+            good: true,
+            expire: Some(now + Duration::from_secs(60)),
+            refresh: now + Duration::from_secs(30),
+        };
+        assert!(!staple.need_refresh());
+        assert!(!staple.is_expired());
+
+        staple.refresh = now;
+        assert!(staple.need_refresh());
+
+        staple.expire = Some(now);
+        assert!(staple.is_expired());
+    }
+
+    #[test]
+    fn seconds_until_next_update() {
+        let (leaf, issuer) = load_certs();
+        let rsp = OcspResponse::from_der(OCSP_GOOD).unwrap();
+        let basic = rsp.basic().unwrap();
+        let id = OcspCertId::from_cert(MessageDigest::sha1(), &leaf, &issuer).unwrap();
+        let status = basic.find_status(&id).unwrap();
+
+        let time = next_update(&basic, &id).unwrap();
+        let secs = seconds_until(time).unwrap();
+        assert!(secs > 99 * 365 * 86400);
+        assert!(secs < 101 * 365 * 86400);
+
+        // this update is in the past
+        assert!(seconds_until(status.this_update).is_none());
+
+        let rsp = OcspResponse::from_der(OCSP_NO_NEXT_UPDATE).unwrap();
+        let basic = rsp.basic().unwrap();
+        assert!(next_update(&basic, &id).is_none());
+    }
+}
@@ -0,0 +1,18 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! A test CA and leaf certificate, with OCSP responses signed by the CA,
+//! which can be used in tests of the OCSP related code.
+
+/// The CA certificate in PEM format, which is the issuer of [LEAF_CERT]
+pub const CA_CERT: &[u8] = include_bytes!("./test_data/ca_cert.pem");
+/// The leaf certificate in PEM format, with subject `test.example.net`
+pub const LEAF_CERT: &[u8] = include_bytes!("./test_data/leaf_cert.pem");
+/// A DER encoded OCSP response with good status for [LEAF_CERT]
+pub const OCSP_GOOD: &[u8] = include_bytes!("./test_data/ocsp_good.der");
+/// A DER encoded OCSP response with revoked status for [LEAF_CERT]
+pub const OCSP_REVOKED: &[u8] = include_bytes!("./test_data/ocsp_revoked.der");
+/// A DER encoded OCSP response with good status but no nextUpdate for [LEAF_CERT]
+pub const OCSP_NO_NEXT_UPDATE: &[u8] = include_bytes!("./test_data/ocsp_no_next.der");
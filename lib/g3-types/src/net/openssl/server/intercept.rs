@@ -59,6 +59,11 @@ impl OpensslInterceptionServerConfigBuilder {
         self.accept_timeout = timeout;
     }
 
+    #[inline]
+    pub fn accept_timeout(&self) -> Duration {
+        self.accept_timeout
+    }
+
     pub fn build(&self) -> anyhow::Result<OpensslInterceptionServerConfig> {
         self.build_with_ticketer(None)
     }
@@ -129,6 +129,47 @@ impl OpensslServerConfigBuilder {
         self.accept_timeout = timeout;
     }
 
+    #[inline]
+    pub fn cert_pairs(&self) -> &[OpensslCertificatePair] {
+        &self.cert_pairs
+    }
+
+    #[inline]
+    pub fn tlcp_cert_pairs(&self) -> &[OpensslTlcpCertificatePair] {
+        &self.tlcp_cert_pairs
+    }
+
+    #[inline]
+    pub fn client_auth(&self) -> bool {
+        self.client_auth
+    }
+
+    /// Get the DER encoded client auth certificates
+    #[inline]
+    pub fn client_auth_certificates(&self) -> &[Vec<u8>] {
+        &self.client_auth_certs
+    }
+
+    #[inline]
+    pub fn session_id_context(&self) -> &str {
+        &self.session_id_context
+    }
+
+    #[inline]
+    pub fn disable_session_ticket(&self) -> bool {
+        self.no_session_ticket
+    }
+
+    #[inline]
+    pub fn disable_session_cache(&self) -> bool {
+        self.no_session_cache
+    }
+
+    #[inline]
+    pub fn accept_timeout(&self) -> Duration {
+        self.accept_timeout
+    }
+
     #[cfg(not(tongsuo))]
     fn build_tls_acceptor(
         &self,
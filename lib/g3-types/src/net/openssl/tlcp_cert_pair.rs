@@ -38,6 +38,31 @@ impl OpensslTlcpCertificatePair {
         Ok(())
     }
 
+    #[inline]
+    pub fn sign_leaf_cert_der(&self) -> &[u8] {
+        &self.sign_leaf_cert
+    }
+
+    #[inline]
+    pub fn enc_leaf_cert_der(&self) -> &[u8] {
+        &self.enc_leaf_cert
+    }
+
+    #[inline]
+    pub fn chain_certs_der(&self) -> &[Vec<u8>] {
+        &self.chain_certs
+    }
+
+    #[inline]
+    pub fn sign_private_key_der(&self) -> &[u8] {
+        &self.sign_key
+    }
+
+    #[inline]
+    pub fn enc_private_key_der(&self) -> &[u8] {
+        &self.enc_key
+    }
+
     pub fn set_sign_certificates(&mut self, certs: Vec<X509>) -> anyhow::Result<()> {
         let mut certs_iter = certs.into_iter();
         let leaf_cert = certs_iter
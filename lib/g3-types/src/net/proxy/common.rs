@@ -15,6 +15,19 @@ pub enum ProxyRequestType {
     SocksUdpAssociate,
 }
 
+impl ProxyRequestType {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ProxyRequestType::HttpForward => "http_forward",
+            ProxyRequestType::HttpsForward => "https_forward",
+            ProxyRequestType::FtpOverHttp => "ftp_over_http",
+            ProxyRequestType::HttpConnect => "http_connect",
+            ProxyRequestType::SocksTcpConnect => "socks_tcp_connect",
+            ProxyRequestType::SocksUdpAssociate => "socks_udp_associate",
+        }
+    }
+}
+
 impl FromStr for ProxyRequestType {
     type Err = ();
 
@@ -9,17 +9,17 @@ use quinn::{IdleTimeout, TransportConfig, VarInt};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct QuinnTransportConfigBuilder {
-    max_idle_timeout: IdleTimeout,
+    max_idle_timeout: Duration,
     keep_alive_interval: Duration,
-    stream_receive_window: Option<VarInt>,
-    receive_window: Option<VarInt>,
-    send_window: Option<u64>,
+    stream_receive_window: Option<u32>,
+    receive_window: Option<u32>,
+    send_window: Option<u32>,
 }
 
 impl Default for QuinnTransportConfigBuilder {
     fn default() -> Self {
         QuinnTransportConfigBuilder {
-            max_idle_timeout: IdleTimeout::from(VarInt::from_u32(60_000)), // 60s
+            max_idle_timeout: Duration::from_secs(60),
             keep_alive_interval: Duration::from_secs(10),
             stream_receive_window: None,
             receive_window: None,
@@ -30,41 +30,69 @@ impl Default for QuinnTransportConfigBuilder {
 
 impl QuinnTransportConfigBuilder {
     pub fn set_max_idle_timeout(&mut self, timeout: Duration) -> anyhow::Result<()> {
-        self.max_idle_timeout = IdleTimeout::try_from(timeout)?;
+        IdleTimeout::try_from(timeout)?;
+        self.max_idle_timeout = timeout;
         Ok(())
     }
 
+    #[inline]
+    pub fn max_idle_timeout(&self) -> Duration {
+        self.max_idle_timeout
+    }
+
     pub fn set_keep_alive_interval(&mut self, interval: Duration) {
         self.keep_alive_interval = interval;
     }
 
+    #[inline]
+    pub fn keep_alive_interval(&self) -> Duration {
+        self.keep_alive_interval
+    }
+
     pub fn set_stream_receive_window(&mut self, size: u32) {
-        self.stream_receive_window = Some(VarInt::from_u32(size));
+        self.stream_receive_window = Some(size);
+    }
+
+    #[inline]
+    pub fn stream_receive_window(&self) -> Option<u32> {
+        self.stream_receive_window
     }
 
     pub fn set_receive_window(&mut self, size: u32) {
-        self.receive_window = Some(VarInt::from_u32(size));
+        self.receive_window = Some(size);
+    }
+
+    #[inline]
+    pub fn receive_window(&self) -> Option<u32> {
+        self.receive_window
     }
 
     pub fn set_send_window(&mut self, size: u32) {
-        self.send_window = Some(size as u64);
+        self.send_window = Some(size);
+    }
+
+    #[inline]
+    pub fn send_window(&self) -> Option<u32> {
+        self.send_window
     }
 
     pub fn build_for_client(&self) -> TransportConfig {
         let mut config = TransportConfig::default();
+        // the timeout value has been checked in set_max_idle_timeout()
+        let max_idle_timeout = IdleTimeout::try_from(self.max_idle_timeout).ok();
         config
             .max_concurrent_bidi_streams(VarInt::from_u32(0))
             .max_concurrent_uni_streams(VarInt::from_u32(0))
-            .max_idle_timeout(Some(self.max_idle_timeout))
+            .max_idle_timeout(max_idle_timeout)
             .keep_alive_interval(Some(self.keep_alive_interval));
         if let Some(v) = self.stream_receive_window {
-            config.stream_receive_window(v);
+            config.stream_receive_window(VarInt::from_u32(v));
         }
         if let Some(v) = self.receive_window {
-            config.receive_window(v);
+            config.receive_window(VarInt::from_u32(v));
         }
         if let Some(v) = self.send_window {
-            config.send_window(v);
+            config.send_window(v as u64);
         }
         config
     }
@@ -51,6 +51,10 @@ impl Clone for RustlsCertificatePair {
 }
 
 impl RustlsCertificatePair {
+    pub fn certs_ref(&self) -> &[CertificateDer<'static>] {
+        &self.certs
+    }
+
     pub fn certs_owned(&self) -> Vec<CertificateDer<'static>> {
         self.certs.clone()
     }
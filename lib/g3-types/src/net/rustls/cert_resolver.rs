@@ -25,11 +25,21 @@ impl MultipleCertResolver {
     }
 
     pub fn push_cert_pair(&mut self, pair: &RustlsCertificatePair) -> anyhow::Result<()> {
+        self.push_cert_pair_with_ocsp(pair, None)
+    }
+
+    /// Add the cert pair along with the OCSP response to be stapled
+    pub fn push_cert_pair_with_ocsp(
+        &mut self,
+        pair: &RustlsCertificatePair,
+        ocsp: Option<Vec<u8>>,
+    ) -> anyhow::Result<()> {
         let Some(provider) = CryptoProvider::get_default() else {
             return Err(anyhow!("no rustls provider registered"));
         };
-        let ck = CertifiedKey::from_der(pair.certs_owned(), pair.key_owned(), provider)
+        let mut ck = CertifiedKey::from_der(pair.certs_owned(), pair.key_owned(), provider)
             .map_err(|e| anyhow!("failed to load cert pair: {e}"))?;
+        ck.ocsp = ocsp;
         self.keys.push(Arc::new(ck));
         Ok(())
     }
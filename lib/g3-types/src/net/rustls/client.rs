@@ -100,6 +100,46 @@ impl RustlsClientConfigBuilder {
         self.use_builtin_ca_certs = true;
     }
 
+    #[inline]
+    pub fn no_session_cache(&self) -> bool {
+        self.no_session_cache
+    }
+
+    #[inline]
+    pub fn disable_sni(&self) -> bool {
+        self.disable_sni
+    }
+
+    #[inline]
+    pub fn max_fragment_size(&self) -> Option<usize> {
+        self.max_fragment_size
+    }
+
+    #[inline]
+    pub fn cert_pair(&self) -> Option<&RustlsCertificatePair> {
+        self.client_cert_pair.as_ref()
+    }
+
+    #[inline]
+    pub fn ca_certificates(&self) -> &[CertificateDer<'static>] {
+        &self.ca_certs
+    }
+
+    #[inline]
+    pub fn no_default_ca_certificates(&self) -> bool {
+        self.no_default_ca_certs
+    }
+
+    #[inline]
+    pub fn use_builtin_ca_certificates(&self) -> bool {
+        self.use_builtin_ca_certs
+    }
+
+    #[inline]
+    pub fn negotiation_timeout(&self) -> Duration {
+        self.handshake_timeout
+    }
+
     fn build_client_config(
         &self,
         alpn_protocols: Option<Vec<AlpnProtocol>>,
@@ -79,6 +79,11 @@ impl RustlsServerConfigBuilder {
         self.client_auth = true;
     }
 
+    #[inline]
+    pub fn client_auth(&self) -> bool {
+        self.client_auth
+    }
+
     pub fn set_client_auth_certificates(&mut self, certs: Vec<CertificateDer<'static>>) {
         self.client_auth_certs = Some(certs);
     }
@@ -97,6 +102,26 @@ impl RustlsServerConfigBuilder {
         self.accept_timeout
     }
 
+    #[inline]
+    pub fn cert_pairs(&self) -> &[RustlsCertificatePair] {
+        &self.cert_pairs
+    }
+
+    #[inline]
+    pub fn client_auth_certificates(&self) -> Option<&[CertificateDer<'static>]> {
+        self.client_auth_certs.as_deref()
+    }
+
+    #[inline]
+    pub fn use_session_ticket(&self) -> bool {
+        self.use_session_ticket
+    }
+
+    #[inline]
+    pub fn disable_session_cache(&self) -> bool {
+        self.no_session_cache
+    }
+
     fn build_server_config<T>(
         &self,
         alpn_protocols: Option<Vec<AlpnProtocol>>,
@@ -141,4 +141,11 @@ impl WebSocketNotes {
     pub fn version(&self) -> Option<&HeaderValue> {
         self.headers.get(header::SEC_WEBSOCKET_VERSION)
     }
+
+    #[inline]
+    pub fn extensions(&self) -> impl Iterator<Item = &HeaderValue> {
+        self.headers
+            .get_all(header::SEC_WEBSOCKET_EXTENSIONS)
+            .iter()
+    }
 }
@@ -54,6 +54,14 @@ impl ResolveRedirectionBuilder {
         self.trie.insert(from, to);
     }
 
+    pub fn exact_iter(&self) -> impl Iterator<Item = (&str, &ResolveRedirectionValue)> {
+        self.ht.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    pub fn parent_iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.trie.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
     pub fn build(&self) -> ResolveRedirection {
         let mut trie = Trie::new();
         for (k, v) in self.trie.iter() {
@@ -17,6 +17,15 @@ pub enum QueryStrategy {
 }
 
 impl QueryStrategy {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            QueryStrategy::Ipv4Only => "ipv4_only",
+            QueryStrategy::Ipv6Only => "ipv6_only",
+            QueryStrategy::Ipv4First => "ipv4_first",
+            QueryStrategy::Ipv6First => "ipv6_first",
+        }
+    }
+
     fn adjust_to(self, other: Self) -> Self {
         if matches!(self, Self::Ipv4Only | Self::Ipv6Only) {
             self
@@ -47,6 +56,15 @@ pub enum PickStrategy {
     Serial,
 }
 
+impl PickStrategy {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            PickStrategy::Random => "random",
+            PickStrategy::Serial => "serial",
+        }
+    }
+}
+
 impl FromStr for PickStrategy {
     type Err = ();
 
@@ -13,7 +13,7 @@ use rustc_hash::{FxBuildHasher, FxHashMap};
 
 use crate::collection::NamedValue;
 use crate::net::Host;
-use crate::resolve::reverse_idna_domain;
+use crate::resolve::{reverse_idna_domain, reverse_to_idna_domain};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct HostMatch<T> {
@@ -90,12 +90,46 @@ impl<T> HostMatch<T> {
         self.default.as_ref()
     }
 
+    pub fn exact_domains(&self) -> impl Iterator<Item = (&Arc<str>, &T)> {
+        self.exact_domain.iter().flat_map(|ht| ht.iter())
+    }
+
+    pub fn exact_ips(&self) -> impl Iterator<Item = (&IpAddr, &T)> {
+        self.exact_ip.iter().flat_map(|ht| ht.iter())
+    }
+
+    pub fn child_domains(&self) -> impl Iterator<Item = (String, &T)> {
+        self.child_domain
+            .iter()
+            .flat_map(|trie| trie.iter())
+            .map(|(d, v)| (reverse_to_idna_domain(d), v))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.exact_domain.is_none()
             && self.exact_ip.is_none()
             && self.child_domain.is_none()
             && self.default.is_none()
     }
+
+    /// Call `f` for each value, the same value may be visited more than once
+    pub fn for_each_value<F>(&self, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        if let Some(ht) = &self.exact_domain {
+            ht.values().for_each(&mut f);
+        }
+        if let Some(ht) = &self.exact_ip {
+            ht.values().for_each(&mut f);
+        }
+        if let Some(trie) = &self.child_domain {
+            trie.values().for_each(&mut f);
+        }
+        if let Some(default) = &self.default {
+            f(default);
+        }
+    }
 }
 
 impl<T> HostMatch<Arc<T>> {
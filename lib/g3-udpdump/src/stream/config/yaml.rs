@@ -6,6 +6,8 @@
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+use g3_yaml::dump::{YamlDump, YamlMapDumper};
+
 use super::StreamDumpConfig;
 
 impl StreamDumpConfig {
@@ -55,3 +57,15 @@ impl StreamDumpConfig {
         }
     }
 }
+
+impl YamlDump for StreamDumpConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("peer", &self.peer)
+            .set("socket_buffer", &self.buffer)
+            .set("misc_opts", &self.opts)
+            .set("packet_size", &self.packet_size)
+            .set("client_side", &self.client_side);
+        map.build()
+    }
+}
@@ -3,6 +3,8 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::fmt;
+
 use openssl::error::ErrorStack;
 
 mod b64;
@@ -49,22 +51,34 @@ impl XCryptHash {
     }
 }
 
+impl fmt::Display for XCryptHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            XCryptHash::Md5(this) => this.fmt(f),
+            XCryptHash::Sha256(this) => this.fmt(f),
+            XCryptHash::Sha512(this) => this.fmt(f),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn md5() {
-        let crypt = XCryptHash::parse("$1$DDiGYGte$K/SAC4VvllDonGcP1EfaY1").unwrap();
+        let s = "$1$DDiGYGte$K/SAC4VvllDonGcP1EfaY1";
+        let crypt = XCryptHash::parse(s).unwrap();
         assert!(crypt.verify("123456".as_bytes()).unwrap());
+        assert_eq!(crypt.to_string(), s);
     }
 
     #[test]
     fn sha256() {
-        let crypt =
-            XCryptHash::parse("$5$W9wFmTCpBILzJn18$X496nPJHVQ895fwotE3WPBLmxgxGD8ivpUhfmoKbtb7")
-                .unwrap();
+        let s = "$5$W9wFmTCpBILzJn18$X496nPJHVQ895fwotE3WPBLmxgxGD8ivpUhfmoKbtb7";
+        let crypt = XCryptHash::parse(s).unwrap();
         assert!(crypt.verify("123456".as_bytes()).unwrap());
+        assert_eq!(crypt.to_string(), s);
     }
 
     #[test]
@@ -73,5 +87,6 @@ mod tests {
             .reNyfNzRJyAJrlh38J1XGx/5QTfBy3IedVNdTqfWqSeZFPAbXzV85uNK9fdmXvGCxizHVcAiIoQ4uXMJWuB6/";
         let crypt = XCryptHash::parse(s).unwrap();
         assert!(crypt.verify("123456".as_bytes()).unwrap());
+        assert_eq!(crypt.to_string(), s);
     }
 }
@@ -3,6 +3,8 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::fmt;
+
 use constant_time_eq::constant_time_eq_16;
 use openssl::error::ErrorStack;
 use openssl::md::Md;
@@ -154,3 +156,9 @@ impl Md5Crypt {
         do_md5_hash(phrase, &self.salt).map(|hash| constant_time_eq_16(&hash, &self.hash_bin))
     }
 }
+
+impl fmt::Display for Md5Crypt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{PREFIX}{}${}", self.salt, self.hash)
+    }
+}
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::fmt;
 use std::str::FromStr;
 
 use constant_time_eq::constant_time_eq_64;
@@ -218,3 +219,13 @@ impl Sha512Crypt {
             .map(|hash| constant_time_eq_64(&hash, &self.hash_bin))
     }
 }
+
+impl fmt::Display for Sha512Crypt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(PREFIX)?;
+        if self.rounds != ROUNDS_DEFAULT {
+            write!(f, "rounds={}$", self.rounds)?;
+        }
+        write!(f, "{}${}", self.salt, self.hash)
+    }
+}
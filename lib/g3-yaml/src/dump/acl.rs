@@ -0,0 +1,252 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::acl::{
+    AclAction, AclChildDomainRuleBuilder, AclExactHostRule, AclExactPortRule,
+    AclNetworkRuleBuilder, AclProxyRequestRule, AclRegexDomainRuleBuilder, AclRegexSetRuleBuilder,
+    AclUserAgentRule, ActionContract,
+};
+use g3_types::acl_set::AclDstHostRuleSetBuilder;
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for AclAction {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        let s = match self {
+            AclAction::Permit => "permit",
+            AclAction::PermitAndLog => "permit_log",
+            AclAction::Forbid => "forbid",
+            AclAction::ForbidAndLog => "forbid_log",
+        };
+        Yaml::String(s.to_string())
+    }
+}
+
+/// Dump the rules in the `{<action>: [<rule>...]}` form
+pub(crate) fn action_rules_to_yaml<A, I>(rules: I, redact: bool) -> yaml::Hash
+where
+    A: YamlDump,
+    I: IntoIterator<Item = (Yaml, A)>,
+{
+    let mut action_map: BTreeMap<Yaml, Vec<Yaml>> = BTreeMap::new();
+    for (rule, action) in rules {
+        action_map
+            .entry(action.dump_yaml(redact))
+            .or_default()
+            .push(rule);
+    }
+
+    let mut map = yaml::Hash::new();
+    for (action, mut rules) in action_map {
+        rules.sort();
+        map.insert(action, Yaml::Array(rules));
+    }
+    map
+}
+
+/// Dump the acl rule in the `{default: <action>, <action>: [<rule>...]}` form
+pub(crate) fn acl_rule_to_yaml<A, I>(missed_action: A, rules: I, redact: bool) -> Yaml
+where
+    A: YamlDump,
+    I: IntoIterator<Item = (Yaml, A)>,
+{
+    let mut map = yaml::Hash::new();
+    map.insert(
+        Yaml::String("default".to_string()),
+        missed_action.dump_yaml(redact),
+    );
+    map.extend(action_rules_to_yaml(rules, redact));
+    Yaml::Hash(map)
+}
+
+impl<Action: ActionContract + YamlDump> YamlDump for AclExactHostRule<Action> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        acl_rule_to_yaml(
+            self.missed_action(),
+            self.iter().map(|(h, a)| (h.dump_yaml(redact), a)),
+            redact,
+        )
+    }
+}
+
+impl<Action: ActionContract + YamlDump> YamlDump for AclChildDomainRuleBuilder<Action> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        acl_rule_to_yaml(
+            self.missed_action(),
+            self.iter().map(|(d, a)| (Yaml::String(d), a)),
+            redact,
+        )
+    }
+}
+
+impl<Action: ActionContract + YamlDump> YamlDump for AclNetworkRuleBuilder<Action> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        acl_rule_to_yaml(
+            self.missed_action(),
+            self.iter().map(|(n, a)| (n.dump_yaml(redact), a)),
+            redact,
+        )
+    }
+}
+
+impl YamlDump for AclRegexDomainRuleBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut prefix_map: BTreeMap<(AclAction, String), Vec<Yaml>> = BTreeMap::new();
+        for (parent, regex, action) in self.prefix_regex_iter() {
+            prefix_map
+                .entry((action, parent))
+                .or_default()
+                .push(Yaml::String(regex.to_string()));
+        }
+
+        let mut rules = Vec::new();
+        for (r, a) in self.full_regex_iter() {
+            rules.push((Yaml::String(r.to_string()), a));
+        }
+        for ((action, parent), mut regex) in prefix_map {
+            regex.sort();
+            let mut map = yaml::Hash::new();
+            map.insert(Yaml::String("parent".to_string()), Yaml::String(parent));
+            map.insert(Yaml::String("regex".to_string()), Yaml::Array(regex));
+            rules.push((Yaml::Hash(map), action));
+        }
+        acl_rule_to_yaml(self.missed_action(), rules, redact)
+    }
+}
+
+impl YamlDump for AclExactPortRule {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut action_ports: BTreeMap<AclAction, Vec<u16>> = BTreeMap::new();
+        for (port, action) in self.iter() {
+            action_ports.entry(action).or_default().push(port);
+        }
+
+        let mut rules = Vec::new();
+        for (action, mut ports) in action_ports {
+            ports.sort_unstable();
+            // merge the continuous ports into ranges
+            let mut start = ports[0];
+            let mut end = ports[0];
+            for port in ports.into_iter().skip(1).chain(std::iter::once(0)) {
+                if end < u16::MAX && port == end + 1 {
+                    end = port;
+                    continue;
+                }
+                let s = if start == end {
+                    start.to_string()
+                } else {
+                    format!("{start}-{end}")
+                };
+                rules.push((Yaml::String(s), action));
+                start = port;
+                end = port;
+            }
+        }
+        acl_rule_to_yaml(self.missed_action(), rules, redact)
+    }
+}
+
+impl YamlDump for AclProxyRequestRule {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        acl_rule_to_yaml(
+            self.missed_action(),
+            self.iter().map(|(r, a)| (r.dump_yaml(redact), a)),
+            redact,
+        )
+    }
+}
+
+impl YamlDump for AclRegexSetRuleBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        acl_rule_to_yaml(
+            self.missed_action(),
+            self.iter().map(|(r, a)| (Yaml::String(r.to_string()), a)),
+            redact,
+        )
+    }
+}
+
+impl YamlDump for AclUserAgentRule {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        acl_rule_to_yaml(
+            self.missed_action(),
+            self.iter().map(|(n, a)| (Yaml::String(n.to_string()), a)),
+            redact,
+        )
+    }
+}
+
+impl YamlDump for AclDstHostRuleSetBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("exact_match", &self.exact)
+            .set("child_match", &self.child)
+            .set("regex_match", &self.regex)
+            .set("subnet_match", &self.subnet);
+        map.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn exact_port() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+            default: permit
+            forbid: [22, "8000-8002", 8003, 65535]
+            forbid_log: 25
+            "#,
+        )
+        .unwrap();
+        let rule = crate::value::acl::as_exact_port_rule(&doc[0]).unwrap();
+        let v = rule.dump_yaml(false);
+        let expected = YamlLoader::load_from_str(
+            r#"
+            default: permit
+            forbid: ["22", "65535", "8000-8003"]
+            forbid_log: ["25"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(v, expected[0]);
+
+        let rule2 = crate::value::acl::as_exact_port_rule(&v).unwrap();
+        assert_eq!(rule2, rule);
+    }
+
+    #[test]
+    fn dst_host_rule_set() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+            exact_match:
+              default: forbid
+              permit: [example.net, 192.0.2.1]
+            child_match:
+              permit: example.org
+            regex_match:
+              permit:
+                - parent: example.com
+                  regex: ["^a.*", "^b.*"]
+                - ".*[.]test$"
+            subnet_match:
+              permit: 198.51.100.0/24
+            "#,
+        )
+        .unwrap();
+        let builder = crate::value::acl_set::as_dst_host_rule_set_builder(&doc[0]).unwrap();
+        let v = builder.dump_yaml(true);
+        let builder2 = crate::value::acl_set::as_dst_host_rule_set_builder(&v).unwrap();
+        assert_eq!(builder2, builder);
+        assert_eq!(v.dump_yaml(true), builder2.dump_yaml(true));
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::Yaml;
+
+use g3_types::auth::{Password, Username};
+
+use super::{REDACTED_VALUE, YamlDump};
+
+impl YamlDump for Username {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        if self.is_empty() {
+            Yaml::Null
+        } else {
+            Yaml::String(self.as_original().to_string())
+        }
+    }
+}
+
+impl YamlDump for Password {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        if self.is_empty() {
+            Yaml::Null
+        } else if redact {
+            Yaml::String(REDACTED_VALUE.to_string())
+        } else {
+            Yaml::String(self.as_original().to_string())
+        }
+    }
+}
@@ -0,0 +1,16 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::Yaml;
+
+use g3_types::collection::SelectivePickPolicy;
+
+use super::YamlDump;
+
+impl YamlDump for SelectivePickPolicy {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
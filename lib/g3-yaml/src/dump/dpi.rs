@@ -0,0 +1,252 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+
+use yaml_rust::{Yaml, yaml};
+
+use g3_dpi::{
+    H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, MaybeProtocol,
+    ProtocolInspectAction, ProtocolInspectPolicyBuilder, ProtocolInspectionConfig, ProtocolPortMap,
+    SmtpInterceptionConfig, WebSocketInterceptionConfig,
+};
+
+use super::acl::action_rules_to_yaml;
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for ProtocolInspectAction {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for MaybeProtocol {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for H1InterceptionConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("pipeline_size", &self.pipeline_size.get())
+            .set(
+                "pipeline_read_idle_timeout",
+                &self.pipeline_read_idle_timeout,
+            )
+            .set("req_header_recv_timeout", &self.req_head_recv_timeout)
+            .set("rsp_header_recv_timeout", &self.rsp_head_recv_timeout)
+            .set("req_header_max_size", &self.req_head_max_size)
+            .set("rsp_header_max_size", &self.rsp_head_max_size)
+            .set("body_line_max_length", &self.body_line_max_len)
+            .set("steal_forwarded_for", &self.steal_forwarded_for);
+        map.build()
+    }
+}
+
+impl YamlDump for H2InterceptionConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("max_header_list_size", &self.max_header_list_size)
+            .set("max_concurrent_streams", &self.max_concurrent_streams)
+            .set("max_frame_size", &self.max_frame_size())
+            .set("stream_window_size", &self.stream_window_size())
+            .set("connection_window_size", &self.connection_window_size())
+            .set("max_send_buffer_size", &self.max_send_buffer_size)
+            .set(
+                "upstream_handshake_timeout",
+                &self.upstream_handshake_timeout,
+            )
+            .set(
+                "upstream_stream_open_timeout",
+                &self.upstream_stream_open_timeout,
+            )
+            .set("client_handshake_timeout", &self.client_handshake_timeout)
+            .set("ping_interval", &self.ping_interval)
+            .set("rsp_header_recv_timeout", &self.rsp_head_recv_timeout)
+            .set("silent_drop_expect_header", &self.silent_drop_expect_header);
+        map.build()
+    }
+}
+
+impl YamlDump for ImapInterceptionConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("greeting_timeout", &self.greeting_timeout)
+            .set("authenticate_timeout", &self.authenticate_timeout)
+            .set("logout_wait_timeout", &self.logout_wait_timeout)
+            .set("command_line_max_size", &self.command_line_max_size)
+            .set("response_line_max_size", &self.response_line_max_size)
+            .set("forward_max_idle_count", &self.forward_max_idle_count)
+            .set("transfer_max_idle_count", &self.transfer_max_idle_count);
+        map.build()
+    }
+}
+
+impl YamlDump for SmtpInterceptionConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("greeting_timeout", &self.greeting_timeout)
+            .set("quit_wait_timeout", &self.quit_wait_timeout)
+            .set("command_wait_timeout", &self.command_wait_timeout)
+            .set("response_wait_timeout", &self.response_wait_timeout)
+            .set("data_initiation_timeout", &self.data_initiation_timeout)
+            .set("data_termination_timeout", &self.data_termination_timeout)
+            .set(
+                "allow_on_demand_mail_relay",
+                &self.allow_on_demand_mail_relay,
+            )
+            .set("allow_data_chunking", &self.allow_data_chunking)
+            .set("allow_burl_data", &self.allow_burl_data);
+        map.build()
+    }
+}
+
+impl YamlDump for WebSocketInterceptionConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("inspect_message", &self.inspect_message)
+            .set("message_buffer_max_size", &self.message_buffer_max_size)
+            .set("icap_adaptation_timeout", &self.icap_adaptation_timeout);
+        map.build()
+    }
+}
+
+impl YamlDump for ProtocolInspectionConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let size_limit = self.size_limit();
+        let mut limit_map = YamlMapDumper::new(redact);
+        limit_map
+            .set("ftp_greeting_msg", &size_limit.ftp_server_greeting_msg())
+            .set("http_request_uri", &size_limit.http_client_request_uri())
+            .set("imap_greeting_msg", &size_limit.imap_server_greeting_msg())
+            .set("nats_info_line", &size_limit.nats_server_info_line());
+
+        let mut map = YamlMapDumper::new(redact);
+        map.set("data0_buffer_size", &self.data0_buffer_size())
+            .set("inspect_max_depth", &self.max_depth())
+            .set("data0_wait_timeout", &self.data0_wait_timeout())
+            .set("data0_read_timeout", &self.data0_read_timeout())
+            .set_yaml("data0_size_limit", limit_map.build());
+        map.build()
+    }
+}
+
+impl YamlDump for ProtocolPortMap {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let sorted: BTreeMap<u16, _> = self.iter().collect();
+        let mut map = yaml::Hash::new();
+        for (port, v) in sorted {
+            let mut protocols: Vec<Yaml> =
+                v.protocols().iter().map(|p| p.dump_yaml(redact)).collect();
+            if v.check_ssl() {
+                protocols.push(MaybeProtocol::Ssl.dump_yaml(redact));
+            }
+            map.insert(Yaml::Integer(port as i64), Yaml::Array(protocols));
+        }
+        Yaml::Hash(map)
+    }
+}
+
+impl YamlDump for ProtocolInspectPolicyBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        // the inspect rules have no default action of their own
+        let mut map = YamlMapDumper::new(redact);
+        map.set("default", &self.missed_action());
+        if let Some(rule) = &self.exact {
+            let rules = rule.iter().map(|(h, a)| (h.dump_yaml(redact), a));
+            map.set_yaml(
+                "exact_match",
+                Yaml::Hash(action_rules_to_yaml(rules, redact)),
+            );
+        }
+        if let Some(rule) = &self.child {
+            let rules = rule.iter().map(|(d, a)| (Yaml::String(d), a));
+            map.set_yaml(
+                "child_match",
+                Yaml::Hash(action_rules_to_yaml(rules, redact)),
+            );
+        }
+        if let Some(rule) = &self.subnet {
+            let rules = rule.iter().map(|(n, a)| (n.dump_yaml(redact), a));
+            map.set_yaml(
+                "subnet_match",
+                Yaml::Hash(action_rules_to_yaml(rules, redact)),
+            );
+        }
+        map.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn portmap() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+            443: https
+            8080: [http, ssl]
+            25: smtp
+            "#,
+        )
+        .unwrap();
+        let mut portmap = ProtocolPortMap::empty();
+        crate::value::update_protocol_portmap(&mut portmap, &doc[0]).unwrap();
+        let v = portmap.dump_yaml(false);
+
+        let mut portmap2 = ProtocolPortMap::empty();
+        crate::value::update_protocol_portmap(&mut portmap2, &v).unwrap();
+        assert_eq!(portmap2, portmap);
+        assert_eq!(
+            v[443],
+            Yaml::Array(vec![
+                Yaml::String("http".to_string()),
+                Yaml::String("ssl".to_string())
+            ])
+        );
+    }
+
+    #[test]
+    fn inspection_config() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+            data0_buffer_size: 8192
+            inspect_max_depth: 2
+            data0_size_limit:
+              http_request_uri: 1024
+            "#,
+        )
+        .unwrap();
+        let config = crate::value::as_protocol_inspection_config(&doc[0]).unwrap();
+        let v = config.dump_yaml(true);
+        let config2 = crate::value::as_protocol_inspection_config(&v).unwrap();
+        assert_eq!(config2.data0_buffer_size(), 8192);
+        assert_eq!(config2.max_depth(), 2);
+        assert_eq!(config2.size_limit().http_client_request_uri(), 1024);
+        assert_eq!(v, config2.dump_yaml(true));
+    }
+
+    #[test]
+    fn inspect_policy() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+            default: bypass
+            exact_match:
+              block: example.net
+            child_match:
+              intercept: example.org
+            "#,
+        )
+        .unwrap();
+        let builder = crate::value::as_protocol_inspect_policy_builder(&doc[0]).unwrap();
+        let v = builder.dump_yaml(true);
+        assert_eq!(v["default"], Yaml::String("bypass".to_string()));
+        let builder2 = crate::value::as_protocol_inspect_policy_builder(&v).unwrap();
+        assert_eq!(builder2.dump_yaml(true), v);
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::Yaml;
+
+use g3_geoip_types::{ContinentCode, IsoCountryCode};
+
+use super::YamlDump;
+
+impl YamlDump for IsoCountryCode {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.alpha2_code().to_string())
+    }
+}
+
+impl YamlDump for ContinentCode {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.code().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn geo_codes() {
+        let v = IsoCountryCode::CN.dump_yaml(false);
+        assert_eq!(
+            crate::value::as_iso_country_code(&v).unwrap(),
+            IsoCountryCode::CN
+        );
+        let v = ContinentCode::EU.dump_yaml(false);
+        assert_eq!(
+            crate::value::as_continent_code(&v).unwrap(),
+            ContinentCode::EU
+        );
+    }
+}
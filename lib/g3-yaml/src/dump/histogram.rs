@@ -0,0 +1,27 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::Yaml;
+
+use g3_histogram::{HistogramMetricsConfig, Quantile};
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for Quantile {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for HistogramMetricsConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        if !self.quantile_list().is_empty() {
+            map.set("quantile", self.quantile_list());
+        }
+        map.set("rotate", &self.rotate_interval());
+        map.build()
+    }
+}
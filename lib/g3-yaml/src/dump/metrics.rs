@@ -0,0 +1,53 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::collection::WeightedValue;
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue, NodeName};
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for NodeName {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        if self.is_empty() {
+            return Yaml::Null;
+        }
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for MetricTagName {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for MetricTagValue {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+/// The tags are sorted by name, so the output is stable
+impl YamlDump for MetricTagMap {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        let mut tags = self.iter().collect::<Vec<_>>();
+        tags.sort_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        let mut map = yaml::Hash::with_capacity(tags.len());
+        for (k, v) in tags {
+            map.insert(Yaml::String(k.to_string()), Yaml::String(v.to_string()));
+        }
+        Yaml::Hash(map)
+    }
+}
+
+impl YamlDump for WeightedValue<NodeName> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("name", self.inner()).set("weight", &self.weight());
+        map.build()
+    }
+}
@@ -0,0 +1,448 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! Dump the parsed config values back to yaml.
+//!
+//! The output uses the same keys and value formats that the parse functions in
+//! [`crate::value`] and [`crate::humanize`] accept.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use ascii::AsciiString;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rand::distr::Bernoulli;
+use yaml_rust::{Yaml, YamlEmitter, yaml};
+
+use g3_types::fs::ConfigFileFormat;
+
+#[cfg(feature = "acl-rule")]
+mod acl;
+mod auth;
+mod collection;
+#[cfg(feature = "dpi")]
+mod dpi;
+#[cfg(feature = "geoip")]
+mod geoip;
+#[cfg(feature = "histogram")]
+mod histogram;
+mod metrics;
+mod net;
+#[cfg(feature = "openssl")]
+mod openssl;
+#[cfg(feature = "quinn")]
+mod quinn;
+#[cfg(feature = "resolve")]
+mod resolve;
+#[cfg(feature = "route")]
+mod route;
+#[cfg(feature = "route")]
+pub use route::host_matched_obj_to_yaml;
+#[cfg(feature = "rustls")]
+mod rustls;
+mod speed_limit;
+
+pub const REDACTED_VALUE: &str = "******";
+
+pub trait YamlDump {
+    /// Dump the value as yaml.
+    ///
+    /// Secret values should be replaced by [`REDACTED_VALUE`] if `redact` is set.
+    /// `Yaml::Null` means the value is not set.
+    fn dump_yaml(&self, redact: bool) -> Yaml;
+}
+
+/// Build a yaml map, the entries with null value will be skipped.
+pub struct YamlMapDumper {
+    map: yaml::Hash,
+    redact: bool,
+}
+
+impl YamlMapDumper {
+    pub fn new(redact: bool) -> Self {
+        YamlMapDumper {
+            map: yaml::Hash::new(),
+            redact,
+        }
+    }
+
+    #[inline]
+    pub fn redact(&self) -> bool {
+        self.redact
+    }
+
+    pub fn set<T: YamlDump + ?Sized>(&mut self, key: &str, value: &T) -> &mut Self {
+        let v = value.dump_yaml(self.redact);
+        self.set_yaml(key, v)
+    }
+
+    /// Set a secret value, which will be replaced by [`REDACTED_VALUE`] if redact is enabled
+    pub fn set_secret<T: YamlDump + ?Sized>(&mut self, key: &str, value: &T) -> &mut Self {
+        let v = value.dump_yaml(self.redact);
+        if self.redact && !v.is_null() {
+            self.set_yaml(key, Yaml::String(REDACTED_VALUE.to_string()))
+        } else {
+            self.set_yaml(key, v)
+        }
+    }
+
+    pub fn set_yaml(&mut self, key: &str, value: Yaml) -> &mut Self {
+        if !value.is_null() {
+            self.map.insert(Yaml::String(key.to_string()), value);
+        }
+        self
+    }
+
+    /// Set an explicit null value, for keys whose presence is meaningful on its own
+    pub fn set_null(&mut self, key: &str) -> &mut Self {
+        self.map.insert(Yaml::String(key.to_string()), Yaml::Null);
+        self
+    }
+
+    pub fn build(self) -> Yaml {
+        Yaml::Hash(self.map)
+    }
+}
+
+pub fn emit_to_string(doc: &Yaml) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(4096);
+    YamlEmitter::new(&mut out)
+        .dump(doc)
+        .map_err(|e| anyhow!("failed to emit yaml: {e}"))?;
+    match out.strip_prefix("---\n") {
+        Some(s) => Ok(s.to_string()),
+        None => Ok(out),
+    }
+}
+
+pub fn duration_to_yaml(d: Duration) -> Yaml {
+    if d.subsec_nanos() == 0 {
+        return Yaml::Integer(i64::try_from(d.as_secs()).unwrap_or(i64::MAX));
+    }
+    if d.subsec_nanos().is_multiple_of(1_000_000) {
+        return Yaml::String(format!("{}ms", d.as_millis()));
+    }
+    if d.subsec_nanos().is_multiple_of(1_000) {
+        return Yaml::String(format!("{}us", d.as_micros()));
+    }
+    Yaml::String(format!("{}ns", d.as_nanos()))
+}
+
+/// Encode the DER data as a PEM block, which is accepted by all the certificate and key parsers
+pub fn pem_encode(label: &str, der: &[u8]) -> String {
+    let b64 = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut s = String::with_capacity(b64.len() + b64.len() / 64 + 2 * label.len() + 32);
+    s.push_str("-----BEGIN ");
+    s.push_str(label);
+    s.push_str("-----\n");
+    for line in b64.as_bytes().chunks(64) {
+        // base64 output is always ascii
+        s.push_str(std::str::from_utf8(line).unwrap());
+        s.push('\n');
+    }
+    s.push_str("-----END ");
+    s.push_str(label);
+    s.push_str("-----\n");
+    s
+}
+
+/// Dump a list of DER encoded certificates as a single PEM string
+pub fn certificates_to_yaml<'a, I>(certs: I) -> Yaml
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    let mut s = String::new();
+    for der in certs {
+        s.push_str(&pem_encode("CERTIFICATE", der));
+    }
+    if s.is_empty() {
+        Yaml::Null
+    } else {
+        Yaml::String(s)
+    }
+}
+
+fn integer<T: TryInto<i64>>(v: T) -> Yaml {
+    match v.try_into() {
+        Ok(i) => Yaml::Integer(i),
+        Err(_) => Yaml::Integer(i64::MAX),
+    }
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl YamlDump for $t {
+                fn dump_yaml(&self, _redact: bool) -> Yaml {
+                    integer(*self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i32, i64);
+
+impl YamlDump for NonZeroU32 {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        integer(self.get())
+    }
+}
+
+impl YamlDump for NonZeroUsize {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        integer(self.get())
+    }
+}
+
+impl YamlDump for f64 {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        // keep the fraction part so that the value will be loaded as real again
+        Yaml::Real(format!("{self:?}"))
+    }
+}
+
+impl YamlDump for Yaml {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        self.clone()
+    }
+}
+
+impl YamlDump for bool {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::Boolean(*self)
+    }
+}
+
+impl YamlDump for str {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for String {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.clone())
+    }
+}
+
+impl YamlDump for AsciiString {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for Path {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.display().to_string())
+    }
+}
+
+impl YamlDump for PathBuf {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        self.as_path().dump_yaml(redact)
+    }
+}
+
+impl YamlDump for Duration {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        duration_to_yaml(*self)
+    }
+}
+
+macro_rules! impl_display {
+    ($($t:ty),*) => {
+        $(
+            impl YamlDump for $t {
+                fn dump_yaml(&self, _redact: bool) -> Yaml {
+                    Yaml::String(self.to_string())
+                }
+            }
+        )*
+    };
+}
+
+impl_display!(IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr);
+
+impl YamlDump for DateTime<Utc> {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_rfc3339())
+    }
+}
+
+impl YamlDump for Bernoulli {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        self.p().dump_yaml(redact)
+    }
+}
+
+impl YamlDump for ConfigFileFormat {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl<T: YamlDump + ?Sized> YamlDump for &T {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        (**self).dump_yaml(redact)
+    }
+}
+
+impl<T: YamlDump + ?Sized> YamlDump for Box<T> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        self.as_ref().dump_yaml(redact)
+    }
+}
+
+impl<T: YamlDump + ?Sized> YamlDump for Arc<T> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        self.as_ref().dump_yaml(redact)
+    }
+}
+
+impl<T: YamlDump> YamlDump for Option<T> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        match self {
+            Some(v) => v.dump_yaml(redact),
+            None => Yaml::Null,
+        }
+    }
+}
+
+impl<T: YamlDump> YamlDump for [T] {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        Yaml::Array(self.iter().map(|v| v.dump_yaml(redact)).collect())
+    }
+}
+
+impl<T: YamlDump> YamlDump for Vec<T> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        self.as_slice().dump_yaml(redact)
+    }
+}
+
+impl<T: YamlDump> YamlDump for BTreeSet<T> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        Yaml::Array(self.iter().map(|v| v.dump_yaml(redact)).collect())
+    }
+}
+
+/// The items are sorted, so the output is stable
+impl<T: YamlDump, S> YamlDump for HashSet<T, S> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut items = self.iter().map(|v| v.dump_yaml(redact)).collect::<Vec<_>>();
+        items.sort();
+        Yaml::Array(items)
+    }
+}
+
+impl<K: YamlDump, V: YamlDump> YamlDump for BTreeMap<K, V> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = yaml::Hash::with_capacity(self.len());
+        for (k, v) in self {
+            map.insert(k.dump_yaml(redact), v.dump_yaml(redact));
+        }
+        Yaml::Hash(map)
+    }
+}
+
+/// The entries are sorted by key, so the output is stable
+impl<K: YamlDump, V: YamlDump, S> YamlDump for HashMap<K, V, S> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut entries = self
+            .iter()
+            .map(|(k, v)| (k.dump_yaml(redact), v.dump_yaml(redact)))
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Yaml::Hash(entries.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duration() {
+        assert_eq!(duration_to_yaml(Duration::from_secs(10)), Yaml::Integer(10));
+        assert_eq!(
+            duration_to_yaml(Duration::from_millis(1500)),
+            Yaml::String("1500ms".to_string())
+        );
+        assert_eq!(
+            duration_to_yaml(Duration::from_micros(10)),
+            Yaml::String("10us".to_string())
+        );
+        assert_eq!(
+            duration_to_yaml(Duration::from_nanos(10)),
+            Yaml::String("10ns".to_string())
+        );
+
+        for d in [
+            Duration::ZERO,
+            Duration::from_secs(3600),
+            Duration::from_millis(1),
+            Duration::from_micros(1500),
+            Duration::from_nanos(1_000_000_001),
+        ] {
+            let v = duration_to_yaml(d);
+            assert_eq!(crate::humanize::as_duration(&v).unwrap(), d);
+        }
+    }
+
+    #[test]
+    fn map_dumper() {
+        let mut dumper = YamlMapDumper::new(true);
+        dumper
+            .set("a", &1u32)
+            .set("b", &None::<u32>)
+            .set_secret("c", "password")
+            .set_secret("d", &None::<String>)
+            .set("e", &vec!["x".to_string()]);
+        let Yaml::Hash(map) = dumper.build() else {
+            panic!("not a map");
+        };
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&Yaml::String("a".to_string())], Yaml::Integer(1));
+        assert_eq!(
+            map[&Yaml::String("c".to_string())],
+            Yaml::String(REDACTED_VALUE.to_string())
+        );
+
+        let mut dumper = YamlMapDumper::new(false);
+        dumper.set_secret("c", "password");
+        let Yaml::Hash(map) = dumper.build() else {
+            panic!("not a map");
+        };
+        assert_eq!(
+            map[&Yaml::String("c".to_string())],
+            Yaml::String("password".to_string())
+        );
+    }
+
+    #[test]
+    fn sorted() {
+        let set: HashSet<u32> = [3, 1, 2].into_iter().collect();
+        assert_eq!(
+            set.dump_yaml(false),
+            Yaml::Array(vec![Yaml::Integer(1), Yaml::Integer(2), Yaml::Integer(3)])
+        );
+
+        let map: HashMap<String, u32> = [("b".to_string(), 2), ("a".to_string(), 1)]
+            .into_iter()
+            .collect();
+        let Yaml::Hash(map) = map.dump_yaml(false) else {
+            panic!("not a map");
+        };
+        let keys = map.keys().map(|k| k.as_str().unwrap()).collect::<Vec<_>>();
+        assert_eq!(keys, ["a", "b"]);
+    }
+}
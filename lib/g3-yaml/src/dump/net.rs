@@ -0,0 +1,297 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+
+use yaml_rust::Yaml;
+
+use g3_types::collection::WeightedValue;
+use g3_types::net::{
+    ConnectionPoolConfig, HappyEyeballsConfig, Host, PortRange, ProxyProtocolVersion,
+    ProxyRequestType, SocketBufferConfig, TcpConnectConfig, TcpKeepAliveConfig, TcpListenConfig,
+    TcpMiscSockOpts, TlsVersion, UdpListenConfig, UdpMiscSockOpts, UpstreamAddr,
+};
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for Host {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for UpstreamAddr {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for WeightedValue<UpstreamAddr> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("addr", self.inner()).set("weight", &self.weight());
+        map.build()
+    }
+}
+
+impl YamlDump for WeightedValue<SocketAddr> {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("addr", self.inner()).set("weight", &self.weight());
+        map.build()
+    }
+}
+
+#[cfg(feature = "acl-rule")]
+impl YamlDump for ip_network::IpNetwork {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+impl YamlDump for url::Url {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_string())
+    }
+}
+
+#[cfg(unix)]
+impl YamlDump for g3_types::net::Interface {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.name().to_string())
+    }
+}
+
+impl YamlDump for SocketBufferConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("recv", &self.recv_size())
+            .set("send", &self.send_size());
+        map.build()
+    }
+}
+
+impl YamlDump for ProxyProtocolVersion {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::Integer(self.as_u8() as i64)
+    }
+}
+
+impl YamlDump for ProxyRequestType {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for TlsVersion {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for PortRange {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("start", &self.start()).set("end", &self.end());
+        map.build()
+    }
+}
+
+impl YamlDump for ConnectionPoolConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("check_interval", &self.check_interval())
+            .set("max_idle_count", &self.max_idle_count())
+            .set("min_idle_count", &self.min_idle_count())
+            .set("idle_timeout", &self.idle_timeout());
+        map.build()
+    }
+}
+
+impl YamlDump for TcpKeepAliveConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("enable", &self.is_enabled())
+            .set("idle_time", &self.idle_time())
+            .set("probe_interval", &self.probe_interval())
+            .set("probe_count", &self.probe_count());
+        map.build()
+    }
+}
+
+impl YamlDump for TcpConnectConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("max_retry", &self.max_tries().saturating_sub(1))
+            .set("each_timeout", &self.each_timeout());
+        map.build()
+    }
+}
+
+impl YamlDump for HappyEyeballsConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("resolution_delay", &self.resolution_delay())
+            .set(
+                "second_resolution_timeout",
+                &self.second_resolution_timeout(),
+            )
+            .set(
+                "first_address_family_count",
+                &self.first_address_family_count(),
+            )
+            .set("connection_attempt_delay", &self.connection_attempt_delay());
+        map.build()
+    }
+}
+
+impl YamlDump for TcpListenConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("address", &self.address());
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("interface", &self.interface());
+        map.set("backlog", &self.backlog());
+        #[cfg(not(target_os = "openbsd"))]
+        map.set("ipv6_only", &self.is_ipv6only());
+        map.set("instance", &self.instance());
+        #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+        map.set("netfilter_mark", &self.mark());
+        map.set("follow_cpu_affinity", &self.follow_cpu_affinity())
+            .set("keepalive", &self.keepalive());
+        map.build()
+    }
+}
+
+impl YamlDump for TcpMiscSockOpts {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("no_delay", &self.no_delay)
+            .set("max_segment_size", &self.max_segment_size)
+            .set("time_to_live", &self.time_to_live)
+            .set("hop_limit", &self.hop_limit)
+            .set("type_of_service", &self.type_of_service);
+        #[cfg(not(windows))]
+        map.set("traffic_class", &self.traffic_class);
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "freebsd",
+            target_os = "solaris",
+            target_os = "illumos"
+        ))]
+        map.set(
+            "congestion_control",
+            &self
+                .congestion_control()
+                .map(|v| String::from_utf8_lossy(v).to_string()),
+        );
+        #[cfg(target_os = "linux")]
+        map.set("netfilter_mark", &self.netfilter_mark);
+        map.build()
+    }
+}
+
+impl YamlDump for UdpMiscSockOpts {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("time_to_live", &self.time_to_live)
+            .set("hop_limit", &self.hop_limit)
+            .set("type_of_service", &self.type_of_service);
+        #[cfg(not(windows))]
+        map.set("traffic_class", &self.traffic_class);
+        #[cfg(target_os = "linux")]
+        map.set("netfilter_mark", &self.netfilter_mark);
+        map.build()
+    }
+}
+
+impl YamlDump for UdpListenConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("address", &self.address());
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "macos",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))]
+        map.set("interface", &self.interface());
+        #[cfg(not(target_os = "openbsd"))]
+        map.set("ipv6_only", &self.is_ipv6only());
+        map.set("socket_buffer", &self.socket_buffer())
+            .set("socket_misc_opts", &self.socket_misc_opts())
+            .set("instance", &self.instance());
+        map.build()
+    }
+}
+
+#[cfg(feature = "http")]
+mod http {
+    use yaml_rust::Yaml;
+
+    use g3_types::net::{
+        Http2ConnectPoolConfig, HttpForwardCapability, HttpForwardedHeaderType,
+        HttpKeepAliveConfig, HttpServerId,
+    };
+
+    use super::{YamlDump, YamlMapDumper};
+
+    impl YamlDump for HttpKeepAliveConfig {
+        fn dump_yaml(&self, redact: bool) -> Yaml {
+            let mut map = YamlMapDumper::new(redact);
+            map.set("enable", &self.is_enabled())
+                .set("idle_expire", &self.idle_expire());
+            map.build()
+        }
+    }
+
+    impl YamlDump for HttpForwardedHeaderType {
+        fn dump_yaml(&self, _redact: bool) -> Yaml {
+            Yaml::String(self.as_str().to_string())
+        }
+    }
+
+    impl YamlDump for HttpForwardCapability {
+        fn dump_yaml(&self, redact: bool) -> Yaml {
+            let mut map = YamlMapDumper::new(redact);
+            map.set("forward_https", &self.forward_https())
+                .set("forward_ftp", &self.forward_ftp_all())
+                .set("forward_ftp_get", &self.forward_ftp(&http::Method::GET))
+                .set("forward_ftp_put", &self.forward_ftp(&http::Method::PUT))
+                .set("forward_ftp_del", &self.forward_ftp(&http::Method::DELETE));
+            map.build()
+        }
+    }
+
+    impl YamlDump for Http2ConnectPoolConfig {
+        fn dump_yaml(&self, redact: bool) -> Yaml {
+            let mut map = YamlMapDumper::new(redact);
+            map.set("max_connections", &self.max_connections())
+                .set("max_streams", &self.max_streams())
+                .set("idle_timeout", &self.idle_timeout())
+                .set("fallback_duration", &self.fallback_duration());
+            map.build()
+        }
+    }
+
+    impl YamlDump for HttpServerId {
+        fn dump_yaml(&self, _redact: bool) -> Yaml {
+            Yaml::String(self.as_str().to_string())
+        }
+    }
+
+    impl YamlDump for http::HeaderName {
+        fn dump_yaml(&self, _redact: bool) -> Yaml {
+            Yaml::String(self.as_str().to_string())
+        }
+    }
+}
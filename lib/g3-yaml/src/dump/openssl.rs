@@ -0,0 +1,193 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use openssl::pkey::PKey;
+use yaml_rust::Yaml;
+
+use g3_types::net::{
+    OpensslCertificatePair, OpensslClientConfigBuilder, OpensslInterceptionClientConfigBuilder,
+    OpensslInterceptionServerConfigBuilder, OpensslProtocol, OpensslRevocationConfig,
+    OpensslRevocationMode, OpensslServerConfigBuilder, OpensslTlcpCertificatePair,
+};
+
+use super::{YamlDump, YamlMapDumper};
+
+fn certificates_to_yaml(leaf: &[u8], chain: &[Vec<u8>]) -> Yaml {
+    super::certificates_to_yaml(std::iter::once(leaf).chain(chain.iter().map(|c| c.as_slice())))
+}
+
+fn private_key_to_yaml(der: &[u8]) -> Yaml {
+    match PKey::private_key_from_der(der).and_then(|key| key.private_key_to_pem_pkcs8()) {
+        Ok(pem) => Yaml::String(String::from_utf8_lossy(&pem).to_string()),
+        Err(_) => Yaml::String(super::pem_encode("PRIVATE KEY", der)),
+    }
+}
+
+fn non_empty_str(s: &str) -> Option<&str> {
+    if s.is_empty() { None } else { Some(s) }
+}
+
+impl YamlDump for OpensslProtocol {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for OpensslRevocationMode {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for OpensslRevocationConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut crl = String::new();
+        for der in self.crl_list() {
+            crl.push_str(&super::pem_encode("X509 CRL", der));
+        }
+
+        let mut map = YamlMapDumper::new(redact);
+        map.set("mode", &self.mode())
+            .set("crl", &non_empty_str(&crl))
+            .set("fetch_ocsp", &self.fetch_ocsp())
+            .set("fetch_crl", &self.fetch_crl())
+            .set("fetch_timeout", &self.fetch_timeout())
+            .set("cache_ttl", &self.cache_ttl())
+            .set("cache_capacity", &self.cache_capacity());
+        map.build()
+    }
+}
+
+impl YamlDump for OpensslCertificatePair {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set_yaml(
+            "certificate",
+            certificates_to_yaml(self.leaf_cert_der(), self.chain_certs_der()),
+        )
+        .set_secret("private_key", &private_key_to_yaml(self.private_key_der()));
+        map.build()
+    }
+}
+
+impl YamlDump for OpensslTlcpCertificatePair {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set_yaml(
+            "sign_certificate",
+            certificates_to_yaml(self.sign_leaf_cert_der(), self.chain_certs_der()),
+        )
+        .set_yaml(
+            "enc_certificate",
+            certificates_to_yaml(self.enc_leaf_cert_der(), &[]),
+        )
+        .set_secret(
+            "sign_private_key",
+            &private_key_to_yaml(self.sign_private_key_der()),
+        )
+        .set_secret(
+            "enc_private_key",
+            &private_key_to_yaml(self.enc_private_key_der()),
+        );
+        map.build()
+    }
+}
+
+macro_rules! dump_common_client_config {
+    ($map:ident, $config:ident) => {
+        $map.set("min_tls_version", &$config.min_tls_version())
+            .set("max_tls_version", &$config.max_tls_version())
+            .set_yaml(
+                "ca_certificate",
+                super::certificates_to_yaml($config.ca_certificates().iter().map(|c| c.as_slice())),
+            )
+            .set(
+                "no_default_ca_certificate",
+                &$config.no_default_ca_certificates(),
+            )
+            .set("handshake_timeout", &$config.handshake_timeout())
+            .set("no_session_cache", &$config.no_session_cache())
+            .set(
+                "session_cache_lru_max_sites",
+                &$config.session_cache_sites_count(),
+            )
+            .set(
+                "session_cache_each_capacity",
+                &$config.session_cache_each_capacity(),
+            )
+            .set(
+                "supported_groups",
+                &non_empty_str($config.supported_groups()),
+            )
+            .set("use_ocsp_stapling", &$config.use_ocsp_stapling())
+            .set("cert_revocation", &$config.revocation_config())
+            .set("enable_sct", &$config.enable_sct())
+            .set("enable_grease", &$config.enable_grease())
+            .set("permute_extensions", &$config.permute_extensions())
+            .set("insecure", &$config.insecure());
+    };
+}
+
+impl YamlDump for OpensslClientConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("protocol", &self.protocol());
+        if !self.ciphers().is_empty() {
+            map.set("ciphers", self.ciphers());
+        }
+        map.set("disable_sni", &self.disable_sni())
+            .set("cert_pair", &self.cert_pair())
+            .set("tlcp_cert_pair", &self.tlcp_cert_pair())
+            .set(
+                "use_builtin_session_cache",
+                &self.use_builtin_session_cache(),
+            );
+        dump_common_client_config!(map, self);
+        map.build()
+    }
+}
+
+impl YamlDump for OpensslInterceptionClientConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        dump_common_client_config!(map, self);
+        map.build()
+    }
+}
+
+impl YamlDump for OpensslServerConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        if !self.cert_pairs().is_empty() {
+            map.set("cert_pairs", self.cert_pairs());
+        }
+        if !self.tlcp_cert_pairs().is_empty() {
+            map.set("tlcp_cert_pairs", self.tlcp_cert_pairs());
+        }
+        map.set("enable_client_auth", &self.client_auth())
+            .set(
+                "session_id_context",
+                &non_empty_str(self.session_id_context()),
+            )
+            .set("no_session_ticket", &self.disable_session_ticket())
+            .set("no_session_cache", &self.disable_session_cache())
+            .set_yaml(
+                "ca_certificate",
+                super::certificates_to_yaml(
+                    self.client_auth_certificates().iter().map(|c| c.as_slice()),
+                ),
+            )
+            .set("handshake_timeout", &self.accept_timeout());
+        map.build()
+    }
+}
+
+impl YamlDump for OpensslInterceptionServerConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("handshake_timeout", &self.accept_timeout());
+        map.build()
+    }
+}
@@ -0,0 +1,22 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::Yaml;
+
+use g3_types::net::QuinnTransportConfigBuilder;
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for QuinnTransportConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("max_idle_timeout", &self.max_idle_timeout())
+            .set("keep_alive_interval", &self.keep_alive_interval())
+            .set("stream_receive_window", &self.stream_receive_window())
+            .set("receive_window", &self.receive_window())
+            .set("send_window", &self.send_window());
+        map.build()
+    }
+}
@@ -0,0 +1,108 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::resolve::{
+    PickStrategy, QueryStrategy, ResolveRedirectionBuilder, ResolveRedirectionValue,
+    ResolveStrategy,
+};
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for QueryStrategy {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for PickStrategy {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for ResolveStrategy {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("query", &self.query).set("pick", &self.pick);
+        map.build()
+    }
+}
+
+impl YamlDump for ResolveRedirectionValue {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        match self {
+            ResolveRedirectionValue::Domain(domain) => Yaml::String(domain.to_string()),
+            ResolveRedirectionValue::Ip((ip4, ip6)) => Yaml::Array(
+                ip4.iter()
+                    .chain(ip6)
+                    .map(|ip| ip.dump_yaml(redact))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl YamlDump for ResolveRedirectionBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let to_record = |key: &str, domain: &str, to: Yaml| {
+            let mut map = yaml::Hash::new();
+            map.insert(
+                Yaml::String(key.to_string()),
+                Yaml::String(domain.to_string()),
+            );
+            map.insert(Yaml::String("to".to_string()), to);
+            Yaml::Hash(map)
+        };
+
+        let mut exact: Vec<_> = self.exact_iter().collect();
+        exact.sort_by_key(|(domain, _)| *domain);
+        let mut parent: Vec<_> = self.parent_iter().collect();
+        parent.sort_unstable();
+
+        let mut records = Vec::with_capacity(exact.len() + parent.len());
+        for (domain, value) in exact {
+            records.push(to_record("exact", domain, value.dump_yaml(redact)));
+        }
+        for (domain, to) in parent {
+            records.push(to_record("parent", domain, Yaml::String(to.to_string())));
+        }
+        Yaml::Array(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn redirection() {
+        let doc = YamlLoader::load_from_str(
+            r#"
+            - exact: a.example.net
+              to: [192.0.2.1, "2001:db8::1"]
+            - exact: b.example.net
+              to: c.example.net
+            - parent: example.org
+              to: example.com
+            "#,
+        )
+        .unwrap();
+        let builder = crate::value::as_resolve_redirection_builder(&doc[0]).unwrap();
+        let v = builder.dump_yaml(false);
+        assert_eq!(v, doc[0]);
+        let builder2 = crate::value::as_resolve_redirection_builder(&v).unwrap();
+        assert!(builder2 == builder);
+    }
+
+    #[test]
+    fn strategy() {
+        let doc = YamlLoader::load_from_str("{query: ipv6_only, pick: serial}").unwrap();
+        let strategy = crate::value::as_resolve_strategy(&doc[0]).unwrap();
+        assert_eq!(strategy.dump_yaml(false), doc[0]);
+    }
+}
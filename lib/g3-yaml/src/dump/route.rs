@@ -0,0 +1,149 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use yaml_rust::Yaml;
+
+use g3_types::route::HostMatch;
+
+use super::YamlMapDumper;
+
+#[derive(Default)]
+struct HostMatchedGroup {
+    exact_match: Vec<String>,
+    child_match: Vec<String>,
+    set_default: bool,
+}
+
+fn group_index<'a, T>(groups: &mut Vec<(&'a Arc<T>, HostMatchedGroup)>, v: &'a Arc<T>) -> usize {
+    match groups.iter().position(|(t, _)| Arc::ptr_eq(t, v)) {
+        Some(i) => i,
+        None => {
+            groups.push((v, HostMatchedGroup::default()));
+            groups.len() - 1
+        }
+    }
+}
+
+/// Dump the value in the format accepted by [`crate::value::as_host_matched_obj`].
+///
+/// The hosts that share the same value are merged into one entry,
+/// and `dump_kv` should add the keys of the value itself.
+pub fn host_matched_obj_to_yaml<T, F>(obj: &HostMatch<Arc<T>>, redact: bool, dump_kv: F) -> Yaml
+where
+    F: Fn(&T, &mut YamlMapDumper),
+{
+    let mut groups: Vec<(&Arc<T>, HostMatchedGroup)> = Vec::new();
+
+    let mut exact = Vec::new();
+    for (domain, v) in obj.exact_domains() {
+        exact.push((group_index(&mut groups, v), domain.to_string()));
+    }
+    for (ip, v) in obj.exact_ips() {
+        exact.push((group_index(&mut groups, v), ip.to_string()));
+    }
+    let mut child = Vec::new();
+    for (domain, v) in obj.child_domains() {
+        child.push((group_index(&mut groups, v), domain));
+    }
+    let default = obj.get_default().map(|v| group_index(&mut groups, v));
+
+    for (i, host) in exact {
+        groups[i].1.exact_match.push(host);
+    }
+    for (i, domain) in child {
+        groups[i].1.child_match.push(domain);
+    }
+    if let Some(i) = default {
+        groups[i].1.set_default = true;
+    }
+
+    let mut seq = groups
+        .into_iter()
+        .map(|(v, mut group)| {
+            group.exact_match.sort();
+            group.child_match.sort();
+
+            let mut map = YamlMapDumper::new(redact);
+            if !group.exact_match.is_empty() {
+                map.set("exact_match", &group.exact_match);
+            }
+            if !group.child_match.is_empty() {
+                map.set("child_match", &group.child_match);
+            }
+            if group.set_default && !(group.exact_match.is_empty() && group.child_match.is_empty())
+            {
+                map.set("set_default", &true);
+            }
+            dump_kv(v, &mut map);
+            map.build()
+        })
+        .collect::<Vec<_>>();
+    // the hash tables have no stable order
+    seq.sort();
+    Yaml::Array(seq)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    use crate::{YamlDocPosition, YamlMapCallback};
+
+    #[derive(Default)]
+    struct TestValue {
+        value: i64,
+    }
+
+    impl YamlMapCallback for TestValue {
+        fn type_name(&self) -> &'static str {
+            "TestValue"
+        }
+
+        fn parse_kv(
+            &mut self,
+            _key: &str,
+            value: &Yaml,
+            _doc: Option<&YamlDocPosition>,
+        ) -> anyhow::Result<()> {
+            self.value = crate::value::as_i64(value)?;
+            Ok(())
+        }
+
+        fn check(&mut self) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let docs = YamlLoader::load_from_str(
+            r#"
+            - exact_match: [www.example.com, 192.168.0.1]
+              child_match: example.net
+              value: 1
+            - child_match: example.org
+              value: 2
+            - value: 3
+            "#,
+        )
+        .unwrap();
+        let obj = crate::value::as_host_matched_obj::<TestValue>(&docs[0], None).unwrap();
+
+        let dump_kv = |v: &TestValue, map: &mut YamlMapDumper| {
+            map.set("value", &v.value);
+        };
+        let dumped = host_matched_obj_to_yaml(&obj, false, dump_kv);
+        let Yaml::Array(seq) = &dumped else {
+            panic!("not an array");
+        };
+        assert_eq!(seq.len(), 3);
+
+        let obj2 = crate::value::as_host_matched_obj::<TestValue>(&dumped, None).unwrap();
+        assert_eq!(host_matched_obj_to_yaml(&obj2, false, dump_kv), dumped);
+    }
+}
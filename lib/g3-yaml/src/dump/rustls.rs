@@ -0,0 +1,97 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use yaml_rust::Yaml;
+
+use g3_types::net::{
+    DnsEncryptionConfigBuilder, DnsEncryptionProtocol, RustlsCertificatePair,
+    RustlsClientConfigBuilder, RustlsServerConfigBuilder,
+};
+
+use super::{YamlDump, YamlMapDumper};
+
+fn certificates_to_yaml(certs: &[CertificateDer<'static>]) -> Yaml {
+    super::certificates_to_yaml(certs.iter().map(|c| c.as_ref()))
+}
+
+fn private_key_to_yaml(key: &PrivateKeyDer<'_>) -> Yaml {
+    let label = match key {
+        PrivateKeyDer::Pkcs1(_) => "RSA PRIVATE KEY",
+        PrivateKeyDer::Sec1(_) => "EC PRIVATE KEY",
+        _ => "PRIVATE KEY",
+    };
+    Yaml::String(super::pem_encode(label, key.secret_der()))
+}
+
+impl YamlDump for ServerName<'_> {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.to_str().to_string())
+    }
+}
+
+impl YamlDump for RustlsCertificatePair {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set_yaml("certificate", certificates_to_yaml(self.certs_ref()))
+            .set_secret("private_key", &private_key_to_yaml(self.key_ref()));
+        map.build()
+    }
+}
+
+impl YamlDump for RustlsClientConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("no_session_cache", &self.no_session_cache())
+            .set("disable_sni", &self.disable_sni())
+            .set("max_fragment_size", &self.max_fragment_size())
+            .set("cert_pair", &self.cert_pair())
+            .set_yaml(
+                "ca_certificate",
+                certificates_to_yaml(self.ca_certificates()),
+            )
+            .set(
+                "no_default_ca_certificate",
+                &self.no_default_ca_certificates(),
+            )
+            .set(
+                "use_builtin_ca_certificate",
+                &self.use_builtin_ca_certificates(),
+            )
+            .set("handshake_timeout", &self.negotiation_timeout());
+        map.build()
+    }
+}
+
+impl YamlDump for RustlsServerConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("cert_pairs", self.cert_pairs())
+            .set("enable_client_auth", &self.client_auth())
+            .set("use_session_ticket", &self.use_session_ticket())
+            .set("no_session_cache", &self.disable_session_cache());
+        if let Some(certs) = self.client_auth_certificates() {
+            map.set_yaml("ca_certificate", certificates_to_yaml(certs));
+        }
+        map.set("handshake_timeout", &self.accept_timeout());
+        map.build()
+    }
+}
+
+impl YamlDump for DnsEncryptionProtocol {
+    fn dump_yaml(&self, _redact: bool) -> Yaml {
+        Yaml::String(self.as_str().to_string())
+    }
+}
+
+impl YamlDump for DnsEncryptionConfigBuilder {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("tls_name", self.tls_name())
+            .set("protocol", &self.protocol())
+            .set("tls_client", self.tls_client_config());
+        map.build()
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use yaml_rust::Yaml;
+
+use g3_types::limit::{
+    GlobalDatagramSpeedLimitConfig, GlobalStreamSpeedLimitConfig, RateLimitQuotaConfig,
+};
+use g3_types::net::{TcpSockSpeedLimitConfig, UdpSockSpeedLimitConfig};
+
+use super::{YamlDump, YamlMapDumper};
+
+impl YamlDump for TcpSockSpeedLimitConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("shift_millis", &self.shift_millis)
+            .set("upload", &self.max_north)
+            .set("download", &self.max_south);
+        map.build()
+    }
+}
+
+impl YamlDump for UdpSockSpeedLimitConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("shift_millis", &self.shift_millis)
+            .set("upload_packets", &self.max_north_packets)
+            .set("download_packets", &self.max_south_packets)
+            .set("upload_bytes", &self.max_north_bytes)
+            .set("download_bytes", &self.max_south_bytes);
+        map.build()
+    }
+}
+
+impl YamlDump for GlobalStreamSpeedLimitConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("replenish_interval", &self.replenish_interval())
+            .set("replenish_bytes", &self.replenish_bytes())
+            .set("max_burst_bytes", &self.max_burst_bytes());
+        map.build()
+    }
+}
+
+impl YamlDump for GlobalDatagramSpeedLimitConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let mut map = YamlMapDumper::new(redact);
+        map.set("replenish_interval", &self.replenish_interval())
+            .set("replenish_bytes", &self.replenish_bytes())
+            .set("replenish_packets", &self.replenish_packets())
+            .set("max_burst_bytes", &self.max_burst_bytes())
+            .set("max_burst_packets", &self.max_burst_packets());
+        map.build()
+    }
+}
+
+impl YamlDump for RateLimitQuotaConfig {
+    fn dump_yaml(&self, redact: bool) -> Yaml {
+        let quota = self.get_inner();
+        let mut map = YamlMapDumper::new(redact);
+        map.set("replenish_interval", &quota.replenish_interval())
+            .set("max_burst", &quota.burst_size());
+        map.build()
+    }
+}
@@ -11,6 +11,7 @@ mod hash;
 mod hybrid;
 mod util;
 
+pub mod dump;
 pub mod humanize;
 pub mod key;
 pub mod value;
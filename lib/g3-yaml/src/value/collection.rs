@@ -47,6 +47,12 @@ mod tests {
             SelectivePickPolicy::RoundRobin
         );
 
+        let value = yaml_str!("wrr");
+        assert_eq!(
+            as_selective_pick_policy(&value).unwrap(),
+            SelectivePickPolicy::WeightedRoundRobin
+        );
+
         let value = yaml_str!("ketama");
         assert_eq!(
             as_selective_pick_policy(&value).unwrap(),
@@ -20,3 +20,6 @@ pub use smtp::as_smtp_interception_config;
 
 mod imap;
 pub use imap::as_imap_interception_config;
+
+mod websocket;
+pub use websocket::as_websocket_interception_config;
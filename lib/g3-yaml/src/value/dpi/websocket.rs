@@ -0,0 +1,95 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_dpi::WebSocketInterceptionConfig;
+
+pub fn as_websocket_interception_config(
+    value: &Yaml,
+) -> anyhow::Result<WebSocketInterceptionConfig> {
+    if let Yaml::Hash(map) = value {
+        let mut config = WebSocketInterceptionConfig::default();
+
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "inspect_message" => {
+                config.inspect_message = crate::value::as_bool(v)?;
+                Ok(())
+            }
+            "message_buffer_max_size" => {
+                config.message_buffer_max_size = crate::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "icap_adaptation_timeout" => {
+                config.icap_adaptation_timeout = crate::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        Ok(config)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'websocket interception config' should be 'map'"
+        ))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "dpi")]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn as_websocket_interception_config_ok() {
+        let yaml = yaml_doc!(
+            r"
+                inspect_message: true
+                message_buffer_max_size: 64KiB
+                icap_adaptation_timeout: 5s
+            "
+        );
+        let config = as_websocket_interception_config(&yaml).unwrap();
+        assert!(config.inspect_message);
+        assert_eq!(config.message_buffer_max_size, 64 * 1024);
+        assert_eq!(config.icap_adaptation_timeout, Duration::from_secs(5));
+
+        let yaml = Yaml::Hash(Default::default());
+        let config = as_websocket_interception_config(&yaml).unwrap();
+        assert_eq!(config, WebSocketInterceptionConfig::default());
+    }
+
+    #[test]
+    fn as_websocket_interception_config_err() {
+        let yaml = yaml_doc!(
+            r"
+                inspect_message: maybe
+            "
+        );
+        assert!(as_websocket_interception_config(&yaml).is_err());
+
+        let yaml = yaml_doc!(
+            r"
+                message_buffer_max_size: -1
+            "
+        );
+        assert!(as_websocket_interception_config(&yaml).is_err());
+
+        let yaml = yaml_doc!(
+            r"
+                invalid_key: value
+            "
+        );
+        assert!(as_websocket_interception_config(&yaml).is_err());
+
+        let yaml = yaml_str!("invalid");
+        assert!(as_websocket_interception_config(&yaml).is_err());
+    }
+}
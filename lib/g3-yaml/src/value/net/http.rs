@@ -11,7 +11,8 @@ use http::{HeaderName, HeaderValue};
 use yaml_rust::Yaml;
 
 use g3_types::net::{
-    HttpForwardCapability, HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId,
+    Http2ConnectPoolConfig, HttpForwardCapability, HttpForwardedHeaderType, HttpKeepAliveConfig,
+    HttpServerId,
 };
 
 pub fn as_http_keepalive_config(v: &Yaml) -> anyhow::Result<HttpKeepAliveConfig> {
@@ -106,6 +107,44 @@ pub fn as_http_forward_capability(value: &Yaml) -> anyhow::Result<HttpForwardCap
     Ok(cap)
 }
 
+pub fn as_http2_connect_pool_config(value: &Yaml) -> anyhow::Result<Http2ConnectPoolConfig> {
+    let Yaml::Hash(map) = value else {
+        return Err(anyhow!(
+            "yaml value type for 'Http2ConnectPoolConfig' should be 'map'"
+        ));
+    };
+
+    let mut config = Http2ConnectPoolConfig::default();
+    crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+        "max_connections" | "max_connections_per_peer" => {
+            let count = crate::value::as_nonzero_usize(v)
+                .context(format!("invalid nonzero usize value for key {k}"))?;
+            config.set_max_connections(count.get());
+            Ok(())
+        }
+        "max_streams" | "max_streams_per_connection" => {
+            let count = crate::value::as_nonzero_usize(v)
+                .context(format!("invalid nonzero usize value for key {k}"))?;
+            config.set_max_streams(count.get());
+            Ok(())
+        }
+        "idle_timeout" => {
+            let timeout = crate::humanize::as_duration(v)
+                .context(format!("invalid humanize duration value for key {k}"))?;
+            config.set_idle_timeout(timeout);
+            Ok(())
+        }
+        "fallback_duration" => {
+            let duration = crate::humanize::as_duration(v)
+                .context(format!("invalid humanize duration value for key {k}"))?;
+            config.set_fallback_duration(duration);
+            Ok(())
+        }
+        _ => Err(anyhow!("invalid key {k}")),
+    })?;
+    Ok(config)
+}
+
 pub fn as_http_server_id(value: &Yaml) -> anyhow::Result<HttpServerId> {
     if let Yaml::String(s) = value {
         let id = HttpServerId::from_str(s)?;
@@ -352,6 +391,53 @@ mod tests {
         assert!(as_http_forward_capability(&yaml).is_err());
     }
 
+    #[test]
+    fn as_http2_connect_pool_config_ok() {
+        let yaml = yaml_doc!(
+            r#"
+                max_connections: 2
+                max_streams: 64
+                idle_timeout: 30s
+                fallback_duration: 10m
+            "#
+        );
+        let config = as_http2_connect_pool_config(&yaml).unwrap();
+        assert_eq!(config.max_connections(), 2);
+        assert_eq!(config.max_streams(), 64);
+        assert_eq!(config.idle_timeout(), Duration::from_secs(30));
+        assert_eq!(config.fallback_duration(), Duration::from_secs(600));
+
+        let yaml = yaml_doc!(
+            r#"
+                max_streams_per_connection: 10
+            "#
+        );
+        let config = as_http2_connect_pool_config(&yaml).unwrap();
+        assert_eq!(config.max_connections(), 4);
+        assert_eq!(config.max_streams(), 10);
+        assert_eq!(config.idle_timeout(), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn as_http2_connect_pool_config_err() {
+        let yaml = yaml_doc!(
+            r#"
+                max_connections: 0
+            "#
+        );
+        assert!(as_http2_connect_pool_config(&yaml).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+                invalid_key: 1
+            "#
+        );
+        assert!(as_http2_connect_pool_config(&yaml).is_err());
+
+        let yaml = Yaml::Boolean(true);
+        assert!(as_http2_connect_pool_config(&yaml).is_err());
+    }
+
     #[test]
     fn as_http_server_id_ok() {
         // Valid config with string value
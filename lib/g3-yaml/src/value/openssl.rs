@@ -9,13 +9,13 @@ use std::str::FromStr;
 
 use anyhow::{Context, anyhow};
 use openssl::pkey::{PKey, Private};
-use openssl::x509::X509;
+use openssl::x509::{X509, X509Crl};
 use yaml_rust::Yaml;
 
 use g3_types::net::{
     OpensslCertificatePair, OpensslClientConfigBuilder, OpensslInterceptionClientConfigBuilder,
-    OpensslInterceptionServerConfigBuilder, OpensslProtocol, OpensslServerConfigBuilder,
-    OpensslTlcpCertificatePair,
+    OpensslInterceptionServerConfigBuilder, OpensslProtocol, OpensslRevocationConfig,
+    OpensslRevocationMode, OpensslServerConfigBuilder, OpensslTlcpCertificatePair,
 };
 
 fn as_certificates_from_single_element(
@@ -92,6 +92,137 @@ pub fn as_openssl_private_key(
         .map_err(|e| anyhow!("invalid private key file({}): {e}", path.display()))
 }
 
+fn as_crl_list_from_single_element(
+    value: &Yaml,
+    lookup_dir: Option<&Path>,
+) -> anyhow::Result<Vec<X509Crl>> {
+    const MAX_FILE_SIZE: usize = 64_000_000; // 64MB
+
+    let (file, path) = crate::value::as_file(value, lookup_dir).context("invalid file")?;
+    let mut contents = Vec::with_capacity(4096);
+    file.take(MAX_FILE_SIZE as u64)
+        .read_to_end(&mut contents)
+        .map_err(|e| anyhow!("failed to read contents of file {}: {e}", path.display()))?;
+
+    const PEM_END_LINE: &str = "-----END X509 CRL-----";
+    let Ok(s) = std::str::from_utf8(&contents) else {
+        let crl = X509Crl::from_der(&contents)
+            .map_err(|e| anyhow!("invalid der crl file({}): {e}", path.display()))?;
+        return Ok(vec![crl]);
+    };
+    let mut crl_list = Vec::new();
+    for block in s.split_inclusive(PEM_END_LINE) {
+        if !block.contains(PEM_END_LINE) {
+            break;
+        }
+        let crl = X509Crl::from_pem(block.as_bytes())
+            .map_err(|e| anyhow!("invalid pem crl file({}): {e}", path.display()))?;
+        crl_list.push(crl);
+    }
+    if crl_list.is_empty() {
+        Err(anyhow!("no valid crl found in file {}", path.display()))
+    } else {
+        Ok(crl_list)
+    }
+}
+
+pub fn as_openssl_crl_list(
+    value: &Yaml,
+    lookup_dir: Option<&Path>,
+) -> anyhow::Result<Vec<X509Crl>> {
+    if let Yaml::Array(seq) = value {
+        let mut crl_list = Vec::new();
+        for (i, v) in seq.iter().enumerate() {
+            let this_list = as_crl_list_from_single_element(v, lookup_dir)
+                .context(format!("invalid crl value for element #{i}"))?;
+            crl_list.extend(this_list);
+        }
+        Ok(crl_list)
+    } else {
+        as_crl_list_from_single_element(value, lookup_dir)
+    }
+}
+
+fn as_openssl_revocation_mode(value: &Yaml) -> anyhow::Result<OpensslRevocationMode> {
+    if let Yaml::String(s) = value {
+        OpensslRevocationMode::from_str(s).map_err(|_| anyhow!("invalid revocation check mode {s}"))
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'openssl revocation mode' should be 'string'"
+        ))
+    }
+}
+
+pub fn as_openssl_revocation_config(
+    value: &Yaml,
+    lookup_dir: Option<&Path>,
+) -> anyhow::Result<OpensslRevocationConfig> {
+    let mut config = OpensslRevocationConfig::default();
+
+    match value {
+        Yaml::Hash(map) => {
+            crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "mode" | "policy" => {
+                    let mode = as_openssl_revocation_mode(v)
+                        .context(format!("invalid revocation mode value for key {k}"))?;
+                    config.set_mode(mode);
+                    Ok(())
+                }
+                "crl" | "crl_file" | "crl_files" => {
+                    let crl_list = as_openssl_crl_list(v, lookup_dir)
+                        .context(format!("invalid crl value for key {k}"))?;
+                    config
+                        .set_crl_list(crl_list)
+                        .context("failed to set crl list")?;
+                    Ok(())
+                }
+                "fetch_ocsp" => {
+                    let enable = crate::value::as_bool(v)
+                        .context(format!("invalid bool value for key {k}"))?;
+                    config.set_fetch_ocsp(enable);
+                    Ok(())
+                }
+                "fetch_crl" => {
+                    let enable = crate::value::as_bool(v)
+                        .context(format!("invalid bool value for key {k}"))?;
+                    config.set_fetch_crl(enable);
+                    Ok(())
+                }
+                "fetch_timeout" => {
+                    let timeout = crate::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    config.set_fetch_timeout(timeout);
+                    Ok(())
+                }
+                "cache_ttl" => {
+                    let ttl = crate::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    config.set_cache_ttl(ttl);
+                    Ok(())
+                }
+                "cache_capacity" | "cache_cap" => {
+                    let cap = crate::value::as_usize(v)
+                        .context(format!("invalid usize value for key {k}"))?;
+                    config.set_cache_capacity(cap);
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+        }
+        Yaml::String(_) => {
+            let mode = as_openssl_revocation_mode(value)?;
+            config.set_mode(mode);
+        }
+        _ => {
+            return Err(anyhow!(
+                "yaml value type for 'openssl revocation config' should be 'map' or 'string'"
+            ));
+        }
+    }
+
+    Ok(config)
+}
+
 pub fn as_openssl_certificate_pair(
     value: &Yaml,
     lookup_dir: Option<&Path>,
@@ -338,6 +469,12 @@ fn set_openssl_tls_client_config_builder(
                 builder.set_use_ocsp_stapling(enable);
                 Ok(())
             }
+            "cert_revocation" | "revocation_check" => {
+                let config = as_openssl_revocation_config(v, lookup_dir)
+                    .context(format!("invalid revocation config value for key {k}"))?;
+                builder.set_revocation_config(config);
+                Ok(())
+            }
             "enable_sct" => {
                 let enable = crate::value::as_bool(v)?;
                 builder.set_enable_sct(enable);
@@ -462,6 +599,12 @@ pub fn as_tls_interception_client_config_builder(
                 builder.set_use_ocsp_stapling(enable);
                 Ok(())
             }
+            "cert_revocation" | "revocation_check" => {
+                let config = as_openssl_revocation_config(v, lookup_dir)
+                    .context(format!("invalid revocation config value for key {k}"))?;
+                builder.set_revocation_config(config);
+                Ok(())
+            }
             "enable_sct" => {
                 let enable = crate::value::as_bool(v)?;
                 builder.set_enable_sct(enable);
@@ -1324,6 +1467,67 @@ mod tests {
         assert!(as_tls_interception_client_config_builder(&yaml, None).is_err());
     }
 
+    #[test]
+    fn as_openssl_revocation_config_ok() {
+        let yaml = yaml_doc!(
+            r#"
+            mode: hard_fail
+            fetch_ocsp: true
+            fetch_crl: true
+            fetch_timeout: 2s
+            cache_ttl: 10m
+            cache_capacity: 100
+            "#
+        );
+        let config = as_openssl_revocation_config(&yaml, None).unwrap();
+        let mut expected = OpensslRevocationConfig::default();
+        expected.set_mode(OpensslRevocationMode::HardFail);
+        expected.set_fetch_ocsp(true);
+        expected.set_fetch_crl(true);
+        expected.set_fetch_timeout(Duration::from_secs(2));
+        expected.set_cache_ttl(Duration::from_secs(600));
+        expected.set_cache_capacity(100);
+        assert_eq!(config, expected);
+
+        let yaml = yaml_str!("log_only");
+        let config = as_openssl_revocation_config(&yaml, None).unwrap();
+        assert_eq!(config.mode(), OpensslRevocationMode::LogOnly);
+
+        let yaml = yaml_doc!(
+            r#"
+            cert_revocation: soft_fail
+            "#
+        );
+        let builder = as_to_one_openssl_tls_client_config_builder(&yaml, None).unwrap();
+        let mut expected = OpensslClientConfigBuilder::with_cache_for_one_site();
+        expected.set_revocation_config(OpensslRevocationConfig::default());
+        assert_eq!(builder, expected);
+    }
+
+    #[test]
+    fn as_openssl_revocation_config_err() {
+        let yaml = yaml_str!("strict");
+        assert!(as_openssl_revocation_config(&yaml, None).is_err());
+
+        let yaml = Yaml::Integer(1);
+        assert!(as_openssl_revocation_config(&yaml, None).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+            mode: soft_fail
+            crl: /non/existent/file.crl
+            "#
+        );
+        assert!(as_openssl_revocation_config(&yaml, None).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+            unknown_key: "value"
+            "#
+        );
+        assert!(as_openssl_revocation_config(&yaml, None).is_err());
+    }
+
     #[test]
     fn as_openssl_tls_server_config_builder_ok() {
         let temp_dir = TempDir::new("openssl_ok");
@@ -0,0 +1,27 @@
+#!/bin/sh
+
+ACME_STORAGE_DIR=/tmp/g3proxy-acme
+
+wait_acme_cert()
+{
+	for _ in $(seq 1 60)
+	do
+		[ -f "${ACME_STORAGE_DIR}/certs/$1/cert.pem" ] && return 0
+		sleep 1
+	done
+	return 1
+}
+
+check_acme_cert()
+{
+	wait_acme_cert "$1"
+	# wait for the new certificate to be loaded
+	sleep 1
+	echo | openssl s_client -connect "127.0.0.1:$2" -servername "$1" 2>/dev/null \
+		| openssl x509 -noout -issuer | grep -i pebble
+}
+
+check_acme_cert alpn.acme.g3proxy.local 5001
+check_acme_cert http.acme.g3proxy.local 9443
+
+curl -k -s https://http.acme.g3proxy.local:9443/get -H "Host: httpbin.local"
@@ -0,0 +1,81 @@
+#!/bin/sh
+
+
+test_http_proxy_https_connect()
+{
+	date
+
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${HTTP_PROXY} -T https://httpbin.local:9443 --no-auth --ca-cert "${TEST_CA_CERT_FILE}"
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${HTTP_PROXY} -T https://httpbin.local:2443 --no-auth --ca-cert "${TEST_CA_CERT_FILE}"
+}
+
+
+test_http_proxy_https_forward()
+{
+	date
+
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${HTTP_PROXY} -T http://httpbin.local --no-auth --request-target-prefix https://httpbin.local:9443
+}
+
+
+test_https_proxy_https_connect()
+{
+	date
+
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${HTTPS_PROXY} -T https://httpbin.local:9443 --no-auth --proxy-ca-cert "${TEST_CA_CERT_FILE}" --ca-cert "${TEST_CA_CERT_FILE}"
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${HTTPS_PROXY} -T https://httpbin.local:2443 --no-auth --proxy-ca-cert "${TEST_CA_CERT_FILE}" --ca-cert "${TEST_CA_CERT_FILE}"
+}
+
+
+test_socks5_proxy_https()
+{
+	date
+
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${SOCKS5_PROXY} -T https://httpbin.local:9443 --no-auth --ca-cert "${TEST_CA_CERT_FILE}"
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${SOCKS5_PROXY} -T https://httpbin.local:2443 --no-auth --ca-cert "${TEST_CA_CERT_FILE}"
+}
+
+
+# the next hop g3proxy doesn't speak h2, so all tasks should fall back to HTTP/1.1
+
+HTTP_PROXY="http://127.0.0.1:8080"
+test_http_proxy_https_connect
+test_http_proxy_https_forward
+
+
+HTTPS_PROXY="https://g3proxy.local:8443"
+test_https_proxy_https_connect
+
+
+SOCKS5_PROXY="socks5h://127.0.0.1:1080"
+test_socks5_proxy_https
+
+
+# nghttpx speaks h2 (with ALPN on 7444 and prior knowledge on 7082), and forwards the tunnels to g3proxy on 7080
+
+NGHTTPX_ACCESS_LOG=/tmp/nghttpx-access.log
+rm -f "${NGHTTPX_ACCESS_LOG}"
+nghttpx --http2-proxy --daemon --pid-file=/tmp/nghttpx.pid \
+	--frontend="127.0.0.1,7444" --frontend="127.0.0.1,7082;no-tls" --backend="127.0.0.1,7080" \
+	--accesslog-file="${NGHTTPX_ACCESS_LOG}" \
+	"${RUN_DIR}/g3proxy.local-key.pem" "${RUN_DIR}/g3proxy.local.pem"
+sleep 1
+
+HTTP_PROXY="http://127.0.0.1:8081"
+test_http_proxy_https_connect
+
+
+SOCKS5_PROXY="socks5h://127.0.0.1:1081"
+test_socks5_proxy_https
+
+sleep 1
+kill -INT $(cat /tmp/nghttpx.pid)
+
+# all tunnels should have been carried by h2 streams
+grep -q "CONNECT httpbin.local:9443 HTTP/2" "${NGHTTPX_ACCESS_LOG}"
+grep -q "CONNECT httpbin.local:2443 HTTP/2" "${NGHTTPX_ACCESS_LOG}"
+if grep -qv "HTTP/2" "${NGHTTPX_ACCESS_LOG}"
+then
+	echo "some tunnels are not carried by h2 streams"
+	exit 1
+fi
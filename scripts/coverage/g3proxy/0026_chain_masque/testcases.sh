@@ -0,0 +1,74 @@
+#!/bin/sh
+
+
+test_http_proxy_https_connect()
+{
+	date
+
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${HTTP_PROXY} -T https://httpbin.local:9443 --no-auth --ca-cert "${TEST_CA_CERT_FILE}"
+}
+
+
+test_socks5_proxy_https()
+{
+	date
+
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+curl/test_httpbin.py" -x ${SOCKS5_PROXY} -T https://httpbin.local:9443 --no-auth --ca-cert "${TEST_CA_CERT_FILE}"
+}
+
+
+test_socks5_proxy_dns()
+{
+	date
+
+	python3 "${PROJECT_DIR}/scripts/test/socks5_dns_query.py" -x ${SOCKS5_PROXY} --dns-server 127.0.0.1 g3proxy.local httpbin.local -v
+}
+
+
+# h2o speaks HTTP/3 on 7445, and proxies both CONNECT and CONNECT-UDP requests
+
+H2O_CONF=/tmp/h2o-masque.conf
+H2O_ACCESS_LOG=/tmp/h2o-access.log
+rm -f "${H2O_ACCESS_LOG}"
+cat > "${H2O_CONF}" <<H2O
+pid-file: /tmp/h2o.pid
+error-log: /tmp/h2o-error.log
+access-log: ${H2O_ACCESS_LOG}
+listen: &ssl_listen
+  host: 127.0.0.1
+  port: 7445
+  ssl:
+    certificate-file: ${RUN_DIR}/g3proxy.local.pem
+    key-file: ${RUN_DIR}/g3proxy.local-key.pem
+listen:
+  <<: *ssl_listen
+  type: quic
+hosts:
+  default:
+    paths:
+      /:
+        proxy.connect:
+          - "+*"
+        proxy.timeout.io: 30000
+H2O
+h2o -m daemon -c "${H2O_CONF}"
+sleep 1
+
+HTTP_PROXY="http://127.0.0.1:8080"
+test_http_proxy_https_connect
+
+
+SOCKS5_PROXY="socks5h://127.0.0.1:1080"
+test_socks5_proxy_https
+test_socks5_proxy_dns
+
+
+SOCKS5_PROXY="socks5h://127.0.0.1:1081"
+test_socks5_proxy_dns
+
+sleep 1
+kill -TERM $(cat /tmp/h2o.pid)
+
+# the tcp tunnels and udp packets should have been carried by HTTP/3 CONNECT and CONNECT-UDP requests
+grep -q "\"CONNECT httpbin.local:9443 HTTP/3\" 200" "${H2O_ACCESS_LOG}"
+grep -q "/.well-known/masque/udp/127.0.0.1/53/ HTTP/3\" 200" "${H2O_ACCESS_LOG}"
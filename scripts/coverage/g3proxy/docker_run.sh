@@ -6,8 +6,8 @@ FTP_TMP_ROOT=/tmp/vsftpd
 FTP_USERNAME=ftpuser
 FTP_PASSWORD=ftppass
 
-docker stop ftp httpbin || :
-docker rm ftp httpbin || :
+docker stop ftp httpbin pebble || :
+docker rm ftp httpbin pebble || :
 
 docker run -p 127.0.0.1:80:80 --name httpbin -d kennethreitz/httpbin
 
@@ -22,3 +22,9 @@ docker run -d -v ${FTP_TMP_ROOT}:/home/vsftpd \
                 -e PASV_ADDRESS=127.0.0.1 \
                 --name ftp \
                 -d bogem/ftp
+
+docker run --network host \
+                -e PEBBLE_VA_NOSLEEP=1 \
+                --name pebble \
+                -d ghcr.io/letsencrypt/pebble \
+                -dnsserver 127.0.0.1:53
@@ -5,6 +5,9 @@
 [ -d /tmp/nginx ] || mkdir /tmp/nginx
 /usr/sbin/nginx -c "${PROJECT_DIR}"/scripts/coverage/g3proxy/nginx.conf
 
+# clean the ACME storage dir
+rm -rf /tmp/g3proxy-acme
+
 # start g3fcgen
 "${PROJECT_DIR}"/target/debug/g3fcgen -c "${RUN_DIR}"/g3fcgen.yaml -G port2999 &
 FCGEN_PID=$!